@@ -275,7 +275,11 @@ pub fn CalculatorApp(
         let serialized = match serde_json::to_string(&snapshot) {
             Ok(raw) => raw,
             Err(err) => {
-                logging::warn!("calculator state serialize failed: {err}");
+                if let Some(services) = services_for_persist.as_ref() {
+                    services
+                        .logs
+                        .warn(format!("calculator state serialize failed: {err}"));
+                }
                 return;
             }
         };
@@ -0,0 +1,203 @@
+//! Breadcrumb trail and path-field completion for the Explorer path bar.
+
+use platform_host::{normalize_virtual_path, ExplorerEntry, ExplorerEntryKind};
+use platform_paths::resolve_path;
+
+/// One clickable ancestor of the current folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Crumb {
+    pub(crate) label: String,
+    pub(crate) path: String,
+}
+
+/// Crumbs for a folder, with the middle of long paths folded into an overflow menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BreadcrumbTrail {
+    pub(crate) leading: Vec<Crumb>,
+    pub(crate) collapsed: Vec<Crumb>,
+    pub(crate) trailing: Vec<Crumb>,
+}
+
+/// Splits `cwd` into crumbs, keeping the root and the deepest folders visible.
+///
+/// At most `max_visible` crumbs are shown; any others are moved to `collapsed`, nearest the root
+/// first.
+pub(crate) fn breadcrumb_trail(cwd: &str, max_visible: usize) -> BreadcrumbTrail {
+    let mut crumbs = vec![Crumb {
+        label: "/".to_string(),
+        path: "/".to_string(),
+    }];
+    let mut path = String::new();
+    for segment in normalize_virtual_path(cwd).split('/').skip(1) {
+        if segment.is_empty() {
+            continue;
+        }
+        path = format!("{path}/{segment}");
+        crumbs.push(Crumb {
+            label: segment.to_string(),
+            path: path.clone(),
+        });
+    }
+
+    let max_visible = max_visible.max(2);
+    if crumbs.len() <= max_visible {
+        return BreadcrumbTrail {
+            leading: crumbs,
+            collapsed: Vec::new(),
+            trailing: Vec::new(),
+        };
+    }
+    let trailing = crumbs.split_off(crumbs.len() - (max_visible - 1));
+    let collapsed = crumbs.split_off(1);
+    BreadcrumbTrail {
+        leading: crumbs,
+        collapsed,
+        trailing,
+    }
+}
+
+/// Splits typed path-field text into the folder to list and the partial name being typed.
+///
+/// Text that does not start at the root is taken relative to `cwd`, like a shell path.
+pub(crate) fn completion_base(cwd: &str, input: &str) -> (String, String) {
+    let input = input.trim().replace('\\', "/");
+    match input.rsplit_once('/') {
+        Some(("", partial)) => ("/".to_string(), partial.to_string()),
+        Some((folder, partial)) => (resolve_path(cwd, folder), partial.to_string()),
+        None => (normalize_virtual_path(cwd), input),
+    }
+}
+
+/// Folders in `entries` whose name starts with `partial`, ignoring case, sorted by name.
+///
+/// Hidden folders are only offered when `show_hidden` is set or `partial` itself starts with a dot.
+pub(crate) fn folder_completions(
+    entries: &[ExplorerEntry],
+    partial: &str,
+    show_hidden: bool,
+) -> Vec<String> {
+    let partial = partial.to_lowercase();
+    let show_hidden = show_hidden || partial.starts_with('.');
+    let mut names = entries
+        .iter()
+        .filter(|entry| entry.kind == ExplorerEntryKind::Directory)
+        .filter(|entry| show_hidden || !entry.name.starts_with('.'))
+        .filter(|entry| entry.name.to_lowercase().starts_with(&partial))
+        .map(|entry| entry.name.clone())
+        .collect::<Vec<_>>();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Text to put in the path field when completing `partial` inside `folder`.
+///
+/// A single match completes to that folder with a trailing slash. Several matches extend the text
+/// to their longest shared prefix, and `None` means there is nothing to add.
+pub(crate) fn complete_input(folder: &str, partial: &str, matches: &[String]) -> Option<String> {
+    let prefix = |name: &str| match folder {
+        "/" => format!("/{name}"),
+        folder => format!("{folder}/{name}"),
+    };
+    match matches {
+        [] => None,
+        [only] => Some(format!("{}/", prefix(only))),
+        [first, rest @ ..] => {
+            let shared = first
+                .chars()
+                .enumerate()
+                .take_while(|(idx, ch)| {
+                    rest.iter().all(|name| {
+                        name.chars()
+                            .nth(*idx)
+                            .is_some_and(|other| other.to_lowercase().eq(ch.to_lowercase()))
+                    })
+                })
+                .map(|(_, ch)| ch)
+                .collect::<String>();
+            (shared.chars().count() > partial.chars().count()).then(|| prefix(&shared))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crumb_paths(crumbs: &[Crumb]) -> Vec<&str> {
+        crumbs.iter().map(|crumb| crumb.path.as_str()).collect()
+    }
+
+    fn folder(name: &str) -> ExplorerEntry {
+        ExplorerEntry {
+            name: name.to_string(),
+            path: format!("/work/{name}"),
+            kind: ExplorerEntryKind::Directory,
+            size: None,
+            modified_at_unix_ms: None,
+        }
+    }
+
+    #[test]
+    fn long_paths_fold_their_middle_crumbs() {
+        let short = breadcrumb_trail("/docs/notes", 4);
+        assert_eq!(crumb_paths(&short.leading), ["/", "/docs", "/docs/notes"]);
+        assert!(short.collapsed.is_empty() && short.trailing.is_empty());
+
+        let long = breadcrumb_trail("/a/b/c/d/e", 4);
+        assert_eq!(crumb_paths(&long.leading), ["/"]);
+        assert_eq!(crumb_paths(&long.collapsed), ["/a", "/a/b"]);
+        assert_eq!(
+            crumb_paths(&long.trailing),
+            ["/a/b/c", "/a/b/c/d", "/a/b/c/d/e"]
+        );
+        assert_eq!(long.trailing[2].label, "e");
+    }
+
+    #[test]
+    fn completion_lists_matching_folders_and_extends_shared_prefixes() {
+        assert_eq!(
+            completion_base("/", "/work/pro"),
+            ("/work".into(), "pro".into())
+        );
+        assert_eq!(
+            completion_base("/", "/work/"),
+            ("/work".into(), String::new())
+        );
+        assert_eq!(completion_base("/", "docs"), ("/".into(), "docs".into()));
+        assert_eq!(
+            completion_base("/work", "docs"),
+            ("/work".into(), "docs".into())
+        );
+        assert_eq!(
+            completion_base("/work/docs", "../mu"),
+            ("/work".into(), "mu".into())
+        );
+        assert_eq!(completion_base("/work", "/mu"), ("/".into(), "mu".into()));
+
+        let mut entries = vec![
+            folder("Projects"),
+            folder("project-notes"),
+            folder(".private"),
+            folder("music"),
+        ];
+        entries.push(ExplorerEntry {
+            kind: ExplorerEntryKind::File,
+            ..folder("programs.txt")
+        });
+        let matches = folder_completions(&entries, "pro", false);
+        assert_eq!(matches, ["project-notes", "Projects"]);
+        assert_eq!(
+            complete_input("/work", "pro", &matches),
+            Some("/work/project".to_string())
+        );
+        assert_eq!(complete_input("/work", "project", &matches), None);
+        assert_eq!(
+            complete_input("/", "mu", &folder_completions(&entries, "mu", false)),
+            Some("/music/".to_string())
+        );
+        assert!(folder_completions(&entries, "", false)
+            .iter()
+            .all(|name| name != ".private"));
+        assert_eq!(folder_completions(&entries, ".p", false), [".private"]);
+    }
+}
@@ -0,0 +1,269 @@
+//! Batch renaming of the Explorer selection.
+//!
+//! Each new name is built in three steps: an optional numbering template replaces the name stem,
+//! find and replace runs over the result, and a case transform applies last. File extensions
+//! survive the template so `photo-{n}` keeps `.jpg` files as JPEGs.
+
+/// Case transform applied to every new name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum NameCase {
+    #[default]
+    Keep,
+    Lower,
+    Upper,
+    /// Capitalizes the first letter of each word, splitting on spaces, `-`, and `_`,
+    /// and lowercases the rest.
+    Title,
+}
+
+impl NameCase {
+    pub(crate) const ALL: [Self; 4] = [Self::Keep, Self::Lower, Self::Upper, Self::Title];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Keep => "Keep case",
+            Self::Lower => "lowercase",
+            Self::Upper => "UPPERCASE",
+            Self::Title => "Title Case",
+        }
+    }
+
+    fn apply(self, name: &str) -> String {
+        match self {
+            Self::Keep => name.to_string(),
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            Self::Title => {
+                let mut titled = String::with_capacity(name.len());
+                let mut word_start = true;
+                for ch in name.chars() {
+                    if word_start {
+                        titled.extend(ch.to_uppercase());
+                    } else {
+                        titled.extend(ch.to_lowercase());
+                    }
+                    word_start = matches!(ch, ' ' | '-' | '_');
+                }
+                titled
+            }
+        }
+    }
+}
+
+/// Settings of the bulk rename dialog.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BulkRenameRule {
+    /// Text to replace in every name; empty skips find and replace.
+    pub(crate) find: String,
+    pub(crate) replace: String,
+    /// New name stem; empty keeps each stem. `{n}` is the entry's position counting from 1,
+    /// zero-padded to the width of the largest number, and `{name}` is the current stem.
+    pub(crate) template: String,
+    pub(crate) case: NameCase,
+}
+
+/// Entry to rename, in the order numbering follows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BulkRenameSource {
+    pub(crate) name: String,
+    pub(crate) is_file: bool,
+}
+
+/// Proposed new name for one entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BulkRenamePreview {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    /// Why the entry cannot take the new name.
+    pub(crate) problem: Option<String>,
+}
+
+impl BulkRenamePreview {
+    pub(crate) fn changes(&self) -> bool {
+        self.from != self.to
+    }
+}
+
+/// Splits a file name into stem and extension (with its dot); folders and dotfiles keep no
+/// extension.
+fn split_extension(name: &str, is_file: bool) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if is_file && dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    }
+}
+
+/// New names for `sources` under `rule`.
+///
+/// `others` are the names of entries in the same folder that are not being renamed. A new name is
+/// a problem when it is invalid, taken by another entry, or produced for more than one entry.
+pub(crate) fn preview_bulk_rename(
+    rule: &BulkRenameRule,
+    sources: &[BulkRenameSource],
+    others: &[String],
+) -> Vec<BulkRenamePreview> {
+    let width = sources.len().to_string().len();
+    let names = sources
+        .iter()
+        .enumerate()
+        .map(|(index, source)| {
+            let mut name = source.name.clone();
+            if !rule.template.is_empty() {
+                let (stem, extension) = split_extension(&source.name, source.is_file);
+                let number = format!("{:0width$}", index + 1);
+                name = rule
+                    .template
+                    .replace("{n}", &number)
+                    .replace("{name}", stem)
+                    + extension;
+            }
+            if !rule.find.is_empty() {
+                name = name.replace(&rule.find, &rule.replace);
+            }
+            rule.case.apply(name.trim())
+        })
+        .collect::<Vec<_>>();
+
+    sources
+        .iter()
+        .zip(&names)
+        .map(|(source, to)| {
+            let taken_by_source = sources
+                .iter()
+                .any(|other| other.name == *to && other.name != source.name);
+            let problem = if to.is_empty() {
+                Some("name is empty".to_string())
+            } else if to == "." || to == ".." || to.contains(['/', '\\']) {
+                Some(format!("`{to}` is not a valid name"))
+            } else if names.iter().filter(|name| *name == to).count() > 1 {
+                Some("name is produced more than once".to_string())
+            } else if taken_by_source || others.iter().any(|other| other == to) {
+                Some("name is already taken".to_string())
+            } else {
+                None
+            };
+            BulkRenamePreview {
+                from: source.name.clone(),
+                to: to.clone(),
+                problem,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(names: &[&str]) -> Vec<BulkRenameSource> {
+        names
+            .iter()
+            .map(|name| BulkRenameSource {
+                name: name.to_string(),
+                is_file: true,
+            })
+            .collect()
+    }
+
+    fn targets(previews: &[BulkRenamePreview]) -> Vec<&str> {
+        previews.iter().map(|preview| preview.to.as_str()).collect()
+    }
+
+    #[test]
+    fn numbering_template_keeps_extensions_and_pads() {
+        let rule = BulkRenameRule {
+            template: "photo-{n}".to_string(),
+            ..BulkRenameRule::default()
+        };
+        let sources = files(&[
+            "a.jpg", "b.jpg", "c.png", "d", "e", "f", "g", "h", "i", "j.tar.gz",
+        ]);
+        let previews = preview_bulk_rename(&rule, &sources, &[]);
+        assert_eq!(previews[0].to, "photo-01.jpg");
+        assert_eq!(previews[2].to, "photo-03.png");
+        assert_eq!(previews[3].to, "photo-04");
+        assert_eq!(previews[9].to, "photo-10.gz");
+        assert!(previews.iter().all(|preview| preview.problem.is_none()));
+
+        let folders = [BulkRenameSource {
+            name: "v1.2".to_string(),
+            is_file: false,
+        }];
+        let rule = BulkRenameRule {
+            template: "{name} old".to_string(),
+            ..BulkRenameRule::default()
+        };
+        assert_eq!(
+            targets(&preview_bulk_rename(&rule, &folders, &[])),
+            ["v1.2 old"]
+        );
+    }
+
+    #[test]
+    fn find_replace_and_case_apply_in_order() {
+        let rule = BulkRenameRule {
+            find: "IMG_".to_string(),
+            replace: "holiday_".to_string(),
+            case: NameCase::Title,
+            ..BulkRenameRule::default()
+        };
+        let previews = preview_bulk_rename(&rule, &files(&["IMG_beach.JPG", "notes.txt"]), &[]);
+        assert_eq!(targets(&previews), ["Holiday_Beach.jpg", "Notes.txt"]);
+
+        let rule = BulkRenameRule {
+            case: NameCase::Lower,
+            ..BulkRenameRule::default()
+        };
+        let previews = preview_bulk_rename(&rule, &files(&["README.MD", "todo.txt"]), &[]);
+        assert_eq!(targets(&previews), ["readme.md", "todo.txt"]);
+        assert!(previews[0].changes());
+        assert!(!previews[1].changes());
+        assert_eq!(NameCase::Upper.apply("a-b"), "A-B");
+    }
+
+    #[test]
+    fn clashing_and_invalid_names_are_reported() {
+        let rule = BulkRenameRule {
+            template: "same".to_string(),
+            ..BulkRenameRule::default()
+        };
+        let previews = preview_bulk_rename(&rule, &files(&["a.txt", "b.txt"]), &[]);
+        assert!(previews.iter().all(|preview| preview.problem.is_some()));
+
+        let rule = BulkRenameRule {
+            find: "a".to_string(),
+            replace: "b".to_string(),
+            ..BulkRenameRule::default()
+        };
+        let previews = preview_bulk_rename(&rule, &files(&["a", "c"]), &["b".to_string()]);
+        assert_eq!(
+            previews[0].problem.as_deref(),
+            Some("name is already taken")
+        );
+        assert_eq!(previews[1].problem, None);
+
+        let previews = preview_bulk_rename(&rule, &files(&["a", "b"]), &[]);
+        assert_eq!(
+            previews[0].problem.as_deref(),
+            Some("name is produced more than once")
+        );
+
+        let rule = BulkRenameRule {
+            find: "x".to_string(),
+            replace: "/".to_string(),
+            ..BulkRenameRule::default()
+        };
+        let previews = preview_bulk_rename(&rule, &files(&["x"]), &[]);
+        assert_eq!(
+            previews[0].problem.as_deref(),
+            Some("`/` is not a valid name")
+        );
+
+        let rule = BulkRenameRule {
+            find: "x".to_string(),
+            ..BulkRenameRule::default()
+        };
+        let previews = preview_bulk_rename(&rule, &files(&["x"]), &[]);
+        assert_eq!(previews[0].problem.as_deref(), Some("name is empty"));
+    }
+}
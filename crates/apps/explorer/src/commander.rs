@@ -0,0 +1,103 @@
+//! Keyboard model for the dual-pane (commander) layout.
+//!
+//! The second pane lists its own folder beside the contents pane. Tab moves focus between the two,
+//! and F5 and F6 copy or move the active pane's selection into the other pane's folder.
+
+/// Listing that last held keyboard focus in dual-pane mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum ActivePane {
+    #[default]
+    Primary,
+    Secondary,
+}
+
+impl ActivePane {
+    pub(crate) fn other(self) -> Self {
+        match self {
+            Self::Primary => Self::Secondary,
+            Self::Secondary => Self::Primary,
+        }
+    }
+}
+
+/// Dual-pane command bound to a listing key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PaneCommand {
+    /// Focus the other pane.
+    Switch,
+    /// Copy the selection into the other pane's folder.
+    Copy,
+    /// Move the selection into the other pane's folder.
+    Move,
+}
+
+/// Command for `key` in dual-pane mode; Shift+Tab switches panes like Tab does.
+pub(crate) fn pane_command(key: &str) -> Option<PaneCommand> {
+    match key {
+        "Tab" => Some(PaneCommand::Switch),
+        "F5" => Some(PaneCommand::Copy),
+        "F6" => Some(PaneCommand::Move),
+        _ => None,
+    }
+}
+
+/// Entry of `order` that an arrow, Home, or End key moves the focus to.
+///
+/// Without a focus still present in `order`, Down and Home start at the first entry and Up and End
+/// at the last.
+pub(crate) fn step_focus(order: &[String], focus: Option<&str>, key: &str) -> Option<String> {
+    let last = order.len().checked_sub(1)?;
+    let index = focus.and_then(|focus| order.iter().position(|path| path == focus));
+    let next = match (key, index) {
+        ("ArrowDown", Some(index)) => (index + 1).min(last),
+        ("ArrowDown" | "Home", _) => 0,
+        ("ArrowUp", Some(index)) => index.saturating_sub(1),
+        ("ArrowUp" | "End", _) => last,
+        _ => return None,
+    };
+    Some(order[next].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pane_keys_switch_copy_and_move() {
+        assert_eq!(pane_command("Tab"), Some(PaneCommand::Switch));
+        assert_eq!(pane_command("F5"), Some(PaneCommand::Copy));
+        assert_eq!(pane_command("F6"), Some(PaneCommand::Move));
+        assert_eq!(pane_command("F2"), None);
+        assert_eq!(ActivePane::Primary.other(), ActivePane::Secondary);
+        assert_eq!(ActivePane::Secondary.other(), ActivePane::Primary);
+    }
+
+    #[test]
+    fn focus_steps_clamp_to_the_listing() {
+        let order = ["/a", "/b", "/c"].map(str::to_string);
+        assert_eq!(
+            step_focus(&order, Some("/b"), "ArrowDown").as_deref(),
+            Some("/c")
+        );
+        assert_eq!(
+            step_focus(&order, Some("/c"), "ArrowDown").as_deref(),
+            Some("/c")
+        );
+        assert_eq!(
+            step_focus(&order, Some("/a"), "ArrowUp").as_deref(),
+            Some("/a")
+        );
+        assert_eq!(step_focus(&order, None, "ArrowDown").as_deref(), Some("/a"));
+        assert_eq!(
+            step_focus(&order, Some("/gone"), "ArrowUp").as_deref(),
+            Some("/c")
+        );
+        assert_eq!(
+            step_focus(&order, Some("/b"), "Home").as_deref(),
+            Some("/a")
+        );
+        assert_eq!(step_focus(&order, Some("/b"), "End").as_deref(), Some("/c"));
+        assert_eq!(step_focus(&order, Some("/b"), "PageDown"), None);
+        assert_eq!(step_focus(&[], None, "ArrowDown"), None);
+    }
+}
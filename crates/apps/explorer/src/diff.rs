@@ -0,0 +1,235 @@
+//! Line diffs for comparing two texts side by side or in one column.
+//!
+//! Lines are matched along a longest common subsequence after trimming the shared start and end,
+//! so the usual case of a few edits in a long file stays cheap.
+
+/// Largest line comparison table built before a differing region is treated as one change.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// How a line differs between the two texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiffKind {
+    Same,
+    Removed,
+    Added,
+}
+
+/// One line of a unified diff, with its 1-based line numbers in the texts that contain it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiffLine {
+    pub(crate) kind: DiffKind,
+    pub(crate) old_line: Option<usize>,
+    pub(crate) new_line: Option<usize>,
+    pub(crate) text: String,
+}
+
+/// One row of a side-by-side diff. Removed and added lines of the same change share rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiffRow {
+    pub(crate) old: Option<(usize, String)>,
+    pub(crate) new: Option<(usize, String)>,
+}
+
+impl DiffRow {
+    pub(crate) fn changed(&self) -> bool {
+        match (&self.old, &self.new) {
+            (Some((_, old)), Some((_, new))) => old != new,
+            _ => true,
+        }
+    }
+}
+
+/// For each line of `base`, the index of the matching line in `other`, following a longest common
+/// subsequence of lines.
+pub(crate) fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+    let prefix = base
+        .iter()
+        .zip(other)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    for (index, slot) in matches.iter_mut().enumerate().take(prefix) {
+        *slot = Some(index);
+    }
+    for offset in 1..=suffix {
+        matches[base.len() - offset] = Some(other.len() - offset);
+    }
+
+    let base_mid = &base[prefix..base.len() - suffix];
+    let other_mid = &other[prefix..other.len() - suffix];
+    let (rows, cols) = (base_mid.len(), other_mid.len());
+    if rows == 0 || cols == 0 || (rows + 1) * (cols + 1) > MAX_DIFF_CELLS {
+        return matches;
+    }
+    // lengths[i][j] is the longest common subsequence of base_mid[i..] and other_mid[j..].
+    let width = cols + 1;
+    let mut lengths = vec![0u32; (rows + 1) * width];
+    for i in (0..rows).rev() {
+        for j in (0..cols).rev() {
+            lengths[i * width + j] = if base_mid[i] == other_mid[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < rows && j < cols {
+        if base_mid[i] == other_mid[j] {
+            matches[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+/// Unified diff turning `old` into `new`.
+pub(crate) fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let matches = match_lines(&old, &new);
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let mut new_at = 0;
+    let push_added = |lines: &mut Vec<DiffLine>, until: usize, new_at: &mut usize| {
+        while *new_at < until {
+            lines.push(DiffLine {
+                kind: DiffKind::Added,
+                old_line: None,
+                new_line: Some(*new_at + 1),
+                text: new[*new_at].to_string(),
+            });
+            *new_at += 1;
+        }
+    };
+    for (old_at, matched) in matches.into_iter().enumerate() {
+        match matched {
+            Some(matched) => {
+                push_added(&mut lines, matched, &mut new_at);
+                lines.push(DiffLine {
+                    kind: DiffKind::Same,
+                    old_line: Some(old_at + 1),
+                    new_line: Some(matched + 1),
+                    text: old[old_at].to_string(),
+                });
+                new_at = matched + 1;
+            }
+            None => lines.push(DiffLine {
+                kind: DiffKind::Removed,
+                old_line: Some(old_at + 1),
+                new_line: None,
+                text: old[old_at].to_string(),
+            }),
+        }
+    }
+    push_added(&mut lines, new.len(), &mut new_at);
+    lines
+}
+
+/// Lays out a unified diff in two columns.
+pub(crate) fn side_by_side(lines: &[DiffLine]) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    let mut at = 0;
+    while at < lines.len() {
+        if lines[at].kind == DiffKind::Same {
+            let line = &lines[at];
+            rows.push(DiffRow {
+                old: line.old_line.map(|number| (number, line.text.clone())),
+                new: line.new_line.map(|number| (number, line.text.clone())),
+            });
+            at += 1;
+            continue;
+        }
+        let change = lines[at..]
+            .iter()
+            .take_while(|line| line.kind != DiffKind::Same)
+            .collect::<Vec<_>>();
+        let side = |kind| {
+            change
+                .iter()
+                .filter(move |line| line.kind == kind)
+                .map(|line| {
+                    (
+                        line.old_line.or(line.new_line).unwrap_or_default(),
+                        line.text.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let (removed, added) = (side(DiffKind::Removed), side(DiffKind::Added));
+        for index in 0..removed.len().max(added.len()) {
+            rows.push(DiffRow {
+                old: removed.get(index).cloned(),
+                new: added.get(index).cloned(),
+            });
+        }
+        at += change.len();
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(lines: &[DiffLine]) -> Vec<(DiffKind, &str)> {
+        lines
+            .iter()
+            .map(|line| (line.kind, line.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn lines_match_along_a_longest_common_subsequence() {
+        let base = ["a\n", "b\n", "c\n", "d\n"];
+        let other = ["b\n", "x\n", "c\n", "d\n", "e\n"];
+        assert_eq!(
+            match_lines(&base, &other),
+            [None, Some(0), Some(2), Some(3)]
+        );
+    }
+
+    #[test]
+    fn unified_diff_numbers_lines_on_both_sides() {
+        let lines = diff_lines("a\nb\nc\n", "a\nB\nc\nd");
+        assert_eq!(
+            kinds(&lines),
+            [
+                (DiffKind::Same, "a"),
+                (DiffKind::Removed, "b"),
+                (DiffKind::Added, "B"),
+                (DiffKind::Same, "c"),
+                (DiffKind::Added, "d"),
+            ]
+        );
+        assert_eq!((lines[1].old_line, lines[1].new_line), (Some(2), None));
+        assert_eq!((lines[2].old_line, lines[2].new_line), (None, Some(2)));
+        assert_eq!((lines[4].old_line, lines[4].new_line), (None, Some(4)));
+        assert!(diff_lines("same\n", "same")
+            .iter()
+            .all(|line| line.kind == DiffKind::Same));
+    }
+
+    #[test]
+    fn side_by_side_pairs_removed_and_added_lines() {
+        let rows = side_by_side(&diff_lines("a\nb\nc\nz", "a\nx\nz"));
+        assert_eq!(rows.len(), 4);
+        assert!(!rows[0].changed());
+        assert_eq!(rows[1].old, Some((2, "b".to_string())));
+        assert_eq!(rows[1].new, Some((2, "x".to_string())));
+        assert_eq!(rows[2].old, Some((3, "c".to_string())));
+        assert_eq!(rows[2].new, None);
+        assert!(rows[2].changed());
+        assert_eq!(rows[3].new, Some((3, "z".to_string())));
+    }
+}
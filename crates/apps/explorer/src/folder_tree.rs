@@ -0,0 +1,277 @@
+//! Expandable folder tree shown in the Explorer sidebar.
+//!
+//! The tree only tracks folders. A folder's children are unknown until it is expanded for the
+//! first time or listed in the contents pane, so large trees are loaded one level at a time.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use platform_host::{ExplorerEntry, ExplorerEntryKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TreeChildren {
+    Loading,
+    /// Child folder names, sorted case-insensitively.
+    Loaded(Vec<String>),
+    Failed,
+}
+
+/// Loaded children and expansion state for every folder the tree has seen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FolderTree {
+    children: BTreeMap<String, TreeChildren>,
+    expanded: BTreeSet<String>,
+}
+
+/// One visible row of the flattened tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct FolderTreeRow {
+    pub(crate) path: String,
+    pub(crate) name: String,
+    /// One-based depth, as used by `aria-level`; the root is level 1.
+    pub(crate) level: usize,
+    pub(crate) expanded: bool,
+    /// Whether the folder has visible subfolders, or `None` while that is unknown.
+    pub(crate) has_children: Option<bool>,
+    pub(crate) loading: bool,
+}
+
+/// Effect of a navigation key on the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TreeMove {
+    Focus(String),
+    Expand(String),
+    Collapse(String),
+}
+
+fn child_path(parent: &str, name: &str) -> String {
+    match parent {
+        "/" => format!("/{name}"),
+        parent => format!("{parent}/{name}"),
+    }
+}
+
+fn is_visible(name: &str, show_hidden: bool) -> bool {
+    show_hidden || !name.starts_with('.')
+}
+
+impl FolderTree {
+    /// Expands `path`, returning whether its children still have to be loaded.
+    pub(crate) fn expand(&mut self, path: &str) -> bool {
+        self.expanded.insert(path.to_string());
+        match self.children.get(path) {
+            Some(TreeChildren::Loading | TreeChildren::Loaded(_)) => false,
+            Some(TreeChildren::Failed) | None => {
+                self.children
+                    .insert(path.to_string(), TreeChildren::Loading);
+                true
+            }
+        }
+    }
+
+    pub(crate) fn is_expanded(&self, path: &str) -> bool {
+        self.expanded.contains(path)
+    }
+
+    pub(crate) fn collapse(&mut self, path: &str) {
+        self.expanded.remove(path);
+    }
+
+    /// Expands every ancestor of `path` so its row is visible, returning the folders that still
+    /// have to be loaded, outermost first.
+    pub(crate) fn reveal(&mut self, path: &str) -> Vec<String> {
+        let mut ancestor = "/".to_string();
+        let mut pending = Vec::new();
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            if self.expand(&ancestor) {
+                pending.push(ancestor.clone());
+            }
+            ancestor = child_path(&ancestor, segment);
+        }
+        pending
+    }
+
+    /// Records the subfolders of `path` from a directory listing.
+    pub(crate) fn set_listing(&mut self, path: &str, entries: &[ExplorerEntry]) {
+        let mut names = entries
+            .iter()
+            .filter(|entry| entry.kind == ExplorerEntryKind::Directory)
+            .map(|entry| entry.name.clone())
+            .collect::<Vec<_>>();
+        names.sort_by_key(|name| name.to_lowercase());
+        self.children
+            .insert(path.to_string(), TreeChildren::Loaded(names));
+    }
+
+    /// Marks a load of `path` as failed so expanding it again retries.
+    pub(crate) fn set_failed(&mut self, path: &str) {
+        self.children.insert(path.to_string(), TreeChildren::Failed);
+    }
+
+    /// Flattens the expanded part of the tree into rows, depth first from the root.
+    pub(crate) fn rows(&self, show_hidden: bool) -> Vec<FolderTreeRow> {
+        let mut rows = Vec::new();
+        let mut pending = vec![("/".to_string(), "/".to_string(), 1)];
+        while let Some((path, name, level)) = pending.pop() {
+            let expanded = self.expanded.contains(&path);
+            let children = match self.children.get(&path) {
+                Some(TreeChildren::Loaded(names)) => Some(
+                    names
+                        .iter()
+                        .filter(|name| is_visible(name, show_hidden))
+                        .collect::<Vec<_>>(),
+                ),
+                _ => None,
+            };
+            if expanded {
+                for name in children.iter().flatten().rev() {
+                    pending.push((child_path(&path, name), name.to_string(), level + 1));
+                }
+            }
+            rows.push(FolderTreeRow {
+                has_children: children.as_ref().map(|names| !names.is_empty()),
+                loading: self.children.get(&path) == Some(&TreeChildren::Loading),
+                path,
+                name,
+                level,
+                expanded,
+            });
+        }
+        rows
+    }
+}
+
+/// Resolves a tree navigation key following the WAI-ARIA tree view pattern.
+///
+/// Right expands a collapsed folder or steps into an expanded one; Left collapses an expanded
+/// folder or steps out to its parent.
+pub(crate) fn tree_key_move(
+    rows: &[FolderTreeRow],
+    focused: Option<&str>,
+    key: &str,
+) -> Option<TreeMove> {
+    const KEYS: [&str; 6] = [
+        "ArrowDown",
+        "ArrowUp",
+        "Home",
+        "End",
+        "ArrowRight",
+        "ArrowLeft",
+    ];
+    if !KEYS.contains(&key) {
+        return None;
+    }
+    let focus = |row: &FolderTreeRow| Some(TreeMove::Focus(row.path.clone()));
+    let Some(index) = focused.and_then(|path| rows.iter().position(|row| row.path == path)) else {
+        return rows.first().and_then(focus);
+    };
+    let row = &rows[index];
+    match key {
+        "ArrowDown" => rows.get(index + 1).and_then(focus),
+        "ArrowUp" => index.checked_sub(1).and_then(|prev| focus(&rows[prev])),
+        "Home" => rows.first().and_then(focus),
+        "End" => rows.last().and_then(focus),
+        "ArrowRight" if !row.expanded && row.has_children != Some(false) => {
+            Some(TreeMove::Expand(row.path.clone()))
+        }
+        "ArrowRight" => rows
+            .get(index + 1)
+            .filter(|next| next.level > row.level)
+            .and_then(focus),
+        "ArrowLeft" if row.expanded => Some(TreeMove::Collapse(row.path.clone())),
+        "ArrowLeft" => rows[..index]
+            .iter()
+            .rev()
+            .find(|parent| parent.level < row.level)
+            .and_then(focus),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(names: &[(&str, ExplorerEntryKind)]) -> Vec<ExplorerEntry> {
+        names
+            .iter()
+            .map(|(name, kind)| ExplorerEntry {
+                name: name.to_string(),
+                path: format!("/{name}"),
+                kind: *kind,
+                size: None,
+                modified_at_unix_ms: None,
+            })
+            .collect()
+    }
+
+    fn summary(rows: &[FolderTreeRow]) -> Vec<(&str, usize)> {
+        rows.iter()
+            .map(|row| (row.path.as_str(), row.level))
+            .collect()
+    }
+
+    #[test]
+    fn folders_load_lazily_and_flatten_depth_first() {
+        let mut tree = FolderTree::default();
+        assert_eq!(tree.reveal("/docs/notes"), ["/", "/docs"]);
+        assert!(!tree.expand("/"));
+        let rows = tree.rows(false);
+        assert_eq!(summary(&rows), [("/", 1)]);
+        assert!(rows[0].loading && rows[0].has_children.is_none());
+
+        tree.set_listing(
+            "/",
+            &listing(&[
+                ("music", ExplorerEntryKind::Directory),
+                ("Docs", ExplorerEntryKind::Directory),
+                (".cache", ExplorerEntryKind::Directory),
+                ("readme.txt", ExplorerEntryKind::File),
+            ]),
+        );
+        tree.set_listing("/Docs", &[]);
+        tree.expand("/Docs");
+        assert_eq!(
+            summary(&tree.rows(false)),
+            [("/", 1), ("/Docs", 2), ("/music", 2)]
+        );
+        assert_eq!(tree.rows(true)[1].path, "/.cache");
+        assert_eq!(tree.rows(false)[1].has_children, Some(false));
+
+        tree.collapse("/");
+        assert_eq!(summary(&tree.rows(false)), [("/", 1)]);
+        tree.set_failed("/music");
+        assert!(tree.expand("/music"));
+    }
+
+    #[test]
+    fn arrow_keys_follow_the_tree_pattern() {
+        let mut tree = FolderTree::default();
+        tree.expand("/");
+        tree.set_listing(
+            "/",
+            &listing(&[
+                ("a", ExplorerEntryKind::Directory),
+                ("b", ExplorerEntryKind::Directory),
+            ]),
+        );
+        let rows = tree.rows(false);
+        let focus = |path: &str| Some(TreeMove::Focus(path.to_string()));
+
+        assert_eq!(tree_key_move(&rows, None, "ArrowDown"), focus("/"));
+        assert_eq!(tree_key_move(&rows, Some("/"), "ArrowDown"), focus("/a"));
+        assert_eq!(tree_key_move(&rows, Some("/a"), "ArrowUp"), focus("/"));
+        assert_eq!(tree_key_move(&rows, Some("/"), "End"), focus("/b"));
+        assert_eq!(tree_key_move(&rows, Some("/"), "ArrowRight"), focus("/a"));
+        assert_eq!(
+            tree_key_move(&rows, Some("/a"), "ArrowRight"),
+            Some(TreeMove::Expand("/a".to_string()))
+        );
+        assert_eq!(tree_key_move(&rows, Some("/b"), "ArrowLeft"), focus("/"));
+        assert_eq!(
+            tree_key_move(&rows, Some("/"), "ArrowLeft"),
+            Some(TreeMove::Collapse("/".to_string()))
+        );
+        assert_eq!(tree_key_move(&rows, Some("/"), "ArrowUp"), None);
+        assert_eq!(tree_key_move(&rows, None, "Enter"), None);
+    }
+}
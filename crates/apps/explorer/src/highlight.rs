@@ -0,0 +1,511 @@
+//! Syntax highlighting for the Explorer editor.
+//!
+//! Highlighting works a line at a time, carrying only the state that can span lines: block
+//! comments, multi-line strings, and fenced Markdown code blocks. It is meant to make files easier
+//! to scan, not to parse them, so unusual constructs may be colored loosely.
+
+use system_ui::{CodeSpan, CodeToken};
+
+/// Texts longer than this are shown without colors so typing stays responsive.
+const MAX_HIGHLIGHT_BYTES: usize = 256 * 1024;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+const JSON_KEYWORDS: &[&str] = &["true", "false", "null"];
+
+/// Language the editor highlights a file as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeLanguage {
+    Plain,
+    Rust,
+    JavaScript,
+    Json,
+    Markdown,
+}
+
+impl CodeLanguage {
+    /// Language for a file, picked by its extension.
+    pub(crate) fn for_path(path: &str) -> Self {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let extension = name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "rs" => Self::Rust,
+            "js" | "mjs" | "cjs" | "jsx" | "ts" | "tsx" => Self::JavaScript,
+            "json" | "jsonc" => Self::Json,
+            "md" | "markdown" => Self::Markdown,
+            _ => Self::Plain,
+        }
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => RUST_KEYWORDS,
+            Self::JavaScript => JS_KEYWORDS,
+            Self::Json => JSON_KEYWORDS,
+            Self::Plain | Self::Markdown => &[],
+        }
+    }
+
+    /// Whether a string opened by `quote` may continue on the next line.
+    fn spans_lines(self, quote: char) -> bool {
+        matches!((self, quote), (Self::Rust, '"') | (Self::JavaScript, '`'))
+    }
+}
+
+/// Highlighting state carried from the end of one line to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Carry {
+    None,
+    BlockComment,
+    String(char),
+    Fence,
+}
+
+/// Collects spans of a line, merging neighbors with the same token.
+#[derive(Default)]
+struct LineSpans(Vec<CodeSpan>);
+
+impl LineSpans {
+    fn push(&mut self, token: CodeToken, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.0.last_mut() {
+            Some(last) if last.token == token => last.text.push_str(text),
+            _ => self.0.push(CodeSpan {
+                token,
+                text: text.to_string(),
+            }),
+        }
+    }
+}
+
+/// Splits `text` into lines of highlighted spans for `language`.
+pub(crate) fn highlight(text: &str, language: CodeLanguage) -> Vec<Vec<CodeSpan>> {
+    let language = if text.len() > MAX_HIGHLIGHT_BYTES {
+        CodeLanguage::Plain
+    } else {
+        language
+    };
+    let mut carry = Carry::None;
+    text.split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let mut spans = LineSpans::default();
+            carry = match language {
+                CodeLanguage::Plain => {
+                    spans.push(CodeToken::Plain, line);
+                    Carry::None
+                }
+                CodeLanguage::Markdown => highlight_markdown(line, carry, &mut spans),
+                _ => highlight_code(line, language, carry, &mut spans),
+            };
+            spans.0
+        })
+        .collect()
+}
+
+/// Byte index just past the string body starting at `from` and closed by `quote`, or `None` when
+/// the line ends first.
+fn string_end(line: &str, from: usize, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, ch) in line[from..].char_indices() {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return Some(from + index + ch.len_utf8());
+        }
+    }
+    None
+}
+
+fn highlight_code(
+    line: &str,
+    language: CodeLanguage,
+    mut carry: Carry,
+    spans: &mut LineSpans,
+) -> Carry {
+    let mut at = 0;
+    while at < line.len() {
+        match carry {
+            Carry::BlockComment => match line[at..].find("*/") {
+                Some(end) => {
+                    spans.push(CodeToken::Comment, &line[at..at + end + 2]);
+                    at += end + 2;
+                    carry = Carry::None;
+                }
+                None => {
+                    spans.push(CodeToken::Comment, &line[at..]);
+                    return carry;
+                }
+            },
+            Carry::String(quote) => match string_end(line, at, quote) {
+                Some(end) => {
+                    spans.push(CodeToken::String, &line[at..end]);
+                    at = end;
+                    carry = Carry::None;
+                }
+                None => {
+                    spans.push(CodeToken::String, &line[at..]);
+                    return carry;
+                }
+            },
+            Carry::None | Carry::Fence => {
+                let rest = &line[at..];
+                let ch = rest.chars().next().unwrap_or_default();
+                if rest.starts_with("//") {
+                    spans.push(CodeToken::Comment, rest);
+                    return Carry::None;
+                } else if rest.starts_with("/*") {
+                    spans.push(CodeToken::Comment, "/*");
+                    at += 2;
+                    carry = Carry::BlockComment;
+                } else if let Some(end) = quoted_at(line, at, language) {
+                    let closed = end.is_some();
+                    let end = end.unwrap_or(line.len());
+                    let after = line[end..].trim_start();
+                    let token = if language == CodeLanguage::Json && after.starts_with(':') {
+                        CodeToken::Property
+                    } else {
+                        CodeToken::String
+                    };
+                    spans.push(token, &line[at..end]);
+                    if !closed && language.spans_lines(ch) {
+                        return Carry::String(ch);
+                    }
+                    at = end;
+                } else if ch.is_ascii_digit() {
+                    let len = word_len(rest, |ch| {
+                        ch.is_ascii_alphanumeric() || ch == '_' || ch == '.'
+                    });
+                    spans.push(CodeToken::Number, &rest[..len]);
+                    at += len;
+                } else if ch.is_alphabetic() || ch == '_' || ch == '$' {
+                    let len = word_len(rest, |ch| ch.is_alphanumeric() || ch == '_' || ch == '$');
+                    let word = &rest[..len];
+                    let is_macro = language == CodeLanguage::Rust && rest[len..].starts_with('!');
+                    let token = if is_macro || language.keywords().contains(&word) {
+                        CodeToken::Keyword
+                    } else if language != CodeLanguage::Json
+                        && word.starts_with(|ch: char| ch.is_uppercase())
+                    {
+                        CodeToken::Type
+                    } else {
+                        CodeToken::Plain
+                    };
+                    let len = len + usize::from(is_macro);
+                    spans.push(token, &rest[..len]);
+                    at += len;
+                } else {
+                    spans.push(CodeToken::Plain, &rest[..ch.len_utf8()]);
+                    at += ch.len_utf8();
+                }
+            }
+        }
+    }
+    carry
+}
+
+/// Whether a string or character literal opens at `at`, with its end (`None` when it runs past the
+/// line).
+///
+/// Rust's `'` opens a character literal only when it closes shortly after, so lifetimes such as
+/// `'a` stay plain.
+fn quoted_at(line: &str, at: usize, language: CodeLanguage) -> Option<Option<usize>> {
+    let quote = line[at..].chars().next()?;
+    let quotes: &[char] = match language {
+        CodeLanguage::Rust => &['"', '\''],
+        CodeLanguage::JavaScript => &['"', '\'', '`'],
+        _ => &['"'],
+    };
+    if !quotes.contains(&quote) {
+        return None;
+    }
+    let end = string_end(line, at + 1, quote);
+    if language == CodeLanguage::Rust && quote == '\'' {
+        let literal = end.filter(|end| {
+            line[at + 1..end - 1].chars().count() <= 1 || line[at + 1..].starts_with('\\')
+        })?;
+        return Some(Some(literal));
+    }
+    Some(end)
+}
+
+fn word_len(text: &str, continues: impl Fn(char) -> bool) -> usize {
+    text.char_indices()
+        .find(|(_, ch)| !continues(*ch))
+        .map_or(text.len(), |(index, _)| index)
+}
+
+fn highlight_markdown(line: &str, carry: Carry, spans: &mut LineSpans) -> Carry {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+        spans.push(CodeToken::Comment, line);
+        return if carry == Carry::Fence {
+            Carry::None
+        } else {
+            Carry::Fence
+        };
+    }
+    if carry == Carry::Fence {
+        spans.push(CodeToken::String, line);
+        return Carry::Fence;
+    }
+    let hashes = trimmed.chars().take_while(|ch| *ch == '#').count();
+    if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+        spans.push(CodeToken::Heading, line);
+        return Carry::None;
+    }
+    if trimmed.starts_with('>') {
+        spans.push(CodeToken::Comment, line);
+        return Carry::None;
+    }
+    spans.push(CodeToken::Plain, indent);
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let marker = if ["- ", "* ", "+ "]
+        .iter()
+        .any(|marker| trimmed.starts_with(marker))
+    {
+        2
+    } else if digits > 0 && trimmed[digits..].starts_with(". ") {
+        digits + 2
+    } else {
+        0
+    };
+    spans.push(CodeToken::Keyword, &trimmed[..marker]);
+    highlight_inline(&trimmed[marker..], spans);
+    Carry::None
+}
+
+/// Highlights inline code, emphasis, and links in Markdown text.
+fn highlight_inline(text: &str, spans: &mut LineSpans) {
+    let mut at = 0;
+    while at < text.len() {
+        let rest = &text[at..];
+        let ch = rest.chars().next().unwrap_or_default();
+        let closed = |open: &str, close: &str, token| {
+            let body = rest.strip_prefix(open)?;
+            let end = body.find(close).filter(|end| *end > 0)?;
+            Some((open.len() + end + close.len(), token))
+        };
+        let run = match ch {
+            '`' => closed("`", "`", CodeToken::String),
+            '*' | '_' => {
+                let double = if ch == '*' { "**" } else { "__" };
+                let single = if ch == '*' { "*" } else { "_" };
+                closed(double, double, CodeToken::Emphasis)
+                    .or_else(|| closed(single, single, CodeToken::Emphasis))
+            }
+            '[' => rest.find("](").and_then(|label| {
+                let end = rest[label..].find(')')?;
+                Some((label + end + 1, CodeToken::Property))
+            }),
+            _ => None,
+        };
+        match run {
+            Some((len, token)) => {
+                spans.push(token, &rest[..len]);
+                at += len;
+            }
+            None => {
+                spans.push(CodeToken::Plain, &rest[..ch.len_utf8()]);
+                at += ch.len_utf8();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(line: &[CodeSpan]) -> Vec<(CodeToken, &str)> {
+        line.iter()
+            .map(|span| (span.token, span.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn languages_follow_the_file_extension() {
+        assert_eq!(CodeLanguage::for_path("/src/main.rs"), CodeLanguage::Rust);
+        assert_eq!(
+            CodeLanguage::for_path("/web/app.MJS"),
+            CodeLanguage::JavaScript
+        );
+        assert_eq!(CodeLanguage::for_path("/data.json"), CodeLanguage::Json);
+        assert_eq!(CodeLanguage::for_path("/README.md"), CodeLanguage::Markdown);
+        assert_eq!(CodeLanguage::for_path("/notes.txt"), CodeLanguage::Plain);
+        assert_eq!(
+            CodeLanguage::for_path("/v1.2/Makefile"),
+            CodeLanguage::Plain
+        );
+    }
+
+    #[test]
+    fn rust_lines_color_keywords_types_strings_and_comments() {
+        let lines = highlight(
+            "pub fn main() -> Result<(), Error> {\n    println!(\"hi {}\", 'x'); // done\n}",
+            CodeLanguage::Rust,
+        );
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            tokens(&lines[0])[..4],
+            [
+                (CodeToken::Keyword, "pub"),
+                (CodeToken::Plain, " "),
+                (CodeToken::Keyword, "fn"),
+                (CodeToken::Plain, " main() -> "),
+            ]
+        );
+        assert!(tokens(&lines[0]).contains(&(CodeToken::Type, "Result")));
+        assert_eq!(
+            tokens(&lines[1]),
+            [
+                (CodeToken::Plain, "    "),
+                (CodeToken::Keyword, "println!"),
+                (CodeToken::Plain, "("),
+                (CodeToken::String, "\"hi {}\""),
+                (CodeToken::Plain, ", "),
+                (CodeToken::String, "'x'"),
+                (CodeToken::Plain, "); "),
+                (CodeToken::Comment, "// done"),
+            ]
+        );
+
+        let lifetime = highlight("fn f<'a>(x: &'a str) {}", CodeLanguage::Rust);
+        assert!(!lifetime[0]
+            .iter()
+            .any(|span| span.token == CodeToken::String));
+    }
+
+    #[test]
+    fn block_comments_and_multiline_strings_carry_across_lines() {
+        let lines = highlight("a /* one\ntwo */ b\n\"x\ny\" z", CodeLanguage::Rust);
+        assert_eq!(
+            tokens(&lines[0]),
+            [(CodeToken::Plain, "a "), (CodeToken::Comment, "/* one")]
+        );
+        assert_eq!(
+            tokens(&lines[1]),
+            [(CodeToken::Comment, "two */"), (CodeToken::Plain, " b")]
+        );
+        assert_eq!(tokens(&lines[2]), [(CodeToken::String, "\"x")]);
+        assert_eq!(
+            tokens(&lines[3]),
+            [(CodeToken::String, "y\""), (CodeToken::Plain, " z")]
+        );
+
+        let js = highlight("const s = \"open\nnext", CodeLanguage::JavaScript);
+        assert_eq!(tokens(&js[1]), [(CodeToken::Plain, "next")]);
+    }
+
+    #[test]
+    fn json_keys_are_properties() {
+        let lines = highlight(
+            "{\"name\": \"x\", \"n\": 1.5, \"ok\": true}",
+            CodeLanguage::Json,
+        );
+        assert_eq!(
+            tokens(&lines[0]),
+            [
+                (CodeToken::Plain, "{"),
+                (CodeToken::Property, "\"name\""),
+                (CodeToken::Plain, ": "),
+                (CodeToken::String, "\"x\""),
+                (CodeToken::Plain, ", "),
+                (CodeToken::Property, "\"n\""),
+                (CodeToken::Plain, ": "),
+                (CodeToken::Number, "1.5"),
+                (CodeToken::Plain, ", "),
+                (CodeToken::Property, "\"ok\""),
+                (CodeToken::Plain, ": "),
+                (CodeToken::Keyword, "true"),
+                (CodeToken::Plain, "}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_marks_headings_lists_inline_runs_and_fences() {
+        let lines = highlight(
+            "# Title\n- item with `code` and **bold**\n```\nlet x = 1;\n```\n[link](url)",
+            CodeLanguage::Markdown,
+        );
+        assert_eq!(tokens(&lines[0]), [(CodeToken::Heading, "# Title")]);
+        assert_eq!(
+            tokens(&lines[1]),
+            [
+                (CodeToken::Keyword, "- "),
+                (CodeToken::Plain, "item with "),
+                (CodeToken::String, "`code`"),
+                (CodeToken::Plain, " and "),
+                (CodeToken::Emphasis, "**bold**"),
+            ]
+        );
+        assert_eq!(tokens(&lines[3]), [(CodeToken::String, "let x = 1;")]);
+        assert_eq!(tokens(&lines[5]), [(CodeToken::Property, "[link](url)")]);
+    }
+
+    #[test]
+    fn plain_text_and_empty_lines_keep_their_line_count() {
+        let lines = highlight("a\n\nb\n", CodeLanguage::Plain);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].is_empty());
+        assert!(lines[3].is_empty());
+    }
+}
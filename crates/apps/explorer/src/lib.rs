@@ -6,28 +6,91 @@
 
 #![warn(missing_docs, rustdoc::broken_intra_doc_links)]
 
-use std::{cell::Cell, rc::Rc};
+mod breadcrumbs;
+mod bulk_rename;
+mod commander;
+mod diff;
+mod folder_tree;
+mod highlight;
+mod locations;
+mod merge;
+mod preview;
+mod search;
+mod selection;
+mod sorting;
+mod type_ahead;
+mod virtual_list;
 
-use desktop_app_contract::{AppEvent, AppServices, CacheHostService, ExplorerHostService};
+use std::{cell::Cell, collections::VecDeque, rc::Rc, time::Duration};
+
+use desktop_app_contract::{
+    accept_drag_over, start_drag, take_drop, AppEvent, AppLifecycleHandler, AppServices,
+    ApplicationId, CacheHostService, CloseResponse, ConfigService, DialogService, DragData,
+    DragEffect, ExplorerCwdChangedEvent, ExplorerHostService, FileAssociations, LauncherService,
+    LifecycleFuture, LogService, TaskService, WindowService, EXPLORER_CWD_CHANGED_TOPIC,
+    EXPLORER_REFRESH_TOPIC, FILE_ASSOCIATIONS_CONFIG_KEY, FILE_ASSOCIATIONS_CONFIG_NAMESPACE,
+    FILE_DRAG_DATA_TYPE,
+};
+use leptos::ev::DragEvent;
+use leptos::wasm_bindgen::JsCast;
 use leptos::*;
 use platform_host::{
-    explorer_preview_cache_key, session_store, CapabilityStatus, ExplorerBackend,
-    ExplorerBackendStatus, ExplorerEntry, ExplorerEntryKind, ExplorerMetadata,
-    ExplorerPermissionMode, ExplorerPrefs, EXPLORER_CACHE_NAME, EXPLORER_PREFS_KEY,
+    archive_top_level_names, explorer_preview_cache_key, fresh_entry_name, is_trash_path,
+    session_store, sniff_mime, CapabilityStatus, ExplorerArchiveConflict, ExplorerBackend,
+    ExplorerBackendStatus, ExplorerEntry, ExplorerEntryKind, ExplorerFileReadResult,
+    ExplorerFolderSize, ExplorerImportFile, ExplorerListResult, ExplorerMetadata,
+    ExplorerPermissionMode, ExplorerPrefs, ExplorerSortDirection, ExplorerSortKey,
+    ExplorerStorageUsage, ExplorerTrashEntry, ExplorerViewMode, ExplorerWatch, EXPLORER_CACHE_NAME,
+    EXPLORER_MOUNT_DIR, EXPLORER_PREFS_KEY, EXPLORER_STREAM_CHUNK_BYTES,
 };
+use platform_paths::{entry_name, join_path, normalize_path, parent_path, resolve_path};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use system_ui::prelude::*;
 
+use crate::breadcrumbs::{
+    breadcrumb_trail, complete_input, completion_base, folder_completions, BreadcrumbTrail, Crumb,
+};
+use crate::bulk_rename::{
+    preview_bulk_rename, BulkRenamePreview, BulkRenameRule, BulkRenameSource, NameCase,
+};
+use crate::commander::{pane_command, step_focus, ActivePane, PaneCommand};
+use crate::diff::{diff_lines, side_by_side, DiffKind, DiffLine};
+use crate::folder_tree::{tree_key_move, FolderTree, FolderTreeRow, TreeMove};
+use crate::highlight::{highlight, CodeLanguage};
+use crate::locations::{
+    is_favorite, is_latest_location, location_label, record_recent_location, toggle_favorite,
+};
+use crate::merge::merge_texts;
+use crate::preview::{
+    hex_page_count, hex_rows, looks_binary, media_kind, media_type, text_may_be_binary, MediaKind,
+    MAX_HEX_VIEW_BYTES, MAX_MEDIA_PREVIEW_BYTES,
+};
+use crate::search::{SearchHit, SearchQuery};
+use crate::selection::ExplorerSelection;
+use crate::sorting::{aria_sort, select_sort_column, sort_entries};
+use crate::type_ahead::{type_ahead_char, type_ahead_match, TypeAhead};
+use crate::virtual_list::{
+    listing_window, reveal_offset, ListingViewport, ListingWindow, VIRTUALIZE_AFTER,
+};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ExplorerPersistedState {
     cwd: String,
     selected_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    selected_paths: Vec<String>,
     selected_metadata: Option<ExplorerMetadata>,
     editor_path: Option<String>,
     editor_text: String,
     editor_dirty: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    editor_base: Option<EditorBase>,
     last_backend: ExplorerBackend,
+    #[serde(default)]
+    dual_pane: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    second_cwd: Option<String>,
 }
 
 impl Default for ExplorerPersistedState {
@@ -35,11 +98,54 @@ impl Default for ExplorerPersistedState {
         Self {
             cwd: "/".to_string(),
             selected_path: None,
+            selected_paths: Vec::new(),
             selected_metadata: None,
             editor_path: None,
             editor_text: String::new(),
             editor_dirty: false,
+            editor_base: None,
             last_backend: ExplorerBackend::IndexedDbVirtual,
+            dual_pane: false,
+            second_cwd: None,
+        }
+    }
+}
+
+/// The open file as it was last read or saved, which a save compares with the file on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EditorBase {
+    modified_at_unix_ms: Option<u64>,
+    text: String,
+}
+
+impl EditorBase {
+    fn of(file: &ExplorerFileReadResult) -> Self {
+        Self {
+            modified_at_unix_ms: file.metadata.modified_at_unix_ms,
+            text: file.text.clone(),
+        }
+    }
+}
+
+/// Read-only view of a file the text editor cannot show, displayed in the inspector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExplorerPreview {
+    path: String,
+    content: PreviewContent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PreviewContent {
+    /// Image, audio, or video served through a blob URL owned by the preview.
+    Media { kind: MediaKind, url: String },
+    /// Leading bytes of a binary file for the hex viewer, with the full file size.
+    Bytes { bytes: Rc<[u8]>, size: u64 },
+}
+
+impl ExplorerPreview {
+    fn revoke(&self) {
+        if let PreviewContent::Media { url, .. } = &self.content {
+            let _ = web_sys::Url::revoke_object_url(url);
         }
     }
 }
@@ -49,14 +155,180 @@ struct ExplorerSignals {
     status: RwSignal<Option<ExplorerBackendStatus>>,
     cwd: RwSignal<String>,
     entries: RwSignal<Vec<ExplorerEntry>>,
-    selected_path: RwSignal<Option<String>>,
+    selection: RwSignal<ExplorerSelection>,
     selected_metadata: RwSignal<Option<ExplorerMetadata>>,
     editor_path: RwSignal<Option<String>>,
     editor_text: RwSignal<String>,
     editor_dirty: RwSignal<bool>,
+    editor_base: RwSignal<Option<EditorBase>>,
+    /// Autosaved text from an earlier window, offered back for the open file.
+    editor_draft: RwSignal<Option<String>>,
+    preview: RwSignal<Option<ExplorerPreview>>,
     error: RwSignal<Option<String>>,
     notice: RwSignal<Option<String>>,
+    undo: RwSignal<Option<ExplorerUndo>>,
+    trash: RwSignal<Vec<ExplorerTrashEntry>>,
     busy: RwSignal<bool>,
+    /// Window log service that [`warn_explorer`] reports background failures to.
+    logs: StoredValue<Option<LogService>>,
+}
+
+/// Folder, listing, and selection of the second pane in dual-pane mode.
+#[derive(Clone, Copy)]
+struct SecondPane {
+    enabled: RwSignal<bool>,
+    cwd: RwSignal<String>,
+    entries: RwSignal<Vec<ExplorerEntry>>,
+    selection: RwSignal<ExplorerSelection>,
+    active: RwSignal<ActivePane>,
+}
+
+/// Crumbs shown in the path bar before the middle of the path folds into a menu.
+const MAX_VISIBLE_CRUMBS: usize = 4;
+/// Stop a search once this many entries have matched.
+const MAX_SEARCH_RESULTS: usize = 500;
+/// Files larger than this are skipped when searching contents.
+const MAX_CONTENT_SEARCH_BYTES: u64 = 1024 * 1024;
+/// Entries requested per listing page.
+const LISTING_PAGE_SIZE: usize = 1000;
+/// Unsaved editor text is stored in the content cache once typing pauses for this long.
+const DRAFT_AUTOSAVE_DELAY: Duration = Duration::from_secs(2);
+/// Changes to a watched path that arrive within this delay are handled with one reload.
+const WATCH_RELOAD_DELAY: Duration = Duration::from_millis(150);
+/// Type-ahead starts a new prefix once typing pauses for this long.
+const TYPE_AHEAD_RESET_DELAY: Duration = Duration::from_millis(1000);
+
+/// Results and progress of the recursive folder search.
+///
+/// Each run bumps `generation`; a run stops as soon as the generation moves on, which is how
+/// newer searches and the Cancel action supersede it.
+#[derive(Clone, Copy)]
+struct ExplorerSearch {
+    results: RwSignal<Vec<SearchHit>>,
+    running: RwSignal<bool>,
+    status: RwSignal<Option<String>>,
+    generation: RwSignal<u64>,
+}
+
+/// Batch operation applied to every selected entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransferKind {
+    Move,
+    Copy,
+}
+
+/// Last reversible operation, kept so the result notice can reverse it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ExplorerUndo {
+    /// `(from, to)` pairs a move or copy completed.
+    Transfer {
+        kind: TransferKind,
+        completed: Vec<(String, String)>,
+    },
+    /// Entries a delete moved to the trash.
+    Trash(Vec<ExplorerTrashEntry>),
+}
+
+/// Where an inline rename field is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenameSurface {
+    Row,
+    Details,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExplorerRename {
+    path: String,
+    surface: RenameSurface,
+}
+
+/// Context menu opened on a listing row or on empty space, positioned at the pointer or, when
+/// opened from the keyboard, at the focused row.
+#[derive(Debug, Clone, PartialEq)]
+struct ExplorerContextMenu {
+    /// Entry the menu acts on, or `None` for the current folder.
+    target: Option<ExplorerEntry>,
+    x: i32,
+    y: i32,
+}
+
+/// Entries marked by Copy or Cut, waiting to be pasted into the current folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExplorerClipboard {
+    kind: TransferKind,
+    paths: Vec<String>,
+}
+
+/// Viewer picked through Open With instead of from the file name and contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileViewer {
+    Editor,
+    Hex,
+    Media,
+}
+
+/// Entries of one folder the bulk rename dialog is open for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingBulkRename {
+    folder: String,
+    /// Selected entries in listing order.
+    sources: Vec<BulkRenameSource>,
+    /// Names of the folder's other entries.
+    others: Vec<String>,
+}
+
+/// How to save an open file that changed on disk since it was read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveConflictChoice {
+    /// Discard the editor's changes and load the file from disk.
+    Reload,
+    /// Save the editor's text over the other change.
+    Overwrite,
+    /// Combine both changes in the editor for review before saving.
+    Merge,
+}
+
+/// Two texts compared line by line in the Compare dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExplorerComparison {
+    old_label: String,
+    new_label: String,
+    lines: Vec<DiffLine>,
+}
+
+/// Contents of the Storage panel. Each part stays `None` while it is being measured.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ExplorerStorageReport {
+    usage: Option<Result<ExplorerStorageUsage, String>>,
+    folders: Option<Result<Vec<(String, ExplorerFolderSize)>, String>>,
+}
+
+/// Archives waiting for the user to decide what happens to names that already exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingExtract {
+    dest: String,
+    archives: Vec<ExplorerImportFile>,
+    /// Top-level archive names already used in `dest`.
+    conflicts: Vec<String>,
+}
+
+/// Contents of the Properties dialog for one entry, filled in as the host answers.
+#[derive(Debug, Clone, PartialEq)]
+struct ExplorerProperties {
+    path: String,
+    metadata: Option<Result<ExplorerMetadata, String>>,
+    /// Recursive totals, only computed for folders.
+    folder_size: Option<Result<ExplorerFolderSize, String>>,
+    checksum: ChecksumState,
+}
+
+/// Progress of the on-demand SHA-256 checksum of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ChecksumState {
+    Idle,
+    Running { hashed: u64, size: u64 },
+    Done(String),
+    Failed(String),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -79,67 +351,90 @@ fn setup_step_status(active: ExplorerSetupStep, step: ExplorerSetupStep) -> Step
     }
 }
 
-fn normalize_path(path: &str) -> String {
-    let trimmed = path.trim();
-    if trimmed.is_empty() {
-        return "/".to_string();
-    }
-    let mut out = String::new();
-    for segment in trimmed.replace('\\', "/").split('/') {
-        if segment.is_empty() || segment == "." {
-            continue;
-        }
-        if segment == ".." {
-            if let Some(idx) = out.rfind('/') {
-                out.truncate(idx);
-            }
-            continue;
-        }
-        out.push('/');
-        out.push_str(segment);
+/// Validates a new entry name and returns the path it gives `from` within the same folder.
+fn rename_target(from: &str, name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Enter a name first".to_string());
     }
-    if out.is_empty() {
-        "/".to_string()
-    } else {
-        out
+    if name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(format!("`{name}` is not a valid name"));
     }
+    Ok(join_path(&parent_path(from), name))
 }
 
-fn join_path(base: &str, name: &str) -> String {
-    let base = normalize_path(base);
-    let name = name.trim().trim_matches('/');
-    if name.is_empty() {
-        return base;
+/// Maps `path` to its location once `from` becomes `to`, or `None` when it lies outside `from`.
+fn rebase_path(path: &str, from: &str, to: &str) -> Option<String> {
+    if path == from {
+        return Some(to.to_string());
     }
-    if base == "/" {
-        format!("/{name}")
+    path.strip_prefix(from)
+        .filter(|rest| rest.starts_with('/'))
+        .map(|rest| format!("{to}{rest}"))
+}
+
+/// Pairs each selected path with its location inside `destination`.
+///
+/// Fails when a folder would be placed inside itself.
+fn transfer_targets(paths: &[String], destination: &str) -> Result<Vec<(String, String)>, String> {
+    let destination = normalize_path(destination);
+    paths
+        .iter()
+        .map(|path| {
+            if rebase_path(&destination, path, path).is_some() {
+                Err(format!("Cannot place {} inside itself", entry_name(path)))
+            } else {
+                Ok((path.clone(), join_path(&destination, &entry_name(path))))
+            }
+        })
+        .collect()
+}
+
+/// Offers dragged rows to other app windows as file [`DragData`], with the plain paths as a
+/// newline-separated text fallback.
+fn start_entry_drag(ev: &DragEvent, paths: &[String]) {
+    start_drag(
+        ev,
+        &DragData::files(paths.iter().cloned()),
+        Some(&paths.join("\n")),
+        DragEffect::CopyMove,
+    );
+}
+
+/// Dropping copies while Ctrl (or Cmd) is held and moves otherwise.
+fn drop_transfer_kind(ev: &DragEvent) -> TransferKind {
+    if ev.ctrl_key() || ev.meta_key() {
+        TransferKind::Copy
     } else {
-        format!("{base}/{name}")
+        TransferKind::Move
     }
 }
 
-fn parent_path(path: &str) -> String {
-    let path = normalize_path(path);
-    if path == "/" {
-        return path;
-    }
-    match path.rfind('/') {
-        Some(0) | None => "/".to_string(),
-        Some(idx) => path[..idx].to_string(),
-    }
+fn dropped_entry_paths(ev: &DragEvent) -> Option<Vec<String>> {
+    take_drop(ev, FILE_DRAG_DATA_TYPE).and_then(|data| data.paths())
 }
 
-fn entry_name(path: &str) -> String {
-    let path = normalize_path(path);
-    if path == "/" {
-        "/".to_string()
-    } else {
-        path.rsplit('/').next().unwrap_or_default().to_string()
+fn entry_icon(kind: ExplorerEntryKind) -> IconName {
+    match kind {
+        ExplorerEntryKind::Directory => IconName::ExplorerFolder,
+        ExplorerEntryKind::File => IconName::DocumentText,
     }
 }
 
 fn explorer_row_dom_id(path: &str) -> String {
-    let mut id = String::from("explorer-row-");
+    path_dom_id("explorer-row-", path)
+}
+
+fn commander_row_dom_id(path: &str) -> String {
+    path_dom_id("explorer-second-row-", path)
+}
+
+fn folder_tree_dom_id(path: &str) -> String {
+    path_dom_id("explorer-folder-", path)
+}
+
+fn path_dom_id(prefix: &str, path: &str) -> String {
+    let mut id = String::from(prefix);
     for ch in path.chars() {
         if ch.is_ascii_alphanumeric() {
             id.push(ch.to_ascii_lowercase());
@@ -150,30 +445,55 @@ fn explorer_row_dom_id(path: &str) -> String {
     id
 }
 
-fn persisted_snapshot(signals: ExplorerSignals) -> ExplorerPersistedState {
+fn persisted_snapshot(signals: ExplorerSignals, second: SecondPane) -> ExplorerPersistedState {
     ExplorerPersistedState {
         cwd: signals.cwd.get(),
-        selected_path: signals.selected_path.get(),
+        selected_path: signals
+            .selection
+            .with(|selection| selection.focus().map(str::to_string)),
+        selected_paths: signals
+            .selection
+            .with(|selection| selection.paths().to_vec()),
         selected_metadata: signals.selected_metadata.get(),
         editor_path: signals.editor_path.get(),
         editor_text: signals.editor_text.get(),
         editor_dirty: signals.editor_dirty.get(),
+        editor_base: signals.editor_base.get(),
         last_backend: signals
             .status
             .get()
             .map(|s| s.backend)
             .unwrap_or(ExplorerBackend::IndexedDbVirtual),
+        dual_pane: second.enabled.get(),
+        second_cwd: Some(second.cwd.get()),
     }
 }
 
+fn focused_path(signals: ExplorerSignals) -> Option<String> {
+    signals
+        .selection
+        .with_untracked(|selection| selection.focus().map(str::to_string))
+}
+
 fn set_error(signals: ExplorerSignals, message: impl Into<String>) {
     signals.error.set(Some(message.into()));
     signals.notice.set(None);
+    signals.undo.set(None);
 }
 
 fn set_notice(signals: ExplorerSignals, message: impl Into<String>) {
     signals.notice.set(Some(message.into()));
     signals.error.set(None);
+    signals.undo.set(None);
+}
+
+/// Logs `message` through the window's [`LogService`], or to the console when mounted without
+/// services.
+fn warn_explorer(logs: StoredValue<Option<LogService>>, message: String) {
+    match logs.get_value() {
+        Some(logs) => logs.warn(message),
+        None => logging::warn!("{message}"),
+    }
 }
 
 fn native_explorer_status(services: Option<&AppServices>) -> CapabilityStatus {
@@ -210,40 +530,257 @@ fn refresh_directory(
             signals.busy.set(false);
             return;
         };
-        let list_result = explorer.list_dir(&target).await;
-        match list_result {
+        match load_listing(signals, &explorer, &target).await {
+            Ok(Some(cwd)) => set_notice(signals, format!("Loaded {}", cwd)),
+            Ok(None) => {}
+            Err(err) => set_error(signals, format!("list failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Lists `target` a page at a time and returns the listed folder, or `None` when the contents
+/// pane moved to another folder before the listing finished.
+///
+/// The first page shows right away so a huge folder appears quickly; the rest replaces it once
+/// every page has arrived.
+async fn load_listing(
+    signals: ExplorerSignals,
+    explorer: &ExplorerHostService,
+    target: &str,
+) -> Result<Option<String>, String> {
+    let first = explorer.list_dir_page(target, 0, LISTING_PAGE_SIZE).await?;
+    let mut more = first.has_more();
+    let total = first.total;
+    let mut listing = ExplorerListResult {
+        cwd: first.cwd,
+        backend: first.backend,
+        permission: first.permission,
+        entries: first.entries,
+    };
+    if more {
+        signals.cwd.set(listing.cwd.clone());
+        signals.entries.set(listing.entries.clone());
+    }
+    while more {
+        set_notice(
+            signals,
+            format!(
+                "Loading {} ({} of {total} items)",
+                listing.cwd,
+                listing.entries.len()
+            ),
+        );
+        let page = explorer
+            .list_dir_page(target, listing.entries.len(), LISTING_PAGE_SIZE)
+            .await?;
+        if signals.cwd.try_get_untracked().as_deref() != Some(listing.cwd.as_str()) {
+            return Ok(None);
+        }
+        more = page.has_more();
+        listing.entries.extend(page.entries);
+    }
+    Ok(Some(apply_listing(signals, listing)))
+}
+
+/// Shows `result` in the contents pane, keeping the part of the selection still listed, and
+/// returns the listed folder.
+fn apply_listing(signals: ExplorerSignals, result: ExplorerListResult) -> String {
+    let cwd = result.cwd.clone();
+    signals.cwd.set(cwd.clone());
+    signals.entries.set(result.entries);
+    let status = signals.status.get_untracked();
+    let merged_status = ExplorerBackendStatus {
+        backend: result.backend,
+        native_supported: status.as_ref().map(|s| s.native_supported).unwrap_or(false),
+        has_native_root: status.as_ref().map(|s| s.has_native_root).unwrap_or(false),
+        permission: result.permission,
+        mounts: status
+            .as_ref()
+            .map(|s| s.mounts.clone())
+            .unwrap_or_default(),
+        root_path_hint: status.and_then(|s| s.root_path_hint),
+    };
+    signals.status.set(Some(merged_status));
+
+    signals.entries.with_untracked(|entries| {
+        signals.selection.update(|selection| {
+            selection.retain(|path| path == cwd || entries.iter().any(|entry| entry.path == path))
+        });
+    });
+    if focused_path(signals).is_none() {
+        signals.selected_metadata.set(None);
+    }
+    cwd
+}
+
+/// Watches `path` and runs `reload` once per burst of changes, such as a folder being copied in.
+///
+/// Reloads wait while the window is suspended, so a minimized window catches up once on restore.
+fn watch_path(
+    explorer: &ExplorerHostService,
+    tasks: TaskService,
+    path: &str,
+    reload: impl Fn() + 'static,
+) -> ExplorerWatch {
+    let pending = Rc::new(Cell::new(false));
+    let reload = Rc::new(reload);
+    explorer.watch(path, move |_| {
+        if pending.replace(true) {
+            return;
+        }
+        let pending = Rc::clone(&pending);
+        let reload = Rc::clone(&reload);
+        tasks.set_timeout(
+            move || {
+                pending.set(false);
+                reload();
+            },
+            WATCH_RELOAD_DELAY,
+        );
+    })
+}
+
+/// Lists the contents pane's folder again after it changed elsewhere, leaving the status line
+/// alone unless the folder is gone.
+fn reload_listing(signals: ExplorerSignals, explorer: Option<ExplorerHostService>) {
+    // The window may have closed while the reload waited.
+    let (Some(explorer), Some(target)) = (explorer, signals.cwd.try_get_untracked()) else {
+        return;
+    };
+    spawn_local(async move {
+        let result = explorer.list_dir(&target).await;
+        if signals.cwd.try_get_untracked().as_deref() != Some(target.as_str()) {
+            return;
+        }
+        match result {
             Ok(result) => {
-                let cwd = result.cwd.clone();
-                signals.cwd.set(cwd.clone());
-                signals.entries.set(result.entries);
-                let status = signals.status.get_untracked();
-                let merged_status = ExplorerBackendStatus {
-                    backend: result.backend,
-                    native_supported: status.as_ref().map(|s| s.native_supported).unwrap_or(false),
-                    has_native_root: status.as_ref().map(|s| s.has_native_root).unwrap_or(false),
-                    permission: result.permission,
-                    root_path_hint: status.and_then(|s| s.root_path_hint),
-                };
-                signals.status.set(Some(merged_status));
-
-                let current_selection = signals.selected_path.get_untracked();
-                let still_exists = current_selection.as_ref().map(|sel| {
-                    sel == &cwd
-                        || signals
-                            .entries
-                            .get_untracked()
-                            .iter()
-                            .any(|entry| &entry.path == sel)
+                apply_listing(signals, result);
+            }
+            Err(err) => set_error(signals, format!("{target} is no longer available: {err}")),
+        }
+    });
+}
+
+/// Loads the open file again after it changed elsewhere. Unsaved edits are kept, with a warning
+/// that saving will ask how to combine them with the other change.
+fn reload_editor(signals: ExplorerSignals, explorer: Option<ExplorerHostService>) {
+    let (Some(explorer), Some(Some(path))) = (explorer, signals.editor_path.try_get_untracked())
+    else {
+        return;
+    };
+    spawn_local(async move {
+        let result = explorer.read_text_file(&path).await;
+        if signals.editor_path.try_get_untracked().flatten().as_deref() != Some(path.as_str()) {
+            return;
+        }
+        let unchanged = result.as_ref().is_ok_and(|file| {
+            signals
+                .editor_text
+                .with_untracked(|text| *text == file.text)
+        });
+        match result {
+            Ok(file) if unchanged => signals.editor_base.set(Some(EditorBase::of(&file))),
+            Ok(_) if signals.editor_dirty.get_untracked() => set_notice(
+                signals,
+                format!("{path} changed elsewhere; saving asks how to combine the changes"),
+            ),
+            Ok(file) => {
+                signals.editor_base.set(Some(EditorBase::of(&file)));
+                signals.editor_text.set(file.text);
+                set_notice(signals, format!("Reloaded {path} after a change elsewhere"));
+            }
+            Err(_) => set_notice(
+                signals,
+                format!("{path} was removed elsewhere; saving recreates it"),
+            ),
+        }
+    });
+}
+
+/// Lists `path`, or the current folder, in the second pane of the dual-pane layout.
+fn refresh_second_pane(
+    signals: ExplorerSignals,
+    second: SecondPane,
+    explorer: Option<ExplorerHostService>,
+    path: Option<String>,
+) {
+    let target = normalize_path(&path.unwrap_or_else(|| second.cwd.get_untracked()));
+    let Some(explorer) = explorer else {
+        set_error(signals, "Explorer host service unavailable");
+        return;
+    };
+    spawn_local(async move {
+        match explorer.list_dir(&target).await {
+            Ok(result) => {
+                second.selection.update(|selection| {
+                    selection.retain(|path| result.entries.iter().any(|entry| entry.path == path))
                 });
-                if !matches!(still_exists, Some(true)) {
-                    signals.selected_path.set(None);
-                    signals.selected_metadata.set(None);
-                }
-                set_notice(signals, format!("Loaded {}", cwd));
+                second.cwd.set(result.cwd);
+                second.entries.set(result.entries);
             }
             Err(err) => set_error(signals, format!("list failed: {err}")),
         }
-        signals.busy.set(false);
+    });
+}
+
+/// Reads the scroll position of a listing surface and the line geometry of its rendered rows or
+/// tiles, keeping `previous` geometry when nothing is rendered.
+fn measure_listing(surface: &web_sys::HtmlElement, previous: ListingViewport) -> ListingViewport {
+    let mut viewport = ListingViewport {
+        scroll_top: f64::from(surface.scroll_top()),
+        height: f64::from(surface.client_height()),
+        ..previous
+    };
+    let Ok(items) = surface.query_selector_all("tbody tr, [data-ui-slot=\"icon-tile\"]") else {
+        return viewport;
+    };
+    let item = |index: u32| {
+        items
+            .item(index)
+            .and_then(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+    };
+    let Some(first) = item(0) else {
+        return viewport;
+    };
+    let top = first.offset_top();
+    let columns = (1..items.length())
+        .take_while(|index| item(*index).is_some_and(|item| item.offset_top() == top))
+        .count()
+        + 1;
+    let pitch = item(columns as u32)
+        .map(|next| next.offset_top() - top)
+        .unwrap_or_else(|| first.offset_height());
+    if pitch > 0 {
+        viewport.line_height = f64::from(pitch);
+        viewport.columns = columns;
+    }
+    viewport
+}
+
+/// Lists `path` for the sidebar folder tree without touching the contents pane.
+fn load_tree_folder(
+    folder_tree: RwSignal<FolderTree>,
+    explorer: Option<ExplorerHostService>,
+    logs: StoredValue<Option<LogService>>,
+    path: String,
+) {
+    let Some(explorer) = explorer else {
+        folder_tree.update(|tree| tree.set_failed(&path));
+        return;
+    };
+    spawn_local(async move {
+        let result = explorer.list_dir(&path).await;
+        folder_tree.try_update(|tree| match result {
+            Ok(listing) => tree.set_listing(&path, &listing.entries),
+            Err(err) => {
+                warn_explorer(
+                    logs,
+                    format!("explorer folder tree load failed for {path}: {err}"),
+                );
+                tree.set_failed(&path);
+            }
+        });
     });
 }
 
@@ -261,104 +798,1660 @@ fn inspect_path(signals: ExplorerSignals, explorer: Option<ExplorerHostService>,
     });
 }
 
-fn open_file(
-    signals: ExplorerSignals,
+/// Applies `apply` to the open Properties dialog if it still shows `path`, so results that
+/// arrive after the dialog was closed or moved to another entry are dropped.
+fn update_properties(
+    properties: RwSignal<Option<ExplorerProperties>>,
+    path: &str,
+    apply: impl FnOnce(&mut ExplorerProperties),
+) {
+    properties.try_update(|current| {
+        if let Some(current) = current.as_mut().filter(|current| current.path == path) {
+            apply(current);
+        }
+    });
+}
+
+fn open_properties(
+    properties: RwSignal<Option<ExplorerProperties>>,
     explorer: Option<ExplorerHostService>,
-    cache: Option<CacheHostService>,
     path: String,
 ) {
     let path = normalize_path(&path);
-    signals.busy.set(true);
+    properties.set(Some(ExplorerProperties {
+        path: path.clone(),
+        metadata: None,
+        folder_size: None,
+        checksum: ChecksumState::Idle,
+    }));
     spawn_local(async move {
         let Some(explorer) = explorer else {
-            set_error(signals, "Explorer host service unavailable");
-            signals.busy.set(false);
+            update_properties(properties, &path, |current| {
+                current.metadata = Some(Err("Explorer host service unavailable".to_string()));
+            });
             return;
         };
-        match explorer.read_text_file(&path).await {
-            Ok(file) => {
-                signals.editor_path.set(Some(file.path.clone()));
-                signals.editor_text.set(file.text.clone());
-                signals.editor_dirty.set(false);
-                signals.selected_path.set(Some(file.path.clone()));
-                signals.selected_metadata.set(Some(file.metadata.clone()));
-                set_notice(
-                    signals,
-                    format!("Opened {} ({:?})", file.path, file.metadata.backend),
-                );
-            }
-            Err(err) => {
-                let cache_key = explorer_preview_cache_key(&path);
-                let Some(cache) = cache else {
-                    set_error(signals, format!("read failed: {err}"));
-                    signals.busy.set(false);
-                    return;
-                };
-                match cache.get_text(EXPLORER_CACHE_NAME, &cache_key).await {
-                    Ok(Some(cached)) => {
-                        signals.editor_path.set(Some(path.clone()));
-                        signals.editor_text.set(cached);
-                        signals.editor_dirty.set(true);
-                        set_error(
-                            signals,
-                            format!("read failed: {err}. Loaded cached preview; save to restore"),
-                        );
-                    }
-                    Ok(None) => set_error(signals, format!("read failed: {err}")),
-                    Err(cache_err) => set_error(
-                        signals,
-                        format!("read failed: {err}; cache fallback failed: {cache_err}"),
-                    ),
-                }
-            }
+        let metadata = explorer.stat(&path).await;
+        let is_folder = matches!(&metadata, Ok(meta) if meta.kind == ExplorerEntryKind::Directory);
+        update_properties(properties, &path, |current| {
+            current.metadata = Some(metadata)
+        });
+        if is_folder {
+            let size = explorer.folder_size(&path).await;
+            update_properties(properties, &path, |current| {
+                current.folder_size = Some(size)
+            });
         }
-        signals.busy.set(false);
     });
 }
 
-fn save_editor(
-    signals: ExplorerSignals,
+fn compute_checksum(
+    properties: RwSignal<Option<ExplorerProperties>>,
     explorer: Option<ExplorerHostService>,
-    cache: Option<CacheHostService>,
 ) {
-    let Some(path) = signals.editor_path.get_untracked() else {
-        set_error(signals, "No file is open in the editor");
+    let Some(path) = properties.with_untracked(|current| {
+        current
+            .as_ref()
+            .filter(|current| !matches!(current.checksum, ChecksumState::Running { .. }))
+            .map(|current| current.path.clone())
+    }) else {
         return;
     };
-    let text = signals.editor_text.get_untracked();
-    signals.busy.set(true);
+    update_properties(properties, &path, |current| {
+        current.checksum = ChecksumState::Running { hashed: 0, size: 0 };
+    });
     spawn_local(async move {
-        let Some(explorer) = explorer else {
-            set_error(signals, "Explorer host service unavailable");
-            signals.busy.set(false);
-            return;
-        };
-        match explorer.write_text_file(&path, &text).await {
-            Ok(meta) => {
-                signals.editor_dirty.set(false);
-                signals.selected_metadata.set(Some(meta.clone()));
-                if let Some(cache) = cache {
-                    let cache_key = explorer_preview_cache_key(&path);
-                    if let Err(err) = cache.delete(EXPLORER_CACHE_NAME, &cache_key).await {
-                        logging::warn!("explorer cache delete failed: {err}");
-                    }
-                }
-                set_notice(signals, format!("Saved {}", meta.path));
-                refresh_directory(signals, Some(explorer.clone()), Some(parent_path(&path)));
+        let result = match explorer {
+            Some(explorer) => {
+                explorer
+                    .sha256_file(&path, |hashed, size| {
+                        update_properties(properties, &path, |current| {
+                            current.checksum = ChecksumState::Running { hashed, size };
+                        });
+                    })
+                    .await
             }
-            Err(err) => set_error(signals, format!("save failed: {err}")),
-        }
-        signals.busy.set(false);
+            None => Err("Explorer host service unavailable".to_string()),
+        };
+        update_properties(properties, &path, |current| {
+            current.checksum = match result {
+                Ok(digest) => ChecksumState::Done(digest),
+                Err(err) => ChecksumState::Failed(err),
+            };
+        });
     });
 }
 
-fn create_folder(
+fn open_file(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    path: String,
+) {
+    let path = normalize_path(&path);
+    if let Some((kind, _)) = media_type(&path) {
+        open_media_preview(signals, explorer, path, kind);
+        return;
+    }
+    open_text_file(signals, explorer, cache, path, true);
+}
+
+/// Reads the file-association registry. It is read on every use because Settings can change it
+/// while Explorer is open.
+async fn load_associations(
+    config: &ConfigService,
+    logs: StoredValue<Option<LogService>>,
+) -> FileAssociations {
+    match config
+        .load::<FileAssociations>(
+            FILE_ASSOCIATIONS_CONFIG_NAMESPACE,
+            FILE_ASSOCIATIONS_CONFIG_KEY,
+        )
+        .await
+    {
+        Ok(registry) => registry.unwrap_or_default(),
+        Err(err) => {
+            warn_explorer(
+                logs,
+                format!("explorer file associations load failed: {err}"),
+            );
+            FileAssociations::default()
+        }
+    }
+}
+
+/// Opens `path` in the app associated with its extension, or in Explorer's own viewers when no
+/// association applies.
+fn open_file_in_default_app(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    apps: Option<(ConfigService, LauncherService)>,
+    path: String,
+) {
+    let Some((config, launcher)) = apps else {
+        open_file(signals, explorer, cache, path);
+        return;
+    };
+    spawn_local(async move {
+        let registry = load_associations(&config, signals.logs).await;
+        match registry.lookup(&path) {
+            Some(association) => {
+                launcher.open_with(association, &path);
+                set_notice(
+                    signals,
+                    format!(
+                        "Opened {} in {}",
+                        entry_name(&path),
+                        launcher.display_name(&association.app_id)
+                    ),
+                );
+            }
+            None => open_file(signals, explorer, cache, path),
+        }
+    });
+}
+
+/// Opens `path` in the text editor of a new Explorer window.
+fn open_in_new_window(signals: ExplorerSignals, window: Option<WindowService>, path: String) {
+    let Some(window) = window else {
+        set_error(signals, "Opening new windows is unavailable");
+        return;
+    };
+    spawn_local(async move {
+        let launch_params = json!({ "path": path, "viewer": "editor" });
+        match window
+            .open_window(ApplicationId::trusted("system.explorer"), launch_params)
+            .await
+        {
+            Ok(_) => set_notice(
+                signals,
+                format!("Opened {} in a new window", entry_name(&path)),
+            ),
+            Err(err) => set_error(signals, err),
+        }
+    });
+}
+
+/// Reveals the `path` launch param, if any, by moving to its folder and selecting it, and opens it
+/// in the editor when `viewer` is `editor`.
+///
+/// Windows opened for a file, for example through a file association, start this way.
+fn open_launch_target(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    launch_params: &Value,
+) {
+    let Some(path) = launch_params
+        .get("path")
+        .and_then(Value::as_str)
+        .map(normalize_path)
+    else {
+        return;
+    };
+    signals.cwd.set(parent_path(&path));
+    signals
+        .selection
+        .set(ExplorerSelection::single(path.clone()));
+    if launch_params.get("viewer").and_then(Value::as_str) == Some("editor") {
+        open_text_file(signals, explorer, cache, path, false);
+    }
+}
+
+/// Opens `path` in `viewer` regardless of what its name or contents suggest.
+fn open_file_with(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    path: String,
+    viewer: FileViewer,
+) {
+    let path = normalize_path(&path);
+    match viewer {
+        FileViewer::Editor => open_text_file(signals, explorer, cache, path, false),
+        FileViewer::Hex => open_hex_view(signals, explorer, path),
+        FileViewer::Media => match media_type(&path) {
+            Some((kind, _)) => open_media_preview(signals, explorer, path, kind),
+            None => set_error(
+                signals,
+                format!(
+                    "{} is not an image, audio, or video file",
+                    entry_name(&path)
+                ),
+            ),
+        },
+    }
+}
+
+/// Opens `path` in the text editor. With `detect_binary`, binary files go to the hex viewer
+/// instead.
+fn open_text_file(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    path: String,
+    detect_binary: bool,
+) {
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let read = explorer.read_text_file(&path).await;
+        // Text reads decode lossily or fail on invalid UTF-8, so suspicious results are checked
+        // against the raw bytes before anything reaches the editor.
+        let suspect = read
+            .as_ref()
+            .map_or(true, |file| text_may_be_binary(&file.text));
+        if detect_binary && suspect && open_binary_file(signals, &explorer, &path).await {
+            signals.busy.set(false);
+            return;
+        }
+        match read {
+            Ok(file) => {
+                let draft = stored_draft(signals.logs, cache.as_ref(), &file.path)
+                    .await
+                    .filter(|draft| *draft != file.text);
+                set_preview(signals, None);
+                signals.editor_path.set(Some(file.path.clone()));
+                signals.editor_text.set(file.text.clone());
+                signals.editor_dirty.set(false);
+                signals.editor_base.set(Some(EditorBase::of(&file)));
+                let restorable = draft.is_some();
+                signals.editor_draft.set(draft);
+                signals
+                    .selection
+                    .set(ExplorerSelection::single(file.path.clone()));
+                signals.selected_metadata.set(Some(file.metadata.clone()));
+                if restorable {
+                    set_notice(
+                        signals,
+                        format!("Opened {}; an unsaved draft can be restored", file.path),
+                    );
+                } else {
+                    set_notice(
+                        signals,
+                        format!("Opened {} ({:?})", file.path, file.metadata.backend),
+                    );
+                }
+            }
+            Err(err) => {
+                let cache_key = explorer_preview_cache_key(&path);
+                let Some(cache) = cache else {
+                    set_error(signals, format!("read failed: {err}"));
+                    signals.busy.set(false);
+                    return;
+                };
+                match cache.get_text(EXPLORER_CACHE_NAME, &cache_key).await {
+                    Ok(Some(cached)) => {
+                        signals.editor_path.set(Some(path.clone()));
+                        signals.editor_text.set(cached);
+                        signals.editor_dirty.set(true);
+                        signals.editor_base.set(None);
+                        signals.editor_draft.set(None);
+                        set_error(
+                            signals,
+                            format!("read failed: {err}. Loaded cached preview; save to restore"),
+                        );
+                    }
+                    Ok(None) => set_error(signals, format!("read failed: {err}")),
+                    Err(cache_err) => set_error(
+                        signals,
+                        format!("read failed: {err}; cache fallback failed: {cache_err}"),
+                    ),
+                }
+            }
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Creates a blob URL for `bytes`; it must be revoked once the preview is replaced.
+fn media_object_url(bytes: &[u8], mime: &str) -> Result<String, String> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(|err| format!("{err:?}"))?;
+    web_sys::Url::create_object_url_with_blob(&blob).map_err(|err| format!("{err:?}"))
+}
+
+/// Replaces the preview, revoking the blob URL of the one it replaces.
+fn set_preview(signals: ExplorerSignals, next: Option<ExplorerPreview>) {
+    signals.preview.update(|preview| {
+        if let Some(previous) = preview.take() {
+            previous.revoke();
+        }
+        *preview = next;
+    });
+}
+
+/// Shows `path` in the media preview when its bytes start like an image, audio, or video file, or
+/// in the hex viewer when they are otherwise binary, returning whether it did.
+async fn open_binary_file(
+    signals: ExplorerSignals,
+    explorer: &ExplorerHostService,
+    path: &str,
+) -> bool {
+    let Ok(leading) = read_leading_bytes(explorer, path).await else {
+        return false;
+    };
+    let media = sniff_mime(&leading.bytes).and_then(media_kind);
+    if let Some(kind) = media.filter(|_| leading.size <= MAX_MEDIA_PREVIEW_BYTES) {
+        show_media_preview(signals, explorer, path, kind).await;
+        return true;
+    }
+    if !looks_binary(&leading.bytes) {
+        return false;
+    }
+    show_bytes(signals, leading);
+    true
+}
+
+/// The start of a file as kept for the hex viewer.
+struct LeadingBytes {
+    metadata: ExplorerMetadata,
+    bytes: Vec<u8>,
+    /// Size of the whole file.
+    size: u64,
+}
+
+/// Reads the bytes the hex viewer keeps from the start of `path`, leaving the rest unread.
+async fn read_leading_bytes(
+    explorer: &ExplorerHostService,
+    path: &str,
+) -> Result<LeadingBytes, String> {
+    let metadata = explorer.stat(path).await?;
+    let mut stream = explorer
+        .read_stream(path)
+        .with_chunk_len(MAX_HEX_VIEW_BYTES);
+    let bytes = stream.next_chunk().await.transpose()?.unwrap_or_default();
+    Ok(LeadingBytes {
+        metadata,
+        size: stream.size().unwrap_or(bytes.len() as u64),
+        bytes,
+    })
+}
+
+fn open_hex_view(signals: ExplorerSignals, explorer: Option<ExplorerHostService>, path: String) {
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        match read_leading_bytes(&explorer, &path).await {
+            Ok(leading) => show_bytes(signals, leading),
+            Err(err) => set_error(signals, format!("read failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Shows the leading bytes of a file in the hex viewer.
+fn show_bytes(signals: ExplorerSignals, leading: LeadingBytes) {
+    let path = leading.metadata.path.clone();
+    signals
+        .selection
+        .set(ExplorerSelection::single(path.clone()));
+    signals.selected_metadata.set(Some(leading.metadata));
+    set_preview(
+        signals,
+        Some(ExplorerPreview {
+            path: path.clone(),
+            content: PreviewContent::Bytes {
+                bytes: Rc::from(leading.bytes),
+                size: leading.size,
+            },
+        }),
+    );
+    set_notice(
+        signals,
+        format!("Opened {path} read-only in the hex viewer"),
+    );
+}
+
+fn open_media_preview(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    path: String,
+    kind: MediaKind,
+) {
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        show_media_preview(signals, &explorer, &path, kind).await;
+        signals.busy.set(false);
+    });
+}
+
+/// Shows `path` in the media preview. The MIME type sniffed from its bytes wins over `kind`,
+/// which was judged from the name.
+async fn show_media_preview(
+    signals: ExplorerSignals,
+    explorer: &ExplorerHostService,
+    path: &str,
+    kind: MediaKind,
+) {
+    let loaded: Result<_, String> = async {
+        let meta = explorer.stat(path).await?;
+        if meta.size.is_some_and(|size| size > MAX_MEDIA_PREVIEW_BYTES) {
+            return Err(format!(
+                "{} is larger than the {} preview limit",
+                meta.name,
+                format_bytes(MAX_MEDIA_PREVIEW_BYTES)
+            ));
+        }
+        let file = explorer.read_file_bytes(path).await?;
+        let mime = file.mime_type();
+        let url = media_object_url(&file.bytes, mime)?;
+        Ok((file, media_kind(mime).unwrap_or(kind), url))
+    }
+    .await;
+    match loaded {
+        Ok((file, kind, url)) => {
+            signals
+                .selection
+                .set(ExplorerSelection::single(file.path.clone()));
+            signals.selected_metadata.set(Some(file.metadata));
+            set_preview(
+                signals,
+                Some(ExplorerPreview {
+                    path: file.path.clone(),
+                    content: PreviewContent::Media { kind, url },
+                }),
+            );
+            set_notice(signals, format!("Previewing {}", file.path));
+        }
+        Err(err) => set_error(signals, format!("preview failed: {err}")),
+    }
+}
+
+/// Saves editor text, streaming text longer than one chunk so the host never takes it in one piece.
+async fn write_editor_text(
+    explorer: &ExplorerHostService,
+    path: &str,
+    text: &str,
+) -> Result<ExplorerMetadata, String> {
+    if text.len() <= EXPLORER_STREAM_CHUNK_BYTES {
+        return explorer.write_text_file(path, text).await;
+    }
+    let mut stream = explorer.write_stream(path);
+    for chunk in text.as_bytes().chunks(EXPLORER_STREAM_CHUNK_BYTES) {
+        stream.write_chunk(chunk).await?;
+    }
+    stream.finish().await
+}
+
+/// Reads the open file from disk when it changed since `base` was read or saved.
+///
+/// An unchanged modification time settles it without reading the file; a file that is gone or
+/// unreadable counts as unchanged, so saving recreates or reports it.
+async fn changed_on_disk(
+    explorer: &ExplorerHostService,
+    path: &str,
+    base: &EditorBase,
+) -> Option<ExplorerFileReadResult> {
+    let meta = explorer.stat(path).await.ok()?;
+    if base.modified_at_unix_ms.is_some() && meta.modified_at_unix_ms == base.modified_at_unix_ms {
+        return None;
+    }
+    let disk = explorer.read_text_file(path).await.ok()?;
+    (disk.text != base.text).then_some(disk)
+}
+
+/// Asks before a window with unsaved editor changes closes.
+struct ExplorerLifecycle {
+    signals: ExplorerSignals,
+    dialogs: DialogService,
+}
+
+impl AppLifecycleHandler for ExplorerLifecycle {
+    fn on_closing(&self) -> LifecycleFuture<'_, CloseResponse> {
+        Box::pin(async move {
+            let unsaved = self
+                .signals
+                .editor_path
+                .try_get_untracked()
+                .flatten()
+                .filter(|_| self.signals.editor_dirty.try_get_untracked() == Some(true));
+            let Some(path) = unsaved else {
+                return CloseResponse::Close;
+            };
+            let discard = self
+                .dialogs
+                .confirm_destructive(
+                    format!("Close without saving {}?", entry_name(&path)),
+                    "Your edits to this file are not saved. Cancel keeps the window open so you \
+                     can save them.",
+                    "Close Without Saving",
+                )
+                .await;
+            if discard {
+                CloseResponse::Close
+            } else {
+                CloseResponse::Cancel
+            }
+        })
+    }
+}
+
+/// Writes the editor's text to its file.
+///
+/// With `check_disk`, a file changed on disk since it was opened is not written; it goes to
+/// `conflict` so the user can reload, overwrite, or merge.
+fn save_editor(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    conflict: RwSignal<Option<ExplorerFileReadResult>>,
+    check_disk: bool,
+) {
+    let Some(path) = signals.editor_path.get_untracked() else {
+        set_error(signals, "No file is open in the editor");
+        return;
+    };
+    let text = signals.editor_text.get_untracked();
+    let base = signals.editor_base.get_untracked().filter(|_| check_disk);
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        if let Some(base) = base {
+            if let Some(disk) = changed_on_disk(&explorer, &path, &base).await {
+                conflict.set(Some(disk));
+                signals.busy.set(false);
+                return;
+            }
+        }
+        match write_editor_text(&explorer, &path, &text).await {
+            Ok(meta) => {
+                signals.editor_dirty.set(false);
+                signals.editor_base.set(Some(EditorBase {
+                    modified_at_unix_ms: meta.modified_at_unix_ms,
+                    text,
+                }));
+                signals.selected_metadata.set(Some(meta.clone()));
+                if let Some(cache) = cache {
+                    let cache_key = explorer_preview_cache_key(&path);
+                    if let Err(err) = cache.delete(EXPLORER_CACHE_NAME, &cache_key).await {
+                        warn_explorer(signals.logs, format!("explorer cache delete failed: {err}"));
+                    }
+                }
+                set_notice(signals, format!("Saved {}", meta.path));
+                refresh_directory(signals, Some(explorer.clone()), Some(parent_path(&path)));
+            }
+            Err(err) => set_error(signals, format!("save failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Compares the text of two files in the Compare dialog.
+fn compare_files(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    comparison: RwSignal<Option<ExplorerComparison>>,
+    old: String,
+    new: String,
+) {
+    let Some(explorer) = explorer else {
+        set_error(signals, "Explorer host service unavailable");
+        return;
+    };
+    signals.busy.set(true);
+    spawn_local(async move {
+        let texts = match explorer.read_text_file(&old).await {
+            Ok(old_file) => explorer
+                .read_text_file(&new)
+                .await
+                .map(|new_file| (old_file.text, new_file.text)),
+            Err(err) => Err(err),
+        };
+        match texts {
+            Ok((old_text, new_text)) => comparison.set(Some(ExplorerComparison {
+                lines: diff_lines(&old_text, &new_text),
+                old_label: old,
+                new_label: new,
+            })),
+            Err(err) => set_error(signals, format!("compare failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Compares a file with the copy kept in the content cache, such as an autosaved draft or the
+/// preview the editor falls back to when a read fails.
+fn compare_with_cache(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    comparison: RwSignal<Option<ExplorerComparison>>,
+    path: String,
+) {
+    let Some(explorer) = explorer else {
+        set_error(signals, "Explorer host service unavailable");
+        return;
+    };
+    spawn_local(async move {
+        let Some(cached) = stored_draft(signals.logs, cache.as_ref(), &path).await else {
+            set_error(signals, format!("No cached copy of {path}"));
+            return;
+        };
+        match explorer.read_text_file(&path).await {
+            Ok(file) => comparison.set(Some(ExplorerComparison {
+                lines: diff_lines(&file.text, &cached),
+                old_label: path,
+                new_label: "Cached copy".to_string(),
+            })),
+            Err(err) => set_error(signals, format!("compare failed: {err}")),
+        }
+    });
+}
+
+/// Applies the user's choice for a save held back because `disk` changed since it was opened.
+fn resolve_save_conflict(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    conflict: RwSignal<Option<ExplorerFileReadResult>>,
+    disk: ExplorerFileReadResult,
+    choice: SaveConflictChoice,
+) {
+    if signals.editor_path.get_untracked().as_deref() != Some(disk.path.as_str()) {
+        return;
+    }
+    match choice {
+        SaveConflictChoice::Reload => {
+            let path = disk.path.clone();
+            spawn_local(async move { forget_preview(signals.logs, cache.as_ref(), &path).await });
+            signals.editor_base.set(Some(EditorBase::of(&disk)));
+            signals.editor_text.set(disk.text);
+            signals.editor_dirty.set(false);
+            signals.selected_metadata.set(Some(disk.metadata));
+            set_notice(
+                signals,
+                format!("Reloaded {}; your edits were discarded", disk.path),
+            );
+        }
+        SaveConflictChoice::Overwrite => save_editor(signals, explorer, cache, conflict, false),
+        SaveConflictChoice::Merge => {
+            let base = signals
+                .editor_base
+                .get_untracked()
+                .map(|base| base.text)
+                .unwrap_or_default();
+            let merged = signals
+                .editor_text
+                .with_untracked(|ours| merge_texts(&base, ours, &disk.text));
+            // Later saves compare against the version merged in, not the one first opened.
+            signals.editor_base.set(Some(EditorBase::of(&disk)));
+            signals.editor_text.set(merged.text);
+            signals.editor_dirty.set(true);
+            match merged.conflicts {
+                0 => set_notice(
+                    signals,
+                    format!("Merged the change to {}; save to keep it", disk.path),
+                ),
+                count => set_error(
+                    signals,
+                    format!(
+                        "Merged {} with {count} conflicting region(s) marked in the editor; \
+                         resolve them and save",
+                        disk.path
+                    ),
+                ),
+            }
+        }
+    }
+}
+
+fn create_folder(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cwd: String,
+    name: String,
+) {
+    let path = join_path(&cwd, &name);
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        match explorer.create_dir(&path).await {
+            Ok(meta) => {
+                set_notice(signals, format!("Created folder {}", meta.path));
+                refresh_directory(
+                    signals,
+                    Some(explorer.clone()),
+                    Some(parent_path(&meta.path)),
+                );
+            }
+            Err(err) => set_error(signals, format!("create folder failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+fn create_file(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    cwd: String,
+    name: String,
+) {
+    let path = join_path(&cwd, &name);
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        match explorer.create_file(&path, "").await {
+            Ok(meta) => {
+                signals
+                    .selection
+                    .set(ExplorerSelection::single(meta.path.clone()));
+                signals.selected_metadata.set(Some(meta.clone()));
+                refresh_directory(
+                    signals,
+                    Some(explorer.clone()),
+                    Some(parent_path(&meta.path)),
+                );
+                open_file(signals, Some(explorer), cache, meta.path.clone());
+                set_notice(signals, format!("Created file {}", meta.path));
+            }
+            Err(err) => set_error(signals, format!("create file failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Creates "New Folder" or "New File.txt" in `cwd`, numbering the name past existing entries, and
+/// passes the new path to `on_created`.
+fn create_untitled(
     signals: ExplorerSignals,
     explorer: Option<ExplorerHostService>,
     cwd: String,
-    name: String,
+    kind: ExplorerEntryKind,
+    on_created: Callback<String>,
+) {
+    let base = match kind {
+        ExplorerEntryKind::Directory => "New Folder",
+        ExplorerEntryKind::File => "New File.txt",
+    };
+    let name = signals.entries.with_untracked(|entries| {
+        fresh_entry_name(entries.iter().map(|entry| entry.name.as_str()), base)
+    });
+    let path = join_path(&cwd, &name);
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let created = match kind {
+            ExplorerEntryKind::Directory => explorer.create_dir(&path).await,
+            ExplorerEntryKind::File => explorer.create_file(&path, "").await,
+        };
+        match created {
+            Ok(meta) => {
+                signals
+                    .selection
+                    .set(ExplorerSelection::single(meta.path.clone()));
+                signals.selected_metadata.set(Some(meta.clone()));
+                refresh_directory(signals, Some(explorer), Some(parent_path(&meta.path)));
+                on_created.call(meta.path);
+            }
+            Err(err) => set_error(signals, format!("create failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Copies files picked on the host machine into `cwd`, numbering names that are already taken.
+fn import_files(signals: ExplorerSignals, explorer: Option<ExplorerHostService>, cwd: String) {
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            return;
+        };
+        let files = match explorer.pick_import_files().await {
+            Ok(files) if files.is_empty() => return,
+            Ok(files) => files,
+            Err(err) => {
+                set_error(signals, format!("import failed: {err}"));
+                return;
+            }
+        };
+        signals.busy.set(true);
+        let mut taken = signals.entries.with_untracked(|entries| {
+            entries
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>()
+        });
+        let mut imported = Vec::new();
+        let mut failure = None;
+        for file in &files {
+            let name = fresh_entry_name(taken.iter().map(String::as_str), &file.name);
+            match explorer
+                .write_file_bytes(&join_path(&cwd, &name), &file.bytes)
+                .await
+            {
+                Ok(meta) => {
+                    imported.push(meta.path);
+                    taken.push(name);
+                }
+                Err(err) if failure.is_none() => failure = Some(format!("{}: {err}", file.name)),
+                Err(_) => {}
+            }
+        }
+        match batch_summary("Imported", imported.len(), files.len(), failure) {
+            Ok(notice) => set_notice(signals, format!("{notice} into {cwd}")),
+            Err(err) => set_error(signals, format!("import failed: {err}")),
+        }
+        if let Some(last) = imported.last() {
+            signals.selection.set(ExplorerSelection::restore(
+                imported.clone(),
+                Some(last.clone()),
+            ));
+        }
+        refresh_directory(signals, Some(explorer), Some(cwd));
+        signals.busy.set(false);
+    });
+}
+
+/// Selected entries that are files, in selection order.
+fn selected_files(signals: ExplorerSignals) -> Vec<String> {
+    let selected = selected_paths(signals);
+    signals.entries.with_untracked(|entries| {
+        selected
+            .into_iter()
+            .filter(|path| {
+                entries
+                    .iter()
+                    .any(|entry| &entry.path == path && entry.kind == ExplorerEntryKind::File)
+            })
+            .collect()
+    })
+}
+
+/// Saves a copy of each selected file to the host machine.
+fn download_selected(signals: ExplorerSignals, explorer: Option<ExplorerHostService>) {
+    let paths = selected_files(signals);
+    if paths.is_empty() {
+        set_error(signals, "Select a file to download");
+        return;
+    }
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            return;
+        };
+        let mut done = 0;
+        let mut failure = None;
+        for path in &paths {
+            let saved = match explorer.read_file_bytes(path).await {
+                Ok(file) => explorer.save_download(&entry_name(path), &file.bytes).await,
+                Err(err) => Err(err),
+            };
+            match saved {
+                Ok(()) => done += 1,
+                Err(err) if failure.is_none() => {
+                    failure = Some(format!("{}: {err}", entry_name(path)))
+                }
+                Err(_) => {}
+            }
+        }
+        match batch_summary("Downloaded", done, paths.len(), failure) {
+            Ok(notice) => set_notice(signals, notice),
+            Err(err) => set_error(signals, format!("download failed: {err}")),
+        }
+    });
+}
+
+/// Renames each `(from, to)` pair in turn; whatever completed can be reversed with [`undo_last`].
+fn bulk_rename_entries(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    renames: Vec<(String, String)>,
+) {
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let mut completed = Vec::new();
+        let mut failure = None;
+        for (from, to) in &renames {
+            let result = if explorer.stat(to).await.is_ok() {
+                Err(format!("{} already exists", entry_name(to)))
+            } else {
+                explorer.rename(from, to).await.map(|_| ())
+            };
+            match result {
+                Ok(()) => {
+                    forget_preview(signals.logs, cache.as_ref(), from).await;
+                    apply_rename(signals, from, to);
+                    completed.push((from.clone(), to.clone()));
+                }
+                Err(err) if failure.is_none() => {
+                    failure = Some(format!("{}: {err}", entry_name(from)));
+                }
+                Err(_) => {}
+            }
+        }
+        match batch_summary("Renamed", completed.len(), renames.len(), failure) {
+            Ok(notice) => set_notice(signals, notice),
+            Err(err) => set_error(signals, format!("rename failed: {err}")),
+        }
+        if !completed.is_empty() {
+            signals.undo.set(Some(ExplorerUndo::Transfer {
+                kind: TransferKind::Move,
+                completed,
+            }));
+        }
+        refresh_directory(signals, Some(explorer), None);
+        signals.busy.set(false);
+    });
+}
+
+/// Packs `path` into a ZIP archive and saves it to the host machine.
+fn export_archive(signals: ExplorerSignals, explorer: Option<ExplorerHostService>, path: String) {
+    let name = match entry_name(&path).as_str() {
+        "/" => "Files".to_string(),
+        name => name.to_string(),
+    };
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let exported = explorer
+            .export_zip(&path, |done, total| {
+                set_notice(
+                    signals,
+                    format!("Compressing {name}: {done} of {total} files"),
+                )
+            })
+            .await;
+        let file_name = format!("{name}.zip");
+        let saved = match exported {
+            Ok(bytes) => explorer
+                .save_download(&file_name, &bytes)
+                .await
+                .map(|()| bytes.len() as u64),
+            Err(err) => Err(err),
+        };
+        match saved {
+            Ok(size) => set_notice(
+                signals,
+                format!("Exported {file_name} ({})", format_bytes(size)),
+            ),
+            Err(err) => set_error(signals, format!("export failed: {err}")),
+        }
+        signals.busy.set(false);
+    });
+}
+
+/// Extracts `archives` into `dest` one after another, then selects what they produced.
+fn extract_archives(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    dest: String,
+    archives: Vec<ExplorerImportFile>,
+    conflict: ExplorerArchiveConflict,
+) {
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let mut roots = Vec::new();
+        let mut files = 0;
+        let mut skipped = 0;
+        let mut failure = None;
+        for archive in &archives {
+            let extracted = explorer
+                .import_zip(&dest, &archive.bytes, conflict, |done, total| {
+                    set_notice(
+                        signals,
+                        format!("Extracting {}: {done} of {total} files", archive.name),
+                    )
+                })
+                .await;
+            match extracted {
+                Ok(outcome) => {
+                    files += outcome.files;
+                    skipped += outcome.skipped.len();
+                    roots.extend(outcome.roots);
+                }
+                Err(err) => {
+                    failure = Some(format!("{}: {err}", archive.name));
+                    break;
+                }
+            }
+        }
+        match failure {
+            Some(err) => set_error(signals, format!("extract failed: {err}")),
+            None if skipped > 0 => set_notice(
+                signals,
+                format!("Extracted {files} files; skipped {skipped} that already existed"),
+            ),
+            None => set_notice(signals, format!("Extracted {files} files")),
+        }
+        if let Some(last) = roots.last().cloned() {
+            signals
+                .selection
+                .set(ExplorerSelection::restore(roots, Some(last)));
+        }
+        refresh_directory(signals, Some(explorer), Some(dest));
+        signals.busy.set(false);
+    });
+}
+
+/// Extracts `archives` into the current folder `dest`, first asking what to do when their
+/// top-level names are already used there.
+fn start_extract(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    pending: RwSignal<Option<PendingExtract>>,
+    dest: String,
+    archives: Vec<ExplorerImportFile>,
+) {
+    let mut conflicts = Vec::new();
+    for archive in &archives {
+        let names = match archive_top_level_names(&archive.bytes) {
+            Ok(names) => names,
+            Err(err) => {
+                set_error(signals, format!("{}: {err}", archive.name));
+                return;
+            }
+        };
+        signals.entries.with_untracked(|entries| {
+            conflicts.extend(names.into_iter().filter(|name| {
+                entries
+                    .iter()
+                    .any(|entry| entry.name.eq_ignore_ascii_case(name))
+            }))
+        });
+    }
+    if conflicts.is_empty() {
+        extract_archives(
+            signals,
+            explorer,
+            dest,
+            archives,
+            ExplorerArchiveConflict::KeepBoth,
+        );
+    } else {
+        pending.set(Some(PendingExtract {
+            dest,
+            archives,
+            conflicts,
+        }));
+    }
+}
+
+/// Extracts ZIP archives picked on the host machine into the current folder.
+fn import_archives(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    pending: RwSignal<Option<PendingExtract>>,
+    dest: String,
+) {
+    spawn_local(async move {
+        let Some(host) = explorer.clone() else {
+            set_error(signals, "Explorer host service unavailable");
+            return;
+        };
+        match host.pick_import_files().await {
+            Ok(archives) if archives.is_empty() => {}
+            Ok(archives) => start_extract(signals, explorer, pending, dest, archives),
+            Err(err) => set_error(signals, format!("import failed: {err}")),
+        }
+    });
+}
+
+/// Extracts the ZIP archive at `path` next to itself.
+fn extract_entry(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    pending: RwSignal<Option<PendingExtract>>,
+    path: String,
+) {
+    spawn_local(async move {
+        let Some(host) = explorer.clone() else {
+            set_error(signals, "Explorer host service unavailable");
+            return;
+        };
+        match host.read_file_bytes(&path).await {
+            Ok(file) => start_extract(
+                signals,
+                explorer,
+                pending,
+                parent_path(&path),
+                vec![ExplorerImportFile {
+                    name: entry_name(&path),
+                    bytes: file.bytes,
+                }],
+            ),
+            Err(err) => set_error(signals, format!("extract failed: {err}")),
+        }
+    });
+}
+
+/// Closes the editor and media preview when their file is `path` or lies inside it.
+fn close_editor_within(signals: ExplorerSignals, path: &str) {
+    let open = signals.editor_path.get_untracked();
+    if open.is_some_and(|open| rebase_path(&open, path, path).is_some()) {
+        signals.editor_path.set(None);
+        signals.editor_text.set(String::new());
+        signals.editor_dirty.set(false);
+        signals.editor_base.set(None);
+        signals.editor_draft.set(None);
+    }
+    let previewed = signals.preview.with_untracked(|preview| {
+        preview
+            .as_ref()
+            .is_some_and(|preview| rebase_path(&preview.path, path, path).is_some())
+    });
+    if previewed {
+        set_preview(signals, None);
+    }
+}
+
+async fn forget_preview(
+    logs: StoredValue<Option<LogService>>,
+    cache: Option<&CacheHostService>,
+    path: &str,
+) {
+    if let Some(cache) = cache {
+        let cache_key = explorer_preview_cache_key(path);
+        if let Err(err) = cache.delete(EXPLORER_CACHE_NAME, &cache_key).await {
+            warn_explorer(logs, format!("explorer cache delete failed: {err}"));
+        }
+    }
+}
+
+/// Text autosaved for `path` by [`store_draft`] and not yet saved or discarded.
+async fn stored_draft(
+    logs: StoredValue<Option<LogService>>,
+    cache: Option<&CacheHostService>,
+    path: &str,
+) -> Option<String> {
+    let cache_key = explorer_preview_cache_key(path);
+    match cache?.get_text(EXPLORER_CACHE_NAME, &cache_key).await {
+        Ok(draft) => draft,
+        Err(err) => {
+            warn_explorer(logs, format!("explorer draft lookup failed: {err}"));
+            None
+        }
+    }
+}
+
+/// Writes the editor's unsaved text to the content cache under the file's preview key, where
+/// opening the file again, or a failed read, finds it.
+fn store_draft(signals: ExplorerSignals, cache: Option<CacheHostService>) {
+    // The window may have closed while the autosave waited.
+    let (Some(cache), Some(Some(path)), Some(true)) = (
+        cache,
+        signals.editor_path.try_get_untracked(),
+        signals.editor_dirty.try_get_untracked(),
+    ) else {
+        return;
+    };
+    let text = signals.editor_text.get_untracked();
+    spawn_local(async move {
+        let cache_key = explorer_preview_cache_key(&path);
+        if let Err(err) = cache.put_text(EXPLORER_CACHE_NAME, &cache_key, &text).await {
+            warn_explorer(
+                signals.logs,
+                format!("explorer draft autosave failed: {err}"),
+            );
+        }
+    });
+}
+
+/// Describes a batch outcome, naming the first failure when some entries did not complete.
+fn batch_summary(
+    verb: &str,
+    done: usize,
+    total: usize,
+    failure: Option<String>,
+) -> Result<String, String> {
+    match failure {
+        None if total == 1 => Ok(format!("{verb} 1 item")),
+        None => Ok(format!("{verb} {total} items")),
+        Some(err) => Err(format!("{done} of {total} done; {err}")),
+    }
+}
+
+/// Moves the selected entries to the trash, offering to undo afterwards.
+fn delete_selected(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+) {
+    let paths = selected_paths(signals);
+    if paths.is_empty() {
+        set_error(signals, "Select a file or folder to delete");
+        return;
+    }
+    if paths.iter().any(|path| path == "/") {
+        set_error(signals, "Cannot delete the root directory");
+        return;
+    }
+    if paths.iter().any(|path| is_trash_path(path)) {
+        set_error(
+            signals,
+            "Items in the trash are restored or deleted from the Trash view",
+        );
+        return;
+    }
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let mut trashed = Vec::new();
+        let mut failure = None;
+        for path in &paths {
+            match explorer.move_to_trash(path).await {
+                Ok(entry) => {
+                    close_editor_within(signals, path);
+                    forget_preview(signals.logs, cache.as_ref(), path).await;
+                    signals
+                        .selection
+                        .update(|selection| selection.retain(|selected| selected != path));
+                    trashed.push(entry);
+                }
+                Err(err) if failure.is_none() => {
+                    failure = Some(format!("{}: {err}", entry_name(path)));
+                }
+                Err(_) => {}
+            }
+        }
+        if focused_path(signals).is_none() {
+            signals.selected_metadata.set(None);
+        }
+        match batch_summary("Moved", trashed.len(), paths.len(), failure) {
+            Ok(notice) => set_notice(signals, format!("{notice} to the trash")),
+            Err(err) => set_error(signals, format!("delete failed: {err}")),
+        }
+        if !trashed.is_empty() {
+            signals.undo.set(Some(ExplorerUndo::Trash(trashed)));
+        }
+        refresh_trash(signals, Some(explorer.clone()));
+        refresh_directory(signals, Some(explorer), None);
+        signals.busy.set(false);
+    });
+}
+
+/// Reloads the Trash view's listing.
+fn refresh_trash(signals: ExplorerSignals, explorer: Option<ExplorerHostService>) {
+    let Some(explorer) = explorer else {
+        return;
+    };
+    spawn_local(async move {
+        match explorer.list_trash().await {
+            Ok(entries) => signals.trash.set(entries),
+            Err(err) => set_error(signals, format!("trash listing failed: {err}")),
+        }
+    });
+}
+
+/// Measures storage usage and quota, then the size of each top-level folder, for the Storage panel.
+fn refresh_storage(
+    explorer: Option<ExplorerHostService>,
+    storage: RwSignal<Option<ExplorerStorageReport>>,
+) {
+    let Some(explorer) = explorer else {
+        storage.set(Some(ExplorerStorageReport {
+            usage: Some(Err("Explorer host service unavailable".to_string())),
+            folders: None,
+        }));
+        return;
+    };
+    storage.set(Some(ExplorerStorageReport::default()));
+    spawn_local(async move {
+        let usage = explorer.usage().await;
+        if storage
+            .try_update(|report| {
+                if let Some(report) = report {
+                    report.usage = Some(usage);
+                }
+            })
+            .is_none()
+        {
+            return;
+        }
+        let folders = explorer.top_level_folder_sizes().await;
+        storage.try_update(|report| {
+            if let Some(report) = report {
+                report.folders = Some(folders);
+            }
+        });
+    });
+}
+
+/// Asks the host to keep Explorer's files through storage pressure and reports its answer.
+fn request_persistent_storage(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    storage: RwSignal<Option<ExplorerStorageReport>>,
+) {
+    let Some(explorer) = explorer else {
+        set_error(signals, "Explorer host service unavailable");
+        return;
+    };
+    spawn_local(async move {
+        match explorer.request_persistent_storage().await {
+            Ok(persisted) => {
+                storage.try_update(|report| {
+                    if let Some(Some(Ok(usage))) = report.as_mut().map(|report| &mut report.usage) {
+                        usage.persisted = Some(persisted);
+                    }
+                });
+                if persisted {
+                    set_notice(signals, "Stored files are now kept when space runs low");
+                } else {
+                    set_notice(signals, "The browser declined to make storage persistent");
+                }
+            }
+            Err(err) => set_error(signals, format!("persistent storage request failed: {err}")),
+        }
+    });
+}
+
+/// Usage measured for the Storage panel, once it is known.
+fn storage_usage(storage: RwSignal<Option<ExplorerStorageReport>>) -> Option<ExplorerStorageUsage> {
+    storage.with(|report| match report.as_ref()?.usage {
+        Some(Ok(usage)) => Some(usage),
+        _ => None,
+    })
+}
+
+/// Usage against quota, with a meter when the host reports both, and whether storage persists.
+fn storage_usage_view(usage: ExplorerStorageUsage) -> View {
+    let summary = match (usage.usage_bytes, usage.quota_bytes) {
+        (Some(used), Some(quota)) => format!(
+            "{} of {} used ({:.0}%)",
+            format_bytes(used),
+            format_bytes(quota),
+            usage.used_fraction().unwrap_or(0.0) * 100.0
+        ),
+        (Some(used), None) => format!("{} used", format_bytes(used)),
+        (None, _) => "Usage not reported by this host".to_string(),
+    };
+    let persistence = match usage.persisted {
+        Some(true) => "Stored files are kept when space runs low.",
+        Some(false) => "The browser may clear stored files when space runs low.",
+        None => "",
+    };
+    view! {
+        <Text>{summary}</Text>
+        {usage.used_fraction().map(|fraction| {
+            view! { <ProgressBar max=1000 value=(fraction * 1000.0).round() as u16 /> }
+        })}
+        <Text tone=TextTone::Secondary>{persistence}</Text>
+    }
+    .into_view()
+}
+
+/// Puts trashed entries back where they were deleted from, selecting what came back.
+fn restore_trashed(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    entries: Vec<ExplorerTrashEntry>,
+) {
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let mut restored = Vec::new();
+        let mut failure = None;
+        for entry in &entries {
+            match explorer.restore_from_trash(&entry.id).await {
+                Ok(meta) => restored.push(meta.path),
+                Err(err) if failure.is_none() => {
+                    failure = Some(format!("{}: {err}", entry.name));
+                }
+                Err(_) => {}
+            }
+        }
+        match batch_summary("Restored", restored.len(), entries.len(), failure) {
+            Ok(notice) => set_notice(signals, notice),
+            Err(err) => set_error(signals, format!("restore failed: {err}")),
+        }
+        if let Some(focus) = restored.last().cloned() {
+            signals
+                .selection
+                .set(ExplorerSelection::restore(restored, Some(focus)));
+        }
+        refresh_trash(signals, Some(explorer.clone()));
+        refresh_directory(signals, Some(explorer), None);
+        signals.busy.set(false);
+    });
+}
+
+/// Permanently deletes one trashed entry, or the whole trash when `id` is `None`.
+fn purge_trash(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    dialogs: Option<DialogService>,
+    entry: Option<ExplorerTrashEntry>,
+) {
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            return;
+        };
+        let (title, message) = match &entry {
+            Some(entry) => (
+                "Delete permanently?",
+                format!("\"{}\" will be deleted permanently.", entry.name),
+            ),
+            None => (
+                "Empty Trash?",
+                "Everything in the Trash will be deleted permanently.".to_string(),
+            ),
+        };
+        if let Some(dialogs) = dialogs {
+            if !dialogs.confirm_destructive(title, message, "Delete").await {
+                return;
+            }
+        }
+        signals.busy.set(true);
+        let result = match &entry {
+            Some(entry) => explorer.delete_from_trash(&entry.id).await.map(|()| 1),
+            None => explorer.empty_trash().await,
+        };
+        match result {
+            Ok(1) => set_notice(signals, "Permanently deleted 1 item"),
+            Ok(count) => set_notice(signals, format!("Permanently deleted {count} items")),
+            Err(err) => set_error(signals, format!("permanent delete failed: {err}")),
+        }
+        refresh_trash(signals, Some(explorer));
+        signals.busy.set(false);
+    });
+}
+
+/// Copies a file or folder tree to `to`, which must not exist yet.
+async fn copy_entry(explorer: &ExplorerHostService, from: &str, to: &str) -> Result<(), String> {
+    let mut pending = vec![(from.to_string(), to.to_string())];
+    while let Some((from, to)) = pending.pop() {
+        match explorer.stat(&from).await?.kind {
+            ExplorerEntryKind::File => {
+                let file = explorer.read_text_file(&from).await?;
+                explorer.create_file(&to, &file.text).await?;
+            }
+            ExplorerEntryKind::Directory => {
+                explorer.create_dir(&to).await?;
+                for entry in explorer.list_dir(&from).await?.entries {
+                    let target = join_path(&to, &entry.name);
+                    pending.push((entry.path, target));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Points the selection and open editor at `to` wherever they referenced `from`.
+fn follow_move(signals: ExplorerSignals, from: &str, to: &str) {
+    signals.editor_path.update(|open| {
+        if let Some(moved) = open.as_deref().and_then(|open| rebase_path(open, from, to)) {
+            *open = Some(moved);
+        }
+    });
+    signals
+        .selection
+        .update(|selection| selection.rebase(|path| rebase_path(path, from, to)));
+}
+
+fn selected_paths(signals: ExplorerSignals) -> Vec<String> {
+    signals
+        .selection
+        .with_untracked(|selection| selection.paths().to_vec())
+}
+
+/// Moves or copies `paths` into `destination`.
+///
+/// Entries whose target already exists are reported instead of replaced. Moved entries carry the
+/// selection and open editor along with them, and whatever completed can be reversed with
+/// [`undo_last`].
+fn transfer_entries(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    paths: Vec<String>,
+    destination: String,
+    kind: TransferKind,
+) {
+    if paths.is_empty() {
+        set_error(signals, "Select files or folders first");
+        return;
+    }
+    if destination.trim().is_empty() {
+        set_error(signals, "Enter a destination folder");
+        return;
+    }
+    let targets = match transfer_targets(&paths, &destination) {
+        Ok(targets) => targets,
+        Err(err) => {
+            set_error(signals, err);
+            return;
+        }
+    };
+    let destination = normalize_path(&destination);
+    signals.busy.set(true);
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            signals.busy.set(false);
+            return;
+        };
+        let mut completed = Vec::new();
+        let mut failure = None;
+        for (from, to) in &targets {
+            let result = if from == to {
+                Err(format!("already in {destination}"))
+            } else if explorer.stat(to).await.is_ok() {
+                Err(format!("{to} already exists"))
+            } else {
+                match kind {
+                    TransferKind::Move => explorer.rename(from, to).await.map(|_| ()),
+                    TransferKind::Copy => copy_entry(&explorer, from, to).await,
+                }
+            };
+            match result {
+                Ok(()) => {
+                    if kind == TransferKind::Move {
+                        forget_preview(signals.logs, cache.as_ref(), from).await;
+                        follow_move(signals, from, to);
+                    }
+                    completed.push((from.clone(), to.clone()));
+                }
+                Err(err) if failure.is_none() => {
+                    failure = Some(format!("{}: {err}", entry_name(from)));
+                }
+                Err(_) => {}
+            }
+        }
+        let verb = match kind {
+            TransferKind::Move => "Moved",
+            TransferKind::Copy => "Copied",
+        };
+        match batch_summary(verb, completed.len(), targets.len(), failure) {
+            Ok(notice) => set_notice(signals, format!("{notice} to {destination}")),
+            Err(err) => set_error(signals, format!("{} failed: {err}", verb.to_lowercase())),
+        }
+        if !completed.is_empty() {
+            signals
+                .undo
+                .set(Some(ExplorerUndo::Transfer { kind, completed }));
+        }
+        refresh_directory(signals, Some(explorer), None);
+        signals.busy.set(false);
+    });
+}
+
+/// Reverses the operation behind the current Undo notice.
+fn undo_last(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+) {
+    let Some(undo) = signals.undo.get_untracked() else {
+        return;
+    };
+    signals.undo.set(None);
+    match undo {
+        ExplorerUndo::Transfer { kind, completed } => {
+            undo_transfer(signals, explorer, cache, kind, completed)
+        }
+        ExplorerUndo::Trash(entries) => restore_trashed(signals, explorer, entries),
+    }
+}
+
+/// Reverses a move or copy: moved entries go back where they came from and copies are deleted.
+/// A move is not undone over an entry that has since taken its original place.
+fn undo_transfer(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    kind: TransferKind,
+    completed: Vec<(String, String)>,
 ) {
-    let path = join_path(&cwd, &name);
     signals.busy.set(true);
     spawn_local(async move {
         let Some(explorer) = explorer else {
@@ -366,96 +2459,1342 @@ fn create_folder(
             signals.busy.set(false);
             return;
         };
-        match explorer.create_dir(&path).await {
-            Ok(meta) => {
-                set_notice(signals, format!("Created folder {}", meta.path));
-                refresh_directory(
-                    signals,
-                    Some(explorer.clone()),
-                    Some(parent_path(&meta.path)),
-                );
+        let mut done = 0;
+        let mut failure = None;
+        for (from, to) in completed.iter().rev() {
+            let result = match kind {
+                TransferKind::Move if explorer.stat(from).await.is_ok() => {
+                    Err(format!("{from} already exists"))
+                }
+                TransferKind::Move => explorer.rename(to, from).await.map(|_| ()),
+                TransferKind::Copy => explorer.delete(to, true).await,
+            };
+            match result {
+                Ok(()) => {
+                    done += 1;
+                    forget_preview(signals.logs, cache.as_ref(), to).await;
+                    match kind {
+                        TransferKind::Move => follow_move(signals, to, from),
+                        TransferKind::Copy => {
+                            close_editor_within(signals, to);
+                            signals
+                                .selection
+                                .update(|selection| selection.retain(|path| path != to));
+                        }
+                    }
+                }
+                Err(err) if failure.is_none() => {
+                    failure = Some(format!("{}: {err}", entry_name(to)));
+                }
+                Err(_) => {}
+            }
+        }
+        let verb = match kind {
+            TransferKind::Move => "Moved back",
+            TransferKind::Copy => "Removed copies of",
+        };
+        match batch_summary(verb, done, completed.len(), failure) {
+            Ok(notice) => set_notice(signals, notice),
+            Err(err) => set_error(signals, format!("undo failed: {err}")),
+        }
+        refresh_directory(signals, Some(explorer), None);
+        signals.busy.set(false);
+    });
+}
+
+/// Walks `root` breadth-first, streaming entries that match `query` into the search results.
+///
+/// Unreadable folders are skipped and counted. Hidden entries are neither matched nor descended
+/// into unless `show_hidden` is set.
+fn run_search(
+    search: ExplorerSearch,
+    explorer: Option<ExplorerHostService>,
+    root: String,
+    query: SearchQuery,
+    show_hidden: bool,
+) {
+    let generation = search.generation.get_untracked() + 1;
+    search.generation.set(generation);
+    search.results.set(Vec::new());
+    let Some(explorer) = explorer else {
+        search
+            .status
+            .set(Some("Explorer host service unavailable".to_string()));
+        return;
+    };
+    search.running.set(true);
+    search.status.set(Some(format!("Searching {root}...")));
+    spawn_local(async move {
+        // The window may close mid-walk, which disposes the signals.
+        let current = move || search.generation.try_get_untracked() == Some(generation);
+        let mut pending = VecDeque::from([root.clone()]);
+        let mut found = 0;
+        let mut unreadable = 0;
+        'walk: while let Some(dir) = pending.pop_front() {
+            let listing = explorer.list_dir(&dir).await;
+            if !current() {
+                return;
+            }
+            let Ok(listing) = listing else {
+                unreadable += 1;
+                continue;
+            };
+            for entry in listing.entries {
+                if !show_hidden && entry.name.starts_with('.') {
+                    continue;
+                }
+                if entry.kind == ExplorerEntryKind::Directory {
+                    pending.push_back(entry.path.clone());
+                }
+                if !query.matches_name(&entry.name) {
+                    continue;
+                }
+                let snippet = if !query.needs_content() {
+                    None
+                } else if entry.kind == ExplorerEntryKind::Directory
+                    || entry
+                        .size
+                        .is_some_and(|size| size > MAX_CONTENT_SEARCH_BYTES)
+                {
+                    continue;
+                } else {
+                    let file = explorer.read_text_file(&entry.path).await;
+                    if !current() {
+                        return;
+                    }
+                    match file.ok().and_then(|file| query.content_snippet(&file.text)) {
+                        Some(snippet) => Some(snippet),
+                        None => continue,
+                    }
+                };
+                search.results.update(|results| {
+                    results.push(SearchHit {
+                        path: entry.path,
+                        name: entry.name,
+                        kind: entry.kind,
+                        snippet,
+                    })
+                });
+                found += 1;
+                if found >= MAX_SEARCH_RESULTS {
+                    break 'walk;
+                }
+            }
+        }
+        let mut summary = match found {
+            1 => format!("1 match under {root}"),
+            found => format!("{found} matches under {root}"),
+        };
+        if found >= MAX_SEARCH_RESULTS {
+            summary.push_str(&format!(" (stopped at {MAX_SEARCH_RESULTS})"));
+        }
+        if unreadable > 0 {
+            summary.push_str(&format!("; {unreadable} folder(s) could not be read"));
+        }
+        search.status.set(Some(summary));
+        search.running.set(false);
+    });
+}
+
+fn cancel_search(search: ExplorerSearch) {
+    if search.running.get_untracked() {
+        search.generation.update(|generation| *generation += 1);
+        search.running.set(false);
+        search.status.set(Some("Search cancelled".to_string()));
+    }
+}
+
+/// Shows `path` in its parent folder, selected and inspected.
+fn reveal_path(signals: ExplorerSignals, explorer: Option<ExplorerHostService>, path: String) {
+    signals
+        .selection
+        .set(ExplorerSelection::single(path.clone()));
+    refresh_directory(signals, explorer.clone(), Some(parent_path(&path)));
+    inspect_path(signals, explorer, path);
+}
+
+/// Points the listing, selection, open editor, and preview at `to` wherever they referenced
+/// `from`.
+fn apply_rename(signals: ExplorerSignals, from: &str, to: &str) {
+    signals.entries.update(|entries| {
+        for entry in entries.iter_mut().filter(|entry| entry.path == from) {
+            entry.path = to.to_string();
+            entry.name = entry_name(to);
+        }
+    });
+    signals
+        .selection
+        .update(|selection| selection.rebase(|path| rebase_path(path, from, to)));
+    signals.editor_path.update(|path| {
+        if let Some(moved) = path.as_deref().and_then(|path| rebase_path(path, from, to)) {
+            *path = Some(moved);
+        }
+    });
+    signals.preview.update(|preview| {
+        if let Some(preview) = preview.as_mut() {
+            if let Some(moved) = rebase_path(&preview.path, from, to) {
+                preview.path = moved;
+            }
+        }
+    });
+    signals.selected_metadata.update(|meta| {
+        if let Some(meta) = meta.as_mut() {
+            if let Some(moved) = rebase_path(&meta.path, from, to) {
+                meta.name = entry_name(&moved);
+                meta.path = moved;
+            }
+        }
+    });
+}
+
+/// Renames `from` to `name` in place, updating the listing before the backend confirms.
+///
+/// Returns `false` without touching the backend when the name is invalid or already taken in the
+/// listing, so the caller can keep its rename field open. A backend failure rolls the listing
+/// back.
+fn rename_entry(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    cache: Option<CacheHostService>,
+    from: String,
+    name: String,
+) -> bool {
+    if from == "/" {
+        set_error(signals, "Cannot rename the root directory");
+        return false;
+    }
+    let to = match rename_target(&from, &name) {
+        Ok(to) => to,
+        Err(err) => {
+            set_error(signals, err);
+            return false;
+        }
+    };
+    if to == from {
+        return true;
+    }
+    if signals
+        .entries
+        .get_untracked()
+        .iter()
+        .any(|entry| entry.path == to)
+    {
+        set_error(signals, format!("{} already exists", entry_name(&to)));
+        return false;
+    }
+    let Some(explorer) = explorer else {
+        set_error(signals, "Explorer host service unavailable");
+        return false;
+    };
+
+    apply_rename(signals, &from, &to);
+    signals.busy.set(true);
+    spawn_local(async move {
+        match explorer.rename(&from, &to).await {
+            Ok(meta) => {
+                if focused_path(signals).as_deref() == Some(meta.path.as_str()) {
+                    signals.selected_metadata.set(Some(meta.clone()));
+                }
+                if let Some(cache) = cache {
+                    let cache_key = explorer_preview_cache_key(&from);
+                    if let Err(err) = cache.delete(EXPLORER_CACHE_NAME, &cache_key).await {
+                        warn_explorer(signals.logs, format!("explorer cache delete failed: {err}"));
+                    }
+                }
+                set_notice(
+                    signals,
+                    format!("Renamed {} to {}", entry_name(&from), meta.name),
+                );
+                refresh_directory(signals, Some(explorer), Some(parent_path(&meta.path)));
+            }
+            Err(err) => {
+                apply_rename(signals, &to, &from);
+                set_error(signals, format!("rename failed: {err}"));
+            }
+        }
+        signals.busy.set(false);
+    });
+    true
+}
+
+#[component]
+/// Inline name editor that commits on Enter or blur and cancels on Escape.
+fn RenameField(
+    draft: RwSignal<String>,
+    on_commit: Callback<()>,
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let input = create_node_ref::<html::Input>();
+    request_animation_frame(move || {
+        if let Some(input) = input.get_untracked() {
+            let _ = input.focus();
+            input.select();
+        }
+    });
+    view! {
+        <span
+            on:mousedown=move |ev: ev::MouseEvent| ev.stop_propagation()
+            on:dblclick=move |ev: ev::MouseEvent| ev.stop_propagation()
+        >
+            <TextField
+                aria_label="New name"
+                node_ref=input
+                spellcheck=false
+                value=Signal::derive(move || draft.get())
+                on_input=Callback::new(move |ev| draft.set(event_target_value(&ev)))
+                on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+                    ev.stop_propagation();
+                    match ev.key().as_str() {
+                        "Enter" => {
+                            ev.prevent_default();
+                            on_commit.call(());
+                        }
+                        "Escape" => {
+                            ev.prevent_default();
+                            on_cancel.call(());
+                        }
+                        _ => {}
+                    }
+                })
+                on_blur=Callback::new(move |_| on_commit.call(()))
+            />
+        </span>
+    }
+}
+
+#[component]
+/// Free-text path input that completes folder names and only navigates to folders that exist.
+///
+/// Paths that do not start at the root are resolved against the folder the field opened in. Tab
+/// completes the typed name, the arrow keys pick a suggestion, Enter navigates, and Escape or
+/// leaving the field cancels.
+fn PathField(
+    initial: String,
+    explorer: Option<ExplorerHostService>,
+    show_hidden: Signal<bool>,
+    on_navigate: Callback<String>,
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let explorer = store_value(explorer);
+    let cwd = store_value(initial.clone());
+    let draft = create_rw_signal(initial);
+    let suggestions = create_rw_signal(Vec::<String>::new());
+    let active = create_rw_signal::<Option<usize>>(None);
+    let error = create_rw_signal::<Option<String>>(None);
+    let input = create_node_ref::<html::Input>();
+    request_animation_frame(move || {
+        if let Some(input) = input.get_untracked() {
+            let _ = input.focus();
+            input.select();
+        }
+    });
+
+    let load_suggestions = move || {
+        let typed = draft.get_untracked();
+        let Some(explorer) = explorer.get_value() else {
+            return;
+        };
+        spawn_local(async move {
+            let (folder, partial) = completion_base(&cwd.get_value(), &typed);
+            let listing = explorer.list_dir(&folder).await;
+            // Drop results for text that has since changed or a field that has closed.
+            if draft.try_get_untracked().as_deref() != Some(typed.as_str()) {
+                return;
+            }
+            let names = listing
+                .map(|listing| {
+                    folder_completions(&listing.entries, &partial, show_hidden.get_untracked())
+                })
+                .unwrap_or_default();
+            suggestions.set(names);
+            active.set(None);
+        });
+    };
+    let suggestion_path = move |index: usize| {
+        let (folder, _) = completion_base(&cwd.get_value(), &draft.get_untracked());
+        suggestions.with_untracked(|names| names.get(index).map(|name| join_path(&folder, name)))
+    };
+    let accept = move |path: String| {
+        draft.set(format!("{}/", path.trim_end_matches('/')));
+        error.set(None);
+        load_suggestions();
+    };
+    let submit = move || {
+        let target = active
+            .get_untracked()
+            .and_then(suggestion_path)
+            .unwrap_or_else(|| resolve_path(&cwd.get_value(), &draft.get_untracked()));
+        let Some(explorer) = explorer.get_value() else {
+            on_navigate.call(target);
+            return;
+        };
+        spawn_local(async move {
+            let result = explorer.stat(&target).await;
+            if draft.try_get_untracked().is_none() {
+                return;
+            }
+            match result {
+                Ok(meta) if meta.kind == ExplorerEntryKind::Directory => on_navigate.call(target),
+                Ok(_) => error.set(Some(format!("{target} is a file, not a folder"))),
+                Err(_) => error.set(Some(format!("No folder at {target}"))),
+            }
+        });
+    };
+    let on_keydown = move |ev: ev::KeyboardEvent| {
+        ev.stop_propagation();
+        let count = suggestions.with_untracked(Vec::len);
+        match ev.key().as_str() {
+            "Enter" => {
+                ev.prevent_default();
+                submit();
+            }
+            "Escape" => {
+                ev.prevent_default();
+                on_cancel.call(());
+            }
+            "Tab" if !ev.shift_key() && count > 0 => {
+                ev.prevent_default();
+                if let Some(path) = active.get_untracked().and_then(suggestion_path) {
+                    accept(path);
+                    return;
+                }
+                let (folder, partial) = completion_base(&cwd.get_value(), &draft.get_untracked());
+                let completed =
+                    suggestions.with_untracked(|names| complete_input(&folder, &partial, names));
+                if let Some(completed) = completed {
+                    draft.set(completed);
+                    load_suggestions();
+                }
+            }
+            "ArrowDown" | "ArrowUp" if count > 0 => {
+                ev.prevent_default();
+                let next = match (ev.key().as_str(), active.get_untracked()) {
+                    ("ArrowDown", None) => 0,
+                    ("ArrowDown", Some(index)) => (index + 1) % count,
+                    (_, None | Some(0)) => count - 1,
+                    (_, Some(index)) => index - 1,
+                };
+                active.set(Some(next));
+            }
+            _ => {}
+        }
+    };
+
+    view! {
+        <div data-ui-slot="path-field">
+            <TextField
+                aria_label="Folder path"
+                node_ref=input
+                spellcheck=false
+                autocomplete="off"
+                value=Signal::derive(move || draft.get())
+                on_input=Callback::new(move |ev| {
+                    draft.set(event_target_value(&ev));
+                    error.set(None);
+                    load_suggestions();
+                })
+                on_keydown=Callback::new(on_keydown)
+                on_blur=Callback::new(move |_| on_cancel.call(()))
+            />
+            <Show when=move || !suggestions.with(Vec::is_empty) fallback=|| ()>
+                <div data-ui-slot="path-suggestions" role="listbox" aria-label="Matching folders">
+                    <For
+                        each=move || suggestions.get().into_iter().enumerate()
+                        key=|(index, name)| (*index, name.clone())
+                        let:item
+                    >
+                        <div
+                            data-ui-slot="path-suggestion"
+                            role="option"
+                            aria-selected=move || active.get() == Some(item.0)
+                            data-ui-selected=move || {
+                                if active.get() == Some(item.0) { "true" } else { "false" }
+                            }
+                            on:mousedown=move |ev: ev::MouseEvent| ev.prevent_default()
+                            on:click=move |_| {
+                                if let Some(path) = suggestion_path(item.0) {
+                                    accept(path);
+                                }
+                            }
+                        >
+                            {item.1.clone()}
+                        </div>
+                    </For>
+                </div>
+            </Show>
+            {move || error.get().map(|error| view! { <Text tone=TextTone::Danger>{error}</Text> })}
+        </div>
+    }
+}
+
+#[component]
+/// Read-only hex dump of a binary file, one page at a time.
+///
+/// `bytes` may be only the start of the file; `size` is the full length, noted when they differ.
+fn ByteView(bytes: Rc<[u8]>, size: u64) -> impl IntoView {
+    let page = create_rw_signal(0usize);
+    let pages = hex_page_count(bytes.len());
+    let shown = bytes.len() as u64;
+    let bytes = store_value(bytes);
+    view! {
+        <div data-ui-slot="hex-view" role="region" aria-label="File bytes" tabindex="0">
+            <For
+                each=move || bytes.with_value(|bytes| hex_rows(bytes, page.get()))
+                key=|row| row.offset.clone()
+                let:row
+            >
+                <div data-ui-slot="hex-row">
+                    <span data-ui-slot="hex-offset">{row.offset}</span>
+                    <span data-ui-slot="hex-bytes">{row.hex}</span>
+                    <span data-ui-slot="hex-ascii">{row.ascii}</span>
+                </div>
+            </For>
+        </div>
+        <Cluster>
+            <Button
+                variant=ButtonVariant::Quiet
+                disabled=Signal::derive(move || page.get() == 0)
+                on_click=Callback::new(move |_| page.update(|page| *page = page.saturating_sub(1)))
+            >
+                "Previous"
+            </Button>
+            <Text tone=TextTone::Secondary>{move || format!("Page {} of {pages}", page.get() + 1)}</Text>
+            <Button
+                variant=ButtonVariant::Quiet
+                disabled=Signal::derive(move || page.get() + 1 >= pages)
+                on_click=Callback::new(move |_| page.update(|page| *page = (*page + 1).min(pages - 1)))
+            >
+                "Next"
+            </Button>
+        </Cluster>
+        {(size > shown).then(|| view! {
+            <Text tone=TextTone::Secondary>
+                {format!("Showing the first {} of {}", format_bytes(shown), format_bytes(size))}
+            </Text>
+        })}
+    }
+}
+
+#[component]
+/// Dialog listing an entry's full metadata, the recursive size of a folder, and a file checksum
+/// computed on request. Escape or Close dismisses it.
+fn PropertiesDialog(
+    properties: RwSignal<Option<ExplorerProperties>>,
+    explorer: StoredValue<Option<ExplorerHostService>>,
+) -> impl IntoView {
+    let backdrop = create_node_ref::<html::Div>();
+    request_animation_frame(move || {
+        if let Some(backdrop) = backdrop.get_untracked() {
+            let _ = backdrop.focus();
+        }
+    });
+    let title = Signal::derive(move || {
+        properties.with(|current| {
+            current
+                .as_ref()
+                .map(|current| format!("{} properties", entry_name(&current.path)))
+                .unwrap_or_default()
+        })
+    });
+    let metadata = create_memo(move |_| {
+        properties.with(|current| {
+            current
+                .as_ref()
+                .and_then(|current| current.metadata.clone())
+        })
+    });
+    let folder_size = create_memo(move |_| {
+        properties.with(|current| {
+            current
+                .as_ref()
+                .and_then(|current| current.folder_size.clone())
+        })
+    });
+    let checksum = create_memo(move |_| {
+        properties.with(|current| current.as_ref().map(|current| current.checksum.clone()))
+    });
+    let close = Callback::new(move |_| properties.set(None));
+
+    let size_view = move |meta: &ExplorerMetadata| {
+        if meta.kind == ExplorerEntryKind::File {
+            let size = meta.size.unwrap_or(0);
+            return view! { <Text>{format!("{} ({size} bytes)", format_bytes(size))}</Text> }
+                .into_view();
+        }
+        (move || match folder_size.get() {
+            None => view! { <Text tone=TextTone::Secondary>"Calculating…"</Text> }.into_view(),
+            Some(Ok(size)) => view! {
+                <Text>
+                    {format!(
+                        "{} ({} bytes) in {} files and {} folders",
+                        format_bytes(size.bytes),
+                        size.bytes,
+                        size.files,
+                        size.folders,
+                    )}
+                </Text>
+            }
+            .into_view(),
+            Some(Err(err)) => view! {
+                <Text tone=TextTone::Secondary>{format!("Could not calculate: {err}")}</Text>
+            }
+            .into_view(),
+        })
+        .into_view()
+    };
+    let checksum_view = move || {
+        let compute = Callback::new(move |_| compute_checksum(properties, explorer.get_value()));
+        match checksum.get().unwrap_or(ChecksumState::Idle) {
+            ChecksumState::Idle => view! {
+                <Button variant=ButtonVariant::Quiet on_click=compute>"Compute"</Button>
+            }
+            .into_view(),
+            ChecksumState::Running { hashed, size } => view! {
+                <Text tone=TextTone::Secondary>
+                    {format!("Computing… {}%", hashed * 100 / size.max(1))}
+                </Text>
+            }
+            .into_view(),
+            ChecksumState::Done(digest) => view! {
+                <span data-ui-slot="checksum">{digest}</span>
+            }
+            .into_view(),
+            ChecksumState::Failed(err) => view! {
+                <Cluster>
+                    <Text tone=TextTone::Secondary>{format!("Failed: {err}")}</Text>
+                    <Button variant=ButtonVariant::Quiet on_click=compute>"Retry"</Button>
+                </Cluster>
+            }
+            .into_view(),
+        }
+    };
+
+    view! {
+        <div
+            data-ui-slot="dialog-backdrop"
+            node_ref=backdrop
+            tabindex="-1"
+            on:keydown=move |ev: ev::KeyboardEvent| {
+                ev.stop_propagation();
+                if ev.key() == "Escape" {
+                    ev.prevent_default();
+                    properties.set(None);
+                }
+            }
+            on:mousedown=move |ev: ev::MouseEvent| ev.stop_propagation()
+        >
+            <Modal aria_label=title>
+                <Cluster justify=LayoutJustify::Between>
+                    <Heading role=TextRole::Title>{move || title.get()}</Heading>
+                    <IconButton icon=IconName::Dismiss aria_label="Close properties" on_click=close />
+                </Cluster>
+                {move || match metadata.get() {
+                    None => view! { <Text tone=TextTone::Secondary>"Loading…"</Text> }.into_view(),
+                    Some(Err(err)) => view! {
+                        <Text tone=TextTone::Secondary>{format!("metadata failed: {err}")}</Text>
+                    }
+                    .into_view(),
+                    Some(Ok(meta)) => {
+                        let name = meta.name.clone();
+                        let path = meta.path.clone();
+                        let kind = format!("{:?}", meta.kind);
+                        let size = size_view(&meta);
+                        let modified = meta
+                            .modified_at_unix_ms
+                            .map(format_timestamp)
+                            .unwrap_or_else(|| "-".to_string());
+                        let backend = format!("{:?}", meta.backend);
+                        let permission = format!("{:?}", meta.permission);
+                        let is_file = meta.kind == ExplorerEntryKind::File;
+                        let mime = meta.mime_type();
+                        view! {
+                            <InspectorGrid>
+                                <Text role=TextRole::Label>"Name"</Text><Text>{name}</Text>
+                                <Text role=TextRole::Label>"Path"</Text><Text>{path}</Text>
+                                <Text role=TextRole::Label>"Kind"</Text><Text>{kind}</Text>
+                                {mime.map(|mime| view! {
+                                    <Text role=TextRole::Label>"Type"</Text><Text>{mime}</Text>
+                                })}
+                                <Text role=TextRole::Label>"Size"</Text>{size}
+                                <Text role=TextRole::Label>"Modified"</Text><Text>{modified}</Text>
+                                <Text role=TextRole::Label>"Backend"</Text><Text>{backend}</Text>
+                                <Text role=TextRole::Label>"Permission"</Text><Text>{permission}</Text>
+                                {is_file.then(|| view! {
+                                    <Text role=TextRole::Label>"SHA-256"</Text>
+                                    {checksum_view}
+                                })}
+                            </InspectorGrid>
+                        }
+                        .into_view()
+                    }
+                }}
+                <Cluster justify=LayoutJustify::End>
+                    <Button on_click=close>"Close"</Button>
+                </Cluster>
+            </Modal>
+        </div>
+    }
+}
+
+#[component]
+/// Dialog renaming several entries at once with find and replace, a numbering template, and a
+/// case transform, previewing every new name before Rename applies them. Escape or Cancel
+/// dismisses it.
+fn BulkRenameDialog(
+    pending: RwSignal<Option<PendingBulkRename>>,
+    on_apply: Callback<Vec<(String, String)>>,
+) -> impl IntoView {
+    let backdrop = create_node_ref::<html::Div>();
+    request_animation_frame(move || {
+        if let Some(backdrop) = backdrop.get_untracked() {
+            let _ = backdrop.focus();
+        }
+    });
+    let rule = create_rw_signal(BulkRenameRule::default());
+    let previews = Signal::derive(move || {
+        pending.with(|pending| {
+            pending
+                .as_ref()
+                .map(|pending| {
+                    rule.with(|rule| preview_bulk_rename(rule, &pending.sources, &pending.others))
+                })
+                .unwrap_or_default()
+        })
+    });
+    let changed = move || {
+        previews.with(|previews| previews.iter().filter(|preview| preview.changes()).count())
+    };
+    let blocked = move || {
+        previews.with(|previews| {
+            previews
+                .iter()
+                .any(|preview| preview.changes() && preview.problem.is_some())
+        })
+    };
+    let cancel = Callback::new(move |_| pending.set(None));
+    let apply = Callback::new(move |_| {
+        let Some(folder) =
+            pending.with_untracked(|pending| pending.as_ref().map(|p| p.folder.clone()))
+        else {
+            return;
+        };
+        let renames = previews.with_untracked(|previews| {
+            previews
+                .iter()
+                .filter(|preview| preview.changes())
+                .map(|preview| {
+                    (
+                        join_path(&folder, &preview.from),
+                        join_path(&folder, &preview.to),
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+        pending.set(None);
+        on_apply.call(renames);
+    });
+    let preview_row = move |preview: BulkRenamePreview| {
+        let status = match (&preview.problem, preview.changes()) {
+            (Some(problem), true) => problem.clone(),
+            (None, true) => "Renamed".to_string(),
+            (_, false) => "Unchanged".to_string(),
+        };
+        view! {
+            <tr>
+                <td>{preview.from}</td>
+                <td>{preview.to}</td>
+                <td>{status}</td>
+            </tr>
+        }
+    };
+
+    view! {
+        <div
+            data-ui-slot="dialog-backdrop"
+            node_ref=backdrop
+            tabindex="-1"
+            on:keydown=move |ev: ev::KeyboardEvent| {
+                ev.stop_propagation();
+                if ev.key() == "Escape" {
+                    ev.prevent_default();
+                    pending.set(None);
+                }
             }
-            Err(err) => set_error(signals, format!("create folder failed: {err}")),
+            on:mousedown=move |ev: ev::MouseEvent| ev.stop_propagation()
+        >
+            <Modal aria_label="Rename items">
+                <Heading role=TextRole::Title>
+                    {move || {
+                        let count = pending.with(|pending| pending.as_ref().map_or(0, |p| p.sources.len()));
+                        format!("Rename {count} items")
+                    }}
+                </Heading>
+                <InspectorGrid>
+                    <Text role=TextRole::Label>"Find"</Text>
+                    <TextField
+                        aria_label="Find"
+                        placeholder="Text to replace"
+                        spellcheck=false
+                        value=Signal::derive(move || rule.with(|rule| rule.find.clone()))
+                        on_input=Callback::new(move |ev| {
+                            let value = event_target_value(&ev);
+                            rule.update(|rule| rule.find = value);
+                        })
+                    />
+                    <Text role=TextRole::Label>"Replace with"</Text>
+                    <TextField
+                        aria_label="Replace with"
+                        placeholder="Replacement"
+                        spellcheck=false
+                        value=Signal::derive(move || rule.with(|rule| rule.replace.clone()))
+                        on_input=Callback::new(move |ev| {
+                            let value = event_target_value(&ev);
+                            rule.update(|rule| rule.replace = value);
+                        })
+                    />
+                    <Text role=TextRole::Label>"Name template"</Text>
+                    <TextField
+                        aria_label="Name template"
+                        placeholder="e.g. photo-{n} or {name}-old"
+                        spellcheck=false
+                        value=Signal::derive(move || rule.with(|rule| rule.template.clone()))
+                        on_input=Callback::new(move |ev| {
+                            let value = event_target_value(&ev);
+                            rule.update(|rule| rule.template = value);
+                        })
+                    />
+                    <Text role=TextRole::Label>"Case"</Text>
+                    <SelectField
+                        aria_label="Case"
+                        value=Signal::derive(move || rule.with(|rule| rule.case.label().to_string()))
+                        on_change=Callback::new(move |ev| {
+                            let label = event_target_value(&ev);
+                            if let Some(case) = NameCase::ALL.into_iter().find(|case| case.label() == label) {
+                                rule.update(|rule| rule.case = case);
+                            }
+                        })
+                    >
+                        {NameCase::ALL
+                            .into_iter()
+                            .map(|case| view! { <option value=case.label()>{case.label()}</option> })
+                            .collect_view()}
+                    </SelectField>
+                </InspectorGrid>
+                <Text tone=TextTone::Secondary>
+                    "{n} numbers the items in listing order and {name} keeps the current name. File extensions are kept."
+                </Text>
+                <DataTable aria_label="New names">
+                    <thead>
+                        <tr>
+                            <th>"Name"</th>
+                            <th>"New name"</th>
+                            <th>"Status"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || previews.get().into_iter().map(preview_row).collect_view()}
+                    </tbody>
+                </DataTable>
+                <Cluster justify=LayoutJustify::End>
+                    <Button on_click=cancel>"Cancel"</Button>
+                    <Button
+                        variant=ButtonVariant::Primary
+                        disabled=Signal::derive(move || changed() == 0 || blocked())
+                        on_click=apply
+                    >
+                        {move || match changed() {
+                            1 => "Rename 1 item".to_string(),
+                            count => format!("Rename {count} items"),
+                        }}
+                    </Button>
+                </Cluster>
+            </Modal>
+        </div>
+    }
+}
+
+#[component]
+/// Dialog asking whether extracted archive entries that clash with existing names are kept
+/// alongside them, replace them, or are skipped. Escape or Cancel abandons the extraction.
+fn ExtractConflictDialog(
+    pending: RwSignal<Option<PendingExtract>>,
+    on_choose: Callback<ExplorerArchiveConflict>,
+) -> impl IntoView {
+    let backdrop = create_node_ref::<html::Div>();
+    request_animation_frame(move || {
+        if let Some(backdrop) = backdrop.get_untracked() {
+            let _ = backdrop.focus();
         }
-        signals.busy.set(false);
     });
+    let conflicts = move || {
+        pending.with(|pending| {
+            pending
+                .as_ref()
+                .map(|pending| pending.conflicts.join(", "))
+                .unwrap_or_default()
+        })
+    };
+    let cancel = Callback::new(move |_| pending.set(None));
+    let choose = move |conflict| Callback::new(move |_| on_choose.call(conflict));
+
+    view! {
+        <div
+            data-ui-slot="dialog-backdrop"
+            node_ref=backdrop
+            tabindex="-1"
+            on:keydown=move |ev: ev::KeyboardEvent| {
+                ev.stop_propagation();
+                if ev.key() == "Escape" {
+                    ev.prevent_default();
+                    pending.set(None);
+                }
+            }
+            on:mousedown=move |ev: ev::MouseEvent| ev.stop_propagation()
+        >
+            <Modal aria_label="Items already exist">
+                <Heading role=TextRole::Title>"Items already exist"</Heading>
+                <Text>{move || format!("This folder already contains {}.", conflicts())}</Text>
+                <Cluster justify=LayoutJustify::End>
+                    <Button on_click=cancel>"Cancel"</Button>
+                    <Button on_click=choose(ExplorerArchiveConflict::Skip)>"Skip Existing"</Button>
+                    <Button
+                        variant=ButtonVariant::Danger
+                        on_click=choose(ExplorerArchiveConflict::Replace)
+                    >
+                        "Replace"
+                    </Button>
+                    <Button
+                        variant=ButtonVariant::Primary
+                        on_click=choose(ExplorerArchiveConflict::KeepBoth)
+                    >
+                        "Keep Both"
+                    </Button>
+                </Cluster>
+            </Modal>
+        </div>
+    }
 }
 
-fn create_file(
-    signals: ExplorerSignals,
-    explorer: Option<ExplorerHostService>,
-    cache: Option<CacheHostService>,
-    cwd: String,
-    name: String,
-) {
-    let path = join_path(&cwd, &name);
-    signals.busy.set(true);
-    spawn_local(async move {
-        let Some(explorer) = explorer else {
-            set_error(signals, "Explorer host service unavailable");
-            signals.busy.set(false);
-            return;
-        };
-        match explorer.create_file(&path, "").await {
-            Ok(meta) => {
-                signals.selected_path.set(Some(meta.path.clone()));
-                signals.selected_metadata.set(Some(meta.clone()));
-                refresh_directory(
-                    signals,
-                    Some(explorer.clone()),
-                    Some(parent_path(&meta.path)),
-                );
-                open_file(signals, Some(explorer), cache, meta.path.clone());
-                set_notice(signals, format!("Created file {}", meta.path));
+#[component]
+/// Dialog showing how two texts differ, side by side or as one unified column.
+fn CompareDialog(comparison: RwSignal<Option<ExplorerComparison>>) -> impl IntoView {
+    let backdrop = create_node_ref::<html::Div>();
+    request_animation_frame(move || {
+        if let Some(backdrop) = backdrop.get_untracked() {
+            let _ = backdrop.focus();
+        }
+    });
+    let unified = create_rw_signal(false);
+    let lines = move || {
+        comparison.with(|comparison| {
+            comparison
+                .as_ref()
+                .map(|comparison| comparison.lines.clone())
+                .unwrap_or_default()
+        })
+    };
+    let title = move || {
+        comparison.with(|comparison| {
+            comparison
+                .as_ref()
+                .map(|comparison| format!("{} → {}", comparison.old_label, comparison.new_label))
+                .unwrap_or_default()
+        })
+    };
+    let summary = move || {
+        comparison.with(|comparison| {
+            let lines = comparison
+                .as_ref()
+                .map_or(&[][..], |comparison| &comparison.lines);
+            let count = |kind| lines.iter().filter(|line| line.kind == kind).count();
+            match (count(DiffKind::Removed), count(DiffKind::Added)) {
+                (0, 0) => "The texts are identical".to_string(),
+                (removed, added) => format!("{removed} line(s) removed, {added} line(s) added"),
             }
-            Err(err) => set_error(signals, format!("create file failed: {err}")),
+        })
+    };
+    let number = |line: Option<usize>| line.map(|line| line.to_string()).unwrap_or_default();
+    let diff_token = |kind| match kind {
+        DiffKind::Same => "same",
+        DiffKind::Removed => "removed",
+        DiffKind::Added => "added",
+    };
+    let unified_rows = move || {
+        lines()
+            .into_iter()
+            .map(|line| {
+                let sign = match line.kind {
+                    DiffKind::Same => ' ',
+                    DiffKind::Removed => '-',
+                    DiffKind::Added => '+',
+                };
+                view! {
+                    <tr>
+                        <td data-align="end">{number(line.old_line)}</td>
+                        <td data-align="end">{number(line.new_line)}</td>
+                        <td data-ui-slot="diff-text" data-ui-diff=diff_token(line.kind)>
+                            {format!("{sign} {}", line.text)}
+                        </td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+    let paired_rows = move || {
+        side_by_side(&lines())
+            .into_iter()
+            .map(|row| {
+                let changed = row.changed();
+                let side = move |side: Option<(usize, String)>, kind| {
+                    let token = match (&side, changed) {
+                        (None, _) => "empty",
+                        (Some(_), false) => "same",
+                        (Some(_), true) => diff_token(kind),
+                    };
+                    let (line, text) = side.unzip();
+                    view! {
+                        <td data-align="end">{number(line)}</td>
+                        <td data-ui-slot="diff-text" data-ui-diff=token>{text}</td>
+                    }
+                };
+                view! {
+                    <tr>
+                        {side(row.old, DiffKind::Removed)}
+                        {side(row.new, DiffKind::Added)}
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+    let close = Callback::new(move |_| comparison.set(None));
+
+    view! {
+        <div
+            data-ui-slot="dialog-backdrop"
+            node_ref=backdrop
+            tabindex="-1"
+            on:keydown=move |ev: ev::KeyboardEvent| {
+                ev.stop_propagation();
+                if ev.key() == "Escape" {
+                    ev.prevent_default();
+                    comparison.set(None);
+                }
+            }
+            on:mousedown=move |ev: ev::MouseEvent| ev.stop_propagation()
+        >
+            <Modal aria_label="Compare">
+                <Heading role=TextRole::Title>"Compare"</Heading>
+                <Text tone=TextTone::Secondary>{title}</Text>
+                <Cluster justify=LayoutJustify::Between>
+                    <Text>{summary}</Text>
+                    <SegmentedControl aria_label="Diff layout">
+                        <SegmentedControlOption
+                            selected=Signal::derive(move || !unified.get())
+                            on_click=Callback::new(move |_| unified.set(false))
+                        >
+                            "Side by Side"
+                        </SegmentedControlOption>
+                        <SegmentedControlOption
+                            selected=unified
+                            on_click=Callback::new(move |_| unified.set(true))
+                        >
+                            "Unified"
+                        </SegmentedControlOption>
+                    </SegmentedControl>
+                </Cluster>
+                <div data-ui-slot="diff-view">
+                    <Show
+                        when=move || unified.get()
+                        fallback=move || view! {
+                            <DataTable aria_label="Side-by-side differences">
+                                <tbody>{paired_rows}</tbody>
+                            </DataTable>
+                        }
+                    >
+                        <DataTable aria_label="Unified differences">
+                            <tbody>{unified_rows}</tbody>
+                        </DataTable>
+                    </Show>
+                </div>
+                <Cluster justify=LayoutJustify::End>
+                    <Button variant=ButtonVariant::Primary on_click=close>"Close"</Button>
+                </Cluster>
+            </Modal>
+        </div>
+    }
+}
+
+#[component]
+/// Dialog asking what to do with unsaved edits to a file that changed on disk since it was
+/// opened. Escape or Cancel leaves the edits unsaved.
+fn SaveConflictDialog(
+    conflict: RwSignal<Option<ExplorerFileReadResult>>,
+    on_choose: Callback<SaveConflictChoice>,
+) -> impl IntoView {
+    let backdrop = create_node_ref::<html::Div>();
+    request_animation_frame(move || {
+        if let Some(backdrop) = backdrop.get_untracked() {
+            let _ = backdrop.focus();
         }
-        signals.busy.set(false);
     });
+    let name = move || {
+        conflict.with(|conflict| {
+            conflict
+                .as_ref()
+                .map(|disk| entry_name(&disk.path))
+                .unwrap_or_default()
+        })
+    };
+    let cancel = Callback::new(move |_| conflict.set(None));
+    let choose = move |choice| Callback::new(move |_| on_choose.call(choice));
+
+    view! {
+        <div
+            data-ui-slot="dialog-backdrop"
+            node_ref=backdrop
+            tabindex="-1"
+            on:keydown=move |ev: ev::KeyboardEvent| {
+                ev.stop_propagation();
+                if ev.key() == "Escape" {
+                    ev.prevent_default();
+                    conflict.set(None);
+                }
+            }
+            on:mousedown=move |ev: ev::MouseEvent| ev.stop_propagation()
+        >
+            <Modal aria_label="File changed on disk">
+                <Heading role=TextRole::Title>"File changed on disk"</Heading>
+                <Text>
+                    {move || {
+                        format!(
+                            "{} was changed in another window or by a command since you opened it.",
+                            name(),
+                        )
+                    }}
+                </Text>
+                <Text tone=TextTone::Secondary>
+                    "Reload discards your edits, Overwrite replaces the other change, and Merge \
+                     combines both in the editor, marking lines changed on both sides."
+                </Text>
+                <Cluster justify=LayoutJustify::End>
+                    <Button on_click=cancel>"Cancel"</Button>
+                    <Button on_click=choose(SaveConflictChoice::Reload)>"Reload"</Button>
+                    <Button
+                        variant=ButtonVariant::Danger
+                        on_click=choose(SaveConflictChoice::Overwrite)
+                    >
+                        "Overwrite"
+                    </Button>
+                    <Button
+                        variant=ButtonVariant::Primary
+                        on_click=choose(SaveConflictChoice::Merge)
+                    >
+                        "Merge"
+                    </Button>
+                </Cluster>
+            </Modal>
+        </div>
+    }
 }
 
-fn delete_selected(
+#[component]
+/// Second listing of the dual-pane layout, browsing its own folder with its own selection.
+///
+/// Files open through `on_open_file`, and the dual-pane keys (Tab, F5, F6) go to `on_command`.
+fn CommanderPane(
     signals: ExplorerSignals,
-    explorer: Option<ExplorerHostService>,
-    cache: Option<CacheHostService>,
-) {
-    let Some(path) = signals.selected_path.get_untracked() else {
-        set_error(signals, "Select a file or folder to delete");
-        return;
+    second: SecondPane,
+    prefs: RwSignal<ExplorerPrefs>,
+    explorer: StoredValue<Option<ExplorerHostService>>,
+    on_open_file: Callback<String>,
+    on_command: Callback<PaneCommand>,
+) -> impl IntoView {
+    let rows = Signal::derive(move || {
+        let prefs = prefs.get();
+        let mut rows = second
+            .entries
+            .get()
+            .into_iter()
+            .filter(|entry| prefs.show_hidden || !entry.name.starts_with('.'))
+            .collect::<Vec<_>>();
+        sort_entries(&mut rows, &prefs);
+        rows
+    });
+    let order = move || {
+        rows.with_untracked(|rows| {
+            rows.iter()
+                .map(|entry| entry.path.clone())
+                .collect::<Vec<_>>()
+        })
     };
-    if path == "/" {
-        set_error(signals, "Cannot delete the root directory");
-        return;
-    }
-    signals.busy.set(true);
-    spawn_local(async move {
-        let Some(explorer) = explorer else {
-            set_error(signals, "Explorer host service unavailable");
-            signals.busy.set(false);
+    let navigate =
+        move |path: String| refresh_second_pane(signals, second, explorer.get_value(), Some(path));
+    let open = move |entry: &ExplorerEntry| match entry.kind {
+        ExplorerEntryKind::Directory => navigate(entry.path.clone()),
+        ExplorerEntryKind::File => on_open_file.call(entry.path.clone()),
+    };
+    let select = move |path: String, extend: bool, toggle: bool| {
+        let order = order();
+        second.selection.update(|selection| {
+            if extend {
+                selection.extend_to(&order, path);
+            } else if toggle {
+                selection.toggle(path);
+            } else {
+                selection.select_only(path);
+            }
+        });
+    };
+    let on_keydown = move |ev: ev::KeyboardEvent| {
+        if let Some(command) = pane_command(&ev.key()) {
+            ev.prevent_default();
+            on_command.call(command);
             return;
-        };
-        match explorer.delete(&path, true).await {
-            Ok(()) => {
-                if signals.editor_path.get_untracked() == Some(path.clone()) {
-                    signals.editor_path.set(None);
-                    signals.editor_text.set(String::new());
-                    signals.editor_dirty.set(false);
+        }
+        let command = ev.ctrl_key() || ev.meta_key();
+        let focus = second
+            .selection
+            .with_untracked(|selection| selection.focus().map(str::to_string));
+        match ev.key().as_str() {
+            "Backspace" => {
+                ev.prevent_default();
+                navigate(parent_path(&second.cwd.get_untracked()));
+            }
+            "a" | "A" if command => {
+                ev.prevent_default();
+                let order = order();
+                second
+                    .selection
+                    .update(|selection| selection.select_all(&order));
+            }
+            " " | "Spacebar" => {
+                ev.prevent_default();
+                if let Some(path) = focus.or_else(|| order().into_iter().next()) {
+                    select(path, ev.shift_key(), command);
                 }
-                if let Some(cache) = cache {
-                    let cache_key = explorer_preview_cache_key(&path);
-                    if let Err(err) = cache.delete(EXPLORER_CACHE_NAME, &cache_key).await {
-                        logging::warn!("explorer cache delete failed: {err}");
-                    }
+            }
+            "Enter" => {
+                ev.prevent_default();
+                let entry = rows.with_untracked(|rows| {
+                    rows.iter()
+                        .find(|entry| Some(entry.path.as_str()) == focus.as_deref())
+                        .or(rows.first())
+                        .cloned()
+                });
+                if let Some(entry) = entry {
+                    open(&entry);
+                }
+            }
+            key => {
+                if let Some(path) = step_focus(&order(), focus.as_deref(), key) {
+                    ev.prevent_default();
+                    select(path, ev.shift_key(), false);
                 }
-                signals.selected_path.set(None);
-                signals.selected_metadata.set(None);
-                set_notice(signals, format!("Deleted {}", path));
-                refresh_directory(signals, Some(explorer), Some(parent_path(&path)));
             }
-            Err(err) => set_error(signals, format!("delete failed: {err}")),
         }
-        signals.busy.set(false);
-    });
+    };
+
+    view! {
+        <div
+            data-ui-slot="commander-pane"
+            data-ui-active=move || (second.active.get() == ActivePane::Secondary).to_string()
+            on:focusin=move |_| second.active.set(ActivePane::Secondary)
+        >
+            <PaneHeader
+                title="Second Pane"
+                meta=Signal::derive(move || format!("Path: {}", second.cwd.get()))
+            >
+                <Button
+                    variant=ButtonVariant::Quiet
+                    on_click=Callback::new(move |_| navigate(parent_path(&second.cwd.get_untracked())))
+                >
+                    "Up"
+                </Button>
+            </PaneHeader>
+            <DataTable
+                role="grid"
+                aria_label="Explorer second pane"
+                tabindex=Signal::derive(|| 0)
+                aria_activedescendant=Signal::derive(move || {
+                    second
+                        .selection
+                        .with(|selection| selection.focus().map(commander_row_dom_id))
+                        .unwrap_or_default()
+                })
+                on_keydown=Callback::new(on_keydown)
+            >
+                <thead>
+                    <tr>
+                        <SortHeader label="Name" key=ExplorerSortKey::Name prefs=prefs/>
+                        <SortHeader label="Type" key=ExplorerSortKey::Type prefs=prefs/>
+                        <SortHeader label="Size" key=ExplorerSortKey::Size prefs=prefs/>
+                    </tr>
+                </thead>
+                <tbody>
+                    <For each=move || rows.get() key=|entry| entry.path.clone() let:entry>
+                        {move || {
+                            let path = store_value(entry.path.clone());
+                            let entry_for_open = entry.clone();
+                            let row_selected =
+                                second.selection.with(|selection| selection.contains(&entry.path));
+                            view! {
+                                <tr
+                                    id=commander_row_dom_id(&entry.path)
+                                    class=if row_selected { "selected" } else { "" }
+                                    aria-selected=row_selected
+                                    on:click=move |ev: ev::MouseEvent| {
+                                        select(
+                                            path.get_value(),
+                                            ev.shift_key(),
+                                            ev.ctrl_key() || ev.meta_key(),
+                                        )
+                                    }
+                                    on:dblclick=move |_| open(&entry_for_open)
+                                >
+                                    <td>{entry.name.clone()}</td>
+                                    <td>{match entry.kind {
+                                        ExplorerEntryKind::Directory => "Folder",
+                                        ExplorerEntryKind::File => "File",
+                                    }}</td>
+                                    <td>{entry
+                                        .size
+                                        .map(format_bytes)
+                                        .unwrap_or_else(|| "-".to_string())}</td>
+                                </tr>
+                            }
+                        }}
+                    </For>
+                </tbody>
+            </DataTable>
+        </div>
+    }
+}
+
+#[component]
+/// Listing column header that sorts by `key`, reversing the direction when clicked again.
+fn SortHeader(
+    label: &'static str,
+    key: ExplorerSortKey,
+    prefs: RwSignal<ExplorerPrefs>,
+) -> impl IntoView {
+    view! {
+        <th aria-sort=move || prefs.with(|prefs| aria_sort(prefs, key))>
+            <Button
+                variant=ButtonVariant::Quiet
+                selected=Signal::derive(move || prefs.with(|prefs| prefs.sort_key == key))
+                on_click=Callback::new(move |_| prefs.update(|prefs| select_sort_column(prefs, key)))
+            >
+                <span>{label}</span>
+                <span aria-hidden="true">{move || {
+                    prefs.with(|prefs| match (prefs.sort_key == key, prefs.sort_direction) {
+                        (false, _) => "",
+                        (true, ExplorerSortDirection::Ascending) => " \u{25b2}",
+                        (true, ExplorerSortDirection::Descending) => " \u{25bc}",
+                    })
+                }}</span>
+            </Button>
+        </th>
+    }
 }
 
 fn request_rw_permission(signals: ExplorerSignals, explorer: Option<ExplorerHostService>) {
@@ -488,16 +3827,74 @@ fn connect_native_folder(signals: ExplorerSignals, explorer: Option<ExplorerHost
             signals.busy.set(false);
             return;
         };
+        let previous = signals
+            .status
+            .get_untracked()
+            .map(|status| status.mounts)
+            .unwrap_or_default();
         match explorer.pick_native_directory().await {
             Ok(status) => {
-                signals.status.set(Some(status));
-                signals.cwd.set("/".to_string());
-                refresh_directory(signals, Some(explorer), Some("/".to_string()));
-                set_notice(signals, "Native folder connected");
+                let added = status
+                    .mounts
+                    .iter()
+                    .find(|mount| previous.iter().all(|known| known.path != mount.path))
+                    .cloned();
+                let legacy_root = status.mounts.is_empty();
+                if signals.status.try_set(Some(status)).is_some() {
+                    return;
+                }
+                match added {
+                    Some(mount) => {
+                        refresh_directory(signals, Some(explorer), Some(mount.path.clone()));
+                        set_notice(
+                            signals,
+                            format!("Mounted {} read-only at {}", mount.name, mount.path),
+                        );
+                    }
+                    None if legacy_root => {
+                        refresh_directory(signals, Some(explorer), Some("/".to_string()));
+                        set_notice(signals, "Native folder connected");
+                    }
+                    None => {
+                        refresh_directory(
+                            signals,
+                            Some(explorer),
+                            Some(EXPLORER_MOUNT_DIR.to_string()),
+                        );
+                        set_notice(signals, "That folder is already mounted");
+                    }
+                }
             }
             Err(err) => set_error(signals, format!("connect folder failed: {err}")),
         }
-        signals.busy.set(false);
+        let _ = signals.busy.try_set(false);
+    });
+}
+
+/// Removes the mount at `path` and returns to the root when the listing was inside it.
+fn unmount_native_folder(
+    signals: ExplorerSignals,
+    explorer: Option<ExplorerHostService>,
+    path: String,
+) {
+    spawn_local(async move {
+        let Some(explorer) = explorer else {
+            set_error(signals, "Explorer host service unavailable");
+            return;
+        };
+        match explorer.unmount_native_directory(&path).await {
+            Ok(status) => {
+                if signals.status.try_set(Some(status)).is_some() {
+                    return;
+                }
+                let cwd = signals.cwd.get_untracked();
+                let inside = cwd == path || cwd.starts_with(&format!("{path}/"));
+                let target = if inside { "/".to_string() } else { cwd };
+                refresh_directory(signals, Some(explorer), Some(target));
+                set_notice(signals, format!("Unmounted {path}"));
+            }
+            Err(err) => set_error(signals, format!("unmount failed: {err}")),
+        }
     });
 }
 
@@ -528,13 +3925,18 @@ pub fn ExplorerApp(
     let status = create_rw_signal::<Option<ExplorerBackendStatus>>(None);
     let cwd = create_rw_signal(normalize_path(&initial_target));
     let entries = create_rw_signal(Vec::<ExplorerEntry>::new());
-    let selected_path = create_rw_signal::<Option<String>>(None);
+    let selection = create_rw_signal(ExplorerSelection::default());
     let selected_metadata = create_rw_signal::<Option<ExplorerMetadata>>(None);
     let editor_path = create_rw_signal::<Option<String>>(None);
     let editor_text = create_rw_signal(String::new());
     let editor_dirty = create_rw_signal(false);
+    let editor_base = create_rw_signal::<Option<EditorBase>>(None);
+    let editor_draft = create_rw_signal::<Option<String>>(None);
+    let preview = create_rw_signal::<Option<ExplorerPreview>>(None);
     let error = create_rw_signal::<Option<String>>(None);
     let notice = create_rw_signal::<Option<String>>(None);
+    let undo = create_rw_signal::<Option<ExplorerUndo>>(None);
+    let trash = create_rw_signal(Vec::<ExplorerTrashEntry>::new());
     let busy = create_rw_signal(false);
     let hydrated = create_rw_signal(false);
     let last_saved = create_rw_signal::<Option<String>>(None);
@@ -544,60 +3946,175 @@ pub fn ExplorerApp(
     let explorer_service = store_value(services.as_ref().map(|services| services.explorer.clone()));
     let cache_service = store_value(services.as_ref().map(|services| services.cache.clone()));
     let prefs_service = store_value(services.as_ref().map(|services| services.prefs.clone()));
+    let dialog_service = store_value(services.as_ref().map(|services| services.dialogs.clone()));
+    let log_service = store_value(services.as_ref().map(|services| services.logs.clone()));
+    let app_services = store_value(
+        services
+            .as_ref()
+            .map(|services| (services.config.clone(), services.launcher.clone())),
+    );
+    let window_service = services.as_ref().map(|services| services.window);
+    let task_service = services
+        .as_ref()
+        .map(|services| services.tasks)
+        .unwrap_or_default();
+    let associations = create_rw_signal(FileAssociations::default());
 
     let session_store = session_store();
     let initial_draft_name = session_store
         .get::<String>("explorer.ui.new_entry_name")
         .unwrap_or_default();
     let new_entry_name = create_rw_signal(initial_draft_name);
+    let soft_wrap = create_rw_signal(
+        session_store
+            .get::<bool>("explorer.ui.soft_wrap")
+            .unwrap_or(true),
+    );
     let setup_step = create_rw_signal(ExplorerSetupStep::Source);
     let show_create_panel = create_rw_signal(false);
     let show_workspace_controls = create_rw_signal(false);
     let show_view_controls = create_rw_signal(false);
-
+    let renaming = create_rw_signal::<Option<ExplorerRename>>(None);
+    let rename_draft = create_rw_signal(String::new());
+    let context_menu = create_rw_signal::<Option<ExplorerContextMenu>>(None);
+    // Element that opened the context menu from the keyboard, refocused when the menu closes.
+    let menu_return_focus = store_value::<Option<web_sys::HtmlElement>>(None);
+    let clipboard = create_rw_signal::<Option<ExplorerClipboard>>(None);
+    let properties = create_rw_signal::<Option<ExplorerProperties>>(None);
+    let pending_extract = create_rw_signal::<Option<PendingExtract>>(None);
+    let pending_bulk_rename = create_rw_signal::<Option<PendingBulkRename>>(None);
+    let save_conflict = create_rw_signal::<Option<ExplorerFileReadResult>>(None);
+    let comparison = create_rw_signal::<Option<ExplorerComparison>>(None);
+    let second = SecondPane {
+        enabled: create_rw_signal(false),
+        cwd: create_rw_signal("/".to_string()),
+        entries: create_rw_signal(Vec::new()),
+        selection: create_rw_signal(ExplorerSelection::default()),
+        active: create_rw_signal(ActivePane::Primary),
+    };
+    let primary_listing = create_node_ref::<html::Div>();
+    let listing_viewport = create_rw_signal(ListingViewport::default());
+    let view_menu_open = create_rw_signal(false);
+    let crumb_menu_open = create_rw_signal(false);
+    let editing_path = create_rw_signal(false);
+    let drop_target = create_rw_signal::<Option<String>>(None);
+    let folder_tree = create_rw_signal(FolderTree::default());
+    let tree_focus = create_rw_signal::<Option<String>>(None);
+    let transfer_destination = create_rw_signal(String::new());
+    let show_search_panel = create_rw_signal(false);
+    let show_trash_panel = create_rw_signal(false);
+    let show_storage_panel = create_rw_signal(false);
+    let storage = create_rw_signal::<Option<ExplorerStorageReport>>(None);
+    let search_name = create_rw_signal(String::new());
+    let search_text = create_rw_signal(String::new());
+    let search = ExplorerSearch {
+        results: create_rw_signal(Vec::new()),
+        running: create_rw_signal(false),
+        status: create_rw_signal(None),
+        generation: create_rw_signal(0),
+    };
     let signals = ExplorerSignals {
         status,
         cwd,
         entries,
-        selected_path,
+        selection,
         selected_metadata,
         editor_path,
         editor_text,
         editor_dirty,
+        editor_base,
+        editor_draft,
+        preview,
         error,
         notice,
+        undo,
+        trash,
         busy,
+        logs: log_service,
+    };
+    if let Some(services) = services.as_ref() {
+        services.window.set_lifecycle_handler(ExplorerLifecycle {
+            signals,
+            dialogs: services.dialogs.clone(),
+        });
+    }
+    let start_search = move || {
+        let Some(query) =
+            SearchQuery::new(&search_name.get_untracked(), &search_text.get_untracked())
+        else {
+            set_error(signals, "Enter a name pattern or text to search for");
+            return;
+        };
+        run_search(
+            search,
+            explorer_service.get_value(),
+            cwd.get_untracked(),
+            query,
+            prefs.with_untracked(|prefs| prefs.show_hidden),
+        );
     };
 
+    open_launch_target(
+        signals,
+        explorer_service.get_value(),
+        cache_service.get_value(),
+        &launch_params,
+    );
+    // Links routed to an open window reveal their target the same way launch params do.
+    if let Some(services) = services.as_ref() {
+        services.register_link_handler(move |link| {
+            open_launch_target(
+                signals,
+                explorer_service.get_value(),
+                cache_service.get_value(),
+                &link.launch_params(),
+            );
+        });
+    }
+
     if let Some(restored_state) = restored_state.as_ref() {
         if let Ok(restored) =
             serde_json::from_value::<ExplorerPersistedState>(restored_state.clone())
         {
             let serialized = serde_json::to_string(&restored).ok();
             signals.cwd.set(normalize_path(&restored.cwd));
-            signals.selected_path.set(restored.selected_path);
+            signals.selection.set(ExplorerSelection::restore(
+                restored.selected_paths,
+                restored.selected_path,
+            ));
             signals.selected_metadata.set(restored.selected_metadata);
             signals.editor_path.set(restored.editor_path.clone());
             signals.editor_text.set(restored.editor_text);
             signals.editor_dirty.set(restored.editor_dirty);
+            signals.editor_base.set(restored.editor_base);
+            second.enabled.set(restored.dual_pane);
+            if let Some(second_cwd) = restored.second_cwd {
+                second.cwd.set(normalize_path(&second_cwd));
+            }
             last_saved.set(serialized);
         }
     }
 
     if let Some(services) = services_for_bus {
         create_effect(move |_| {
-            services.ipc.subscribe("explorer.refresh");
+            services.ipc.subscribe(EXPLORER_REFRESH_TOPIC.name());
         });
         on_cleanup(move || {
-            services.ipc.unsubscribe("explorer.refresh");
+            services.ipc.unsubscribe(EXPLORER_REFRESH_TOPIC.name());
         });
     }
 
     if let Some(services) = services_for_publish {
         create_effect(move |_| {
-            services
+            let event = ExplorerCwdChangedEvent { cwd: cwd.get() };
+            if let Err(err) = services
                 .ipc
-                .publish("explorer.cwd.changed", json!({ "cwd": cwd.get() }));
+                .publish_typed(&EXPLORER_CWD_CHANGED_TOPIC, &event)
+            {
+                services
+                    .logs
+                    .warn(format!("publish explorer cwd failed: {err}"));
+            }
         });
     }
 
@@ -613,19 +4130,137 @@ pub fn ExplorerApp(
             let events = inbox.get();
             let start = cursor.get().min(events.len());
             for event in events[start..].iter() {
-                if event.topic == "explorer.refresh" {
-                    let target = event
-                        .payload
-                        .get("path")
-                        .and_then(Value::as_str)
-                        .map(str::to_string);
-                    refresh_directory(signals, explorer_service.get_value(), target);
+                match EXPLORER_REFRESH_TOPIC.decode(event) {
+                    Some(Ok(refresh)) => {
+                        refresh_directory(signals, explorer_service.get_value(), refresh.path)
+                    }
+                    Some(Err(err)) => {
+                        warn_explorer(log_service, format!("ignored explorer refresh: {err}"))
+                    }
+                    None => {}
                 }
             }
             cursor.set(events.len());
         });
     }
 
+    // Changes made by other windows, the terminal, or the native folder reload the open folders and
+    // file. Replacing a watch drops the previous one, which stops its notifications.
+    let folder_watch = store_value::<Option<ExplorerWatch>>(None);
+    create_effect(move |_| {
+        let path = cwd.get();
+        folder_watch.set_value(explorer_service.get_value().map(|explorer| {
+            watch_path(&explorer, task_service, &path, move || {
+                reload_listing(signals, explorer_service.try_get_value().flatten())
+            })
+        }));
+    });
+    let second_watch = store_value::<Option<ExplorerWatch>>(None);
+    create_effect(move |_| {
+        let path = second.cwd.get();
+        let enabled = second.enabled.get();
+        second_watch.set_value(
+            explorer_service
+                .get_value()
+                .filter(|_| enabled)
+                .map(|explorer| {
+                    watch_path(&explorer, task_service, &path, move || {
+                        if second.enabled.try_get_untracked() == Some(true) {
+                            refresh_second_pane(
+                                signals,
+                                second,
+                                explorer_service.try_get_value().flatten(),
+                                None,
+                            );
+                        }
+                    })
+                }),
+        );
+    });
+    let editor_watch = store_value::<Option<ExplorerWatch>>(None);
+    create_effect(move |_| {
+        let path = signals.editor_path.get();
+        editor_watch.set_value(
+            explorer_service
+                .get_value()
+                .zip(path)
+                .map(|(explorer, path)| {
+                    watch_path(&explorer, task_service, &path, move || {
+                        reload_editor(signals, explorer_service.try_get_value().flatten())
+                    })
+                }),
+        );
+    });
+    // Unsaved edits are autosaved as a draft once typing pauses, so a crashed or closed window
+    // does not lose them.
+    let draft_edits = Rc::new(Cell::new(0_u64));
+    create_effect(move |_| {
+        editor_text.with(|_| ());
+        if !editor_dirty.get() {
+            return;
+        }
+        let edit = draft_edits.get() + 1;
+        draft_edits.set(edit);
+        let draft_edits = Rc::clone(&draft_edits);
+        set_timeout(
+            move || {
+                if draft_edits.get() == edit {
+                    store_draft(signals, cache_service.try_get_value().flatten());
+                }
+            },
+            DRAFT_AUTOSAVE_DELAY,
+        );
+    });
+
+    // The second pane lists again whenever it is switched on and after every contents listing, so
+    // transfers and edits in either pane show up in both.
+    create_effect(move |_| {
+        entries.with(|_| ());
+        if second.enabled.get() {
+            refresh_second_pane(signals, second, explorer_service.get_value(), None);
+        }
+    });
+
+    let expand_tree_folder = move |path: &str| {
+        if folder_tree.try_update(|tree| tree.expand(path)) == Some(true) {
+            load_tree_folder(
+                folder_tree,
+                explorer_service.get_value(),
+                log_service,
+                path.to_string(),
+            );
+        }
+    };
+
+    create_effect(move |_| {
+        let cwd = cwd.get();
+        let pending = folder_tree
+            .try_update(|tree| tree.reveal(&cwd))
+            .unwrap_or_default();
+        for path in pending {
+            load_tree_folder(folder_tree, explorer_service.get_value(), log_service, path);
+        }
+        tree_focus.set(Some(cwd));
+    });
+
+    // Every contents listing also refreshes that folder's children in the tree. The first run only
+    // sees the empty placeholder listing, so it is skipped.
+    create_effect(move |previous: Option<()>| {
+        entries.with(|entries| {
+            if previous.is_some() {
+                let cwd = cwd.get_untracked();
+                folder_tree.update(|tree| tree.set_listing(&cwd, entries));
+            }
+        });
+    });
+
+    create_effect(move |_| {
+        let cwd = cwd.get();
+        if prefs_hydrated.get() && !prefs.with_untracked(|prefs| is_latest_location(prefs, &cwd)) {
+            prefs.update(|prefs| record_recent_location(prefs, &cwd));
+        }
+    });
+
     create_effect(move |_| {
         if !prefs_hydrated.get() {
             return;
@@ -635,7 +4270,7 @@ pub fn ExplorerApp(
         spawn_local(async move {
             if let Some(prefs_service) = prefs_service {
                 if let Err(err) = prefs_service.save(EXPLORER_PREFS_KEY, &prefs_value).await {
-                    logging::warn!("explorer prefs persist failed: {err}");
+                    warn_explorer(log_service, format!("explorer prefs persist failed: {err}"));
                 }
             }
         });
@@ -647,9 +4282,14 @@ pub fn ExplorerApp(
         let _ = session_store_for_name.set("explorer.ui.new_entry_name", &value);
     });
 
+    let session_store_for_wrap = session_store.clone();
+    create_effect(move |_| {
+        let _ = session_store_for_wrap.set("explorer.ui.soft_wrap", &soft_wrap.get());
+    });
+
     let session_store_for_selection = session_store.clone();
     create_effect(move |_| {
-        let value = selected_path.get();
+        let value = selection.with(|selection| selection.focus().map(str::to_string));
         let _ = session_store_for_selection.set("explorer.ui.selected_path", &value);
     });
 
@@ -657,21 +4297,35 @@ pub fn ExplorerApp(
         if hydrated.get_untracked() {
             return;
         }
-        let _ = prefs;
         let _ = last_saved;
-        prefs_hydrated.set(true);
         hydrated.set(true);
     });
 
+    spawn_local(async move {
+        if let Some(prefs_service) = prefs_service.get_value() {
+            match prefs_service
+                .load::<ExplorerPrefs>(EXPLORER_PREFS_KEY)
+                .await
+            {
+                Ok(Some(stored)) => prefs.set(stored),
+                Ok(None) => {}
+                Err(err) => {
+                    warn_explorer(log_service, format!("explorer prefs load failed: {err}"))
+                }
+            }
+        }
+        prefs_hydrated.set(true);
+    });
+
     create_effect(move |_| {
         if !hydrated.get() {
             return;
         }
-        let snapshot = persisted_snapshot(signals);
+        let snapshot = persisted_snapshot(signals, second);
         let serialized = match serde_json::to_string(&snapshot) {
             Ok(raw) => raw,
             Err(err) => {
-                logging::warn!("explorer serialize failed: {err}");
+                warn_explorer(log_service, format!("explorer serialize failed: {err}"));
                 return;
             }
         };
@@ -701,26 +4355,619 @@ pub fn ExplorerApp(
         );
     });
 
-    let visible_entries = Signal::derive(move || {
-        let show_hidden = prefs.get().show_hidden;
-        entries
+    let outside_click_listener = window_event_listener(ev::mousedown, move |_| {
+        if context_menu.get_untracked().is_some() {
+            context_menu.set(None);
+        }
+        if view_menu_open.get_untracked() {
+            view_menu_open.set(false);
+        }
+        if crumb_menu_open.get_untracked() {
+            crumb_menu_open.set(false);
+        }
+    });
+    on_cleanup(move || outside_click_listener.remove());
+    on_cleanup(move || {
+        if let Some(Some(preview)) = preview.try_get_untracked() {
+            preview.revoke();
+        }
+    });
+
+    let close_context_menu = move || {
+        context_menu.set(None);
+        if let Some(element) = menu_return_focus.get_value() {
+            menu_return_focus.set_value(None);
+            let _ = element.focus();
+        }
+    };
+    let start_rename = move |path: String, surface: RenameSurface| {
+        context_menu.set(None);
+        if path == "/" {
+            set_error(signals, "Cannot rename the root directory");
+            return;
+        }
+        rename_draft.set(entry_name(&path));
+        renaming.set(Some(ExplorerRename { path, surface }));
+    };
+    // Opens the bulk rename dialog over the selection, numbered in listing order.
+    let start_bulk_rename = move || {
+        context_menu.set(None);
+        let selection = signals.selection.get_untracked();
+        let (sources, others) = entries.with_untracked(|entries| {
+            let mut rows = entries.clone();
+            prefs.with_untracked(|prefs| sort_entries(&mut rows, prefs));
+            let (selected, others): (Vec<_>, Vec<_>) = rows
+                .into_iter()
+                .partition(|entry| selection.contains(&entry.path));
+            (
+                selected
+                    .into_iter()
+                    .map(|entry| BulkRenameSource {
+                        is_file: entry.kind == ExplorerEntryKind::File,
+                        name: entry.name,
+                    })
+                    .collect::<Vec<_>>(),
+                others
+                    .into_iter()
+                    .map(|entry| entry.name)
+                    .collect::<Vec<_>>(),
+            )
+        });
+        if sources.is_empty() {
+            set_error(signals, "Select files or folders first");
+            return;
+        }
+        pending_bulk_rename.set(Some(PendingBulkRename {
+            folder: cwd.get_untracked(),
+            sources,
+            others,
+        }));
+    };
+    let commit_rename = Callback::new(move |()| {
+        let Some(target) = renaming.get_untracked() else {
+            return;
+        };
+        if rename_entry(
+            signals,
+            explorer_service.get_value(),
+            cache_service.get_value(),
+            target.path,
+            rename_draft.get_untracked(),
+        ) {
+            renaming.set(None);
+        }
+    });
+    let cancel_rename = Callback::new(move |()| renaming.set(None));
+
+    let drag_over_folder = move |ev: &DragEvent, folder: &str| {
+        let effect = match drop_transfer_kind(ev) {
+            TransferKind::Move => DragEffect::Move,
+            TransferKind::Copy => DragEffect::Copy,
+        };
+        if !accept_drag_over(ev, FILE_DRAG_DATA_TYPE, effect) {
+            return;
+        }
+        if drop_target.with_untracked(|target| target.as_deref() != Some(folder)) {
+            drop_target.set(Some(folder.to_string()));
+        }
+    };
+    let drag_leave_folder = move |folder: &str| {
+        if drop_target.with_untracked(|target| target.as_deref() == Some(folder)) {
+            drop_target.set(None);
+        }
+    };
+    let drop_on_folder = move |ev: &DragEvent, folder: String| {
+        drop_target.set(None);
+        let Some(paths) = dropped_entry_paths(ev) else {
+            return;
+        };
+        transfer_entries(
+            signals,
+            explorer_service.get_value(),
+            cache_service.get_value(),
+            paths,
+            folder,
+            drop_transfer_kind(ev),
+        );
+    };
+    let is_drop_target =
+        move |folder: &str| drop_target.with(|target| target.as_deref() == Some(folder));
+    let crumb_button = move |crumb: Crumb, current: bool| {
+        let folder = store_value(crumb.path.clone());
+        view! {
+            <span
+                data-ui-slot="crumb"
+                aria-current=current.then_some("location")
+                data-ui-drop-target=move || {
+                    if folder.with_value(|folder| is_drop_target(folder)) { "true" } else { "false" }
+                }
+                on:dragover=move |ev: DragEvent| folder.with_value(|folder| drag_over_folder(&ev, folder))
+                on:dragleave=move |_| folder.with_value(|folder| drag_leave_folder(folder))
+                on:drop=move |ev: DragEvent| drop_on_folder(&ev, folder.get_value())
+            >
+                <Button
+                    variant=ButtonVariant::Quiet
+                    title=crumb.path.clone()
+                    on_click=Callback::new(move |_| {
+                        refresh_directory(signals, explorer_service.get_value(), Some(folder.get_value()))
+                    })
+                >
+                    {crumb.label}
+                </Button>
+            </span>
+        }
+    };
+    let location_item = move |path: String, pinned: bool| {
+        let folder = store_value(path);
+        let label = location_label(&folder.get_value()).to_string();
+        let unpin_label = format!("Unpin {label}");
+        view! {
+            <TreeItem
+                selected=Signal::derive(move || folder.with_value(|folder| cwd.get() == *folder))
+                drop_target=Signal::derive(move || folder.with_value(|folder| is_drop_target(folder)))
+                on_dragover=Callback::new(move |ev: DragEvent| folder.with_value(|folder| drag_over_folder(&ev, folder)))
+                on_dragleave=Callback::new(move |_| folder.with_value(|folder| drag_leave_folder(folder)))
+                on_drop=Callback::new(move |ev: DragEvent| drop_on_folder(&ev, folder.get_value()))
+            >
+                <Button
+                    variant=ButtonVariant::Quiet
+                    title=folder.get_value()
+                    on_click=Callback::new(move |_| {
+                        refresh_directory(signals, explorer_service.get_value(), Some(folder.get_value()))
+                    })
+                >
+                    <Icon icon=IconName::ExplorerFolder/>
+                    <span>{label}</span>
+                </Button>
+                {pinned.then(|| view! {
+                    <Button
+                        variant=ButtonVariant::Quiet
+                        aria_label=unpin_label
+                        on_click=Callback::new(move |_| {
+                            folder.with_value(|folder| prefs.update(|prefs| toggle_favorite(prefs, folder)))
+                        })
+                    >
+                        "Unpin"
+                    </Button>
+                })}
+            </TreeItem>
+        }
+    };
+    let folder_tree_item = move |row: FolderTreeRow| {
+        let folder = store_value(row.path.clone());
+        let twisty = match (row.loading, row.has_children, row.expanded) {
+            (true, _, _) => "…",
+            (false, Some(false), _) => "",
+            (false, _, true) => "▾",
+            (false, _, false) => "▸",
+        };
+        view! {
+            <TreeItem
+                id=folder_tree_dom_id(&row.path)
+                role="treeitem"
+                aria_level=row.level
+                aria_expanded=(row.has_children != Some(false)).then_some(row.expanded)
+                selected=Signal::derive(move || folder.with_value(|folder| cwd.get() == *folder))
+                drop_target=Signal::derive(move || folder.with_value(|folder| is_drop_target(folder)))
+                on_click=Callback::new(move |_| {
+                    tree_focus.set(Some(folder.get_value()));
+                    refresh_directory(signals, explorer_service.get_value(), Some(folder.get_value()));
+                })
+                on_dragover=Callback::new(move |ev: DragEvent| folder.with_value(|folder| drag_over_folder(&ev, folder)))
+                on_dragleave=Callback::new(move |_| folder.with_value(|folder| drag_leave_folder(folder)))
+                on_drop=Callback::new(move |ev: DragEvent| drop_on_folder(&ev, folder.get_value()))
+            >
+                <span
+                    data-ui-slot="folder-tree-row"
+                    data-ui-focused=move || {
+                        if folder.with_value(|folder| tree_focus.with(|focus| focus.as_ref() == Some(folder))) {
+                            "true"
+                        } else {
+                            "false"
+                        }
+                    }
+                    title=row.path.clone()
+                >
+                    <span
+                        data-ui-slot="folder-tree-twisty"
+                        aria-hidden="true"
+                        on:click=move |ev: ev::MouseEvent| {
+                            ev.stop_propagation();
+                            folder.with_value(|folder| {
+                                if folder_tree.with_untracked(|tree| tree.is_expanded(folder)) {
+                                    folder_tree.update(|tree| tree.collapse(folder));
+                                } else {
+                                    expand_tree_folder(folder);
+                                }
+                            });
+                        }
+                    >
+                        {twisty}
+                    </span>
+                    <Icon icon=IconName::ExplorerFolder/>
+                    <span>{row.name}</span>
+                </span>
+            </TreeItem>
+        }
+    };
+    let on_folder_tree_keydown = move |ev: ev::KeyboardEvent| {
+        let focused = tree_focus.get_untracked();
+        if matches!(ev.key().as_str(), "Enter" | " ") {
+            ev.prevent_default();
+            if let Some(path) = focused {
+                refresh_directory(signals, explorer_service.get_value(), Some(path));
+            }
+            return;
+        }
+        let show_hidden = prefs.with_untracked(|prefs| prefs.show_hidden);
+        let rows = folder_tree.with_untracked(|tree| tree.rows(show_hidden));
+        let Some(step) = tree_key_move(&rows, focused.as_deref(), &ev.key()) else {
+            return;
+        };
+        ev.prevent_default();
+        match step {
+            TreeMove::Focus(path) => tree_focus.set(Some(path)),
+            TreeMove::Expand(path) => expand_tree_folder(&path),
+            TreeMove::Collapse(path) => folder_tree.update(|tree| tree.collapse(&path)),
+        }
+    };
+    let is_renaming = move |path: &str, surface: RenameSurface| {
+        renaming.with(|target| {
+            target
+                .as_ref()
+                .is_some_and(|target| target.path == path && target.surface == surface)
+        })
+    };
+
+    let visible_entries = create_memo(move |_| {
+        let prefs = prefs.get();
+        let mut rows = entries
             .get()
             .into_iter()
-            .filter(|entry| show_hidden || !entry.name.starts_with('.'))
-            .collect::<Vec<_>>()
+            .filter(|entry| prefs.show_hidden || !entry.name.starts_with('.'))
+            .collect::<Vec<_>>();
+        sort_entries(&mut rows, &prefs);
+        rows
+    });
+    let editor_lines = create_memo(move |_| {
+        let language = editor_path.with(|path| {
+            path.as_deref()
+                .map_or(CodeLanguage::Plain, CodeLanguage::for_path)
+        });
+        editor_text.with(|text| highlight(text, language))
+    });
+    let rendered_window = create_memo(move |_| {
+        listing_window(visible_entries.with(Vec::len), listing_viewport.get())
+    });
+    let rendered_entries = Signal::derive(move || {
+        let window = rendered_window.get();
+        visible_entries.with(|rows| {
+            rows.get(window.start..window.end)
+                .unwrap_or_default()
+                .to_vec()
+        })
+    });
+    let spacer_height = move |lines: fn(&ListingWindow) -> usize| {
+        Signal::derive(move || {
+            rendered_window.with(lines) as f64
+                * listing_viewport.with(|viewport| viewport.line_height)
+        })
+    };
+    let spacer_before = spacer_height(|window| window.lines_before);
+    let spacer_after = spacer_height(|window| window.lines_after);
+    let measure_primary_listing = move || {
+        if let Some(surface) = primary_listing.get_untracked() {
+            let measured = measure_listing(&surface, listing_viewport.get_untracked());
+            if measured != listing_viewport.get_untracked() {
+                listing_viewport.set(measured);
+            }
+        }
+    };
+    // Line geometry depends on the layout, so it is measured again once each listing renders.
+    create_effect(move |_| {
+        prefs.with(|prefs| prefs.view_mode);
+        visible_entries.with(|_| ());
+        request_animation_frame(measure_primary_listing);
+    });
+    // Keyboard moves can focus an entry that is not rendered, so it is scrolled into view.
+    create_effect(move |_| {
+        let Some(focus) = selection.with(|selection| selection.focus().map(str::to_string)) else {
+            return;
+        };
+        if !rendered_window.with_untracked(ListingWindow::is_partial) {
+            return;
+        }
+        let index = visible_entries
+            .with_untracked(|rows| rows.iter().position(|entry| entry.path == focus));
+        let offset = index.and_then(|index| reveal_offset(index, listing_viewport.get_untracked()));
+        if let (Some(offset), Some(surface)) = (offset, primary_listing.get_untracked()) {
+            surface.set_scroll_top(offset as i32);
+            measure_primary_listing();
+        }
     });
+    let select_entry = move |path: String, extend: bool, toggle: bool| {
+        let order = visible_entries.with_untracked(|rows| {
+            rows.iter()
+                .map(|entry| entry.path.clone())
+                .collect::<Vec<_>>()
+        });
+        signals.selection.update(|selection| {
+            if extend {
+                selection.extend_to(&order, path.clone());
+            } else if toggle {
+                selection.toggle(path.clone());
+            } else {
+                selection.select_only(path.clone());
+            }
+        });
+        inspect_path(signals, explorer_service.get_value(), path);
+    };
+    let open_entry = move |entry: &ExplorerEntry| {
+        signals
+            .selection
+            .set(ExplorerSelection::single(entry.path.clone()));
+        match entry.kind {
+            ExplorerEntryKind::Directory => refresh_directory(
+                signals,
+                explorer_service.get_value(),
+                Some(entry.path.clone()),
+            ),
+            ExplorerEntryKind::File => open_file_in_default_app(
+                signals,
+                explorer_service.get_value(),
+                cache_service.get_value(),
+                app_services.get_value(),
+                entry.path.clone(),
+            ),
+        }
+    };
+    let press_entry = move |ev: &ev::MouseEvent, path: &str| {
+        if ev.button() != 0 {
+            return;
+        }
+        let command = ev.ctrl_key() || ev.meta_key();
+        let already_selected = signals
+            .selection
+            .with_untracked(|selection| selection.contains(path));
+        // A plain press on a selected entry keeps the group so it can be dragged; the click that
+        // follows narrows it.
+        if ev.shift_key() || command || !already_selected {
+            select_entry(path.to_string(), ev.shift_key(), command);
+        }
+    };
+    let click_entry = move |ev: &ev::MouseEvent, path: &str| {
+        let plain = !(ev.shift_key() || ev.ctrl_key() || ev.meta_key());
+        if plain
+            && signals
+                .selection
+                .with_untracked(|selection| selection.len() > 1)
+        {
+            select_entry(path.to_string(), false, false);
+        }
+    };
+    // Keeps the Open With entries for associated apps current with Settings.
+    let reload_associations = move || {
+        if let Some((config, _)) = app_services.get_value() {
+            spawn_local(async move {
+                associations.set(load_associations(&config, log_service).await);
+            });
+        }
+    };
+    let open_entry_menu = move |ev: &ev::MouseEvent, path: &str| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        if !signals
+            .selection
+            .with_untracked(|selection| selection.contains(path))
+        {
+            select_entry(path.to_string(), false, false);
+        }
+        menu_return_focus.set_value(None);
+        reload_associations();
+        context_menu.set(Some(ExplorerContextMenu {
+            target: entries
+                .with_untracked(|entries| entries.iter().find(|entry| entry.path == path).cloned()),
+            x: ev.client_x(),
+            y: ev.client_y(),
+        }));
+    };
+    let open_folder_menu = move |ev: ev::MouseEvent| {
+        ev.prevent_default();
+        menu_return_focus.set_value(None);
+        context_menu.set(Some(ExplorerContextMenu {
+            target: None,
+            x: ev.client_x(),
+            y: ev.client_y(),
+        }));
+    };
+    // The Menu key has no pointer position, so the menu opens below the focused row, or at the
+    // corner of the listing when nothing is focused.
+    let open_menu_from_keyboard = move |ev: &ev::KeyboardEvent| {
+        let listing = ev
+            .current_target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok());
+        let target = focused_path(signals).and_then(|path| {
+            entries
+                .with_untracked(|entries| entries.iter().find(|entry| entry.path == path).cloned())
+        });
+        let row = target
+            .as_ref()
+            .and_then(|entry| document().get_element_by_id(&explorer_row_dom_id(&entry.path)));
+        let (x, y) = match (&row, &listing) {
+            (Some(row), _) => {
+                let rect = row.get_bounding_client_rect();
+                (rect.left() as i32, rect.bottom() as i32)
+            }
+            (None, Some(listing)) => {
+                let rect = listing.get_bounding_client_rect();
+                (rect.left() as i32, rect.top() as i32)
+            }
+            (None, None) => (0, 0),
+        };
+        menu_return_focus.set_value(listing);
+        reload_associations();
+        context_menu.set(Some(ExplorerContextMenu { target, x, y }));
+    };
+    let mark_for_paste = move |kind: TransferKind| {
+        let paths = selected_paths(signals);
+        if paths.is_empty() {
+            set_error(signals, "Select files or folders first");
+            return;
+        }
+        let verb = match kind {
+            TransferKind::Move => "Cut",
+            TransferKind::Copy => "Copied",
+        };
+        let count = paths.len();
+        let noun = if count == 1 { "item" } else { "items" };
+        set_notice(
+            signals,
+            format!("{verb} {count} {noun}; paste into a folder to finish"),
+        );
+        clipboard.set(Some(ExplorerClipboard { kind, paths }));
+    };
+    let paste_clipboard = move || {
+        let Some(marked) = clipboard.get_untracked() else {
+            return;
+        };
+        // Cut entries move once; copied entries stay marked for further pastes.
+        if marked.kind == TransferKind::Move {
+            clipboard.set(None);
+        }
+        transfer_entries(
+            signals,
+            explorer_service.get_value(),
+            cache_service.get_value(),
+            marked.paths,
+            cwd.get_untracked(),
+            marked.kind,
+        );
+    };
+    let focus_pane = move |pane: ActivePane| {
+        let listing = match pane {
+            ActivePane::Primary => primary_listing
+                .get_untracked()
+                .and_then(|surface| surface.query_selector("[tabindex=\"0\"]").ok().flatten()),
+            ActivePane::Secondary => document()
+                .query_selector("[data-ui-slot=\"commander-pane\"] [tabindex=\"0\"]")
+                .ok()
+                .flatten(),
+        };
+        if let Some(listing) =
+            listing.and_then(|listing| listing.dyn_into::<web_sys::HtmlElement>().ok())
+        {
+            let _ = listing.focus();
+        }
+    };
+    // Copies or moves the active pane's selection into the other pane's folder.
+    let run_pane_command = move |command: PaneCommand| {
+        let active = second.active.get_untracked();
+        let kind = match command {
+            PaneCommand::Switch => {
+                focus_pane(active.other());
+                return;
+            }
+            PaneCommand::Copy => TransferKind::Copy,
+            PaneCommand::Move => TransferKind::Move,
+        };
+        let (paths, destination) = match active {
+            ActivePane::Primary => (selected_paths(signals), second.cwd.get_untracked()),
+            ActivePane::Secondary => (
+                second
+                    .selection
+                    .with_untracked(|selection| selection.paths().to_vec()),
+                cwd.get_untracked(),
+            ),
+        };
+        transfer_entries(
+            signals,
+            explorer_service.get_value(),
+            cache_service.get_value(),
+            paths,
+            destination,
+            kind,
+        );
+    };
+    let drag_entries = move |ev: &DragEvent, path: &str| {
+        let paths = signals.selection.with_untracked(|selection| {
+            if selection.contains(path) {
+                selection.paths().to_vec()
+            } else {
+                vec![path.to_string()]
+            }
+        });
+        start_entry_drag(ev, &paths);
+    };
+    let type_ahead = store_value(TypeAhead::default());
     let on_list_grid_keydown = move |ev: ev::KeyboardEvent| {
+        if is_context_menu_key(&ev) {
+            ev.prevent_default();
+            open_menu_from_keyboard(&ev);
+            return;
+        }
+        if second.enabled.get_untracked() {
+            if let Some(command) = pane_command(&ev.key()) {
+                ev.prevent_default();
+                run_pane_command(command);
+                return;
+            }
+        }
+        if (ev.ctrl_key() || ev.meta_key())
+            && ev.key().eq_ignore_ascii_case("v")
+            && clipboard.with_untracked(Option::is_some)
+        {
+            ev.prevent_default();
+            paste_clipboard();
+            return;
+        }
         let rows = visible_entries.get_untracked();
         if rows.is_empty() {
             return;
         }
 
-        let selected = selected_path.get_untracked();
-        let current_index = selected
+        let focused = focused_path(signals);
+        let current_index = focused
             .as_deref()
             .and_then(|path| rows.iter().position(|entry| entry.path == path));
         let last_index = rows.len().saturating_sub(1);
-        let key = ev.key();
+        let extend = ev.shift_key();
+        let command = ev.ctrl_key() || ev.meta_key();
+        let icons = prefs.with_untracked(|prefs| prefs.view_mode == ExplorerViewMode::Icons);
+        // Icons wrap into rows, so Left/Right step through entries the way Up/Down do.
+        let key = match ev.key().as_str() {
+            "ArrowRight" if icons => "ArrowDown".to_string(),
+            "ArrowLeft" if icons => "ArrowUp".to_string(),
+            key => key.to_string(),
+        };
+
+        // Space toggles the selection unless it continues a type-ahead prefix.
+        let typing = type_ahead_char(&key, command || ev.alt_key()).filter(|&ch| {
+            ch != ' ' || type_ahead.with_value(|type_ahead| !type_ahead.typed().is_empty())
+        });
+        if let Some(ch) = typing {
+            ev.prevent_default();
+            let Some(generation) = type_ahead.try_update_value(|type_ahead| type_ahead.push(ch))
+            else {
+                return;
+            };
+            set_timeout(
+                move || {
+                    type_ahead.try_update_value(|type_ahead| type_ahead.expire(generation));
+                },
+                TYPE_AHEAD_RESET_DELAY,
+            );
+            let names = rows
+                .iter()
+                .map(|entry| entry.name.as_str())
+                .collect::<Vec<_>>();
+            let found = type_ahead.with_value(|type_ahead| {
+                type_ahead_match(&names, type_ahead.typed(), current_index)
+            });
+            if let Some(index) = found {
+                select_entry(rows[index].path.clone(), false, false);
+            }
+            return;
+        }
 
         match key.as_str() {
             "ArrowDown" => {
@@ -728,53 +4975,88 @@ pub fn ExplorerApp(
                 let next = current_index
                     .map(|idx| (idx + 1).min(last_index))
                     .unwrap_or(0);
-                let entry = rows[next].clone();
-                signals.selected_path.set(Some(entry.path.clone()));
-                inspect_path(signals, explorer_service.get_value(), entry.path);
+                select_entry(rows[next].path.clone(), extend, false);
             }
             "ArrowUp" => {
                 ev.prevent_default();
                 let next = current_index
                     .map(|idx| idx.saturating_sub(1))
                     .unwrap_or(last_index);
-                let entry = rows[next].clone();
-                signals.selected_path.set(Some(entry.path.clone()));
-                inspect_path(signals, explorer_service.get_value(), entry.path);
+                select_entry(rows[next].path.clone(), extend, false);
             }
             "Home" => {
                 ev.prevent_default();
-                let entry = rows[0].clone();
-                signals.selected_path.set(Some(entry.path.clone()));
-                inspect_path(signals, explorer_service.get_value(), entry.path);
+                select_entry(rows[0].path.clone(), extend, false);
             }
             "End" => {
                 ev.prevent_default();
-                let entry = rows[last_index].clone();
-                signals.selected_path.set(Some(entry.path.clone()));
-                inspect_path(signals, explorer_service.get_value(), entry.path);
+                select_entry(rows[last_index].path.clone(), extend, false);
             }
             " " | "Spacebar" => {
                 ev.prevent_default();
                 let index = current_index.unwrap_or(0);
-                let entry = rows[index].clone();
-                signals.selected_path.set(Some(entry.path.clone()));
-                inspect_path(signals, explorer_service.get_value(), entry.path);
+                select_entry(rows[index].path.clone(), extend, command);
             }
-            "Enter" => {
+            "a" | "A" if command => {
                 ev.prevent_default();
-                let index = current_index.unwrap_or(0);
-                let entry = rows[index].clone();
-                signals.selected_path.set(Some(entry.path.clone()));
-                match entry.kind {
-                    ExplorerEntryKind::Directory => {
-                        refresh_directory(signals, explorer_service.get_value(), Some(entry.path))
-                    }
-                    ExplorerEntryKind::File => open_file(
-                        signals,
+                let order = rows
+                    .iter()
+                    .map(|entry| entry.path.clone())
+                    .collect::<Vec<_>>();
+                signals
+                    .selection
+                    .update(|selection| selection.select_all(&order));
+            }
+            "c" | "C" if command => {
+                ev.prevent_default();
+                mark_for_paste(TransferKind::Copy);
+            }
+            "x" | "X" if command => {
+                ev.prevent_default();
+                mark_for_paste(TransferKind::Move);
+            }
+            "z" | "Z" if command && signals.undo.with_untracked(Option::is_some) => {
+                ev.prevent_default();
+                undo_last(
+                    signals,
+                    explorer_service.get_value(),
+                    cache_service.get_value(),
+                );
+            }
+            "Escape"
+                if !signals
+                    .selection
+                    .with_untracked(ExplorerSelection::is_empty) =>
+            {
+                ev.prevent_default();
+                signals.selection.update(ExplorerSelection::clear);
+                signals.selected_metadata.set(None);
+            }
+            "Enter" if ev.alt_key() => {
+                ev.prevent_default();
+                if let Some(index) = current_index {
+                    open_properties(
+                        properties,
                         explorer_service.get_value(),
-                        cache_service.get_value(),
-                        entry.path,
-                    ),
+                        rows[index].path.clone(),
+                    );
+                }
+            }
+            "Enter" => {
+                ev.prevent_default();
+                open_entry(&rows[current_index.unwrap_or(0)]);
+            }
+            "F2" if signals
+                .selection
+                .with_untracked(|selection| selection.len() > 1) =>
+            {
+                ev.prevent_default();
+                start_bulk_rename();
+            }
+            "F2" => {
+                ev.prevent_default();
+                if let Some(index) = current_index {
+                    start_rename(rows[index].path.clone(), RenameSurface::Row);
                 }
             }
             _ => {}
@@ -792,10 +5074,74 @@ pub fn ExplorerApp(
     });
 
     view! {
-        <AppShell>
+        <AppShell on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+            if (ev.ctrl_key() || ev.meta_key()) && ev.key().eq_ignore_ascii_case("l") {
+                ev.prevent_default();
+                editing_path.set(true);
+            }
+        })>
             <MenuBar aria_label="Explorer menu">
                 <Button variant=ButtonVariant::Quiet>"File"</Button>
-                <Button variant=ButtonVariant::Quiet>"View"</Button>
+                <span data-ui-slot="menu-anchor">
+                    <Button
+                        variant=ButtonVariant::Quiet
+                        aria_haspopup="menu"
+                        aria_expanded=Signal::derive(move || view_menu_open.get())
+                        on_mousedown=Callback::new(move |ev: ev::MouseEvent| ev.stop_propagation())
+                        on_click=Callback::new(move |_| view_menu_open.update(|open| *open = !*open))
+                    >
+                        "View"
+                    </Button>
+                    <Show when=move || view_menu_open.get() fallback=|| ()>
+                        <MenuSurface
+                            role="menu"
+                            aria_label="Explorer view menu"
+                            ui_slot="explorer-view-menu"
+                            on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+                                if ev.key() == "Escape" {
+                                    ev.prevent_default();
+                                    view_menu_open.set(false);
+                                }
+                            })
+                            on_mousedown=Callback::new(move |ev: ev::MouseEvent| ev.stop_propagation())
+                        >
+                            {[
+                                (ExplorerViewMode::Details, "Details"),
+                                (ExplorerViewMode::Icons, "Icons"),
+                            ]
+                                .into_iter()
+                                .map(move |(mode, label)| {
+                                    let checked = move || prefs.with(|prefs| prefs.view_mode == mode);
+                                    view! {
+                                        <MenuItem
+                                            role="menuitemradio"
+                                            aria_checked=Signal::derive(move || checked().to_string())
+                                            selected=Signal::derive(checked)
+                                            on_click=Callback::new(move |_| {
+                                                prefs.update(|prefs| prefs.view_mode = mode);
+                                                view_menu_open.set(false);
+                                            })
+                                        >
+                                            {label}
+                                        </MenuItem>
+                                    }
+                                })
+                                .collect_view()}
+                            <MenuItem
+                                role="menuitemcheckbox"
+                                aria_checked=Signal::derive(move || second.enabled.get().to_string())
+                                selected=Signal::derive(move || second.enabled.get())
+                                on_click=Callback::new(move |_| {
+                                    second.enabled.update(|enabled| *enabled = !*enabled);
+                                    second.active.set(ActivePane::Primary);
+                                    view_menu_open.set(false);
+                                })
+                            >
+                                "Dual Pane"
+                            </MenuItem>
+                        </MenuSurface>
+                    </Show>
+                </span>
                 <Button variant=ButtonVariant::Quiet>"Help"</Button>
             </MenuBar>
 
@@ -833,6 +5179,60 @@ pub fn ExplorerApp(
                                 >
                                     {move || if show_create_panel.get() { "Hide New" } else { "New" }}
                                 </Button>
+                                <Button
+                                    variant=ButtonVariant::Quiet
+                                    title="Copy files from this computer into the current folder"
+                                    on_click=Callback::new(move |_| {
+                                        import_files(signals, explorer_service.get_value(), cwd.get_untracked());
+                                    })
+                                >
+                                    "Import"
+                                </Button>
+                                <Button
+                                    variant=ButtonVariant::Quiet
+                                    title="Save the selected files to this computer"
+                                    disabled=Signal::derive(move || {
+                                        selection.with(ExplorerSelection::is_empty)
+                                    })
+                                    on_click=Callback::new(move |_| {
+                                        download_selected(signals, explorer_service.get_value());
+                                    })
+                                >
+                                    "Download"
+                                </Button>
+                                <Button
+                                    variant=ButtonVariant::Quiet
+                                    selected=Signal::derive(move || show_search_panel.get())
+                                    on_click=Callback::new(move |_| {
+                                        show_search_panel.update(|open| *open = !*open);
+                                    })
+                                >
+                                    "Search"
+                                </Button>
+                                <Button
+                                    variant=ButtonVariant::Quiet
+                                    selected=Signal::derive(move || show_trash_panel.get())
+                                    on_click=Callback::new(move |_| {
+                                        show_trash_panel.update(|open| *open = !*open);
+                                        if show_trash_panel.get_untracked() {
+                                            refresh_trash(signals, explorer_service.get_value());
+                                        }
+                                    })
+                                >
+                                    "Trash"
+                                </Button>
+                                <Button
+                                    variant=ButtonVariant::Quiet
+                                    selected=Signal::derive(move || show_storage_panel.get())
+                                    on_click=Callback::new(move |_| {
+                                        show_storage_panel.update(|open| *open = !*open);
+                                        if show_storage_panel.get_untracked() {
+                                            refresh_storage(explorer_service.get_value(), storage);
+                                        }
+                                    })
+                                >
+                                    "Storage"
+                                </Button>
                                 <Button
                                     variant=ButtonVariant::Quiet
                                     disabled=Signal::derive(move || !editor_dirty.get())
@@ -841,6 +5241,8 @@ pub fn ExplorerApp(
                                             signals,
                                             explorer_service.get_value(),
                                             cache_service.get_value(),
+                                            save_conflict,
+                                            true,
                                         );
                                     })
                                 >
@@ -848,6 +5250,107 @@ pub fn ExplorerApp(
                                 </Button>
                             </ToolBar>
 
+                            <nav data-ui-slot="path-bar" aria-label="Folder path">
+                                <Show
+                                    when=move || editing_path.get()
+                                    fallback=move || {
+                                        let BreadcrumbTrail { leading, collapsed, trailing } =
+                                            breadcrumb_trail(&cwd.get(), MAX_VISIBLE_CRUMBS);
+                                        let last = trailing
+                                            .last()
+                                            .or(leading.last())
+                                            .map(|crumb| crumb.path.clone());
+                                        let current = move |crumb: &Crumb| Some(&crumb.path) == last.as_ref();
+                                        let collapsed = store_value(collapsed);
+                                        view! {
+                                            {leading
+                                                .into_iter()
+                                                .map(|crumb| {
+                                                    let is_current = current(&crumb);
+                                                    crumb_button(crumb, is_current)
+                                                })
+                                                .collect_view()}
+                                            <Show
+                                                when=move || collapsed.with_value(|collapsed| !collapsed.is_empty())
+                                                fallback=|| ()
+                                            >
+                                                <span data-ui-slot="menu-anchor">
+                                                    <Button
+                                                        variant=ButtonVariant::Quiet
+                                                        aria_label="Show hidden path folders"
+                                                        aria_haspopup="menu"
+                                                        aria_expanded=Signal::derive(move || crumb_menu_open.get())
+                                                        on_mousedown=Callback::new(move |ev: ev::MouseEvent| ev.stop_propagation())
+                                                        on_click=Callback::new(move |_| crumb_menu_open.update(|open| *open = !*open))
+                                                    >
+                                                        "..."
+                                                    </Button>
+                                                    <Show when=move || crumb_menu_open.get() fallback=|| ()>
+                                                        <MenuSurface
+                                                            role="menu"
+                                                            aria_label="Hidden path folders"
+                                                            ui_slot="breadcrumb-menu"
+                                                            on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+                                                                if ev.key() == "Escape" {
+                                                                    ev.prevent_default();
+                                                                    crumb_menu_open.set(false);
+                                                                }
+                                                            })
+                                                            on_mousedown=Callback::new(move |ev: ev::MouseEvent| ev.stop_propagation())
+                                                        >
+                                                            {collapsed
+                                                                .get_value()
+                                                                .into_iter()
+                                                                .map(|crumb| view! {
+                                                                    <MenuItem
+                                                                        on_click=Callback::new(move |_| {
+                                                                            crumb_menu_open.set(false);
+                                                                            refresh_directory(
+                                                                                signals,
+                                                                                explorer_service.get_value(),
+                                                                                Some(crumb.path.clone()),
+                                                                            );
+                                                                        })
+                                                                    >
+                                                                        {crumb.label.clone()}
+                                                                    </MenuItem>
+                                                                })
+                                                                .collect_view()}
+                                                        </MenuSurface>
+                                                    </Show>
+                                                </span>
+                                            </Show>
+                                            {trailing
+                                                .into_iter()
+                                                .map(|crumb| {
+                                                    let is_current = current(&crumb);
+                                                    crumb_button(crumb, is_current)
+                                                })
+                                                .collect_view()}
+                                            <Button
+                                                variant=ButtonVariant::Quiet
+                                                aria_keyshortcuts="Ctrl+L"
+                                                title="Type a path (Ctrl+L)"
+                                                on_click=Callback::new(move |_| editing_path.set(true))
+                                            >
+                                                "Edit Path"
+                                            </Button>
+                                        }
+                                    }
+                                >
+                                    <PathField
+                                        initial=cwd.get_untracked()
+                                        explorer=explorer_service.get_value()
+                                        show_hidden=Signal::derive(move || prefs.with(|prefs| prefs.show_hidden))
+                                        on_navigate=Callback::new(move |path: String| {
+                                            editing_path.set(false);
+                                            refresh_directory(signals, explorer_service.get_value(), Some(path));
+                                        })
+                                        on_cancel=Callback::new(move |_| editing_path.set(false))
+                                    />
+                                </Show>
+                            </nav>
+
                         <DisclosurePanel
                                 title="Workspace actions"
                                 description="Advanced filesystem and permission actions stay here until they are needed."
@@ -865,29 +5368,67 @@ pub fn ExplorerApp(
                                             connect_native_folder(signals, explorer_service.get_value())
                                         })
                                     >
-                                        "Connect Folder"
+                                        "Connect Folder"
+                                    </Button>
+                                    <Button
+                                        variant=ButtonVariant::Quiet
+                                        title=native_explorer_status_label(native_explorer)
+                                        disabled=!can_connect_native_folder(native_explorer)
+                                        on_click=Callback::new(move |_| {
+                                            request_rw_permission(signals, explorer_service.get_value())
+                                        })
+                                    >
+                                        "Request RW"
+                                    </Button>
+                                    <Button
+                                        variant=ButtonVariant::Danger
+                                        on_click=Callback::new(move |_| {
+                                            delete_selected(
+                                                signals,
+                                                explorer_service.get_value(),
+                                                cache_service.get_value(),
+                                            );
+                                        })
+                                    >
+                                        "Delete Selection"
                                     </Button>
+                                    <TextField
+                                        placeholder="Destination folder"
+                                        aria_label="Destination folder for the selection"
+                                        value=Signal::derive(move || transfer_destination.get())
+                                        on_input=Callback::new(move |ev| {
+                                            transfer_destination.set(event_target_value(&ev));
+                                        })
+                                    />
                                     <Button
                                         variant=ButtonVariant::Quiet
-                                        title=native_explorer_status_label(native_explorer)
-                                        disabled=!can_connect_native_folder(native_explorer)
                                         on_click=Callback::new(move |_| {
-                                            request_rw_permission(signals, explorer_service.get_value())
+                                            transfer_entries(
+                                                signals,
+                                                explorer_service.get_value(),
+                                                cache_service.get_value(),
+                                                selected_paths(signals),
+                                                transfer_destination.get_untracked(),
+                                                TransferKind::Move,
+                                            );
                                         })
                                     >
-                                        "Request RW"
+                                        "Move Selection"
                                     </Button>
                                     <Button
-                                        variant=ButtonVariant::Danger
+                                        variant=ButtonVariant::Quiet
                                         on_click=Callback::new(move |_| {
-                                            delete_selected(
+                                            transfer_entries(
                                                 signals,
                                                 explorer_service.get_value(),
                                                 cache_service.get_value(),
+                                                selected_paths(signals),
+                                                transfer_destination.get_untracked(),
+                                                TransferKind::Copy,
                                             );
                                         })
                                     >
-                                        "Delete Selection"
+                                        "Copy Selection"
                                     </Button>
                                     <Button
                                         variant=ButtonVariant::Quiet
@@ -895,6 +5436,8 @@ pub fn ExplorerApp(
                                             signals.editor_path.set(None);
                                             signals.editor_text.set(String::new());
                                             signals.editor_dirty.set(false);
+                                            signals.editor_base.set(None);
+                                            signals.editor_draft.set(None);
                                         })
                                     >
                                         "Close Editor"
@@ -929,9 +5472,273 @@ pub fn ExplorerApp(
                                     >
                                         {move || if prefs.get().show_hidden { "Hidden Visible" } else { "Show Hidden" }}
                                     </Button>
+                                    <Button
+                                        variant=ButtonVariant::Quiet
+                                        selected=Signal::derive(move || prefs.get().directories_first)
+                                        on_click=Callback::new(move |_| {
+                                            prefs.update(|p| p.directories_first = !p.directories_first)
+                                        })
+                                    >
+                                        {move || if prefs.get().directories_first { "Folders First" } else { "Show Folders First" }}
+                                    </Button>
                                 </Cluster>
                             </DisclosurePanel>
 
+                            <Show when=move || show_search_panel.get() fallback=|| ()>
+                                <Panel variant=SurfaceVariant::Muted>
+                                    <Cluster justify=LayoutJustify::Between>
+                                        <Text role=TextRole::Label>"Search this folder"</Text>
+                                        <Text tone=TextTone::Secondary>
+                                            {move || format!("Searches {} and every folder below it.", cwd.get())}
+                                        </Text>
+                                    </Cluster>
+                                    <Cluster>
+                                        <TextField
+                                            placeholder="Name, e.g. *.txt"
+                                            aria_label="Name pattern"
+                                            value=Signal::derive(move || search_name.get())
+                                            on_input=Callback::new(move |ev| search_name.set(event_target_value(&ev)))
+                                            on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+                                                if ev.key() == "Enter" {
+                                                    ev.prevent_default();
+                                                    start_search();
+                                                }
+                                            })
+                                        />
+                                        <TextField
+                                            placeholder="Containing text (optional)"
+                                            aria_label="File contents"
+                                            value=Signal::derive(move || search_text.get())
+                                            on_input=Callback::new(move |ev| search_text.set(event_target_value(&ev)))
+                                            on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+                                                if ev.key() == "Enter" {
+                                                    ev.prevent_default();
+                                                    start_search();
+                                                }
+                                            })
+                                        />
+                                        <Button
+                                            variant=ButtonVariant::Primary
+                                            on_click=Callback::new(move |_| start_search())
+                                        >
+                                            "Find"
+                                        </Button>
+                                        <Button
+                                            variant=ButtonVariant::Quiet
+                                            disabled=Signal::derive(move || !search.running.get())
+                                            on_click=Callback::new(move |_| cancel_search(search))
+                                        >
+                                            "Cancel"
+                                        </Button>
+                                    </Cluster>
+                                    <Text tone=TextTone::Secondary>
+                                        {move || search.status.get().unwrap_or_default()}
+                                    </Text>
+                                    <ListSurface role="list" aria_label="Search results">
+                                        <For
+                                            each=move || search.results.get()
+                                            key=|hit| hit.path.clone()
+                                            let:hit
+                                        >
+                                            {
+                                                let name = hit.name.clone();
+                                                let parent = parent_path(&hit.path).to_string();
+                                                let path = hit.path.clone();
+                                                let snippet = hit.snippet.clone();
+                                                view! {
+                                                    <div role="listitem">
+                                                        <Cluster justify=LayoutJustify::Between>
+                                                            <Stack gap=LayoutGap::Sm>
+                                                                <Cluster>
+                                                                    <Icon icon=entry_icon(hit.kind)/>
+                                                                    <Text>{name}</Text>
+                                                                    <Text tone=TextTone::Secondary>{parent}</Text>
+                                                                </Cluster>
+                                                                {snippet.map(|snippet| view! {
+                                                                    <Text tone=TextTone::Secondary>{snippet}</Text>
+                                                                })}
+                                                            </Stack>
+                                                            <Button
+                                                                variant=ButtonVariant::Quiet
+                                                                on_click=Callback::new(move |_| {
+                                                                    reveal_path(
+                                                                        signals,
+                                                                        explorer_service.get_value(),
+                                                                        path.clone(),
+                                                                    )
+                                                                })
+                                                            >
+                                                                "Reveal in Folder"
+                                                            </Button>
+                                                        </Cluster>
+                                                    </div>
+                                                }
+                                            }
+                                        </For>
+                                    </ListSurface>
+                                </Panel>
+                            </Show>
+
+                            <Show when=move || show_trash_panel.get() fallback=|| ()>
+                                <Panel variant=SurfaceVariant::Muted>
+                                    <Cluster justify=LayoutJustify::Between>
+                                        <Text role=TextRole::Label>"Trash"</Text>
+                                        <Cluster>
+                                            <Text tone=TextTone::Secondary>
+                                                {move || format!("{} item(s)", trash.get().len())}
+                                            </Text>
+                                            <Button
+                                                variant=ButtonVariant::Danger
+                                                disabled=Signal::derive(move || trash.get().is_empty())
+                                                on_click=Callback::new(move |_| {
+                                                    purge_trash(
+                                                        signals,
+                                                        explorer_service.get_value(),
+                                                        dialog_service.get_value(),
+                                                        None,
+                                                    )
+                                                })
+                                            >
+                                                "Empty Trash"
+                                            </Button>
+                                        </Cluster>
+                                    </Cluster>
+                                    <ListSurface role="list" aria_label="Trashed items">
+                                        <For
+                                            each=move || trash.get()
+                                            key=|entry| entry.id.clone()
+                                            let:entry
+                                        >
+                                            {
+                                                let name = entry.name.clone();
+                                                let origin = format!("from {}", parent_path(&entry.original_path));
+                                                let restored = entry.clone();
+                                                let purged = entry.clone();
+                                                view! {
+                                                    <div role="listitem">
+                                                        <Cluster justify=LayoutJustify::Between>
+                                                            <Cluster>
+                                                                <Icon icon=entry_icon(entry.kind)/>
+                                                                <Text>{name}</Text>
+                                                                <Text tone=TextTone::Secondary>{origin}</Text>
+                                                            </Cluster>
+                                                            <Cluster>
+                                                                <Button
+                                                                    variant=ButtonVariant::Quiet
+                                                                    on_click=Callback::new(move |_| {
+                                                                        restore_trashed(
+                                                                            signals,
+                                                                            explorer_service.get_value(),
+                                                                            vec![restored.clone()],
+                                                                        )
+                                                                    })
+                                                                >
+                                                                    "Restore"
+                                                                </Button>
+                                                                <Button
+                                                                    variant=ButtonVariant::Quiet
+                                                                    on_click=Callback::new(move |_| {
+                                                                        purge_trash(
+                                                                            signals,
+                                                                            explorer_service.get_value(),
+                                                                            dialog_service.get_value(),
+                                                                            Some(purged.clone()),
+                                                                        )
+                                                                    })
+                                                                >
+                                                                    "Delete Permanently"
+                                                                </Button>
+                                                            </Cluster>
+                                                        </Cluster>
+                                                    </div>
+                                                }
+                                            }
+                                        </For>
+                                    </ListSurface>
+                                </Panel>
+                            </Show>
+
+                            <Show when=move || show_storage_panel.get() fallback=|| ()>
+                                <Panel variant=SurfaceVariant::Muted>
+                                    <Cluster justify=LayoutJustify::Between>
+                                        <Text role=TextRole::Label>"Storage"</Text>
+                                        <Cluster>
+                                            <Button
+                                                variant=ButtonVariant::Quiet
+                                                on_click=Callback::new(move |_| {
+                                                    refresh_storage(explorer_service.get_value(), storage)
+                                                })
+                                            >
+                                                "Refresh"
+                                            </Button>
+                                            <Button
+                                                variant=ButtonVariant::Quiet
+                                                disabled=Signal::derive(move || {
+                                                    storage_usage(storage).is_none_or(|usage| usage.persisted == Some(true))
+                                                })
+                                                on_click=Callback::new(move |_| {
+                                                    request_persistent_storage(
+                                                        signals,
+                                                        explorer_service.get_value(),
+                                                        storage,
+                                                    )
+                                                })
+                                            >
+                                                "Keep Files Persistent"
+                                            </Button>
+                                        </Cluster>
+                                    </Cluster>
+                                    {move || {
+                                        let usage = storage.with(|report| {
+                                            report.as_ref().and_then(|report| report.usage.clone())
+                                        });
+                                        match usage {
+                                            None => view! { <Text tone=TextTone::Secondary>"Measuring storage..."</Text> }
+                                                .into_view(),
+                                            Some(Err(err)) => view! {
+                                                <Text tone=TextTone::Secondary>{format!("Storage usage unavailable: {err}")}</Text>
+                                            }
+                                            .into_view(),
+                                            Some(Ok(usage)) => storage_usage_view(usage),
+                                        }
+                                    }}
+                                    {move || {
+                                        let folders = storage.with(|report| {
+                                            report.as_ref().and_then(|report| report.folders.clone())
+                                        });
+                                        match folders {
+                                            None => view! { <Text tone=TextTone::Secondary>"Measuring folders..."</Text> }
+                                                .into_view(),
+                                            Some(Err(err)) => view! {
+                                                <Text tone=TextTone::Secondary>{format!("Folder sizes unavailable: {err}")}</Text>
+                                            }
+                                            .into_view(),
+                                            Some(Ok(folders)) => view! {
+                                                <ListSurface role="list" aria_label="Folder sizes">
+                                                    {folders
+                                                        .into_iter()
+                                                        .map(|(path, size)| view! {
+                                                            <div role="listitem">
+                                                                <Cluster justify=LayoutJustify::Between>
+                                                                    <Cluster>
+                                                                        <Icon icon=entry_icon(ExplorerEntryKind::Directory)/>
+                                                                        <Text>{path}</Text>
+                                                                    </Cluster>
+                                                                    <Text tone=TextTone::Secondary>
+                                                                        {format!("{} in {} file(s)", format_bytes(size.bytes), size.files)}
+                                                                    </Text>
+                                                                </Cluster>
+                                                            </div>
+                                                        })
+                                                        .collect_view()}
+                                                </ListSurface>
+                                            }
+                                            .into_view(),
+                                        }
+                                    }}
+                                </Panel>
+                            </Show>
+
                             <Show when=move || show_create_panel.get() fallback=|| ()>
                                 <Panel variant=SurfaceVariant::Muted>
                                     <Cluster justify=LayoutJustify::Between>
@@ -988,7 +5795,85 @@ pub fn ExplorerApp(
                             </Show>
 
                             <SplitLayout ui_slot="workspace">
-                                <Pane ui_slot="sidebar-pane" aria_label="Explorer status and path">
+                                <Pane ui_slot="sidebar-pane" aria_label="Explorer locations and status">
+                                    <PaneHeader title="Favorites">
+                                        <Button
+                                            variant=ButtonVariant::Quiet
+                                            on_click=Callback::new(move |_| {
+                                                let current = cwd.get_untracked();
+                                                prefs.update(|prefs| toggle_favorite(prefs, &current));
+                                            })
+                                        >
+                                            {move || {
+                                                let current = cwd.get();
+                                                if prefs.with(|prefs| is_favorite(prefs, &current)) {
+                                                    "Unpin Current"
+                                                } else {
+                                                    "Pin Current"
+                                                }
+                                            }}
+                                        </Button>
+                                    </PaneHeader>
+                                    <Show
+                                        when=move || prefs.with(|prefs| !prefs.favorites.is_empty())
+                                        fallback=|| view! {
+                                            <Text tone=TextTone::Secondary>"Pin folders to reach them from here."</Text>
+                                        }
+                                    >
+                                        <Tree>
+                                            <For
+                                                each=move || prefs.with(|prefs| prefs.favorites.clone())
+                                                key=|path| path.clone()
+                                                let:path
+                                            >
+                                                {location_item(path, true)}
+                                            </For>
+                                        </Tree>
+                                    </Show>
+
+                                    <PaneHeader title="Recent">
+                                        <Button
+                                            variant=ButtonVariant::Quiet
+                                            disabled=Signal::derive(move || prefs.with(|prefs| prefs.recent_locations.len() <= 1))
+                                            on_click=Callback::new(move |_| {
+                                                prefs.update(|prefs| prefs.recent_locations.truncate(1));
+                                            })
+                                        >
+                                            "Clear"
+                                        </Button>
+                                    </PaneHeader>
+                                    <Tree>
+                                        <For
+                                            each=move || prefs.with(|prefs| prefs.recent_locations.clone())
+                                            key=|path| path.clone()
+                                            let:path
+                                        >
+                                            {location_item(path, false)}
+                                        </For>
+                                    </Tree>
+
+                                    <PaneHeader title="Folders"><span></span></PaneHeader>
+                                    <Tree
+                                        role="tree"
+                                        aria_label="Folders"
+                                        tabindex=0
+                                        aria_activedescendant=Signal::derive(move || {
+                                            tree_focus.get().map(|path| folder_tree_dom_id(&path)).unwrap_or_default()
+                                        })
+                                        on_keydown=Callback::new(on_folder_tree_keydown)
+                                    >
+                                        <For
+                                            each=move || {
+                                                let show_hidden = prefs.with(|prefs| prefs.show_hidden);
+                                                folder_tree.with(|tree| tree.rows(show_hidden))
+                                            }
+                                            key=|row| row.clone()
+                                            let:row
+                                        >
+                                            {folder_tree_item(row)}
+                                        </For>
+                                    </Tree>
+
                                     <PaneHeader title="Workspace"><span></span></PaneHeader>
                                     <Card variant=SurfaceVariant::Muted>
                                         <InspectorGrid>
@@ -1014,156 +5899,259 @@ pub fn ExplorerApp(
                                                 .unwrap_or_else(|| "(virtual root)".to_string())
                                             }}</Text>
                                         </InspectorGrid>
+                                        <Show when=move || status.with(|s| s.as_ref().is_some_and(|s| !s.mounts.is_empty()))>
+                                            <Stack gap=LayoutGap::Sm>
+                                                <Text role=TextRole::Label>"Mounted folders (read-only)"</Text>
+                                                <For
+                                                    each=move || status.with(|s| s.as_ref().map(|s| s.mounts.clone()).unwrap_or_default())
+                                                    key=|mount| mount.path.clone()
+                                                    let:mount
+                                                >
+                                                    {
+                                                        let open_path = mount.path.clone();
+                                                        let unmount_path = mount.path.clone();
+                                                        view! {
+                                                            <Cluster justify=LayoutJustify::Between>
+                                                                <Button
+                                                                    variant=ButtonVariant::Quiet
+                                                                    title=format!("{} ({:?})", mount.name, mount.permission)
+                                                                    on_click=Callback::new(move |_| {
+                                                                        refresh_directory(signals, explorer_service.get_value(), Some(open_path.clone()));
+                                                                    })
+                                                                >
+                                                                    {mount.path.clone()}
+                                                                </Button>
+                                                                <Button
+                                                                    variant=ButtonVariant::Quiet
+                                                                    on_click=Callback::new(move |_| {
+                                                                        unmount_native_folder(signals, explorer_service.get_value(), unmount_path.clone());
+                                                                    })
+                                                                >
+                                                                    "Unmount"
+                                                                </Button>
+                                                            </Cluster>
+                                                        }
+                                                    }
+                                                </For>
+                                            </Stack>
+                                        </Show>
                                     </Card>
 
-                                    <PaneHeader title="Path Segments"><span></span></PaneHeader>
-                                    <Tree>
-                                        <TreeItem>
-                                            <Button
-                                                variant=ButtonVariant::Quiet
-                                                on_click=Callback::new(move |_| {
-                                                    refresh_directory(
-                                                        signals,
-                                                        explorer_service.get_value(),
-                                                        Some("/".to_string()),
-                                                    )
-                                                })
-                                            >
-                                                <span>"[]"</span>
-                                                <span>"/"</span>
-                                            </Button>
-                                        </TreeItem>
-                                        <For
-                                            each=move || {
-                                                let current = cwd.get();
-                                                let mut segments = Vec::new();
-                                                let mut running = String::new();
-                                                for seg in current.trim_start_matches('/').split('/') {
-                                                    if seg.is_empty() {
-                                                        continue;
-                                                    }
-                                                    running = join_path(&running, seg);
-                                                    segments.push((seg.to_string(), running.clone()));
-                                                }
-                                                segments
-                                            }
-                                            key=|(_, path)| path.clone()
-                                            let:item
-                                        >
-                                            <TreeItem>
-                                                <Button
-                                                    variant=ButtonVariant::Quiet
-                                                    on_click=Callback::new(move |_| {
-                                                        refresh_directory(
-                                                            signals,
-                                                            explorer_service.get_value(),
-                                                            Some(item.1.clone()),
-                                                        )
-                                                    })
-                                                >
-                                                    <span>">"</span>
-                                                    <span>{item.0.clone()}</span>
-                                                </Button>
-                                            </TreeItem>
-                                        </For>
-                                    </Tree>
                                 </Pane>
 
                                 <Pane ui_slot="primary-pane" aria_label="Explorer contents">
                                     <PaneHeader title="Contents" meta=Signal::derive(move || format!("Path: {}", cwd.get()))><span></span></PaneHeader>
 
-                                    <div>
-                                        <DataTable
-                                            role="grid"
-                                            aria_label="Explorer list view"
-                                            tabindex=Signal::derive(|| 0)
-                                            aria_activedescendant=Signal::derive(move || {
-                                                selected_path
-                                                    .get()
-                                                    .map(|path| explorer_row_dom_id(&path))
-                                                    .unwrap_or_default()
-                                            })
-                                            on_keydown=Callback::new(on_list_grid_keydown)
+                                    <div data-ui-slot="dual-pane">
+                                        <div
+                                            data-ui-slot="listing-surface"
+                                            node_ref=primary_listing
+                                            data-ui-active=move || {
+                                                (second.enabled.get() && second.active.get() == ActivePane::Primary)
+                                                    .to_string()
+                                            }
+                                            data-ui-virtualized=move || {
+                                                (visible_entries.with(Vec::len) > VIRTUALIZE_AFTER).to_string()
+                                            }
+                                            on:focusin=move |_| second.active.set(ActivePane::Primary)
+                                            on:contextmenu=open_folder_menu
+                                            on:scroll=move |_| measure_primary_listing()
                                         >
-                                            <thead>
-                                                <tr>
-                                                    <th>"Name"</th>
-                                                    <th>"Type"</th>
-                                                    <th>"Modified"</th>
-                                                    <th>"Size"</th>
-                                                </tr>
-                                            </thead>
-                                            <tbody>
-                                                <For
-                                                    each=move || visible_entries.get()
-                                                    key=|entry| entry.path.clone()
-                                                    let:entry
-                                                >
-                                                    {move || {
-                                                        let entry_for_select = entry.clone();
-                                                        let entry_for_open = entry.clone();
-                                                        let explorer_for_select = explorer_service.get_value();
-                                                        let explorer_for_open = explorer_service.get_value();
-                                                        let cache_for_open = cache_service.get_value();
-                                                        let row_selected =
-                                                            selected_path.get() == Some(entry.path.clone());
-                                                        view! {
-                                                            <tr
-                                                                id=explorer_row_dom_id(&entry.path)
-                                                                class=if row_selected { "selected" } else { "" }
-                                                                aria-selected=row_selected
-                                                                on:mousedown=move |_| {
-                                                                    signals.selected_path.set(Some(
-                                                                        entry_for_select.path.clone(),
-                                                                    ));
-                                                                    inspect_path(
-                                                                        signals,
-                                                                        explorer_for_select.clone(),
-                                                                        entry_for_select.path.clone(),
-                                                                    );
+                                            <Show
+                                                when=move || prefs.with(|prefs| prefs.view_mode == ExplorerViewMode::Details)
+                                                fallback=move || view! {
+                                                    <div
+                                                        data-ui-slot="icon-grid"
+                                                        role="listbox"
+                                                        aria-label="Explorer icon view"
+                                                        aria-multiselectable="true"
+                                                        tabindex="0"
+                                                        aria-activedescendant=move || {
+                                                            selection
+                                                                .with(|selection| selection.focus().map(explorer_row_dom_id))
+                                                                .unwrap_or_default()
+                                                        }
+                                                        on:keydown=on_list_grid_keydown
+                                                    >
+                                                        <VirtualSpacer height_px=spacer_before/>
+                                                        <For
+                                                            each=move || rendered_entries.get()
+                                                            key=|entry| entry.path.clone()
+                                                            let:entry
+                                                        >
+                                                            {move || {
+                                                                let path = store_value(entry.path.clone());
+                                                                let is_folder = entry.kind == ExplorerEntryKind::Directory;
+                                                                let entry_for_open = entry.clone();
+                                                                let name = entry.name.clone();
+                                                                let tile_selected =
+                                                                    selection.with(|selection| selection.contains(&entry.path));
+                                                                view! {
+                                                                    <div
+                                                                        id=explorer_row_dom_id(&entry.path)
+                                                                        data-ui-slot="icon-tile"
+                                                                        role="option"
+                                                                        title=entry.path.clone()
+                                                                        aria-selected=tile_selected
+                                                                        data-ui-selected=if tile_selected { "true" } else { "false" }
+                                                                        draggable="true"
+                                                                        data-ui-drop-target=move || {
+                                                                            if path.with_value(|path| is_drop_target(path)) { "true" } else { "false" }
+                                                                        }
+                                                                        on:dragover=move |ev: DragEvent| {
+                                                                            if is_folder {
+                                                                                path.with_value(|path| drag_over_folder(&ev, path));
+                                                                            }
+                                                                        }
+                                                                        on:dragleave=move |_| path.with_value(|path| drag_leave_folder(path))
+                                                                        on:drop=move |ev: DragEvent| {
+                                                                            if is_folder {
+                                                                                drop_on_folder(&ev, path.get_value());
+                                                                            }
+                                                                        }
+                                                                        on:dragend=move |_| drop_target.set(None)
+                                                                        on:dragstart=move |ev: DragEvent| path.with_value(|path| drag_entries(&ev, path))
+                                                                        on:mousedown=move |ev: ev::MouseEvent| path.with_value(|path| press_entry(&ev, path))
+                                                                        on:click=move |ev: ev::MouseEvent| path.with_value(|path| click_entry(&ev, path))
+                                                                        on:contextmenu=move |ev: ev::MouseEvent| path.with_value(|path| open_entry_menu(&ev, path))
+                                                                        on:dblclick=move |_| open_entry(&entry_for_open)
+                                                                    >
+                                                                        <Icon icon=entry_icon(entry.kind) size=IconSize::Lg/>
+                                                                        <span data-ui-slot="label">{move || {
+                                                                            if path.with_value(|path| is_renaming(path, RenameSurface::Row)) {
+                                                                                view! {
+                                                                                    <RenameField
+                                                                                        draft=rename_draft
+                                                                                        on_commit=commit_rename
+                                                                                        on_cancel=cancel_rename
+                                                                                    />
+                                                                                }
+                                                                                .into_view()
+                                                                            } else {
+                                                                                name.clone().into_view()
+                                                                            }
+                                                                        }}</span>
+                                                                    </div>
                                                                 }
-                                                                on:dblclick=move |_| {
-                                                                    signals.selected_path.set(Some(
-                                                                        entry_for_open.path.clone(),
-                                                                    ));
-                                                                    match entry_for_open.kind {
-                                                                        ExplorerEntryKind::Directory => {
-                                                                            refresh_directory(
-                                                                                signals,
-                                                                                explorer_for_open.clone(),
-                                                                                Some(entry_for_open.path.clone()),
-                                                                            );
+                                                            }}
+                                                        </For>
+                                                        <VirtualSpacer height_px=spacer_after/>
+                                                    </div>
+                                                }
+                                            >
+                                                <VirtualSpacer height_px=spacer_before/>
+                                                <DataTable
+                                                    role="grid"
+                                                    aria_label="Explorer list view"
+                                                    tabindex=Signal::derive(|| 0)
+                                                    aria_activedescendant=Signal::derive(move || {
+                                                        selection
+                                                            .with(|selection| selection.focus().map(explorer_row_dom_id))
+                                                            .unwrap_or_default()
+                                                    })
+                                                    on_keydown=Callback::new(on_list_grid_keydown)
+                                                >
+                                                    <thead>
+                                                        <tr>
+                                                            <SortHeader label="Name" key=ExplorerSortKey::Name prefs=prefs/>
+                                                            <SortHeader label="Type" key=ExplorerSortKey::Type prefs=prefs/>
+                                                            <SortHeader label="Modified" key=ExplorerSortKey::Modified prefs=prefs/>
+                                                            <SortHeader label="Size" key=ExplorerSortKey::Size prefs=prefs/>
+                                                        </tr>
+                                                    </thead>
+                                                    <tbody>
+                                                        <For
+                                                            each=move || rendered_entries.get()
+                                                            key=|entry| entry.path.clone()
+                                                            let:entry
+                                                        >
+                                                            {move || {
+                                                                let path = store_value(entry.path.clone());
+                                                                let is_folder = entry.kind == ExplorerEntryKind::Directory;
+                                                                let entry_for_open = entry.clone();
+                                                                let name = entry.name.clone();
+                                                                let row_selected =
+                                                                    selection.with(|selection| selection.contains(&entry.path));
+                                                                view! {
+                                                                    <tr
+                                                                        id=explorer_row_dom_id(&entry.path)
+                                                                        class=if row_selected { "selected" } else { "" }
+                                                                        aria-selected=row_selected
+                                                                        draggable="true"
+                                                                        data-ui-drop-target=move || {
+                                                                            if path.with_value(|path| is_drop_target(path)) { "true" } else { "false" }
                                                                         }
-                                                                        ExplorerEntryKind::File => {
-                                                                            open_file(
-                                                                                signals,
-                                                                                explorer_for_open.clone(),
-                                                                                cache_for_open.clone(),
-                                                                                entry_for_open.path.clone(),
-                                                                            );
+                                                                        on:dragover=move |ev: DragEvent| {
+                                                                            if is_folder {
+                                                                                path.with_value(|path| drag_over_folder(&ev, path));
+                                                                            }
                                                                         }
-                                                                    }
+                                                                        on:dragleave=move |_| path.with_value(|path| drag_leave_folder(path))
+                                                                        on:drop=move |ev: DragEvent| {
+                                                                            if is_folder {
+                                                                                drop_on_folder(&ev, path.get_value());
+                                                                            }
+                                                                        }
+                                                                        on:dragend=move |_| drop_target.set(None)
+                                                                        on:dragstart=move |ev: DragEvent| path.with_value(|path| drag_entries(&ev, path))
+                                                                        on:mousedown=move |ev: ev::MouseEvent| path.with_value(|path| press_entry(&ev, path))
+                                                                        on:click=move |ev: ev::MouseEvent| path.with_value(|path| click_entry(&ev, path))
+                                                                        on:contextmenu=move |ev: ev::MouseEvent| path.with_value(|path| open_entry_menu(&ev, path))
+                                                                        on:dblclick=move |_| open_entry(&entry_for_open)
+                                                                    >
+                                                                        <td>{move || {
+                                                                            if path.with_value(|path| is_renaming(path, RenameSurface::Row)) {
+                                                                                view! {
+                                                                                    <RenameField
+                                                                                        draft=rename_draft
+                                                                                        on_commit=commit_rename
+                                                                                        on_cancel=cancel_rename
+                                                                                    />
+                                                                                }
+                                                                                .into_view()
+                                                                            } else {
+                                                                                name.clone().into_view()
+                                                                            }
+                                                                        }}</td>
+                                                                        <td>{match entry.kind {
+                                                                            ExplorerEntryKind::Directory => "Folder",
+                                                                            ExplorerEntryKind::File => "File",
+                                                                        }}</td>
+                                                                        <td>{entry
+                                                                            .modified_at_unix_ms
+                                                                            .map(format_timestamp)
+                                                                            .unwrap_or_else(|| "-".to_string())}</td>
+                                                                        <td>{entry
+                                                                            .size
+                                                                            .map(format_bytes)
+                                                                            .unwrap_or_else(|| "-".to_string())}</td>
+                                                                    </tr>
                                                                 }
-                                                            >
-                                                                <td>{entry.name.clone()}</td>
-                                                                <td>{match entry.kind {
-                                                                    ExplorerEntryKind::Directory => "Folder",
-                                                                    ExplorerEntryKind::File => "File",
-                                                                }}</td>
-                                                                <td>{entry
-                                                                    .modified_at_unix_ms
-                                                                    .map(format_timestamp)
-                                                                    .unwrap_or_else(|| "-".to_string())}</td>
-                                                                <td>{entry
-                                                                    .size
-                                                                    .map(format_bytes)
-                                                                    .unwrap_or_else(|| "-".to_string())}</td>
-                                                            </tr>
-                                                        }
-                                                    }}
-                                                </For>
-                                            </tbody>
-                                        </DataTable>
+                                                            }}
+                                                        </For>
+                                                    </tbody>
+                                                </DataTable>
+                                                <VirtualSpacer height_px=spacer_after/>
+                                            </Show>
+                                        </div>
+                                        <Show when=move || second.enabled.get() fallback=|| ()>
+                                            <CommanderPane
+                                                signals=signals
+                                                second=second
+                                                prefs=prefs
+                                                explorer=explorer_service
+                                                on_open_file=Callback::new(move |path| {
+                                                    open_file_in_default_app(
+                                                        signals,
+                                                        explorer_service.get_value(),
+                                                        cache_service.get_value(),
+                                                        app_services.get_value(),
+                                                        path,
+                                                    )
+                                                })
+                                                on_command=Callback::new(run_pane_command)
+                                            />
+                                        </Show>
                                     </div>
                                 </Pane>
 
@@ -1171,13 +6159,61 @@ pub fn ExplorerApp(
                                     <PaneHeader
                                         title="Inspector"
                                         meta=Signal::derive(move || {
-                                            selected_path
-                                                .get()
-                                                .map(|path| entry_name(&path))
-                                                .unwrap_or_else(|| "No selection".to_string())
+                                            selection.with(|selection| match (selection.len(), selection.focus()) {
+                                                (0, _) | (1, None) => "No selection".to_string(),
+                                                (1, Some(path)) => entry_name(path),
+                                                (count, _) => format!("{count} items selected"),
+                                            })
                                         })
                                     ><span></span></PaneHeader>
 
+                                    <Show when=move || preview.with(Option::is_some) fallback=|| ()>
+                                        <div data-ui-slot="file-preview">
+                                            <PaneHeader
+                                                title=Signal::derive(move || {
+                                                    preview
+                                                        .with(|preview| preview.as_ref().map(|preview| entry_name(&preview.path)))
+                                                        .map(|name| format!("Preview: {name}"))
+                                                        .unwrap_or_else(|| "Preview".to_string())
+                                                })
+                                            >
+                                                <Button
+                                                    variant=ButtonVariant::Quiet
+                                                    on_click=Callback::new(move |_| set_preview(signals, None))
+                                                >
+                                                    "Close"
+                                                </Button>
+                                            </PaneHeader>
+                                            {move || preview.get().map(|preview| {
+                                                let name = entry_name(&preview.path);
+                                                let (kind, url) = match preview.content {
+                                                    PreviewContent::Media { kind, url } => (kind, url),
+                                                    PreviewContent::Bytes { bytes, size } => {
+                                                        return view! { <ByteView bytes=bytes size=size/> }.into_view();
+                                                    }
+                                                };
+                                                let alt = name.clone();
+                                                let unplayable = move |_| {
+                                                    set_error(signals, format!("{name} cannot be played in this browser"))
+                                                };
+                                                match kind {
+                                                    MediaKind::Image => view! {
+                                                        <img src=url alt=alt on:error=unplayable/>
+                                                    }
+                                                    .into_view(),
+                                                    MediaKind::Audio => view! {
+                                                        <audio src=url controls=true on:error=unplayable></audio>
+                                                    }
+                                                    .into_view(),
+                                                    MediaKind::Video => view! {
+                                                        <video src=url controls=true on:error=unplayable></video>
+                                                    }
+                                                    .into_view(),
+                                                }
+                                            })}
+                                        </div>
+                                    </Show>
+
                                     <Show when=move || editor_path.get().is_some() fallback=|| ()>
                                         <div>
                                             <PaneHeader
@@ -1194,9 +6230,71 @@ pub fn ExplorerApp(
                                                         "Saved".to_string()
                                                     }
                                                 })
-                                            ><span></span></PaneHeader>
-                                            <TextArea
+                                            >
+                                                <Button
+                                                    variant=ButtonVariant::Quiet
+                                                    size=ButtonSize::Sm
+                                                    aria_pressed=Signal::derive(move || soft_wrap.get())
+                                                    title="Wrap long lines"
+                                                    on_click=Callback::new(move |_| soft_wrap.update(|wrap| *wrap = !*wrap))
+                                                >
+                                                    "Soft Wrap"
+                                                </Button>
+                                            </PaneHeader>
+                                            <Show when=move || editor_draft.with(Option::is_some) fallback=|| ()>
+                                                <Cluster>
+                                                    <Text tone=TextTone::Secondary>
+                                                        "An unsaved draft of this file was kept from an earlier session."
+                                                    </Text>
+                                                    <Button
+                                                        variant=ButtonVariant::Primary
+                                                        on_click=Callback::new(move |_| {
+                                                            if let Some(draft) = editor_draft.get_untracked() {
+                                                                editor_draft.set(None);
+                                                                editor_text.set(draft);
+                                                                editor_dirty.set(true);
+                                                            }
+                                                        })
+                                                    >
+                                                        "Restore Draft"
+                                                    </Button>
+                                                    <Button
+                                                        variant=ButtonVariant::Quiet
+                                                        on_click=Callback::new(move |_| {
+                                                            if let Some(path) = editor_path.get_untracked() {
+                                                                compare_with_cache(
+                                                                    signals,
+                                                                    explorer_service.get_value(),
+                                                                    cache_service.get_value(),
+                                                                    comparison,
+                                                                    path,
+                                                                );
+                                                            }
+                                                        })
+                                                    >
+                                                        "Compare"
+                                                    </Button>
+                                                    <Button
+                                                        variant=ButtonVariant::Quiet
+                                                        on_click=Callback::new(move |_| {
+                                                            editor_draft.set(None);
+                                                            let cache = cache_service.get_value();
+                                                            if let Some(path) = editor_path.get_untracked() {
+                                                                spawn_local(async move {
+                                                                    forget_preview(log_service, cache.as_ref(), &path).await
+                                                                });
+                                                            }
+                                                        })
+                                                    >
+                                                        "Discard Draft"
+                                                    </Button>
+                                                </Cluster>
+                                            </Show>
+                                            <CodeEditor
+                                                aria_label="File contents"
                                                 value=Signal::derive(move || editor_text.get())
+                                                lines=editor_lines
+                                                soft_wrap=soft_wrap
                                                 on_input=Callback::new(move |ev| {
                                                     editor_text.set(event_target_value(&ev));
                                                     editor_dirty.set(true);
@@ -1209,11 +6307,46 @@ pub fn ExplorerApp(
                                         <div>
                                             {move || {
                                                 if let Some(meta) = selected_metadata.get() {
+                                                    let is_root = meta.path == "/";
+                                                    let renaming = is_renaming(&meta.path, RenameSurface::Details);
+                                                    let name = meta.name.clone();
+                                                    let rename_path = meta.path.clone();
+                                                    let path = meta.path.clone();
+                                                    let mime = meta.mime_type();
                                                     view! {
                                                         <InspectorGrid>
-                                                            <Text role=TextRole::Label>"Name"</Text><Text>{meta.name.clone()}</Text>
-                                                            <Text role=TextRole::Label>"Path"</Text><Text>{meta.path.clone()}</Text>
+                                                            <Text role=TextRole::Label>"Name"</Text>
+                                                            {if renaming {
+                                                                view! {
+                                                                    <RenameField
+                                                                        draft=rename_draft
+                                                                        on_commit=commit_rename
+                                                                        on_cancel=cancel_rename
+                                                                    />
+                                                                }
+                                                                .into_view()
+                                                            } else {
+                                                                view! {
+                                                                    <Cluster>
+                                                                        <Text>{name}</Text>
+                                                                        <Button
+                                                                            variant=ButtonVariant::Quiet
+                                                                            disabled=is_root
+                                                                            on_click=Callback::new(move |_| {
+                                                                                start_rename(rename_path.clone(), RenameSurface::Details)
+                                                                            })
+                                                                        >
+                                                                            "Rename"
+                                                                        </Button>
+                                                                    </Cluster>
+                                                                }
+                                                                .into_view()
+                                                            }}
+                                                            <Text role=TextRole::Label>"Path"</Text><Text>{path}</Text>
                                                             <Text role=TextRole::Label>"Kind"</Text><Text>{format!("{:?}", meta.kind)}</Text>
+                                                            {mime.map(|mime| view! {
+                                                                <Text role=TextRole::Label>"Type"</Text><Text>{mime}</Text>
+                                                            })}
                                                             <Text role=TextRole::Label>"Backend"</Text><Text>{format!("{:?}", meta.backend)}</Text>
                                                             <Text role=TextRole::Label>"Permission"</Text><Text>{format!("{:?}", meta.permission)}</Text>
                                                             <Text role=TextRole::Label>"Modified"</Text><Text>{meta
@@ -1240,6 +6373,341 @@ pub fn ExplorerApp(
                                     </Show>
                                 </Pane>
                             </SplitLayout>
+
+                            <Show when=move || context_menu.get().is_some() fallback=|| ()>
+                                {move || {
+                                    let Some(menu) = context_menu.get() else {
+                                        return ().into_view();
+                                    };
+                                    let position = (menu.x, menu.y);
+                                    let on_close = Callback::new(move |()| close_context_menu());
+                                    match menu.target {
+                                        Some(entry) => {
+                                            let entry = store_value(entry);
+                                            let path = move || entry.with_value(|entry| entry.path.clone());
+                                            let is_file = entry.with_value(|entry| entry.kind == ExplorerEntryKind::File);
+                                            let is_media = entry.with_value(|entry| media_type(&entry.path).is_some());
+                                            let is_zip = is_file
+                                                && entry.with_value(|entry| entry.name.to_ascii_lowercase().ends_with(".zip"));
+                                            let associated_app = Signal::derive(move || {
+                                                let path = path();
+                                                let app_id = associations.with(|registry| {
+                                                    registry.lookup(&path).map(|association| association.app_id.clone())
+                                                })?;
+                                                app_services.with_value(|apps| {
+                                                    apps.as_ref().map(|(_, launcher)| launcher.display_name(&app_id))
+                                                })
+                                            });
+                                            let open_in_associated_app = move || {
+                                                context_menu.set(None);
+                                                let path = path();
+                                                let association = associations
+                                                    .with_untracked(|registry| registry.lookup(&path).cloned());
+                                                if let (Some(association), Some((_, launcher))) =
+                                                    (association, app_services.get_value())
+                                                {
+                                                    launcher.open_with(&association, &path);
+                                                }
+                                            };
+                                            let open_with = move |viewer: FileViewer| {
+                                                context_menu.set(None);
+                                                open_file_with(
+                                                    signals,
+                                                    explorer_service.get_value(),
+                                                    cache_service.get_value(),
+                                                    path(),
+                                                    viewer,
+                                                );
+                                            };
+                                            view! {
+                                                <ContextMenu
+                                                    aria_label="Explorer item menu"
+                                                    position=position
+                                                    on_close=on_close
+                                                >
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            entry.with_value(open_entry);
+                                                        })
+                                                    >
+                                                        "Open"
+                                                    </MenuItem>
+                                                    <Show when=move || is_file fallback=|| ()>
+                                                        <Show when=move || associated_app.with(Option::is_some) fallback=|| ()>
+                                                            <MenuItem
+                                                                role="menuitem"
+                                                                on_click=Callback::new(move |_| open_in_associated_app())
+                                                            >
+                                                                {move || format!("Open with {}", associated_app.get().unwrap_or_default())}
+                                                            </MenuItem>
+                                                        </Show>
+                                                        <MenuItem
+                                                            role="menuitem"
+                                                            on_click=Callback::new(move |_| open_with(FileViewer::Editor))
+                                                        >
+                                                            "Open with Text Editor"
+                                                        </MenuItem>
+                                                        <MenuItem
+                                                            role="menuitem"
+                                                            on_click=Callback::new(move |_| open_with(FileViewer::Hex))
+                                                        >
+                                                            "Open with Hex Viewer"
+                                                        </MenuItem>
+                                                        <MenuItem
+                                                            role="menuitem"
+                                                            on_click=Callback::new(move |_| {
+                                                                context_menu.set(None);
+                                                                open_in_new_window(signals, window_service, path());
+                                                            })
+                                                        >
+                                                            "Open in New Window"
+                                                        </MenuItem>
+                                                        <Show when=move || is_media fallback=|| ()>
+                                                            <MenuItem
+                                                                role="menuitem"
+                                                                on_click=Callback::new(move |_| open_with(FileViewer::Media))
+                                                            >
+                                                                "Open with Media Preview"
+                                                            </MenuItem>
+                                                        </Show>
+                                                        <MenuItem
+                                                            role="menuitem"
+                                                            on_click=Callback::new(move |_| {
+                                                                context_menu.set(None);
+                                                                download_selected(signals, explorer_service.get_value());
+                                                            })
+                                                        >
+                                                            "Download"
+                                                        </MenuItem>
+                                                        <Show when=move || selected_files(signals).len() == 2 fallback=|| ()>
+                                                            <MenuItem
+                                                                role="menuitem"
+                                                                on_click=Callback::new(move |_| {
+                                                                    context_menu.set(None);
+                                                                    if let [old, new] = selected_files(signals).as_slice() {
+                                                                        compare_files(
+                                                                            signals,
+                                                                            explorer_service.get_value(),
+                                                                            comparison,
+                                                                            old.clone(),
+                                                                            new.clone(),
+                                                                        );
+                                                                    }
+                                                                })
+                                                            >
+                                                                "Compare"
+                                                            </MenuItem>
+                                                        </Show>
+                                                        <MenuItem
+                                                            role="menuitem"
+                                                            on_click=Callback::new(move |_| {
+                                                                context_menu.set(None);
+                                                                compare_with_cache(
+                                                                    signals,
+                                                                    explorer_service.get_value(),
+                                                                    cache_service.get_value(),
+                                                                    comparison,
+                                                                    path(),
+                                                                );
+                                                            })
+                                                        >
+                                                            "Compare with Cached Copy"
+                                                        </MenuItem>
+                                                    </Show>
+                                                    <Show when=move || is_zip fallback=|| ()>
+                                                        <MenuItem
+                                                            role="menuitem"
+                                                            on_click=Callback::new(move |_| {
+                                                                context_menu.set(None);
+                                                                extract_entry(
+                                                                    signals,
+                                                                    explorer_service.get_value(),
+                                                                    pending_extract,
+                                                                    path(),
+                                                                );
+                                                            })
+                                                        >
+                                                            "Extract Here"
+                                                        </MenuItem>
+                                                    </Show>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            export_archive(signals, explorer_service.get_value(), path());
+                                                        })
+                                                    >
+                                                        "Export as ZIP"
+                                                    </MenuItem>
+                                                    <MenuSeparator />
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            mark_for_paste(TransferKind::Move);
+                                                        })
+                                                    >
+                                                        "Cut"
+                                                    </MenuItem>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            mark_for_paste(TransferKind::Copy);
+                                                        })
+                                                    >
+                                                        "Copy"
+                                                    </MenuItem>
+                                                    <MenuSeparator />
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| start_rename(path(), RenameSurface::Row))
+                                                    >
+                                                        "Rename"
+                                                    </MenuItem>
+                                                    <Show
+                                                        when=move || selection.with(|selection| selection.len() > 1)
+                                                        fallback=|| ()
+                                                    >
+                                                        <MenuItem
+                                                            role="menuitem"
+                                                            on_click=Callback::new(move |_| start_bulk_rename())
+                                                        >
+                                                            "Rename Selected…"
+                                                        </MenuItem>
+                                                    </Show>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        variant=ButtonVariant::Danger
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            delete_selected(
+                                                                signals,
+                                                                explorer_service.get_value(),
+                                                                cache_service.get_value(),
+                                                            );
+                                                        })
+                                                    >
+                                                        "Delete"
+                                                    </MenuItem>
+                                                    <MenuSeparator />
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            open_properties(properties, explorer_service.get_value(), path());
+                                                        })
+                                                    >
+                                                        "Properties"
+                                                    </MenuItem>
+                                                </ContextMenu>
+                                            }
+                                            .into_view()
+                                        }
+                                        None => {
+                                            let new_entry = move |kind: ExplorerEntryKind| {
+                                                context_menu.set(None);
+                                                create_untitled(
+                                                    signals,
+                                                    explorer_service.get_value(),
+                                                    cwd.get_untracked(),
+                                                    kind,
+                                                    Callback::new(move |path: String| {
+                                                        start_rename(path, RenameSurface::Row)
+                                                    }),
+                                                );
+                                            };
+                                            view! {
+                                                <ContextMenu
+                                                    aria_label="Explorer folder menu"
+                                                    position=position
+                                                    on_close=on_close
+                                                >
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| new_entry(ExplorerEntryKind::Directory))
+                                                    >
+                                                        "New Folder"
+                                                    </MenuItem>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| new_entry(ExplorerEntryKind::File))
+                                                    >
+                                                        "New File"
+                                                    </MenuItem>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            import_files(
+                                                                signals,
+                                                                explorer_service.get_value(),
+                                                                cwd.get_untracked(),
+                                                            );
+                                                        })
+                                                    >
+                                                        "Import Files"
+                                                    </MenuItem>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            import_archives(
+                                                                signals,
+                                                                explorer_service.get_value(),
+                                                                pending_extract,
+                                                                cwd.get_untracked(),
+                                                            );
+                                                        })
+                                                    >
+                                                        "Extract ZIP Here"
+                                                    </MenuItem>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            export_archive(
+                                                                signals,
+                                                                explorer_service.get_value(),
+                                                                cwd.get_untracked(),
+                                                            );
+                                                        })
+                                                    >
+                                                        "Export Folder as ZIP"
+                                                    </MenuItem>
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        disabled=Signal::derive(move || clipboard.with(Option::is_none))
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            paste_clipboard();
+                                                        })
+                                                    >
+                                                        "Paste"
+                                                    </MenuItem>
+                                                    <MenuSeparator />
+                                                    <MenuItem
+                                                        role="menuitem"
+                                                        on_click=Callback::new(move |_| {
+                                                            context_menu.set(None);
+                                                            open_properties(
+                                                                properties,
+                                                                explorer_service.get_value(),
+                                                                cwd.get_untracked(),
+                                                            );
+                                                        })
+                                                    >
+                                                        "Properties"
+                                                    </MenuItem>
+                                                </ContextMenu>
+                                            }
+                                            .into_view()
+                                        }
+                                    }
+                                }}
+                            </Show>
                         </>
                     }
                 }
@@ -1389,7 +6857,13 @@ pub fn ExplorerApp(
             </Show>
 
             <StatusBar>
-                <StatusBarItem>{move || format!("{} item(s)", visible_entries.get().len())}</StatusBarItem>
+                <StatusBarItem>{move || {
+                    let items = visible_entries.get().len();
+                    match selection.with(ExplorerSelection::len) {
+                        0 => format!("{items} item(s)"),
+                        selected => format!("{items} item(s), {selected} selected"),
+                    }
+                }}</StatusBarItem>
                 <StatusBarItem>{move || {
                     status
                         .get()
@@ -1409,7 +6883,78 @@ pub fn ExplorerApp(
                         "Hydrating...".to_string()
                     }
                 }}</StatusBarItem>
+                <Show when=move || undo.get().is_some() fallback=|| ()>
+                    <StatusBarItem>
+                        <Button
+                            variant=ButtonVariant::Quiet
+                            aria_keyshortcuts="Ctrl+Z"
+                            on_click=Callback::new(move |_| {
+                                undo_last(
+                                    signals,
+                                    explorer_service.get_value(),
+                                    cache_service.get_value(),
+                                );
+                            })
+                        >
+                            "Undo"
+                        </Button>
+                    </StatusBarItem>
+                </Show>
             </StatusBar>
+            <Show when=move || properties.with(Option::is_some) fallback=|| ()>
+                <PropertiesDialog properties=properties explorer=explorer_service />
+            </Show>
+            <Show when=move || pending_bulk_rename.with(Option::is_some) fallback=|| ()>
+                <BulkRenameDialog
+                    pending=pending_bulk_rename
+                    on_apply=Callback::new(move |renames| {
+                        bulk_rename_entries(
+                            signals,
+                            explorer_service.get_value(),
+                            cache_service.get_value(),
+                            renames,
+                        )
+                    })
+                />
+            </Show>
+            <Show when=move || pending_extract.with(Option::is_some) fallback=|| ()>
+                <ExtractConflictDialog
+                    pending=pending_extract
+                    on_choose=Callback::new(move |conflict| {
+                        if let Some(pending) = pending_extract.get_untracked() {
+                            pending_extract.set(None);
+                            extract_archives(
+                                signals,
+                                explorer_service.get_value(),
+                                pending.dest,
+                                pending.archives,
+                                conflict,
+                            );
+                        }
+                    })
+                />
+            </Show>
+            <Show when=move || comparison.with(Option::is_some) fallback=|| ()>
+                <CompareDialog comparison=comparison />
+            </Show>
+            <Show when=move || save_conflict.with(Option::is_some) fallback=|| ()>
+                <SaveConflictDialog
+                    conflict=save_conflict
+                    on_choose=Callback::new(move |choice| {
+                        if let Some(disk) = save_conflict.get_untracked() {
+                            save_conflict.set(None);
+                            resolve_save_conflict(
+                                signals,
+                                explorer_service.get_value(),
+                                cache_service.get_value(),
+                                save_conflict,
+                                disk,
+                                choice,
+                            );
+                        }
+                    })
+                />
+            </Show>
         </AppShell>
     }
 }
@@ -1423,8 +6968,11 @@ fn format_timestamp(unix_ms: u64) -> String {
 fn format_bytes(bytes: u64) -> String {
     const KB: f64 = 1024.0;
     const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
     let bytes_f = bytes as f64;
-    if bytes_f >= MB {
+    if bytes_f >= GB {
+        format!("{:.1} GB", bytes_f / GB)
+    } else if bytes_f >= MB {
         format!("{:.1} MB", bytes_f / MB)
     } else if bytes_f >= KB {
         format!("{:.1} KB", bytes_f / KB)
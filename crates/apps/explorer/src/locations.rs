@@ -0,0 +1,78 @@
+//! Favorite and recently visited folders listed in the Explorer sidebar.
+
+use platform_host::ExplorerPrefs;
+
+/// Recent locations kept before the oldest are dropped.
+pub(crate) const MAX_RECENT_LOCATIONS: usize = 8;
+
+pub(crate) fn is_favorite(prefs: &ExplorerPrefs, path: &str) -> bool {
+    prefs.favorites.iter().any(|favorite| favorite == path)
+}
+
+/// Pins `path` to the end of the favorites, or unpins it when it is already there.
+pub(crate) fn toggle_favorite(prefs: &mut ExplorerPrefs, path: &str) {
+    if is_favorite(prefs, path) {
+        prefs.favorites.retain(|favorite| favorite != path);
+    } else {
+        prefs.favorites.push(path.to_string());
+    }
+}
+
+/// Whether `path` is already the most recent location, so recording it would change nothing.
+pub(crate) fn is_latest_location(prefs: &ExplorerPrefs, path: &str) -> bool {
+    prefs.recent_locations.first().map(String::as_str) == Some(path)
+}
+
+/// Moves `path` to the front of the recent locations, dropping the oldest past the limit.
+pub(crate) fn record_recent_location(prefs: &mut ExplorerPrefs, path: &str) {
+    prefs.recent_locations.retain(|recent| recent != path);
+    prefs.recent_locations.insert(0, path.to_string());
+    prefs.recent_locations.truncate(MAX_RECENT_LOCATIONS);
+}
+
+/// Label for a sidebar location: its folder name, or `/` for the root.
+pub(crate) fn location_label(path: &str) -> &str {
+    match path.rsplit('/').next() {
+        Some("") | None => "/",
+        Some(name) => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn favorites_toggle_and_recents_stay_unique_and_bounded() {
+        let mut prefs = ExplorerPrefs::default();
+        toggle_favorite(&mut prefs, "/docs");
+        toggle_favorite(&mut prefs, "/music");
+        assert_eq!(prefs.favorites, ["/docs", "/music"]);
+        assert!(is_favorite(&prefs, "/docs"));
+        toggle_favorite(&mut prefs, "/docs");
+        assert_eq!(prefs.favorites, ["/music"]);
+
+        for idx in 0..10 {
+            record_recent_location(&mut prefs, &format!("/dir{idx}"));
+        }
+        record_recent_location(&mut prefs, "/dir5");
+        assert_eq!(prefs.recent_locations.len(), MAX_RECENT_LOCATIONS);
+        assert_eq!(prefs.recent_locations[..3], ["/dir5", "/dir9", "/dir8"]);
+        assert_eq!(
+            prefs
+                .recent_locations
+                .iter()
+                .filter(|path| *path == "/dir5")
+                .count(),
+            1
+        );
+        assert!(is_latest_location(&prefs, "/dir5"));
+        assert!(!is_latest_location(&prefs, "/dir9"));
+    }
+
+    #[test]
+    fn location_labels_use_the_folder_name() {
+        assert_eq!(location_label("/"), "/");
+        assert_eq!(location_label("/docs/notes"), "notes");
+    }
+}
@@ -0,0 +1,122 @@
+//! Line-based three-way merge of editor text with a file changed on disk.
+//!
+//! Both versions are compared against the text the editor last loaded or saved. Lines changed on
+//! one side only are taken from that side; lines changed differently on both sides are kept from
+//! both between conflict markers for the user to resolve.
+
+use crate::diff::match_lines;
+
+pub(crate) const CONFLICT_START: &str = "<<<<<<< yours\n";
+pub(crate) const CONFLICT_SEPARATOR: &str = "=======\n";
+pub(crate) const CONFLICT_END: &str = ">>>>>>> on disk\n";
+
+/// Result of [`merge_texts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TextMerge {
+    pub(crate) text: String,
+    /// Regions left between conflict markers.
+    pub(crate) conflicts: usize,
+}
+
+fn push_lines(text: &mut String, lines: &[&str]) {
+    for line in lines {
+        text.push_str(line);
+    }
+}
+
+/// Ends the last line of `text` so a conflict marker starts on its own line.
+fn end_line(text: &mut String) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+/// Merges the editor's `ours` and the on-disk `theirs`, both edited from `base`.
+pub(crate) fn merge_texts(base: &str, ours: &str, theirs: &str) -> TextMerge {
+    let base = base.split_inclusive('\n').collect::<Vec<_>>();
+    let ours = ours.split_inclusive('\n').collect::<Vec<_>>();
+    let theirs = theirs.split_inclusive('\n').collect::<Vec<_>>();
+    let ours_matches = match_lines(&base, &ours);
+    let theirs_matches = match_lines(&base, &theirs);
+
+    let mut merged = TextMerge {
+        text: String::new(),
+        conflicts: 0,
+    };
+    let (mut base_at, mut ours_at, mut theirs_at) = (0, 0, 0);
+    loop {
+        // The next base line both sides kept, or the end of all three texts.
+        let sync = (base_at..base.len())
+            .find_map(|index| Some((index, ours_matches[index]?, theirs_matches[index]?)));
+        let (base_end, ours_end, theirs_end) =
+            sync.unwrap_or((base.len(), ours.len(), theirs.len()));
+        let base_chunk = &base[base_at..base_end];
+        let ours_chunk = &ours[ours_at..ours_end];
+        let theirs_chunk = &theirs[theirs_at..theirs_end];
+        if ours_chunk == base_chunk || ours_chunk == theirs_chunk {
+            push_lines(&mut merged.text, theirs_chunk);
+        } else if theirs_chunk == base_chunk {
+            push_lines(&mut merged.text, ours_chunk);
+        } else {
+            merged.conflicts += 1;
+            for (marker, lines) in [
+                (CONFLICT_START, ours_chunk),
+                (CONFLICT_SEPARATOR, theirs_chunk),
+            ] {
+                end_line(&mut merged.text);
+                merged.text.push_str(marker);
+                push_lines(&mut merged.text, lines);
+            }
+            end_line(&mut merged.text);
+            merged.text.push_str(CONFLICT_END);
+        }
+        let Some((base_end, ours_end, theirs_end)) = sync else {
+            break;
+        };
+        merged.text.push_str(base[base_end]);
+        (base_at, ours_at, theirs_at) = (base_end + 1, ours_end + 1, theirs_end + 1);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_on_one_side_are_taken() {
+        let base = "one\ntwo\nthree\nfour\n";
+        let ours = "one\n2\nthree\nfour\n";
+        let theirs = "one\ntwo\nthree\nfour\nfive\n";
+        let merged = merge_texts(base, ours, theirs);
+        assert_eq!(merged.text, "one\n2\nthree\nfour\nfive\n");
+        assert_eq!(merged.conflicts, 0);
+
+        let merged = merge_texts(base, base, "one\nthree\nfour\n");
+        assert_eq!(merged.text, "one\nthree\nfour\n");
+    }
+
+    #[test]
+    fn identical_changes_merge_cleanly() {
+        let merged = merge_texts("a\nb\n", "a\nc\n", "a\nc\n");
+        assert_eq!(merged.text, "a\nc\n");
+        assert_eq!(merged.conflicts, 0);
+    }
+
+    #[test]
+    fn overlapping_changes_keep_both_between_markers() {
+        let merged = merge_texts("a\nb\nc\n", "a\nmine\nc\n", "a\ntheirs\nc\n");
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            merged.text,
+            format!("a\n{CONFLICT_START}mine\n{CONFLICT_SEPARATOR}theirs\n{CONFLICT_END}c\n")
+        );
+
+        let merged = merge_texts("", "mine", "theirs");
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            merged.text,
+            format!("{CONFLICT_START}mine\n{CONFLICT_SEPARATOR}theirs\n{CONFLICT_END}")
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Media and byte previews for files the text editor cannot show.
+
+use platform_host::mime_from_name;
+
+/// Files larger than this are not read into memory for a preview.
+pub(crate) const MAX_MEDIA_PREVIEW_BYTES: u64 = 32 * 1024 * 1024;
+/// Leading bytes of a binary file kept for the hex viewer.
+pub(crate) const MAX_HEX_VIEW_BYTES: usize = 256 * 1024;
+/// Bytes shown on one hex viewer page.
+pub(crate) const HEX_PAGE_BYTES: usize = 4 * 1024;
+const HEX_ROW_BYTES: usize = 16;
+/// Bytes inspected when deciding whether a file is text.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Element used to present a media file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MediaKind {
+    Image,
+    Audio,
+    Video,
+}
+
+/// Media kind and MIME type for `path`, judged by its extension.
+///
+/// Returns `None` for files that should open in the text editor instead.
+pub(crate) fn media_type(path: &str) -> Option<(MediaKind, &'static str)> {
+    let mime = mime_from_name(path)?;
+    Some((media_kind(mime)?, mime))
+}
+
+/// Element that can present files of type `mime`, or `None` when it is not media.
+pub(crate) fn media_kind(mime: &str) -> Option<MediaKind> {
+    match mime.split('/').next()? {
+        "image" => Some(MediaKind::Image),
+        "audio" => Some(MediaKind::Audio),
+        "video" => Some(MediaKind::Video),
+        _ => None,
+    }
+}
+
+/// Whether decoded file text shows signs of binary content: NUL characters or replacement
+/// characters left by invalid UTF-8.
+pub(crate) fn text_may_be_binary(text: &str) -> bool {
+    text.contains(['\0', '\u{FFFD}'])
+}
+
+/// Whether `bytes` look like a binary file rather than text.
+///
+/// Only the leading bytes are inspected. They are binary when they hold a NUL byte, are not valid
+/// UTF-8, or are more than a tenth control characters.
+pub(crate) fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sample.contains(&0) {
+        return true;
+    }
+    // A multi-byte character cut off by the end of the sample is still text.
+    if std::str::from_utf8(sample).is_err_and(|err| err.error_len().is_some()) {
+        return true;
+    }
+    let controls = sample
+        .iter()
+        .filter(|byte| {
+            byte.is_ascii_control() && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b)
+        })
+        .count();
+    controls * 10 > sample.len()
+}
+
+/// One line of the hex viewer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HexRow {
+    pub(crate) offset: String,
+    pub(crate) hex: String,
+    pub(crate) ascii: String,
+}
+
+/// Number of hex viewer pages needed for `len` bytes; an empty file still has one page.
+pub(crate) fn hex_page_count(len: usize) -> usize {
+    len.div_ceil(HEX_PAGE_BYTES).max(1)
+}
+
+/// Rows for page `page` of `bytes`, sixteen bytes per row.
+pub(crate) fn hex_rows(bytes: &[u8], page: usize) -> Vec<HexRow> {
+    let start = (page * HEX_PAGE_BYTES).min(bytes.len());
+    let end = (start + HEX_PAGE_BYTES).min(bytes.len());
+    bytes[start..end]
+        .chunks(HEX_ROW_BYTES)
+        .enumerate()
+        .map(|(idx, chunk)| HexRow {
+            offset: format!("{:08x}", start + idx * HEX_ROW_BYTES),
+            hex: chunk
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            ascii: chunk
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        char::from(*byte)
+                    } else {
+                        '.'
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn media_types_follow_the_extension() {
+        assert_eq!(
+            media_type("/photos/Cat.JPG"),
+            Some((MediaKind::Image, "image/jpeg"))
+        );
+        assert_eq!(
+            media_type("/music/song.flac"),
+            Some((MediaKind::Audio, "audio/flac"))
+        );
+        assert_eq!(
+            media_type("/clips/demo.webm"),
+            Some((MediaKind::Video, "video/webm"))
+        );
+        assert_eq!(media_type("/notes/readme.txt"), None);
+        assert_eq!(media_type("/photos/.png"), None);
+        assert_eq!(media_type("/photos.png/notes"), None);
+        assert_eq!(media_kind("image/svg+xml"), Some(MediaKind::Image));
+        assert_eq!(media_kind("application/pdf"), None);
+    }
+
+    #[test]
+    fn binary_detection_looks_for_nuls_invalid_utf8_and_controls() {
+        assert!(!looks_binary(b"plain text\r\n\twith tabs"));
+        assert!(!looks_binary("caf\u{e9} \u{1b}[1m".as_bytes()));
+        assert!(!looks_binary(&"\u{e9}".as_bytes()[..1]));
+        assert!(looks_binary(b"PK\x03\x04\0\0"));
+        assert!(looks_binary(&[0x89, b'P', b'N', b'G', 0xff, 0xfe]));
+        assert!(looks_binary(b"\x01\x02\x03 abc"));
+        assert!(text_may_be_binary("bad \u{FFFD} byte"));
+        assert!(!text_may_be_binary("fine"));
+    }
+
+    #[test]
+    fn hex_rows_page_through_the_bytes() {
+        let bytes = (0..=255u8)
+            .cycle()
+            .take(HEX_PAGE_BYTES + 20)
+            .collect::<Vec<_>>();
+        assert_eq!(hex_page_count(bytes.len()), 2);
+        assert_eq!(hex_page_count(0), 1);
+
+        let first = hex_rows(&bytes, 0);
+        assert_eq!(first.len(), HEX_PAGE_BYTES / 16);
+        assert_eq!(first[0].offset, "00000000");
+        assert_eq!(
+            first[0].hex,
+            "00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f"
+        );
+        assert_eq!(first[4].ascii, "@ABCDEFGHIJKLMNO");
+        assert_eq!(first[2].ascii, " !\"#$%&'()*+,-./");
+
+        let last = hex_rows(&bytes, 1);
+        assert_eq!(last.len(), 2);
+        assert_eq!(last[0].offset, "00001000");
+        assert_eq!(last[1].hex, "10 11 12 13");
+        assert!(hex_rows(&bytes, 5).is_empty());
+    }
+}
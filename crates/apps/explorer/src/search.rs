@@ -0,0 +1,141 @@
+//! Query matching for the Explorer's recursive folder search.
+//!
+//! Names are matched case-insensitively against a glob where `*` matches any run of characters
+//! and `?` matches one. A pattern without wildcards matches any name containing it. File contents
+//! are matched as a case-insensitive literal.
+
+use platform_host::ExplorerEntryKind;
+
+/// Longest snippet kept from a matching line of file content.
+const SNIPPET_MAX_CHARS: usize = 96;
+
+/// Entry found by a search, with the matching line when contents were searched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchHit {
+    pub(crate) path: String,
+    pub(crate) name: String,
+    pub(crate) kind: ExplorerEntryKind,
+    pub(crate) snippet: Option<String>,
+}
+
+/// Parsed name pattern and optional content text for one search run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchQuery {
+    name: Vec<char>,
+    content: Option<String>,
+}
+
+impl SearchQuery {
+    /// Builds a query, or `None` when both the name pattern and the content text are blank.
+    ///
+    /// A blank name pattern matches every name, so a content-only search looks at all files.
+    pub(crate) fn new(name_pattern: &str, content: &str) -> Option<Self> {
+        let name_pattern = name_pattern.trim().to_lowercase();
+        let content = content.trim().to_lowercase();
+        if name_pattern.is_empty() && content.is_empty() {
+            return None;
+        }
+        let name = if name_pattern.contains(['*', '?']) {
+            name_pattern
+        } else {
+            format!("*{name_pattern}*")
+        };
+        Some(Self {
+            name: name.chars().collect(),
+            content: (!content.is_empty()).then_some(content),
+        })
+    }
+
+    pub(crate) fn matches_name(&self, name: &str) -> bool {
+        let name = name.to_lowercase().chars().collect::<Vec<_>>();
+        glob_matches(&self.name, &name)
+    }
+
+    /// Whether matches also depend on file contents, which excludes folders.
+    pub(crate) fn needs_content(&self) -> bool {
+        self.content.is_some()
+    }
+
+    /// Returns the first line of `text` containing the content text, trimmed for display.
+    ///
+    /// Without content text every file matches and there is no snippet to show.
+    pub(crate) fn content_snippet(&self, text: &str) -> Option<String> {
+        let needle = self.content.as_deref()?;
+        let line = text
+            .lines()
+            .find(|line| line.to_lowercase().contains(needle))?
+            .trim();
+        let mut snippet = line.chars().take(SNIPPET_MAX_CHARS).collect::<String>();
+        if line.chars().count() > SNIPPET_MAX_CHARS {
+            snippet.push_str("...");
+        }
+        Some(snippet)
+    }
+}
+
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name index it is currently standing in for.
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_match_globs_and_plain_substrings_case_insensitively() {
+        let glob = SearchQuery::new("*.RS", "").expect("query");
+        assert!(glob.matches_name("main.rs"));
+        assert!(!glob.matches_name("main.rs.bak"));
+        assert!(!glob.needs_content());
+
+        let single = SearchQuery::new("report-202?.txt", "").expect("query");
+        assert!(single.matches_name("Report-2024.txt"));
+        assert!(!single.matches_name("report-24.txt"));
+
+        let plain = SearchQuery::new("note", "").expect("query");
+        assert!(plain.matches_name("MyNotes.md"));
+        assert!(!plain.matches_name("readme.md"));
+
+        assert!(SearchQuery::new("  ", "").is_none());
+    }
+
+    #[test]
+    fn content_text_yields_the_first_matching_line() {
+        let query = SearchQuery::new("", "TODO").expect("query");
+        assert!(query.needs_content());
+        assert!(query.matches_name("anything"));
+        assert_eq!(
+            query.content_snippet("fn main() {}\n    // todo: ship it\n// TODO again"),
+            Some("// todo: ship it".to_string())
+        );
+        assert_eq!(query.content_snippet("nothing here"), None);
+
+        let long = format!("todo {}", "x".repeat(200));
+        let snippet = query.content_snippet(&long).expect("snippet");
+        assert!(snippet.ends_with("..."));
+        assert_eq!(snippet.chars().count(), SNIPPET_MAX_CHARS + 3);
+    }
+}
@@ -0,0 +1,199 @@
+//! Multi-entry selection for the Explorer listing.
+//!
+//! Paths are kept in the order they were selected. The anchor is where Shift ranges start, and the
+//! focus is the entry keyboard navigation moves from and the details pane describes.
+
+/// Selected listing entries plus the anchor and focus used by range and keyboard selection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ExplorerSelection {
+    paths: Vec<String>,
+    anchor: Option<String>,
+    focus: Option<String>,
+}
+
+impl ExplorerSelection {
+    /// Selection holding only `path`, which becomes both anchor and focus.
+    pub(crate) fn single(path: impl Into<String>) -> Self {
+        let path = path.into();
+        Self {
+            paths: vec![path.clone()],
+            anchor: Some(path.clone()),
+            focus: Some(path),
+        }
+    }
+
+    /// Rebuilds a persisted selection; an empty `paths` falls back to the focus alone.
+    pub(crate) fn restore(paths: Vec<String>, focus: Option<String>) -> Self {
+        let Some(focus) = focus else {
+            return Self::default();
+        };
+        if paths.is_empty() {
+            return Self::single(focus);
+        }
+        let mut paths = paths;
+        if !paths.contains(&focus) {
+            paths.push(focus.clone());
+        }
+        Self {
+            paths,
+            anchor: Some(focus.clone()),
+            focus: Some(focus),
+        }
+    }
+
+    pub(crate) fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub(crate) fn contains(&self, path: &str) -> bool {
+        self.paths.iter().any(|selected| selected == path)
+    }
+
+    pub(crate) fn focus(&self) -> Option<&str> {
+        self.focus.as_deref()
+    }
+
+    /// Replaces the selection with `path`.
+    pub(crate) fn select_only(&mut self, path: impl Into<String>) {
+        *self = Self::single(path);
+    }
+
+    /// Adds or removes `path`, moving the anchor and focus to it.
+    pub(crate) fn toggle(&mut self, path: impl Into<String>) {
+        let path = path.into();
+        if let Some(index) = self.paths.iter().position(|selected| *selected == path) {
+            self.paths.remove(index);
+        } else {
+            self.paths.push(path.clone());
+        }
+        self.anchor = Some(path.clone());
+        self.focus = Some(path);
+    }
+
+    /// Selects every entry of `order` between the anchor and `path`, inclusive.
+    ///
+    /// Without an anchor still present in `order` this behaves like [`Self::select_only`]. The
+    /// anchor is kept so repeated Shift moves grow or shrink the same range.
+    pub(crate) fn extend_to(&mut self, order: &[String], path: impl Into<String>) {
+        let path = path.into();
+        let anchor_index = self
+            .anchor
+            .as_ref()
+            .and_then(|anchor| order.iter().position(|entry| entry == anchor));
+        let (Some(anchor_index), Some(path_index)) =
+            (anchor_index, order.iter().position(|entry| *entry == path))
+        else {
+            self.select_only(path);
+            return;
+        };
+        let (start, end) = if anchor_index <= path_index {
+            (anchor_index, path_index)
+        } else {
+            (path_index, anchor_index)
+        };
+        self.paths = order[start..=end].to_vec();
+        self.focus = Some(path);
+    }
+
+    /// Selects every entry of `order`, keeping the focus when it is still listed.
+    pub(crate) fn select_all(&mut self, order: &[String]) {
+        self.paths = order.to_vec();
+        let focus = self
+            .focus
+            .clone()
+            .filter(|focus| self.contains(focus))
+            .or_else(|| order.first().cloned());
+        self.anchor = focus.clone();
+        self.focus = focus;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Drops entries for which `keep` is false, including the anchor and focus.
+    pub(crate) fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.paths.retain(|path| keep(path));
+        self.anchor = self.anchor.take().filter(|anchor| keep(anchor));
+        self.focus = self.focus.take().filter(|focus| keep(focus));
+    }
+
+    /// Rewrites every path for which `map` returns a new location, as after a rename or move.
+    pub(crate) fn rebase(&mut self, map: impl Fn(&str) -> Option<String>) {
+        let rebase = |path: &mut String| {
+            if let Some(moved) = map(path) {
+                *path = moved;
+            }
+        };
+        self.paths.iter_mut().for_each(rebase);
+        self.anchor.iter_mut().for_each(rebase);
+        self.focus.iter_mut().for_each(rebase);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order() -> Vec<String> {
+        ["/a", "/b", "/c", "/d"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn ranges_grow_from_the_anchor_and_toggles_move_it() {
+        let order = order();
+        let mut selection = ExplorerSelection::single("/b");
+        selection.extend_to(&order, "/d");
+        assert_eq!(selection.paths(), ["/b", "/c", "/d"]);
+        selection.extend_to(&order, "/a");
+        assert_eq!(selection.paths(), ["/a", "/b"]);
+        assert_eq!(selection.focus(), Some("/a"));
+
+        selection.toggle("/d");
+        assert_eq!(selection.paths(), ["/a", "/b", "/d"]);
+        selection.toggle("/a");
+        assert_eq!(selection.paths(), ["/b", "/d"]);
+        selection.extend_to(&order, "/c");
+        assert_eq!(selection.paths(), ["/a", "/b", "/c"]);
+
+        let mut fresh = ExplorerSelection::default();
+        fresh.extend_to(&order, "/c");
+        assert_eq!(fresh, ExplorerSelection::single("/c"));
+    }
+
+    #[test]
+    fn select_all_retain_and_rebase_keep_focus_consistent() {
+        let order = order();
+        let mut selection = ExplorerSelection::single("/c");
+        selection.select_all(&order);
+        assert_eq!(selection.len(), 4);
+        assert_eq!(selection.focus(), Some("/c"));
+
+        selection.retain(|path| path != "/c");
+        assert_eq!(selection.paths(), ["/a", "/b", "/d"]);
+        assert_eq!(selection.focus(), None);
+
+        selection.toggle("/b");
+        selection.rebase(|path| (path == "/d").then(|| "/e".to_string()));
+        assert_eq!(selection.paths(), ["/a", "/e"]);
+
+        assert_eq!(
+            ExplorerSelection::restore(Vec::new(), Some("/a".to_string())),
+            ExplorerSelection::single("/a")
+        );
+        assert!(ExplorerSelection::restore(order, None).is_empty());
+        selection.clear();
+        assert!(selection.is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+//! Column sorting for the Explorer listing.
+//!
+//! Sorting is stable, and ties on the chosen column fall back to the name, so the resulting
+//! order depends only on the listing's contents.
+
+use std::cmp::Ordering;
+
+use platform_host::{
+    ExplorerEntry, ExplorerEntryKind, ExplorerPrefs, ExplorerSortDirection, ExplorerSortKey,
+};
+
+/// Sorts `entries` by the column and direction stored in `prefs`.
+///
+/// Name tie-breaks are always ascending. With `directories_first`, folders lead regardless of
+/// direction.
+pub(crate) fn sort_entries(entries: &mut [ExplorerEntry], prefs: &ExplorerPrefs) {
+    entries.sort_by(|a, b| {
+        let folders = if prefs.directories_first {
+            kind_rank(a.kind).cmp(&kind_rank(b.kind))
+        } else {
+            Ordering::Equal
+        };
+        let column = compare_column(a, b, prefs.sort_key);
+        let column = match prefs.sort_direction {
+            ExplorerSortDirection::Ascending => column,
+            ExplorerSortDirection::Descending => column.reverse(),
+        };
+        folders
+            .then(column)
+            .then_with(|| compare_names(&a.name, &b.name))
+    });
+}
+
+/// Toggles the direction when `key` is already the sort column, otherwise sorts ascending by it.
+pub(crate) fn select_sort_column(prefs: &mut ExplorerPrefs, key: ExplorerSortKey) {
+    if prefs.sort_key == key {
+        prefs.sort_direction = prefs.sort_direction.reversed();
+    } else {
+        prefs.sort_key = key;
+        prefs.sort_direction = ExplorerSortDirection::Ascending;
+    }
+}
+
+/// `aria-sort` value for a column header.
+pub(crate) fn aria_sort(prefs: &ExplorerPrefs, key: ExplorerSortKey) -> &'static str {
+    match (prefs.sort_key == key, prefs.sort_direction) {
+        (false, _) => "none",
+        (true, ExplorerSortDirection::Ascending) => "ascending",
+        (true, ExplorerSortDirection::Descending) => "descending",
+    }
+}
+
+fn kind_rank(kind: ExplorerEntryKind) -> u8 {
+    match kind {
+        ExplorerEntryKind::Directory => 0,
+        ExplorerEntryKind::File => 1,
+    }
+}
+
+fn compare_column(a: &ExplorerEntry, b: &ExplorerEntry, key: ExplorerSortKey) -> Ordering {
+    match key {
+        ExplorerSortKey::Name => compare_names(&a.name, &b.name),
+        ExplorerSortKey::Type => kind_rank(a.kind)
+            .cmp(&kind_rank(b.kind))
+            .then_with(|| extension(&a.name).cmp(&extension(&b.name))),
+        // Entries without a timestamp or size sort as the smallest value.
+        ExplorerSortKey::Modified => a.modified_at_unix_ms.cmp(&b.modified_at_unix_ms),
+        ExplorerSortKey::Size => a.size.cmp(&b.size),
+    }
+}
+
+fn compare_names(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+fn extension(name: &str) -> String {
+    name.trim_start_matches('.')
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_lowercase())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        name: &str,
+        kind: ExplorerEntryKind,
+        size: Option<u64>,
+        modified: u64,
+    ) -> ExplorerEntry {
+        ExplorerEntry {
+            name: name.to_string(),
+            path: format!("/{name}"),
+            kind,
+            size,
+            modified_at_unix_ms: Some(modified),
+        }
+    }
+
+    fn names(entries: &[ExplorerEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    fn listing() -> Vec<ExplorerEntry> {
+        vec![
+            entry("notes.txt", ExplorerEntryKind::File, Some(40), 3),
+            entry("Docs", ExplorerEntryKind::Directory, None, 5),
+            entry("b.rs", ExplorerEntryKind::File, Some(10), 1),
+            entry("archive", ExplorerEntryKind::Directory, None, 2),
+            entry("A.txt", ExplorerEntryKind::File, Some(10), 4),
+        ]
+    }
+
+    #[test]
+    fn sorts_by_column_with_folders_first_and_name_tie_breaks() {
+        let mut prefs = ExplorerPrefs::default();
+        let mut entries = listing();
+        sort_entries(&mut entries, &prefs);
+        assert_eq!(
+            names(&entries),
+            ["archive", "Docs", "A.txt", "b.rs", "notes.txt"]
+        );
+
+        select_sort_column(&mut prefs, ExplorerSortKey::Size);
+        select_sort_column(&mut prefs, ExplorerSortKey::Size);
+        assert_eq!(prefs.sort_direction, ExplorerSortDirection::Descending);
+        assert_eq!(aria_sort(&prefs, ExplorerSortKey::Size), "descending");
+        assert_eq!(aria_sort(&prefs, ExplorerSortKey::Name), "none");
+        sort_entries(&mut entries, &prefs);
+        assert_eq!(
+            names(&entries),
+            ["archive", "Docs", "notes.txt", "A.txt", "b.rs"]
+        );
+
+        select_sort_column(&mut prefs, ExplorerSortKey::Type);
+        sort_entries(&mut entries, &prefs);
+        assert_eq!(
+            names(&entries),
+            ["archive", "Docs", "b.rs", "A.txt", "notes.txt"]
+        );
+    }
+
+    #[test]
+    fn folders_follow_the_direction_when_not_pinned_first() {
+        let prefs = ExplorerPrefs {
+            sort_key: ExplorerSortKey::Modified,
+            sort_direction: ExplorerSortDirection::Descending,
+            directories_first: false,
+            ..ExplorerPrefs::default()
+        };
+        let mut entries = listing();
+        sort_entries(&mut entries, &prefs);
+        assert_eq!(
+            names(&entries),
+            ["Docs", "A.txt", "notes.txt", "archive", "b.rs"]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Type-ahead navigation for the Explorer listing.
+//!
+//! Characters typed while the listing has focus build up a prefix, and focus jumps to the first
+//! entry whose name starts with it. The prefix starts over once typing pauses. Typing the same
+//! character again steps through the entries starting with it, like native file managers.
+
+/// Characters typed since typing last paused.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TypeAhead {
+    typed: String,
+    /// Bumped on every keystroke, so a reset scheduled by an earlier keystroke can tell it is stale.
+    generation: u64,
+}
+
+impl TypeAhead {
+    /// Adds `ch` to the prefix and returns the generation a later [`TypeAhead::expire`] must match.
+    pub(crate) fn push(&mut self, ch: char) -> u64 {
+        self.typed.extend(ch.to_lowercase());
+        self.generation += 1;
+        self.generation
+    }
+
+    /// Starts the prefix over unless another character was typed after `generation`.
+    pub(crate) fn expire(&mut self, generation: u64) {
+        if self.generation == generation {
+            self.typed.clear();
+        }
+    }
+
+    pub(crate) fn typed(&self) -> &str {
+        &self.typed
+    }
+}
+
+/// Whether a key press is a character for type-ahead rather than a shortcut or navigation key.
+pub(crate) fn type_ahead_char(key: &str, modified: bool) -> Option<char> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if !modified && !ch.is_control() => Some(ch),
+        _ => None,
+    }
+}
+
+/// Index of the entry to focus for the typed prefix, or `None` when no name starts with it.
+///
+/// The search starts at the focused entry so a longer prefix keeps focus where it still matches.
+/// A prefix of one repeated character starts after it, so each press moves to the next match.
+pub(crate) fn type_ahead_match(
+    names: &[&str],
+    typed: &str,
+    focused: Option<usize>,
+) -> Option<usize> {
+    let first = typed.chars().next()?;
+    let repeated = typed.chars().all(|ch| ch == first);
+    let prefix = if repeated {
+        &typed[..first.len_utf8()]
+    } else {
+        typed
+    };
+    let start = match focused {
+        Some(index) if repeated => index + 1,
+        Some(index) => index,
+        None => 0,
+    };
+    (0..names.len())
+        .map(|offset| (start + offset) % names.len())
+        .find(|&index| names[index].to_lowercase().starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: [&str; 5] = ["alpha", "Beta", "banana", "bread", "cherry"];
+
+    #[test]
+    fn prefix_jumps_to_the_first_matching_name() {
+        assert_eq!(type_ahead_match(&NAMES, "c", None), Some(4));
+        assert_eq!(type_ahead_match(&NAMES, "ban", Some(1)), Some(2));
+        assert_eq!(type_ahead_match(&NAMES, "be", Some(1)), Some(1));
+        assert_eq!(type_ahead_match(&NAMES, "br", Some(4)), Some(3));
+        assert_eq!(type_ahead_match(&NAMES, "z", Some(0)), None);
+    }
+
+    #[test]
+    fn repeated_characters_cycle_through_matches() {
+        assert_eq!(type_ahead_match(&NAMES, "b", Some(0)), Some(1));
+        assert_eq!(type_ahead_match(&NAMES, "bb", Some(1)), Some(2));
+        assert_eq!(type_ahead_match(&NAMES, "bbb", Some(3)), Some(1));
+    }
+
+    #[test]
+    fn typed_prefix_resets_only_after_the_latest_keystroke() {
+        let mut type_ahead = TypeAhead::default();
+        let first = type_ahead.push('B');
+        let second = type_ahead.push('a');
+        type_ahead.expire(first);
+        assert_eq!(type_ahead.typed(), "ba");
+        type_ahead.expire(second);
+        assert_eq!(type_ahead.typed(), "");
+
+        assert_eq!(type_ahead_char("a", false), Some('a'));
+        assert_eq!(type_ahead_char("a", true), None);
+        assert_eq!(type_ahead_char("Enter", false), None);
+    }
+}
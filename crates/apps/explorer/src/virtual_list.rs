@@ -0,0 +1,144 @@
+//! Windowing for listings too long to render in full.
+//!
+//! A long listing renders only the lines near the scroll position, with spacers above and below
+//! that keep the full scroll height. A line is one table row in the details view and one row of
+//! tiles in the icon view.
+
+/// Listings up to this many entries render every entry.
+pub(crate) const VIRTUALIZE_AFTER: usize = 300;
+/// Lines rendered beyond each edge of the viewport, so fast scrolling rarely shows blank space.
+const OVERSCAN_LINES: usize = 10;
+
+/// Scroll position and measured geometry of a listing surface, in CSS pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ListingViewport {
+    pub(crate) scroll_top: f64,
+    pub(crate) height: f64,
+    /// Distance from the top of one line to the top of the next.
+    pub(crate) line_height: f64,
+    /// Entries per line.
+    pub(crate) columns: usize,
+}
+
+impl Default for ListingViewport {
+    /// Geometry assumed until the surface is first measured.
+    fn default() -> Self {
+        Self {
+            scroll_top: 0.0,
+            height: 720.0,
+            line_height: 28.0,
+            columns: 1,
+        }
+    }
+}
+
+/// Entries `start..end` of a listing to render, with the lines skipped on either side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ListingWindow {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) lines_before: usize,
+    pub(crate) lines_after: usize,
+}
+
+impl ListingWindow {
+    /// Whether entries are left out.
+    pub(crate) fn is_partial(&self) -> bool {
+        self.lines_before > 0 || self.lines_after > 0
+    }
+}
+
+/// Part of a listing of `total` entries to render for `viewport`.
+pub(crate) fn listing_window(total: usize, viewport: ListingViewport) -> ListingWindow {
+    if total <= VIRTUALIZE_AFTER {
+        return ListingWindow {
+            start: 0,
+            end: total,
+            lines_before: 0,
+            lines_after: 0,
+        };
+    }
+    let columns = viewport.columns.max(1);
+    let line_height = viewport.line_height.max(1.0);
+    let lines = total.div_ceil(columns);
+    let visible_lines = (viewport.height.max(0.0) / line_height).ceil() as usize + 1;
+    // A surface scrolled past the end, as after the listing shrinks, shows the last lines.
+    let first_visible = ((viewport.scroll_top.max(0.0) / line_height) as usize)
+        .min(lines.saturating_sub(visible_lines));
+    let last = (first_visible + visible_lines + OVERSCAN_LINES).min(lines);
+    let first = first_visible.saturating_sub(OVERSCAN_LINES).min(last);
+    ListingWindow {
+        start: first * columns,
+        end: (last * columns).min(total),
+        lines_before: first,
+        lines_after: lines - last,
+    }
+}
+
+/// Scroll offset that brings entry `index` fully into view, or `None` when it already is.
+pub(crate) fn reveal_offset(index: usize, viewport: ListingViewport) -> Option<f64> {
+    let line = (index / viewport.columns.max(1)) as f64;
+    let top = line * viewport.line_height;
+    let bottom = top + viewport.line_height;
+    if top < viewport.scroll_top {
+        Some(top)
+    } else if bottom > viewport.scroll_top + viewport.height {
+        Some(bottom - viewport.height)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport(scroll_top: f64, columns: usize) -> ListingViewport {
+        ListingViewport {
+            scroll_top,
+            height: 200.0,
+            line_height: 20.0,
+            columns,
+        }
+    }
+
+    #[test]
+    fn short_listings_render_in_full() {
+        let window = listing_window(VIRTUALIZE_AFTER, viewport(500.0, 1));
+        assert_eq!((window.start, window.end), (0, VIRTUALIZE_AFTER));
+        assert!(!window.is_partial());
+    }
+
+    #[test]
+    fn long_listings_render_around_the_scroll_position() {
+        let top = listing_window(10_000, viewport(0.0, 1));
+        assert_eq!((top.start, top.end), (0, 21));
+        assert_eq!((top.lines_before, top.lines_after), (0, 9_979));
+
+        let middle = listing_window(10_000, viewport(2_000.0, 1));
+        assert_eq!((middle.start, middle.end), (90, 121));
+        assert_eq!(middle.lines_before + (middle.end - middle.start), 121);
+
+        let bottom = listing_window(10_000, viewport(1_000_000.0, 1));
+        assert_eq!(bottom.end, 10_000);
+        assert_eq!(bottom.lines_after, 0);
+        assert_eq!(bottom.start, bottom.lines_before);
+
+        let tiles = listing_window(1_001, viewport(2_000.0, 4));
+        assert_eq!((tiles.start, tiles.end), (360, 484));
+        assert_eq!((tiles.lines_before, tiles.lines_after), (90, 130));
+
+        let last_line = listing_window(1_001, viewport(1_000_000.0, 4));
+        assert_eq!(last_line.end, 1_001);
+        assert_eq!(last_line.lines_after, 0);
+    }
+
+    #[test]
+    fn reveal_scrolls_only_when_the_entry_is_out_of_view() {
+        assert_eq!(reveal_offset(10, viewport(100.0, 1)), None);
+        assert_eq!(reveal_offset(2, viewport(100.0, 1)), Some(40.0));
+        assert_eq!(reveal_offset(20, viewport(100.0, 1)), Some(220.0));
+        assert_eq!(reveal_offset(9, viewport(0.0, 4)), None);
+        assert_eq!(reveal_offset(40, viewport(0.0, 4)), Some(20.0));
+    }
+}
@@ -188,7 +188,11 @@ pub fn NotepadApp(
         let serialized = match serde_json::to_string(&snapshot) {
             Ok(raw) => raw,
             Err(err) => {
-                logging::warn!("notepad serialize failed: {err}");
+                if let Some(services) = services_for_persist.as_ref() {
+                    services
+                        .logs
+                        .warn(format!("notepad serialize failed: {err}"));
+                }
                 return;
             }
         };
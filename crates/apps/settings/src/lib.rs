@@ -1,4 +1,5 @@
-//! Built-in System Settings desktop app for wallpaper, theme, and accessibility preferences.
+//! Built-in System Settings desktop app for wallpaper, theme, accessibility, default-app,
+//! notification, app permission, and storage preferences.
 //!
 //! The app consumes the injected v2 service surface from [`desktop_app_contract::AppServices`]
 //! so wallpaper and theme configuration stay synchronized with the desktop runtime.
@@ -7,9 +8,13 @@
 
 #![warn(missing_docs, rustdoc::broken_intra_doc_links)]
 
-use desktop_app_contract::AppServices;
+use desktop_app_contract::{
+    AppCapability, AppServices, ApplicationId, FileAssociation, FileAssociations, LaunchableApp,
+    NotificationDelivery, FILE_ASSOCIATIONS_CONFIG_KEY, FILE_ASSOCIATIONS_CONFIG_NAMESPACE,
+};
 use leptos::*;
 use platform_host::{
+    total_usage_bytes, ExplorerFolderSize, ExplorerStorageUsage, StorageUsageEntry,
     WallpaperAnimationPolicy, WallpaperAssetRecord, WallpaperCollection, WallpaperConfig,
     WallpaperDisplayMode, WallpaperMediaKind, WallpaperPosition, WallpaperSelection,
     WallpaperSourceKind,
@@ -23,6 +28,10 @@ enum SettingsSection {
     Personalize,
     Appearance,
     Accessibility,
+    DefaultApps,
+    Notifications,
+    Permissions,
+    Storage,
 }
 
 impl SettingsSection {
@@ -31,6 +40,10 @@ impl SettingsSection {
             Self::Personalize => "Personalize",
             Self::Appearance => "Appearance",
             Self::Accessibility => "Accessibility",
+            Self::DefaultApps => "Default Apps",
+            Self::Notifications => "Notifications",
+            Self::Permissions => "Permissions",
+            Self::Storage => "Storage",
         }
     }
 
@@ -39,6 +52,10 @@ impl SettingsSection {
             "personalize" => Some(Self::Personalize),
             "appearance" => Some(Self::Appearance),
             "accessibility" => Some(Self::Accessibility),
+            "default-apps" => Some(Self::DefaultApps),
+            "notifications" => Some(Self::Notifications),
+            "permissions" => Some(Self::Permissions),
+            "storage" => Some(Self::Storage),
             _ => None,
         }
     }
@@ -113,6 +130,28 @@ fn wallpaper_step_status(active: WallpaperFlowStep, step: WallpaperFlowStep) ->
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq)]
+struct StorageReport {
+    files: Option<ExplorerStorageUsage>,
+    folders: Vec<(String, ExplorerFolderSize)>,
+    app_state: Vec<StorageUsageEntry>,
+    prefs: Vec<StorageUsageEntry>,
+    caches: Vec<StorageUsageEntry>,
+}
+
+impl StorageReport {
+    /// Bytes stored under the app's declared storage keys, across app state, preferences, and
+    /// caches.
+    fn app_bytes(&self, app: &LaunchableApp) -> u64 {
+        [&self.app_state, &self.prefs, &self.caches]
+            .into_iter()
+            .flatten()
+            .filter(|entry| app.owns_storage_key(&entry.name))
+            .map(|entry| entry.bytes)
+            .sum()
+    }
+}
+
 #[component]
 /// Settings app window contents.
 pub fn SettingsApp(
@@ -130,6 +169,30 @@ pub fn SettingsApp(
     let rename_value = create_rw_signal(String::new());
     let tags_value = create_rw_signal(String::new());
     let new_collection_name = create_rw_signal(String::new());
+    let config_service = store_value(services.config.clone());
+    let log_service = store_value(services.logs.clone());
+    let dialog_service = store_value(services.dialogs.clone());
+    let launcher = store_value(services.launcher.clone());
+    let permissions = services.permissions;
+    let notifications = services.notifications;
+    let associations = create_rw_signal(FileAssociations::default());
+    let association_extension = create_rw_signal(String::new());
+    let association_app = create_rw_signal(
+        services
+            .launcher
+            .apps()
+            .first()
+            .map(|app| app.app_id.to_string())
+            .unwrap_or_default(),
+    );
+    let association_params = create_rw_signal(FileAssociation::default_launch_params().to_string());
+    let association_error = create_rw_signal::<Option<String>>(None);
+    let app_state_service = store_value(services.app_state.clone());
+    let prefs_service = store_value(services.prefs.clone());
+    let cache_service = store_value(services.cache.clone());
+    let explorer_service = store_value(services.explorer.clone());
+    let storage_report = create_rw_signal(StorageReport::default());
+    let storage_error = create_rw_signal::<Option<String>>(None);
 
     if let Some(restored_state) = restored_state {
         if let Ok(restored) = serde_json::from_value::<SettingsAppState>(restored_state) {
@@ -151,6 +214,22 @@ pub fn SettingsApp(
         }
     });
 
+    spawn_local(async move {
+        let config = config_service.get_value();
+        match config
+            .load::<FileAssociations>(
+                FILE_ASSOCIATIONS_CONFIG_NAMESPACE,
+                FILE_ASSOCIATIONS_CONFIG_KEY,
+            )
+            .await
+        {
+            Ok(Some(loaded)) => associations.set(loaded),
+            Ok(None) => {}
+            Err(err) => log_service
+                .with_value(|logs| logs.warn(format!("load file associations failed: {err}"))),
+        }
+    });
+
     let active_wallpaper = Signal::derive(move || {
         services
             .wallpaper
@@ -275,11 +354,200 @@ pub fn SettingsApp(
         }
     };
     let delete_asset = move |_| {
-        if let Some(asset) = selected_asset.get_untracked() {
-            services.wallpaper.delete_asset(asset.asset_id);
-            selected_asset_id.set(String::new());
+        let Some(asset) = selected_asset.get_untracked() else {
+            return;
+        };
+        let dialogs = dialog_service.get_value();
+        let wallpaper = services.wallpaper;
+        spawn_local(async move {
+            let confirmed = dialogs
+                .confirm_destructive(
+                    "Delete wallpaper?",
+                    format!(
+                        "\"{}\" will be removed from the wallpaper library.",
+                        asset.display_name
+                    ),
+                    "Delete",
+                )
+                .await;
+            if confirmed {
+                wallpaper.delete_asset(asset.asset_id);
+                selected_asset_id.set(String::new());
+            }
+        });
+    };
+    let save_associations = move |next: FileAssociations| {
+        match serde_json::to_value(&next) {
+            Ok(value) => config_service.with_value(|config| {
+                config.save(
+                    FILE_ASSOCIATIONS_CONFIG_NAMESPACE,
+                    FILE_ASSOCIATIONS_CONFIG_KEY,
+                    value,
+                )
+            }),
+            Err(err) => log_service
+                .with_value(|logs| logs.warn(format!("serialize file associations failed: {err}"))),
+        }
+        associations.set(next);
+    };
+    let add_association = move |_| {
+        let app_id = match ApplicationId::new(association_app.get_untracked()) {
+            Ok(app_id) => app_id,
+            Err(err) => {
+                association_error.set(Some(err));
+                return;
+            }
+        };
+        let launch_params = match association_params.get_untracked().trim() {
+            "" => Value::Null,
+            raw => match serde_json::from_str::<Value>(raw) {
+                Ok(params) => params,
+                Err(err) => {
+                    association_error.set(Some(format!("Launch params must be JSON: {err}")));
+                    return;
+                }
+            },
+        };
+        match FileAssociation::new(
+            &association_extension.get_untracked(),
+            app_id,
+            launch_params,
+        ) {
+            Ok(association) => {
+                let mut next = associations.get_untracked();
+                next.set(association);
+                save_associations(next);
+                association_extension.set(String::new());
+                association_error.set(None);
+            }
+            Err(err) => association_error.set(Some(err)),
         }
     };
+    let override_association = move |extension: String, app_id: String| {
+        let Ok(app_id) = ApplicationId::new(app_id) else {
+            return;
+        };
+        let mut next = associations.get_untracked();
+        if next.set_app(&extension, app_id) {
+            save_associations(next);
+        }
+    };
+    let reset_association = move |extension: String| {
+        let mut next = associations.get_untracked();
+        if next.remove(&extension) {
+            save_associations(next);
+        }
+    };
+    let reset_all_associations = move |_| {
+        let dialogs = dialog_service.get_value();
+        spawn_local(async move {
+            let confirmed = dialogs
+                .confirm_destructive(
+                    "Reset all file associations?",
+                    "Every file will open in Explorer's own viewers again.",
+                    "Reset",
+                )
+                .await;
+            if confirmed {
+                save_associations(FileAssociations::default());
+            }
+        });
+    };
+    let refresh_storage = move || {
+        let app_state = app_state_service.get_value();
+        let prefs = prefs_service.get_value();
+        let cache = cache_service.get_value();
+        let explorer = explorer_service.get_value();
+        spawn_local(async move {
+            let report = async {
+                Ok::<_, String>(StorageReport {
+                    files: explorer.usage().await.ok(),
+                    folders: explorer.top_level_folder_sizes().await?,
+                    app_state: app_state.usage().await?,
+                    prefs: prefs.usage().await?,
+                    caches: cache.usage().await?,
+                })
+            }
+            .await;
+            match report {
+                Ok(report) => {
+                    storage_report.set(report);
+                    storage_error.set(None);
+                }
+                Err(err) => storage_error.set(Some(err)),
+            }
+        });
+    };
+    create_effect(move |_| {
+        if settings_state.with(|state| state.active_section == SettingsSection::Storage) {
+            refresh_storage();
+        }
+    });
+    let request_persistent_storage = move |_| {
+        let explorer = explorer_service.get_value();
+        spawn_local(async move {
+            match explorer.request_persistent_storage().await {
+                Ok(true) => refresh_storage(),
+                Ok(false) => storage_error.set(Some(
+                    "The browser kept storage best-effort; it may still be cleared under storage pressure."
+                        .to_string(),
+                )),
+                Err(err) => storage_error.set(Some(err)),
+            }
+        });
+    };
+    let clear_cache = move |cache_name: String| {
+        let cache = cache_service.get_value();
+        spawn_local(async move {
+            if let Err(err) = cache.clear(&cache_name).await {
+                storage_error.set(Some(err));
+            }
+            refresh_storage();
+        });
+    };
+    let clear_app_data = move |app: LaunchableApp| {
+        let dialogs = dialog_service.get_value();
+        let app_state = app_state_service.get_value();
+        let prefs = prefs_service.get_value();
+        let cache = cache_service.get_value();
+        let report = storage_report.get_untracked();
+        spawn_local(async move {
+            let confirmed = dialogs
+                .confirm_destructive(
+                    format!("Clear {} data?", app.display_name),
+                    "Saved state, preferences, and cached content for this app will be deleted. Files in Explorer are kept.",
+                    "Clear Data",
+                )
+                .await;
+            if !confirmed {
+                return;
+            }
+            let owned = |entries: &[StorageUsageEntry]| {
+                entries
+                    .iter()
+                    .filter(|entry| app.owns_storage_key(&entry.name))
+                    .map(|entry| entry.name.clone())
+                    .collect::<Vec<_>>()
+            };
+            let mut result = Ok(());
+            for namespace in owned(&report.app_state) {
+                result = result.and(app_state.delete(&namespace).await);
+            }
+            for key in owned(&report.prefs) {
+                result = result.and(prefs.delete(&key).await);
+            }
+            for cache_name in owned(&report.caches) {
+                result = result.and(cache.clear(&cache_name).await);
+            }
+            services.state.clear_shared_state(app.app_id.clone());
+            match result {
+                Ok(()) => log_service
+                    .with_value(|logs| logs.info(format!("cleared app data for {}", app.app_id))),
+                Err(err) => storage_error.set(Some(err)),
+            }
+            refresh_storage();
+        });
+    };
     let create_collection = move |_| {
         let name = new_collection_name.get_untracked();
         if !name.trim().is_empty() {
@@ -297,6 +565,10 @@ pub fn SettingsApp(
                             SettingsSection::Personalize,
                             SettingsSection::Appearance,
                             SettingsSection::Accessibility,
+                            SettingsSection::DefaultApps,
+                            SettingsSection::Notifications,
+                            SettingsSection::Permissions,
+                            SettingsSection::Storage,
                         ]
                     }
                     key=|section| *section as u8
@@ -750,6 +1022,428 @@ pub fn SettingsApp(
                 </Surface>
             </Show>
 
+            <Show when=move || settings_state.get().active_section == SettingsSection::DefaultApps fallback=|| ()>
+                <Surface
+                    variant=SurfaceVariant::Muted
+                    elevation=Elevation::Inset
+                >
+                    <Stack gap=LayoutGap::Md>
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Heading role=TextRole::Title>"File associations"</Heading>
+                            <Text tone=TextTone::Secondary>
+                                "Explorer opens files with these extensions in the chosen app. Other files open in Explorer's own viewers, and resetting an extension returns it to them."
+                            </Text>
+                            <Show
+                                when=move || associations.with(|registry| !registry.associations.is_empty())
+                                fallback=|| view! { <Text tone=TextTone::Secondary>"No file associations yet."</Text> }
+                            >
+                                <Stack gap=LayoutGap::Sm>
+                                    <For
+                                        each=move || associations.get().associations
+                                        key=|association| association.extension.clone()
+                                        let:association
+                                    >
+                                        {
+                                            let extension = store_value(association.extension.clone());
+                                            let current_app = Signal::derive(move || {
+                                                associations.with(|registry| {
+                                                    extension.with_value(|extension| {
+                                                        registry
+                                                            .associations
+                                                            .iter()
+                                                            .find(|association| &association.extension == extension)
+                                                            .map(|association| association.app_id.to_string())
+                                                            .unwrap_or_default()
+                                                    })
+                                                })
+                                            });
+                                            let label = format!(".{}", association.extension);
+                                            let aria_label = format!("App for .{}", association.extension);
+                                            view! {
+                                                <Cluster justify=LayoutJustify::Between>
+                                                    <Stack gap=LayoutGap::Sm>
+                                                        <Text role=TextRole::Label>{label}</Text>
+                                                        <SelectField
+                                                            aria_label=aria_label
+                                                            value=current_app
+                                                            on_change=Callback::new(move |ev| {
+                                                                override_association(
+                                                                    extension.get_value(),
+                                                                    event_target_value(&ev),
+                                                                );
+                                                            })
+                                                        >
+                                                            {launcher.with_value(|launcher| {
+                                                                launcher
+                                                                    .apps()
+                                                                    .iter()
+                                                                    .map(|app| view! {
+                                                                        <option value=app.app_id.to_string()>{app.display_name.clone()}</option>
+                                                                    })
+                                                                    .collect_view()
+                                                            })}
+                                                        </SelectField>
+                                                        <Text tone=TextTone::Secondary>{association.launch_params.to_string()}</Text>
+                                                    </Stack>
+                                                    <Button
+                                                        variant=ButtonVariant::Quiet
+                                                        on_click=Callback::new(move |_| reset_association(extension.get_value()))
+                                                    >
+                                                        "Reset"
+                                                    </Button>
+                                                </Cluster>
+                                            }
+                                        }
+                                    </For>
+                                </Stack>
+                                <Cluster>
+                                    <Button
+                                        variant=ButtonVariant::Quiet
+                                        on_click=Callback::new(reset_all_associations)
+                                    >
+                                        "Reset All"
+                                    </Button>
+                                </Cluster>
+                            </Show>
+                        </Panel>
+
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Heading role=TextRole::Title>"Add an association"</Heading>
+                            <Grid>
+                                <label>
+                                    <Text role=TextRole::Label>"Extension"</Text>
+                                    <TextField
+                                        placeholder="md"
+                                        value=Signal::derive(move || association_extension.get())
+                                        on_input=Callback::new(move |ev| {
+                                            association_extension.set(event_target_value(&ev));
+                                        })
+                                    />
+                                </label>
+                                <label>
+                                    <Text role=TextRole::Label>"App"</Text>
+                                    <SelectField
+                                        value=Signal::derive(move || association_app.get())
+                                        on_change=Callback::new(move |ev| {
+                                            association_app.set(event_target_value(&ev));
+                                        })
+                                    >
+                                        {launcher.with_value(|launcher| {
+                                            launcher
+                                                .apps()
+                                                .iter()
+                                                .map(|app| view! {
+                                                    <option value=app.app_id.to_string()>{app.display_name.clone()}</option>
+                                                })
+                                                .collect_view()
+                                        })}
+                                    </SelectField>
+                                </label>
+                                <label>
+                                    <Text role=TextRole::Label>"Launch params"</Text>
+                                    <TextField
+                                        value=Signal::derive(move || association_params.get())
+                                        on_input=Callback::new(move |ev| {
+                                            association_params.set(event_target_value(&ev));
+                                        })
+                                    />
+                                </label>
+                            </Grid>
+                            <Text tone=TextTone::Secondary>
+                                "Launch params are JSON. {path}, {dir}, and {name} are replaced with the file's path, folder, and name."
+                            </Text>
+                            <Show when=move || association_error.with(Option::is_some) fallback=|| ()>
+                                <Text tone=TextTone::Danger>{move || association_error.get().unwrap_or_default()}</Text>
+                            </Show>
+                            <Cluster>
+                                <Button
+                                    variant=ButtonVariant::Primary
+                                    on_click=Callback::new(add_association)
+                                >
+                                    "Save Association"
+                                </Button>
+                            </Cluster>
+                        </Panel>
+                    </Stack>
+                </Surface>
+            </Show>
+
+            <Show when=move || settings_state.get().active_section == SettingsSection::Notifications fallback=|| ()>
+                <Surface
+                    variant=SurfaceVariant::Muted
+                    elevation=Elevation::Inset
+                >
+                    <Panel variant=SurfaceVariant::Standard>
+                        <Heading role=TextRole::Title>"Notifications"</Heading>
+                        <Text tone=TextTone::Secondary>
+                            "Choose how each app's notifications reach you. Silent notifications only go to the notification center; muted apps' notifications are dropped."
+                        </Text>
+                        <Stack gap=LayoutGap::Sm>
+                            {launcher
+                                .with_value(|launcher| launcher.apps().to_vec())
+                                .into_iter()
+                                .map(|app| {
+                                    let app_id = store_value(app.app_id);
+                                    let aria_label = format!("Notifications from {}", app.display_name);
+                                    view! {
+                                        <Cluster justify=LayoutJustify::Between>
+                                            <Text role=TextRole::Label>{app.display_name}</Text>
+                                            <SelectField
+                                                aria_label=aria_label
+                                                value=Signal::derive(move || {
+                                                    app_id
+                                                        .with_value(|app_id| notifications.delivery(app_id))
+                                                        .as_str()
+                                                        .to_string()
+                                                })
+                                                on_change=Callback::new(move |ev| {
+                                                    if let Some(delivery) =
+                                                        NotificationDelivery::from_id(&event_target_value(&ev))
+                                                    {
+                                                        notifications.set_delivery(app_id.get_value(), delivery);
+                                                    }
+                                                })
+                                            >
+                                                {NotificationDelivery::ALL
+                                                    .into_iter()
+                                                    .map(|delivery| view! {
+                                                        <option value=delivery.as_str()>
+                                                            {notification_delivery_label(delivery)}
+                                                        </option>
+                                                    })
+                                                    .collect_view()}
+                                            </SelectField>
+                                        </Cluster>
+                                    }
+                                })
+                                .collect_view()}
+                        </Stack>
+                    </Panel>
+                </Surface>
+            </Show>
+
+            <Show when=move || settings_state.get().active_section == SettingsSection::Permissions fallback=|| ()>
+                <Surface
+                    variant=SurfaceVariant::Muted
+                    elevation=Elevation::Inset
+                >
+                    <Panel variant=SurfaceVariant::Standard>
+                        <Heading role=TextRole::Title>"App permissions"</Heading>
+                        <Text tone=TextTone::Secondary>
+                            "Permissions you allowed when an app asked for them. Revoking one takes effect in open windows right away; the app has to ask again to get it back."
+                        </Text>
+                        <Show
+                            when=move || permissions.grants.with(|grants| !grants.is_empty())
+                            fallback=|| view! { <Text tone=TextTone::Secondary>"No app has asked for extra permissions yet."</Text> }
+                        >
+                            <Stack gap=LayoutGap::Sm>
+                                <For
+                                    each=move || permissions.grants.get()
+                                    key=|grant| format!("{}:{}", grant.app_id, grant.capability.as_str())
+                                    let:grant
+                                >
+                                    {
+                                        let app_id = grant.app_id.clone();
+                                        let capability = grant.capability;
+                                        view! {
+                                            <Cluster justify=LayoutJustify::Between>
+                                                <Stack gap=LayoutGap::Sm>
+                                                    <Text role=TextRole::Label>
+                                                        {launcher.with_value(|launcher| launcher.display_name(&grant.app_id))}
+                                                    </Text>
+                                                    <Text>{capability_label(capability)}</Text>
+                                                </Stack>
+                                                <Button
+                                                    variant=ButtonVariant::Quiet
+                                                    on_click=Callback::new(move |_| permissions.revoke(app_id.clone(), capability))
+                                                >
+                                                    "Revoke"
+                                                </Button>
+                                            </Cluster>
+                                        }
+                                    }
+                                </For>
+                            </Stack>
+                        </Show>
+                    </Panel>
+                </Surface>
+            </Show>
+
+            <Show when=move || settings_state.get().active_section == SettingsSection::Storage fallback=|| ()>
+                <Surface
+                    variant=SurfaceVariant::Muted
+                    elevation=Elevation::Inset
+                >
+                    <Stack gap=LayoutGap::Md>
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Cluster justify=LayoutJustify::Between>
+                                <Heading role=TextRole::Title>"Storage"</Heading>
+                                <Button
+                                    variant=ButtonVariant::Quiet
+                                    on_click=Callback::new(move |_| refresh_storage())
+                                >
+                                    "Refresh"
+                                </Button>
+                            </Cluster>
+                            <Text>
+                                {move || storage_report.with(|report| match report.files.as_ref().and_then(|files| files.usage_bytes) {
+                                    Some(used) => match report.files.as_ref().and_then(|files| files.quota_bytes) {
+                                        Some(quota) => format!("{} of {} used", format_bytes(used), format_bytes(quota)),
+                                        None => format!("{} used", format_bytes(used)),
+                                    },
+                                    None => "This host does not report storage usage.".to_string(),
+                                })}
+                            </Text>
+                            <Text tone=TextTone::Secondary>
+                                {move || storage_report.with(|report| format!(
+                                    "App state {}, preferences {}, caches {}",
+                                    format_bytes(total_usage_bytes(&report.app_state)),
+                                    format_bytes(total_usage_bytes(&report.prefs)),
+                                    format_bytes(total_usage_bytes(&report.caches)),
+                                ))}
+                            </Text>
+                            <Cluster justify=LayoutJustify::Between>
+                                <Text tone=TextTone::Secondary>
+                                    {move || match storage_report.with(|report| report.files.as_ref().and_then(|files| files.persisted)) {
+                                        Some(true) => "Storage is persistent and will not be cleared under storage pressure.",
+                                        Some(false) => "Storage is best-effort and may be cleared under storage pressure.",
+                                        None => "This host does not report whether storage is persistent.",
+                                    }}
+                                </Text>
+                                <Show
+                                    when=move || storage_report.with(|report| report.files.as_ref().and_then(|files| files.persisted) == Some(false))
+                                    fallback=|| ()
+                                >
+                                    <Button on_click=Callback::new(request_persistent_storage)>
+                                        "Request Persistent Storage"
+                                    </Button>
+                                </Show>
+                            </Cluster>
+                            <Show when=move || storage_error.with(Option::is_some) fallback=|| ()>
+                                <Text tone=TextTone::Danger>{move || storage_error.get().unwrap_or_default()}</Text>
+                            </Show>
+                        </Panel>
+
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Heading role=TextRole::Title>"App data"</Heading>
+                            <Text tone=TextTone::Secondary>
+                                "Clearing an app's data deletes its saved state, preferences, and cached content. Files in Explorer are kept."
+                            </Text>
+                            <Stack gap=LayoutGap::Sm>
+                                {move || {
+                                    let report = storage_report.get();
+                                    let apps = launcher.with_value(|launcher| {
+                                        launcher
+                                            .apps()
+                                            .iter()
+                                            .map(|app| (app.clone(), report.app_bytes(app)))
+                                            .filter(|(_, bytes)| *bytes > 0)
+                                            .collect::<Vec<_>>()
+                                    });
+                                    if apps.is_empty() {
+                                        return view! { <Text tone=TextTone::Secondary>"No app has stored data yet."</Text> }.into_view();
+                                    }
+                                    apps.into_iter()
+                                        .map(|(app, bytes)| {
+                                            let name = app.display_name.clone();
+                                            let target = app.clone();
+                                            view! {
+                                                <Cluster justify=LayoutJustify::Between>
+                                                    <Stack gap=LayoutGap::Sm>
+                                                        <Text role=TextRole::Label>{name}</Text>
+                                                        <Text tone=TextTone::Secondary>{format_bytes(bytes)}</Text>
+                                                    </Stack>
+                                                    <Button
+                                                        variant=ButtonVariant::Quiet
+                                                        on_click=Callback::new(move |_| clear_app_data(target.clone()))
+                                                    >
+                                                        "Clear Data"
+                                                    </Button>
+                                                </Cluster>
+                                            }
+                                        })
+                                        .collect_view()
+                                        .into_view()
+                                }}
+                            </Stack>
+                        </Panel>
+
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Heading role=TextRole::Title>"Files"</Heading>
+                            <StorageUsageTable
+                                aria_label="Folder usage"
+                                name_label="Folder"
+                                entries=Signal::derive(move || storage_report.with(|report| {
+                                    report
+                                        .folders
+                                        .iter()
+                                        .map(|(path, size)| StorageUsageEntry {
+                                            name: path.clone(),
+                                            entries: size.files,
+                                            bytes: size.bytes,
+                                        })
+                                        .collect()
+                                }))
+                            />
+                        </Panel>
+
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Heading role=TextRole::Title>"App state"</Heading>
+                            <StorageUsageTable
+                                aria_label="App state usage"
+                                name_label="Namespace"
+                                entries=Signal::derive(move || storage_report.with(|report| report.app_state.clone()))
+                            />
+                        </Panel>
+
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Heading role=TextRole::Title>"Preferences"</Heading>
+                            <StorageUsageTable
+                                aria_label="Preference usage"
+                                name_label="Key"
+                                entries=Signal::derive(move || storage_report.with(|report| report.prefs.clone()))
+                            />
+                        </Panel>
+
+                        <Panel variant=SurfaceVariant::Standard>
+                            <Heading role=TextRole::Title>"Caches"</Heading>
+                            <Show
+                                when=move || storage_report.with(|report| !report.caches.is_empty())
+                                fallback=|| view! { <Text tone=TextTone::Secondary>"Nothing stored."</Text> }
+                            >
+                                <Stack gap=LayoutGap::Sm>
+                                    <For
+                                        each=move || storage_report.get().caches
+                                        key=|entry| (entry.name.clone(), entry.entries, entry.bytes)
+                                        let:entry
+                                    >
+                                        {
+                                            let cache_name = entry.name.clone();
+                                            view! {
+                                                <Cluster justify=LayoutJustify::Between>
+                                                    <Stack gap=LayoutGap::Sm>
+                                                        <Text role=TextRole::Label>{entry.name}</Text>
+                                                        <Text tone=TextTone::Secondary>
+                                                            {format!("{} entries, {}", entry.entries, format_bytes(entry.bytes))}
+                                                        </Text>
+                                                    </Stack>
+                                                    <Button
+                                                        variant=ButtonVariant::Quiet
+                                                        on_click=Callback::new(move |_| clear_cache(cache_name.clone()))
+                                                    >
+                                                        "Clear"
+                                                    </Button>
+                                                </Cluster>
+                                            }
+                                        }
+                                    </For>
+                                </Stack>
+                            </Show>
+                        </Panel>
+                    </Stack>
+                </Surface>
+            </Show>
+
             <StatusBar>
                 <StatusBarItem>{move || format!("Skin: {}", theme_skin_id.get())}</StatusBarItem>
                 <StatusBarItem>
@@ -771,6 +1465,39 @@ pub fn SettingsApp(
     }
 }
 
+fn notification_delivery_label(delivery: NotificationDelivery) -> &'static str {
+    match delivery {
+        NotificationDelivery::Banner => "Show banners",
+        NotificationDelivery::Silent => "Silent",
+        NotificationDelivery::Muted => "Muted",
+    }
+}
+
+fn capability_label(capability: AppCapability) -> &'static str {
+    match capability {
+        AppCapability::Notifications => "Show notifications",
+        AppCapability::ExternalUrl => "Open links in your browser",
+        AppCapability::Network => "Connect to the internet",
+        _ => capability.as_str(),
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    let bytes_f = bytes as f64;
+    if bytes_f >= GB {
+        format!("{:.1} GB", bytes_f / GB)
+    } else if bytes_f >= MB {
+        format!("{:.1} MB", bytes_f / MB)
+    } else if bytes_f >= KB {
+        format!("{:.1} KB", bytes_f / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
 fn asset_to_config(asset: &WallpaperAssetRecord, current: &WallpaperConfig) -> WallpaperConfig {
     let animation = match asset.media_kind {
         WallpaperMediaKind::AnimatedImage | WallpaperMediaKind::Video => {
@@ -848,6 +1575,44 @@ fn asset_label_prefix(asset: &WallpaperAssetRecord) -> &'static str {
     }
 }
 
+#[component]
+fn StorageUsageTable(
+    aria_label: &'static str,
+    name_label: &'static str,
+    entries: Signal<Vec<StorageUsageEntry>>,
+) -> impl IntoView {
+    move || {
+        let entries = entries.get();
+        if entries.is_empty() {
+            return view! { <Text tone=TextTone::Secondary>"Nothing stored."</Text> }.into_view();
+        }
+        view! {
+            <DataTable aria_label=aria_label>
+                <thead>
+                    <tr>
+                        <th>{name_label}</th>
+                        <th>"Items"</th>
+                        <th>"Size"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {entries
+                        .into_iter()
+                        .map(|entry| view! {
+                            <tr>
+                                <td>{entry.name}</td>
+                                <td>{entry.entries}</td>
+                                <td>{format_bytes(entry.bytes)}</td>
+                            </tr>
+                        })
+                        .collect_view()}
+                </tbody>
+            </DataTable>
+        }
+        .into_view()
+    }
+}
+
 #[component]
 fn WallpaperLibraryItem(
     asset: WallpaperAssetRecord,
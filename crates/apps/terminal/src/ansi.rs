@@ -0,0 +1,226 @@
+//! ANSI escape sequence parsing for terminal output.
+//!
+//! Only SGR (`ESC [ ... m`) styling is interpreted: palette colors, bold, dim, italic, and
+//! underline. Every other escape sequence, including cursor movement, OSC titles, and 24-bit
+//! colors, is stripped so it never reaches the transcript as raw text.
+
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+
+/// SGR attributes active for a run of text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct AnsiStyle {
+    /// Foreground palette index (`0..=15`).
+    pub(crate) foreground: Option<u8>,
+    /// Background palette index (`0..=15`).
+    pub(crate) background: Option<u8>,
+    pub(crate) bold: bool,
+    pub(crate) dim: bool,
+    pub(crate) italic: bool,
+    pub(crate) underline: bool,
+}
+
+impl AnsiStyle {
+    pub(crate) fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn apply(&mut self, params: &str) {
+        let mut codes = params
+            .split([';', ':'])
+            .map(|code| code.parse::<u16>().unwrap_or(0));
+        // `ESC [ m` is a reset, and `split` yields one empty code for it.
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                30..=37 => self.foreground = Some((code - 30) as u8),
+                39 => self.foreground = None,
+                40..=47 => self.background = Some((code - 40) as u8),
+                49 => self.background = None,
+                90..=97 => self.foreground = Some((code - 90 + 8) as u8),
+                100..=107 => self.background = Some((code - 100 + 8) as u8),
+                38 | 48 => {
+                    let color = extended_color(&mut codes);
+                    if code == 38 {
+                        self.foreground = color.or(self.foreground);
+                    } else {
+                        self.background = color.or(self.background);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Consumes a `38`/`48` color argument, returning it only when it names one of the 16 palette
+/// colors; 256-color cube entries and 24-bit colors are skipped.
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<u8> {
+    match codes.next() {
+        Some(5) => codes
+            .next()
+            .filter(|index| *index < 16)
+            .map(|index| index as u8),
+        Some(2) => {
+            codes.take(3).for_each(drop);
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Run of text sharing one style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AnsiSegment {
+    pub(crate) text: String,
+    pub(crate) style: AnsiStyle,
+}
+
+/// Splits `text` into lines of styled segments, carrying SGR state across line breaks.
+///
+/// Like [`str::lines`], a trailing newline does not produce an empty final line.
+pub(crate) fn parse_ansi_lines(text: &str) -> Vec<Vec<AnsiSegment>> {
+    let mut style = AnsiStyle::default();
+    text.lines()
+        .map(|line| parse_line(line, &mut style))
+        .collect()
+}
+
+/// Returns `text` with every escape sequence removed, keeping its line structure.
+pub(crate) fn strip_ansi(text: &str) -> String {
+    parse_ansi_lines(text)
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_line(line: &str, style: &mut AnsiStyle) -> Vec<AnsiSegment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != ESC {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                let mut params = String::new();
+                let mut final_byte = None;
+                for ch in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&ch) {
+                        final_byte = Some(ch);
+                        break;
+                    }
+                    params.push(ch);
+                }
+                if final_byte == Some('m') {
+                    let mut next = *style;
+                    next.apply(&params);
+                    if next != *style {
+                        flush(&mut segments, &mut text, *style);
+                        *style = next;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(ch) = chars.next() {
+                    if ch == BEL || (ch == ESC && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            Some(' '..='/') => {
+                while chars.next_if(|ch| (' '..='/').contains(ch)).is_some() {}
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    flush(&mut segments, &mut text, *style);
+    segments
+}
+
+fn flush(segments: &mut Vec<AnsiSegment>, text: &mut String, style: AnsiStyle) {
+    if !text.is_empty() {
+        segments.push(AnsiSegment {
+            text: std::mem::take(text),
+            style,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str) -> AnsiSegment {
+        AnsiSegment {
+            text: text.to_string(),
+            style: AnsiStyle::default(),
+        }
+    }
+
+    #[test]
+    fn sgr_sequences_split_text_into_styled_segments() {
+        let lines = parse_ansi_lines("ok \u{1b}[1;31merror\u{1b}[0m done\n\u{1b}[4mnext");
+        assert_eq!(
+            lines,
+            vec![
+                vec![
+                    plain("ok "),
+                    AnsiSegment {
+                        text: "error".to_string(),
+                        style: AnsiStyle {
+                            foreground: Some(1),
+                            bold: true,
+                            ..AnsiStyle::default()
+                        },
+                    },
+                    plain(" done"),
+                ],
+                vec![AnsiSegment {
+                    text: "next".to_string(),
+                    style: AnsiStyle {
+                        underline: true,
+                        ..AnsiStyle::default()
+                    },
+                }],
+            ]
+        );
+    }
+
+    #[test]
+    fn style_carries_across_lines_and_extended_colors_map_to_the_palette() {
+        let lines = parse_ansi_lines("\u{1b}[38;5;12;48;2;1;2;3mblue\nstill\u{1b}[m");
+        let style = AnsiStyle {
+            foreground: Some(12),
+            ..AnsiStyle::default()
+        };
+        assert_eq!(lines[0][0].style, style);
+        assert_eq!(lines[1][0].style, style);
+    }
+
+    #[test]
+    fn unsupported_sequences_are_stripped() {
+        let lines =
+            parse_ansi_lines("\u{1b}]0;title\u{7}a\u{1b}[2Kb\u{1b}]8;;x\u{1b}\\c\u{1b}(Bd\u{1b}[");
+        assert_eq!(lines, vec![vec![plain("abcd")]]);
+        assert_eq!(strip_ansi("\u{1b}[31mred\u{1b}[0m\nplain\n"), "red\nplain");
+    }
+}
@@ -0,0 +1,183 @@
+//! Keyboard-driven copy mode over a pane's plain-text transcript.
+//!
+//! Copy mode freezes the transcript as plain lines and moves a line cursor over them with vi-like
+//! keys. Visual selection is line-wise: `v` anchors a selection at the cursor and movement extends
+//! it, and `y` yanks the selection (or the cursor line) for the clipboard.
+
+use std::ops::{Range, RangeInclusive};
+
+/// Transcript lines shown around the cursor while copy mode is active.
+pub(crate) const COPY_MODE_VIEW_LINES: usize = 24;
+const HALF_PAGE: usize = COPY_MODE_VIEW_LINES / 2;
+
+/// Result of a key handled by copy mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CopyModeOutcome {
+    /// Stay in copy mode.
+    Continue,
+    /// Copy this text and leave copy mode.
+    Yank(String),
+    /// Leave copy mode without copying.
+    Exit,
+}
+
+/// Cursor, selection anchor, and viewport over a frozen transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CopyMode {
+    lines: Vec<String>,
+    cursor: usize,
+    anchor: Option<usize>,
+    top: usize,
+}
+
+impl CopyMode {
+    /// Starts copy mode on the last line of `lines`.
+    pub(crate) fn new(mut lines: Vec<String>) -> Self {
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        let mut mode = Self {
+            cursor: lines.len() - 1,
+            lines,
+            anchor: None,
+            top: 0,
+        };
+        mode.scroll_to_cursor();
+        mode
+    }
+
+    /// Applies one key press, returning `None` for keys copy mode does not bind.
+    pub(crate) fn handle_key(&mut self, key: &str, ctrl: bool) -> Option<CopyModeOutcome> {
+        let last = self.lines.len() - 1;
+        match (key, ctrl) {
+            ("j" | "ArrowDown", false) => self.move_to(self.cursor.saturating_add(1).min(last)),
+            ("k" | "ArrowUp", false) => self.move_to(self.cursor.saturating_sub(1)),
+            ("d", true) | ("PageDown", false) => self.move_to((self.cursor + HALF_PAGE).min(last)),
+            ("u", true) | ("PageUp", false) => self.move_to(self.cursor.saturating_sub(HALF_PAGE)),
+            ("g" | "Home", false) => self.move_to(0),
+            ("G" | "End", false) => self.move_to(last),
+            ("v" | "V", false) => {
+                self.anchor = match self.anchor {
+                    Some(_) => None,
+                    None => Some(self.cursor),
+                };
+            }
+            ("y" | "Enter", false) => return Some(CopyModeOutcome::Yank(self.selected_text())),
+            ("Escape", false) if self.anchor.is_some() => self.anchor = None,
+            ("Escape" | "q", false) | ("c", true) => return Some(CopyModeOutcome::Exit),
+            _ => return None,
+        }
+        Some(CopyModeOutcome::Continue)
+    }
+
+    /// Lines covered by the visual selection, or the cursor line when nothing is selected.
+    pub(crate) fn selection(&self) -> RangeInclusive<usize> {
+        let anchor = self.anchor.unwrap_or(self.cursor);
+        anchor.min(self.cursor)..=anchor.max(self.cursor)
+    }
+
+    pub(crate) fn selected_text(&self) -> String {
+        self.lines[self.selection()].join("\n")
+    }
+
+    pub(crate) fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub(crate) fn is_visual(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// Indexes of the lines currently in view.
+    pub(crate) fn viewport(&self) -> Range<usize> {
+        self.top..(self.top + COPY_MODE_VIEW_LINES).min(self.lines.len())
+    }
+
+    pub(crate) fn line(&self, index: usize) -> &str {
+        &self.lines[index]
+    }
+
+    /// Mode line shown under the copy-mode view.
+    pub(crate) fn status(&self) -> String {
+        match self.anchor {
+            Some(_) => format!(
+                "-- VISUAL LINE -- {} line(s) selected; y copies, Esc clears.",
+                self.selection().count()
+            ),
+            None => format!(
+                "-- COPY -- line {}/{}; v selects, y copies, q exits.",
+                self.cursor + 1,
+                self.lines.len()
+            ),
+        }
+    }
+
+    fn move_to(&mut self, line: usize) {
+        self.cursor = line;
+        self.scroll_to_cursor();
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        if self.cursor < self.top {
+            self.top = self.cursor;
+        } else if self.cursor >= self.top + COPY_MODE_VIEW_LINES {
+            self.top = self.cursor + 1 - COPY_MODE_VIEW_LINES;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(count: usize) -> Vec<String> {
+        (0..count).map(|line| format!("line {line}")).collect()
+    }
+
+    #[test]
+    fn movement_clamps_and_keeps_the_cursor_in_view() {
+        let mut mode = CopyMode::new(lines(40));
+        assert_eq!(mode.cursor(), 39);
+        assert_eq!(mode.viewport(), 16..40);
+
+        mode.handle_key("j", false);
+        assert_eq!(mode.cursor(), 39);
+        mode.handle_key("g", false);
+        assert_eq!((mode.cursor(), mode.viewport()), (0, 0..24));
+        mode.handle_key("d", true);
+        mode.handle_key("ArrowDown", false);
+        assert_eq!(mode.cursor(), 13);
+        mode.handle_key("G", false);
+        mode.handle_key("k", false);
+        assert_eq!((mode.cursor(), mode.viewport()), (38, 16..40));
+        assert_eq!(mode.handle_key("x", false), None);
+    }
+
+    #[test]
+    fn visual_selection_yanks_the_covered_lines() {
+        let mut mode = CopyMode::new(lines(5));
+        assert_eq!(
+            mode.handle_key("y", false),
+            Some(CopyModeOutcome::Yank("line 4".to_string()))
+        );
+
+        mode.handle_key("v", false);
+        mode.handle_key("k", false);
+        mode.handle_key("k", false);
+        assert_eq!(mode.selection(), 2..=4);
+        assert_eq!(
+            mode.handle_key("y", false),
+            Some(CopyModeOutcome::Yank("line 2\nline 3\nline 4".to_string()))
+        );
+
+        assert_eq!(
+            mode.handle_key("Escape", false),
+            Some(CopyModeOutcome::Continue)
+        );
+        assert!(!mode.is_visual());
+        assert_eq!(
+            mode.handle_key("Escape", false),
+            Some(CopyModeOutcome::Exit)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Per-pane command history recalled with ArrowUp and Ctrl+R.
+//!
+//! Every pane records its own submissions, so parallel terminals never interleave each other's
+//! recall. The merged mode switches recall back to the shell's global history shared by every
+//! window.
+
+use serde::{Deserialize, Serialize};
+use system_shell_contract::HistoryMatch;
+
+/// Submissions each pane keeps, matching the shell's global history capacity.
+pub(crate) const PANE_HISTORY_CAPACITY: usize = 100;
+
+/// Which history ArrowUp and Ctrl+R recall from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HistoryMode {
+    /// The pane's own submissions.
+    #[default]
+    Pane,
+    /// The shell's global history, shared by every terminal window.
+    Merged,
+}
+
+impl HistoryMode {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Pane => "pane",
+            Self::Merged => "merged",
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        [Self::Pane, Self::Merged]
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+/// Records a submitted line the way the global history does: blank lines are ignored and a
+/// repeated line moves to the newest slot.
+pub(crate) fn record_history(entries: &mut Vec<String>, line: &str) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    entries.retain(|existing| existing != line);
+    entries.push(line.to_string());
+    if entries.len() > PANE_HISTORY_CAPACITY {
+        let overflow = entries.len() - PANE_HISTORY_CAPACITY;
+        entries.drain(..overflow);
+    }
+}
+
+/// Finds the newest entry containing `query` strictly older than index `before`.
+pub(crate) fn reverse_search(
+    entries: &[String],
+    query: &str,
+    before: Option<usize>,
+) -> Option<HistoryMatch> {
+    let end = before.unwrap_or(entries.len()).min(entries.len());
+    entries[..end]
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, line)| line.contains(query))
+        .map(|(index, line)| HistoryMatch {
+            index,
+            line: line.clone(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_dedupes_and_caps_entries() {
+        let mut entries = Vec::new();
+        for line in ["ls", "  ", "cd /docs", " ls "] {
+            record_history(&mut entries, line);
+        }
+        assert_eq!(entries, vec!["cd /docs", "ls"]);
+
+        for index in 0..PANE_HISTORY_CAPACITY {
+            record_history(&mut entries, &format!("echo {index}"));
+        }
+        assert_eq!(entries.len(), PANE_HISTORY_CAPACITY);
+        assert_eq!(entries[0], "echo 0");
+    }
+
+    #[test]
+    fn reverse_search_steps_to_older_matches() {
+        let entries = ["git status", "ls", "git log"].map(String::from);
+        let newest = reverse_search(&entries, "git", None).unwrap();
+        assert_eq!((newest.index, newest.line.as_str()), (2, "git log"));
+        let older = reverse_search(&entries, "git", Some(newest.index)).unwrap();
+        assert_eq!(older.index, 0);
+        assert_eq!(reverse_search(&entries, "git", Some(0)), None);
+        assert_eq!(HistoryMode::parse("Merged"), Some(HistoryMode::Merged));
+        assert_eq!(HistoryMode::parse("global"), None);
+    }
+}
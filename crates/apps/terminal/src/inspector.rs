@@ -0,0 +1,365 @@
+//! Structured output inspector: a collapsible tree over one data frame.
+//!
+//! Nodes are addressed by jq-style paths such as `.entries[3].path`, and tables are inspected as
+//! lists of row records. The selected node can be copied as a path or handed to the pane's shell
+//! session so a new command can read it back with `var`.
+
+use std::collections::BTreeSet;
+
+use system_shell_contract::{StructuredData, StructuredScalar, StructuredValue};
+
+/// Session variable that holds the subtree piped out of the inspector.
+pub(crate) const INSPECTOR_VARIABLE: &str = "inspected";
+
+/// Rows rendered at once; deeper expansions are still reachable by collapsing siblings.
+pub(crate) const INSPECTOR_ROW_LIMIT: usize = 400;
+
+/// One step from a value into a child.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+/// Formats a path the way jq addresses it; the root is `.`.
+pub(crate) fn format_path(path: &[PathSegment]) -> String {
+    if path.is_empty() {
+        return ".".to_string();
+    }
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Field(name) if is_identifier(name) => format!(".{name}"),
+            PathSegment::Field(name) => {
+                format!(".[{}]", serde_json::to_string(name).unwrap_or_default())
+            }
+            PathSegment::Index(index) => format!("[{index}]"),
+        })
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Tree root for a data frame, or `None` when the frame holds nothing to expand.
+fn root_value(data: &StructuredData) -> Option<StructuredValue> {
+    let value = match data {
+        StructuredData::Empty => return None,
+        StructuredData::Value(value) => value.clone(),
+        StructuredData::Record(record) => StructuredValue::Record(record.clone()),
+        StructuredData::List(values) => StructuredValue::List(values.clone()),
+        StructuredData::Table(table) => StructuredValue::List(
+            table
+                .rows
+                .iter()
+                .cloned()
+                .map(StructuredValue::Record)
+                .collect(),
+        ),
+    };
+    (!matches!(value, StructuredValue::Scalar(_))).then_some(value)
+}
+
+/// Whether a data frame has a record or list the inspector can open.
+pub(crate) fn inspectable(data: &StructuredData) -> bool {
+    root_value(data).is_some()
+}
+
+fn children(value: &StructuredValue) -> Vec<(PathSegment, &StructuredValue)> {
+    match value {
+        StructuredValue::Scalar(_) => Vec::new(),
+        StructuredValue::Record(record) => record
+            .fields
+            .iter()
+            .map(|field| (PathSegment::Field(field.name.clone()), &field.value))
+            .collect(),
+        StructuredValue::List(values) => values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (PathSegment::Index(index), value))
+            .collect(),
+    }
+}
+
+fn value_at<'a>(root: &'a StructuredValue, path: &[PathSegment]) -> Option<&'a StructuredValue> {
+    path.iter().try_fold(root, |value, segment| {
+        children(value)
+            .into_iter()
+            .find(|(child, _)| child == segment)
+            .map(|(_, value)| value)
+    })
+}
+
+fn summary(value: &StructuredValue) -> String {
+    match value {
+        StructuredValue::Scalar(StructuredScalar::Null) => "null".to_string(),
+        StructuredValue::Scalar(StructuredScalar::Bool(value)) => value.to_string(),
+        StructuredValue::Scalar(StructuredScalar::Int(value)) => value.to_string(),
+        StructuredValue::Scalar(StructuredScalar::Float(value)) => value.to_string(),
+        StructuredValue::Scalar(StructuredScalar::String(value)) => {
+            serde_json::to_string(value).unwrap_or_default()
+        }
+        StructuredValue::Record(record) => format!("{{{} fields}}", record.fields.len()),
+        StructuredValue::List(values) => format!("[{} items]", values.len()),
+    }
+}
+
+/// One visible line of the inspector tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InspectorRow {
+    pub(crate) path: Vec<PathSegment>,
+    pub(crate) depth: usize,
+    /// Field name, `[index]`, or `.` for the root.
+    pub(crate) label: String,
+    /// Scalar value, or the child count of a record or list.
+    pub(crate) summary: String,
+    pub(crate) expandable: bool,
+    pub(crate) expanded: bool,
+}
+
+impl InspectorRow {
+    /// Row text with indentation and a disclosure marker.
+    pub(crate) fn text(&self) -> String {
+        let marker = match (self.expandable, self.expanded) {
+            (false, _) => ' ',
+            (true, false) => '\u{25b8}',
+            (true, true) => '\u{25be}',
+        };
+        format!(
+            "{}{marker} {}: {}",
+            "  ".repeat(self.depth),
+            self.label,
+            self.summary
+        )
+    }
+}
+
+/// Collapsible tree state over one data frame.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Inspector {
+    data: StructuredData,
+    root: StructuredValue,
+    /// Formatted paths of expanded nodes.
+    expanded: BTreeSet<String>,
+    selected: Vec<PathSegment>,
+}
+
+impl Inspector {
+    /// Opens `data` with the root expanded and selected, or `None` when it is not inspectable.
+    pub(crate) fn new(data: StructuredData) -> Option<Self> {
+        let root = root_value(&data)?;
+        Some(Self {
+            data,
+            root,
+            expanded: BTreeSet::from([format_path(&[])]),
+            selected: Vec::new(),
+        })
+    }
+
+    /// Visible rows in display order, capped at [`INSPECTOR_ROW_LIMIT`].
+    pub(crate) fn rows(&self) -> Vec<InspectorRow> {
+        let mut rows = Vec::new();
+        self.push_rows(&self.root, Vec::new(), ".".to_string(), &mut rows);
+        rows
+    }
+
+    fn push_rows(
+        &self,
+        value: &StructuredValue,
+        path: Vec<PathSegment>,
+        label: String,
+        rows: &mut Vec<InspectorRow>,
+    ) {
+        if rows.len() >= INSPECTOR_ROW_LIMIT {
+            return;
+        }
+        let children = children(value);
+        let expanded = !children.is_empty() && self.expanded.contains(&format_path(&path));
+        rows.push(InspectorRow {
+            depth: path.len(),
+            label,
+            summary: summary(value),
+            expandable: !children.is_empty(),
+            expanded,
+            path: path.clone(),
+        });
+        if !expanded {
+            return;
+        }
+        for (segment, child) in children {
+            let label = match &segment {
+                PathSegment::Field(name) => name.clone(),
+                PathSegment::Index(index) => format!("[{index}]"),
+            };
+            let mut child_path = path.clone();
+            child_path.push(segment);
+            self.push_rows(child, child_path, label, rows);
+        }
+    }
+
+    pub(crate) fn is_selected(&self, path: &[PathSegment]) -> bool {
+        self.selected == path
+    }
+
+    pub(crate) fn select(&mut self, path: Vec<PathSegment>) {
+        self.selected = path;
+    }
+
+    /// Expands or collapses the node at `path`.
+    pub(crate) fn toggle(&mut self, path: &[PathSegment]) {
+        let key = format_path(path);
+        if !self.expanded.remove(&key) {
+            self.expanded.insert(key);
+        }
+    }
+
+    /// Path of the selected node, as copied to the clipboard.
+    pub(crate) fn selected_path(&self) -> String {
+        format_path(&self.selected)
+    }
+
+    /// Data under the selected node; the root keeps the frame's original shape.
+    pub(crate) fn selected_data(&self) -> Option<StructuredData> {
+        if self.selected.is_empty() {
+            return Some(self.data.clone());
+        }
+        value_at(&self.root, &self.selected).map(|value| StructuredData::Value(value.clone()))
+    }
+
+    /// Applies a tree navigation key, returning whether it was handled.
+    ///
+    /// Up and down move between visible rows, right expands or steps into a node, left collapses
+    /// or steps out to the parent, and Enter toggles.
+    pub(crate) fn handle_key(&mut self, key: &str) -> bool {
+        let rows = self.rows();
+        let Some(current) = rows.iter().position(|row| row.path == self.selected) else {
+            self.selected = Vec::new();
+            return true;
+        };
+        let row = &rows[current];
+        match key {
+            "ArrowDown" | "j" => {
+                if let Some(next) = rows.get(current + 1) {
+                    self.selected = next.path.clone();
+                }
+            }
+            "ArrowUp" | "k" => {
+                if let Some(previous) = current.checked_sub(1).map(|index| &rows[index]) {
+                    self.selected = previous.path.clone();
+                }
+            }
+            "ArrowRight" | "l" if row.expandable && !row.expanded => self.toggle(&row.path),
+            "ArrowRight" | "l" => {
+                if let Some(child) = rows.get(current + 1).filter(|next| next.depth > row.depth) {
+                    self.selected = child.path.clone();
+                }
+            }
+            "ArrowLeft" | "h" if row.expanded => self.toggle(&row.path),
+            "ArrowLeft" | "h" => {
+                self.selected.pop();
+            }
+            "Enter" | " " if row.expandable => self.toggle(&row.path),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use system_shell_contract::{StructuredField, StructuredRecord, StructuredTable};
+
+    fn text(value: &str) -> StructuredValue {
+        StructuredValue::Scalar(StructuredScalar::String(value.to_string()))
+    }
+
+    fn record(fields: Vec<(&str, StructuredValue)>) -> StructuredRecord {
+        StructuredRecord {
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| StructuredField {
+                    name: name.to_string(),
+                    value,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn paths_format_like_jq() {
+        assert_eq!(format_path(&[]), ".");
+        assert_eq!(
+            format_path(&[
+                PathSegment::Field("entries".to_string()),
+                PathSegment::Index(3),
+                PathSegment::Field("path".to_string()),
+            ]),
+            ".entries[3].path"
+        );
+        assert_eq!(
+            format_path(&[PathSegment::Field("my key".to_string())]),
+            ".[\"my key\"]"
+        );
+        assert!(!inspectable(&StructuredData::Value(text("plain"))));
+        assert!(!inspectable(&StructuredData::Empty));
+    }
+
+    #[test]
+    fn navigation_expands_nodes_and_selects_subtrees() {
+        let entries = (0..2)
+            .map(|index| {
+                StructuredValue::Record(record(vec![("path", text(&format!("/f{index}")))]))
+            })
+            .collect();
+        let data = StructuredData::Record(record(vec![
+            ("entries", StructuredValue::List(entries)),
+            ("total", StructuredValue::Scalar(StructuredScalar::Int(2))),
+        ]));
+        let mut inspector = Inspector::new(data.clone()).unwrap();
+        assert_eq!(
+            inspector
+                .rows()
+                .iter()
+                .map(InspectorRow::text)
+                .collect::<Vec<_>>(),
+            vec![
+                "\u{25be} .: {2 fields}",
+                "  \u{25b8} entries: [2 items]",
+                "    total: 2"
+            ]
+        );
+        assert_eq!(inspector.selected_data(), Some(data));
+
+        for key in ["j", "l", "l", "j", "l", "l"] {
+            assert!(inspector.handle_key(key));
+        }
+        assert_eq!(inspector.selected_path(), ".entries[1].path");
+        assert_eq!(
+            inspector.selected_data(),
+            Some(StructuredData::Value(text("/f1")))
+        );
+        assert_eq!(inspector.rows().len(), 6);
+
+        inspector.handle_key("ArrowLeft");
+        inspector.handle_key("ArrowLeft");
+        assert_eq!(inspector.selected_path(), ".entries[1]");
+        assert_eq!(inspector.rows().len(), 5);
+        assert!(!inspector.handle_key("x"));
+
+        let table = StructuredData::Table(StructuredTable {
+            columns: vec!["name".to_string()],
+            rows: vec![record(vec![("name", text("a"))])],
+            schema: None,
+            source_command: None,
+            fallback_text: None,
+        });
+        let inspector = Inspector::new(table.clone()).unwrap();
+        assert_eq!(inspector.rows()[0].summary, "[1 items]");
+        assert_eq!(inspector.selected_data(), Some(table));
+    }
+}
@@ -1,29 +1,77 @@
 //! Terminal desktop app UI component backed by the browser-native shell session bridge.
 //!
-//! The app persists cwd, input, transcript, and active-execution metadata through the runtime and
-//! renders typed shell notices, progress, and structured output produced by
-//! [`system_shell_contract`].
+//! A window holds one or more split panes, each with its own shell session. The app persists every
+//! pane's cwd, input, transcript, and active-execution metadata through the runtime and renders
+//! typed shell notices, progress, and structured output produced by [`system_shell_contract`].
 
 #![warn(missing_docs, rustdoc::broken_intra_doc_links)]
 
-use std::rc::Rc;
+mod ansi;
+mod copy_mode;
+mod history;
+mod inspector;
+mod paste;
+mod profile;
 
-use desktop_app_contract::{window_primary_input_dom_id, AppServices, WindowRuntimeId};
-use leptos::ev::KeyboardEvent;
+use std::{cmp::Ordering, collections::VecDeque, rc::Rc};
+
+use desktop_app_contract::{
+    accept_drag_over, take_drop, window_primary_input_dom_id, AppCapability, AppLifecycleEvent,
+    AppServices, ApplicationId, DragEffect, Notification, ShellSessionHandle, WindowRuntimeId,
+    FILE_DRAG_DATA_TYPE,
+};
+use leptos::ev::{DragEvent, KeyboardEvent};
 use leptos::html;
+use leptos::wasm_bindgen::JsCast;
 use leptos::*;
-use platform_host::CapabilityStatus;
+use platform_host::{normalize_virtual_path, CapabilityStatus};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use system_shell_contract::{
-    CommandNotice, CompletionItem, CompletionRequest, DisplayPreference, ExecutionId, ShellRequest,
-    ShellStreamEvent, StructuredData, StructuredRecord, StructuredScalar, StructuredTable,
-    StructuredValue,
+    last_token_start, CommandNotice, CommandNoticeLevel, CompletionItem, CompletionRequest,
+    DisplayPreference, ExecutionId, HistoryMatch, PromptKind, ShellRequest, ShellStreamEvent,
+    StructuredData, StructuredRecord, StructuredScalar, StructuredTable, StructuredValue,
 };
 use system_ui::prelude::*;
 
-const MAX_TERMINAL_ENTRIES: usize = 200;
+use crate::ansi::AnsiSegment;
+use crate::copy_mode::{CopyMode, CopyModeOutcome};
+use crate::history::{record_history, reverse_search, HistoryMode};
+use crate::inspector::{
+    format_path, inspectable, Inspector, INSPECTOR_ROW_LIMIT, INSPECTOR_VARIABLE,
+};
+use crate::paste::{insert_dropped_paths, needs_review, paste_commands, splice_paste};
+use crate::profile::{
+    active_profile, find_profile, render_prompt, ProfileFontSize, ProfileTheme, PromptContext,
+    TerminalProfile, DEFAULT_PROFILE,
+};
+
+/// Transcript entries each pane keeps until the user sets `terminal scrollback`.
+const DEFAULT_TERMINAL_SCROLLBACK: usize = 200;
+const MIN_TERMINAL_SCROLLBACK: usize = 50;
+const MAX_TERMINAL_SCROLLBACK: usize = 5_000;
+const TERMINAL_PREFS_KEY: &str = "retrodesk.terminal.prefs.v1";
+const TERMINAL_PROFILES_KEY: &str = "retrodesk.terminal.profiles.v1";
+const MAX_TERMINAL_PANES: usize = 4;
 const AUTO_FOLLOW_THRESHOLD_PX: i32 = 32;
+/// Transcript entries rendered while the view follows output.
+const TRANSCRIPT_WINDOW: usize = 60;
+/// Entries added to the rendered window each time the user scrolls to its top.
+const TRANSCRIPT_WINDOW_STEP: usize = 40;
+/// Distance from the top of the viewport that loads earlier entries.
+const LOAD_EARLIER_THRESHOLD_PX: i32 = 48;
+/// Best command palette matches listed at once.
+const PALETTE_ITEMS: usize = 12;
+/// Commands running at least this long raise a notification when they finish unwatched.
+const COMPLETION_NOTIFY_AFTER_MS: u64 = 10_000;
+
+/// Per-user terminal preferences shared by every terminal window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct TerminalPrefs {
+    scrollback: usize,
+    #[serde(default)]
+    history_mode: HistoryMode,
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct PersistedExecutionState {
@@ -52,20 +100,313 @@ enum TerminalTranscriptEntry {
         value: Option<f32>,
         label: Option<String>,
     },
+    Stdout {
+        execution_id: ExecutionId,
+        text: String,
+    },
+    Stderr {
+        execution_id: ExecutionId,
+        text: String,
+    },
     System {
         text: String,
     },
 }
 
+/// Persisted state of one terminal pane.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-struct TerminalPersistedState {
+struct TerminalPaneState {
     cwd: String,
     input: String,
     transcript: Vec<TerminalTranscriptEntry>,
+    /// Commands submitted in this pane, oldest first.
+    #[serde(default)]
+    history: Vec<String>,
     history_cursor: Option<usize>,
     active_execution: Option<PersistedExecutionState>,
 }
 
+impl TerminalPaneState {
+    fn new(cwd: String) -> Self {
+        Self {
+            cwd,
+            input: String::new(),
+            transcript: default_terminal_transcript(),
+            history: Vec::new(),
+            history_cursor: None,
+            active_execution: None,
+        }
+    }
+}
+
+/// How the panes of a split terminal window are arranged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TerminalSplit {
+    /// Panes sit side by side.
+    #[default]
+    SideBySide,
+    /// Panes stack top to bottom.
+    Stacked,
+}
+
+impl TerminalSplit {
+    fn direction(self) -> LayoutDirection {
+        match self {
+            Self::SideBySide => LayoutDirection::Row,
+            Self::Stacked => LayoutDirection::Column,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct TerminalPersistedState {
+    /// First pane, flattened so single-pane snapshots keep their original shape.
+    #[serde(flatten)]
+    primary: TerminalPaneState,
+    /// Panes split off the first one, in layout order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    splits: Vec<TerminalPaneState>,
+    #[serde(default)]
+    split: TerminalSplit,
+    #[serde(default)]
+    focused_pane: usize,
+    /// Profile selected for this window; `None` uses the default profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profile: Option<String>,
+    /// Whether a command typed in one pane is submitted to every pane.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    synchronized: bool,
+}
+
+/// Window-wide signals shared by every pane of one terminal window.
+#[derive(Clone, Copy)]
+struct TerminalWindow {
+    scrollback: RwSignal<usize>,
+    history_mode: RwSignal<HistoryMode>,
+    profiles: TerminalProfiles,
+    synchronized: RwSignal<bool>,
+    lifecycle: Option<ReadSignal<AppLifecycleEvent>>,
+}
+
+/// Stored terminal profiles and the profile selected for one window.
+#[derive(Clone, Copy)]
+struct TerminalProfiles {
+    stored: RwSignal<Vec<TerminalProfile>>,
+    selected: RwSignal<Option<String>>,
+}
+
+impl TerminalProfiles {
+    fn active(&self) -> TerminalProfile {
+        let selected = self.selected.get();
+        self.stored
+            .with(|stored| active_profile(stored, selected.as_deref()))
+    }
+
+    fn active_untracked(&self) -> TerminalProfile {
+        let selected = self.selected.get_untracked();
+        self.stored
+            .with_untracked(|stored| active_profile(stored, selected.as_deref()))
+    }
+}
+
+fn surface_font_size(size: ProfileFontSize) -> TerminalFontSize {
+    match size {
+        ProfileFontSize::Small => TerminalFontSize::Sm,
+        ProfileFontSize::Medium => TerminalFontSize::Md,
+        ProfileFontSize::Large => TerminalFontSize::Lg,
+    }
+}
+
+fn surface_theme(theme: ProfileTheme) -> TerminalTheme {
+    match theme {
+        ProfileTheme::Default => TerminalTheme::Default,
+        ProfileTheme::Light => TerminalTheme::Light,
+        ProfileTheme::Amber => TerminalTheme::Amber,
+        ProfileTheme::Green => TerminalTheme::Green,
+    }
+}
+
+/// Local wall-clock time for the `{time}` prompt placeholder.
+fn prompt_clock_time() -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let now = js_sys::Date::new_0();
+        format!(
+            "{:02}:{:02}:{:02}",
+            now.get_hours(),
+            now.get_minutes(),
+            now.get_seconds()
+        )
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let seconds = platform_host::unix_time_ms_now() / 1000 % 86_400;
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3_600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+/// Whether the user is looking elsewhere: the window lost focus or the desktop page is hidden.
+fn window_unwatched(lifecycle: Option<AppLifecycleEvent>) -> bool {
+    let blurred = matches!(
+        lifecycle,
+        Some(
+            AppLifecycleEvent::Blurred
+                | AppLifecycleEvent::Minimized
+                | AppLifecycleEvent::Suspended
+        )
+    );
+    #[cfg(target_arch = "wasm32")]
+    {
+        blurred || document().hidden()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        blurred
+    }
+}
+
+fn sync_notice(enabled: bool) -> &'static str {
+    if enabled {
+        "Input synchronization on: commands typed in any pane run in every pane."
+    } else {
+        "Input synchronization off."
+    }
+}
+
+/// Formats an execution duration as `42s` or `3m 05s`.
+fn format_elapsed(elapsed_ms: u64) -> String {
+    let seconds = elapsed_ms / 1000;
+    if seconds < 60 {
+        format!("{seconds}s")
+    } else {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    }
+}
+
+/// Pane layout action bound to a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PaneCommand {
+    Split(TerminalSplit),
+    Close,
+    FocusNext,
+    FocusPrevious,
+    ToggleSync,
+    NewWindow,
+}
+
+/// Maps Alt-based pane shortcuts by physical key so they survive keyboard layouts that remap
+/// Alt combinations to other characters.
+fn pane_command(ev: &KeyboardEvent) -> Option<PaneCommand> {
+    if !ev.alt_key() || ev.ctrl_key() || ev.meta_key() {
+        return None;
+    }
+    match (ev.code().as_str(), ev.shift_key()) {
+        ("Equal", true) => Some(PaneCommand::Split(TerminalSplit::SideBySide)),
+        ("Minus", true) => Some(PaneCommand::Split(TerminalSplit::Stacked)),
+        ("KeyW", true) => Some(PaneCommand::Close),
+        ("KeyS", true) => Some(PaneCommand::ToggleSync),
+        ("KeyN", true) => Some(PaneCommand::NewWindow),
+        ("BracketRight", false) => Some(PaneCommand::FocusNext),
+        ("BracketLeft", false) => Some(PaneCommand::FocusPrevious),
+        _ => None,
+    }
+}
+
+/// Clipboard action bound to a keyboard shortcut or a `terminal copy` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CopyAction {
+    EnterCopyMode,
+    LastOutput,
+    LastCommand,
+}
+
+/// Maps Alt+Shift copy shortcuts by physical key, like the pane shortcuts.
+fn copy_action(ev: &KeyboardEvent) -> Option<CopyAction> {
+    if !ev.alt_key() || !ev.shift_key() || ev.ctrl_key() || ev.meta_key() {
+        return None;
+    }
+    match ev.code().as_str() {
+        "KeyC" => Some(CopyAction::EnterCopyMode),
+        "KeyO" => Some(CopyAction::LastOutput),
+        "KeyP" => Some(CopyAction::LastCommand),
+        _ => None,
+    }
+}
+
+/// Ctrl+Shift+P opens the command palette, matched by physical key.
+fn opens_palette(ev: &KeyboardEvent) -> bool {
+    ev.ctrl_key() && ev.shift_key() && !ev.alt_key() && ev.code() == "KeyP"
+}
+
+/// Open command palette: the search query and the best registry matches for it.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CommandPalette {
+    query: String,
+    items: Vec<CompletionItem>,
+    selected: usize,
+}
+
+impl CommandPalette {
+    fn move_selection(&mut self, delta: isize) {
+        if !self.items.is_empty() {
+            let len = self.items.len() as isize;
+            self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+}
+
+/// Question a running command is waiting on, answered through the inline prompt input.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingPrompt {
+    execution_id: ExecutionId,
+    prompt: String,
+    kind: PromptKind,
+}
+
+impl PendingPrompt {
+    /// Transcript echo of the answer; secret answers are masked and never stored.
+    fn echo(&self, answer: Option<&str>) -> String {
+        let shown = match (answer, self.kind) {
+            (None, _) => "(dismissed)".to_string(),
+            (Some(_), PromptKind::Secret) => "********".to_string(),
+            (Some(answer), _) => answer.to_string(),
+        };
+        format!("{} {shown}", self.prompt)
+    }
+}
+
+/// Active Ctrl+R reverse history search.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct HistorySearchState {
+    query: String,
+    matched: Option<HistoryMatch>,
+}
+
+impl HistorySearchState {
+    fn label(&self) -> String {
+        match &self.matched {
+            Some(matched) => format!("(reverse-i-search)`{}`: {}", self.query, matched.line),
+            None if self.query.is_empty() => "(reverse-i-search)``: ".to_string(),
+            None => format!("(failed reverse-i-search)`{}`", self.query),
+        }
+    }
+
+    fn accepted_line(&self) -> String {
+        self.matched
+            .as_ref()
+            .map(|matched| matched.line.clone())
+            .unwrap_or_else(|| self.query.clone())
+    }
+}
+
 fn default_terminal_transcript() -> Vec<TerminalTranscriptEntry> {
     vec![TerminalTranscriptEntry::System {
         text: "Use `help list` to inspect commands.".to_string(),
@@ -94,22 +435,60 @@ fn terminal_mode_notice(services: Option<&AppServices>) -> &'static str {
     }
 }
 
-fn normalize_terminal_transcript(transcript: &mut Vec<TerminalTranscriptEntry>) {
+/// Restores the default banner for an empty transcript and trims it to `scrollback` entries,
+/// returning how many entries were dropped from the front.
+fn normalize_terminal_transcript(
+    transcript: &mut Vec<TerminalTranscriptEntry>,
+    scrollback: usize,
+) -> usize {
     if transcript.is_empty() {
         *transcript = default_terminal_transcript();
-        return;
+        return 0;
+    }
+
+    let overflow = transcript.len().saturating_sub(scrollback);
+    transcript.drain(0..overflow);
+    overflow
+}
+
+/// Changes whenever an entry's rendered content changes in place, as output chunks append to the
+/// last stdout or stderr entry.
+fn entry_revision(entry: &TerminalTranscriptEntry) -> usize {
+    match entry {
+        TerminalTranscriptEntry::Stdout { text, .. }
+        | TerminalTranscriptEntry::Stderr { text, .. } => text.len(),
+        _ => 0,
     }
+}
 
-    if transcript.len() > MAX_TERMINAL_ENTRIES {
-        let overflow = transcript.len() - MAX_TERMINAL_ENTRIES;
-        transcript.drain(0..overflow);
+/// Appends a stream chunk, extending the previous entry when it continues the same stream.
+fn push_output_chunk(
+    transcript: &mut Vec<TerminalTranscriptEntry>,
+    execution_id: ExecutionId,
+    stderr: bool,
+    text: &str,
+) {
+    match transcript.last_mut() {
+        Some(TerminalTranscriptEntry::Stdout {
+            execution_id: last,
+            text: existing,
+        }) if !stderr && *last == execution_id => existing.push_str(text),
+        Some(TerminalTranscriptEntry::Stderr {
+            execution_id: last,
+            text: existing,
+        }) if stderr && *last == execution_id => existing.push_str(text),
+        _ if stderr => transcript.push(TerminalTranscriptEntry::Stderr {
+            execution_id,
+            text: text.to_string(),
+        }),
+        _ => transcript.push(TerminalTranscriptEntry::Stdout {
+            execution_id,
+            text: text.to_string(),
+        }),
     }
 }
 
-fn restore_terminal_state(
-    mut restored: TerminalPersistedState,
-    launch_cwd: &str,
-) -> TerminalPersistedState {
+fn restore_pane_state(mut restored: TerminalPaneState, launch_cwd: &str) -> TerminalPaneState {
     if restored.cwd.trim().is_empty() {
         restored.cwd = launch_cwd.to_string();
     }
@@ -119,10 +498,39 @@ fn restore_terminal_state(
             text: "Previous command interrupted during restore.".to_string(),
         });
     }
-    normalize_terminal_transcript(&mut restored.transcript);
+    // Panes saved before per-pane history recall the commands still in their transcript.
+    if restored.history.is_empty() {
+        for entry in &restored.transcript {
+            if let TerminalTranscriptEntry::Prompt { command, .. } = entry {
+                record_history(&mut restored.history, command);
+            }
+        }
+    }
+    // The scrollback preference loads later and trims the live transcript once known.
+    normalize_terminal_transcript(&mut restored.transcript, MAX_TERMINAL_SCROLLBACK);
     restored
 }
 
+fn restore_terminal_state(
+    restored: TerminalPersistedState,
+    launch_cwd: &str,
+) -> TerminalPersistedState {
+    let splits = restored
+        .splits
+        .into_iter()
+        .take(MAX_TERMINAL_PANES - 1)
+        .map(|pane| restore_pane_state(pane, launch_cwd))
+        .collect::<Vec<_>>();
+    TerminalPersistedState {
+        primary: restore_pane_state(restored.primary, launch_cwd),
+        focused_pane: restored.focused_pane.min(splits.len()),
+        splits,
+        split: restored.split,
+        profile: restored.profile,
+        synchronized: restored.synchronized,
+    }
+}
+
 fn should_auto_follow(
     scroll_height: i32,
     scroll_top: i32,
@@ -138,24 +546,6 @@ fn scroll_terminal_to_bottom(terminal_screen: &NodeRef<html::Div>) {
     }
 }
 
-fn terminal_snapshot(
-    cwd: &RwSignal<String>,
-    input: &RwSignal<String>,
-    transcript: &RwSignal<Vec<TerminalTranscriptEntry>>,
-    history_cursor: &RwSignal<Option<usize>>,
-    active_execution: &RwSignal<Option<PersistedExecutionState>>,
-) -> TerminalPersistedState {
-    let mut snapshot = TerminalPersistedState {
-        cwd: cwd.get_untracked(),
-        input: input.get_untracked(),
-        transcript: transcript.get_untracked(),
-        history_cursor: history_cursor.get_untracked(),
-        active_execution: active_execution.get_untracked(),
-    };
-    normalize_terminal_transcript(&mut snapshot.transcript);
-    snapshot
-}
-
 fn completion_request(cwd: &str, line: &str) -> CompletionRequest {
     CompletionRequest {
         cwd: cwd.to_string(),
@@ -169,6 +559,27 @@ fn completion_request(cwd: &str, line: &str) -> CompletionRequest {
     }
 }
 
+/// Replaces the token under completion at the end of `line` with `value`.
+///
+/// Completion items carry a single token, so earlier words of the line are kept. The token is
+/// found with shell quoting rules, so a quoted argument containing spaces is replaced whole.
+fn apply_completion(line: &str, value: &str) -> String {
+    format!("{}{value} ", &line[..last_token_start(line)])
+}
+
+/// Splits `label` into runs of matched and unmatched characters for highlighting.
+fn matched_segments(label: &str, matched: &[usize]) -> Vec<(String, bool)> {
+    let mut segments: Vec<(String, bool)> = Vec::new();
+    for (index, ch) in label.chars().enumerate() {
+        let is_match = matched.contains(&index);
+        match segments.last_mut() {
+            Some((text, run)) if *run == is_match => text.push(ch),
+            _ => segments.push((ch.to_string(), is_match)),
+        }
+    }
+    segments
+}
+
 fn scalar_text(value: &StructuredScalar) -> String {
     match value {
         StructuredScalar::Null => "null".to_string(),
@@ -230,44 +641,257 @@ fn field_text(record: &StructuredRecord, name: &str) -> String {
         .unwrap_or_default()
 }
 
+/// Direction of an active column sort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Column and direction a rendered table is sorted by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TableSort {
+    column: String,
+    order: SortOrder,
+}
+
+/// Comparable projection of a table cell; variant order groups numbers before booleans and text.
+#[derive(Debug, PartialEq, PartialOrd)]
+enum SortKey {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+    Missing,
+}
+
+fn cell<'a>(record: &'a StructuredRecord, name: &str) -> Option<&'a StructuredValue> {
+    record
+        .fields
+        .iter()
+        .find(|field| field.name == name)
+        .map(|field| &field.value)
+}
+
+fn sort_key(value: Option<&StructuredValue>) -> SortKey {
+    match value {
+        Some(StructuredValue::Scalar(StructuredScalar::Int(value))) => {
+            SortKey::Number(*value as f64)
+        }
+        Some(StructuredValue::Scalar(StructuredScalar::Float(value))) => SortKey::Number(*value),
+        Some(StructuredValue::Scalar(StructuredScalar::Bool(value))) => SortKey::Bool(*value),
+        Some(StructuredValue::Scalar(StructuredScalar::String(value))) => {
+            SortKey::Text(value.to_lowercase())
+        }
+        Some(StructuredValue::Scalar(StructuredScalar::Null)) | None => SortKey::Missing,
+        Some(other) => SortKey::Text(value_summary(other)),
+    }
+}
+
+/// Cycles a header through ascending, descending, and the command's own row order.
+fn next_sort(current: Option<TableSort>, column: &str) -> Option<TableSort> {
+    match current {
+        Some(sort) if sort.column == column && sort.order == SortOrder::Ascending => {
+            Some(TableSort {
+                order: SortOrder::Descending,
+                ..sort
+            })
+        }
+        Some(sort) if sort.column == column => None,
+        _ => Some(TableSort {
+            column: column.to_string(),
+            order: SortOrder::Ascending,
+        }),
+    }
+}
+
+/// Returns `rows` ordered by `sort`, keeping empty cells last and ties in command order.
+fn sorted_rows(rows: &[StructuredRecord], sort: Option<&TableSort>) -> Vec<StructuredRecord> {
+    let mut rows = rows.to_vec();
+    let Some(sort) = sort else {
+        return rows;
+    };
+    rows.sort_by(|left, right| {
+        let left = sort_key(cell(left, &sort.column));
+        let right = sort_key(cell(right, &sort.column));
+        match (&left, &right) {
+            (SortKey::Missing, SortKey::Missing) => Ordering::Equal,
+            (SortKey::Missing, _) => Ordering::Greater,
+            (_, SortKey::Missing) => Ordering::Less,
+            _ => {
+                let ordering = left.partial_cmp(&right).unwrap_or(Ordering::Equal);
+                match sort.order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            }
+        }
+    });
+    rows
+}
+
+/// Whether every non-empty cell in `column` is a number, so the column aligns to the end.
+fn is_numeric_column(rows: &[StructuredRecord], column: &str) -> bool {
+    let mut keys = rows
+        .iter()
+        .map(|row| sort_key(cell(row, column)))
+        .filter(|key| *key != SortKey::Missing)
+        .peekable();
+    keys.peek().is_some() && keys.all(|key| matches!(key, SortKey::Number(_)))
+}
+
+fn aria_sort(sort: Option<&TableSort>, column: &str) -> &'static str {
+    match sort {
+        Some(sort) if sort.column == column => match sort.order {
+            SortOrder::Ascending => "ascending",
+            SortOrder::Descending => "descending",
+        },
+        _ => "none",
+    }
+}
+
 fn render_table(table: StructuredTable) -> impl IntoView {
-    let columns = table.columns.clone();
-    let rows = table.rows.clone();
-    view! {
-        <ListSurface>
+    let raw_json =
+        serde_json::to_string_pretty(&StructuredData::Table(table.clone())).unwrap_or_default();
+    let columns = Rc::new(
+        table
+            .columns
+            .iter()
+            .map(|column| (column.clone(), is_numeric_column(&table.rows, column)))
+            .collect::<Vec<_>>(),
+    );
+    let rows = Rc::new(table.rows);
+    let sort = create_rw_signal::<Option<TableSort>>(None);
+    let show_json = create_rw_signal(false);
+
+    let table_view = move || {
+        let header_columns = columns.clone();
+        let body_columns = columns.clone();
+        let rows = rows.clone();
+        view! {
             <DataTable role="table">
                 <thead>
                     <tr>
-                        {columns
+                        {header_columns
                             .iter()
-                            .map(|column| view! { <th>{column.clone()}</th> })
+                            .map(|(column, numeric)| {
+                                let label = column.clone();
+                                let sort_column = column.clone();
+                                let target = column.clone();
+                                let indicator_column = column.clone();
+                                view! {
+                                    <th
+                                        data-align=numeric.then_some("end")
+                                        aria-sort=move || {
+                                            aria_sort(sort.get().as_ref(), &sort_column)
+                                        }
+                                    >
+                                        <Button
+                                            variant=ButtonVariant::Quiet
+                                            size=ButtonSize::Sm
+                                            title=format!("Sort by {label}")
+                                            on_click=Callback::new(move |_| {
+                                                sort.update(|sort| *sort = next_sort(sort.take(), &target));
+                                            })
+                                        >
+                                            {label.clone()}
+                                            {move || match aria_sort(sort.get().as_ref(), &indicator_column) {
+                                                "ascending" => " \u{25b4}",
+                                                "descending" => " \u{25be}",
+                                                _ => "",
+                                            }}
+                                        </Button>
+                                    </th>
+                                }
+                            })
                             .collect_view()}
                     </tr>
                 </thead>
                 <tbody>
-                    {rows
-                        .iter()
-                        .enumerate()
-                        .map(|(index, row)| {
-                            view! {
-                                <tr data-row=index.to_string()>
-                                    {columns
-                                        .iter()
-                                        .map(|column| view! { <td>{field_text(row, column)}</td> })
-                                        .collect_view()}
-                                </tr>
-                            }
-                        })
-                        .collect_view()}
+                    {move || {
+                        sorted_rows(&rows, sort.get().as_ref())
+                            .iter()
+                            .enumerate()
+                            .map(|(index, row)| {
+                                view! {
+                                    <tr data-row=index.to_string()>
+                                        {body_columns
+                                            .iter()
+                                            .map(|(column, numeric)| {
+                                                view! {
+                                                    <td data-align=numeric.then_some("end")>
+                                                        {field_text(row, column)}
+                                                    </td>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </tr>
+                                }
+                            })
+                            .collect_view()
+                    }}
                 </tbody>
             </DataTable>
+        }
+    };
+
+    view! {
+        <ListSurface>
+            <div>
+                <Button
+                    variant=ButtonVariant::Quiet
+                    size=ButtonSize::Sm
+                    aria_pressed=Signal::derive(move || show_json.get())
+                    title="Toggle raw JSON"
+                    on_click=Callback::new(move |_| show_json.update(|show| *show = !*show))
+                >
+                    {move || if show_json.get() { "Table" } else { "JSON" }}
+                </Button>
+            </div>
+            <Show when=move || show_json.get() fallback=table_view>
+                {render_output(raw_json.clone(), TextTone::Secondary)}
+            </Show>
         </ListSurface>
     }
 }
 
-fn render_data(data: StructuredData, _display: DisplayPreference) -> View {
+fn render_manual(record: StructuredRecord) -> impl IntoView {
+    record
+        .fields
+        .into_iter()
+        .map(|field| {
+            let lines = match field.value {
+                StructuredValue::List(entries) => entries
+                    .iter()
+                    .map(|entry| match entry {
+                        StructuredValue::Record(record) => {
+                            format!(
+                                "{}    {}",
+                                field_text(record, "term"),
+                                field_text(record, "summary")
+                            )
+                        }
+                        other => value_summary(other),
+                    })
+                    .collect::<Vec<_>>(),
+                other => vec![value_summary(&other)],
+            };
+            view! {
+                <TerminalLine tone=TextTone::Accent>{field.name.to_uppercase()}</TerminalLine>
+                {lines
+                    .into_iter()
+                    .map(|line| view! { <TerminalLine>{format!("    {line}")}</TerminalLine> })
+                    .collect_view()}
+            }
+        })
+        .collect_view()
+}
+
+fn render_data(data: StructuredData, display: DisplayPreference) -> View {
     match data {
         StructuredData::Empty => ().into_view(),
+        StructuredData::Record(record) if display == DisplayPreference::Help => {
+            render_manual(record).into_view()
+        }
         StructuredData::Value(StructuredValue::Scalar(value)) => {
             view! { <TerminalLine>{scalar_text(&value)}</TerminalLine> }.into_view()
         }
@@ -277,6 +901,15 @@ fn render_data(data: StructuredData, _display: DisplayPreference) -> View {
         StructuredData::Value(StructuredValue::List(values)) | StructuredData::List(values) => {
             render_list(values).into_view()
         }
+        StructuredData::Table(table) if display == DisplayPreference::Record => table
+            .rows
+            .into_iter()
+            .map(|row| render_record(row).into_view())
+            .collect_view(),
+        StructuredData::Table(StructuredTable {
+            fallback_text: Some(text),
+            ..
+        }) if display == DisplayPreference::Value => render_output(text, TextTone::Primary),
         StructuredData::Table(table) => render_table(table).into_view(),
     }
 }
@@ -302,6 +935,8 @@ fn render_entry(entry: TerminalTranscriptEntry) -> View {
             }
             .into_view()
         }
+        TerminalTranscriptEntry::Stdout { text, .. } => render_output(text, TextTone::Primary),
+        TerminalTranscriptEntry::Stderr { text, .. } => render_output(text, TextTone::Danger),
         TerminalTranscriptEntry::System { text } => view! {
             <TerminalLine tone=TextTone::Secondary>{text}</TerminalLine>
         }
@@ -309,393 +944,2364 @@ fn render_entry(entry: TerminalTranscriptEntry) -> View {
     }
 }
 
-#[component]
-/// Terminal app window contents.
-///
-/// This component presents a browser-native shell backed by runtime-owned commands and persists
-/// transcript state via typed host contracts.
-pub fn TerminalApp(
-    /// Stable runtime window id used to expose the primary input focus target.
-    window_id: WindowRuntimeId,
-    /// App launch parameters (for example, the initial working directory).
-    launch_params: Value,
-    /// Manager-restored app state payload for this window instance.
-    restored_state: Option<Value>,
-    /// Optional app-host bridge for manager-owned commands.
-    services: Option<AppServices>,
-) -> impl IntoView {
-    let input_id = window_primary_input_dom_id(window_id);
-    let launch_cwd = launch_params
-        .get("cwd")
-        .and_then(Value::as_str)
-        .unwrap_or("~/desktop")
-        .to_string();
-    let mode_label = terminal_mode_label(services.as_ref());
-    let shell_session = services
-        .as_ref()
-        .and_then(|services| services.commands.create_session(launch_cwd.clone()).ok());
-    let services_for_persist = services.clone();
-    let cwd = create_rw_signal(launch_cwd.clone());
-    let input = create_rw_signal(String::new());
-    let transcript = create_rw_signal(default_terminal_transcript());
-    let suggestions = create_rw_signal(Vec::<CompletionItem>::new());
-    let history_cursor = create_rw_signal::<Option<usize>>(None);
-    let active_execution = create_rw_signal::<Option<PersistedExecutionState>>(None);
-    let processed_events = create_rw_signal(0usize);
-    let pending_command = create_rw_signal::<Option<String>>(None);
-    let hydrated = create_rw_signal(false);
-    let last_saved = create_rw_signal::<Option<String>>(None);
-    let should_follow_output = create_rw_signal(true);
-    let terminal_screen = create_node_ref::<html::Div>();
-    let prompt_mode = move || {
-        if active_execution.get().is_some() {
-            "running"
-        } else {
-            mode_label
-        }
-    };
-    if let Some(restored_state) = restored_state.as_ref() {
-        if let Ok(restored) =
-            serde_json::from_value::<TerminalPersistedState>(restored_state.clone())
-        {
-            let restored = restore_terminal_state(restored, &launch_cwd);
-            let serialized = serde_json::to_string(&restored).ok();
-            cwd.set(restored.cwd);
-            input.set(restored.input);
-            transcript.set(restored.transcript);
-            history_cursor.set(restored.history_cursor);
-            active_execution.set(restored.active_execution);
-            last_saved.set(serialized);
-            hydrated.set(true);
-        }
-    }
-    transcript.update(|entries| {
-        entries.push(TerminalTranscriptEntry::System {
-            text: terminal_mode_notice(services.as_ref()).to_string(),
-        });
-        normalize_terminal_transcript(entries);
-    });
-    hydrated.set(true);
-
-    create_effect(move |_| {
-        if !hydrated.get() {
-            return;
-        }
-
-        let _cwd = cwd.get();
-        let _input = input.get();
-        let _transcript = transcript.get();
-        let _history_cursor = history_cursor.get();
-        let _active_execution = active_execution.get();
-        let snapshot = terminal_snapshot(
-            &cwd,
-            &input,
-            &transcript,
-            &history_cursor,
-            &active_execution,
-        );
-
-        let serialized = match serde_json::to_string(&snapshot) {
-            Ok(raw) => raw,
-            Err(err) => {
-                logging::warn!("terminal serialize failed: {err}");
-                return;
+fn render_output(text: String, tone: TextTone) -> View {
+    ansi::parse_ansi_lines(&text)
+        .into_iter()
+        .map(|segments| {
+            view! {
+                <TerminalLine tone=tone>
+                    {segments.into_iter().map(render_segment).collect_view()}
+                </TerminalLine>
             }
-        };
+        })
+        .collect_view()
+}
 
-        if last_saved.get().as_deref() == Some(serialized.as_str()) {
-            return;
-        }
-        last_saved.set(Some(serialized));
+fn render_segment(segment: AnsiSegment) -> View {
+    let style = segment.style;
+    if style.is_plain() {
+        return segment.text.into_view();
+    }
+    view! {
+        <TerminalSpan
+            foreground=style.foreground.and_then(TerminalColor::from_ansi_index)
+            background=style.background.and_then(TerminalColor::from_ansi_index)
+            bold=style.bold
+            dim=style.dim
+            italic=style.italic
+            underline=style.underline
+        >
+            {segment.text}
+        </TerminalSpan>
+    }
+    .into_view()
+}
 
-        if let Some(services) = services_for_persist.clone() {
-            if let Ok(value) = serde_json::to_value(&snapshot) {
-                services.state.persist_window_state(value);
+/// Renders the copy-mode viewport with its selection, followed by the mode line.
+fn render_copy_mode(mode: &CopyMode) -> View {
+    let selection = mode.selection();
+    let lines = mode
+        .viewport()
+        .map(|index| {
+            let current = index == mode.cursor();
+            let line_ref = create_node_ref::<html::Div>();
+            if current {
+                line_ref.on_load(|line| line.scroll_into_view_with_bool(false));
             }
-        }
-    });
-
-    if let Some(shell_session) = shell_session.clone() {
-        create_effect(move |_| {
-            let events = shell_session.events.get();
-            let already_processed = processed_events.get();
-            if already_processed >= events.len() {
-                return;
+            let text = match mode.line(index) {
+                "" => " ".to_string(),
+                text => text.to_string(),
+            };
+            view! {
+                <TerminalLine
+                    selected=mode.is_visual() && selection.contains(&index)
+                    current=current
+                    node_ref=line_ref
+                >
+                    {text}
+                </TerminalLine>
             }
+        })
+        .collect_view();
+    let status = mode.status();
+    view! {
+        {lines}
+        <TerminalLine tone=TextTone::Accent>{status}</TerminalLine>
+    }
+    .into_view()
+}
 
-            for event in events.iter().skip(already_processed) {
-                match event {
-                    ShellStreamEvent::Started { execution_id } => {
-                        let command = pending_command.get_untracked().unwrap_or_default();
-                        if !command.is_empty() {
-                            active_execution.set(Some(PersistedExecutionState {
-                                execution_id: *execution_id,
-                                command,
-                            }));
-                            pending_command.set(None);
-                        }
-                    }
-                    ShellStreamEvent::Notice {
-                        execution_id,
-                        notice,
-                    } => transcript.update(|entries| {
-                        entries.push(TerminalTranscriptEntry::Notice {
-                            notice: notice.clone(),
-                            execution_id: *execution_id,
-                        });
-                        normalize_terminal_transcript(entries);
-                    }),
-                    ShellStreamEvent::Data {
-                        execution_id,
-                        data,
-                        display,
-                    } => transcript.update(|entries| {
-                        entries.push(TerminalTranscriptEntry::Data {
-                            data: data.clone(),
-                            display: *display,
-                            execution_id: *execution_id,
-                        });
-                        normalize_terminal_transcript(entries);
-                    }),
-                    ShellStreamEvent::Progress {
-                        execution_id,
-                        value,
-                        label,
-                    } => transcript.update(|entries| {
-                        entries.push(TerminalTranscriptEntry::Progress {
-                            execution_id: *execution_id,
-                            value: *value,
-                            label: label.clone(),
-                        });
-                        normalize_terminal_transcript(entries);
-                    }),
-                    ShellStreamEvent::Cancelled { .. } => {
-                        active_execution.set(None);
+/// Terminal commands the pane runs itself. The runtime registers matching no-op descriptors so
+/// they still appear in help and completion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LocalCommand {
+    Clear,
+    Scrollback(Option<String>),
+    HistoryMode(Option<String>),
+    /// `terminal sync`: `None` toggles input synchronization.
+    Sync(Option<bool>),
+    Export {
+        path: Option<String>,
+        json: bool,
+    },
+    Copy(CopyAction),
+    Inspect,
+    Profile(ProfileCommand),
+    Usage(&'static str),
+}
+
+/// `terminal profile` subcommands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ProfileCommand {
+    List,
+    Use(String),
+    Set {
+        name: String,
+        field: String,
+        value: String,
+    },
+    Delete(String),
+}
+
+/// Returns `line` after its first `count` words, keeping the remainder's inner spacing.
+fn after_words(line: &str, count: usize) -> &str {
+    let mut rest = line.trim_start();
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        rest = rest[end..].trim_start();
+    }
+    rest.trim_end()
+}
+
+fn local_command(line: &str) -> Option<LocalCommand> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let is = |word: &str, expected: &str| word.eq_ignore_ascii_case(expected);
+    match words.as_slice() {
+        [clear] if is(clear, "clear") => Some(LocalCommand::Clear),
+        [terminal, clear] if is(terminal, "terminal") && is(clear, "clear") => {
+            Some(LocalCommand::Clear)
+        }
+        [terminal, scrollback, rest @ ..]
+            if is(terminal, "terminal") && is(scrollback, "scrollback") =>
+        {
+            Some(match rest {
+                [] => LocalCommand::Scrollback(None),
+                [entries] => LocalCommand::Scrollback(Some(entries.to_string())),
+                _ => LocalCommand::Usage("terminal scrollback [<entries>]"),
+            })
+        }
+        [terminal, history, rest @ ..] if is(terminal, "terminal") && is(history, "history") => {
+            Some(match rest {
+                [] => LocalCommand::HistoryMode(None),
+                [mode] => LocalCommand::HistoryMode(Some(mode.to_string())),
+                _ => LocalCommand::Usage("terminal history [pane|merged]"),
+            })
+        }
+        [terminal, sync, rest @ ..] if is(terminal, "terminal") && is(sync, "sync") => {
+            Some(match rest {
+                [] => LocalCommand::Sync(None),
+                [state] if is(state, "on") => LocalCommand::Sync(Some(true)),
+                [state] if is(state, "off") => LocalCommand::Sync(Some(false)),
+                _ => LocalCommand::Usage("terminal sync [on|off]"),
+            })
+        }
+        [terminal, export, rest @ ..] if is(terminal, "terminal") && is(export, "export") => {
+            let json = rest.contains(&"--json");
+            let paths = rest
+                .iter()
+                .filter(|word| **word != "--json")
+                .collect::<Vec<_>>();
+            Some(match paths.as_slice() {
+                [] => LocalCommand::Export { path: None, json },
+                [path] if !path.starts_with('-') => LocalCommand::Export {
+                    path: Some(path.to_string()),
+                    json,
+                },
+                _ => LocalCommand::Usage("terminal export [--json] [<path>]"),
+            })
+        }
+        [terminal, copy, rest @ ..] if is(terminal, "terminal") && is(copy, "copy") => {
+            Some(match rest {
+                [target] if is(target, "output") => LocalCommand::Copy(CopyAction::LastOutput),
+                [target] if is(target, "command") => LocalCommand::Copy(CopyAction::LastCommand),
+                [target] if is(target, "mode") => LocalCommand::Copy(CopyAction::EnterCopyMode),
+                _ => LocalCommand::Usage("terminal copy <output|command|mode>"),
+            })
+        }
+        [terminal, inspect, rest @ ..] if is(terminal, "terminal") && is(inspect, "inspect") => {
+            Some(match rest {
+                [] => LocalCommand::Inspect,
+                _ => LocalCommand::Usage("terminal inspect"),
+            })
+        }
+        [terminal, profile, rest @ ..] if is(terminal, "terminal") && is(profile, "profile") => {
+            Some(match rest {
+                [] => LocalCommand::Profile(ProfileCommand::List),
+                [action, name] if is(action, "use") => {
+                    LocalCommand::Profile(ProfileCommand::Use(name.to_string()))
+                }
+                [action, name, field, ..] if is(action, "set") => {
+                    LocalCommand::Profile(ProfileCommand::Set {
+                        name: name.to_string(),
+                        field: field.to_string(),
+                        value: after_words(line, 5).to_string(),
+                    })
+                }
+                [action, name] if is(action, "delete") => {
+                    LocalCommand::Profile(ProfileCommand::Delete(name.to_string()))
+                }
+                _ => LocalCommand::Usage(
+                    "terminal profile [use <name> | set <name> <field> [<value>] | delete <name>]",
+                ),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Resolves an export destination against the pane cwd when the cwd is an explorer path.
+fn resolve_export_path(cwd: &str, path: &str) -> String {
+    if path.starts_with('/') || !cwd.starts_with('/') {
+        return normalize_virtual_path(path);
+    }
+    normalize_virtual_path(&format!("{}/{path}", cwd.trim_end_matches('/')))
+}
+
+/// Plain-text rendering of a transcript, as `terminal export` writes it.
+fn transcript_text(entries: &[TerminalTranscriptEntry]) -> String {
+    let mut text = transcript_lines(entries).join("\n");
+    text.push('\n');
+    text
+}
+
+/// Plain-text lines of a transcript, as exported and as copy mode shows them.
+fn transcript_lines(entries: &[TerminalTranscriptEntry]) -> Vec<String> {
+    entries
+        .iter()
+        .map(entry_text)
+        .filter(|entry| !entry.is_empty())
+        .flat_map(|entry| entry.lines().map(str::to_string).collect::<Vec<_>>())
+        .collect()
+}
+
+/// Plain text of the newest command's output: its stdout, stderr, and structured data entries.
+fn last_output_block(entries: &[TerminalTranscriptEntry]) -> Option<String> {
+    let output_execution = |entry: &TerminalTranscriptEntry| match entry {
+        TerminalTranscriptEntry::Data { execution_id, .. }
+        | TerminalTranscriptEntry::Stdout { execution_id, .. }
+        | TerminalTranscriptEntry::Stderr { execution_id, .. } => Some(*execution_id),
+        _ => None,
+    };
+    let last = entries.iter().rev().find_map(output_execution)?;
+    let text = entries
+        .iter()
+        .filter(|entry| output_execution(entry) == Some(last))
+        .map(entry_text)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Newest submitted command, skipping `terminal copy` invocations themselves.
+fn last_command(entries: &[TerminalTranscriptEntry]) -> Option<String> {
+    entries.iter().rev().find_map(|entry| match entry {
+        TerminalTranscriptEntry::Prompt { command, .. }
+            if !matches!(local_command(command), Some(LocalCommand::Copy(_))) =>
+        {
+            Some(command.clone())
+        }
+        _ => None,
+    })
+}
+
+fn entry_text(entry: &TerminalTranscriptEntry) -> String {
+    match entry {
+        TerminalTranscriptEntry::Prompt { cwd, command, .. } => {
+            format!("{cwd} \u{203a} {command}")
+        }
+        TerminalTranscriptEntry::Notice { notice, .. } => notice.message.clone(),
+        TerminalTranscriptEntry::Data { data, .. } => data_text(data),
+        TerminalTranscriptEntry::Progress { value, label, .. } => {
+            let label = label.as_deref().unwrap_or("progress");
+            match value {
+                Some(value) => format!("{label} {:.0}%", value * 100.0),
+                None => label.to_string(),
+            }
+        }
+        TerminalTranscriptEntry::Stdout { text, .. }
+        | TerminalTranscriptEntry::Stderr { text, .. } => ansi::strip_ansi(text),
+        TerminalTranscriptEntry::System { text } => text.clone(),
+    }
+}
+
+fn data_text(data: &StructuredData) -> String {
+    let record_text = |record: &StructuredRecord| {
+        record
+            .fields
+            .iter()
+            .map(|field| format!("{}: {}", field.name, value_summary(&field.value)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    match data {
+        StructuredData::Empty => String::new(),
+        StructuredData::Value(StructuredValue::Scalar(value)) => scalar_text(value),
+        StructuredData::Value(StructuredValue::Record(record)) | StructuredData::Record(record) => {
+            record_text(record)
+        }
+        StructuredData::Value(StructuredValue::List(values)) | StructuredData::List(values) => {
+            values
+                .iter()
+                .map(value_summary)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        StructuredData::Table(table) => table_text(table),
+    }
+}
+
+/// Renders a table as space-padded columns.
+fn table_text(table: &StructuredTable) -> String {
+    let rows = std::iter::once(table.columns.clone())
+        .chain(table.rows.iter().map(|row| {
+            table
+                .columns
+                .iter()
+                .map(|column| field_text(row, column))
+                .collect()
+        }))
+        .collect::<Vec<Vec<String>>>();
+    let widths = (0..table.columns.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Live signals and shell session behind one terminal pane.
+#[derive(Clone)]
+struct TerminalPane {
+    id: u64,
+    cwd: RwSignal<String>,
+    input: RwSignal<String>,
+    transcript: RwSignal<Vec<TerminalTranscriptEntry>>,
+    suggestions: RwSignal<Vec<CompletionItem>>,
+    history: RwSignal<Vec<String>>,
+    /// Window-wide choice between pane and merged recall, from the terminal preferences.
+    history_mode: RwSignal<HistoryMode>,
+    history_cursor: RwSignal<Option<usize>>,
+    history_search: RwSignal<Option<HistorySearchState>>,
+    active_execution: RwSignal<Option<PersistedExecutionState>>,
+    /// Unix time the active execution started, for completion notifications.
+    execution_started_ms: RwSignal<Option<u64>>,
+    pending_command: RwSignal<Option<String>>,
+    pending_prompt: RwSignal<Option<PendingPrompt>>,
+    prompt_answer: RwSignal<String>,
+    should_follow_output: RwSignal<bool>,
+    /// Entries ever removed from the front of the transcript, so rendered keys stay stable.
+    entry_offset: RwSignal<u64>,
+    /// Number of trailing transcript entries currently rendered.
+    window_len: RwSignal<usize>,
+    /// Window-wide transcript entry cap from the scrollback preference.
+    scrollback: RwSignal<usize>,
+    copy_mode: RwSignal<Option<CopyMode>>,
+    profiles: TerminalProfiles,
+    /// Exit code of the last completed command, for the `{exit_code}` prompt placeholder.
+    last_exit: RwSignal<Option<i32>>,
+    /// Editor text of a multi-line paste awaiting confirmation.
+    paste_review: RwSignal<Option<String>>,
+    /// Confirmed pasted commands still waiting for the running command to finish.
+    queued_commands: RwSignal<VecDeque<String>>,
+    palette: RwSignal<Option<CommandPalette>>,
+    /// Structured output inspector drawer, when open.
+    inspector: RwSignal<Option<Inspector>>,
+    /// Latest window lifecycle event, or `None` outside a managed window.
+    lifecycle: Option<ReadSignal<AppLifecycleEvent>>,
+    /// Window-wide input synchronization toggle.
+    synchronized: RwSignal<bool>,
+    screen: NodeRef<html::Div>,
+    command_input: NodeRef<html::Input>,
+    services: Option<AppServices>,
+    session: Option<ShellSessionHandle>,
+}
+
+impl TerminalPane {
+    /// Creates the pane signals, opens its shell session, and installs the effects that stream
+    /// session events into the transcript.
+    fn new(
+        id: u64,
+        state: TerminalPaneState,
+        services: Option<AppServices>,
+        window: TerminalWindow,
+    ) -> Self {
+        let session = services
+            .as_ref()
+            .and_then(|services| services.commands.create_session(state.cwd.clone()).ok());
+        if let Some(session) = &session {
+            session.attach_prompt_responder();
+        }
+        let pane = Self {
+            id,
+            cwd: create_rw_signal(state.cwd),
+            input: create_rw_signal(state.input),
+            transcript: create_rw_signal(state.transcript),
+            suggestions: create_rw_signal(Vec::new()),
+            history: create_rw_signal(state.history),
+            history_mode: window.history_mode,
+            history_cursor: create_rw_signal(state.history_cursor),
+            history_search: create_rw_signal(None),
+            active_execution: create_rw_signal(state.active_execution),
+            execution_started_ms: create_rw_signal(None),
+            pending_command: create_rw_signal(None),
+            pending_prompt: create_rw_signal(None),
+            prompt_answer: create_rw_signal(String::new()),
+            should_follow_output: create_rw_signal(true),
+            entry_offset: create_rw_signal(0),
+            window_len: create_rw_signal(TRANSCRIPT_WINDOW),
+            scrollback: window.scrollback,
+            copy_mode: create_rw_signal(None),
+            profiles: window.profiles,
+            last_exit: create_rw_signal(None),
+            paste_review: create_rw_signal(None),
+            queued_commands: create_rw_signal(VecDeque::new()),
+            palette: create_rw_signal(None),
+            inspector: create_rw_signal(None),
+            lifecycle: window.lifecycle,
+            synchronized: window.synchronized,
+            screen: create_node_ref::<html::Div>(),
+            command_input: create_node_ref::<html::Input>(),
+            services,
+            session,
+        };
+        pane.push_entry(TerminalTranscriptEntry::System {
+            text: terminal_mode_notice(pane.services.as_ref()).to_string(),
+        });
+        pane.install_effects();
+        pane
+    }
+
+    fn install_effects(&self) {
+        let pane = self.clone();
+        create_effect(move |_| {
+            let _scrollback = pane.scrollback.get();
+            pane.update_transcript(|_| {});
+        });
+
+        if let Some(session) = self.session.clone() {
+            let pane = self.clone();
+            create_effect(move |_| {
+                let events = session.events.get();
+                if events.is_empty() {
+                    return;
+                }
+
+                for event in &events {
+                    pane.apply_event(event);
+                }
+
+                session.acknowledge_events(events.len());
+                pane.cwd.set(session.cwd.get());
+            });
+        }
+
+        let transcript = self.transcript;
+        let should_follow_output = self.should_follow_output;
+        let screen = self.screen;
+        create_effect(move |_| {
+            let _transcript_len = transcript.get().len();
+            if should_follow_output.get() {
+                scroll_terminal_to_bottom(&screen);
+            }
+        });
+
+        if let Some(services) = self.services.as_ref() {
+            let registry_generation = services.commands.registry_generation;
+            let pane = self.clone();
+            create_effect(move |previous: Option<u64>| {
+                let generation = registry_generation.get();
+                let changed = previous.is_some_and(|previous| previous != generation);
+                if changed && !pane.suggestions.get_untracked().is_empty() {
+                    pane.refresh_suggestions();
+                }
+                if changed {
+                    if let Some(palette) = pane.palette.get_untracked() {
+                        pane.search_palette(palette.query);
+                    }
+                }
+                generation
+            });
+        }
+    }
+
+    /// Reads the persisted fields tracked, so persistence effects rerun when any changes.
+    fn snapshot(&self) -> TerminalPaneState {
+        let mut snapshot = TerminalPaneState {
+            cwd: self.cwd.get(),
+            input: self.input.get(),
+            transcript: self.transcript.get(),
+            history: self.history.get(),
+            history_cursor: self.history_cursor.get(),
+            active_execution: self.active_execution.get(),
+        };
+        normalize_terminal_transcript(&mut snapshot.transcript, self.scrollback.get_untracked());
+        snapshot
+    }
+
+    fn push_entry(&self, entry: TerminalTranscriptEntry) {
+        self.update_transcript(|entries| entries.push(entry));
+    }
+
+    /// Edits the transcript, then trims it to the entry cap and advances the key offset past any
+    /// entries dropped from the front.
+    fn update_transcript(&self, edit: impl FnOnce(&mut Vec<TerminalTranscriptEntry>)) {
+        let mut dropped = 0;
+        self.transcript.update(|entries| {
+            edit(entries);
+            dropped = normalize_terminal_transcript(entries, self.scrollback.get_untracked());
+        });
+        if dropped > 0 {
+            self.entry_offset.update(|offset| *offset += dropped as u64);
+        }
+    }
+
+    fn clear_transcript(&self) {
+        let cleared = self.transcript.with_untracked(Vec::len);
+        self.entry_offset.update(|offset| *offset += cleared as u64);
+        self.transcript.set(default_terminal_transcript());
+        self.window_len.set(TRANSCRIPT_WINDOW);
+    }
+
+    /// Renders another page of earlier entries, keeping the visible content in place.
+    fn load_earlier_entries(&self) {
+        let hidden = self
+            .transcript
+            .with_untracked(Vec::len)
+            .saturating_sub(self.window_len.get_untracked());
+        if hidden == 0 {
+            return;
+        }
+        let Some(screen) = self.screen.get_untracked() else {
+            return;
+        };
+        let distance_from_bottom = screen.scroll_height() - screen.scroll_top();
+        self.window_len
+            .update(|window| *window += TRANSCRIPT_WINDOW_STEP.min(hidden));
+        request_animation_frame(move || {
+            screen.set_scroll_top(screen.scroll_height() - distance_from_bottom);
+        });
+    }
+
+    /// Keyed slice of the transcript currently rendered.
+    fn visible_entries(&self) -> Vec<((u64, usize), TerminalTranscriptEntry)> {
+        let offset = self.entry_offset.get();
+        let window = self.window_len.get();
+        self.transcript.with(|entries| {
+            let start = entries.len().saturating_sub(window);
+            entries[start..]
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    (
+                        (offset + (start + index) as u64, entry_revision(entry)),
+                        entry.clone(),
+                    )
+                })
+                .collect()
+        })
+    }
+
+    fn apply_event(&self, event: &ShellStreamEvent) {
+        match event {
+            ShellStreamEvent::Started { execution_id } => {
+                let command = self.pending_command.get_untracked().unwrap_or_default();
+                if !command.is_empty() {
+                    self.active_execution.set(Some(PersistedExecutionState {
+                        execution_id: *execution_id,
+                        command,
+                    }));
+                    self.execution_started_ms
+                        .set(Some(platform_host::unix_time_ms_now()));
+                    self.pending_command.set(None);
+                }
+            }
+            ShellStreamEvent::Notice {
+                execution_id,
+                notice,
+            } => self.push_entry(TerminalTranscriptEntry::Notice {
+                notice: notice.clone(),
+                execution_id: *execution_id,
+            }),
+            ShellStreamEvent::Data {
+                execution_id,
+                data,
+                display,
+                more,
+            } => self.update_transcript(|entries| {
+                entries.push(TerminalTranscriptEntry::Data {
+                    data: data.clone(),
+                    display: *display,
+                    execution_id: *execution_id,
+                });
+                if let Some(more) = more {
+                    entries.push(TerminalTranscriptEntry::Notice {
+                        notice: CommandNotice {
+                            level: CommandNoticeLevel::Info,
+                            message: format!(
+                                "{} more row(s); run `page next` or `page all {}`",
+                                more.remaining, more.token
+                            ),
+                        },
+                        execution_id: *execution_id,
+                    });
+                }
+            }),
+            ShellStreamEvent::Progress {
+                execution_id,
+                value,
+                label,
+            } => self.push_entry(TerminalTranscriptEntry::Progress {
+                execution_id: *execution_id,
+                value: *value,
+                label: label.clone(),
+            }),
+            ShellStreamEvent::StdoutChunk { execution_id, text } => {
+                self.update_transcript(|entries| {
+                    push_output_chunk(entries, *execution_id, false, text)
+                })
+            }
+            ShellStreamEvent::StderrChunk { execution_id, text } => self
+                .update_transcript(|entries| push_output_chunk(entries, *execution_id, true, text)),
+            ShellStreamEvent::PromptRequest {
+                execution_id,
+                prompt,
+                kind,
+            } => {
+                self.prompt_answer.set(String::new());
+                self.pending_prompt.set(Some(PendingPrompt {
+                    execution_id: *execution_id,
+                    prompt: prompt.clone(),
+                    kind: *kind,
+                }));
+            }
+            ShellStreamEvent::Completed { summary } => {
+                self.last_exit.set(Some(summary.exit.code));
+                self.notify_if_unwatched(summary.exit.code);
+                self.active_execution.set(None);
+                self.pending_prompt.set(None);
+                if self
+                    .queued_commands
+                    .with_untracked(|queue| !queue.is_empty())
+                {
+                    let pane = self.clone();
+                    request_animation_frame(move || pane.run_next_queued());
+                }
+            }
+            ShellStreamEvent::Cancelled { .. } => {
+                self.execution_started_ms.set(None);
+                self.active_execution.set(None);
+                self.pending_prompt.set(None);
+                let skipped = self.queued_commands.with_untracked(VecDeque::len);
+                if skipped > 0 {
+                    self.queued_commands.set(VecDeque::new());
+                    self.push_entry(TerminalTranscriptEntry::System {
+                        text: format!("Skipped {skipped} queued pasted command(s)."),
+                    });
+                }
+            }
+            ShellStreamEvent::Truncated { dropped } => {
+                self.push_entry(TerminalTranscriptEntry::System {
+                    text: format!("{dropped} earlier shell event(s) were dropped."),
+                })
+            }
+        }
+    }
+
+    /// Raises a host notification for a long command that finished while the window was
+    /// unfocused.
+    fn notify_if_unwatched(&self, exit_code: i32) {
+        let Some(started_ms) = self.execution_started_ms.get_untracked() else {
+            return;
+        };
+        self.execution_started_ms.set(None);
+        let Some(services) = self.services.as_ref() else {
+            return;
+        };
+        let elapsed_ms = platform_host::unix_time_ms_now().saturating_sub(started_ms);
+        let unwatched = window_unwatched(self.lifecycle.map(|lifecycle| lifecycle.get_untracked()));
+        if elapsed_ms < COMPLETION_NOTIFY_AFTER_MS
+            || !unwatched
+            || !services
+                .capabilities()
+                .can_use(AppCapability::Notifications)
+        {
+            return;
+        }
+        let Some(execution) = self.active_execution.get_untracked() else {
+            return;
+        };
+        let title = if exit_code == 0 {
+            "Command finished".to_string()
+        } else {
+            format!("Command failed (exit {exit_code})")
+        };
+        services.notifications.notify(
+            Notification::new(
+                title,
+                format!(
+                    "{} \u{2014} {}",
+                    execution.command,
+                    format_elapsed(elapsed_ms)
+                ),
+            )
+            .with_icon("terminal"),
+        );
+    }
+
+    fn submit(&self, command: String) {
+        let command = command.trim().to_string();
+        if command.is_empty() {
+            return;
+        }
+
+        self.push_entry(TerminalTranscriptEntry::Prompt {
+            cwd: self.cwd.get_untracked(),
+            command: command.clone(),
+            execution_id: None,
+        });
+
+        self.history
+            .update(|entries| record_history(entries, &command));
+        self.history_cursor.set(None);
+        self.suggestions.set(Vec::new());
+        self.input.set(String::new());
+
+        match local_command(&command) {
+            Some(LocalCommand::Clear) => {
+                self.clear_transcript();
+                self.active_execution.set(None);
+                self.pending_command.set(None);
+                return;
+            }
+            Some(LocalCommand::Scrollback(entries)) => {
+                self.set_scrollback(entries);
+                return;
+            }
+            Some(LocalCommand::HistoryMode(mode)) => {
+                self.set_history_mode(mode);
+                return;
+            }
+            Some(LocalCommand::Sync(enabled)) => {
+                let enabled = enabled.unwrap_or(!self.synchronized.get_untracked());
+                self.synchronized.set(enabled);
+                self.push_entry(TerminalTranscriptEntry::System {
+                    text: sync_notice(enabled).to_string(),
+                });
+                return;
+            }
+            Some(LocalCommand::Export { path, json }) => {
+                self.export_transcript(path, json);
+                return;
+            }
+            Some(LocalCommand::Copy(action)) => {
+                self.run_copy_action(action);
+                return;
+            }
+            Some(LocalCommand::Inspect) => {
+                self.inspect_last_output();
+                return;
+            }
+            Some(LocalCommand::Profile(command)) => {
+                self.run_profile_command(command);
+                return;
+            }
+            Some(LocalCommand::Usage(usage)) => {
+                self.push_entry(TerminalTranscriptEntry::System {
+                    text: format!("usage: {usage}"),
+                });
+                return;
+            }
+            None => {}
+        }
+
+        match self.session.as_ref() {
+            Some(session) => {
+                self.pending_command.set(Some(command.clone()));
+                session.submit(ShellRequest {
+                    line: command,
+                    cwd: self.cwd.get_untracked(),
+                    source_window_id: None,
+                });
+            }
+            None => self.push_entry(TerminalTranscriptEntry::System {
+                text: "Shell session unavailable.".to_string(),
+            }),
+        }
+    }
+
+    fn set_scrollback(&self, entries: Option<String>) {
+        let Some(entries) = entries else {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: format!(
+                    "Scrollback keeps the newest {} entries.",
+                    self.scrollback.get_untracked()
+                ),
+            });
+            return;
+        };
+        let Ok(entries) = entries.parse::<usize>() else {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: format!(
+                    "`terminal scrollback` expects a number of entries, got `{entries}`."
+                ),
+            });
+            return;
+        };
+
+        let scrollback = entries.clamp(MIN_TERMINAL_SCROLLBACK, MAX_TERMINAL_SCROLLBACK);
+        self.scrollback.set(scrollback);
+        self.push_entry(TerminalTranscriptEntry::System {
+            text: format!("Scrollback set to {scrollback} entries."),
+        });
+        self.save_prefs();
+    }
+
+    fn set_history_mode(&self, mode: Option<String>) {
+        let Some(mode) = mode else {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: format!(
+                    "History recall uses {} history.",
+                    self.history_mode.get_untracked().as_str()
+                ),
+            });
+            return;
+        };
+        let Some(mode) = HistoryMode::parse(&mode) else {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: format!("`terminal history` expects `pane` or `merged`, got `{mode}`."),
+            });
+            return;
+        };
+
+        self.history_mode.set(mode);
+        self.history_cursor.set(None);
+        self.push_entry(TerminalTranscriptEntry::System {
+            text: format!("History recall set to {} history.", mode.as_str()),
+        });
+        self.save_prefs();
+    }
+
+    /// Writes the window-wide terminal preferences.
+    fn save_prefs(&self) {
+        let prefs = TerminalPrefs {
+            scrollback: self.scrollback.get_untracked(),
+            history_mode: self.history_mode.get_untracked(),
+        };
+        if let Some(services) = self.services.clone() {
+            spawn_local(async move {
+                if let Err(err) = services.prefs.save(TERMINAL_PREFS_KEY, &prefs).await {
+                    services
+                        .logs
+                        .warn(format!("terminal prefs persist failed: {err}"));
+                }
+            });
+        }
+    }
+
+    fn export_transcript(&self, path: Option<String>, json: bool) {
+        let Some(services) = self.services.clone() else {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: "Transcript export needs the explorer filesystem.".to_string(),
+            });
+            return;
+        };
+        let default_path = if json {
+            "terminal-transcript.json"
+        } else {
+            "terminal-transcript.txt"
+        };
+        let path = resolve_export_path(
+            &self.cwd.get_untracked(),
+            path.as_deref().unwrap_or(default_path),
+        );
+        let entries = self.transcript.get_untracked();
+        let body = if json {
+            serde_json::to_string_pretty(&entries).unwrap_or_default()
+        } else {
+            transcript_text(&entries)
+        };
+        let count = entries.len();
+        let pane = self.clone();
+        spawn_local(async move {
+            let text = match services.explorer.write_text_file(&path, &body).await {
+                Ok(_) => format!("Exported {count} transcript entries to {path}."),
+                Err(err) => format!("Transcript export to {path} failed: {err}"),
+            };
+            pane.push_entry(TerminalTranscriptEntry::System { text });
+        });
+    }
+
+    fn run_profile_command(&self, command: ProfileCommand) {
+        let profiles = self.profiles;
+        let text = match command {
+            ProfileCommand::List => {
+                let active = profiles.active_untracked().name;
+                let mut listed = profiles.stored.get_untracked();
+                if !listed.iter().any(|profile| profile.name == DEFAULT_PROFILE) {
+                    listed.insert(0, TerminalProfile::new(DEFAULT_PROFILE));
+                }
+                listed
+                    .iter()
+                    .map(|profile| {
+                        let marker = if profile.name == active { "*" } else { " " };
+                        format!("{marker} {}", profile.summary())
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            ProfileCommand::Use(name) => {
+                if profiles
+                    .stored
+                    .with_untracked(|stored| find_profile(stored, &name))
+                    .is_none()
+                {
+                    format!("No terminal profile named `{name}`; create it with `terminal profile set`.")
+                } else {
+                    profiles
+                        .selected
+                        .set((name != DEFAULT_PROFILE).then(|| name.clone()));
+                    format!("This window now uses the `{name}` profile.")
+                }
+            }
+            ProfileCommand::Set { name, field, value } => {
+                let mut stored = profiles.stored.get_untracked();
+                let index = match stored.iter().position(|profile| profile.name == name) {
+                    Some(index) => index,
+                    None => {
+                        stored.push(TerminalProfile::new(name.clone()));
+                        stored.len() - 1
                     }
-                    ShellStreamEvent::Completed { .. } => {
-                        active_execution.set(None);
+                };
+                match stored[index].set_field(&field, &value) {
+                    Ok(()) => {
+                        let summary = stored[index].summary();
+                        self.save_profiles(stored);
+                        format!("Saved profile {summary}")
                     }
+                    Err(err) => err,
+                }
+            }
+            ProfileCommand::Delete(name) => {
+                let mut stored = profiles.stored.get_untracked();
+                let count = stored.len();
+                stored.retain(|profile| profile.name != name);
+                if stored.len() == count {
+                    format!("No saved terminal profile named `{name}`.")
+                } else {
+                    self.save_profiles(stored);
+                    format!("Deleted profile `{name}`.")
+                }
+            }
+        };
+        self.push_entry(TerminalTranscriptEntry::System { text });
+    }
+
+    fn save_profiles(&self, stored: Vec<TerminalProfile>) {
+        self.profiles.stored.set(stored.clone());
+        if let Some(services) = self.services.clone() {
+            spawn_local(async move {
+                if let Err(err) = services.prefs.save(TERMINAL_PROFILES_KEY, &stored).await {
+                    services
+                        .logs
+                        .warn(format!("terminal profiles persist failed: {err}"));
                 }
+            });
+        }
+    }
+
+    /// Submits the active profile's startup command, if it has one.
+    fn run_startup_command(&self) {
+        if let Some(command) = self.profiles.active_untracked().startup_command {
+            self.submit(command);
+        }
+    }
+
+    fn run_copy_action(&self, action: CopyAction) {
+        let entries = self.transcript.get_untracked();
+        match action {
+            CopyAction::EnterCopyMode => {
+                self.suggestions.set(Vec::new());
+                self.copy_mode
+                    .set(Some(CopyMode::new(transcript_lines(&entries))));
+            }
+            CopyAction::LastOutput => match last_output_block(&entries) {
+                Some(text) => self.copy_text(text, "the last output block".to_string()),
+                None => self.push_entry(TerminalTranscriptEntry::System {
+                    text: "No command output to copy.".to_string(),
+                }),
+            },
+            CopyAction::LastCommand => match last_command(&entries) {
+                Some(command) => self.copy_text(command, "the last command".to_string()),
+                None => self.push_entry(TerminalTranscriptEntry::System {
+                    text: "No command to copy.".to_string(),
+                }),
+            },
+        }
+    }
+
+    /// Routes a key press to copy mode while it is active, returning whether the key was consumed.
+    ///
+    /// Unbound printable keys are swallowed too so they never reach the command input.
+    fn copy_mode_key(&self, ev: &KeyboardEvent) -> bool {
+        if self.copy_mode.with_untracked(Option::is_none) {
+            return false;
+        }
+        let key = ev.key();
+        let mut outcome = None;
+        self.copy_mode.update(|mode| {
+            outcome = mode
+                .as_mut()
+                .and_then(|mode| mode.handle_key(&key, ev.ctrl_key()));
+        });
+        match &outcome {
+            Some(CopyModeOutcome::Yank(text)) => {
+                self.leave_copy_mode();
+                let lines = text.lines().count().max(1);
+                self.copy_text(text.clone(), format!("{lines} line(s)"));
+            }
+            Some(CopyModeOutcome::Exit) => self.leave_copy_mode(),
+            Some(CopyModeOutcome::Continue) | None => {}
+        }
+        outcome.is_some() || key.chars().count() == 1
+    }
+
+    /// Restores the live transcript and returns the view to the prompt.
+    fn leave_copy_mode(&self) {
+        self.copy_mode.set(None);
+        self.should_follow_output.set(true);
+        let screen = self.screen;
+        request_animation_frame(move || scroll_terminal_to_bottom(&screen));
+    }
+
+    fn copy_text(&self, text: String, what: String) {
+        let Some(services) = self.services.clone() else {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: "Copying needs the desktop clipboard service.".to_string(),
+            });
+            return;
+        };
+        let pane = self.clone();
+        spawn_local(async move {
+            let message = match services.clipboard.write_text(&text).await {
+                Ok(()) => format!("Copied {what} to the clipboard."),
+                Err(err) => format!("Copying {what} failed: {err}"),
+            };
+            pane.push_entry(TerminalTranscriptEntry::System { text: message });
+        });
+    }
+
+    /// Opens the paste review editor instead of letting a multi-line paste reach the input.
+    fn paste(&self, ev: &web_sys::Event) {
+        let Some(text) = ev
+            .dyn_ref::<web_sys::ClipboardEvent>()
+            .and_then(|ev| ev.clipboard_data())
+            .and_then(|data| data.get_data("text").ok())
+        else {
+            return;
+        };
+        if !needs_review(&text) {
+            return;
+        }
+        ev.prevent_default();
+        let line = self.input.get_untracked();
+        let selection = self.input_selection(&line);
+        self.suggestions.set(Vec::new());
+        self.paste_review
+            .set(Some(splice_paste(&line, selection, &text)));
+    }
+
+    /// Current UTF-16 selection in the command input, or the end of `line` when unknown.
+    fn input_selection(&self, line: &str) -> (u32, u32) {
+        let end = line.encode_utf16().count() as u32;
+        self.command_input
+            .get_untracked()
+            .map_or((end, end), |input| {
+                (
+                    input.selection_start().ok().flatten().unwrap_or(end),
+                    input.selection_end().ok().flatten().unwrap_or(end),
+                )
+            })
+    }
+
+    /// Lets file drags from other app windows target the command input.
+    fn drag_over(&self, ev: &DragEvent) {
+        accept_drag_over(ev, FILE_DRAG_DATA_TYPE, DragEffect::Copy);
+    }
+
+    /// Inserts the quoted paths of files dropped from another app window at the input cursor.
+    fn drop_files(&self, ev: &DragEvent) {
+        let Some(paths) = take_drop(ev, FILE_DRAG_DATA_TYPE).and_then(|data| data.paths()) else {
+            return;
+        };
+        let line = self.input.get_untracked();
+        let (line, cursor) = insert_dropped_paths(&line, self.input_selection(&line), &paths);
+        self.suggestions.set(Vec::new());
+        self.input.set(line);
+        let command_input = self.command_input;
+        request_animation_frame(move || {
+            if let Some(input) = command_input.get_untracked() {
+                let _ = input.focus();
+                let _ = input.set_selection_range(cursor, cursor);
+            }
+        });
+    }
+
+    /// Queues the reviewed commands and submits the first; the rest follow as each completes.
+    fn run_paste_review(&self) {
+        let Some(text) = self.paste_review.get_untracked() else {
+            return;
+        };
+        let commands = paste_commands(&text);
+        if commands.is_empty() {
+            return;
+        }
+        self.paste_review.set(None);
+        self.queued_commands.set(commands.into());
+        self.run_next_queued();
+        self.focus_input();
+    }
+
+    fn cancel_paste_review(&self) {
+        self.paste_review.set(None);
+        self.focus_input();
+    }
+
+    /// Submits queued commands until one starts a shell execution; local commands finish
+    /// immediately, so the queue keeps draining past them.
+    fn run_next_queued(&self) {
+        loop {
+            let mut next = None;
+            self.queued_commands
+                .update(|queue| next = queue.pop_front());
+            let Some(command) = next else {
+                return;
+            };
+            self.submit(command);
+            if self.pending_command.get_untracked().is_some() {
+                return;
+            }
+        }
+    }
+
+    fn answer_prompt(&self, answer: Option<String>) {
+        let Some(prompt) = self.pending_prompt.get_untracked() else {
+            return;
+        };
+        self.pending_prompt.set(None);
+        self.prompt_answer.set(String::new());
+        self.push_entry(TerminalTranscriptEntry::System {
+            text: prompt.echo(answer.as_deref()),
+        });
+        if let Some(session) = self.session.as_ref() {
+            session.respond(prompt.execution_id, answer);
+        }
+    }
+
+    /// Entries ArrowUp recalls from in the current history mode, oldest first.
+    fn recall_history(&self) -> Vec<String> {
+        match (self.history_mode.get_untracked(), self.services.as_ref()) {
+            (HistoryMode::Merged, Some(services)) => services.commands.history.get_untracked(),
+            (HistoryMode::Merged, None) => Vec::new(),
+            (HistoryMode::Pane, _) => self.history.get_untracked(),
+        }
+    }
+
+    fn navigate_history(&self, direction: i32) {
+        let history = self.recall_history();
+        if history.is_empty() {
+            return;
+        }
+
+        // Another pane may have switched the mode under a stale cursor.
+        let cursor = self
+            .history_cursor
+            .get_untracked()
+            .filter(|index| *index < history.len());
+        let next_index = match (cursor, direction) {
+            (None, -1) => Some(history.len().saturating_sub(1)),
+            (Some(index), -1) if index > 0 => Some(index - 1),
+            (Some(index), 1) if index + 1 < history.len() => Some(index + 1),
+            (Some(_), 1) => None,
+            (current, _) => current,
+        };
+
+        self.history_cursor.set(next_index);
+        match next_index {
+            Some(index) => self.input.set(history[index].clone()),
+            None => self.input.set(String::new()),
+        }
+    }
+
+    fn search_history(&self, query: String, older: bool) {
+        let previous = self.history_search.get_untracked().unwrap_or_default();
+        let before = if older {
+            previous.matched.as_ref().map(|matched| matched.index)
+        } else {
+            None
+        };
+        let found = match (self.history_mode.get_untracked(), self.services.as_ref()) {
+            (HistoryMode::Merged, Some(services)) => {
+                services.commands.reverse_search_history(&query, before)
+            }
+            (HistoryMode::Merged, None) => return,
+            (HistoryMode::Pane, _) => self
+                .history
+                .with_untracked(|entries| reverse_search(entries, &query, before)),
+        };
+        let matched = found.or(if older { previous.matched } else { None });
+        self.history_search
+            .set(Some(HistorySearchState { query, matched }));
+    }
+
+    fn accept_history_search(&self) {
+        if let Some(search) = self.history_search.get_untracked() {
+            self.history_search.set(None);
+            self.history_cursor.set(None);
+            self.input.set(search.accepted_line());
+        }
+    }
+
+    fn complete(&self) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let current_input = self.input.get_untracked();
+        let cwd = self.cwd.get_untracked();
+        let pane = self.clone();
+        spawn_local(async move {
+            match session
+                .complete(completion_request(&cwd, &current_input))
+                .await
+            {
+                Ok(items) => {
+                    if items.len() == 1 {
+                        pane.input
+                            .set(apply_completion(&current_input, &items[0].value));
+                        pane.suggestions.set(Vec::new());
+                    } else {
+                        pane.suggestions.set(items);
+                    }
+                }
+                Err(err) => pane.push_entry(TerminalTranscriptEntry::System { text: err.message }),
+            }
+        });
+    }
+
+    /// Re-resolves open completion suggestions after the command registry changed.
+    fn refresh_suggestions(&self) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        let current_input = self.input.get_untracked();
+        let cwd = self.cwd.get_untracked();
+        let suggestions = self.suggestions;
+        spawn_local(async move {
+            if let Ok(items) = session
+                .complete(completion_request(&cwd, &current_input))
+                .await
+            {
+                suggestions.set(items);
             }
-
-            processed_events.set(events.len());
-            cwd.set(shell_session.cwd.get());
         });
     }
 
-    create_effect(move |_| {
-        let _transcript_len = transcript.get().len();
-        let hydrated = hydrated.get();
-        let should_follow_output = should_follow_output.get();
-        if !hydrated || !should_follow_output {
+    fn open_palette(&self) {
+        if self.services.is_none() {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: "The command palette needs the desktop command service.".to_string(),
+            });
             return;
         }
+        self.suggestions.set(Vec::new());
+        self.search_palette(String::new());
+    }
 
-        scroll_terminal_to_bottom(&terminal_screen);
-    });
+    /// Reruns the palette search, keeping the selection on the best match.
+    fn search_palette(&self, query: String) {
+        let Some(services) = self.services.as_ref() else {
+            return;
+        };
+        let mut items = services.commands.search_commands(&query);
+        items.truncate(PALETTE_ITEMS);
+        self.palette.set(Some(CommandPalette {
+            query,
+            items,
+            selected: 0,
+        }));
+    }
 
-    let submit_command: Rc<dyn Fn(String)> = Rc::new({
-        let shell_session = shell_session.clone();
-        move |command: String| {
-            let command = command.trim().to_string();
-            if command.is_empty() {
-                return;
+    /// Handles a key press in the palette query field.
+    fn palette_key(&self, ev: &KeyboardEvent) {
+        match ev.key().as_str() {
+            "ArrowDown" | "ArrowUp" => {
+                ev.prevent_default();
+                let delta = if ev.key() == "ArrowDown" { 1 } else { -1 };
+                self.palette.update(|palette| {
+                    if let Some(palette) = palette {
+                        palette.move_selection(delta);
+                    }
+                });
+            }
+            "Enter" => {
+                ev.prevent_default();
+                ev.stop_propagation();
+                let selected = self
+                    .palette
+                    .with_untracked(|palette| palette.as_ref().map(|palette| palette.selected));
+                if let Some(selected) = selected {
+                    self.accept_palette(selected, ev.ctrl_key() || ev.meta_key());
+                }
+            }
+            "Escape" => {
+                ev.prevent_default();
+                self.close_palette();
             }
+            _ => {}
+        }
+    }
 
-            transcript.update(|entries| {
-                entries.push(TerminalTranscriptEntry::Prompt {
-                    cwd: cwd.get_untracked(),
-                    command: command.clone(),
-                    execution_id: None,
-                });
-                normalize_terminal_transcript(entries);
-            });
+    /// Inserts the chosen command into the input, or runs it right away when `run` is set.
+    fn accept_palette(&self, index: usize, run: bool) {
+        let Some(item) = self.palette.with_untracked(|palette| {
+            palette
+                .as_ref()
+                .and_then(|palette| palette.items.get(index).cloned())
+        }) else {
+            return;
+        };
+        self.close_palette();
+        if run {
+            self.submit(item.value);
+        } else {
+            self.input.set(format!("{} ", item.value));
+        }
+    }
 
-            history_cursor.set(None);
-            suggestions.set(Vec::new());
-            input.set(String::new());
+    fn close_palette(&self) {
+        self.palette.set(None);
+        self.focus_input();
+    }
 
-            if command.eq_ignore_ascii_case("clear")
-                || command.eq_ignore_ascii_case("terminal clear")
-            {
-                transcript.set(default_terminal_transcript());
-                active_execution.set(None);
-                pending_command.set(None);
-                return;
-            }
+    /// Opens the inspector drawer on a structured data frame.
+    fn open_inspector(&self, data: StructuredData) {
+        self.suggestions.set(Vec::new());
+        self.inspector.set(Inspector::new(data));
+    }
 
-            match shell_session.clone() {
-                Some(shell_session) => {
-                    pending_command.set(Some(command.clone()));
-                    shell_session.submit(ShellRequest {
-                        line: command,
-                        cwd: cwd.get_untracked(),
-                        source_window_id: None,
-                    });
+    /// Opens the inspector on the newest record, list, or table output in the transcript.
+    fn inspect_last_output(&self) {
+        let data = self.transcript.with_untracked(|entries| {
+            entries.iter().rev().find_map(|entry| match entry {
+                TerminalTranscriptEntry::Data { data, .. } if inspectable(data) => {
+                    Some(data.clone())
                 }
-                None => transcript.update(|entries| {
-                    entries.push(TerminalTranscriptEntry::System {
-                        text: "Shell session unavailable.".to_string(),
-                    });
-                    normalize_terminal_transcript(entries);
-                }),
-            }
+                _ => None,
+            })
+        });
+        match data {
+            Some(data) => self.open_inspector(data),
+            None => self.push_entry(TerminalTranscriptEntry::System {
+                text: "No structured output to inspect.".to_string(),
+            }),
         }
-    });
+    }
 
-    let try_history_navigation: Rc<dyn Fn(i32)> = Rc::new({
-        let services = services.clone();
-        move |direction: i32| {
-            let Some(services) = services.as_ref() else {
-                return;
-            };
-            let history = services.commands.history.get();
-            if history.is_empty() {
-                return;
+    /// Handles keys pressed while the inspector tree has focus.
+    fn inspector_key(&self, ev: &KeyboardEvent) {
+        match ev.key().as_str() {
+            "Escape" => self.close_inspector(),
+            "y" => self.copy_inspected_path(),
+            "|" => self.pipe_inspected(),
+            key => {
+                let mut handled = false;
+                self.inspector.update(|inspector| {
+                    if let Some(inspector) = inspector.as_mut() {
+                        handled = inspector.handle_key(key);
+                    }
+                });
+                if !handled {
+                    return;
+                }
             }
+        }
+        ev.prevent_default();
+        ev.stop_propagation();
+    }
 
-            let next_index = match (history_cursor.get_untracked(), direction) {
-                (None, -1) => Some(history.len().saturating_sub(1)),
-                (Some(index), -1) if index > 0 => Some(index - 1),
-                (Some(index), 1) if index + 1 < history.len() => Some(index + 1),
-                (Some(_), 1) => None,
-                (current, _) => current,
-            };
+    fn copy_inspected_path(&self) {
+        if let Some(path) = self
+            .inspector
+            .with_untracked(|inspector| inspector.as_ref().map(Inspector::selected_path))
+        {
+            self.copy_text(path.clone(), format!("path `{path}`"));
+        }
+    }
 
-            history_cursor.set(next_index);
-            match next_index {
-                Some(index) => input.set(history[index].clone()),
-                None => input.set(String::new()),
+    /// Stores the selected subtree in the session and starts a command line that reads it.
+    fn pipe_inspected(&self) {
+        let Some(data) = self
+            .inspector
+            .with_untracked(|inspector| inspector.as_ref().and_then(Inspector::selected_data))
+        else {
+            return;
+        };
+        let Some(session) = self.session.as_ref() else {
+            self.push_entry(TerminalTranscriptEntry::System {
+                text: "Piping the selection needs a shell session.".to_string(),
+            });
+            return;
+        };
+        match session.set_variable(INSPECTOR_VARIABLE, data) {
+            Ok(()) => {
+                self.input.set(format!("var ${INSPECTOR_VARIABLE} | "));
+                self.close_inspector();
             }
+            Err(err) => self.push_entry(TerminalTranscriptEntry::System {
+                text: format!("Piping the selection failed: {}", err.message),
+            }),
         }
-    });
+    }
+
+    fn close_inspector(&self) {
+        self.inspector.set(None);
+        self.focus_input();
+    }
+
+    /// Moves keyboard focus to the command input once the pane is mounted.
+    fn focus_input(&self) {
+        let command_input = self.command_input;
+        request_animation_frame(move || {
+            if let Some(input) = command_input.get_untracked() {
+                let _ = input.focus();
+            }
+        });
+    }
+}
 
-    let trigger_completion: Rc<dyn Fn()> = Rc::new({
-        let shell_session = shell_session.clone();
-        move || {
-            let Some(shell_session) = shell_session.clone() else {
+/// Window-level pane callbacks shared by every rendered pane.
+#[derive(Clone)]
+struct PaneControls {
+    focused_pane: Signal<Option<u64>>,
+    split: Signal<bool>,
+    on_focus: Rc<dyn Fn(u64)>,
+    on_command: Rc<dyn Fn(PaneCommand)>,
+    /// Whether typed commands currently reach every pane.
+    synchronized: Signal<bool>,
+    /// Submits a typed line from the pane with this id, broadcasting it while synchronized.
+    on_submit: Rc<dyn Fn(u64, String)>,
+}
+
+fn render_pane(
+    pane: TerminalPane,
+    input_id: String,
+    mode_label: &'static str,
+    controls: PaneControls,
+) -> impl IntoView {
+    let pane_id = pane.id;
+    let active_execution = pane.active_execution;
+    let prompt_mode = move || {
+        if active_execution.get().is_some() {
+            "running"
+        } else {
+            mode_label
+        }
+    };
+    let cwd = pane.cwd;
+    let input = pane.input;
+    let suggestions = pane.suggestions;
+    let history_search = pane.history_search;
+    let pending_prompt = pane.pending_prompt;
+    let prompt_answer = pane.prompt_answer;
+    let should_follow_output = pane.should_follow_output;
+    let terminal_screen = pane.screen;
+    let focused_pane = controls.focused_pane;
+    let split = controls.split;
+    let synchronized = controls.synchronized;
+    let window_len = pane.window_len;
+    let transcript = pane.transcript;
+    let copy_mode = pane.copy_mode;
+    let profiles = pane.profiles;
+    let last_exit = pane.last_exit;
+    let paste_review = pane.paste_review;
+    let palette = pane.palette;
+    let palette_open = create_memo(move |_| palette.with(Option::is_some));
+    let reviewing_paste = create_memo(move |_| paste_review.with(Option::is_some));
+    let inspector = pane.inspector;
+    let inspecting = create_memo(move |_| inspector.with(Option::is_some));
+    let entry_pane = store_value(pane.clone());
+    let profile_prompt = move || {
+        let _transcript_len = transcript.with(Vec::len);
+        profiles.active().prompt.map(|template| {
+            render_prompt(
+                &template,
+                &PromptContext {
+                    cwd: &cwd.get(),
+                    exit_code: last_exit.get(),
+                    time: &prompt_clock_time(),
+                },
+            )
+        })
+    };
+    let hidden_entries = move || transcript.with(Vec::len).saturating_sub(window_len.get());
+    let visible_entries = {
+        let pane = pane.clone();
+        move || pane.visible_entries()
+    };
+    let on_scroll = {
+        let pane = pane.clone();
+        move |_: ev::Event| {
+            let Some(screen) = terminal_screen.get() else {
                 return;
             };
-            let current_input = input.get_untracked();
-            spawn_local(async move {
-                match shell_session
-                    .complete(completion_request(&cwd.get_untracked(), &current_input))
-                    .await
-                {
-                    Ok(items) => {
-                        if items.len() == 1 {
-                            let value = items[0].value.clone();
-                            input.set(format!("{value} "));
-                            suggestions.set(Vec::new());
-                        } else {
-                            suggestions.set(items);
-                        }
-                    }
-                    Err(err) => {
-                        transcript.update(|entries| {
-                            entries.push(TerminalTranscriptEntry::System { text: err.message });
-                            normalize_terminal_transcript(entries);
+            let follow = should_auto_follow(
+                screen.scroll_height(),
+                screen.scroll_top(),
+                screen.client_height(),
+                AUTO_FOLLOW_THRESHOLD_PX,
+            );
+            should_follow_output.set(follow);
+            if follow && window_len.get_untracked() > TRANSCRIPT_WINDOW {
+                window_len.set(TRANSCRIPT_WINDOW);
+            } else if screen.scroll_top() <= LOAD_EARLIER_THRESHOLD_PX {
+                pane.load_earlier_entries();
+            }
+        }
+    };
+
+    view! {
+        <TerminalSurface
+            role="log"
+            aria_live="polite"
+            aria_label=Signal::derive(move || {
+                if split.get() {
+                    format!("Terminal pane in {}", cwd.get())
+                } else {
+                    String::new()
+                }
+            })
+            selected=Signal::derive(move || split.get() && focused_pane.get() == Some(pane_id))
+            font_size=Signal::derive(move || surface_font_size(profiles.active().font_size))
+            theme=Signal::derive(move || surface_theme(profiles.active().theme))
+            node_ref=terminal_screen
+            on:scroll=on_scroll
+        >
+            {
+                let pane = pane.clone();
+                move || {
+                    palette_open.get().then(|| {
+                        let pane = pane.clone();
+                        let keys = pane.clone();
+                        let query_input = create_node_ref::<html::Input>();
+                        query_input.on_load(|input| {
+                            let _ = input.focus();
                         });
-                    }
+                        let palette_items = move || {
+                            palette.with(|palette| {
+                                palette
+                                    .as_ref()
+                                    .map(|palette| palette.items.clone())
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .enumerate()
+                                    .collect::<Vec<_>>()
+                            })
+                        };
+                        view! {
+                            <Stack gap=LayoutGap::Sm>
+                                <TextField
+                                    node_ref=query_input
+                                    placeholder="Search commands"
+                                    aria_label="Search commands"
+                                    autocomplete="off"
+                                    spellcheck=false
+                                    value=Signal::derive(move || {
+                                        palette.with(|palette| {
+                                            palette.as_ref().map(|palette| palette.query.clone()).unwrap_or_default()
+                                        })
+                                    })
+                                    on_input=Callback::new({
+                                        let pane = pane.clone();
+                                        move |ev| pane.search_palette(event_target_value(&ev))
+                                    })
+                                    on_keydown=Callback::new(move |ev: KeyboardEvent| keys.palette_key(&ev))
+                                />
+                                <CompletionList role="listbox" aria_label="Commands">
+                                    <For
+                                        each=palette_items
+                                        key=|(index, item)| (*index, item.value.clone())
+                                        let:entry
+                                    >
+                                        {
+                                            let (index, item) = entry;
+                                            let pane = pane.clone();
+                                            view! {
+                                                <CompletionItem
+                                                    selected=Signal::derive(move || {
+                                                        palette.with(|palette| {
+                                                            palette.as_ref().is_some_and(|palette| palette.selected == index)
+                                                        })
+                                                    })
+                                                    on_click=Callback::new(move |_| pane.accept_palette(index, false))
+                                                >
+                                                    {matched_segments(&item.label, &item.matched)
+                                                        .into_iter()
+                                                        .map(|(text, matched)| {
+                                                            if matched {
+                                                                view! { <Text tone=TextTone::Accent>{text}</Text> }
+                                                                    .into_view()
+                                                            } else {
+                                                                text.into_view()
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                    {item.detail.map(|detail| {
+                                                        view! { <Text tone=TextTone::Secondary>{format!(" \u{2014} {detail}")}</Text> }
+                                                    })}
+                                                </CompletionItem>
+                                            }
+                                        }
+                                    </For>
+                                </CompletionList>
+                                <TerminalLine tone=TextTone::Secondary>
+                                    {move || {
+                                        if palette.with(|palette| palette.as_ref().is_some_and(|palette| palette.items.is_empty())) {
+                                            "No matching commands."
+                                        } else {
+                                            "Enter inserts the command, Ctrl+Enter runs it, Esc closes."
+                                        }
+                                    }}
+                                </TerminalLine>
+                            </Stack>
+                        }
+                    })
                 }
-            });
-        }
-    });
+            }
+
+            <Show when=move || !suggestions.get().is_empty() fallback=|| ()>
+                <CompletionList role="listbox" aria_label="Completions">
+                    <For each=move || suggestions.get() key=|item| item.value.clone() let:item>
+                        <CompletionItem
+                            on_click=Callback::new(move |_| {
+                                input.update(|line| *line = apply_completion(line, &item.value));
+                                suggestions.set(Vec::new());
+                            })
+                        >
+                            {matched_segments(&item.label, &item.matched)
+                                .into_iter()
+                                .map(|(text, matched)| {
+                                    if matched {
+                                        view! { <Text tone=TextTone::Accent>{text}</Text> }
+                                            .into_view()
+                                    } else {
+                                        text.into_view()
+                                    }
+                                })
+                                .collect_view()}
+                        </CompletionItem>
+                    </For>
+                </CompletionList>
+            </Show>
+
+            <TerminalTranscript>
+                <Show when=move || copy_mode.with(Option::is_none) fallback=|| ()>
+                    <Show when=move || { hidden_entries() > 0 } fallback=|| ()>
+                        <TerminalLine tone=TextTone::Secondary>
+                            {move || format!("{} earlier entries; scroll up to show more.", hidden_entries())}
+                        </TerminalLine>
+                    </Show>
+                    <For each=visible_entries.clone() key=|(key, _)| *key let:entry>
+                        {
+                            let inspect = match &entry.1 {
+                                TerminalTranscriptEntry::Data { data, .. } if inspectable(data) => {
+                                    Some(data.clone())
+                                }
+                                _ => None,
+                            };
+                            view! {
+                                {render_entry(entry.1)}
+                                {inspect.map(|data| {
+                                    view! {
+                                        <Button
+                                            variant=ButtonVariant::Quiet
+                                            size=ButtonSize::Sm
+                                            title="Open this output in the inspector"
+                                            on_click=Callback::new(move |_| {
+                                                entry_pane.get_value().open_inspector(data.clone());
+                                            })
+                                        >
+                                            "Inspect"
+                                        </Button>
+                                    }
+                                })}
+                            }
+                        }
+                    </For>
+                </Show>
+                {move || copy_mode.with(|mode| mode.as_ref().map(render_copy_mode))}
 
-    let indexed_entries = move || transcript.get().into_iter().enumerate().collect::<Vec<_>>();
+                <Show when=move || history_search.get().is_some() fallback=|| ()>
+                    <TerminalLine tone=TextTone::Secondary>
+                        {move || history_search.get().map(|search| search.label()).unwrap_or_default()}
+                    </TerminalLine>
+                </Show>
 
-    view! {
-        <AppShell>
-            <TerminalSurface
-                role="log"
-                aria_live="polite"
-                node_ref=terminal_screen
-                on:scroll=move |_| {
-                    if let Some(screen) = terminal_screen.get() {
-                        should_follow_output.set(should_auto_follow(
-                            screen.scroll_height(),
-                            screen.scroll_top(),
-                            screen.client_height(),
-                            AUTO_FOLLOW_THRESHOLD_PX,
-                        ));
+                {
+                    let pane = pane.clone();
+                    move || {
+                        pending_prompt
+                            .get()
+                            .map(|prompt| {
+                                let pane = pane.clone();
+                                let prompt_input = create_node_ref::<html::Input>();
+                                prompt_input.on_load(|input| {
+                                    let _ = input.focus();
+                                });
+                                view! {
+                                    <TerminalPrompt>
+                                        <div aria-hidden="true">
+                                            <span>{prompt.prompt.clone()}</span>
+                                        </div>
+                                        <TextField
+                                            input_type=if prompt.kind == PromptKind::Secret {
+                                                "password"
+                                            } else {
+                                                "text"
+                                            }
+                                            node_ref=prompt_input
+                                            value=Signal::derive(move || prompt_answer.get())
+                                            autocomplete="off"
+                                            spellcheck=false
+                                            aria_label=prompt.prompt.clone()
+                                            on_input=Callback::new(move |ev| {
+                                                prompt_answer.set(event_target_value(&ev));
+                                            })
+                                            on_keydown=Callback::new(move |ev: KeyboardEvent| {
+                                                match ev.key().as_str() {
+                                                    "Enter" => {
+                                                        ev.prevent_default();
+                                                        ev.stop_propagation();
+                                                        pane.answer_prompt(Some(prompt_answer.get_untracked()));
+                                                    }
+                                                    "Escape" => {
+                                                        ev.prevent_default();
+                                                        pane.answer_prompt(None);
+                                                    }
+                                                    _ => {}
+                                                }
+                                            })
+                                        />
+                                    </TerminalPrompt>
+                                }
+                            })
                     }
                 }
-            >
-                <Show when=move || !suggestions.get().is_empty() fallback=|| ()>
-                    <CompletionList role="listbox" aria_label="Completions">
-                        <For each=move || suggestions.get() key=|item| item.value.clone() let:item>
-                            <CompletionItem
-                                on_click=Callback::new(move |_| {
-                                    input.set(format!("{} ", item.value));
-                                    suggestions.set(Vec::new());
+
+                {
+                    let pane = pane.clone();
+                    move || {
+                        reviewing_paste.get().then(|| {
+                            let run = pane.clone();
+                            let cancel = pane.clone();
+                            let keys = pane.clone();
+                            let command_count = move || {
+                                paste_review.with(|text| {
+                                    text.as_deref().map_or(0, |text| paste_commands(text).len())
                                 })
-                            >
-                                {item.label}
-                            </CompletionItem>
-                        </For>
-                    </CompletionList>
-                </Show>
+                            };
+                            let editor = create_node_ref::<html::Textarea>();
+                            editor.on_load(|editor| {
+                                let _ = editor.focus();
+                            });
+                            view! {
+                                <Stack gap=LayoutGap::Sm>
+                                    <TerminalLine tone=TextTone::Secondary>
+                                        {move || format!(
+                                            "Pasted text spans {} command(s). Review or edit them; they run one at a time. Ctrl+Enter runs, Esc cancels.",
+                                            command_count(),
+                                        )}
+                                    </TerminalLine>
+                                    <TextArea
+                                        node_ref=editor
+                                        aria_label="Pasted commands to review"
+                                        value=Signal::derive(move || paste_review.get().unwrap_or_default())
+                                        on_input=Callback::new(move |ev| {
+                                            paste_review.set(Some(event_target_value(&ev)));
+                                        })
+                                        on_keydown=Callback::new(move |ev: KeyboardEvent| {
+                                            match ev.key().as_str() {
+                                                "Enter" if ev.ctrl_key() || ev.meta_key() => {
+                                                    ev.prevent_default();
+                                                    keys.run_paste_review();
+                                                }
+                                                "Escape" => {
+                                                    ev.prevent_default();
+                                                    keys.cancel_paste_review();
+                                                }
+                                                _ => {}
+                                            }
+                                        })
+                                    />
+                                    <Cluster gap=LayoutGap::Sm>
+                                        <Button
+                                            variant=ButtonVariant::Primary
+                                            disabled=Signal::derive(move || command_count() == 0)
+                                            on_click=Callback::new(move |_| run.run_paste_review())
+                                        >
+                                            {move || format!("Run {} command(s)", command_count())}
+                                        </Button>
+                                        <Button on_click=Callback::new(move |_| cancel.cancel_paste_review())>
+                                            "Cancel"
+                                        </Button>
+                                    </Cluster>
+                                </Stack>
+                            }
+                        })
+                    }
+                }
 
-                <TerminalTranscript>
-                    <For each=indexed_entries key=|(idx, _)| *idx let:entry>
-                        {render_entry(entry.1)}
-                    </For>
+                {
+                    let pane = pane.clone();
+                    move || {
+                        inspecting.get().then(|| {
+                            let keys = pane.clone();
+                            let copy = pane.clone();
+                            let pipe = pane.clone();
+                            let close = pane.clone();
+                            let tree = create_node_ref::<html::Div>();
+                            tree.on_load(|tree| {
+                                let _ = tree.focus();
+                            });
+                            let rows = move || {
+                                inspector.with(|inspector| {
+                                    inspector
+                                        .as_ref()
+                                        .map(|inspector| {
+                                            inspector
+                                                .rows()
+                                                .into_iter()
+                                                .map(|row| {
+                                                    let selected = inspector.is_selected(&row.path);
+                                                    (row, selected)
+                                                })
+                                                .collect::<Vec<_>>()
+                                        })
+                                        .unwrap_or_default()
+                                })
+                            };
+                            let selected_path = move || {
+                                inspector.with(|inspector| {
+                                    inspector.as_ref().map(Inspector::selected_path).unwrap_or_default()
+                                })
+                            };
+                            view! {
+                                <Stack gap=LayoutGap::Sm>
+                                    <TerminalLine tone=TextTone::Secondary>
+                                        {move || format!(
+                                            "Inspecting {}. Arrows move and expand, y copies the path, | pipes it into a new command, Esc closes.",
+                                            selected_path(),
+                                        )}
+                                    </TerminalLine>
+                                    <div
+                                        node_ref=tree
+                                        tabindex="0"
+                                        aria-label="Structured output inspector"
+                                        on:keydown=move |ev: KeyboardEvent| keys.inspector_key(&ev)
+                                    >
+                                        <Tree>
+                                            <For
+                                                each=rows
+                                                key=|(row, selected)| (format_path(&row.path), row.text(), *selected)
+                                                let:entry
+                                            >
+                                                {
+                                                    let (row, selected) = entry;
+                                                    let path = row.path.clone();
+                                                    view! {
+                                                        <TreeItem selected=selected>
+                                                            <Button
+                                                                variant=ButtonVariant::Quiet
+                                                                size=ButtonSize::Sm
+                                                                on_click=Callback::new(move |_| {
+                                                                    inspector.update(|inspector| {
+                                                                        if let Some(inspector) = inspector.as_mut() {
+                                                                            if inspector.is_selected(&path) {
+                                                                                inspector.toggle(&path);
+                                                                            }
+                                                                            inspector.select(path.clone());
+                                                                        }
+                                                                    });
+                                                                    if let Some(tree) = tree.get_untracked() {
+                                                                        let _ = tree.focus();
+                                                                    }
+                                                                })
+                                                            >
+                                                                {row.text()}
+                                                            </Button>
+                                                        </TreeItem>
+                                                    }
+                                                }
+                                            </For>
+                                        </Tree>
+                                    </div>
+                                    <Show when=move || { rows().len() >= INSPECTOR_ROW_LIMIT } fallback=|| ()>
+                                        <TerminalLine tone=TextTone::Secondary>
+                                            {format!("Showing the first {INSPECTOR_ROW_LIMIT} rows; collapse nodes to see the rest.")}
+                                        </TerminalLine>
+                                    </Show>
+                                    <Cluster gap=LayoutGap::Sm>
+                                        <Button on_click=Callback::new(move |_| copy.copy_inspected_path())>
+                                            "Copy path"
+                                        </Button>
+                                        <Button on_click=Callback::new(move |_| pipe.pipe_inspected())>
+                                            "Pipe into command"
+                                        </Button>
+                                        <Button on_click=Callback::new(move |_| close.close_inspector())>
+                                            "Close"
+                                        </Button>
+                                    </Cluster>
+                                </Stack>
+                            }
+                        })
+                    }
+                }
 
-                    <TerminalPrompt>
-                        <label hidden for=input_id.clone()>
-                            {move || format!("Command input for {} in {} mode", cwd.get(), prompt_mode())}
-                        </label>
-                        <div aria-hidden="true">
-                            <span>{move || cwd.get()}</span>
-                            <span>{move || prompt_mode()}</span>
-                            <span>"\u{203a}"</span>
-                        </div>
-                        <TextField
-                            id=input_id.clone()
-                            input_type="text"
-                            value=Signal::derive(move || input.get())
-                            autocomplete="off"
-                            spellcheck=false
-                            aria_label="Terminal command input"
-                            on_input=Callback::new(move |ev| {
-                                input.set(event_target_value(&ev));
+                <TerminalPrompt>
+                    <label hidden for=input_id.clone()>
+                        {move || {
+                            let scope = if synchronized.get() { ", synchronized with every pane" } else { "" };
+                            format!("Command input for {} in {} mode{scope}", cwd.get(), prompt_mode())
+                        }}
+                    </label>
+                    <div aria-hidden="true">
+                        <Show when=move || synchronized.get() fallback=|| ()>
+                            <span>"sync"</span>
+                        </Show>
+                        {move || match profile_prompt() {
+                            Some(prompt) => view! { <span>{prompt}</span> }.into_view(),
+                            None => view! {
+                                <span>{move || cwd.get()}</span>
+                                <span>{move || prompt_mode()}</span>
+                                <span>"\u{203a}"</span>
+                            }
+                            .into_view(),
+                        }}
+                    </div>
+                    <TextField
+                        id=input_id
+                        input_type="text"
+                        node_ref=pane.command_input
+                        value=Signal::derive(move || input.get())
+                        autocomplete="off"
+                        spellcheck=false
+                        aria_label="Terminal command input"
+                        on_focus=Callback::new({
+                            let on_focus = controls.on_focus.clone();
+                            move |_| on_focus(pane_id)
+                        })
+                        on_dragover=Callback::new({
+                            let pane = pane.clone();
+                            move |ev: DragEvent| pane.drag_over(&ev)
+                        })
+                        on_drop=Callback::new({
+                            let pane = pane.clone();
+                            move |ev: DragEvent| pane.drop_files(&ev)
+                        })
+                        on_paste=Callback::new({
+                            let pane = pane.clone();
+                            move |ev: web_sys::Event| pane.paste(&ev)
+                        })
+                        on_input=Callback::new({
+                            let pane = pane.clone();
+                            move |ev| {
+                                let value = event_target_value(&ev);
+                                input.set(value.clone());
                                 suggestions.set(Vec::new());
-                            })
-                            on_keydown=Callback::new(move |ev: KeyboardEvent| match ev.key().as_str() {
+                                if history_search.get_untracked().is_some() {
+                                    pane.search_history(value, false);
+                                }
+                            }
+                        })
+                        on_keydown=Callback::new(move |ev: KeyboardEvent| {
+                            if let Some(command) = pane_command(&ev) {
+                                ev.prevent_default();
+                                (controls.on_command)(command);
+                                return;
+                            }
+                            if let Some(action) = copy_action(&ev) {
+                                ev.prevent_default();
+                                pane.run_copy_action(action);
+                                return;
+                            }
+                            if opens_palette(&ev) {
+                                ev.prevent_default();
+                                pane.open_palette();
+                                return;
+                            }
+                            if pane.copy_mode_key(&ev) {
+                                ev.prevent_default();
+                                return;
+                            }
+                            match ev.key().as_str() {
                                 "Enter" => {
                                     ev.prevent_default();
                                     ev.stop_propagation();
-                                    submit_command(input.get_untracked());
+                                    pane.accept_history_search();
+                                    (controls.on_submit)(pane_id, input.get_untracked());
                                 }
                                 "ArrowUp" => {
                                     ev.prevent_default();
-                                    try_history_navigation(-1);
+                                    pane.accept_history_search();
+                                    pane.navigate_history(-1);
                                 }
                                 "ArrowDown" => {
                                     ev.prevent_default();
-                                    try_history_navigation(1);
+                                    pane.accept_history_search();
+                                    pane.navigate_history(1);
                                 }
                                 "Tab" => {
                                     ev.prevent_default();
-                                    trigger_completion();
+                                    pane.accept_history_search();
+                                    pane.complete();
+                                }
+                                "Escape" if history_search.get_untracked().is_some() => {
+                                    ev.prevent_default();
+                                    pane.accept_history_search();
                                 }
                                 "Escape" => suggestions.set(Vec::new()),
+                                "r" | "R" if ev.ctrl_key() => {
+                                    ev.prevent_default();
+                                    suggestions.set(Vec::new());
+                                    match history_search.get_untracked() {
+                                        Some(search) => pane.search_history(search.query, true),
+                                        None => pane.search_history(input.get_untracked(), false),
+                                    }
+                                }
+                                "g" | "G" if ev.ctrl_key() && history_search.get_untracked().is_some() => {
+                                    ev.prevent_default();
+                                    history_search.set(None);
+                                }
                                 "c" | "C" if ev.ctrl_key() => {
-                                    if let Some(shell_session) = shell_session.clone() {
+                                    if let Some(session) = pane.session.as_ref() {
                                         ev.prevent_default();
-                                        shell_session.cancel();
+                                        session.cancel();
                                     }
                                 }
                                 "l" | "L" if ev.ctrl_key() => {
                                     ev.prevent_default();
-                                    transcript.set(default_terminal_transcript());
+                                    pane.clear_transcript();
                                 }
                                 _ => {}
-                            })
-                        />
-                    </TerminalPrompt>
-                </TerminalTranscript>
-            </TerminalSurface>
+                            }
+                        })
+                    />
+                </TerminalPrompt>
+            </TerminalTranscript>
+        </TerminalSurface>
+    }
+}
+
+#[component]
+/// Terminal app window contents.
+///
+/// This component presents a browser-native shell backed by runtime-owned commands and persists
+/// transcript state via typed host contracts. A window can be split into up to four panes, each
+/// with its own shell session.
+pub fn TerminalApp(
+    /// Stable runtime window id used to expose the primary input focus target.
+    window_id: WindowRuntimeId,
+    /// App launch parameters (for example, the initial working directory).
+    launch_params: Value,
+    /// Manager-restored app state payload for this window instance.
+    restored_state: Option<Value>,
+    /// Optional app-host bridge for manager-owned commands.
+    services: Option<AppServices>,
+    /// Window lifecycle signal, used to notify about long commands that finish unwatched.
+    lifecycle: Option<ReadSignal<AppLifecycleEvent>>,
+) -> impl IntoView {
+    let input_id = window_primary_input_dom_id(window_id);
+    let launch_cwd = launch_params
+        .get("cwd")
+        .and_then(Value::as_str)
+        .unwrap_or("~/desktop")
+        .to_string();
+    let mode_label = terminal_mode_label(services.as_ref());
+    let restored = restored_state
+        .and_then(|restored| serde_json::from_value::<TerminalPersistedState>(restored).ok())
+        .map(|restored| restore_terminal_state(restored, &launch_cwd));
+    let last_saved = create_rw_signal(
+        restored
+            .as_ref()
+            .and_then(|restored| serde_json::to_string(restored).ok()),
+    );
+    let fresh_window = restored.is_none();
+    let restored = restored.unwrap_or_else(|| TerminalPersistedState {
+        primary: TerminalPaneState::new(launch_cwd.clone()),
+        splits: Vec::new(),
+        split: TerminalSplit::default(),
+        focused_pane: 0,
+        profile: None,
+        synchronized: false,
+    });
+
+    // Keep everything restored until the scrollback preference loads.
+    let scrollback = create_rw_signal(MAX_TERMINAL_SCROLLBACK);
+    let history_mode = create_rw_signal(HistoryMode::default());
+    let profiles = TerminalProfiles {
+        stored: create_rw_signal(Vec::new()),
+        selected: create_rw_signal(restored.profile.clone()),
+    };
+    let synchronized = create_rw_signal(restored.synchronized);
+    let window = TerminalWindow {
+        scrollback,
+        history_mode,
+        profiles,
+        synchronized,
+        lifecycle,
+    };
+
+    let window_service = services.as_ref().map(|services| services.window);
+    let owner = Owner::current();
+    let next_pane_id = store_value(0u64);
+    let open_pane: Rc<dyn Fn(TerminalPaneState) -> TerminalPane> = Rc::new({
+        let services = services.clone();
+        move |state| {
+            let id = next_pane_id.get_value();
+            next_pane_id.set_value(id + 1);
+            let create = || TerminalPane::new(id, state, services.clone(), window);
+            match owner {
+                Some(owner) => with_owner(owner, create),
+                None => create(),
+            }
+        }
+    });
+    let panes = create_rw_signal(
+        std::iter::once(restored.primary)
+            .chain(restored.splits)
+            .map(&*open_pane)
+            .collect::<Vec<_>>(),
+    );
+    let split = create_rw_signal(restored.split);
+    let focused = create_rw_signal(restored.focused_pane);
+
+    match services.clone() {
+        Some(services) => spawn_local(async move {
+            let prefs = services
+                .prefs
+                .load::<TerminalPrefs>(TERMINAL_PREFS_KEY)
+                .await
+                .unwrap_or_else(|err| {
+                    services
+                        .logs
+                        .warn(format!("terminal prefs load failed: {err}"));
+                    None
+                });
+            scrollback.set(prefs.map_or(DEFAULT_TERMINAL_SCROLLBACK, |prefs| {
+                prefs
+                    .scrollback
+                    .clamp(MIN_TERMINAL_SCROLLBACK, MAX_TERMINAL_SCROLLBACK)
+            }));
+            history_mode.set(prefs.map(|prefs| prefs.history_mode).unwrap_or_default());
+            match services
+                .prefs
+                .load::<Vec<TerminalProfile>>(TERMINAL_PROFILES_KEY)
+                .await
+            {
+                Ok(stored) => profiles.stored.set(stored.unwrap_or_default()),
+                Err(err) => services
+                    .logs
+                    .warn(format!("terminal profiles load failed: {err}")),
+            }
+            // Only a newly opened window starts a session; restored transcripts already ran theirs.
+            if fresh_window {
+                if let Some(pane) = panes.with_untracked(|panes| panes.first().cloned()) {
+                    pane.run_startup_command();
+                }
+            }
+        }),
+        None => scrollback.set(DEFAULT_TERMINAL_SCROLLBACK),
+    }
+
+    create_effect(move |_| {
+        let mut states =
+            panes.with(|panes| panes.iter().map(TerminalPane::snapshot).collect::<Vec<_>>());
+        if states.is_empty() {
+            return;
+        }
+        let snapshot = TerminalPersistedState {
+            primary: states.remove(0),
+            splits: states,
+            split: split.get(),
+            focused_pane: focused.get(),
+            profile: profiles.selected.get(),
+            synchronized: synchronized.get(),
+        };
+
+        let serialized = match serde_json::to_string(&snapshot) {
+            Ok(raw) => raw,
+            Err(err) => {
+                if let Some(services) = services.as_ref() {
+                    services
+                        .logs
+                        .warn(format!("terminal serialize failed: {err}"));
+                }
+                return;
+            }
+        };
+
+        if last_saved.get().as_deref() == Some(serialized.as_str()) {
+            return;
+        }
+        last_saved.set(Some(serialized));
+
+        if let Some(services) = services.clone() {
+            if let Ok(value) = serde_json::to_value(&snapshot) {
+                services.state.persist_window_state(value);
+            }
+        }
+    });
+
+    let focused_pane_id =
+        Signal::derive(move || panes.with(|panes| panes.get(focused.get()).map(|pane| pane.id)));
+    let focus_pane = move |index: usize| {
+        focused.set(index);
+        if let Some(pane) = panes.with_untracked(|panes| panes.get(index).cloned()) {
+            pane.focus_input();
+        }
+    };
+    let run_pane_command: Rc<dyn Fn(PaneCommand)> = Rc::new(move |command| {
+        let count = panes.with_untracked(Vec::len);
+        let current = focused.get_untracked().min(count.saturating_sub(1));
+        match command {
+            PaneCommand::Split(direction) => {
+                let Some(source) = panes.with_untracked(|panes| panes.get(current).cloned()) else {
+                    return;
+                };
+                if count >= MAX_TERMINAL_PANES {
+                    source.push_entry(TerminalTranscriptEntry::System {
+                        text: format!(
+                            "A terminal window holds at most {MAX_TERMINAL_PANES} panes."
+                        ),
+                    });
+                    return;
+                }
+                let pane = open_pane(TerminalPaneState::new(source.cwd.get_untracked()));
+                panes.update(|panes| panes.insert(current + 1, pane.clone()));
+                split.set(direction);
+                focus_pane(current + 1);
+                pane.run_startup_command();
+            }
+            PaneCommand::Close => {
+                if count <= 1 {
+                    return;
+                }
+                let mut closed = None;
+                panes.update(|panes| closed = Some(panes.remove(current)));
+                if let Some(session) = closed.and_then(|pane| pane.session) {
+                    session.cancel();
+                }
+                focus_pane(current.min(count - 2));
+            }
+            PaneCommand::FocusNext => focus_pane((current + 1) % count.max(1)),
+            PaneCommand::FocusPrevious => focus_pane((current + count.max(1) - 1) % count.max(1)),
+            PaneCommand::ToggleSync => {
+                let enabled = !synchronized.get_untracked();
+                synchronized.set(enabled);
+                if let Some(pane) = panes.with_untracked(|panes| panes.get(current).cloned()) {
+                    pane.push_entry(TerminalTranscriptEntry::System {
+                        text: sync_notice(enabled).to_string(),
+                    });
+                }
+            }
+            PaneCommand::NewWindow => {
+                let Some(source) = panes.with_untracked(|panes| panes.get(current).cloned()) else {
+                    return;
+                };
+                let Some(window) = window_service else {
+                    return;
+                };
+                let launch_params = json!({ "cwd": source.cwd.get_untracked() });
+                spawn_local(async move {
+                    if let Err(err) = window
+                        .open_window(ApplicationId::trusted("system.terminal"), launch_params)
+                        .await
+                    {
+                        source.push_entry(TerminalTranscriptEntry::System {
+                            text: format!("Could not open a terminal window: {err}"),
+                        });
+                    }
+                });
+            }
+        }
+    });
+    let submit_line: Rc<dyn Fn(u64, String)> = Rc::new(move |pane_id, line| {
+        let targets = panes.with_untracked(|panes| {
+            // `terminal sync` only changes the toggle, so it runs once in the pane that typed it.
+            let local_toggle = matches!(local_command(&line), Some(LocalCommand::Sync(_)));
+            panes
+                .iter()
+                .filter(|pane| {
+                    pane.id == pane_id || (synchronized.get_untracked() && !local_toggle)
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        for pane in targets {
+            pane.submit(line.clone());
+        }
+    });
+    let controls = PaneControls {
+        focused_pane: focused_pane_id,
+        split: Signal::derive(move || panes.with(|panes| panes.len() > 1)),
+        on_focus: Rc::new(move |pane_id| {
+            let index =
+                panes.with_untracked(|panes| panes.iter().position(|pane| pane.id == pane_id));
+            if let Some(index) = index {
+                if focused.get_untracked() != index {
+                    focused.set(index);
+                }
+            }
+        }),
+        on_command: run_pane_command,
+        synchronized: Signal::derive(move || {
+            synchronized.get() && panes.with(|panes| panes.len() > 1)
+        }),
+        on_submit: submit_line,
+    };
+
+    view! {
+        <AppShell>
+            <TerminalPaneGrid
+                direction=Signal::derive(move || split.get().direction())
+                aria_label="Terminal panes"
+            >
+                <For
+                    each=move || {
+                        panes
+                            .get()
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, pane)| (index == 0, pane))
+                            .collect::<Vec<_>>()
+                    }
+                    key=|(first, pane)| (pane.id, *first)
+                    let:entry
+                >
+                    {
+                        let (first, pane) = entry;
+                        let pane_input_id = if first {
+                            input_id.clone()
+                        } else {
+                            format!("{input_id}-pane-{}", pane.id)
+                        };
+                        render_pane(pane, pane_input_id, mode_label, controls.clone())
+                    }
+                </For>
+            </TerminalPaneGrid>
         </AppShell>
     }
 }
@@ -0,0 +1,135 @@
+//! Multi-line paste and file drop handling for the command input.
+//!
+//! Pasting text that spans several lines never submits it directly. The pane opens a review editor
+//! with the text that would run, and confirmed commands are queued and submitted one at a time as
+//! each completes. Files dropped on the input insert their quoted paths at the cursor instead.
+
+const BRACKETED_PASTE_START: &str = "\u{1b}[200~";
+const BRACKETED_PASTE_END: &str = "\u{1b}[201~";
+
+/// Returns `text` with the current input selection replaced by `pasted`, the way a plain paste
+/// would edit the line.
+///
+/// Selection offsets are UTF-16 code units, as reported by the input element.
+pub(crate) fn splice_paste(input: &str, selection: (u32, u32), pasted: &str) -> String {
+    let byte_offset = |units: u32| {
+        let mut seen = 0;
+        input
+            .char_indices()
+            .find(|(_, ch)| {
+                let found = seen >= units;
+                seen += ch.len_utf16() as u32;
+                found
+            })
+            .map_or(input.len(), |(index, _)| index)
+    };
+    let start = byte_offset(selection.0.min(selection.1));
+    let end = byte_offset(selection.0.max(selection.1));
+    format!("{}{}{}", &input[..start], pasted, &input[end..])
+}
+
+/// Shell word for a dropped path: bare when safe, single-quoted when it holds spaces or shell
+/// syntax, and double-quoted with escapes when it also holds a single quote.
+///
+/// Single quotes keep `$` from expanding as a variable; the shell still reads `\` as an escape
+/// inside them.
+pub(crate) fn quote_path(path: &str) -> String {
+    let plain = !path.is_empty()
+        && !path
+            .chars()
+            .any(|ch| ch.is_whitespace() || matches!(ch, '"' | '\'' | '\\' | '|' | '$'));
+    if plain {
+        path.to_string()
+    } else if !path.contains('\'') {
+        format!("'{}'", path.replace('\\', "\\\\"))
+    } else {
+        let escaped = path
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$");
+        format!("\"{escaped}\"")
+    }
+}
+
+/// Returns `input` with the selection replaced by the quoted `paths`, padded with spaces so they
+/// stay separate words, and the UTF-16 cursor offset just past the insertion.
+pub(crate) fn insert_dropped_paths(
+    input: &str,
+    selection: (u32, u32),
+    paths: &[String],
+) -> (String, u32) {
+    let start = selection.0.min(selection.1) as usize;
+    let before = input.encode_utf16().take(start).collect::<Vec<_>>();
+    let needs_space = String::from_utf16_lossy(&before)
+        .chars()
+        .last()
+        .is_some_and(|ch| !ch.is_whitespace());
+    let words = paths
+        .iter()
+        .map(|path| quote_path(path))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let inserted = format!("{}{words} ", if needs_space { " " } else { "" });
+    let cursor = before.len() + inserted.encode_utf16().count();
+    (splice_paste(input, selection, &inserted), cursor as u32)
+}
+
+/// Commands a reviewed paste would submit: one per non-blank line, with bracketed-paste markers
+/// and trailing whitespace removed.
+pub(crate) fn paste_commands(text: &str) -> Vec<String> {
+    text.replace(BRACKETED_PASTE_START, "")
+        .replace(BRACKETED_PASTE_END, "")
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether a paste must be reviewed before anything runs.
+pub(crate) fn needs_review(text: &str) -> bool {
+    paste_commands(text).len() > 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pasted_scripts_split_into_commands() {
+        let script = "\u{1b}[200~cd /Projects  \r\n\n  ls -la\r\n\t\necho done\n\u{1b}[201~";
+        assert_eq!(
+            paste_commands(script),
+            vec!["cd /Projects", "  ls -la", "echo done"]
+        );
+        assert!(needs_review(script));
+        assert!(!needs_review("ls -la\n"));
+        assert!(!needs_review("\n\n"));
+    }
+
+    #[test]
+    fn dropped_paths_are_quoted_at_the_cursor() {
+        assert_eq!(quote_path("/Docs/readme.md"), "/Docs/readme.md");
+        assert_eq!(quote_path("/My Files/$HOME"), "'/My Files/$HOME'");
+        assert_eq!(quote_path(r"/a\b c"), r"'/a\\b c'");
+        assert_eq!(quote_path("/it's $x"), r#""/it's \$x""#);
+
+        let paths = ["/Docs".to_string(), "/My Files".to_string()];
+        assert_eq!(
+            insert_dropped_paths("cat", (3, 3), &paths),
+            ("cat /Docs '/My Files' ".to_string(), 22)
+        );
+        assert_eq!(
+            insert_dropped_paths("ls  | wc", (3, 3), &paths[..1]),
+            ("ls /Docs  | wc".to_string(), 9)
+        );
+    }
+
+    #[test]
+    fn pastes_replace_the_input_selection() {
+        assert_eq!(splice_paste("echo ", (5, 5), "a\nb"), "echo a\nb");
+        assert_eq!(splice_paste("echo old!", (8, 5), "new"), "echo new!");
+        assert_eq!(splice_paste("é→x", (2, 3), "y\nz"), "é→y\nz");
+        assert_eq!(splice_paste("ls", (9, 9), "\n"), "ls\n");
+    }
+}
@@ -0,0 +1,257 @@
+//! Terminal profiles: named appearance, prompt, and startup presets selected per window.
+
+use serde::{Deserialize, Serialize};
+
+/// Profile a window uses until another is selected. A stored profile with this name overrides the
+/// built-in defaults.
+pub(crate) const DEFAULT_PROFILE: &str = "default";
+
+/// Text size a profile applies to the terminal surface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProfileFontSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl ProfileFontSize {
+    const ALL: [Self; 3] = [Self::Small, Self::Medium, Self::Large];
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Small => "small",
+            Self::Medium => "medium",
+            Self::Large => "large",
+        }
+    }
+}
+
+/// Color scheme a profile applies to the terminal surface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProfileTheme {
+    #[default]
+    Default,
+    Light,
+    Amber,
+    Green,
+}
+
+impl ProfileTheme {
+    const ALL: [Self; 4] = [Self::Default, Self::Light, Self::Amber, Self::Green];
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Light => "light",
+            Self::Amber => "amber",
+            Self::Green => "green",
+        }
+    }
+}
+
+/// Named terminal preset stored in the profiles preference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TerminalProfile {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) font_size: ProfileFontSize,
+    #[serde(default)]
+    pub(crate) theme: ProfileTheme,
+    /// Composer prompt template; `None` keeps the built-in cwd and mode prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) prompt: Option<String>,
+    /// Command submitted when a new pane opens with this profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) startup_command: Option<String>,
+}
+
+impl TerminalProfile {
+    pub(crate) fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            font_size: ProfileFontSize::default(),
+            theme: ProfileTheme::default(),
+            prompt: None,
+            startup_command: None,
+        }
+    }
+
+    /// Sets one field from `terminal profile set`; an empty value resets the field.
+    pub(crate) fn set_field(&mut self, field: &str, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        match field.to_ascii_lowercase().as_str() {
+            "font-size" => {
+                self.font_size =
+                    parse_choice(value, &ProfileFontSize::ALL, ProfileFontSize::as_str)
+                        .ok_or_else(|| {
+                            format!("font-size must be small, medium, or large, got `{value}`")
+                        })?;
+            }
+            "theme" => {
+                self.theme = parse_choice(value, &ProfileTheme::ALL, ProfileTheme::as_str)
+                    .ok_or_else(|| {
+                        format!("theme must be default, light, amber, or green, got `{value}`")
+                    })?;
+            }
+            "prompt" => self.prompt = (!value.is_empty()).then(|| value.to_string()),
+            "startup" => self.startup_command = (!value.is_empty()).then(|| value.to_string()),
+            _ => {
+                return Err(format!(
+                    "unknown profile field `{field}`; expected font-size, theme, prompt, or startup"
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// One-line description listed by `terminal profile`.
+    pub(crate) fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("font-size {}", self.font_size.as_str()),
+            format!("theme {}", self.theme.as_str()),
+        ];
+        if let Some(prompt) = &self.prompt {
+            parts.push(format!("prompt `{prompt}`"));
+        }
+        if let Some(command) = &self.startup_command {
+            parts.push(format!("startup `{command}`"));
+        }
+        format!("{}: {}", self.name, parts.join(", "))
+    }
+}
+
+/// Parses a case-insensitive choice, treating an empty value as the default.
+fn parse_choice<T: Copy + Default>(
+    value: &str,
+    choices: &[T],
+    name: impl Fn(T) -> &'static str,
+) -> Option<T> {
+    if value.is_empty() {
+        return Some(T::default());
+    }
+    choices
+        .iter()
+        .copied()
+        .find(|choice| name(*choice).eq_ignore_ascii_case(value))
+}
+
+/// Looks up a stored profile, falling back to built-in defaults for [`DEFAULT_PROFILE`].
+pub(crate) fn find_profile(profiles: &[TerminalProfile], name: &str) -> Option<TerminalProfile> {
+    profiles
+        .iter()
+        .find(|profile| profile.name == name)
+        .cloned()
+        .or_else(|| (name == DEFAULT_PROFILE).then(|| TerminalProfile::new(DEFAULT_PROFILE)))
+}
+
+/// Profile a window renders with: its selected profile when that exists, otherwise the default.
+pub(crate) fn active_profile(
+    profiles: &[TerminalProfile],
+    selected: Option<&str>,
+) -> TerminalProfile {
+    selected
+        .and_then(|name| find_profile(profiles, name))
+        .or_else(|| find_profile(profiles, DEFAULT_PROFILE))
+        .unwrap_or_else(|| TerminalProfile::new(DEFAULT_PROFILE))
+}
+
+/// Values substituted into a prompt template.
+pub(crate) struct PromptContext<'a> {
+    pub(crate) cwd: &'a str,
+    /// Exit code of the pane's last completed command.
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) time: &'a str,
+}
+
+/// Expands `{cwd}`, `{exit_code}`, and `{time}` in a prompt template.
+///
+/// `{exit_code}` is empty until a command completes; unknown placeholders are kept verbatim.
+pub(crate) fn render_prompt(template: &str, context: &PromptContext<'_>) -> String {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let Some(end) = placeholder.find('}') else {
+            rest = placeholder;
+            break;
+        };
+        match &placeholder[1..end] {
+            "cwd" => rendered.push_str(context.cwd),
+            "exit_code" => {
+                if let Some(code) = context.exit_code {
+                    rendered.push_str(&code.to_string());
+                }
+            }
+            "time" => rendered.push_str(context.time),
+            _ => rendered.push_str(&placeholder[..=end]),
+        }
+        rest = &placeholder[end + 1..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_templates_expand_known_placeholders() {
+        let context = PromptContext {
+            cwd: "/Projects",
+            exit_code: Some(2),
+            time: "09:41:00",
+        };
+        assert_eq!(
+            render_prompt("[{time}] {cwd} ({exit_code}) {user} {", &context),
+            "[09:41:00] /Projects (2) {user} {"
+        );
+        assert_eq!(
+            render_prompt(
+                "{exit_code}$",
+                &PromptContext {
+                    exit_code: None,
+                    ..context
+                }
+            ),
+            "$"
+        );
+    }
+
+    #[test]
+    fn fields_parse_reset_and_reject_unknown_values() {
+        let mut profile = TerminalProfile::new("work");
+        profile.set_field("font-size", "Large").unwrap();
+        profile.set_field("theme", "amber").unwrap();
+        profile.set_field("prompt", " {cwd} $ ").unwrap();
+        profile.set_field("startup", "ls").unwrap();
+        assert_eq!(
+            profile.summary(),
+            "work: font-size large, theme amber, prompt `{cwd} $`, startup `ls`"
+        );
+
+        profile.set_field("theme", "").unwrap();
+        profile.set_field("startup", "").unwrap();
+        assert_eq!(profile.theme, ProfileTheme::Default);
+        assert_eq!(profile.startup_command, None);
+        assert!(profile.set_field("theme", "neon").is_err());
+        assert!(profile.set_field("colour", "red").is_err());
+
+        let stored: TerminalProfile = serde_json::from_str(r#"{"name":"plain"}"#).unwrap();
+        assert_eq!(stored, TerminalProfile::new("plain"));
+        assert_eq!(
+            find_profile(&[], DEFAULT_PROFILE),
+            Some(TerminalProfile::new(DEFAULT_PROFILE))
+        );
+        assert_eq!(find_profile(&[], "work"), None);
+        assert_eq!(active_profile(&[profile.clone()], Some("work")), profile);
+        assert_eq!(
+            active_profile(&[profile], Some("gone")).name,
+            DEFAULT_PROFILE
+        );
+    }
+}
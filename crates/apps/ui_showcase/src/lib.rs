@@ -88,7 +88,11 @@ pub fn UiShowcaseApp(
         let serialized = match serde_json::to_string(&snapshot) {
             Ok(serialized) => serialized,
             Err(err) => {
-                logging::warn!("ui showcase serialize failed: {err}");
+                if let Some(services) = services_for_persist.as_ref() {
+                    services
+                        .logs
+                        .warn(format!("ui showcase serialize failed: {err}"));
+                }
                 return;
             }
         };
@@ -14,24 +14,50 @@
 
 #![warn(missing_docs, rustdoc::broken_intra_doc_links)]
 
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
 
-use futures::future::LocalBoxFuture;
-use leptos::{Callable, Callback, ReadSignal, RwSignal, View};
+use futures::{
+    channel::oneshot,
+    future::{abortable, AbortHandle, LocalBoxFuture},
+};
+use leptos::{
+    logging, set_interval_with_handle, set_timeout, set_timeout_with_handle, spawn_local,
+    store_value, Callable, Callback, ReadSignal, RwSignal, Signal, SignalGet, SignalGetUntracked,
+    SignalWith, SignalWithUntracked, StoredValue, View,
+};
 use platform_host::{
-    load_app_state_with_migration, load_pref_with, save_app_state_with, save_pref_with,
-    AppStateEnvelope, AppStateStore, CapabilityStatus, ContentCache, ExplorerBackendStatus,
-    ExplorerFileReadResult, ExplorerFsService, ExplorerListResult, ExplorerMetadata,
-    ExplorerPermissionMode, ExplorerPermissionState, HostCapabilities, PrefsStore, WallpaperConfig,
-    WallpaperImportRequest, WallpaperLibrarySnapshot,
+    delete_from_trash_with, empty_trash_with, export_zip_with, folder_size_with, import_zip_with,
+    list_trash_with, load_app_state_with_migration, load_pref_with, move_to_trash_with,
+    restore_from_trash_with, save_app_state_with, save_pref_with, sha256_file_with,
+    top_level_folder_sizes_with, AppStateEnvelope, AppStateStore, CapabilityStatus,
+    ClipboardService, ContentCache, ExplorerArchiveConflict, ExplorerArchiveImport,
+    ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult, ExplorerFileBytesResult,
+    ExplorerFileChunk, ExplorerFileReadResult, ExplorerFolderSize, ExplorerFsService,
+    ExplorerImportFile, ExplorerListPage, ExplorerListResult, ExplorerMetadata,
+    ExplorerPermissionMode, ExplorerPermissionState, ExplorerReadStream, ExplorerStorageUsage,
+    ExplorerTrashEntry, ExplorerWatch, ExplorerWriteStream, HostCapabilities, HttpMethod,
+    HttpRequest, HttpResponse, HttpService, LogLevel, LogQuery, LogRecord, LogRegistry,
+    NoopHttpService, PrefsStore, StorageUsageEntry, WallpaperConfig, WallpaperImportRequest,
+    WallpaperLibrarySnapshot,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use system_shell_contract::{
-    CommandDescriptor, CommandNotice, CommandNoticeLevel, CommandResult, CompletionItem,
-    CompletionRequest, DisplayPreference, ExecutionId, ParsedInvocation, ShellError, ShellRequest,
+    is_affirmative, CommandDescriptor, CommandNotice, CommandNoticeLevel, CommandResult,
+    CompletionItem, CompletionRequest, DisplayPreference, ExecutionId, HistoryMatch,
+    ParsedInvocation, PromptKind, ShellError, ShellErrorCode, ShellExit, ShellRequest,
     ShellStreamEvent, StructuredData,
 };
+use web_sys::DragEvent;
 
 /// Stable identifier for a runtime-managed window.
 pub type WindowRuntimeId = u64;
@@ -44,6 +70,165 @@ pub fn window_primary_input_dom_id(window_id: WindowRuntimeId) -> String {
     format!("window-primary-input-{window_id}")
 }
 
+/// `DataTransfer` type carrying [`FileDragPayload`] JSON between app windows.
+///
+/// Drag sources also set `text/plain` to the newline-joined paths so drops outside the desktop
+/// stay useful.
+pub const FILE_DRAG_DATA_TYPE: &str = "application/x-retrodesk-files+json";
+
+/// Virtual filesystem paths dragged out of an app window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDragPayload {
+    /// Absolute virtual paths, in the order the source listed them.
+    pub paths: Vec<String>,
+}
+
+impl FileDragPayload {
+    /// Creates a payload for the given paths.
+    pub fn new(paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            paths: paths.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Encodes the payload for [`FILE_DRAG_DATA_TYPE`].
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Decodes [`FILE_DRAG_DATA_TYPE`] data, returning `None` for malformed or empty payloads.
+    pub fn decode(raw: &str) -> Option<Self> {
+        serde_json::from_str::<Self>(raw)
+            .ok()
+            .filter(|payload| !payload.paths.is_empty())
+    }
+}
+
+/// Typed data dragged between app windows.
+///
+/// `kind` is the MIME-like `DataTransfer` type the data travels under, such as
+/// [`FILE_DRAG_DATA_TYPE`], and `body` is its JSON. Drop targets check the kind during `dragover`,
+/// since browsers only expose the types until the drop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragData {
+    /// MIME-like data type, for example `application/x-retrodesk-files+json`.
+    pub kind: String,
+    /// JSON body carried under `kind`.
+    pub body: Value,
+}
+
+impl DragData {
+    /// Creates drag data of `kind` from a serializable body.
+    pub fn new(kind: impl Into<String>, body: &impl Serialize) -> Self {
+        Self {
+            kind: kind.into(),
+            body: serde_json::to_value(body).unwrap_or(Value::Null),
+        }
+    }
+
+    /// Creates a [`FILE_DRAG_DATA_TYPE`] drag for the given virtual paths.
+    pub fn files(paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::new(FILE_DRAG_DATA_TYPE, &FileDragPayload::new(paths))
+    }
+
+    /// Encodes the body for the `DataTransfer`.
+    pub fn encode(&self) -> String {
+        self.body.to_string()
+    }
+
+    /// Decodes `DataTransfer` data read for `kind`, returning `None` for malformed JSON.
+    pub fn decode(kind: impl Into<String>, raw: &str) -> Option<Self> {
+        serde_json::from_str(raw).ok().map(|body| Self {
+            kind: kind.into(),
+            body,
+        })
+    }
+
+    /// Returns the body as `T`, or `None` when it does not match.
+    pub fn body_as<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_value(self.body.clone()).ok()
+    }
+
+    /// Returns the dragged paths of a non-empty [`FILE_DRAG_DATA_TYPE`] drag.
+    pub fn paths(&self) -> Option<Vec<String>> {
+        if self.kind != FILE_DRAG_DATA_TYPE {
+            return None;
+        }
+        self.body_as::<FileDragPayload>()
+            .map(|payload| payload.paths)
+            .filter(|paths| !paths.is_empty())
+    }
+}
+
+/// Operations a drag source allows or a drop target performs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragEffect {
+    /// The target copies the data.
+    Copy,
+    /// The target moves the data.
+    Move,
+    /// The target links to the data.
+    Link,
+    /// The target may copy or move the data (drag sources only).
+    CopyMove,
+}
+
+impl DragEffect {
+    /// Returns the `DataTransfer` effect keyword.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Copy => "copy",
+            Self::Move => "move",
+            Self::Link => "link",
+            Self::CopyMove => "copyMove",
+        }
+    }
+}
+
+/// `dragstart` hook: offers `data` to drop targets, with `text` as the `text/plain` fallback for
+/// drops outside the desktop.
+pub fn start_drag(ev: &DragEvent, data: &DragData, text: Option<&str>, allowed: DragEffect) {
+    let Some(transfer) = ev.data_transfer() else {
+        return;
+    };
+    let _ = transfer.set_data(&data.kind, &data.encode());
+    if let Some(text) = text {
+        let _ = transfer.set_data("text/plain", text);
+    }
+    transfer.set_effect_allowed(allowed.as_str());
+}
+
+/// Whether a drag carries data of `kind`.
+pub fn drag_carries(ev: &DragEvent, kind: &str) -> bool {
+    ev.data_transfer()
+        .is_some_and(|transfer| transfer.types().includes(&kind.into(), 0))
+}
+
+/// `dragover` hook: accepts a drag carrying `kind` with `effect`, returning whether it did.
+///
+/// Targets must accept every `dragover` for the browser to deliver the drop.
+pub fn accept_drag_over(ev: &DragEvent, kind: &str, effect: DragEffect) -> bool {
+    if !drag_carries(ev, kind) {
+        return false;
+    }
+    ev.prevent_default();
+    if let Some(transfer) = ev.data_transfer() {
+        transfer.set_drop_effect(effect.as_str());
+    }
+    true
+}
+
+/// `drop` hook: takes the dropped data of `kind`, preventing the browser's own handling when it
+/// is present and well formed.
+pub fn take_drop(ev: &DragEvent, kind: &str) -> Option<DragData> {
+    let data = ev
+        .data_transfer()
+        .and_then(|transfer| transfer.get_data(kind).ok())
+        .and_then(|raw| DragData::decode(kind, &raw))?;
+    ev.prevent_default();
+    Some(data)
+}
+
 /// Stable identifier for an app package/module.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ApplicationId(String);
@@ -117,6 +302,163 @@ fn is_valid_application_id(raw: &str) -> bool {
     count >= 2
 }
 
+/// Config namespace for desktop-wide file settings.
+pub const FILE_ASSOCIATIONS_CONFIG_NAMESPACE: &str = "system.files";
+/// Config key under [`FILE_ASSOCIATIONS_CONFIG_NAMESPACE`] holding [`FileAssociations`].
+pub const FILE_ASSOCIATIONS_CONFIG_KEY: &str = "associations";
+
+/// Opens files with a given extension in an app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileAssociation {
+    /// Lowercase extension without the leading dot, for example `md` or `tar.gz`.
+    pub extension: String,
+    /// App launched for matching files.
+    pub app_id: ApplicationId,
+    /// Launch params template.
+    ///
+    /// `{path}`, `{dir}`, and `{name}` inside string values are replaced with the file's path, its
+    /// folder, and its file name when the app is launched.
+    #[serde(default)]
+    pub launch_params: Value,
+}
+
+impl FileAssociation {
+    /// Creates an association, normalizing `extension`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message when the extension is empty or contains a path separator.
+    pub fn new(
+        extension: &str,
+        app_id: ApplicationId,
+        launch_params: Value,
+    ) -> Result<Self, String> {
+        let extension = normalize_file_extension(extension)
+            .ok_or_else(|| format!("invalid file extension `{}`", extension.trim()))?;
+        Ok(Self {
+            extension,
+            app_id,
+            launch_params,
+        })
+    }
+
+    /// Default launch params template, passing the file path as `path`.
+    pub fn default_launch_params() -> Value {
+        serde_json::json!({ "path": "{path}" })
+    }
+
+    /// Resolves the launch params template for `path`.
+    pub fn launch_params_for(&self, path: &str) -> Value {
+        let (dir, name) = match path.rsplit_once('/') {
+            Some(("", name)) => ("/", name),
+            Some((dir, name)) => (dir, name),
+            None => ("/", path),
+        };
+        fill_launch_params(
+            &self.launch_params,
+            &[("{path}", path), ("{dir}", dir), ("{name}", name)],
+        )
+    }
+}
+
+fn fill_launch_params(template: &Value, placeholders: &[(&str, &str)]) -> Value {
+    match template {
+        Value::String(raw) => Value::String(
+            placeholders
+                .iter()
+                .fold(raw.clone(), |text, (placeholder, value)| {
+                    text.replace(placeholder, value)
+                }),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| fill_launch_params(item, placeholders))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), fill_launch_params(value, placeholders)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Normalizes a user-entered extension: trims it, drops leading dots, and lowercases it.
+///
+/// Returns `None` when nothing is left or the extension contains a path separator.
+pub fn normalize_file_extension(raw: &str) -> Option<String> {
+    let extension = raw.trim().trim_start_matches('.').to_ascii_lowercase();
+    (!extension.is_empty() && !extension.contains(['/', '\\'])).then_some(extension)
+}
+
+/// File-association registry managed in Settings and stored through [`ConfigService`] under
+/// [`FILE_ASSOCIATIONS_CONFIG_NAMESPACE`] and [`FILE_ASSOCIATIONS_CONFIG_KEY`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileAssociations {
+    /// Associations sorted by extension, at most one per extension.
+    #[serde(default)]
+    pub associations: Vec<FileAssociation>,
+}
+
+impl FileAssociations {
+    /// Returns the association for `path`, preferring the longest matching extension so
+    /// `tar.gz` wins over `gz`.
+    pub fn lookup(&self, path: &str) -> Option<&FileAssociation> {
+        let name = path.rsplit('/').next().unwrap_or(path).to_ascii_lowercase();
+        self.associations
+            .iter()
+            .filter(|association| {
+                name.strip_suffix(&association.extension)
+                    .and_then(|stem| stem.strip_suffix('.'))
+                    .is_some_and(|stem| !stem.is_empty())
+            })
+            .max_by_key(|association| association.extension.len())
+    }
+
+    /// Adds `association`, replacing any existing one for the same extension.
+    pub fn set(&mut self, association: FileAssociation) {
+        self.remove(&association.extension);
+        let index = self
+            .associations
+            .partition_point(|existing| existing.extension < association.extension);
+        self.associations.insert(index, association);
+    }
+
+    /// Points the association for `extension` at `app_id`, keeping its launch params template.
+    ///
+    /// Returns whether an association for `extension` existed.
+    pub fn set_app(&mut self, extension: &str, app_id: ApplicationId) -> bool {
+        let Some(extension) = normalize_file_extension(extension) else {
+            return false;
+        };
+        match self
+            .associations
+            .iter_mut()
+            .find(|association| association.extension == extension)
+        {
+            Some(association) => {
+                association.app_id = app_id;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the association for `extension`, returning whether one existed.
+    pub fn remove(&mut self, extension: &str) -> bool {
+        let Some(extension) = normalize_file_extension(extension) else {
+            return false;
+        };
+        let before = self.associations.len();
+        self.associations
+            .retain(|association| association.extension != extension);
+        self.associations.len() != before
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 /// Declared app capability scopes enforced by runtime policy.
@@ -139,6 +481,57 @@ pub enum AppCapability {
     ExternalUrl,
     /// Dynamic system terminal command registration.
     Commands,
+    /// Outbound HTTP requests to the app's allowlisted URLs.
+    Network,
+    /// Force-closing other apps' windows.
+    Processes,
+}
+
+impl AppCapability {
+    /// Returns the kebab-case id used in app manifests and command descriptors.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Window => "window",
+            Self::State => "state",
+            Self::Config => "config",
+            Self::Theme => "theme",
+            Self::Wallpaper => "wallpaper",
+            Self::Notifications => "notifications",
+            Self::Ipc => "ipc",
+            Self::ExternalUrl => "external-url",
+            Self::Commands => "commands",
+            Self::Network => "network",
+            Self::Processes => "processes",
+        }
+    }
+
+    /// Parses a kebab-case capability id as returned by [`AppCapability::as_str`].
+    pub fn from_id(id: &str) -> Option<Self> {
+        [
+            Self::Window,
+            Self::State,
+            Self::Config,
+            Self::Theme,
+            Self::Wallpaper,
+            Self::Notifications,
+            Self::Ipc,
+            Self::ExternalUrl,
+            Self::Commands,
+            Self::Network,
+            Self::Processes,
+        ]
+        .into_iter()
+        .find(|capability| capability.as_str() == id)
+    }
+
+    /// Returns whether apps may ask the user for this capability at runtime through
+    /// [`AppServices::request_capability`] instead of declaring it in their manifest.
+    pub fn is_requestable(self) -> bool {
+        matches!(
+            self,
+            Self::Notifications | Self::ExternalUrl | Self::Network
+        )
+    }
 }
 
 /// Runtime-granted app capabilities paired with host availability for optional domains.
@@ -167,6 +560,13 @@ impl CapabilitySet {
         self.host
     }
 
+    /// Adds a runtime grant for `capability`; granting it again has no effect.
+    pub fn grant(&mut self, capability: AppCapability) {
+        if !self.is_granted(capability) {
+            self.granted.push(capability);
+        }
+    }
+
     /// Returns whether the runtime granted `capability` to the mounted app.
     pub fn is_granted(&self, capability: AppCapability) -> bool {
         self.granted.contains(&capability)
@@ -183,11 +583,13 @@ impl CapabilitySet {
             AppCapability::Wallpaper => self.host.wallpaper_library,
             AppCapability::Notifications => self.host.notifications,
             AppCapability::ExternalUrl => self.host.external_urls,
+            AppCapability::Network => self.host.network,
             AppCapability::Window
             | AppCapability::State
             | AppCapability::Config
             | AppCapability::Theme
-            | AppCapability::Ipc => CapabilityStatus::Available,
+            | AppCapability::Ipc
+            | AppCapability::Processes => CapabilityStatus::Available,
         }
     }
 
@@ -207,6 +609,180 @@ impl CapabilitySet {
     }
 }
 
+/// Capability a user granted an app at runtime through [`AppServices::request_capability`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityGrant {
+    /// App holding the grant.
+    pub app_id: ApplicationId,
+    /// Granted capability.
+    pub capability: AppCapability,
+}
+
+#[derive(Clone, Copy)]
+/// Service listing and revoking the capabilities users granted apps at runtime.
+///
+/// Only privileged apps may revoke grants; the window manager ignores requests from other apps.
+/// Revocation reaches the apps' live [`CapabilitySet`] signals at once.
+pub struct PermissionsService {
+    sender: Callback<AppCommand>,
+    /// Runtime grants across all apps, ordered by app id.
+    pub grants: ReadSignal<Vec<CapabilityGrant>>,
+}
+
+impl PermissionsService {
+    /// Revokes `capability` from `app_id`.
+    pub fn revoke(&self, app_id: ApplicationId, capability: AppCapability) {
+        self.sender
+            .call(AppCommand::RevokeCapability { app_id, capability });
+    }
+}
+
+/// Window listed by [`ProcessService::list`] with its runtime metrics.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowProcessInfo {
+    /// Runtime window id.
+    pub window_id: WindowRuntimeId,
+    /// App shown in the window.
+    pub app_id: ApplicationId,
+    /// Window title.
+    pub title: String,
+    /// Whether the window is focused.
+    pub focused: bool,
+    /// Whether the window is minimized.
+    pub minimized: bool,
+    /// Whether the window manager suspended the app.
+    pub suspended: bool,
+    /// Zero-based workspace the window belongs to.
+    pub workspace: u32,
+    /// App-bus events delivered to the window's inbox since it mounted.
+    pub events_delivered: u64,
+    /// Events dropped from the window's inbox because it was full.
+    pub events_dropped: u64,
+    /// Envelopes currently waiting in the window's inbox.
+    pub inbox_len: usize,
+    /// App-bus topics the window subscribes to.
+    pub subscriptions: usize,
+    /// Size in bytes of the window's serialized manager-persisted app state.
+    pub app_state_bytes: usize,
+}
+
+/// Lists every open window, oldest first.
+pub type WindowLister = Rc<dyn Fn() -> Vec<WindowProcessInfo>>;
+
+#[derive(Clone, Copy)]
+/// Service for listing open windows and force-closing them, as a task manager does.
+///
+/// Listing is open to every app. Force-closing requires [`AppCapability::Processes`]; the window
+/// manager ignores requests from other apps. A forced close skips the target window's lifecycle
+/// handler, so the app gets no chance to veto or delay it.
+pub struct ProcessService {
+    sender: Callback<AppCommand>,
+    lister: StoredValue<WindowLister>,
+}
+
+impl ProcessService {
+    /// Returns a snapshot of the open windows and their metrics.
+    pub fn list(&self) -> Vec<WindowProcessInfo> {
+        self.lister
+            .try_get_value()
+            .map(|list| list())
+            .unwrap_or_default()
+    }
+
+    /// Closes `window_id` without consulting its lifecycle handler.
+    pub fn force_close(&self, window_id: WindowRuntimeId) {
+        self.sender.call(AppCommand::ForceCloseWindow { window_id });
+    }
+}
+
+#[derive(Clone)]
+/// Leveled, structured logging into the desktop's shared log buffers.
+///
+/// Records are tagged with the app id, kept in a per-app ring buffer that the Log Viewer and the
+/// `logs tail` command read, and echoed to the browser console. Apps use it instead of
+/// `leptos::logging` so their problems show up in the desktop's own tooling. When the user turns
+/// on log persistence, each record is also written to prefs.
+pub struct LogService {
+    source: Rc<str>,
+    registry: LogRegistry,
+}
+
+impl LogService {
+    /// Creates a service that logs into `registry` as `source`.
+    pub fn new(registry: LogRegistry, source: impl Into<String>) -> Self {
+        Self {
+            source: source.into().into(),
+            registry,
+        }
+    }
+
+    /// Logs `message` at [`LogLevel::Debug`].
+    pub fn debug(&self, message: impl Into<String>) {
+        self.emit(LogLevel::Debug, message.into(), BTreeMap::new());
+    }
+
+    /// Logs `message` at [`LogLevel::Info`].
+    pub fn info(&self, message: impl Into<String>) {
+        self.emit(LogLevel::Info, message.into(), BTreeMap::new());
+    }
+
+    /// Logs `message` at [`LogLevel::Warn`].
+    pub fn warn(&self, message: impl Into<String>) {
+        self.emit(LogLevel::Warn, message.into(), BTreeMap::new());
+    }
+
+    /// Logs `message` at [`LogLevel::Error`].
+    pub fn error(&self, message: impl Into<String>) {
+        self.emit(LogLevel::Error, message.into(), BTreeMap::new());
+    }
+
+    /// Logs `message` at `level` with structured `fields`.
+    pub fn log<K: Into<String>>(
+        &self,
+        level: LogLevel,
+        message: impl Into<String>,
+        fields: impl IntoIterator<Item = (K, Value)>,
+    ) {
+        let fields = fields
+            .into_iter()
+            .map(|(key, value)| (key.into(), value))
+            .collect();
+        self.emit(level, message.into(), fields);
+    }
+
+    fn emit(&self, level: LogLevel, message: String, fields: BTreeMap<String, Value>) {
+        let record = self.registry.record(&self.source, level, message, fields);
+        let line = if record.fields.is_empty() {
+            format!("[{}] {}", record.source, record.message)
+        } else {
+            let fields = serde_json::to_string(&record.fields).unwrap_or_default();
+            format!("[{}] {} {fields}", record.source, record.message)
+        };
+        match level {
+            LogLevel::Debug | LogLevel::Info => logging::log!("{line}"),
+            LogLevel::Warn => logging::warn!("{line}"),
+            LogLevel::Error => logging::error!("{line}"),
+        }
+        if self.registry.claim_persist() {
+            let registry = self.registry.clone();
+            spawn_local(async move {
+                if let Err(err) = registry.persist().await {
+                    logging::warn!("persist logs failed: {err}");
+                }
+            });
+        }
+    }
+
+    /// Returns this source's newest `limit` records, oldest first.
+    pub fn tail(&self, limit: usize) -> Vec<LogRecord> {
+        self.registry.tail(&LogQuery {
+            source: Some(self.source.to_string()),
+            min_level: None,
+            limit: Some(limit),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 /// Lifecycle events emitted by the desktop window manager.
 pub enum AppLifecycleEvent {
@@ -247,6 +823,53 @@ impl AppLifecycleEvent {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Answer an [`AppLifecycleHandler`] gives when its window is asked to close.
+pub enum CloseResponse {
+    /// Let the window close.
+    Close,
+    /// Keep the window open.
+    Cancel,
+}
+
+/// Object-safe boxed future returned by [`AppLifecycleHandler`] hooks.
+pub type LifecycleFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Hooks a mounted app registers through [`WindowService::set_lifecycle_handler`] to take part in
+/// its window's lifecycle.
+///
+/// [`AppMountContext::lifecycle`] reports what already happened; handler methods run before the
+/// window manager acts and can change the outcome.
+pub trait AppLifecycleHandler {
+    /// Called when the user asks to close the window.
+    ///
+    /// The window stays open until the returned future resolves and closes only on
+    /// [`CloseResponse::Close`], so an app can ask to save changes, save, and then close. The
+    /// default closes right away.
+    fn on_closing(&self) -> LifecycleFuture<'_, CloseResponse> {
+        Box::pin(async { CloseResponse::Close })
+    }
+}
+
+/// Callback receiving the [`AppLink`]s routed to a window.
+pub type LinkHandler = Rc<dyn Fn(AppLink)>;
+
+/// Slot the window manager reads a window's registered [`LinkHandler`] from.
+pub type LinkHandlerSlot = StoredValue<Option<LinkHandler>>;
+
+/// Slot the window manager reads a window's registered [`AppLifecycleHandler`] from.
+pub type LifecycleHandlerSlot = StoredValue<Option<Rc<dyn AppLifecycleHandler>>>;
+
+/// Asks the handler in `slot`, if any, whether its window may close.
+///
+/// Windows without a handler, or whose slot was already disposed, may close.
+pub async fn confirm_window_close(slot: LifecycleHandlerSlot) -> CloseResponse {
+    match slot.try_get_value().flatten() {
+        Some(handler) => handler.on_closing().await,
+        None => CloseResponse::Close,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Typed IPC envelope delivered through runtime-managed app inbox channels.
 pub struct AppEvent {
@@ -306,6 +929,36 @@ pub enum AppCommand {
         /// New title text.
         title: String,
     },
+    /// Resize the current window, keeping its top-left corner.
+    SetWindowSize {
+        /// New width in pixels.
+        width: i32,
+        /// New height in pixels.
+        height: i32,
+    },
+    /// Move the current window's top-left corner.
+    SetWindowPosition {
+        /// New left position in pixels.
+        x: i32,
+        /// New top position in pixels.
+        y: i32,
+    },
+    /// Minimize the current window.
+    MinimizeWindow,
+    /// Maximize the current window to the desktop viewport.
+    MaximizeWindow,
+    /// Restore the current window from a minimized or maximized state.
+    RestoreWindow,
+    /// Replace the icon shown in the current window's chrome and taskbar button.
+    SetWindowIcon {
+        /// Icon token (for example `play`).
+        icon_id: String,
+    },
+    /// Show a count badge on the current window's taskbar button.
+    SetWindowBadge {
+        /// Badge count; `0` hides the badge.
+        count: u32,
+    },
     /// Persist manager-owned app state for the current window.
     PersistState {
         /// Serialized app state payload.
@@ -437,12 +1090,67 @@ pub enum AppCommand {
         /// Whether reduced motion should be enabled.
         enabled: bool,
     },
-    /// Emit a host notification.
+    /// Post a notification to the notification center and the host.
     Notify {
-        /// Notification title.
-        title: String,
-        /// Notification body.
-        body: String,
+        /// Notification to post.
+        notification: Notification,
+    },
+    /// Open a window for another app through the window manager.
+    LaunchApp {
+        /// App to launch.
+        app_id: ApplicationId,
+        /// Launch params passed to the new window.
+        launch_params: Value,
+    },
+    /// Route an [`AppLink`] to the app it names.
+    OpenLink {
+        /// Link to open.
+        link: AppLink,
+    },
+    /// Open another window, even for an app that is already open.
+    ///
+    /// Unlike [`AppCommand::LaunchApp`], a single-instance app may open more windows of itself;
+    /// other single-instance apps that are already open are focused instead.
+    OpenWindow {
+        /// App to open a window for.
+        app_id: ApplicationId,
+        /// Launch params passed to the new window.
+        launch_params: Value,
+    },
+    /// Revoke a capability granted to an app at runtime; only privileged apps may send this.
+    RevokeCapability {
+        /// App losing the grant.
+        app_id: ApplicationId,
+        /// Revoked capability.
+        capability: AppCapability,
+    },
+    /// Close another window immediately, skipping its lifecycle handler.
+    ForceCloseWindow {
+        /// Window to close.
+        window_id: WindowRuntimeId,
+    },
+    /// Drop every app-shared state key of an app; only privileged apps may send this.
+    ClearSharedState {
+        /// App whose shared state is dropped.
+        app_id: ApplicationId,
+    },
+    /// Change how an app's notifications are delivered; only privileged apps may send this.
+    SetNotificationDelivery {
+        /// App whose notifications are affected.
+        app_id: ApplicationId,
+        /// New delivery mode.
+        delivery: NotificationDelivery,
+    },
+    /// Deliver a reply straight to the inbox of the window that sent a request.
+    ReplyToWindow {
+        /// Window that sent the request.
+        target_window_id: WindowRuntimeId,
+        /// Reply topic named by the request.
+        topic: String,
+        /// Correlation id copied from the request.
+        correlation_id: String,
+        /// Reply payload.
+        payload: Value,
     },
 }
 
@@ -456,10 +1164,41 @@ pub enum SuspendPolicy {
     Never,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+/// Current placement and state of an app's window.
+pub struct WindowGeometry {
+    /// Left position in pixels.
+    pub x: i32,
+    /// Top position in pixels.
+    pub y: i32,
+    /// Width in pixels.
+    pub width: i32,
+    /// Height in pixels.
+    pub height: i32,
+    /// Whether the window is minimized.
+    pub minimized: bool,
+    /// Whether the window is maximized.
+    pub maximized: bool,
+}
+
+/// Opens a window through the window manager and resolves with the window that ended up focused
+/// for the request.
+pub type WindowOpener =
+    Rc<dyn Fn(ApplicationId, Value) -> LocalBoxFuture<'static, Result<WindowRuntimeId, String>>>;
+
 #[derive(Clone, Copy)]
 /// Window-scoped app service for shell window integration APIs.
+///
+/// Requests go through the window manager, which ignores the ones the window's flags forbid (for
+/// example resizing a fixed-size window) and clamps sizes to the manager's minimum.
 pub struct WindowService {
     sender: Callback<AppCommand>,
+    /// Reactive geometry of the current window.
+    pub geometry: ReadSignal<WindowGeometry>,
+    /// Reactive focus state of the current window.
+    pub focused: ReadSignal<bool>,
+    opener: StoredValue<WindowOpener>,
+    lifecycle_handler: LifecycleHandlerSlot,
 }
 
 impl WindowService {
@@ -469,64 +1208,423 @@ impl WindowService {
             title: title.into(),
         });
     }
-}
-
-#[derive(Clone, Copy)]
-/// State persistence service for window and app-shared state channels.
-pub struct StateService {
-    sender: Callback<AppCommand>,
-}
 
-impl StateService {
-    /// Persists manager-owned state for this window instance.
-    pub fn persist_window_state(&self, state: Value) {
-        self.sender.call(AppCommand::PersistState { state });
+    /// Requests a new size for the current window.
+    pub fn set_size(&self, width: i32, height: i32) {
+        self.sender
+            .call(AppCommand::SetWindowSize { width, height });
     }
 
-    /// Persists app-shared state under `key`.
-    pub fn persist_shared_state(&self, key: impl Into<String>, state: Value) {
-        self.sender.call(AppCommand::PersistSharedState {
-            key: key.into(),
-            state,
-        });
+    /// Requests a new top-left position for the current window.
+    pub fn set_position(&self, x: i32, y: i32) {
+        self.sender.call(AppCommand::SetWindowPosition { x, y });
     }
-}
 
-#[derive(Clone)]
-/// Namespaced app config service.
-pub struct ConfigService {
-    sender: Callback<AppCommand>,
-    prefs: Rc<dyn PrefsStore>,
-}
+    /// Minimizes the current window.
+    pub fn minimize(&self) {
+        self.sender.call(AppCommand::MinimizeWindow);
+    }
 
-impl ConfigService {
-    fn pref_key(namespace: &str, key: &str) -> String {
-        format!("{namespace}.{key}")
+    /// Maximizes the current window.
+    pub fn maximize(&self) {
+        self.sender.call(AppCommand::MaximizeWindow);
     }
 
-    /// Loads a typed namespaced config value from the runtime-selected host preference store.
-    pub async fn load<T: serde::de::DeserializeOwned>(
-        &self,
-        namespace: &str,
-        key: &str,
-    ) -> Result<Option<T>, String> {
-        load_pref_with(self.prefs.as_ref(), &Self::pref_key(namespace, key)).await
+    /// Restores the current window from a minimized or maximized state.
+    pub fn restore(&self) {
+        self.sender.call(AppCommand::RestoreWindow);
     }
 
-    /// Saves a namespaced config key/value payload.
-    pub fn save(&self, namespace: impl Into<String>, key: impl Into<String>, value: Value) {
-        let namespace = namespace.into();
-        let key = key.into();
-        self.sender.call(AppCommand::SaveConfig {
-            namespace,
-            key,
-            value,
+    /// Replaces the current window's icon with the icon named by `icon_id`.
+    ///
+    /// Unknown icon ids fall back to the app's own icon.
+    pub fn set_icon(&self, icon_id: impl Into<String>) {
+        self.sender.call(AppCommand::SetWindowIcon {
+            icon_id: icon_id.into(),
         });
     }
-}
 
-#[derive(Clone)]
-/// Typed app-state persistence service backed by the runtime-selected host strategy.
+    /// Shows `count` on the current window's taskbar button, or hides the badge when it is `0`.
+    pub fn set_badge_count(&self, count: u32) {
+        self.sender.call(AppCommand::SetWindowBadge { count });
+    }
+
+    /// Opens a new window for `app_id` with `launch_params` and returns its runtime id.
+    ///
+    /// An app may open any number of its own windows. Another single-instance app that is already
+    /// open is focused instead, and its existing window id is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the app lacks the window capability or no window could be opened.
+    pub async fn open_window(
+        &self,
+        app_id: ApplicationId,
+        launch_params: Value,
+    ) -> Result<WindowRuntimeId, String> {
+        let open = self.opener.get_value();
+        open(app_id, launch_params).await
+    }
+
+    /// Registers `handler` for the current window, replacing any earlier one.
+    ///
+    /// Closes the user starts from the title bar or taskbar wait for
+    /// [`AppLifecycleHandler::on_closing`]; scripted closes such as the `windows close` shell
+    /// command do not.
+    pub fn set_lifecycle_handler(&self, handler: impl AppLifecycleHandler + 'static) {
+        let handler: Rc<dyn AppLifecycleHandler> = Rc::new(handler);
+        self.lifecycle_handler
+            .try_update_value(|slot| *slot = Some(handler));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+/// Workspace layout as seen from one app window.
+pub struct WorkspaceInfo {
+    /// Zero-based index of the workspace on screen.
+    pub active: u32,
+    /// Number of workspaces.
+    pub count: u32,
+    /// Zero-based index of the workspace the window belongs to.
+    pub window: u32,
+}
+
+impl WorkspaceInfo {
+    /// Returns whether the window's workspace is the one on screen.
+    pub fn window_visible(&self) -> bool {
+        self.active == self.window
+    }
+}
+
+#[derive(Clone, Copy)]
+/// Read-only view of the window manager's workspaces.
+///
+/// Users switch workspaces and move windows between them; apps can only observe the result, for
+/// example to pause work while their window is on a hidden workspace.
+pub struct WorkspaceService {
+    /// Reactive workspace layout for the current window.
+    pub info: ReadSignal<WorkspaceInfo>,
+}
+
+/// App listed by [`LauncherService::apps`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchableApp {
+    /// Canonical app id.
+    pub app_id: ApplicationId,
+    /// Human-readable display name.
+    pub display_name: String,
+    /// App-state namespaces, preference keys, and cache names the app declares as its own.
+    #[serde(default)]
+    pub storage_keys: Vec<String>,
+}
+
+impl LaunchableApp {
+    /// Returns whether `name` is one of the app's declared storage keys.
+    ///
+    /// Matching is exact, so shared stores such as the system log buffer are never claimed by an
+    /// app whose id happens to prefix them.
+    pub fn owns_storage_key(&self, name: &str) -> bool {
+        self.storage_keys.iter().any(|key| key == name)
+    }
+}
+
+#[derive(Clone)]
+/// Service for opening other apps through the window manager.
+pub struct LauncherService {
+    sender: Callback<AppCommand>,
+    apps: Rc<[LaunchableApp]>,
+}
+
+impl LauncherService {
+    /// Returns the apps shown in the desktop launcher.
+    pub fn apps(&self) -> &[LaunchableApp] {
+        &self.apps
+    }
+
+    /// Returns the display name of `app_id`, falling back to the id itself.
+    pub fn display_name(&self, app_id: &ApplicationId) -> String {
+        self.apps
+            .iter()
+            .find(|app| app.app_id == *app_id)
+            .map(|app| app.display_name.clone())
+            .unwrap_or_else(|| app_id.to_string())
+    }
+
+    /// Asks the window manager to open `app_id` with `launch_params`.
+    ///
+    /// Single-instance apps that are already open are focused instead and keep their current
+    /// launch params.
+    pub fn launch(&self, app_id: ApplicationId, launch_params: Value) {
+        self.sender.call(AppCommand::LaunchApp {
+            app_id,
+            launch_params,
+        });
+    }
+
+    /// Opens `path` in the app its [`FileAssociation`] names.
+    pub fn open_with(&self, association: &FileAssociation, path: &str) {
+        self.launch(
+            association.app_id.clone(),
+            association.launch_params_for(path),
+        );
+    }
+
+    /// Routes `link` to the app it names.
+    ///
+    /// An open window of that app with a [link handler](AppServices::register_link_handler) is
+    /// focused and handles the link; otherwise the app is launched with
+    /// [`AppLink::launch_params`].
+    pub fn open_link(&self, link: AppLink) {
+        self.sender.call(AppCommand::OpenLink { link });
+    }
+}
+
+/// URI scheme of links that route into apps.
+pub const APP_LINK_SCHEME: &str = "web+os";
+
+/// Parsed `web+os://<app-id>/<path>?<params>` link into an app.
+///
+/// The path is an app-internal route and the query holds string parameters. Both are
+/// percent-decoded; the query also decodes `+` as a space.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppLink {
+    /// App the link opens.
+    pub app_id: ApplicationId,
+    /// App-internal route starting with `/`; `/` when the link names only the app.
+    pub path: String,
+    /// Query parameters by name.
+    pub params: BTreeMap<String, String>,
+}
+
+impl AppLink {
+    /// Creates a link to the root route of `app_id`.
+    pub fn new(app_id: ApplicationId) -> Self {
+        Self {
+            app_id,
+            path: "/".to_string(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the app-internal route, adding a leading `/` when missing.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.path = if path.starts_with('/') {
+            path
+        } else {
+            format!("/{path}")
+        };
+        self
+    }
+
+    /// Sets the query parameter `name` to `value`.
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Parses a `web+os://` URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the scheme is not [`APP_LINK_SCHEME`], the app id is invalid, or a
+    /// percent escape is malformed.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim();
+        let rest = raw
+            .split_once("://")
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(APP_LINK_SCHEME))
+            .map(|(_, rest)| rest)
+            .ok_or_else(|| format!("`{raw}` is not a {APP_LINK_SCHEME}:// link"))?;
+        let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+        let (location, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (app_id, path) = match location.find('/') {
+            Some(index) => location.split_at(index),
+            None => (location, "/"),
+        };
+        let app_id = ApplicationId::new(percent_decode(app_id, false)?)?;
+        let mut link = Self::new(app_id).with_path(percent_decode(path, false)?);
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            link.params
+                .insert(percent_decode(name, true)?, percent_decode(value, true)?);
+        }
+        Ok(link)
+    }
+
+    /// Parses a `web+os://` URI that was itself percent-encoded, as in a page URL's
+    /// `?link=web%2Bos%3A%2F%2F...` query value.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::parse`] for the decoded URI.
+    pub fn parse_encoded(raw: &str) -> Result<Self, String> {
+        Self::parse(&percent_decode(raw, false)?)
+    }
+
+    /// Launch params for a window opened by this link.
+    ///
+    /// Each query parameter becomes a string field, and a path other than `/` becomes the `path`
+    /// field, so `web+os://system.explorer/Documents?viewer=editor` opens Explorer on
+    /// `/Documents` in the editor.
+    pub fn launch_params(&self) -> Value {
+        let mut params = self
+            .params
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+            .collect::<serde_json::Map<_, _>>();
+        if self.path != "/" {
+            params.insert("path".to_string(), Value::String(self.path.clone()));
+        }
+        Value::Object(params)
+    }
+}
+
+impl std::fmt::Display for AppLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{APP_LINK_SCHEME}://{}", self.app_id)?;
+        if self.path != "/" {
+            f.write_str(&percent_encode(&self.path, "/"))?;
+        }
+        for (index, (name, value)) in self.params.iter().enumerate() {
+            let separator = if index == 0 { '?' } else { '&' };
+            write!(
+                f,
+                "{separator}{}={}",
+                percent_encode(name, ""),
+                percent_encode(value, "")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes `%XX` escapes, and `+` as a space when `plus_as_space` is set.
+fn percent_decode(raw: &str, plus_as_space: bool) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut input = raw.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'%' => {
+                let escape = [input.next(), input.next()];
+                let hex = escape
+                    .iter()
+                    .flatten()
+                    .map(|digit| char::from(*digit))
+                    .collect::<String>();
+                let decoded = u8::from_str_radix(&hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 2)
+                    .ok_or_else(|| format!("invalid percent escape `%{hex}` in `{raw}`"))?;
+                bytes.push(decoded);
+            }
+            b'+' if plus_as_space => bytes.push(b' '),
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| format!("`{raw}` does not decode to UTF-8"))
+}
+
+/// Escapes every byte except unreserved URI characters and those in `keep`.
+fn percent_encode(raw: &str, keep: &str) -> String {
+    raw.bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric()
+                || b"-._~".contains(&byte)
+                || keep.as_bytes().contains(&byte)
+            {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy)]
+/// State persistence service for window and app-shared state channels.
+///
+/// App-shared state is one key space per app, visible to all of its windows, so windows can
+/// coordinate by writing and watching the same key.
+pub struct StateService {
+    sender: Callback<AppCommand>,
+    shared: ReadSignal<BTreeMap<String, Value>>,
+}
+
+impl StateService {
+    /// Persists manager-owned state for this window instance.
+    pub fn persist_window_state(&self, state: Value) {
+        self.sender.call(AppCommand::PersistState { state });
+    }
+
+    /// Persists app-shared state under `key`.
+    pub fn persist_shared_state(&self, key: impl Into<String>, state: Value) {
+        self.sender.call(AppCommand::PersistSharedState {
+            key: key.into(),
+            state,
+        });
+    }
+
+    /// Drops every app-shared state key of `app_id`.
+    ///
+    /// Only privileged apps may clear another app's state; the window manager ignores the request
+    /// from other apps.
+    pub fn clear_shared_state(&self, app_id: ApplicationId) {
+        self.sender.call(AppCommand::ClearSharedState { app_id });
+    }
+
+    /// Returns the app-shared state under `key` without tracking it.
+    pub fn load_shared_state(&self, key: &str) -> Option<Value> {
+        self.shared
+            .try_with_untracked(|shared| shared.get(key.trim()).cloned())
+            .flatten()
+    }
+
+    /// Returns a signal of the app-shared state under `key`.
+    ///
+    /// It updates whenever any window of the app persists the key, including this one.
+    pub fn watch_shared_state(&self, key: impl Into<String>) -> Signal<Option<Value>> {
+        let shared = self.shared;
+        let key = key.into().trim().to_string();
+        Signal::derive(move || shared.with(|shared| shared.get(&key).cloned()))
+    }
+}
+
+#[derive(Clone)]
+/// Namespaced app config service.
+pub struct ConfigService {
+    sender: Callback<AppCommand>,
+    prefs: Rc<dyn PrefsStore>,
+}
+
+impl ConfigService {
+    fn pref_key(namespace: &str, key: &str) -> String {
+        format!("{namespace}.{key}")
+    }
+
+    /// Loads a typed namespaced config value from the runtime-selected host preference store.
+    pub async fn load<T: serde::de::DeserializeOwned>(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<T>, String> {
+        load_pref_with(self.prefs.as_ref(), &Self::pref_key(namespace, key)).await
+    }
+
+    /// Saves a namespaced config key/value payload.
+    pub fn save(&self, namespace: impl Into<String>, key: impl Into<String>, value: Value) {
+        let namespace = namespace.into();
+        let key = key.into();
+        self.sender.call(AppCommand::SaveConfig {
+            namespace,
+            key,
+            value,
+        });
+    }
+}
+
+#[derive(Clone)]
+/// Typed app-state persistence service backed by the runtime-selected host strategy.
 pub struct AppStateHostService {
     store: Rc<dyn AppStateStore>,
 }
@@ -566,6 +1664,16 @@ impl AppStateHostService {
     ) -> Result<(), String> {
         save_app_state_with(self.store.as_ref(), namespace, schema_version, payload).await
     }
+
+    /// Deletes the app state stored under `namespace`.
+    pub async fn delete(&self, namespace: &str) -> Result<(), String> {
+        self.store.delete_app_state(namespace).await
+    }
+
+    /// Reports the stored size of every app-state namespace.
+    pub async fn usage(&self) -> Result<Vec<StorageUsageEntry>, String> {
+        self.store.app_state_usage().await
+    }
 }
 
 #[derive(Clone)]
@@ -597,6 +1705,11 @@ impl PrefsHostService {
     pub async fn delete(&self, key: &str) -> Result<(), String> {
         self.store.delete_pref(key).await
     }
+
+    /// Reports the stored size of every preference key.
+    pub async fn usage(&self) -> Result<Vec<StorageUsageEntry>, String> {
+        self.store.prefs_usage().await
+    }
 }
 
 #[derive(Clone)]
@@ -621,6 +1734,14 @@ impl ExplorerHostService {
         self.service.pick_native_directory().await
     }
 
+    /// Removes the native folder mounted at `path`.
+    pub async fn unmount_native_directory(
+        &self,
+        path: &str,
+    ) -> Result<ExplorerBackendStatus, String> {
+        self.service.unmount_native_directory(path).await
+    }
+
     /// Requests backend permissions.
     pub async fn request_permission(
         &self,
@@ -634,11 +1755,36 @@ impl ExplorerHostService {
         self.service.list_dir(path).await
     }
 
+    /// Lists up to `limit` entries of a directory starting at `offset`.
+    pub async fn list_dir_page(
+        &self,
+        path: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<ExplorerListPage, String> {
+        self.service.list_dir_page(path, offset, limit).await
+    }
+
     /// Reads a text file.
     pub async fn read_text_file(&self, path: &str) -> Result<ExplorerFileReadResult, String> {
         self.service.read_text_file(path).await
     }
 
+    /// Reads a file's raw bytes.
+    pub async fn read_file_bytes(&self, path: &str) -> Result<ExplorerFileBytesResult, String> {
+        self.service.read_file_bytes(path).await
+    }
+
+    /// Reads up to `len` bytes of a file starting at `offset`.
+    pub async fn read_file_chunk(
+        &self,
+        path: &str,
+        offset: u64,
+        len: usize,
+    ) -> Result<ExplorerFileChunk, String> {
+        self.service.read_file_chunk(path, offset, len).await
+    }
+
     /// Writes a text file.
     pub async fn write_text_file(
         &self,
@@ -648,6 +1794,45 @@ impl ExplorerHostService {
         self.service.write_text_file(path, text).await
     }
 
+    /// Writes a file's raw bytes, replacing any existing file.
+    pub async fn write_file_bytes(
+        &self,
+        path: &str,
+        bytes: &[u8],
+    ) -> Result<ExplorerMetadata, String> {
+        self.service.write_file_bytes(path, bytes).await
+    }
+
+    /// Writes `bytes` at `offset`, replacing the file at offset 0 and appending otherwise.
+    pub async fn write_file_chunk(
+        &self,
+        path: &str,
+        offset: u64,
+        bytes: &[u8],
+    ) -> Result<ExplorerMetadata, String> {
+        self.service.write_file_chunk(path, offset, bytes).await
+    }
+
+    /// Streams a file's bytes in bounded chunks.
+    pub fn read_stream<'a>(&'a self, path: &'a str) -> ExplorerReadStream<'a> {
+        self.service.read_stream(path)
+    }
+
+    /// Streams bytes into a file in bounded chunks, replacing any existing file.
+    pub fn write_stream<'a>(&'a self, path: &'a str) -> ExplorerWriteStream<'a> {
+        self.service.write_stream(path)
+    }
+
+    /// Opens the host file picker; an empty list means the picker was cancelled.
+    pub async fn pick_import_files(&self) -> Result<Vec<ExplorerImportFile>, String> {
+        self.service.pick_import_files().await
+    }
+
+    /// Saves `bytes` to the host machine as a file called `name`.
+    pub async fn save_download(&self, name: &str, bytes: &[u8]) -> Result<(), String> {
+        self.service.save_download(name, bytes).await
+    }
+
     /// Creates a directory.
     pub async fn create_dir(&self, path: &str) -> Result<ExplorerMetadata, String> {
         self.service.create_dir(path).await
@@ -663,10 +1848,101 @@ impl ExplorerHostService {
         self.service.delete(path, recursive).await
     }
 
+    /// Renames or moves a path without replacing an existing entry.
+    pub async fn rename(&self, from: &str, to: &str) -> Result<ExplorerMetadata, String> {
+        self.service.rename(from, to).await
+    }
+
+    /// Applies several changes in one host call, reporting each operation's outcome in order.
+    pub async fn batch(&self, ops: &[ExplorerBatchOp]) -> Result<Vec<ExplorerBatchResult>, String> {
+        self.service.batch(ops).await
+    }
+
     /// Retrieves metadata for a path.
     pub async fn stat(&self, path: &str) -> Result<ExplorerMetadata, String> {
         self.service.stat(path).await
     }
+
+    /// Reports storage used by the active backend and the host's quota.
+    pub async fn usage(&self) -> Result<ExplorerStorageUsage, String> {
+        self.service.usage().await
+    }
+
+    /// Asks the host to keep stored files through storage pressure, returning whether it will.
+    pub async fn request_persistent_storage(&self) -> Result<bool, String> {
+        self.service.request_persistent_storage().await
+    }
+
+    /// Calls `on_change` with each changed path that concerns `path` until the watch is dropped.
+    pub fn watch(&self, path: &str, on_change: impl Fn(&str) + 'static) -> ExplorerWatch {
+        self.service.watch(path, Rc::new(on_change))
+    }
+
+    /// Moves a path into the trash so it can be restored later.
+    pub async fn move_to_trash(&self, path: &str) -> Result<ExplorerTrashEntry, String> {
+        move_to_trash_with(self.service.as_ref(), path).await
+    }
+
+    /// Lists trashed entries, most recently deleted first.
+    pub async fn list_trash(&self) -> Result<Vec<ExplorerTrashEntry>, String> {
+        list_trash_with(self.service.as_ref()).await
+    }
+
+    /// Restores a trashed entry to its original path.
+    pub async fn restore_from_trash(&self, id: &str) -> Result<ExplorerMetadata, String> {
+        restore_from_trash_with(self.service.as_ref(), id).await
+    }
+
+    /// Permanently deletes one trashed entry.
+    pub async fn delete_from_trash(&self, id: &str) -> Result<(), String> {
+        delete_from_trash_with(self.service.as_ref(), id).await
+    }
+
+    /// Permanently deletes everything in the trash, returning the number of entries removed.
+    pub async fn empty_trash(&self) -> Result<usize, String> {
+        empty_trash_with(self.service.as_ref()).await
+    }
+
+    /// Adds up the sizes of every file below a folder.
+    pub async fn folder_size(&self, path: &str) -> Result<ExplorerFolderSize, String> {
+        folder_size_with(self.service.as_ref(), path).await
+    }
+
+    /// Sizes every folder directly inside the root, largest first.
+    pub async fn top_level_folder_sizes(
+        &self,
+    ) -> Result<Vec<(String, ExplorerFolderSize)>, String> {
+        top_level_folder_sizes_with(self.service.as_ref()).await
+    }
+
+    /// Computes a file's SHA-256 digest as lowercase hex, reporting bytes hashed and file size.
+    pub async fn sha256_file(
+        &self,
+        path: &str,
+        on_progress: impl FnMut(u64, u64),
+    ) -> Result<String, String> {
+        sha256_file_with(self.service.as_ref(), path, on_progress).await
+    }
+
+    /// Packs a folder or file into ZIP archive bytes, reporting files packed and the total.
+    pub async fn export_zip(
+        &self,
+        path: &str,
+        on_progress: impl FnMut(usize, usize),
+    ) -> Result<Vec<u8>, String> {
+        export_zip_with(self.service.as_ref(), path, on_progress).await
+    }
+
+    /// Extracts ZIP archive bytes into a folder, reporting files extracted and the total.
+    pub async fn import_zip(
+        &self,
+        dest: &str,
+        bytes: &[u8],
+        conflict: ExplorerArchiveConflict,
+        on_progress: impl FnMut(usize, usize),
+    ) -> Result<ExplorerArchiveImport, String> {
+        import_zip_with(self.service.as_ref(), dest, bytes, conflict, on_progress).await
+    }
 }
 
 #[derive(Clone)]
@@ -695,23 +1971,300 @@ impl CacheHostService {
     pub async fn delete(&self, cache_name: &str, key: &str) -> Result<(), String> {
         self.cache.delete(cache_name, key).await
     }
+
+    /// Reports entry counts and stored size per cache name.
+    pub async fn usage(&self) -> Result<Vec<StorageUsageEntry>, String> {
+        self.cache.cache_usage().await
+    }
+
+    /// Deletes every entry stored under `cache_name`.
+    pub async fn clear(&self, cache_name: &str) -> Result<(), String> {
+        self.cache.clear_cache(cache_name).await
+    }
 }
 
-#[derive(Clone, Copy)]
-/// Theme service for shell appearance/accessibility actions.
-pub struct ThemeService {
-    sender: Callback<AppCommand>,
-    /// Current shell skin id.
-    pub skin_id: ReadSignal<String>,
-    /// Current high-contrast flag.
-    pub high_contrast: ReadSignal<bool>,
-    /// Current reduced-motion flag.
-    pub reduced_motion: ReadSignal<bool>,
+#[derive(Clone)]
+/// Clipboard service backed by the runtime-selected host strategy.
+pub struct ClipboardHostService {
+    service: Rc<dyn ClipboardService>,
 }
 
-impl ThemeService {
-    /// Requests shell skin change by id.
-    pub fn set_skin(&self, skin_id: impl Into<String>) {
+impl ClipboardHostService {
+    /// Creates a clipboard host service from a concrete adapter object.
+    pub fn new(service: Rc<dyn ClipboardService>) -> Self {
+        Self { service }
+    }
+
+    /// Replaces the clipboard contents with plain text.
+    pub async fn write_text(&self, text: &str) -> Result<(), String> {
+        self.service.write_text(text).await
+    }
+}
+
+/// Largest response body, in bytes, [`FetchPolicy`] accepts unless configured otherwise.
+pub const FETCH_DEFAULT_MAX_RESPONSE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Time after which [`FetchPolicy`] aborts requests unless configured otherwise.
+pub const FETCH_DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Why a [`FetchService`] request failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The app lacks the network capability or the host cannot make requests.
+    Unavailable,
+    /// The URL is not an absolute `http` or `https` URL.
+    InvalidUrl {
+        /// Requested URL.
+        url: String,
+    },
+    /// The URL is outside the app's network allowlist.
+    NotAllowed {
+        /// Requested URL.
+        url: String,
+    },
+    /// The response body exceeded the policy's size limit.
+    TooLarge {
+        /// Size limit in bytes.
+        limit_bytes: u64,
+    },
+    /// The host failed the request, timed it out, or the response was unusable.
+    Failed {
+        /// Failure description.
+        message: String,
+    },
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unavailable => write!(f, "network access is unavailable"),
+            Self::InvalidUrl { url } => write!(f, "`{url}` is not an http or https URL"),
+            Self::NotAllowed { url } => write!(f, "`{url}` is not on the app's network allowlist"),
+            Self::TooLarge { limit_bytes } => {
+                write!(f, "response exceeded the {limit_bytes} byte limit")
+            }
+            Self::Failed { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Per-app network policy enforced by [`FetchService`].
+///
+/// Allowlist entries are URL prefixes such as `https://api.example.com` or
+/// `https://example.com/packages/`. An entry matches a URL that equals it or continues it with a
+/// path, query, or fragment, so `https://api.example.com` does not match
+/// `https://api.example.com.evil.test`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchPolicy {
+    allowlist: Vec<String>,
+    max_response_bytes: u64,
+    timeout: Duration,
+}
+
+impl FetchPolicy {
+    /// Creates a policy for `allowlist` with the default size and time limits.
+    pub fn new(allowlist: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            allowlist: allowlist.into_iter().map(Into::into).collect(),
+            max_response_bytes: FETCH_DEFAULT_MAX_RESPONSE_BYTES,
+            timeout: FETCH_DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Replaces the response size limit.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: u64) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// Replaces the request time limit.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the allowlisted URL prefixes.
+    pub fn allowlist(&self) -> &[String] {
+        &self.allowlist
+    }
+
+    /// Checks that `url` is an `http` or `https` URL covered by the allowlist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::InvalidUrl`] or [`FetchError::NotAllowed`].
+    pub fn check(&self, url: &str) -> Result<(), FetchError> {
+        let host = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"));
+        if !host.is_some_and(|host| !host.is_empty() && !host.starts_with(['/', '?', '#'])) {
+            return Err(FetchError::InvalidUrl {
+                url: url.to_string(),
+            });
+        }
+        let allowed = self.allowlist.iter().any(|entry| {
+            url.strip_prefix(entry.as_str()).is_some_and(|rest| {
+                entry.ends_with('/') || rest.is_empty() || rest.starts_with(['/', '?', '#'])
+            })
+        });
+        if allowed {
+            Ok(())
+        } else {
+            Err(FetchError::NotAllowed {
+                url: url.to_string(),
+            })
+        }
+    }
+}
+
+#[derive(Clone)]
+/// HTTP service for apps granted [`AppCapability::Network`].
+///
+/// Requests go through the host's HTTP adapter under the app's [`FetchPolicy`]: URLs outside the
+/// allowlist are refused before anything is sent, and the host aborts requests that run past the
+/// time limit or whose response grows past the size limit.
+pub struct FetchService {
+    http: Rc<dyn HttpService>,
+    policy: Option<FetchPolicy>,
+    capabilities: Option<ReadSignal<CapabilitySet>>,
+}
+
+impl FetchService {
+    /// Creates a fetch service that sends requests allowed by `policy` through `http`.
+    pub fn new(http: Rc<dyn HttpService>, policy: FetchPolicy) -> Self {
+        Self {
+            http,
+            policy: Some(policy),
+            capabilities: None,
+        }
+    }
+
+    /// Creates a fetch service that only sends requests while `capabilities` allows
+    /// [`AppCapability::Network`], so a grant made after mount takes effect immediately.
+    fn gated(
+        http: Rc<dyn HttpService>,
+        policy: FetchPolicy,
+        capabilities: ReadSignal<CapabilitySet>,
+    ) -> Self {
+        Self {
+            capabilities: Some(capabilities),
+            ..Self::new(http, policy)
+        }
+    }
+
+    /// Creates a fetch service whose requests all fail with [`FetchError::Unavailable`].
+    pub fn disabled() -> Self {
+        Self {
+            http: Rc::new(NoopHttpService),
+            policy: None,
+            capabilities: None,
+        }
+    }
+
+    /// Returns the enforced policy, or `None` when network access is unavailable.
+    pub fn policy(&self) -> Option<&FetchPolicy> {
+        let allowed = self.capabilities.is_none_or(|capabilities| {
+            capabilities
+                .try_with_untracked(|capabilities| capabilities.can_use(AppCapability::Network))
+                .unwrap_or(false)
+        });
+        self.policy.as_ref().filter(|_| allowed)
+    }
+
+    /// Sends a `GET` request to `url`.
+    ///
+    /// Responses with non-success statuses are returned as-is.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FetchError`] when the policy refuses the URL or the request fails.
+    pub async fn get(&self, url: &str) -> Result<HttpResponse, FetchError> {
+        self.send(HttpMethod::Get, url, Vec::new(), None).await
+    }
+
+    /// Sends a `POST` request with a `content_type` body to `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FetchError`] when the policy refuses the URL or the request fails.
+    pub async fn post(
+        &self,
+        url: &str,
+        content_type: &str,
+        body: impl Into<String>,
+    ) -> Result<HttpResponse, FetchError> {
+        let headers = vec![("content-type".to_string(), content_type.to_string())];
+        self.send(HttpMethod::Post, url, headers, Some(body.into()))
+            .await
+    }
+
+    /// Sends a `GET` request to `url` and decodes a successful JSON response as `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FetchError::Failed`] for non-success statuses and bodies that do not decode, in
+    /// addition to the errors of [`FetchService::get`].
+    pub async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, FetchError> {
+        let response = self.get(url).await?;
+        if !response.is_success() {
+            return Err(FetchError::Failed {
+                message: format!("HTTP {} from {url}", response.status),
+            });
+        }
+        serde_json::from_str(&response.body).map_err(|err| FetchError::Failed {
+            message: err.to_string(),
+        })
+    }
+
+    async fn send(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        headers: Vec<(String, String)>,
+        body: Option<String>,
+    ) -> Result<HttpResponse, FetchError> {
+        let policy = self.policy().ok_or(FetchError::Unavailable)?;
+        policy.check(url)?;
+        let request = HttpRequest {
+            method,
+            url: url.to_string(),
+            headers,
+            body,
+            max_response_bytes: policy.max_response_bytes,
+            timeout_ms: policy.timeout.as_millis() as u64,
+        };
+        let response = self
+            .http
+            .send(&request)
+            .await
+            .map_err(|message| FetchError::Failed { message })?;
+        // Hosts abort oversized downloads themselves; this also covers hosts that cannot.
+        if response.body.len() as u64 > policy.max_response_bytes {
+            return Err(FetchError::TooLarge {
+                limit_bytes: policy.max_response_bytes,
+            });
+        }
+        Ok(response)
+    }
+}
+
+#[derive(Clone, Copy)]
+/// Theme service for shell appearance/accessibility actions.
+pub struct ThemeService {
+    sender: Callback<AppCommand>,
+    /// Current shell skin id.
+    pub skin_id: ReadSignal<String>,
+    /// Current high-contrast flag.
+    pub high_contrast: ReadSignal<bool>,
+    /// Current reduced-motion flag.
+    pub reduced_motion: ReadSignal<bool>,
+}
+
+impl ThemeService {
+    /// Requests shell skin change by id.
+    pub fn set_skin(&self, skin_id: impl Into<String>) {
         self.sender.call(AppCommand::SetDesktopSkin {
             skin_id: skin_id.into(),
         });
@@ -837,151 +2390,1208 @@ impl WallpaperService {
 
 #[derive(Clone, Copy)]
 /// Notification service routed through host capabilities.
+///
+/// The window manager applies the posting app's [`NotificationDelivery`] before honoring
+/// [`NotificationService::notify`]. Only privileged apps may change delivery modes; the window
+/// manager ignores requests from other apps.
 pub struct NotificationService {
     sender: Callback<AppCommand>,
+    /// Delivery modes users changed from the default, ordered by app id.
+    pub preferences: ReadSignal<Vec<NotificationPreference>>,
 }
 
 impl NotificationService {
-    /// Emits a host notification request.
-    pub fn notify(&self, title: impl Into<String>, body: impl Into<String>) {
-        self.sender.call(AppCommand::Notify {
-            title: title.into(),
-            body: body.into(),
-        });
+    /// Posts `notification` to the notification center and as a host notification.
+    pub fn notify(&self, notification: Notification) {
+        self.sender.call(AppCommand::Notify { notification });
     }
-}
-
-#[derive(Clone, Copy)]
-/// Inter-app IPC service for topic subscriptions and pub/sub request-reply envelopes.
-pub struct IpcService {
-    sender: Callback<AppCommand>,
-}
 
-impl IpcService {
-    /// Subscribes this window to a topic.
-    pub fn subscribe(&self, topic: impl Into<String>) {
-        self.sender.call(AppCommand::Subscribe {
-            topic: topic.into(),
-        });
+    /// Returns how `app_id`'s notifications are delivered.
+    pub fn delivery(&self, app_id: &ApplicationId) -> NotificationDelivery {
+        self.preferences.with(|preferences| {
+            preferences
+                .iter()
+                .find(|preference| preference.app_id == *app_id)
+                .map_or_else(NotificationDelivery::default, |preference| {
+                    preference.delivery
+                })
+        })
     }
 
-    /// Unsubscribes this window from a topic.
-    pub fn unsubscribe(&self, topic: impl Into<String>) {
-        self.sender.call(AppCommand::Unsubscribe {
-            topic: topic.into(),
-        });
+    /// Changes how `app_id`'s notifications are delivered.
+    pub fn set_delivery(&self, app_id: ApplicationId, delivery: NotificationDelivery) {
+        self.sender
+            .call(AppCommand::SetNotificationDelivery { app_id, delivery });
     }
+}
 
-    /// Publishes a one-way event payload.
-    pub fn publish(&self, topic: impl Into<String>, payload: Value) {
-        self.sender.call(AppCommand::PublishEvent {
-            topic: topic.into(),
-            payload,
-            correlation_id: None,
-            reply_to: None,
-        });
+/// How the window manager delivers an app's notifications.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationDelivery {
+    /// Post to the notification center and show a host notification banner.
+    #[default]
+    Banner,
+    /// Post to the notification center without a banner.
+    Silent,
+    /// Drop the app's notifications.
+    Muted,
+}
+
+impl NotificationDelivery {
+    /// Every delivery mode, loudest first.
+    pub const ALL: [Self; 3] = [Self::Banner, Self::Silent, Self::Muted];
+
+    /// Returns the kebab-case id used in persisted preferences.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Banner => "banner",
+            Self::Silent => "silent",
+            Self::Muted => "muted",
+        }
     }
 
-    /// Publishes a request payload carrying correlation metadata.
-    pub fn request(
-        &self,
-        topic: impl Into<String>,
-        payload: Value,
-        correlation_id: impl Into<String>,
-        reply_to: impl Into<String>,
-    ) {
-        self.sender.call(AppCommand::PublishEvent {
-            topic: topic.into(),
-            payload,
-            correlation_id: Some(correlation_id.into()),
-            reply_to: Some(reply_to.into()),
-        });
+    /// Parses an id as returned by [`NotificationDelivery::as_str`].
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|delivery| delivery.as_str() == id)
     }
 }
 
-/// Async completion provider used by command registrations.
-pub type AppCommandCompletion = Rc<
-    dyn Fn(CompletionRequest) -> LocalBoxFuture<'static, Result<Vec<CompletionItem>, ShellError>>,
->;
+/// Notification delivery mode a user chose for an app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreference {
+    /// App the mode applies to.
+    pub app_id: ApplicationId,
+    /// Chosen delivery mode.
+    pub delivery: NotificationDelivery,
+}
 
-/// Async command handler used by app command registrations.
-pub type AppCommandHandler =
-    Rc<dyn Fn(AppCommandContext) -> LocalBoxFuture<'static, Result<CommandResult, ShellError>>>;
+/// Most action buttons a notification shows; extra actions are dropped.
+pub const NOTIFICATION_MAX_ACTIONS: usize = 3;
 
-/// Execution context supplied to app-registered command handlers.
-#[derive(Clone)]
-pub struct AppCommandContext {
-    /// Execution identifier for the current command.
-    pub execution_id: ExecutionId,
-    /// Parsed invocation metadata for the current pipeline stage.
-    pub invocation: ParsedInvocation,
-    /// Full parsed argv payload.
-    pub argv: Vec<String>,
-    /// Positional argument tokens after command-path resolution.
-    pub args: Vec<String>,
-    /// Current logical cwd.
-    pub cwd: String,
-    /// Structured input passed from the previous pipeline stage.
-    pub input: StructuredData,
-    /// Optional source window identifier.
-    pub source_window_id: Option<WindowRuntimeId>,
-    emit: Rc<dyn Fn(ShellStreamEvent)>,
-    set_cwd: Rc<dyn Fn(String)>,
-    is_cancelled: Rc<dyn Fn() -> bool>,
+/// App window a notification opens, with the launch parameters it is opened with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationTarget {
+    /// App to open or focus.
+    pub app_id: ApplicationId,
+    /// Launch parameters passed to the app when a new window is opened.
+    #[serde(default)]
+    pub launch_params: Value,
+    /// Link routed through the app's link handler instead, when set.
+    #[serde(default)]
+    pub link: Option<AppLink>,
 }
 
-type ShellEventEmitter = Rc<dyn Fn(ShellStreamEvent)>;
-type ShellCwdSetter = Rc<dyn Fn(String)>;
-type CancellationProbe = Rc<dyn Fn() -> bool>;
-type ShellSessionFactory = Rc<dyn Fn(String) -> Result<ShellSessionHandle, String>>;
-type CommandRegistrar =
-    Rc<dyn Fn(AppCommandRegistration) -> Result<CommandRegistrationHandle, String>>;
-type ProviderRegistrar =
-    Rc<dyn Fn(Rc<dyn AppCommandProvider>) -> Result<CommandRegistrationHandle, String>>;
-
-impl AppCommandContext {
-    /// Emits an informational notice for the current execution.
-    pub fn info(&self, message: impl Into<String>) {
-        self.notice(CommandNoticeLevel::Info, message);
+impl NotificationTarget {
+    /// Creates a target that opens `app_id` with `launch_params`.
+    pub fn new(app_id: ApplicationId, launch_params: Value) -> Self {
+        Self {
+            app_id,
+            launch_params,
+            link: None,
+        }
     }
 
-    /// Emits a warning notice for the current execution.
-    pub fn warn(&self, message: impl Into<String>) {
-        self.notice(CommandNoticeLevel::Warning, message);
+    /// Creates a target that opens `link` as [`LauncherService::open_link`] does.
+    pub fn link(link: AppLink) -> Self {
+        Self {
+            app_id: link.app_id.clone(),
+            launch_params: link.launch_params(),
+            link: Some(link),
+        }
     }
+}
 
-    /// Emits an error notice for the current execution.
-    pub fn error(&self, message: impl Into<String>) {
-        self.notice(CommandNoticeLevel::Error, message);
+/// Button shown on a notification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationAction {
+    /// Button label.
+    pub label: String,
+    /// Window the button opens.
+    pub target: NotificationTarget,
+}
+
+/// Notification an app posts through [`NotificationService`].
+///
+/// Clicking it opens `on_click`, or focuses the app that posted it when there is none.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    /// Notification title.
+    pub title: String,
+    /// Notification body.
+    pub body: String,
+    /// Icon token, such as `terminal`; the posting app's icon when unset.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Action buttons, at most [`NOTIFICATION_MAX_ACTIONS`].
+    #[serde(default)]
+    pub actions: Vec<NotificationAction>,
+    /// Window opened when the notification itself is clicked.
+    #[serde(default)]
+    pub on_click: Option<NotificationTarget>,
+}
+
+impl Notification {
+    /// Creates a notification with a title and body.
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            icon: None,
+            actions: Vec::new(),
+            on_click: None,
+        }
     }
 
-    /// Emits a structured notice for the current execution.
-    pub fn notice(&self, level: CommandNoticeLevel, message: impl Into<String>) {
-        self.emit(ShellStreamEvent::Notice {
-            execution_id: self.execution_id,
-            notice: CommandNotice {
-                level,
-                message: message.into(),
-            },
-        });
+    /// Shows the icon named by `icon` instead of the app's icon.
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
     }
 
-    /// Emits a progress update for the current execution.
-    pub fn progress(&self, value: Option<f32>, label: Option<String>) {
-        self.emit(ShellStreamEvent::Progress {
-            execution_id: self.execution_id,
-            value,
-            label,
+    /// Adds a button labelled `label` that opens `target`.
+    pub fn with_action(mut self, label: impl Into<String>, target: NotificationTarget) -> Self {
+        self.actions.push(NotificationAction {
+            label: label.into(),
+            target,
         });
+        self
     }
 
-    /// Emits a structured data frame for the current execution.
-    pub fn data(&self, data: StructuredData, display: DisplayPreference) {
-        self.emit(ShellStreamEvent::Data {
-            execution_id: self.execution_id,
-            data,
-            display,
-        });
+    /// Opens `target` when the notification is clicked.
+    pub fn with_on_click(mut self, target: NotificationTarget) -> Self {
+        self.on_click = Some(target);
+        self
+    }
+}
+
+/// Dialog an app asks the window manager to show over its window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogRequest {
+    /// Question answered by confirming or cancelling.
+    Confirm {
+        /// Dialog title.
+        title: String,
+        /// Question or consequence shown under the title.
+        message: String,
+        /// Label of the confirming button.
+        confirm_label: String,
+        /// Whether confirming destroys data, which marks the confirming button as dangerous.
+        destructive: bool,
+    },
+    /// Single line of text input.
+    Prompt {
+        /// Dialog title.
+        title: String,
+        /// Explanation shown above the input.
+        message: String,
+        /// Text the input starts with.
+        value: String,
+    },
+    /// Picks an existing file.
+    OpenFile {
+        /// Dialog title.
+        title: String,
+        /// Folder the picker starts in.
+        folder: String,
+    },
+    /// Picks the path to save a file to.
+    SaveFile {
+        /// Dialog title.
+        title: String,
+        /// Folder the picker starts in.
+        folder: String,
+        /// File name the name input starts with.
+        file_name: String,
+    },
+}
+
+/// How the user answered a [`DialogRequest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialogResponse {
+    /// The dialog was cancelled or dismissed.
+    Cancelled,
+    /// A confirmation was accepted.
+    Confirmed,
+    /// Text entered into a prompt.
+    Text(String),
+    /// Virtual path picked in a file dialog.
+    Path(String),
+}
+
+/// Asks the user to grant a capability and resolves with its status once they answer.
+pub type CapabilityRequester =
+    Rc<dyn Fn(AppCapability) -> LocalBoxFuture<'static, CapabilityStatus>>;
+
+/// Shows a dialog and resolves once the user answers it.
+pub type DialogPresenter = Rc<dyn Fn(DialogRequest) -> LocalBoxFuture<'static, DialogResponse>>;
+
+#[derive(Clone)]
+/// Modal dialog service rendered by the window manager over the app's window.
+///
+/// A window shows one dialog at a time; asking for another cancels the one already showing.
+pub struct DialogService {
+    present: DialogPresenter,
+}
+
+impl DialogService {
+    /// Creates a dialog service from the runtime presenter.
+    pub fn new(present: DialogPresenter) -> Self {
+        Self { present }
+    }
+
+    /// Creates a dialog service whose dialogs all resolve as cancelled.
+    pub fn disabled() -> Self {
+        Self::new(Rc::new(|_| Box::pin(async { DialogResponse::Cancelled })))
+    }
+
+    /// Shows `request` and returns how the user answered it.
+    pub async fn show(&self, request: DialogRequest) -> DialogResponse {
+        (self.present)(request).await
+    }
+
+    /// Asks a question, returning `true` when the user confirms.
+    pub async fn confirm(&self, title: impl Into<String>, message: impl Into<String>) -> bool {
+        let request = DialogRequest::Confirm {
+            title: title.into(),
+            message: message.into(),
+            confirm_label: "OK".to_string(),
+            destructive: false,
+        };
+        self.show(request).await == DialogResponse::Confirmed
+    }
+
+    /// Asks before an action that destroys data, naming the action on the confirming button.
+    pub async fn confirm_destructive(
+        &self,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        confirm_label: impl Into<String>,
+    ) -> bool {
+        let request = DialogRequest::Confirm {
+            title: title.into(),
+            message: message.into(),
+            confirm_label: confirm_label.into(),
+            destructive: true,
+        };
+        self.show(request).await == DialogResponse::Confirmed
+    }
+
+    /// Asks for a line of text starting from `value`; `None` when cancelled.
+    pub async fn prompt(
+        &self,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        let request = DialogRequest::Prompt {
+            title: title.into(),
+            message: message.into(),
+            value: value.into(),
+        };
+        match self.show(request).await {
+            DialogResponse::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Picks an existing file, browsing from `folder`; `None` when cancelled.
+    pub async fn open_file(
+        &self,
+        title: impl Into<String>,
+        folder: impl Into<String>,
+    ) -> Option<String> {
+        let request = DialogRequest::OpenFile {
+            title: title.into(),
+            folder: folder.into(),
+        };
+        match self.show(request).await {
+            DialogResponse::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Picks a path to save to, browsing from `folder` with `file_name` filled in; `None` when
+    /// cancelled.
+    pub async fn save_file(
+        &self,
+        title: impl Into<String>,
+        folder: impl Into<String>,
+        file_name: impl Into<String>,
+    ) -> Option<String> {
+        let request = DialogRequest::SaveFile {
+            title: title.into(),
+            folder: folder.into(),
+            file_name: file_name.into(),
+        };
+        match self.show(request).await {
+            DialogResponse::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// Clears a timer started through [`TaskTimers`].
+type TimerCancel = Box<dyn FnOnce()>;
+
+type StartTimeout = Rc<dyn Fn(Box<dyn FnOnce()>, Duration) -> Option<TimerCancel>>;
+
+type StartInterval = Rc<dyn Fn(Box<dyn Fn()>, Duration) -> Option<TimerCancel>>;
+
+/// Starts the timers behind a [`TaskService`]: browser timers, unless a test swaps them out.
+#[derive(Clone)]
+struct TaskTimers {
+    timeout: StartTimeout,
+    interval: StartInterval,
+}
+
+impl Default for TaskTimers {
+    fn default() -> Self {
+        Self {
+            timeout: Rc::new(|callback, delay| {
+                set_timeout_with_handle(callback, delay)
+                    .ok()
+                    .map(|timer| Box::new(move || timer.clear()) as TimerCancel)
+            }),
+            interval: Rc::new(|callback, period| {
+                set_interval_with_handle(callback, period)
+                    .ok()
+                    .map(|timer| Box::new(move || timer.clear()) as TimerCancel)
+            }),
+        }
+    }
+}
+
+/// Timer or background task owned by a [`TaskService`].
+enum Task {
+    /// One-shot callback; `due` marks one whose timer fired while the window was suspended.
+    Timeout {
+        timer: Option<TimerCancel>,
+        callback: Option<Box<dyn FnOnce()>>,
+        due: bool,
+    },
+    /// Repeating callback whose timer is stopped while the window is suspended.
+    Interval {
+        callback: Rc<dyn Fn()>,
+        period: Duration,
+        timer: Option<TimerCancel>,
+    },
+    /// Spawned future, held at its next poll while the window is suspended.
+    Background { abort: AbortHandle },
+}
+
+impl Task {
+    fn stop(self) {
+        match self {
+            Self::Timeout { timer, .. } | Self::Interval { timer, .. } => {
+                if let Some(cancel) = timer {
+                    cancel();
+                }
+            }
+            Self::Background { abort } => abort.abort(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TaskRegistry {
+    timers: TaskTimers,
+    next_id: u64,
+    suspended: bool,
+    tasks: HashMap<u64, Task>,
+    /// Background tasks waiting for the window to resume.
+    parked: Vec<Waker>,
+}
+
+impl TaskRegistry {
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+}
+
+/// Future that makes no progress while its window is suspended.
+struct Suspendable {
+    registry: StoredValue<TaskRegistry>,
+    task: LocalBoxFuture<'static, ()>,
+}
+
+impl Future for Suspendable {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let suspended = self.registry.try_update_value(|registry| {
+            if registry.suspended {
+                registry.parked.push(cx.waker().clone());
+            }
+            registry.suspended
+        });
+        match suspended {
+            // The window has closed.
+            None => Poll::Ready(()),
+            Some(true) => Poll::Pending,
+            Some(false) => self.task.as_mut().poll(cx),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+/// Handle to a timer or background task started through [`TaskService`].
+pub struct TaskHandle {
+    id: u64,
+    registry: StoredValue<TaskRegistry>,
+}
+
+impl TaskHandle {
+    /// Stops the task; does nothing once it has finished or was cancelled.
+    pub fn cancel(&self) {
+        if let Some(task) = self
+            .registry
+            .try_update_value(|registry| registry.tasks.remove(&self.id))
+            .flatten()
+        {
+            task.stop();
+        }
+    }
+
+    /// Returns whether the task is still scheduled or running.
+    pub fn is_active(&self) -> bool {
+        self.registry
+            .try_update_value(|registry| registry.tasks.contains_key(&self.id))
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Clone, Copy)]
+/// Timers and background tasks tied to the app's window.
+///
+/// The window manager pauses everything started here while the window is suspended and cancels
+/// it when the window closes. Intervals skip the suspended stretch, timeouts that come due run on
+/// resume, and background tasks wait at their next poll.
+pub struct TaskService {
+    registry: StoredValue<TaskRegistry>,
+}
+
+impl Default for TaskService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskService {
+    /// Creates a task service with no running tasks.
+    pub fn new() -> Self {
+        Self {
+            registry: store_value(TaskRegistry::default()),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_timers(timers: TaskTimers) -> Self {
+        Self {
+            registry: store_value(TaskRegistry {
+                timers,
+                ..TaskRegistry::default()
+            }),
+        }
+    }
+
+    fn timers(&self) -> TaskTimers {
+        self.registry
+            .try_update_value(|registry| registry.timers.clone())
+            .unwrap_or_default()
+    }
+
+    /// Runs `callback` once after `delay`.
+    pub fn set_timeout(&self, callback: impl FnOnce() + 'static, delay: Duration) -> TaskHandle {
+        let registry = self.registry;
+        let timers = self.timers();
+        let id = registry
+            .try_update_value(TaskRegistry::next_id)
+            .unwrap_or_default();
+        let timer = (timers.timeout)(Box::new(move || fire_timeout(registry, id)), delay);
+        registry.update_value(|registry| {
+            registry.tasks.insert(
+                id,
+                Task::Timeout {
+                    timer,
+                    callback: Some(Box::new(callback)),
+                    due: false,
+                },
+            );
+        });
+        TaskHandle { id, registry }
+    }
+
+    /// Runs `callback` every `period` until cancelled.
+    pub fn set_interval(&self, callback: impl Fn() + 'static, period: Duration) -> TaskHandle {
+        let registry = self.registry;
+        registry
+            .try_update_value(|state| {
+                let id = state.next_id();
+                let timer = (!state.suspended)
+                    .then(|| start_interval(&state.timers, registry, id, period))
+                    .flatten();
+                state.tasks.insert(
+                    id,
+                    Task::Interval {
+                        callback: Rc::new(callback),
+                        period,
+                        timer,
+                    },
+                );
+                TaskHandle { id, registry }
+            })
+            .unwrap_or(TaskHandle { id: 0, registry })
+    }
+
+    /// Runs `task` in the background until it finishes or is cancelled.
+    pub fn spawn(&self, task: impl Future<Output = ()> + 'static) -> TaskHandle {
+        let registry = self.registry;
+        let (task, abort) = abortable(Suspendable {
+            registry,
+            task: Box::pin(task),
+        });
+        let id = registry
+            .try_update_value(|state| {
+                let id = state.next_id();
+                state.tasks.insert(id, Task::Background { abort });
+                id
+            })
+            .unwrap_or_default();
+        spawn_local(async move {
+            let _ = task.await;
+            registry.try_update_value(|registry| registry.tasks.remove(&id));
+        });
+        TaskHandle { id, registry }
+    }
+
+    /// Resolves after `duration`, so background tasks can wait between steps.
+    pub async fn sleep(&self, duration: Duration) {
+        let (done, wait) = oneshot::channel::<()>();
+        (self.timers().timeout)(
+            Box::new(move || {
+                let _ = done.send(());
+            }),
+            duration,
+        );
+        let _ = wait.await;
+    }
+
+    /// Returns whether the window is suspended and its tasks are paused.
+    pub fn is_suspended(&self) -> bool {
+        self.registry
+            .try_update_value(|registry| registry.suspended)
+            .unwrap_or(false)
+    }
+
+    /// Pauses or resumes every task; the window manager calls this on `Suspended` and `Resumed`.
+    pub fn set_suspended(&self, suspended: bool) {
+        let registry = self.registry;
+        let Some((parked, due)) = registry.try_update_value(|state| {
+            if state.suspended == suspended {
+                return (Vec::new(), Vec::new());
+            }
+            state.suspended = suspended;
+            if suspended {
+                for task in state.tasks.values_mut() {
+                    if let Task::Interval { timer, .. } = task {
+                        if let Some(cancel) = timer.take() {
+                            cancel();
+                        }
+                    }
+                }
+                return (Vec::new(), Vec::new());
+            }
+            for (&id, task) in state.tasks.iter_mut() {
+                if let Task::Interval { period, timer, .. } = task {
+                    *timer = start_interval(&state.timers, registry, id, *period);
+                }
+            }
+            let mut due_ids = state
+                .tasks
+                .iter()
+                .filter(|(_, task)| matches!(task, Task::Timeout { due: true, .. }))
+                .map(|(&id, _)| id)
+                .collect::<Vec<_>>();
+            due_ids.sort_unstable();
+            let due = due_ids
+                .into_iter()
+                .filter_map(|id| match state.tasks.remove(&id) {
+                    Some(Task::Timeout { callback, .. }) => callback,
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            (std::mem::take(&mut state.parked), due)
+        }) else {
+            return;
+        };
+        for waker in parked {
+            waker.wake();
+        }
+        for callback in due {
+            callback();
+        }
+    }
+
+    /// Cancels every task; the window manager calls this when the window closes.
+    pub fn cancel_all(&self) {
+        let tasks = self
+            .registry
+            .try_update_value(|registry| {
+                registry.parked.clear();
+                std::mem::take(&mut registry.tasks)
+            })
+            .unwrap_or_default();
+        for task in tasks.into_values() {
+            task.stop();
+        }
+    }
+}
+
+fn fire_timeout(registry: StoredValue<TaskRegistry>, id: u64) {
+    let callback = registry
+        .try_update_value(|registry| {
+            if registry.suspended {
+                if let Some(Task::Timeout { due, .. }) = registry.tasks.get_mut(&id) {
+                    *due = true;
+                }
+                return None;
+            }
+            match registry.tasks.remove(&id) {
+                Some(Task::Timeout { callback, .. }) => callback,
+                _ => None,
+            }
+        })
+        .flatten();
+    if let Some(callback) = callback {
+        callback();
+    }
+}
+
+fn start_interval(
+    timers: &TaskTimers,
+    registry: StoredValue<TaskRegistry>,
+    id: u64,
+    period: Duration,
+) -> Option<TimerCancel> {
+    (timers.interval)(
+        Box::new(move || {
+            let callback = registry
+                .try_update_value(|registry| match registry.tasks.get(&id) {
+                    Some(Task::Interval { callback, .. }) => Some(Rc::clone(callback)),
+                    _ => None,
+                })
+                .flatten();
+            if let Some(callback) = callback {
+                callback();
+            }
+        }),
+        period,
+    )
+}
+
+/// Reply topic [`IpcService::call`] asks responders to answer on.
+pub const IPC_REPLY_TOPIC: &str = "ipc.reply";
+
+/// How long [`IpcService::call`] waits for a reply.
+pub const IPC_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Topic the bus reports rejected one-way publishes on, delivered only to the publishing window.
+///
+/// The payload is the serialized [`IpcError`] and the correlation id is copied from the rejected
+/// event. Rejected requests are answered on their reply topic instead.
+pub const IPC_ERROR_TOPIC: &str = "ipc.error";
+
+/// Topic whose payloads must deserialize as `T`.
+///
+/// Publishers encode through the topic and subscribers decode through it; topics registered in
+/// [`IpcSchemaRegistry`] are also checked by the bus before delivery.
+pub struct IpcTopic<T> {
+    name: &'static str,
+    payload: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for IpcTopic<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for IpcTopic<T> {}
+
+impl<T> std::fmt::Debug for IpcTopic<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("IpcTopic").field(&self.name).finish()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> IpcTopic<T> {
+    /// Declares a typed topic.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            payload: PhantomData,
+        }
+    }
+
+    /// Returns the topic name.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Serializes `payload` for this topic.
+    pub fn encode(&self, payload: &T) -> Result<Value, IpcError> {
+        serde_json::to_value(payload).map_err(|err| self.invalid(err))
+    }
+
+    /// Decodes `event` when it was published on this topic, returning `None` for other topics.
+    pub fn decode(&self, event: &IpcEnvelope) -> Option<Result<T, IpcError>> {
+        (event.topic == self.name)
+            .then(|| T::deserialize(&event.payload).map_err(|err| self.invalid(err)))
+    }
+
+    fn invalid(&self, err: serde_json::Error) -> IpcError {
+        IpcError::InvalidPayload {
+            topic: self.name.to_string(),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Payload of [`EXPLORER_REFRESH_TOPIC`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplorerRefreshEvent {
+    /// Folder to reload, or the open folder when absent.
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// Payload of [`EXPLORER_CWD_CHANGED_TOPIC`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplorerCwdChangedEvent {
+    /// Folder Explorer now shows.
+    pub cwd: String,
+}
+
+/// Asks open Explorer windows to reload a folder.
+pub const EXPLORER_REFRESH_TOPIC: IpcTopic<ExplorerRefreshEvent> =
+    IpcTopic::new("explorer.refresh");
+
+/// Published by Explorer whenever its open folder changes.
+pub const EXPLORER_CWD_CHANGED_TOPIC: IpcTopic<ExplorerCwdChangedEvent> =
+    IpcTopic::new("explorer.cwd.changed");
+
+type PayloadValidator = fn(&Value) -> Result<(), String>;
+
+fn validate_payload<T: DeserializeOwned>(payload: &Value) -> Result<(), String> {
+    T::deserialize(payload)
+        .map(drop)
+        .map_err(|err| err.to_string())
+}
+
+#[derive(Clone, Default)]
+/// Payload schemas the app bus checks before delivering an event.
+///
+/// Topics without a registered schema carry any payload.
+pub struct IpcSchemaRegistry {
+    validators: HashMap<String, PayloadValidator>,
+}
+
+impl IpcSchemaRegistry {
+    /// Creates a registry holding the built-in typed topics.
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.register(&EXPLORER_REFRESH_TOPIC);
+        registry.register(&EXPLORER_CWD_CHANGED_TOPIC);
+        registry
+    }
+
+    /// Requires payloads published on `topic` to deserialize as its payload type.
+    pub fn register<T: Serialize + DeserializeOwned>(&mut self, topic: &IpcTopic<T>) {
+        self.validators
+            .insert(topic.name().to_string(), validate_payload::<T>);
+    }
+
+    /// Checks `payload` against the schema registered for `topic`, if any.
+    pub fn validate(&self, topic: &str, payload: &Value) -> Result<(), IpcError> {
+        match self.validators.get(topic) {
+            Some(validate) => validate(payload).map_err(|message| IpcError::InvalidPayload {
+                topic: topic.to_string(),
+                message,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+/// Why an IPC call or publish failed.
+pub enum IpcError {
+    /// No open window is subscribed to the topic.
+    NoResponder {
+        /// Topic the request was published on.
+        topic: String,
+    },
+    /// No reply arrived in time.
+    Timeout {
+        /// Topic the request was published on.
+        topic: String,
+        /// Time waited, in milliseconds.
+        timeout_ms: u64,
+    },
+    /// The responder handled the request and reported a failure.
+    Failed {
+        /// Machine-readable failure code chosen by the responder.
+        code: String,
+        /// Human-readable failure description.
+        message: String,
+    },
+    /// The reply payload was not a serialized [`IpcResult`].
+    InvalidReply {
+        /// Decoding error.
+        message: String,
+    },
+    /// The calling window closed before a reply arrived.
+    Cancelled,
+    /// The payload does not match the schema registered for its topic.
+    InvalidPayload {
+        /// Topic the payload was published on.
+        topic: String,
+        /// Schema mismatch description.
+        message: String,
+    },
+}
+
+impl IpcError {
+    /// Builds a responder failure.
+    pub fn failed(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Failed {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for IpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoResponder { topic } => write!(f, "no app is listening on `{topic}`"),
+            Self::Timeout { topic, timeout_ms } => {
+                write!(f, "`{topic}` did not reply within {timeout_ms} ms")
+            }
+            Self::Failed { code, message } => write!(f, "{code}: {message}"),
+            Self::InvalidReply { message } => write!(f, "invalid reply: {message}"),
+            Self::Cancelled => f.write_str("the call was cancelled"),
+            Self::InvalidPayload { topic, message } => {
+                write!(f, "invalid payload for `{topic}`: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IpcError {}
+
+/// Outcome of an [`IpcService::call`], carried as the reply payload.
+pub type IpcResult = Result<Value, IpcError>;
+
+/// Serializes `result` as a reply payload.
+pub fn ipc_reply_payload(result: IpcResult) -> Value {
+    serde_json::to_value(result).unwrap_or(Value::Null)
+}
+
+/// Decodes a reply payload produced by [`ipc_reply_payload`].
+pub fn parse_ipc_reply(payload: Value) -> IpcResult {
+    serde_json::from_value::<IpcResult>(payload).unwrap_or_else(|err| {
+        Err(IpcError::InvalidReply {
+            message: err.to_string(),
+        })
+    })
+}
+
+thread_local! {
+    static NEXT_CALL_ID: Cell<u64> = const { Cell::new(1) };
+}
+
+type PendingCalls = HashMap<String, oneshot::Sender<IpcResult>>;
+
+#[derive(Clone, Copy)]
+/// Inter-app IPC service for topic subscriptions and pub/sub request-reply envelopes.
+pub struct IpcService {
+    sender: Callback<AppCommand>,
+    calls: StoredValue<PendingCalls>,
+}
+
+impl IpcService {
+    fn new(sender: Callback<AppCommand>) -> Self {
+        Self {
+            sender,
+            calls: store_value(PendingCalls::new()),
+        }
+    }
+
+    /// Subscribes this window to a topic.
+    pub fn subscribe(&self, topic: impl Into<String>) {
+        self.sender.call(AppCommand::Subscribe {
+            topic: topic.into(),
+        });
+    }
+
+    /// Unsubscribes this window from a topic.
+    pub fn unsubscribe(&self, topic: impl Into<String>) {
+        self.sender.call(AppCommand::Unsubscribe {
+            topic: topic.into(),
+        });
+    }
+
+    /// Publishes a one-way event payload.
+    pub fn publish(&self, topic: impl Into<String>, payload: Value) {
+        self.sender.call(AppCommand::PublishEvent {
+            topic: topic.into(),
+            payload,
+            correlation_id: None,
+            reply_to: None,
+        });
+    }
+
+    /// Publishes a one-way event on a typed topic.
+    pub fn publish_typed<T: Serialize + DeserializeOwned>(
+        &self,
+        topic: &IpcTopic<T>,
+        payload: &T,
+    ) -> Result<(), IpcError> {
+        self.publish(topic.name(), topic.encode(payload)?);
+        Ok(())
+    }
+
+    /// Publishes a request payload carrying correlation metadata.
+    pub fn request(
+        &self,
+        topic: impl Into<String>,
+        payload: Value,
+        correlation_id: impl Into<String>,
+        reply_to: impl Into<String>,
+    ) {
+        self.sender.call(AppCommand::PublishEvent {
+            topic: topic.into(),
+            payload,
+            correlation_id: Some(correlation_id.into()),
+            reply_to: Some(reply_to.into()),
+        });
+    }
+
+    /// Sends a request on `topic` and waits up to [`IPC_CALL_TIMEOUT`] for the reply.
+    pub async fn call(&self, topic: impl Into<String>, payload: Value) -> IpcResult {
+        self.call_with_timeout(topic, payload, IPC_CALL_TIMEOUT)
+            .await
+    }
+
+    /// Sends a request on `topic` and waits up to `timeout` for the reply.
+    ///
+    /// Responders answer with [`IpcService::reply`]. Replies reach this window through its inbox
+    /// and are matched by correlation id, so callers never see them there.
+    pub async fn call_with_timeout(
+        &self,
+        topic: impl Into<String>,
+        payload: Value,
+        timeout: Duration,
+    ) -> IpcResult {
+        let topic = topic.into();
+        let correlation_id = NEXT_CALL_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            format!("call-{id}")
+        });
+        let (reply, answer) = oneshot::channel();
+        self.calls.update_value(|calls| {
+            calls.insert(correlation_id.clone(), reply);
+        });
+        set_timeout(
+            {
+                let calls = self.calls;
+                let correlation_id = correlation_id.clone();
+                let topic = topic.clone();
+                move || {
+                    if let Some(reply) = calls
+                        .try_update_value(|calls| calls.remove(&correlation_id))
+                        .flatten()
+                    {
+                        let _ = reply.send(Err(IpcError::Timeout {
+                            topic,
+                            timeout_ms: timeout.as_millis() as u64,
+                        }));
+                    }
+                }
+            },
+            timeout,
+        );
+        self.request(topic, payload, correlation_id, IPC_REPLY_TOPIC);
+        answer.await.unwrap_or(Err(IpcError::Cancelled))
+    }
+
+    /// Answers `request` by delivering `result` to the window that sent it.
+    ///
+    /// Requests without a correlation id, reply topic, or source window are one-way and are
+    /// ignored. The window manager delivers only the first reply to a request that was routed to
+    /// this window, and only on [`IPC_REPLY_TOPIC`].
+    pub fn reply(&self, request: &IpcEnvelope, result: IpcResult) {
+        let (Some(correlation_id), Some(topic), Some(target_window_id)) = (
+            request.correlation_id.clone(),
+            request.reply_to.clone(),
+            request.source_window_id,
+        ) else {
+            return;
+        };
+        self.sender.call(AppCommand::ReplyToWindow {
+            target_window_id,
+            topic,
+            correlation_id,
+            payload: ipc_reply_payload(result),
+        });
+    }
+
+    /// Resolves the pending [`IpcService::call`] that `event` answers.
+    ///
+    /// The window manager feeds inbox events through here and drops the ones that return `true`.
+    pub fn resolve_reply(&self, event: &IpcEnvelope) -> bool {
+        if event.topic != IPC_REPLY_TOPIC {
+            return false;
+        }
+        let Some(correlation_id) = event.correlation_id.as_deref() else {
+            return false;
+        };
+        let Some(reply) = self
+            .calls
+            .try_update_value(|calls| calls.remove(correlation_id))
+            .flatten()
+        else {
+            return false;
+        };
+        let _ = reply.send(parse_ipc_reply(event.payload.clone()));
+        true
+    }
+}
+
+/// Async completion provider used by command registrations.
+pub type AppCommandCompletion = Rc<
+    dyn Fn(CompletionRequest) -> LocalBoxFuture<'static, Result<Vec<CompletionItem>, ShellError>>,
+>;
+
+/// Async command handler used by app command registrations.
+pub type AppCommandHandler =
+    Rc<dyn Fn(AppCommandContext) -> LocalBoxFuture<'static, Result<CommandResult, ShellError>>>;
+
+/// Execution context supplied to app-registered command handlers.
+#[derive(Clone)]
+pub struct AppCommandContext {
+    /// Execution identifier for the current command.
+    pub execution_id: ExecutionId,
+    /// Parsed invocation metadata for the current pipeline stage.
+    pub invocation: ParsedInvocation,
+    /// Full parsed argv payload.
+    pub argv: Vec<String>,
+    /// Positional argument tokens after command-path resolution.
+    pub args: Vec<String>,
+    /// Current logical cwd.
+    pub cwd: String,
+    /// Structured input passed from the previous pipeline stage.
+    pub input: StructuredData,
+    /// Optional source window identifier.
+    pub source_window_id: Option<WindowRuntimeId>,
+    /// Whether a destructive command was invoked with `--dry-run` and must not change state.
+    pub dry_run: bool,
+    emit: Rc<dyn Fn(ShellStreamEvent)>,
+    set_cwd: Rc<dyn Fn(String)>,
+    is_cancelled: Rc<dyn Fn() -> bool>,
+    execute_line: Option<LineExecutor>,
+    prompter: Option<Prompter>,
+}
+
+/// Nested line executor supplied by the runtime for script-style commands.
+pub type LineExecutor = Rc<dyn Fn(String) -> LocalBoxFuture<'static, ShellExit>>;
+
+/// Interactive prompt bridge supplied by the runtime for commands that ask for user input.
+pub type Prompter =
+    Rc<dyn Fn(String, PromptKind) -> LocalBoxFuture<'static, Result<String, ShellError>>>;
+
+type ShellEventEmitter = Rc<dyn Fn(ShellStreamEvent)>;
+type ShellCwdSetter = Rc<dyn Fn(String)>;
+type CancellationProbe = Rc<dyn Fn() -> bool>;
+type PromptResponder = Rc<dyn Fn(ExecutionId, Option<String>) -> bool>;
+type VariableSetter = Rc<dyn Fn(&str, StructuredData) -> Result<(), ShellError>>;
+type ShellSessionFactory = Rc<dyn Fn(String) -> Result<ShellSessionHandle, String>>;
+type HistorySearch = Rc<dyn Fn(&str, Option<usize>) -> Option<HistoryMatch>>;
+type CommandSearch = Rc<dyn Fn(&str) -> Vec<CompletionItem>>;
+type CommandRegistrar =
+    Rc<dyn Fn(AppCommandRegistration) -> Result<CommandRegistrationHandle, String>>;
+type ProviderRegistrar =
+    Rc<dyn Fn(Rc<dyn AppCommandProvider>) -> Result<CommandRegistrationHandle, String>>;
+
+impl AppCommandContext {
+    /// Emits an informational notice for the current execution.
+    pub fn info(&self, message: impl Into<String>) {
+        self.notice(CommandNoticeLevel::Info, message);
+    }
+
+    /// Emits a warning notice for the current execution.
+    pub fn warn(&self, message: impl Into<String>) {
+        self.notice(CommandNoticeLevel::Warning, message);
+    }
+
+    /// Emits an error notice for the current execution.
+    pub fn error(&self, message: impl Into<String>) {
+        self.notice(CommandNoticeLevel::Error, message);
+    }
+
+    /// Emits a structured notice for the current execution.
+    pub fn notice(&self, level: CommandNoticeLevel, message: impl Into<String>) {
+        self.emit(ShellStreamEvent::Notice {
+            execution_id: self.execution_id,
+            notice: CommandNotice {
+                level,
+                message: message.into(),
+            },
+        });
+    }
+
+    /// Emits a progress update for the current execution.
+    pub fn progress(&self, value: Option<f32>, label: Option<String>) {
+        self.emit(ShellStreamEvent::Progress {
+            execution_id: self.execution_id,
+            value,
+            label,
+        });
+    }
+
+    /// Emits a structured data frame for the current execution.
+    pub fn data(&self, data: StructuredData, display: DisplayPreference) {
+        self.emit(ShellStreamEvent::Data {
+            execution_id: self.execution_id,
+            data,
+            display,
+            more: None,
+        });
+    }
+
+    /// Writes plain text to standard output for the current execution.
+    ///
+    /// The shell engine batches consecutive writes into [`ShellStreamEvent::StdoutChunk`] events.
+    pub fn stdout(&self, text: impl Into<String>) {
+        self.emit(ShellStreamEvent::StdoutChunk {
+            execution_id: self.execution_id,
+            text: text.into(),
+        });
+    }
+
+    /// Writes plain text to the diagnostic stream for the current execution.
+    pub fn stderr(&self, text: impl Into<String>) {
+        self.emit(ShellStreamEvent::StderrChunk {
+            execution_id: self.execution_id,
+            text: text.into(),
+        });
     }
 
     /// Emits an incremental shell stream event.
@@ -999,6 +3609,70 @@ impl AppCommandContext {
         (self.is_cancelled)()
     }
 
+    /// Parses and executes `line` inside the current execution.
+    ///
+    /// Output streams under this context's [`ExecutionId`]. When the runtime did not attach a
+    /// [`LineExecutor`], the call resolves to an `Unavailable`-style exit without running anything.
+    pub async fn execute_line(&self, line: impl Into<String>) -> ShellExit {
+        match self.execute_line.as_ref() {
+            Some(execute_line) => execute_line(line.into()).await,
+            None => ShellExit::from(ShellError::new(
+                ShellErrorCode::Unavailable,
+                "nested command execution is unavailable",
+            )),
+        }
+    }
+
+    /// Asks the user for input and waits for the answer.
+    ///
+    /// The terminal renders the question inline; see [`PromptKind`] for input styles.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Cancelled` error when the user dismisses the prompt or cancels the execution,
+    /// and an `Unavailable` error when the runtime did not attach a [`Prompter`].
+    pub async fn prompt(
+        &self,
+        prompt: impl Into<String>,
+        kind: PromptKind,
+    ) -> Result<String, ShellError> {
+        match self.prompter.as_ref() {
+            Some(prompter) => prompter(prompt.into(), kind).await,
+            None => Err(ShellError::new(
+                ShellErrorCode::Unavailable,
+                "interactive prompts are unavailable",
+            )),
+        }
+    }
+
+    /// Asks a yes/no question; see [`Self::prompt`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::prompt`].
+    pub async fn confirm(&self, prompt: impl Into<String>) -> Result<bool, ShellError> {
+        let answer = self.prompt(prompt, PromptKind::Confirm).await?;
+        Ok(is_affirmative(&answer))
+    }
+
+    /// Attaches the runtime prompt bridge used by [`Self::prompt`].
+    pub fn with_prompter(mut self, prompter: Prompter) -> Self {
+        self.prompter = Some(prompter);
+        self
+    }
+
+    /// Attaches the runtime line executor used by [`Self::execute_line`].
+    pub fn with_line_executor(mut self, execute_line: LineExecutor) -> Self {
+        self.execute_line = Some(execute_line);
+        self
+    }
+
+    /// Marks the execution as a dry run of a destructive command.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
     /// Creates a new command context from runtime-provided callbacks.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -1021,9 +3695,12 @@ impl AppCommandContext {
             cwd,
             input,
             source_window_id,
+            dry_run: false,
             emit,
             set_cwd,
             is_cancelled,
+            execute_line: None,
+            prompter: None,
         }
     }
 }
@@ -1091,7 +3768,11 @@ pub struct ShellSessionHandle {
     pub cwd: ReadSignal<String>,
     submit: Rc<dyn Fn(ShellRequest)>,
     cancel: Rc<dyn Fn()>,
+    respond: PromptResponder,
     complete: AppCommandCompletion,
+    acknowledge: Option<Rc<dyn Fn(usize)>>,
+    set_variable: Option<VariableSetter>,
+    attach_prompts: Option<Rc<dyn Fn()>>,
 }
 
 impl ShellSessionHandle {
@@ -1102,6 +3783,7 @@ impl ShellSessionHandle {
         cwd: ReadSignal<String>,
         submit: Rc<dyn Fn(ShellRequest)>,
         cancel: Rc<dyn Fn()>,
+        respond: PromptResponder,
         complete: AppCommandCompletion,
     ) -> Self {
         Self {
@@ -1110,7 +3792,39 @@ impl ShellSessionHandle {
             cwd,
             submit,
             cancel,
+            respond,
             complete,
+            acknowledge: None,
+            set_variable: None,
+            attach_prompts: None,
+        }
+    }
+
+    /// Attaches the runtime bridge used by [`Self::attach_prompt_responder`].
+    pub fn with_prompt_attacher(mut self, attach_prompts: Rc<dyn Fn()>) -> Self {
+        self.attach_prompts = Some(attach_prompts);
+        self
+    }
+
+    /// Attaches the runtime bridge used by [`Self::acknowledge_events`].
+    pub fn with_event_acknowledger(mut self, acknowledge: Rc<dyn Fn(usize)>) -> Self {
+        self.acknowledge = Some(acknowledge);
+        self
+    }
+
+    /// Attaches the runtime bridge used by [`Self::set_variable`].
+    pub fn with_variable_setter(mut self, set_variable: VariableSetter) -> Self {
+        self.set_variable = Some(set_variable);
+        self
+    }
+
+    /// Marks the first `count` entries of [`Self::events`] as processed so the session can
+    /// release them. Consumers that acknowledge should read the stream from the start on every
+    /// change; a leading [`ShellStreamEvent::Truncated`] reports events dropped before they were
+    /// read.
+    pub fn acknowledge_events(&self, count: usize) {
+        if let Some(acknowledge) = &self.acknowledge {
+            acknowledge(count);
         }
     }
 
@@ -1124,6 +3838,38 @@ impl ShellSessionHandle {
         (self.cancel)();
     }
 
+    /// Declares that this consumer renders prompts and answers them through [`Self::respond`].
+    ///
+    /// Until then, commands that prompt fail as cancelled instead of waiting for an answer that
+    /// would never come.
+    pub fn attach_prompt_responder(&self) {
+        if let Some(attach_prompts) = &self.attach_prompts {
+            attach_prompts();
+        }
+    }
+
+    /// Answers the [`ShellStreamEvent::PromptRequest`] raised by `execution_id`; `None`
+    /// dismisses it. Returns `false` when that execution is not waiting on a prompt.
+    pub fn respond(&self, execution_id: ExecutionId, answer: Option<String>) -> bool {
+        (self.respond)(execution_id, answer)
+    }
+
+    /// Stores `data` in a session variable that later `var <$name>` stages read back.
+    ///
+    /// # Errors
+    ///
+    /// Returns a usage error for invalid names and an unavailable error when the session has no
+    /// variable bridge.
+    pub fn set_variable(&self, name: &str, data: StructuredData) -> Result<(), ShellError> {
+        match &self.set_variable {
+            Some(set_variable) => set_variable(name, data),
+            None => Err(ShellError::new(
+                ShellErrorCode::Unavailable,
+                "this shell session does not accept variables",
+            )),
+        }
+    }
+
     /// Resolves completion candidates for the current request.
     pub async fn complete(
         &self,
@@ -1136,8 +3882,12 @@ impl ShellSessionHandle {
 /// Command service bridging shell sessions and dynamic registration.
 #[derive(Clone)]
 pub struct CommandService {
-    /// Reactive global terminal history maintained by the desktop runtime.
+    /// Reactive global terminal history owned by the shell engine, oldest first.
     pub history: ReadSignal<Vec<String>>,
+    /// Reactive counter that changes whenever commands are registered or unregistered.
+    pub registry_generation: ReadSignal<u64>,
+    search_history: HistorySearch,
+    search_commands: CommandSearch,
     create_session: ShellSessionFactory,
     register_command: CommandRegistrar,
     register_provider: ProviderRegistrar,
@@ -1147,12 +3897,18 @@ impl CommandService {
     /// Creates a command service from runtime-provided callbacks.
     pub fn new(
         history: ReadSignal<Vec<String>>,
+        registry_generation: ReadSignal<u64>,
+        search_history: HistorySearch,
+        search_commands: CommandSearch,
         create_session: ShellSessionFactory,
         register_command: CommandRegistrar,
         register_provider: ProviderRegistrar,
     ) -> Self {
         Self {
             history,
+            registry_generation,
+            search_history,
+            search_commands,
             create_session,
             register_command,
             register_provider,
@@ -1163,12 +3919,34 @@ impl CommandService {
     pub fn disabled() -> Self {
         Self::new(
             leptos::create_rw_signal(Vec::new()).read_only(),
+            leptos::create_rw_signal(0).read_only(),
+            Rc::new(|_, _| None),
+            Rc::new(|_| Vec::new()),
             Rc::new(|_| Err("command sessions are unavailable".to_string())),
             Rc::new(|_| Err("command registration is unavailable".to_string())),
             Rc::new(|_| Err("command registration is unavailable".to_string())),
         )
     }
 
+    /// Finds the newest history entry containing `query` that is older than index `before`.
+    ///
+    /// Pass the previous match index as `before` to step through older matches (Ctrl+R).
+    pub fn reverse_search_history(
+        &self,
+        query: &str,
+        before: Option<usize>,
+    ) -> Option<HistoryMatch> {
+        (self.search_history)(query, before)
+    }
+
+    /// Fuzzy-searches the public commands this window may run, best match first.
+    ///
+    /// Items carry the command path as their value and the help summary as their detail; an
+    /// empty query lists every runnable command.
+    pub fn search_commands(&self, query: &str) -> Vec<CompletionItem> {
+        (self.search_commands)(query)
+    }
+
     /// Creates a new shell session for the current app window.
     pub fn create_session(&self, cwd: impl Into<String>) -> Result<ShellSessionHandle, String> {
         (self.create_session)(cwd.into())
@@ -1195,13 +3973,19 @@ impl CommandService {
 /// Injected app services bundle.
 ///
 /// This is the main app-facing service surface. It combines runtime-mediated command callbacks
-/// with host-selected persistence, explorer, cache, wallpaper, notification, and command-session
-/// adapters, while [`CapabilitySet`] exposes which optional domains are currently granted and
-/// available.
+/// with host-selected persistence, explorer, cache, clipboard, network, wallpaper, notification,
+/// and command-session adapters, while [`CapabilitySet`] exposes which optional domains are currently
+/// granted and available.
 pub struct AppServices {
-    capabilities: CapabilitySet,
+    capabilities: ReadSignal<CapabilitySet>,
+    capability_requester: StoredValue<CapabilityRequester>,
+    link_handler: LinkHandlerSlot,
     /// Window integration service.
     pub window: WindowService,
+    /// Read-only workspace service.
+    pub workspaces: WorkspaceService,
+    /// App launch service.
+    pub launcher: LauncherService,
     /// State persistence service.
     pub state: StateService,
     /// Namespaced config service.
@@ -1214,12 +3998,26 @@ pub struct AppServices {
     pub explorer: ExplorerHostService,
     /// Content-cache service.
     pub cache: CacheHostService,
+    /// Clipboard service.
+    pub clipboard: ClipboardHostService,
+    /// Network fetch service.
+    pub fetch: FetchService,
     /// Theme/accessibility service.
     pub theme: ThemeService,
     /// Wallpaper query/preview/library service.
     pub wallpaper: WallpaperService,
     /// Notification service.
     pub notifications: NotificationService,
+    /// Runtime capability grant listing and revocation service.
+    pub permissions: PermissionsService,
+    /// Window listing and force-close service.
+    pub processes: ProcessService,
+    /// Leveled, structured logging service.
+    pub logs: LogService,
+    /// Modal dialog service.
+    pub dialogs: DialogService,
+    /// Window-scoped timer and background task service.
+    pub tasks: TaskService,
     /// IPC service.
     pub ipc: IpcService,
     /// Shell command registration and session service.
@@ -1231,11 +4029,25 @@ impl AppServices {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         sender: Callback<AppCommand>,
-        capabilities: CapabilitySet,
+        capabilities: ReadSignal<CapabilitySet>,
+        capability_requester: CapabilityRequester,
+        permission_grants: ReadSignal<Vec<CapabilityGrant>>,
+        notification_preferences: ReadSignal<Vec<NotificationPreference>>,
+        window_lister: WindowLister,
+        shared_state: ReadSignal<BTreeMap<String, Value>>,
         app_state: Rc<dyn AppStateStore>,
         prefs: Rc<dyn PrefsStore>,
         explorer: Rc<dyn ExplorerFsService>,
         cache: Rc<dyn ContentCache>,
+        clipboard: Rc<dyn ClipboardService>,
+        http: Rc<dyn HttpService>,
+        network_allowlist: Vec<String>,
+        window_geometry: ReadSignal<WindowGeometry>,
+        window_focused: ReadSignal<bool>,
+        workspace: ReadSignal<WorkspaceInfo>,
+        window_opener: WindowOpener,
+        lifecycle_handler: LifecycleHandlerSlot,
+        link_handler: LinkHandlerSlot,
         theme_skin_id: ReadSignal<String>,
         theme_high_contrast: ReadSignal<bool>,
         theme_reduced_motion: ReadSignal<bool>,
@@ -1243,11 +4055,31 @@ impl AppServices {
         wallpaper_preview: ReadSignal<Option<WallpaperConfig>>,
         wallpaper_library: ReadSignal<WallpaperLibrarySnapshot>,
         commands: CommandService,
+        dialogs: DialogService,
+        logs: LogService,
+        launchable_apps: Vec<LaunchableApp>,
     ) -> Self {
+        let fetch = FetchService::gated(http, FetchPolicy::new(network_allowlist), capabilities);
         Self {
             capabilities,
-            window: WindowService { sender },
-            state: StateService { sender },
+            capability_requester: store_value(capability_requester),
+            link_handler,
+            window: WindowService {
+                sender,
+                geometry: window_geometry,
+                focused: window_focused,
+                opener: store_value(window_opener),
+                lifecycle_handler,
+            },
+            workspaces: WorkspaceService { info: workspace },
+            launcher: LauncherService {
+                sender,
+                apps: launchable_apps.into(),
+            },
+            state: StateService {
+                sender,
+                shared: shared_state,
+            },
             config: ConfigService {
                 sender,
                 prefs: prefs.clone(),
@@ -1256,6 +4088,8 @@ impl AppServices {
             prefs: PrefsHostService::new(prefs),
             explorer: ExplorerHostService::new(explorer),
             cache: CacheHostService::new(cache),
+            clipboard: ClipboardHostService::new(clipboard),
+            fetch,
             theme: ThemeService {
                 sender,
                 skin_id: theme_skin_id,
@@ -1268,15 +4102,58 @@ impl AppServices {
                 preview: wallpaper_preview,
                 library: wallpaper_library,
             },
-            notifications: NotificationService { sender },
-            ipc: IpcService { sender },
+            notifications: NotificationService {
+                sender,
+                preferences: notification_preferences,
+            },
+            permissions: PermissionsService {
+                sender,
+                grants: permission_grants,
+            },
+            processes: ProcessService {
+                sender,
+                lister: store_value(window_lister),
+            },
+            logs,
+            dialogs,
+            tasks: TaskService::new(),
+            ipc: IpcService::new(sender),
             commands,
         }
     }
 
     /// Returns the runtime-granted and host-available capability snapshot for the mounted app.
-    pub fn capabilities(&self) -> &CapabilitySet {
-        &self.capabilities
+    ///
+    /// The snapshot includes grants made after mount; reading it inside a reactive scope tracks
+    /// them.
+    pub fn capabilities(&self) -> CapabilitySet {
+        self.capabilities.get()
+    }
+
+    /// Handles [`AppLink`]s to this app in this window, replacing any earlier handler.
+    ///
+    /// Links to an app that has an open window with a handler are delivered to the handler of its
+    /// most relevant window, which is focused first, instead of opening a new window. Apps use it
+    /// for internal routes such as navigating to a folder.
+    pub fn register_link_handler(&self, handler: impl Fn(AppLink) + 'static) {
+        let handler: LinkHandler = Rc::new(handler);
+        self.link_handler
+            .try_update_value(|slot| *slot = Some(handler));
+    }
+
+    /// Asks the user to grant `capability` and returns its status afterwards.
+    ///
+    /// The window manager shows a consent prompt over the app's window unless the capability is
+    /// already granted, is not [requestable](AppCapability::is_requestable), or the host cannot
+    /// provide it. An accepted grant updates [`AppMountContext::capabilities`] and is remembered
+    /// for the app; a declined prompt can be asked again later.
+    pub async fn request_capability(&self, capability: AppCapability) -> CapabilityStatus {
+        let current = self.capabilities.get_untracked();
+        if current.is_granted(capability) || !capability.is_requestable() {
+            return current.status(capability);
+        }
+        let request = self.capability_requester.get_value();
+        request(capability).await
     }
 }
 
@@ -1328,6 +4205,9 @@ impl AppModule {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 /// Manifest-backed registration metadata for a runtime app entry.
+///
+/// Registrations come from [`AppManifest::validate`], so every field already satisfies the
+/// runtime's manifest rules.
 pub struct AppRegistration {
     /// Canonical app id.
     pub app_id: ApplicationId,
@@ -1339,6 +4219,8 @@ pub struct AppRegistration {
     pub runtime_contract_version: String,
     /// Declared requested capabilities.
     pub requested_capabilities: Vec<AppCapability>,
+    /// URL prefixes the app may fetch when granted [`AppCapability::Network`].
+    pub network_allowlist: Vec<String>,
     /// Whether only one instance should be active.
     pub single_instance: bool,
     /// Suspend policy for minimized windows.
@@ -1347,6 +4229,366 @@ pub struct AppRegistration {
     pub show_in_launcher: bool,
     /// Desktop icon visibility flag.
     pub show_on_desktop: bool,
+    /// Minimum default window size.
+    pub window_defaults: ManifestWindowDefaults,
+    /// Quick actions listed on the app's taskbar menus.
+    pub quick_actions: Vec<AppQuickAction>,
+    /// App-state namespaces, preference keys, and cache names Settings clears for the app.
+    pub storage_keys: Vec<String>,
+}
+
+/// Manifest schema version this runtime reads.
+pub const APP_MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+/// App contract version this runtime implements.
+///
+/// A manifest is compatible when it targets the same major version and no newer minor version.
+pub const RUNTIME_CONTRACT_VERSION: &str = "2.0.0";
+
+/// Default window size declared in an app manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestWindowDefaults {
+    /// Minimum default width in pixels.
+    pub width: i32,
+    /// Minimum default height in pixels.
+    pub height: i32,
+}
+
+/// Jump-list entry an app declares as `[[quick_actions]]` in its manifest.
+///
+/// The taskbar lists quick actions on the app's pinned button and window menus. Choosing one opens
+/// a new window of the app with `launch_params`, so apps handle them like any other launch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppQuickAction {
+    /// Kebab-case id, unique within the app.
+    pub id: String,
+    /// Menu label.
+    pub label: String,
+    /// Icon token shown next to the label; the app icon is used when absent or unknown.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Launch params for the window the action opens.
+    #[serde(default)]
+    pub launch_params: Value,
+}
+
+/// App manifest as written in `app.manifest.toml`, or its JSON form, before validation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppManifest {
+    /// Manifest schema version; must equal [`APP_MANIFEST_SCHEMA_VERSION`].
+    pub schema_version: u32,
+    /// Namespaced app id such as `system.calculator`.
+    pub app_id: String,
+    /// Name shown in the launcher and window titles.
+    pub display_name: String,
+    /// App version as `major.minor.patch`.
+    pub version: String,
+    /// Contract version the app was built against, as `major.minor.patch`.
+    pub runtime_contract_version: String,
+    /// Kebab-case capability ids the app requests.
+    pub requested_capabilities: Vec<String>,
+    /// URL prefixes the app may fetch; requires the `network` capability.
+    #[serde(default)]
+    pub network_allowlist: Vec<String>,
+    /// Whether only one instance should be active.
+    pub single_instance: bool,
+    /// `on-minimize` or `never`.
+    pub suspend_policy: String,
+    /// Launcher visibility flag.
+    pub show_in_launcher: bool,
+    /// Desktop icon visibility flag.
+    pub show_on_desktop: bool,
+    /// Minimum default window size.
+    pub window_defaults: ManifestWindowDefaults,
+    /// Taskbar quick actions, in menu order.
+    #[serde(default)]
+    pub quick_actions: Vec<AppQuickAction>,
+    /// Exact app-state namespaces, preference keys, and cache names the app owns.
+    #[serde(default)]
+    pub storage_keys: Vec<String>,
+}
+
+/// One problem found in an app manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestDiagnostic {
+    /// Manifest field the problem is in, or `manifest` for the document as a whole.
+    pub field: &'static str,
+    /// What is wrong and how to fix it.
+    pub message: String,
+}
+
+impl std::fmt::Display for ManifestDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// App manifest that failed to parse or validate, with every problem found in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestError {
+    /// App id from the manifest, or a description of where it came from when the id is unusable.
+    pub source: String,
+    /// Problems found, in field order.
+    pub diagnostics: Vec<ManifestDiagnostic>,
+}
+
+impl ManifestError {
+    fn new(source: impl Into<String>, field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            diagnostics: vec![ManifestDiagnostic {
+                field,
+                message: message.into(),
+            }],
+        }
+    }
+}
+
+impl std::fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid app manifest `{}`", self.source)?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n  - {diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl AppManifest {
+    /// Parses a manifest from its JSON form.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ManifestError`] naming the JSON problem when the document does not match the
+    /// manifest format.
+    pub fn from_json(raw: &str) -> Result<Self, ManifestError> {
+        serde_json::from_str(raw)
+            .map_err(|err| ManifestError::new("<unparsed manifest>", "manifest", err.to_string()))
+    }
+
+    /// Checks the manifest against the runtime's rules and returns the app registration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ManifestError`] listing every rule the manifest breaks: schema version, app id
+    /// policy, version format, contract compatibility, capability ids, network allowlist, suspend
+    /// policy, window size, quick actions, and storage keys.
+    pub fn validate(&self) -> Result<AppRegistration, ManifestError> {
+        let mut diagnostics = Vec::new();
+        let mut problem = |field: &'static str, message: String| {
+            diagnostics.push(ManifestDiagnostic { field, message });
+        };
+
+        if self.schema_version != APP_MANIFEST_SCHEMA_VERSION {
+            problem(
+                "schema_version",
+                format!(
+                    "expected {APP_MANIFEST_SCHEMA_VERSION}, found {}",
+                    self.schema_version
+                ),
+            );
+        }
+        let app_id = ApplicationId::new(self.app_id.clone())
+            .map_err(|err| problem("app_id", err))
+            .ok();
+        if self.display_name.trim().is_empty() {
+            problem("display_name", "must not be empty".to_string());
+        }
+        if parse_manifest_version(&self.version).is_none() {
+            problem(
+                "version",
+                format!("`{}` is not a `major.minor.patch` version", self.version),
+            );
+        }
+        match parse_manifest_version(&self.runtime_contract_version) {
+            None => problem(
+                "runtime_contract_version",
+                format!(
+                    "`{}` is not a `major.minor.patch` version",
+                    self.runtime_contract_version
+                ),
+            ),
+            Some((major, minor, _)) => {
+                let (runtime_major, runtime_minor, _) =
+                    parse_manifest_version(RUNTIME_CONTRACT_VERSION).unwrap_or_default();
+                if major != runtime_major || minor > runtime_minor {
+                    problem(
+                        "runtime_contract_version",
+                        format!(
+                            "targets contract {}, but this runtime implements \
+                             {RUNTIME_CONTRACT_VERSION}; build the app against contract \
+                             {runtime_major}.{runtime_minor} or older {runtime_major}.x",
+                            self.runtime_contract_version
+                        ),
+                    );
+                }
+            }
+        }
+
+        let mut requested_capabilities = Vec::new();
+        for id in &self.requested_capabilities {
+            match AppCapability::from_id(id) {
+                Some(capability) if requested_capabilities.contains(&capability) => {
+                    problem("requested_capabilities", format!("`{id}` is listed twice"));
+                }
+                Some(capability) => requested_capabilities.push(capability),
+                None => problem(
+                    "requested_capabilities",
+                    format!("unknown capability `{id}`"),
+                ),
+            }
+        }
+        if !self.network_allowlist.is_empty()
+            && !requested_capabilities.contains(&AppCapability::Network)
+        {
+            problem(
+                "network_allowlist",
+                "is set but `network` is not in requested_capabilities".to_string(),
+            );
+        }
+        for entry in &self.network_allowlist {
+            if !entry.starts_with("https://") && !entry.starts_with("http://") {
+                problem(
+                    "network_allowlist",
+                    format!("`{entry}` must be an http or https URL"),
+                );
+            }
+        }
+        let suspend_policy = match self.suspend_policy.as_str() {
+            "on-minimize" => Some(SuspendPolicy::OnMinimize),
+            "never" => Some(SuspendPolicy::Never),
+            other => {
+                problem(
+                    "suspend_policy",
+                    format!("unknown policy `{other}`; use `on-minimize` or `never`"),
+                );
+                None
+            }
+        };
+        if self.window_defaults.width <= 0 || self.window_defaults.height <= 0 {
+            problem(
+                "window_defaults",
+                format!(
+                    "{}x{} is not a positive size",
+                    self.window_defaults.width, self.window_defaults.height
+                ),
+            );
+        }
+        for (index, action) in self.quick_actions.iter().enumerate() {
+            let kebab = !action.id.is_empty()
+                && action
+                    .id
+                    .chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-');
+            if !kebab {
+                problem(
+                    "quick_actions",
+                    format!("`{}` is not a kebab-case action id", action.id),
+                );
+            } else if self.quick_actions[..index]
+                .iter()
+                .any(|earlier| earlier.id == action.id)
+            {
+                problem("quick_actions", format!("`{}` is listed twice", action.id));
+            }
+            if action.label.trim().is_empty() {
+                problem(
+                    "quick_actions",
+                    format!("`{}` needs a non-empty label", action.id),
+                );
+            }
+        }
+        for (index, key) in self.storage_keys.iter().enumerate() {
+            if key.trim().is_empty() {
+                problem("storage_keys", "entries must not be empty".to_string());
+            } else if self.storage_keys[..index].contains(key) {
+                problem("storage_keys", format!("`{key}` is listed twice"));
+            }
+        }
+
+        match (app_id, suspend_policy) {
+            (Some(app_id), Some(suspend_policy)) if diagnostics.is_empty() => Ok(AppRegistration {
+                app_id,
+                display_name: self.display_name.clone(),
+                version: self.version.clone(),
+                runtime_contract_version: self.runtime_contract_version.clone(),
+                requested_capabilities,
+                network_allowlist: self.network_allowlist.clone(),
+                single_instance: self.single_instance,
+                suspend_policy,
+                show_in_launcher: self.show_in_launcher,
+                show_on_desktop: self.show_on_desktop,
+                window_defaults: self.window_defaults,
+                quick_actions: self.quick_actions.clone(),
+                storage_keys: self.storage_keys.clone(),
+            }),
+            _ => Err(ManifestError {
+                source: self.app_id.clone(),
+                diagnostics,
+            }),
+        }
+    }
+}
+
+/// Parses and validates a JSON array of manifests, rejecting duplicate app ids.
+///
+/// # Errors
+///
+/// Returns one [`ManifestError`] per manifest that fails, so every broken manifest is reported at
+/// once.
+pub fn parse_manifest_catalog(raw_json: &str) -> Result<Vec<AppRegistration>, Vec<ManifestError>> {
+    let entries: Vec<Value> = serde_json::from_str(raw_json).map_err(|err| {
+        vec![ManifestError::new(
+            "<manifest catalog>",
+            "manifest",
+            err.to_string(),
+        )]
+    })?;
+    let mut registrations = Vec::<AppRegistration>::new();
+    let mut errors = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let manifest = match serde_json::from_value::<AppManifest>(entry) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                errors.push(ManifestError::new(
+                    format!("<catalog entry {index}>"),
+                    "manifest",
+                    err.to_string(),
+                ));
+                continue;
+            }
+        };
+        match manifest.validate() {
+            Ok(registration)
+                if registrations
+                    .iter()
+                    .any(|existing| existing.app_id == registration.app_id) =>
+            {
+                errors.push(ManifestError::new(
+                    manifest.app_id,
+                    "app_id",
+                    "is declared by more than one manifest",
+                ));
+            }
+            Ok(registration) => registrations.push(registration),
+            Err(err) => errors.push(err),
+        }
+    }
+    if errors.is_empty() {
+        Ok(registrations)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses `major.minor.patch`, ignoring a `-prerelease` or `+build` suffix.
+fn parse_manifest_version(raw: &str) -> Option<(u64, u64, u64)> {
+    let core = raw.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let version = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(version)
 }
 
 #[cfg(test)]
@@ -1362,6 +4604,466 @@ mod tests {
         assert!(ApplicationId::new("system..calc").is_err());
     }
 
+    #[test]
+    fn log_service_tags_records_with_its_source() {
+        let registry = LogRegistry::new(Rc::new(platform_host::MemoryPrefsStore::default()));
+        let weather = LogService::new(registry.clone(), "acme.weather");
+        let chat = LogService::new(registry.clone(), "acme.chat");
+        weather.info("fetching");
+        chat.warn("offline");
+        weather.log(
+            LogLevel::Error,
+            "fetch failed",
+            [("status", serde_json::json!(503))],
+        );
+
+        let tail = weather.tail(5);
+        assert_eq!(
+            tail.iter()
+                .map(|record| (record.level, record.message.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (LogLevel::Info, "fetching"),
+                (LogLevel::Error, "fetch failed")
+            ]
+        );
+        assert_eq!(tail[1].fields["status"], serde_json::json!(503));
+        assert_eq!(weather.tail(1).len(), 1);
+        assert_eq!(registry.sources(), ["acme.chat", "acme.weather"]);
+    }
+
+    #[test]
+    fn dialog_helpers_map_responses() {
+        use futures::executor::block_on;
+
+        let answer = |response: DialogResponse| {
+            DialogService::new(Rc::new(move |_| {
+                let response = response.clone();
+                Box::pin(async move { response })
+            }))
+        };
+        let confirmed = answer(DialogResponse::Confirmed);
+        assert!(block_on(confirmed.confirm("Delete", "Sure?")));
+        assert_eq!(block_on(confirmed.prompt("Name", "", "a")), None);
+        let text = answer(DialogResponse::Text("notes".to_string()));
+        assert_eq!(
+            block_on(text.prompt("Name", "", "a")).as_deref(),
+            Some("notes")
+        );
+        let path = answer(DialogResponse::Path("/Docs/a.txt".to_string()));
+        assert_eq!(
+            block_on(path.save_file("Save", "/Docs", "a.txt")).as_deref(),
+            Some("/Docs/a.txt")
+        );
+        let disabled = DialogService::disabled();
+        assert!(!block_on(
+            disabled.confirm_destructive("Delete", "Sure?", "Delete")
+        ));
+        assert_eq!(block_on(disabled.open_file("Open", "/")), None);
+    }
+
+    #[test]
+    fn shared_state_reads_follow_persisted_keys() {
+        use leptos::{create_rw_signal, SignalUpdate};
+
+        let shared = create_rw_signal(BTreeMap::new());
+        let service = StateService {
+            sender: Callback::new(|_| {}),
+            shared: shared.read_only(),
+        };
+        let watched = service.watch_shared_state(" layout ");
+        assert_eq!(service.load_shared_state("layout"), None);
+        assert_eq!(watched.get(), None);
+
+        shared.update(|shared| {
+            shared.insert("layout".to_string(), serde_json::json!({ "panes": 2 }));
+        });
+        assert_eq!(
+            service.load_shared_state(" layout"),
+            Some(serde_json::json!({ "panes": 2 }))
+        );
+        assert_eq!(watched.get(), Some(serde_json::json!({ "panes": 2 })));
+    }
+
+    #[test]
+    fn app_links_parse_and_round_trip() {
+        let link = AppLink::parse("web+os://system.explorer/My%20Docs/a.txt?viewer=editor&q=a+b")
+            .expect("link");
+        assert_eq!(link.app_id.as_str(), "system.explorer");
+        assert_eq!(link.path, "/My Docs/a.txt");
+        assert_eq!(link.params.get("q").map(String::as_str), Some("a b"));
+        assert_eq!(
+            link.launch_params(),
+            serde_json::json!({ "path": "/My Docs/a.txt", "viewer": "editor", "q": "a b" })
+        );
+        assert_eq!(AppLink::parse(&link.to_string()), Ok(link));
+
+        let bare =
+            AppLink::parse("WEB+OS://system.settings?section=permissions#top").expect("link");
+        assert_eq!(bare.path, "/");
+        assert_eq!(
+            bare.launch_params(),
+            serde_json::json!({ "section": "permissions" })
+        );
+        assert_eq!(
+            bare.to_string(),
+            "web+os://system.settings?section=permissions"
+        );
+
+        assert!(AppLink::parse("https://system.settings").is_err());
+        assert!(AppLink::parse("web+os://Settings/").is_err());
+        assert!(AppLink::parse("web+os://system.explorer/%zz").is_err());
+    }
+
+    #[test]
+    fn lifecycle_handlers_decide_whether_windows_close() {
+        use futures::executor::block_on;
+
+        struct KeepOpen;
+        impl AppLifecycleHandler for KeepOpen {
+            fn on_closing(&self) -> LifecycleFuture<'_, CloseResponse> {
+                Box::pin(async { CloseResponse::Cancel })
+            }
+        }
+        struct Defaults;
+        impl AppLifecycleHandler for Defaults {}
+
+        let slot: LifecycleHandlerSlot = store_value(None);
+        assert_eq!(block_on(confirm_window_close(slot)), CloseResponse::Close);
+        slot.update_value(|handler| *handler = Some(Rc::new(KeepOpen)));
+        assert_eq!(block_on(confirm_window_close(slot)), CloseResponse::Cancel);
+        slot.update_value(|handler| *handler = Some(Rc::new(Defaults)));
+        assert_eq!(block_on(confirm_window_close(slot)), CloseResponse::Close);
+    }
+
+    #[test]
+    fn fetch_policy_matches_allowlisted_url_prefixes() {
+        let policy = FetchPolicy::new(["https://api.example.com", "https://cdn.example.com/pkg/"]);
+        for allowed in [
+            "https://api.example.com",
+            "https://api.example.com/v1/weather?city=Oslo",
+            "https://api.example.com?q=1",
+            "https://cdn.example.com/pkg/index.json",
+        ] {
+            assert_eq!(policy.check(allowed), Ok(()), "{allowed}");
+        }
+        for refused in [
+            "https://api.example.com.evil.test/",
+            "https://api.example.com@evil.test/",
+            "https://api.example.com:8443/",
+            "http://api.example.com/",
+            "https://cdn.example.com/other/",
+        ] {
+            assert!(
+                matches!(policy.check(refused), Err(FetchError::NotAllowed { .. })),
+                "{refused}"
+            );
+        }
+        for invalid in [
+            "ftp://api.example.com",
+            "https://",
+            "/relative",
+            "https:///path",
+        ] {
+            assert!(
+                matches!(policy.check(invalid), Err(FetchError::InvalidUrl { .. })),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn task_service_pauses_tasks_while_suspended() {
+        let started = Rc::new(Cell::new(0));
+        let cleared = Rc::new(Cell::new(0));
+        let fake_timer = {
+            let started = Rc::clone(&started);
+            let cleared = Rc::clone(&cleared);
+            move || {
+                started.set(started.get() + 1);
+                let cleared = Rc::clone(&cleared);
+                Some(Box::new(move || cleared.set(cleared.get() + 1)) as TimerCancel)
+            }
+        };
+        let tasks = TaskService::with_timers(TaskTimers {
+            timeout: Rc::new({
+                let fake_timer = fake_timer.clone();
+                move |_, _| fake_timer()
+            }),
+            interval: Rc::new(move |_, _| fake_timer()),
+        });
+        let interval = tasks.set_interval(|| {}, Duration::from_secs(1));
+        let cancelled = tasks.set_timeout(|| {}, Duration::from_secs(1));
+        cancelled.cancel();
+        assert!(interval.is_active());
+        assert!(!cancelled.is_active());
+        assert_eq!((started.get(), cleared.get()), (2, 1));
+
+        let fired = Rc::new(Cell::new(0));
+        let timeout = tasks.set_timeout(
+            {
+                let fired = Rc::clone(&fired);
+                move || fired.set(fired.get() + 1)
+            },
+            Duration::from_secs(1),
+        );
+        let mut background = Suspendable {
+            registry: tasks.registry,
+            task: Box::pin({
+                let fired = Rc::clone(&fired);
+                async move { fired.set(fired.get() + 10) }
+            }),
+        };
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        tasks.set_suspended(true);
+        assert!(tasks.is_suspended());
+        assert_eq!((started.get(), cleared.get()), (3, 2));
+        fire_timeout(tasks.registry, timeout.id);
+        assert!(Pin::new(&mut background).poll(&mut cx).is_pending());
+        assert_eq!(fired.get(), 0);
+        assert!(timeout.is_active());
+
+        tasks.set_suspended(false);
+        assert_eq!((started.get(), cleared.get()), (4, 2));
+        assert_eq!(fired.get(), 1);
+        assert!(!timeout.is_active());
+        assert!(Pin::new(&mut background).poll(&mut cx).is_ready());
+        assert_eq!(fired.get(), 11);
+
+        tasks.cancel_all();
+        assert!(!interval.is_active());
+        assert_eq!(cleared.get(), 3);
+    }
+
+    #[test]
+    fn fetch_service_enforces_policy_and_limits() {
+        use futures::executor::block_on;
+        use leptos::SignalUpdate;
+        use std::cell::RefCell;
+
+        struct RecordingHttp {
+            sent: RefCell<Vec<HttpRequest>>,
+            body: String,
+        }
+
+        impl HttpService for RecordingHttp {
+            fn send<'a>(
+                &'a self,
+                request: &'a HttpRequest,
+            ) -> platform_host::HttpFuture<'a, Result<HttpResponse, String>> {
+                self.sent.borrow_mut().push(request.clone());
+                let response = HttpResponse {
+                    status: 200,
+                    headers: vec![("content-type".to_string(), "application/json".to_string())],
+                    body: self.body.clone(),
+                };
+                Box::pin(async move { Ok(response) })
+            }
+        }
+
+        let http = Rc::new(RecordingHttp {
+            sent: RefCell::new(Vec::new()),
+            body: r#"{"temp":21}"#.to_string(),
+        });
+        let policy = FetchPolicy::new(["https://api.example.com"])
+            .with_timeout(Duration::from_secs(3))
+            .with_max_response_bytes(64);
+        let fetch = FetchService::new(http.clone(), policy.clone());
+
+        let weather: Value =
+            block_on(fetch.get_json("https://api.example.com/weather")).expect("allowed request");
+        assert_eq!(weather, serde_json::json!({ "temp": 21 }));
+        let response = block_on(fetch.post("https://api.example.com/echo", "text/plain", "hi"))
+            .expect("allowed post");
+        assert_eq!(response.header("Content-Type"), Some("application/json"));
+        assert!(matches!(
+            block_on(fetch.get("https://elsewhere.example.com/")),
+            Err(FetchError::NotAllowed { .. })
+        ));
+
+        let sent = http.sent.borrow();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].method, HttpMethod::Get);
+        assert_eq!(sent[0].timeout_ms, 3000);
+        assert_eq!(sent[0].max_response_bytes, 64);
+        assert_eq!(sent[1].method, HttpMethod::Post);
+        assert_eq!(sent[1].body.as_deref(), Some("hi"));
+        assert_eq!(
+            sent[1].headers,
+            vec![("content-type".to_string(), "text/plain".to_string())]
+        );
+        drop(sent);
+
+        let tiny = FetchService::new(http.clone(), policy.clone().with_max_response_bytes(4));
+        assert_eq!(
+            block_on(tiny.get("https://api.example.com/")),
+            Err(FetchError::TooLarge { limit_bytes: 4 })
+        );
+        assert_eq!(
+            block_on(FetchService::disabled().get("https://api.example.com/")),
+            Err(FetchError::Unavailable)
+        );
+
+        let capabilities = leptos::create_rw_signal(CapabilitySet::new(
+            vec![AppCapability::Window],
+            HostCapabilities::browser(),
+        ));
+        let gated = FetchService::gated(http, policy, capabilities.read_only());
+        assert_eq!(
+            block_on(gated.get("https://api.example.com/")),
+            Err(FetchError::Unavailable)
+        );
+        capabilities.update(|capabilities| capabilities.grant(AppCapability::Network));
+        assert!(block_on(gated.get("https://api.example.com/")).is_ok());
+    }
+
+    #[test]
+    fn file_drag_payloads_round_trip() {
+        let payload = FileDragPayload::new(["/Projects/my notes.md", "/Docs"]);
+        assert_eq!(FileDragPayload::decode(&payload.encode()), Some(payload));
+        assert_eq!(FileDragPayload::decode(r#"{"paths":[]}"#), None);
+        assert_eq!(FileDragPayload::decode("/Docs"), None);
+    }
+
+    #[test]
+    fn notifications_carry_actions_and_click_targets() {
+        let explorer = ApplicationId::trusted("system.explorer");
+        let notification = Notification::new("Copy finished", "3 files copied")
+            .with_icon("explorer-folder")
+            .with_action(
+                "Open folder",
+                NotificationTarget::new(explorer.clone(), serde_json::json!({ "path": "/docs" })),
+            )
+            .with_on_click(NotificationTarget::new(explorer, Value::Null));
+        let encoded = serde_json::to_value(&notification).expect("encode notification");
+        let decoded: Notification = serde_json::from_value(encoded).expect("decode notification");
+        assert_eq!(decoded, notification);
+        assert_eq!(decoded.actions[0].label, "Open folder");
+
+        let plain: Notification =
+            serde_json::from_value(serde_json::json!({ "title": "Hi", "body": "" }))
+                .expect("decode plain notification");
+        assert_eq!(plain, Notification::new("Hi", ""));
+    }
+
+    #[test]
+    fn drag_data_round_trips_typed_bodies() {
+        let files = DragData::files(["/Pictures/dunes.png"]);
+        assert_eq!(files.kind, FILE_DRAG_DATA_TYPE);
+        let decoded = DragData::decode(FILE_DRAG_DATA_TYPE, &files.encode()).expect("decodes");
+        assert_eq!(decoded, files);
+        assert_eq!(
+            decoded.paths(),
+            Some(vec!["/Pictures/dunes.png".to_string()])
+        );
+        assert_eq!(
+            FileDragPayload::decode(&files.encode()),
+            Some(FileDragPayload::new(["/Pictures/dunes.png"]))
+        );
+
+        let custom = DragData::new(
+            "application/x-example+json",
+            &serde_json::json!({ "id": 7 }),
+        );
+        assert_eq!(custom.paths(), None);
+        assert_eq!(
+            custom.body_as::<Value>(),
+            Some(serde_json::json!({ "id": 7 }))
+        );
+        assert_eq!(DragData::decode("text/plain", "/Docs"), None);
+        assert_eq!(DragData::files(Vec::<String>::new()).paths(), None);
+    }
+
+    #[test]
+    fn file_associations_match_the_longest_extension() {
+        let explorer = ApplicationId::trusted("system.explorer");
+        let terminal = ApplicationId::trusted("system.terminal");
+        let mut registry = FileAssociations::default();
+        registry.set(FileAssociation::new(".GZ", explorer.clone(), Value::Null).expect("gz"));
+        registry.set(
+            FileAssociation::new(
+                "tar.gz",
+                terminal.clone(),
+                serde_json::json!({ "cwd": "{dir}" }),
+            )
+            .expect("tar.gz"),
+        );
+        registry.set(
+            FileAssociation::new(
+                "gz",
+                terminal.clone(),
+                FileAssociation::default_launch_params(),
+            )
+            .expect("gz again"),
+        );
+
+        assert_eq!(
+            registry
+                .associations
+                .iter()
+                .map(|association| association.extension.as_str())
+                .collect::<Vec<_>>(),
+            ["gz", "tar.gz"]
+        );
+        let tarball = registry.lookup("/Backups/Site.TAR.GZ").expect("tar.gz");
+        assert_eq!(
+            tarball.launch_params_for("/Backups/Site.TAR.GZ"),
+            serde_json::json!({ "cwd": "/Backups" })
+        );
+        let gzip = registry.lookup("/log.gz").expect("gz");
+        assert_eq!(gzip.app_id, terminal);
+        assert_eq!(
+            gzip.launch_params_for("/log.gz"),
+            serde_json::json!({ "path": "/log.gz" })
+        );
+        assert!(registry.lookup("/.gz").is_none());
+        assert!(registry.lookup("/gz").is_none());
+
+        assert!(registry.remove(".gz"));
+        assert!(!registry.remove("gz"));
+        assert!(FileAssociation::new(" . ", explorer, Value::Null).is_err());
+    }
+
+    #[test]
+    fn apps_own_only_their_declared_storage_keys() {
+        let terminal = LaunchableApp {
+            app_id: ApplicationId::trusted("system.terminal"),
+            display_name: "Terminal".to_string(),
+            storage_keys: vec![
+                "app.terminal".to_string(),
+                "retrodesk.terminal.prefs.v1".to_string(),
+            ],
+        };
+        assert!(terminal.owns_storage_key("app.terminal"));
+        assert!(terminal.owns_storage_key("retrodesk.terminal.prefs.v1"));
+        assert!(!terminal.owns_storage_key("retrodesk.terminal.prefs"));
+        assert!(!terminal.owns_storage_key("system.terminal"));
+        assert!(!terminal.owns_storage_key("system.terminal.v1"));
+    }
+
+    #[test]
+    fn file_associations_override_the_app_but_keep_launch_params() {
+        let explorer = ApplicationId::trusted("system.explorer");
+        let terminal = ApplicationId::trusted("system.terminal");
+        let mut registry = FileAssociations::default();
+        registry.set(
+            FileAssociation::new("sh", explorer, serde_json::json!({ "cwd": "{dir}" }))
+                .expect("sh"),
+        );
+
+        assert!(registry.set_app(".SH", terminal.clone()));
+        assert!(!registry.set_app("md", terminal.clone()));
+        let script = registry.lookup("/bin/build.sh").expect("sh");
+        assert_eq!(script.app_id, terminal);
+        assert_eq!(
+            script.launch_params_for("/bin/build.sh"),
+            serde_json::json!({ "cwd": "/bin" })
+        );
+        assert_eq!(registry.associations.len(), 1);
+    }
+
     #[test]
     fn publish_event_request_metadata_is_attached() {
         let envelope = AppEvent::new("app.system.calc.events.v1", Value::Null, Some(3))
@@ -1377,6 +5079,182 @@ mod tests {
         );
     }
 
+    #[test]
+    fn typed_topics_encode_decode_and_validate_payloads() {
+        let event = AppEvent::new(
+            EXPLORER_REFRESH_TOPIC.name(),
+            EXPLORER_REFRESH_TOPIC
+                .encode(&ExplorerRefreshEvent {
+                    path: Some("/Docs".to_string()),
+                })
+                .expect("encode"),
+            None,
+        );
+        assert_eq!(
+            EXPLORER_REFRESH_TOPIC.decode(&event),
+            Some(Ok(ExplorerRefreshEvent {
+                path: Some("/Docs".to_string())
+            }))
+        );
+        assert_eq!(EXPLORER_CWD_CHANGED_TOPIC.decode(&event), None);
+
+        let registry = IpcSchemaRegistry::builtin();
+        assert_eq!(
+            registry.validate("explorer.refresh", &serde_json::json!({})),
+            Ok(())
+        );
+        assert!(matches!(
+            registry.validate("explorer.cwd.changed", &serde_json::json!({ "cwd": 3 })),
+            Err(IpcError::InvalidPayload { topic, .. }) if topic == "explorer.cwd.changed"
+        ));
+        assert_eq!(
+            registry.validate("app.custom.events.v1", &serde_json::json!(3)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn ipc_reply_payloads_round_trip_results() {
+        let value = serde_json::json!({ "sum": 3 });
+        assert_eq!(
+            parse_ipc_reply(ipc_reply_payload(Ok(value.clone()))),
+            Ok(value)
+        );
+        let failure = IpcError::failed("bad_input", "operands must be numbers");
+        assert_eq!(
+            parse_ipc_reply(ipc_reply_payload(Err(failure.clone()))),
+            Err(failure)
+        );
+        assert!(matches!(
+            parse_ipc_reply(serde_json::json!({ "sum": 3 })),
+            Err(IpcError::InvalidReply { .. })
+        ));
+        assert_eq!(
+            IpcError::NoResponder {
+                topic: "calc".to_string()
+            }
+            .to_string(),
+            "no app is listening on `calc`"
+        );
+    }
+
+    #[test]
+    fn manifest_validation_reports_every_problem() {
+        let manifest = AppManifest {
+            schema_version: 1,
+            app_id: "acme.weather".to_string(),
+            display_name: "Weather".to_string(),
+            version: "1.2.0".to_string(),
+            runtime_contract_version: "2.0.0".to_string(),
+            requested_capabilities: vec!["window".to_string(), "network".to_string()],
+            network_allowlist: vec!["https://api.example.com".to_string()],
+            single_instance: true,
+            suspend_policy: "on-minimize".to_string(),
+            show_in_launcher: true,
+            show_on_desktop: false,
+            window_defaults: ManifestWindowDefaults {
+                width: 320,
+                height: 240,
+            },
+            quick_actions: vec![AppQuickAction {
+                id: "radar".to_string(),
+                label: "Radar".to_string(),
+                icon: None,
+                launch_params: serde_json::json!({ "view": "radar" }),
+            }],
+            storage_keys: vec!["acme.weather.prefs.v1".to_string()],
+        };
+        let registration = manifest.validate().expect("valid manifest");
+        assert_eq!(registration.quick_actions, manifest.quick_actions);
+        assert_eq!(registration.storage_keys, manifest.storage_keys);
+        assert_eq!(
+            registration.requested_capabilities,
+            vec![AppCapability::Window, AppCapability::Network]
+        );
+        assert_eq!(registration.suspend_policy, SuspendPolicy::OnMinimize);
+
+        let broken = AppManifest {
+            app_id: "Weather".to_string(),
+            version: "1.2".to_string(),
+            runtime_contract_version: "3.0.0".to_string(),
+            requested_capabilities: vec!["window".to_string(), "gps".to_string()],
+            suspend_policy: "sometimes".to_string(),
+            quick_actions: vec![
+                manifest.quick_actions[0].clone(),
+                manifest.quick_actions[0].clone(),
+            ],
+            storage_keys: vec!["acme.weather.prefs.v1".to_string(), " ".to_string()],
+            ..manifest.clone()
+        };
+        let err = broken.validate().expect_err("broken manifest");
+        let fields = err
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.field)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                "app_id",
+                "version",
+                "runtime_contract_version",
+                "requested_capabilities",
+                "network_allowlist",
+                "suspend_policy",
+                "quick_actions",
+                "storage_keys",
+            ]
+        );
+        assert!(err.to_string().contains("unknown capability `gps`"));
+
+        let older_minor = AppManifest {
+            runtime_contract_version: "2.0.3-beta".to_string(),
+            ..manifest.clone()
+        };
+        assert!(older_minor.validate().is_ok());
+
+        let catalog = serde_json::to_string(&[manifest.clone(), manifest]).expect("catalog");
+        let errors = parse_manifest_catalog(&catalog).expect_err("duplicate ids");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].diagnostics[0].field, "app_id");
+        assert!(parse_manifest_catalog(r#"[{"app_id": "acme.weather"}]"#).is_err());
+    }
+
+    #[test]
+    fn capability_ids_round_trip() {
+        for capability in [
+            AppCapability::Window,
+            AppCapability::ExternalUrl,
+            AppCapability::Network,
+            AppCapability::Processes,
+        ] {
+            assert_eq!(
+                AppCapability::from_id(capability.as_str()),
+                Some(capability)
+            );
+        }
+        assert_eq!(AppCapability::from_id("external_url"), None);
+    }
+
+    #[test]
+    fn notification_delivery_ids_round_trip_and_match_serialization() {
+        for delivery in NotificationDelivery::ALL {
+            assert_eq!(
+                NotificationDelivery::from_id(delivery.as_str()),
+                Some(delivery)
+            );
+            assert_eq!(
+                serde_json::to_value(delivery).expect("serialize delivery"),
+                Value::String(delivery.as_str().to_string())
+            );
+        }
+        assert_eq!(
+            NotificationDelivery::default(),
+            NotificationDelivery::Banner
+        );
+        assert_eq!(NotificationDelivery::from_id("mute"), None);
+    }
+
     #[test]
     fn capability_set_combines_runtime_grant_with_host_availability() {
         let capabilities = CapabilitySet::new(
@@ -1394,6 +5272,23 @@ mod tests {
         assert!(!capabilities.supports_terminal_process());
     }
 
+    #[test]
+    fn capability_ids_match_manifest_serialization() {
+        for capability in [
+            AppCapability::Window,
+            AppCapability::Config,
+            AppCapability::ExternalUrl,
+            AppCapability::Commands,
+            AppCapability::Network,
+            AppCapability::Processes,
+        ] {
+            assert_eq!(
+                serde_json::to_value(capability).expect("serialize"),
+                Value::String(capability.as_str().to_string())
+            );
+        }
+    }
+
     #[test]
     fn primary_input_dom_id_uses_window_id() {
         assert_eq!(window_primary_input_dom_id(42), "window-primary-input-42");
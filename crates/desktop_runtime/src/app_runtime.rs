@@ -2,12 +2,17 @@
 
 use std::collections::{BTreeSet, HashMap};
 
-use desktop_app_contract::{AppEvent, AppLifecycleEvent};
+use desktop_app_contract::{
+    ipc_reply_payload, AppEvent, AppLifecycleEvent, IpcError, IpcSchemaRegistry,
+    LifecycleHandlerSlot, LinkHandlerSlot, IPC_ERROR_TOPIC, IPC_REPLY_TOPIC,
+};
 use leptos::*;
 use platform_host::unix_time_ms_now;
 
 use crate::model::{WindowId, WindowRecord};
 const MAX_INBOX_EVENTS: usize = 256;
+/// Most unanswered requests remembered per requesting window; older ones can no longer be answered.
+const MAX_PENDING_REPLIES: usize = 256;
 
 #[derive(Clone, Copy)]
 /// Reactive per-window app session signals.
@@ -16,13 +21,52 @@ pub struct WindowAppSession {
     pub lifecycle: RwSignal<AppLifecycleEvent>,
     /// App-bus inbox for the window.
     pub inbox: RwSignal<Vec<AppEvent>>,
+    /// Lifecycle handler the window's app registered, consulted before user-initiated closes.
+    pub lifecycle_handler: LifecycleHandlerSlot,
+    /// Handler the window's app registered for links routed to it.
+    pub link_handler: LinkHandlerSlot,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// App-bus activity of one window, as reported to task managers.
+pub struct WindowBusMetrics {
+    /// Events delivered to the window's inbox since it mounted.
+    pub delivered: u64,
+    /// Events dropped from the front of the window's inbox because it was full.
+    pub dropped: u64,
+    /// Events currently waiting in the window's inbox.
+    pub inbox_len: usize,
+    /// Topics the window subscribes to.
+    pub subscriptions: usize,
+}
+
+/// Request a window is still waiting to have answered on [`IPC_REPLY_TOPIC`].
+struct PendingReply {
+    correlation_id: String,
+    /// Windows the request was routed to; only they may answer it.
+    responders: BTreeSet<WindowId>,
 }
 
-#[derive(Default)]
 /// Runtime-owned app session and topic subscription state.
 pub struct AppRuntimeState {
     sessions: HashMap<WindowId, WindowAppSession>,
     topic_subscribers: HashMap<String, BTreeSet<WindowId>>,
+    event_counts: HashMap<WindowId, (u64, u64)>,
+    schemas: IpcSchemaRegistry,
+    /// Unanswered requests keyed by the requesting window, oldest first.
+    pending_replies: HashMap<WindowId, Vec<PendingReply>>,
+}
+
+impl Default for AppRuntimeState {
+    fn default() -> Self {
+        Self {
+            sessions: HashMap::new(),
+            topic_subscribers: HashMap::new(),
+            event_counts: HashMap::new(),
+            schemas: IpcSchemaRegistry::builtin(),
+            pending_replies: HashMap::new(),
+        }
+    }
 }
 
 impl AppRuntimeState {
@@ -34,6 +78,8 @@ impl AppRuntimeState {
         let session = WindowAppSession {
             lifecycle: create_rw_signal(AppLifecycleEvent::Mounted),
             inbox: create_rw_signal(Vec::new()),
+            lifecycle_handler: store_value(None),
+            link_handler: store_value(None),
         };
         self.sessions.insert(window_id, session);
         session
@@ -41,6 +87,8 @@ impl AppRuntimeState {
 
     fn remove_session(&mut self, window_id: WindowId) {
         self.sessions.remove(&window_id);
+        self.event_counts.remove(&window_id);
+        self.pending_replies.remove(&window_id);
         for subscribers in self.topic_subscribers.values_mut() {
             subscribers.remove(&window_id);
         }
@@ -55,13 +103,39 @@ impl AppRuntimeState {
 
     fn deliver_event(&mut self, window_id: WindowId, event: AppEvent) {
         let session = self.ensure_session(window_id);
+        let mut overflow = 0;
         session.inbox.update(|inbox| {
             inbox.push(event);
             if inbox.len() > MAX_INBOX_EVENTS {
-                let overflow = inbox.len() - MAX_INBOX_EVENTS;
+                overflow = inbox.len() - MAX_INBOX_EVENTS;
                 inbox.drain(0..overflow);
             }
         });
+        let (delivered, dropped) = self.event_counts.entry(window_id).or_default();
+        *delivered += 1;
+        *dropped += overflow as u64;
+    }
+
+    fn bus_metrics(&self, window_id: WindowId) -> WindowBusMetrics {
+        let (delivered, dropped) = self
+            .event_counts
+            .get(&window_id)
+            .copied()
+            .unwrap_or_default();
+        WindowBusMetrics {
+            delivered,
+            dropped,
+            inbox_len: self
+                .sessions
+                .get(&window_id)
+                .and_then(|session| session.inbox.try_with_untracked(Vec::len))
+                .unwrap_or_default(),
+            subscriptions: self
+                .topic_subscribers
+                .values()
+                .filter(|subscribers| subscribers.contains(&window_id))
+                .count(),
+        }
     }
 
     fn subscribe(&mut self, window_id: WindowId, topic: &str) {
@@ -83,29 +157,61 @@ impl AppRuntimeState {
 
     fn publish(
         &mut self,
-        source_window_id: WindowId,
+        source_window_id: Option<WindowId>,
         topic: &str,
         payload: serde_json::Value,
         correlation_id: Option<String>,
         reply_to: Option<String>,
     ) {
-        let Some(subscribers) = self.topic_subscribers.get(topic).cloned() else {
+        if let Err(err) = self.schemas.validate(topic, &payload) {
+            match source_window_id {
+                Some(source) => self.report_failure(source, err, correlation_id, reply_to),
+                None => logging::warn!("dropped runtime event: {err}"),
+            }
             return;
-        };
+        }
+        let subscribers = self
+            .topic_subscribers
+            .get(topic)
+            .cloned()
+            .unwrap_or_default();
         let mut stale_subscribers = Vec::new();
+        let mut responders = BTreeSet::new();
 
         for target in subscribers {
             if self.sessions.contains_key(&target) {
-                let mut event = AppEvent::new(topic, payload.clone(), Some(source_window_id.0));
+                let mut event =
+                    AppEvent::new(topic, payload.clone(), source_window_id.map(|id| id.0));
                 event.correlation_id = correlation_id.clone();
                 event.reply_to = reply_to.clone();
                 event.timestamp_unix_ms = Some(unix_time_ms_now());
                 self.deliver_event(target, event);
+                responders.insert(target);
             } else {
                 stale_subscribers.push(target);
             }
         }
 
+        // A request nobody can answer fails straight away instead of waiting out its timeout.
+        let is_request = correlation_id.is_some() && reply_to.is_some();
+        let delivered = !responders.is_empty();
+        if let (false, true, Some(source)) = (delivered, is_request, source_window_id) {
+            let err = IpcError::NoResponder {
+                topic: topic.to_string(),
+            };
+            self.report_failure(source, err, correlation_id, reply_to);
+        } else if let (Some(source), Some(correlation_id), Some(IPC_REPLY_TOPIC)) =
+            (source_window_id, correlation_id, reply_to.as_deref())
+        {
+            let pending = self.pending_replies.entry(source).or_default();
+            pending.push(PendingReply {
+                correlation_id,
+                responders,
+            });
+            let overflow = pending.len().saturating_sub(MAX_PENDING_REPLIES);
+            pending.drain(..overflow);
+        }
+
         if !stale_subscribers.is_empty() {
             if let Some(topic_subscribers) = self.topic_subscribers.get_mut(topic) {
                 for stale in stale_subscribers {
@@ -118,6 +224,53 @@ impl AppRuntimeState {
         }
     }
 
+    /// Delivers `reply` from `responder` to `target` only when it answers a request of `target`
+    /// that was routed to `responder`, and forgets that request so it is answered once.
+    fn deliver_reply(&mut self, responder: WindowId, target: WindowId, reply: AppEvent) -> bool {
+        let Some(pending) = self.pending_replies.get_mut(&target) else {
+            return false;
+        };
+        let Some(index) = pending.iter().position(|request| {
+            Some(&request.correlation_id) == reply.correlation_id.as_ref()
+                && request.responders.contains(&responder)
+        }) else {
+            return false;
+        };
+        pending.remove(index);
+        if pending.is_empty() {
+            self.pending_replies.remove(&target);
+        }
+        self.deliver_event(target, reply);
+        true
+    }
+
+    /// Tells the publishing window why its event was not delivered.
+    ///
+    /// Requests are answered on their reply topic; one-way events are reported on
+    /// [`IPC_ERROR_TOPIC`].
+    fn report_failure(
+        &mut self,
+        source: WindowId,
+        err: IpcError,
+        correlation_id: Option<String>,
+        reply_to: Option<String>,
+    ) {
+        let mut event = match (correlation_id, reply_to) {
+            (Some(correlation_id), Some(reply_to)) => {
+                AppEvent::new(reply_to, ipc_reply_payload(Err(err)), None)
+                    .with_correlation(Some(correlation_id), None)
+            }
+            (correlation_id, _) => AppEvent::new(
+                IPC_ERROR_TOPIC,
+                serde_json::to_value(err).unwrap_or_default(),
+                None,
+            )
+            .with_correlation(correlation_id, None),
+        };
+        event.timestamp_unix_ms = Some(unix_time_ms_now());
+        self.deliver_event(source, event);
+    }
+
     fn sync_windows(&mut self, windows: &[WindowRecord]) {
         let active: BTreeSet<WindowId> = windows.iter().map(|win| win.id).collect();
 
@@ -156,6 +309,22 @@ pub fn ensure_window_session(
     session.expect("window app session ensured")
 }
 
+/// Returns the app session of `window_id` when its window has mounted.
+pub fn window_session(
+    runtime_state: RwSignal<AppRuntimeState>,
+    window_id: WindowId,
+) -> Option<WindowAppSession> {
+    runtime_state.with_untracked(|state| state.sessions.get(&window_id).copied())
+}
+
+/// Returns the app-bus activity of `window_id`; windows that have not mounted report zeros.
+pub fn window_bus_metrics(
+    runtime_state: RwSignal<AppRuntimeState>,
+    window_id: WindowId,
+) -> WindowBusMetrics {
+    runtime_state.with_untracked(|state| state.bus_metrics(window_id))
+}
+
 /// Syncs app runtime session state with currently open windows.
 pub fn sync_runtime_sessions(runtime_state: RwSignal<AppRuntimeState>, windows: &[WindowRecord]) {
     runtime_state.update(|state| state.sync_windows(windows));
@@ -179,6 +348,27 @@ pub fn deliver_window_event(
     runtime_state.update(|state| state.deliver_event(window_id, event));
 }
 
+/// Delivers an IPC reply from `responder` to the request of `target` it answers.
+///
+/// Replies that answer no pending request routed to `responder`, or one already answered, are
+/// dropped.
+pub fn deliver_ipc_reply(
+    runtime_state: RwSignal<AppRuntimeState>,
+    responder: WindowId,
+    target: WindowId,
+    reply: AppEvent,
+) {
+    runtime_state.update(|state| {
+        if !state.deliver_reply(responder, target, reply) {
+            logging::warn!(
+                "dropped IPC reply from window {} to window {}: no matching request",
+                responder.0,
+                target.0
+            );
+        }
+    });
+}
+
 /// Adds a topic subscription for a window.
 pub fn subscribe_window_topic(
     runtime_state: RwSignal<AppRuntimeState>,
@@ -206,6 +396,81 @@ pub fn publish_topic_event(
     correlation_id: Option<String>,
     reply_to: Option<String>,
 ) {
-    runtime_state
-        .update(|state| state.publish(source_window_id, topic, payload, correlation_id, reply_to));
+    runtime_state.update(|state| {
+        state.publish(
+            Some(source_window_id),
+            topic,
+            payload,
+            correlation_id,
+            reply_to,
+        )
+    });
+}
+
+/// Publishes a runtime-originated event, with no source window, to all subscribers of `topic`.
+pub fn publish_runtime_topic_event(
+    runtime_state: RwSignal<AppRuntimeState>,
+    topic: &str,
+    payload: serde_json::Value,
+) {
+    runtime_state.update(|state| state.publish(None, topic, payload, None, None));
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn inbox(state: &AppRuntimeState, window_id: WindowId) -> Vec<AppEvent> {
+        state.sessions[&window_id].inbox.get_untracked()
+    }
+
+    fn reply(correlation_id: &str) -> AppEvent {
+        AppEvent::new(IPC_REPLY_TOPIC, json!({ "Ok": 3 }), None)
+            .with_correlation(Some(correlation_id.to_string()), None)
+    }
+
+    #[test]
+    fn replies_reach_only_requesters_from_the_windows_they_asked_once() {
+        let (caller, responder, bystander) = (WindowId(1), WindowId(2), WindowId(3));
+        let mut state = AppRuntimeState::default();
+        state.ensure_session(caller);
+        state.ensure_session(bystander);
+        state.subscribe(responder, "calc.add");
+        state.publish(
+            Some(caller),
+            "calc.add",
+            json!([1, 2]),
+            Some("call-1".to_string()),
+            Some(IPC_REPLY_TOPIC.to_string()),
+        );
+        assert_eq!(inbox(&state, responder).len(), 1);
+
+        assert!(!state.deliver_reply(bystander, caller, reply("call-1")));
+        assert!(!state.deliver_reply(responder, caller, reply("call-2")));
+        assert!(!state.deliver_reply(responder, bystander, reply("call-1")));
+        assert!(inbox(&state, caller).is_empty());
+
+        assert!(state.deliver_reply(responder, caller, reply("call-1")));
+        assert_eq!(inbox(&state, caller), vec![reply("call-1")]);
+        assert!(!state.deliver_reply(responder, caller, reply("call-1")));
+        assert_eq!(inbox(&state, caller).len(), 1);
+    }
+
+    #[test]
+    fn closing_the_requester_forgets_its_pending_requests() {
+        let (caller, responder) = (WindowId(1), WindowId(2));
+        let mut state = AppRuntimeState::default();
+        state.subscribe(responder, "calc.add");
+        state.publish(
+            Some(caller),
+            "calc.add",
+            json!([1, 2]),
+            Some("call-1".to_string()),
+            Some(IPC_REPLY_TOPIC.to_string()),
+        );
+        state.remove_session(caller);
+        assert!(!state.deliver_reply(responder, caller, reply("call-1")));
+    }
 }
@@ -1,5 +1,7 @@
 //! Desktop app registry metadata and app-content mounting helpers.
 
+mod log_viewer;
+mod notification_center;
 mod placeholders;
 
 use std::sync::OnceLock;
@@ -7,7 +9,8 @@ use std::sync::OnceLock;
 use crate::model::{OpenWindowRequest, DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH};
 use desktop_app_calculator::CalculatorApp;
 use desktop_app_contract::{
-    AppCapability, AppModule, AppMountContext, ApplicationId, SuspendPolicy,
+    parse_manifest_catalog, AppCapability, AppModule, AppMountContext, AppQuickAction,
+    AppRegistration, ApplicationId, LaunchableApp, SuspendPolicy,
 };
 use desktop_app_explorer::ExplorerApp;
 use desktop_app_notepad::NotepadApp;
@@ -24,17 +27,8 @@ const APP_ID_TERMINAL: &str = "system.terminal";
 const APP_ID_SETTINGS: &str = "system.settings";
 const APP_ID_UI_SHOWCASE: &str = "system.ui-showcase";
 const APP_ID_DIALUP: &str = "system.dialup";
-
-#[derive(Debug, Clone, Copy)]
-struct GeneratedAppManifestMetadata {
-    display_name: &'static str,
-    requested_capabilities: &'static [AppCapability],
-    single_instance: bool,
-    suspend_policy: SuspendPolicy,
-    show_in_launcher: bool,
-    show_on_desktop: bool,
-    window_defaults: (i32, i32),
-}
+const APP_ID_NOTIFICATIONS: &str = "system.notifications";
+const APP_ID_LOGS: &str = "system.logs";
 
 include!(concat!(env!("OUT_DIR"), "/app_catalog_generated.rs"));
 
@@ -47,6 +41,55 @@ pub fn app_manifest_catalog_json() -> &'static str {
     APP_MANIFEST_CATALOG_JSON
 }
 
+/// Returns the validated registrations for every built-in app that ships a manifest.
+///
+/// # Panics
+///
+/// Panics with every manifest diagnostic when a built-in manifest is invalid.
+pub fn app_manifests() -> &'static [AppRegistration] {
+    static MANIFESTS: OnceLock<Vec<AppRegistration>> = OnceLock::new();
+    MANIFESTS.get_or_init(|| {
+        parse_manifest_catalog(APP_MANIFEST_CATALOG_JSON).unwrap_or_else(|errors| {
+            let report = errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n");
+            panic!("built-in app manifests are invalid:\n{report}")
+        })
+    })
+}
+
+fn builtin_manifest(app_id: &str) -> &'static AppRegistration {
+    app_manifests()
+        .iter()
+        .find(|manifest| manifest.app_id.as_str() == app_id)
+        .unwrap_or_else(|| panic!("built-in app `{app_id}` has no manifest"))
+}
+
+fn manifest_descriptor(app_id: &str, module: AppModule) -> AppDescriptor {
+    let manifest = builtin_manifest(app_id);
+    AppDescriptor {
+        app_id: manifest.app_id.clone(),
+        launcher_label: &manifest.display_name,
+        desktop_icon_label: &manifest.display_name,
+        show_in_launcher: manifest.show_in_launcher,
+        show_on_desktop: manifest.show_on_desktop,
+        single_instance: manifest.single_instance,
+        module,
+        suspend_policy: manifest.suspend_policy,
+        requested_capabilities: &manifest.requested_capabilities,
+        network_allowlist: &manifest.network_allowlist,
+        quick_actions: &manifest.quick_actions,
+        storage_keys: &manifest.storage_keys,
+    }
+}
+
+fn manifest_window_defaults(app_id: &str) -> (i32, i32) {
+    let defaults = builtin_manifest(app_id).window_defaults;
+    (defaults.width, defaults.height)
+}
+
 #[derive(Debug, Clone)]
 /// Metadata describing how an app appears in the launcher/desktop and how it is instantiated.
 pub struct AppDescriptor {
@@ -68,42 +111,21 @@ pub struct AppDescriptor {
     pub suspend_policy: SuspendPolicy,
     /// Declared capability scopes requested by the app.
     pub requested_capabilities: &'static [AppCapability],
+    /// URL prefixes the app may fetch when granted [`AppCapability::Network`].
+    pub network_allowlist: &'static [String],
+    /// Taskbar quick actions declared in the app manifest.
+    pub quick_actions: &'static [AppQuickAction],
+    /// Storage keys declared in the app manifest; see [`LaunchableApp::owns_storage_key`].
+    pub storage_keys: &'static [String],
 }
 
 fn build_app_registry() -> Vec<AppDescriptor> {
     vec![
+        manifest_descriptor(APP_ID_CALCULATOR, AppModule::new(mount_calculator_app)),
+        manifest_descriptor(APP_ID_EXPLORER, AppModule::new(mount_explorer_app)),
         AppDescriptor {
-            app_id: builtin_app_id(APP_ID_CALCULATOR),
-            launcher_label: SYSTEM_CALCULATOR_MANIFEST.display_name,
-            desktop_icon_label: SYSTEM_CALCULATOR_MANIFEST.display_name,
-            show_in_launcher: SYSTEM_CALCULATOR_MANIFEST.show_in_launcher,
-            show_on_desktop: SYSTEM_CALCULATOR_MANIFEST.show_on_desktop,
-            single_instance: SYSTEM_CALCULATOR_MANIFEST.single_instance,
-            module: AppModule::new(mount_calculator_app),
-            suspend_policy: SYSTEM_CALCULATOR_MANIFEST.suspend_policy,
-            requested_capabilities: SYSTEM_CALCULATOR_MANIFEST.requested_capabilities,
-        },
-        AppDescriptor {
-            app_id: builtin_app_id(APP_ID_EXPLORER),
-            launcher_label: SYSTEM_EXPLORER_MANIFEST.display_name,
-            desktop_icon_label: SYSTEM_EXPLORER_MANIFEST.display_name,
-            show_in_launcher: SYSTEM_EXPLORER_MANIFEST.show_in_launcher,
-            show_on_desktop: SYSTEM_EXPLORER_MANIFEST.show_on_desktop,
-            single_instance: SYSTEM_EXPLORER_MANIFEST.single_instance,
-            module: AppModule::new(mount_explorer_app),
-            suspend_policy: SYSTEM_EXPLORER_MANIFEST.suspend_policy,
-            requested_capabilities: SYSTEM_EXPLORER_MANIFEST.requested_capabilities,
-        },
-        AppDescriptor {
-            app_id: builtin_app_id(APP_ID_NOTEPAD),
-            launcher_label: SYSTEM_NOTEPAD_MANIFEST.display_name,
             desktop_icon_label: "Notes",
-            show_in_launcher: SYSTEM_NOTEPAD_MANIFEST.show_in_launcher,
-            show_on_desktop: SYSTEM_NOTEPAD_MANIFEST.show_on_desktop,
-            single_instance: SYSTEM_NOTEPAD_MANIFEST.single_instance,
-            module: AppModule::new(mount_notepad_app),
-            suspend_policy: SYSTEM_NOTEPAD_MANIFEST.suspend_policy,
-            requested_capabilities: SYSTEM_NOTEPAD_MANIFEST.requested_capabilities,
+            ..manifest_descriptor(APP_ID_NOTEPAD, AppModule::new(mount_notepad_app))
         },
         AppDescriptor {
             app_id: builtin_app_id(APP_ID_PAINT),
@@ -115,40 +137,16 @@ fn build_app_registry() -> Vec<AppDescriptor> {
             module: AppModule::new(placeholders::mount_paint_placeholder_app),
             suspend_policy: SuspendPolicy::OnMinimize,
             requested_capabilities: &[AppCapability::Window, AppCapability::State],
+            network_allowlist: &[],
+            quick_actions: &[],
+            storage_keys: &[],
         },
+        manifest_descriptor(APP_ID_TERMINAL, AppModule::new(mount_terminal_app)),
         AppDescriptor {
-            app_id: builtin_app_id(APP_ID_TERMINAL),
-            launcher_label: SYSTEM_TERMINAL_MANIFEST.display_name,
-            desktop_icon_label: SYSTEM_TERMINAL_MANIFEST.display_name,
-            show_in_launcher: SYSTEM_TERMINAL_MANIFEST.show_in_launcher,
-            show_on_desktop: SYSTEM_TERMINAL_MANIFEST.show_on_desktop,
-            single_instance: SYSTEM_TERMINAL_MANIFEST.single_instance,
-            module: AppModule::new(mount_terminal_app),
-            suspend_policy: SYSTEM_TERMINAL_MANIFEST.suspend_policy,
-            requested_capabilities: SYSTEM_TERMINAL_MANIFEST.requested_capabilities,
-        },
-        AppDescriptor {
-            app_id: builtin_app_id(APP_ID_SETTINGS),
-            launcher_label: SYSTEM_SETTINGS_MANIFEST.display_name,
             desktop_icon_label: "Settings",
-            show_in_launcher: SYSTEM_SETTINGS_MANIFEST.show_in_launcher,
-            show_on_desktop: SYSTEM_SETTINGS_MANIFEST.show_on_desktop,
-            single_instance: SYSTEM_SETTINGS_MANIFEST.single_instance,
-            module: AppModule::new(mount_settings_app),
-            suspend_policy: SYSTEM_SETTINGS_MANIFEST.suspend_policy,
-            requested_capabilities: SYSTEM_SETTINGS_MANIFEST.requested_capabilities,
-        },
-        AppDescriptor {
-            app_id: builtin_app_id(APP_ID_UI_SHOWCASE),
-            launcher_label: SYSTEM_UI_SHOWCASE_MANIFEST.display_name,
-            desktop_icon_label: SYSTEM_UI_SHOWCASE_MANIFEST.display_name,
-            show_in_launcher: SYSTEM_UI_SHOWCASE_MANIFEST.show_in_launcher,
-            show_on_desktop: SYSTEM_UI_SHOWCASE_MANIFEST.show_on_desktop,
-            single_instance: SYSTEM_UI_SHOWCASE_MANIFEST.single_instance,
-            module: AppModule::new(mount_ui_showcase_app),
-            suspend_policy: SYSTEM_UI_SHOWCASE_MANIFEST.suspend_policy,
-            requested_capabilities: SYSTEM_UI_SHOWCASE_MANIFEST.requested_capabilities,
+            ..manifest_descriptor(APP_ID_SETTINGS, AppModule::new(mount_settings_app))
         },
+        manifest_descriptor(APP_ID_UI_SHOWCASE, AppModule::new(mount_ui_showcase_app)),
         AppDescriptor {
             app_id: builtin_app_id(APP_ID_DIALUP),
             launcher_label: "Dial-up",
@@ -159,6 +157,37 @@ fn build_app_registry() -> Vec<AppDescriptor> {
             module: AppModule::new(placeholders::mount_dialup_placeholder_app),
             suspend_policy: SuspendPolicy::OnMinimize,
             requested_capabilities: &[AppCapability::Window],
+            network_allowlist: &[],
+            quick_actions: &[],
+            storage_keys: &[],
+        },
+        AppDescriptor {
+            app_id: builtin_app_id(APP_ID_NOTIFICATIONS),
+            launcher_label: "Notifications",
+            desktop_icon_label: "Notifications",
+            show_in_launcher: true,
+            show_on_desktop: false,
+            single_instance: true,
+            module: AppModule::new(notification_center::mount_notification_center_app),
+            suspend_policy: SuspendPolicy::OnMinimize,
+            requested_capabilities: &[AppCapability::Window],
+            network_allowlist: &[],
+            quick_actions: &[],
+            storage_keys: &[],
+        },
+        AppDescriptor {
+            app_id: builtin_app_id(APP_ID_LOGS),
+            launcher_label: "Log Viewer",
+            desktop_icon_label: "Logs",
+            show_in_launcher: true,
+            show_on_desktop: false,
+            single_instance: true,
+            module: AppModule::new(log_viewer::mount_log_viewer_app),
+            suspend_policy: SuspendPolicy::OnMinimize,
+            requested_capabilities: &[AppCapability::Window],
+            network_allowlist: &[],
+            quick_actions: &[],
+            storage_keys: &[],
         },
     ]
 }
@@ -196,6 +225,18 @@ pub fn launcher_apps() -> Vec<AppDescriptor> {
         .collect()
 }
 
+/// Returns the launcher apps in the form injected into app services.
+pub fn launchable_apps() -> Vec<LaunchableApp> {
+    launcher_apps()
+        .into_iter()
+        .map(|entry| LaunchableApp {
+            app_id: entry.app_id,
+            display_name: entry.launcher_label.to_string(),
+            storage_keys: entry.storage_keys.to_vec(),
+        })
+        .collect()
+}
+
 /// Returns app descriptors that should appear as desktop icons.
 pub fn desktop_icon_apps() -> Vec<AppDescriptor> {
     app_registry()
@@ -232,6 +273,19 @@ pub fn app_requested_capabilities_by_id(app_id: &ApplicationId) -> &'static [App
     app_descriptor_by_id(app_id).requested_capabilities
 }
 
+/// Returns the URL prefixes `app_id` declared for network access.
+pub fn app_network_allowlist_by_id(app_id: &ApplicationId) -> &'static [String] {
+    app_descriptor_by_id(app_id).network_allowlist
+}
+
+/// Returns the taskbar quick actions `app_id` declared; unknown apps have none.
+pub fn app_quick_actions_by_id(app_id: &ApplicationId) -> &'static [AppQuickAction] {
+    app_registry()
+        .iter()
+        .find(|entry| &entry.app_id == app_id)
+        .map_or(&[], |entry| entry.quick_actions)
+}
+
 /// Returns whether `app_id` is privileged in shell policy.
 pub fn app_is_privileged_by_id(app_id: &ApplicationId) -> bool {
     BUILTIN_PRIVILEGED_APP_IDS
@@ -265,6 +319,8 @@ pub fn app_icon_id_by_id(app_id: &ApplicationId) -> &'static str {
         APP_ID_SETTINGS => "settings",
         APP_ID_UI_SHOWCASE => "window",
         APP_ID_DIALUP => "modem",
+        APP_ID_NOTIFICATIONS => "alert",
+        APP_ID_LOGS => "document-text",
         _ => "window",
     }
 }
@@ -280,6 +336,8 @@ pub fn app_icon_name_by_id(app_id: &ApplicationId) -> IconName {
         APP_ID_SETTINGS => IconName::Settings,
         APP_ID_UI_SHOWCASE => IconName::WindowMultiple,
         APP_ID_DIALUP => IconName::Connect,
+        APP_ID_NOTIFICATIONS => IconName::Alert,
+        APP_ID_LOGS => IconName::DocumentText,
         _ => IconName::WindowMultiple,
     }
 }
@@ -289,6 +347,11 @@ pub fn settings_application_id() -> ApplicationId {
     builtin_app_id(APP_ID_SETTINGS)
 }
 
+/// Returns the canonical notification center application id.
+pub fn notification_center_application_id() -> ApplicationId {
+    builtin_app_id(APP_ID_NOTIFICATIONS)
+}
+
 /// Returns whether `app_id` refers to the built-in dial-up app.
 pub fn is_dialup_application_id(app_id: &ApplicationId) -> bool {
     app_id.as_str() == APP_ID_DIALUP
@@ -334,61 +397,56 @@ fn default_window_rect_for_app(
         h: 760,
     });
 
-    let (min_w, min_h, max_w_ratio, max_h_ratio, default_w_ratio, default_h_ratio) =
+    let ((min_w, min_h), max_w_ratio, max_h_ratio, default_w_ratio, default_h_ratio) =
         match app_id.as_str() {
             APP_ID_EXPLORER => (
-                SYSTEM_EXPLORER_MANIFEST.window_defaults.0,
-                SYSTEM_EXPLORER_MANIFEST.window_defaults.1,
+                manifest_window_defaults(APP_ID_EXPLORER),
                 0.92,
                 0.92,
                 0.80,
                 0.78,
             ),
             APP_ID_NOTEPAD => (
-                SYSTEM_NOTEPAD_MANIFEST.window_defaults.0,
-                SYSTEM_NOTEPAD_MANIFEST.window_defaults.1,
+                manifest_window_defaults(APP_ID_NOTEPAD),
                 0.88,
                 0.88,
                 0.74,
                 0.74,
             ),
             APP_ID_TERMINAL => (
-                SYSTEM_TERMINAL_MANIFEST.window_defaults.0,
-                SYSTEM_TERMINAL_MANIFEST.window_defaults.1,
+                manifest_window_defaults(APP_ID_TERMINAL),
                 0.88,
                 0.86,
                 0.74,
                 0.70,
             ),
             APP_ID_SETTINGS => (
-                SYSTEM_SETTINGS_MANIFEST.window_defaults.0,
-                SYSTEM_SETTINGS_MANIFEST.window_defaults.1,
+                manifest_window_defaults(APP_ID_SETTINGS),
                 0.92,
                 0.92,
                 0.82,
                 0.82,
             ),
             APP_ID_UI_SHOWCASE => (
-                SYSTEM_UI_SHOWCASE_MANIFEST.window_defaults.0,
-                SYSTEM_UI_SHOWCASE_MANIFEST.window_defaults.1,
+                manifest_window_defaults(APP_ID_UI_SHOWCASE),
                 0.92,
                 0.92,
                 0.88,
                 0.88,
             ),
             APP_ID_CALCULATOR => (
-                SYSTEM_CALCULATOR_MANIFEST.window_defaults.0,
-                SYSTEM_CALCULATOR_MANIFEST.window_defaults.1,
+                manifest_window_defaults(APP_ID_CALCULATOR),
                 0.78,
                 0.86,
                 0.56,
                 0.74,
             ),
-            APP_ID_PAINT => (620, 420, 0.92, 0.92, 0.78, 0.78),
-            APP_ID_DIALUP => (420, 300, 0.66, 0.68, 0.48, 0.50),
+            APP_ID_PAINT => ((620, 420), 0.92, 0.92, 0.78, 0.78),
+            APP_ID_DIALUP => ((420, 300), 0.66, 0.68, 0.48, 0.50),
+            APP_ID_NOTIFICATIONS => ((360, 420), 0.50, 0.86, 0.32, 0.70),
+            APP_ID_LOGS => ((720, 440), 0.90, 0.86, 0.70, 0.70),
             _ => (
-                DEFAULT_WINDOW_WIDTH,
-                DEFAULT_WINDOW_HEIGHT,
+                (DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT),
                 0.80,
                 0.80,
                 0.70,
@@ -411,6 +469,59 @@ fn default_window_rect_for_app(
 mod default_open_request_tests {
     use super::*;
 
+    #[test]
+    fn builtin_manifests_validate_and_cover_manifest_apps() {
+        let ids = app_manifests()
+            .iter()
+            .map(|manifest| manifest.app_id.as_str())
+            .collect::<Vec<_>>();
+        for app_id in [
+            APP_ID_CALCULATOR,
+            APP_ID_EXPLORER,
+            APP_ID_NOTEPAD,
+            APP_ID_SETTINGS,
+            APP_ID_TERMINAL,
+            APP_ID_UI_SHOWCASE,
+        ] {
+            assert!(ids.contains(&app_id), "missing manifest for {app_id}");
+        }
+        assert_eq!(
+            app_title_by_id(&builtin_app_id(APP_ID_CALCULATOR)),
+            builtin_manifest(APP_ID_CALCULATOR).display_name
+        );
+    }
+
+    #[test]
+    fn builtin_apps_declare_the_storage_keys_they_write() {
+        let apps = launchable_apps();
+        let owner = |name: &str| {
+            apps.iter()
+                .filter(|app| app.owns_storage_key(name))
+                .map(|app| app.app_id.as_str())
+                .collect::<Vec<_>>()
+        };
+        for (name, app_id) in [
+            (platform_host::CALCULATOR_STATE_NAMESPACE, APP_ID_CALCULATOR),
+            (platform_host::NOTEPAD_STATE_NAMESPACE, APP_ID_NOTEPAD),
+            (platform_host::EXPLORER_STATE_NAMESPACE, APP_ID_EXPLORER),
+            (platform_host::EXPLORER_PREFS_KEY, APP_ID_EXPLORER),
+            (platform_host::EXPLORER_CACHE_NAME, APP_ID_EXPLORER),
+            (platform_host::TERMINAL_STATE_NAMESPACE, APP_ID_TERMINAL),
+            ("retrodesk.terminal.prefs.v1", APP_ID_TERMINAL),
+            ("retrodesk.terminal.profiles.v1", APP_ID_TERMINAL),
+            (crate::persistence::TERMINAL_HISTORY_KEY, APP_ID_TERMINAL),
+        ] {
+            assert_eq!(owner(name), [app_id], "owner of {name}");
+        }
+        for shared in [
+            platform_host::LOGS_PREFS_KEY,
+            platform_host::PERMISSIONS_PREFS_KEY,
+            platform_host::DESKTOP_STATE_NAMESPACE,
+        ] {
+            assert!(owner(shared).is_empty(), "{shared} is shared");
+        }
+    }
+
     #[test]
     fn default_open_request_by_id_scales_to_viewport() {
         let viewport = crate::model::WindowRect {
@@ -492,6 +603,7 @@ fn mount_terminal_app(context: AppMountContext) -> View {
             launch_params=context.launch_params.clone()
             restored_state=Some(context.restored_state.clone())
             services=Some(context.services)
+            lifecycle=Some(context.lifecycle)
         />
     }
     .into_view()
@@ -0,0 +1,176 @@
+//! Built-in log viewer listing what apps and the runtime recorded through `LogService`.
+
+use desktop_app_contract::AppMountContext;
+use leptos::*;
+use platform_host::{LogLevel, LogQuery, LogRecord};
+use system_ui::prelude::*;
+
+use crate::runtime_context::use_desktop_runtime;
+
+/// Mounts the log viewer app.
+pub(super) fn mount_log_viewer_app(_context: AppMountContext) -> View {
+    view! { <LogViewerApp /> }.into_view()
+}
+
+/// Wall-clock label such as `14:03:07.250` (UTC) for a record made at `unix_ms`.
+fn log_time_label(unix_ms: u64) -> String {
+    let millis = unix_ms % 1_000;
+    let seconds = unix_ms / 1_000;
+    format!(
+        "{:02}:{:02}:{:02}.{millis:03}",
+        seconds / 3_600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn log_level_tone(level: LogLevel) -> TextTone {
+    match level {
+        LogLevel::Debug => TextTone::Secondary,
+        LogLevel::Info => TextTone::Accent,
+        LogLevel::Warn => TextTone::Warning,
+        LogLevel::Error => TextTone::Danger,
+    }
+}
+
+fn log_row(record: LogRecord) -> impl IntoView {
+    let fields = (!record.fields.is_empty())
+        .then(|| serde_json::to_string(&record.fields).unwrap_or_default());
+    view! {
+        <tr>
+            <td>{log_time_label(record.unix_ms)}</td>
+            <td>
+                <Badge tone=log_level_tone(record.level)>{record.level.as_str()}</Badge>
+            </td>
+            <td>{record.source}</td>
+            <td>
+                <Text>{record.message}</Text>
+                {fields.map(|fields| view! { <Text tone=TextTone::Secondary>{fields}</Text> })}
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn LogViewerApp() -> impl IntoView {
+    let runtime = use_desktop_runtime();
+    let registry = store_value(runtime.host.get_value().log_registry());
+    let source = create_rw_signal(String::new());
+    let min_level = create_rw_signal(String::new());
+    let persistent = create_rw_signal(registry.with_value(|logs| logs.is_persistent()));
+    let sources = create_memo(move |_| {
+        runtime.log_revision.track();
+        registry.with_value(|logs| logs.sources())
+    });
+    let records = create_memo(move |_| {
+        runtime.log_revision.track();
+        let query = LogQuery {
+            source: source.with(|source| (!source.is_empty()).then(|| source.clone())),
+            min_level: min_level.with(|level| LogLevel::from_id(level)),
+            limit: None,
+        };
+        let mut records = registry.with_value(|logs| logs.tail(&query));
+        records.reverse();
+        records
+    });
+
+    let set_persistent = Callback::new(move |enabled: bool| {
+        persistent.set(enabled);
+        let logs = registry.get_value();
+        spawn_local(async move {
+            if let Err(err) = logs.set_persistent(enabled).await {
+                runtime
+                    .logger()
+                    .warn(format!("log persistence change failed: {err}"));
+            }
+        });
+    });
+    let clear = Callback::new(move |_| {
+        let logs = registry.get_value();
+        let source = source.get_untracked();
+        spawn_local(async move {
+            let source = (!source.is_empty()).then_some(source);
+            if let Err(err) = logs.clear(source.as_deref()).await {
+                runtime.logger().warn(format!("log clear failed: {err}"));
+            }
+            runtime.log_revision.update(|revision| *revision += 1);
+        });
+    });
+
+    view! {
+        <AppShell>
+            <ToolBar aria_label="Log filters">
+                <SelectField
+                    aria_label="Source"
+                    value=Signal::derive(move || source.get())
+                    on_change=Callback::new(move |ev| source.set(event_target_value(&ev)))
+                >
+                    <option value="">"All sources"</option>
+                    {move || {
+                        sources
+                            .get()
+                            .into_iter()
+                            .map(|source| view! { <option value=source.clone()>{source.clone()}</option> })
+                            .collect_view()
+                    }}
+                </SelectField>
+                <SelectField
+                    aria_label="Minimum level"
+                    value=Signal::derive(move || min_level.get())
+                    on_change=Callback::new(move |ev| min_level.set(event_target_value(&ev)))
+                >
+                    <option value="">"All levels"</option>
+                    {LogLevel::ALL
+                        .into_iter()
+                        .map(|level| view! { <option value=level.as_str()>{level.as_str()}</option> })
+                        .collect_view()}
+                </SelectField>
+                <Switch
+                    aria_label="Keep logs across reloads"
+                    checked=Signal::derive(move || persistent.get())
+                    on_toggle=set_persistent
+                />
+                <Text tone=TextTone::Secondary>"Keep across reloads"</Text>
+                <Button
+                    variant=ButtonVariant::Quiet
+                    disabled=Signal::derive(move || records.with(Vec::is_empty))
+                    on_click=clear
+                >
+                    "Clear"
+                </Button>
+            </ToolBar>
+            {move || {
+                let records = records.get();
+                if records.is_empty() {
+                    return view! { <EmptyState>"No log records"</EmptyState> }.into_view();
+                }
+                view! {
+                    <DataTable aria_label="Log records">
+                        <thead>
+                            <tr>
+                                <th>"Time (UTC)"</th>
+                                <th>"Level"</th>
+                                <th>"Source"</th>
+                                <th>"Message"</th>
+                            </tr>
+                        </thead>
+                        <tbody>{records.into_iter().map(log_row).collect_view()}</tbody>
+                    </DataTable>
+                }
+                .into_view()
+            }}
+        </AppShell>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_time_label_shows_utc_time_of_day_with_millis() {
+        assert_eq!(log_time_label(0), "00:00:00.000");
+        assert_eq!(log_time_label(1_700_000_000_250), "22:13:20.250");
+        assert_eq!(log_time_label(86_399_999), "23:59:59.999");
+    }
+}
@@ -0,0 +1,172 @@
+//! Built-in notification center listing what apps posted through `NotificationService`.
+
+use desktop_app_contract::AppMountContext;
+use leptos::*;
+use platform_host::unix_time_ms_now;
+use system_ui::prelude::*;
+
+use crate::{
+    apps, model::NotificationRecord, reducer::DesktopAction, runtime_context::use_desktop_runtime,
+};
+
+/// Mounts the notification center app.
+pub(super) fn mount_notification_center_app(_context: AppMountContext) -> View {
+    view! { <NotificationCenterApp /> }.into_view()
+}
+
+/// Short age label such as `5 min ago` for a notification posted at `posted_unix_ms`.
+fn notification_age(now_unix_ms: u64, posted_unix_ms: u64) -> String {
+    let minutes = now_unix_ms.saturating_sub(posted_unix_ms) / 60_000;
+    match minutes {
+        0 => "Just now".to_string(),
+        1..=59 => format!("{minutes} min ago"),
+        60..=1_439 => format!("{} h ago", minutes / 60),
+        _ => format!("{} d ago", minutes / 1_440),
+    }
+}
+
+/// Icon a notification asked for, or the icon of the app that posted it.
+fn notification_icon(record: &NotificationRecord) -> IconName {
+    record
+        .notification
+        .icon
+        .as_deref()
+        .and_then(IconName::from_token)
+        .unwrap_or_else(|| apps::app_icon_name_by_id(&record.app_id))
+}
+
+#[component]
+fn NotificationCenterApp() -> impl IntoView {
+    let runtime = use_desktop_runtime();
+    let notifications = create_memo(move |_| {
+        runtime.state.with(|state| {
+            let mut notifications = state.notifications.clone();
+            notifications.reverse();
+            notifications
+        })
+    });
+    // Everything listed here counts as seen, including notifications posted while it is open.
+    create_effect(move |_| {
+        if notifications.with(|notifications| notifications.iter().any(|record| !record.read)) {
+            spawn_local(async move {
+                runtime.dispatch_action(DesktopAction::MarkNotificationsRead);
+            });
+        }
+    });
+
+    view! {
+        <AppShell>
+            <ToolBar aria_label="Notification actions">
+                <Button
+                    variant=ButtonVariant::Quiet
+                    disabled=Signal::derive(move || notifications.with(Vec::is_empty))
+                    on_click=Callback::new(move |_| {
+                        runtime.dispatch_action(DesktopAction::ClearNotifications)
+                    })
+                >
+                    "Clear all"
+                </Button>
+            </ToolBar>
+            <ListSurface role="list" aria_label="Notifications">
+                {move || {
+                    let now = unix_time_ms_now();
+                    let notifications = notifications.get();
+                    if notifications.is_empty() {
+                        return view! { <EmptyState>"No notifications"</EmptyState> }.into_view();
+                    }
+                    notifications
+                        .into_iter()
+                        .map(|record| {
+                            let id = record.id;
+                            let icon = notification_icon(&record);
+                            let age = notification_age(now, record.posted_unix_ms);
+                            let actions = record
+                                .notification
+                                .actions
+                                .iter()
+                                .cloned()
+                                .enumerate()
+                                .map(|(index, action)| {
+                                    let label = action.label;
+                                    view! {
+                                        <Button
+                                            size=ButtonSize::Sm
+                                            on_click=Callback::new(move |_| {
+                                                runtime.dispatch_action(
+                                                    DesktopAction::ActivateNotification {
+                                                        notification_id: id,
+                                                        action: Some(index),
+                                                    },
+                                                )
+                                            })
+                                        >
+                                            {label}
+                                        </Button>
+                                    }
+                                })
+                                .collect_view();
+                            view! {
+                                <div role="listitem">
+                                    <Card>
+                                        <Stack gap=LayoutGap::Sm>
+                                            <Cluster justify=LayoutJustify::Between>
+                                                <Button
+                                                    variant=ButtonVariant::Quiet
+                                                    leading_icon=icon
+                                                    on_click=Callback::new(move |_| {
+                                                        runtime.dispatch_action(
+                                                            DesktopAction::ActivateNotification {
+                                                                notification_id: id,
+                                                                action: None,
+                                                            },
+                                                        )
+                                                    })
+                                                >
+                                                    {record.notification.title.clone()}
+                                                </Button>
+                                                <Cluster gap=LayoutGap::Sm>
+                                                    <Text tone=TextTone::Secondary>{age}</Text>
+                                                    <IconButton
+                                                        icon=IconName::Dismiss
+                                                        aria_label="Dismiss notification"
+                                                        on_click=Callback::new(move |_| {
+                                                            runtime.dispatch_action(
+                                                                DesktopAction::DismissNotification {
+                                                                    notification_id: id,
+                                                                },
+                                                            )
+                                                        })
+                                                    />
+                                                </Cluster>
+                                            </Cluster>
+                                            <Text>{record.notification.body.clone()}</Text>
+                                            <Cluster gap=LayoutGap::Sm>{actions}</Cluster>
+                                        </Stack>
+                                    </Card>
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ListSurface>
+        </AppShell>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notification_age_rounds_down_to_the_largest_unit() {
+        let posted = 1_000_000;
+        assert_eq!(notification_age(posted + 30_000, posted), "Just now");
+        assert_eq!(notification_age(posted + 5 * 60_000, posted), "5 min ago");
+        assert_eq!(notification_age(posted + 150 * 60_000, posted), "2 h ago");
+        assert_eq!(
+            notification_age(posted + 3 * 1_440 * 60_000, posted),
+            "3 d ago"
+        );
+        assert_eq!(notification_age(posted - 1, posted), "Just now");
+    }
+}
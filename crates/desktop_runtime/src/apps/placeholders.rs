@@ -269,7 +269,9 @@ where
         let serialized = match serde_json::to_string(&snapshot) {
             Ok(raw) => raw,
             Err(err) => {
-                logging::warn!("utility app serialize failed: {err}");
+                services
+                    .logs
+                    .warn(format!("utility app serialize failed: {err}"));
                 return;
             }
         };
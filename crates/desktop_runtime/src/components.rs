@@ -1,10 +1,12 @@
 //! Desktop shell UI composition and interaction surfaces.
 
 mod a11y;
+mod dialogs;
 mod menus;
 mod taskbar;
 mod taskbar_input;
 mod window;
+mod window_switcher;
 
 use std::time::Duration;
 
@@ -23,13 +25,14 @@ use self::{
     taskbar::Taskbar,
     taskbar_input::{is_activation_key, is_context_menu_shortcut, try_handle_taskbar_shortcuts},
     window::DesktopWindow,
+    window_switcher::WindowSwitcher,
 };
 
 use crate::{
     apps,
     e2e::{BrowserE2eConfig, BrowserE2eScene},
     host::DesktopHostContext,
-    model::{DesktopState, PointerPosition, ResizeEdge, WindowId, WindowRecord},
+    model::{DesktopState, PointerPosition, ResizeEdge, WindowId, WindowRecord, WORKSPACE_COUNT},
     reducer::DesktopAction,
     runtime_context::open_system_settings,
     wallpaper,
@@ -47,6 +50,11 @@ fn app_icon_name(app_id: &ApplicationId) -> IconName {
     apps::app_icon_name_by_id(app_id)
 }
 
+/// Icon the window's app chose with `WindowService::set_icon`, or the app's own icon.
+fn window_icon_name(win: &WindowRecord) -> IconName {
+    IconName::from_token(&win.icon_id).unwrap_or_else(|| app_icon_name(&win.app_id))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct DesktopContextMenuState {
     x: i32,
@@ -519,6 +527,8 @@ pub fn DesktopShell() -> impl IntoView {
                     desktop_context_menu
                     open_system_settings
                 />
+
+                <WindowSwitcher runtime />
             </DesktopBackdrop>
 
             <Taskbar />
@@ -587,6 +597,13 @@ struct TaskbarWindowContextMenuState {
     y: i32,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TaskbarAppContextMenuState {
+    app_id: ApplicationId,
+    x: i32,
+    y: i32,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 struct PinnedTaskbarAppState {
     running_count: usize,
@@ -603,6 +620,7 @@ enum TaskbarShortcutTarget {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TaskbarTrayWidgetAction {
     None,
+    OpenNotificationCenter,
     ToggleHighContrast,
     ToggleReducedMotion,
 }
@@ -621,12 +639,41 @@ fn pinned_taskbar_apps() -> Vec<ApplicationId> {
     apps::pinned_taskbar_app_ids()
 }
 
+/// Windows on the active workspace in taskbar order.
 fn ordered_taskbar_windows(state: &DesktopState) -> Vec<WindowRecord> {
-    let mut windows = state.windows.clone();
+    let mut windows: Vec<WindowRecord> = state
+        .windows
+        .iter()
+        .filter(|win| state.is_on_active_workspace(win))
+        .cloned()
+        .collect();
     windows.sort_by_key(|win| (win.z_index, win.id.0));
     windows
 }
 
+/// User-facing name of `workspace`, counting from one.
+fn workspace_label(workspace: u32) -> String {
+    format!("Workspace {}", workspace + 1)
+}
+
+/// Accessible name of a workspace switcher button.
+fn workspace_switcher_aria_label(state: &DesktopState, workspace: u32) -> String {
+    let count = state
+        .windows
+        .iter()
+        .filter(|win| win.workspace == workspace)
+        .count();
+    match count {
+        1 => format!("{} (1 window)", workspace_label(workspace)),
+        count => format!("{} ({count} windows)", workspace_label(workspace)),
+    }
+}
+
+/// Workspace `delta` steps from the active one, wrapping around.
+fn adjacent_workspace(state: &DesktopState, delta: i32) -> u32 {
+    (state.active_workspace as i32 + delta).rem_euclid(WORKSPACE_COUNT as i32) as u32
+}
+
 pub(crate) fn preferred_window_for_app(
     state: &DesktopState,
     app_id: &ApplicationId,
@@ -781,6 +828,9 @@ fn taskbar_window_aria_label(win: &WindowRecord) -> String {
     if win.maximized {
         parts.push("maximized".to_string());
     }
+    if win.badge_count > 0 {
+        parts.push(format!("{} new", win.badge_count));
+    }
     parts.join(", ")
 }
 
@@ -907,10 +957,22 @@ fn open_taskbar_window_context_menu(
     x: i32,
     y: i32,
 ) {
-    let (x, y) = clamp_taskbar_popup_position(host, x, y, 220, 190);
+    let (x, y) = clamp_taskbar_popup_position(host, x, y, 220, 300);
     menu.set(Some(TaskbarWindowContextMenuState { window_id, x, y }));
 }
 
+fn open_taskbar_app_context_menu(
+    host: DesktopHostContext,
+    menu: RwSignal<Option<TaskbarAppContextMenuState>>,
+    app_id: ApplicationId,
+    x: i32,
+    y: i32,
+) {
+    let rows = apps::app_quick_actions_by_id(&app_id).len() as i32 + 1;
+    let (x, y) = clamp_taskbar_popup_position(host, x, y, 220, 16 + rows * 32);
+    menu.set(Some(TaskbarAppContextMenuState { app_id, x, y }));
+}
+
 fn clamp_taskbar_popup_position(
     host: DesktopHostContext,
     x: i32,
@@ -932,7 +994,21 @@ fn build_taskbar_tray_widgets(state: &DesktopState) -> Vec<TaskbarTrayWidget> {
         .iter()
         .any(|win| apps::is_dialup_application_id(&win.app_id) && !win.minimized);
 
+    let unread_notifications = state
+        .notifications
+        .iter()
+        .filter(|record| !record.read)
+        .count();
+
     vec![
+        TaskbarTrayWidget {
+            id: "notifications",
+            icon: IconName::Alert,
+            label: "Unread notifications",
+            value: unread_notifications.to_string(),
+            pressed: None,
+            action: TaskbarTrayWidgetAction::OpenNotificationCenter,
+        },
         TaskbarTrayWidget {
             id: "win-count",
             icon: IconName::WindowMultiple,
@@ -1001,6 +1077,16 @@ fn build_taskbar_tray_widgets(state: &DesktopState) -> Vec<TaskbarTrayWidget> {
 fn activate_taskbar_tray_widget(runtime: DesktopRuntimeContext, action: TaskbarTrayWidgetAction) {
     match action {
         TaskbarTrayWidgetAction::None => {}
+        TaskbarTrayWidgetAction::OpenNotificationCenter => {
+            let viewport = runtime
+                .host
+                .get_value()
+                .desktop_viewport_rect(TASKBAR_HEIGHT_PX);
+            runtime.dispatch_action(DesktopAction::ActivateApp {
+                app_id: apps::notification_center_application_id(),
+                viewport: Some(viewport),
+            });
+        }
         TaskbarTrayWidgetAction::ToggleHighContrast => {
             let enabled = runtime.state.get_untracked().theme.high_contrast;
             runtime.dispatch_action(DesktopAction::SetHighContrast { enabled: !enabled });
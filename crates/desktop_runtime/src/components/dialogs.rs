@@ -0,0 +1,382 @@
+//! Modal dialogs the window manager shows over an app window for [`DialogService`] requests.
+
+use std::{cell::RefCell, rc::Rc};
+
+use desktop_app_contract::{DialogRequest, DialogResponse, DialogService};
+use futures::channel::oneshot;
+use leptos::*;
+use platform_host::{ExplorerEntry, ExplorerEntryKind, ExplorerFsService};
+use system_shell::resolve_path;
+use system_ui::{
+    Button, ButtonVariant, Cluster, Heading, IconName, LayoutJustify, ListSurface, Modal, Text,
+    TextField, TextRole, TextTone,
+};
+
+/// Dialog showing over a window and the channel its answer goes back through.
+#[derive(Clone)]
+pub(super) struct PendingDialog {
+    request: DialogRequest,
+    reply: Rc<RefCell<Option<oneshot::Sender<DialogResponse>>>>,
+}
+
+impl PendingDialog {
+    fn answer(&self, response: DialogResponse) {
+        if let Some(reply) = self.reply.borrow_mut().take() {
+            let _ = reply.send(response);
+        }
+    }
+}
+
+/// Builds the dialog service for one window, showing its requests through `pending`.
+///
+/// A new request cancels the dialog already showing, and closing the window cancels whatever is
+/// still open, so every request resolves.
+pub(super) fn window_dialog_service(pending: RwSignal<Option<PendingDialog>>) -> DialogService {
+    on_cleanup(move || {
+        if let Some(dialog) = pending.try_get_untracked().flatten() {
+            dialog.answer(DialogResponse::Cancelled);
+        }
+    });
+    DialogService::new(Rc::new(move |request| {
+        let (reply, answer) = oneshot::channel();
+        let dialog = PendingDialog {
+            request,
+            reply: Rc::new(RefCell::new(Some(reply))),
+        };
+        pending.try_update(|pending| {
+            if let Some(previous) = pending.replace(dialog) {
+                previous.answer(DialogResponse::Cancelled);
+            }
+        });
+        Box::pin(async move { answer.await.unwrap_or(DialogResponse::Cancelled) })
+    }))
+}
+
+#[component]
+/// Backdrop and modal for the window's pending dialog, if any.
+pub(super) fn WindowDialog(
+    pending: RwSignal<Option<PendingDialog>>,
+    fs: Rc<dyn ExplorerFsService>,
+) -> impl IntoView {
+    let fs = store_value(fs);
+    move || {
+        pending.get().map(|dialog| {
+            let backdrop = create_node_ref::<html::Div>();
+            let finish = Callback::new({
+                let dialog = dialog.clone();
+                move |response: DialogResponse| {
+                    dialog.answer(response);
+                    pending.set(None);
+                }
+            });
+            let body = match dialog.request.clone() {
+                DialogRequest::Confirm {
+                    title,
+                    message,
+                    confirm_label,
+                    destructive,
+                } => {
+                    // Confirmations focus the backdrop rather than a button, so a stray Enter
+                    // never confirms; Escape still cancels.
+                    focus_on_mount(backdrop);
+                    view! {
+                        <ConfirmDialog
+                            title=title
+                            message=message
+                            confirm_label=confirm_label
+                            destructive=destructive
+                            finish=finish
+                        />
+                    }
+                    .into_view()
+                }
+                DialogRequest::Prompt {
+                    title,
+                    message,
+                    value,
+                } => view! {
+                    <PromptDialog title=title message=message value=value finish=finish />
+                }
+                .into_view(),
+                DialogRequest::OpenFile { title, folder } => view! {
+                    <FileDialog
+                        title=title
+                        folder=folder
+                        file_name=String::new()
+                        save=false
+                        fs=fs.get_value()
+                        finish=finish
+                    />
+                }
+                .into_view(),
+                DialogRequest::SaveFile {
+                    title,
+                    folder,
+                    file_name,
+                } => view! {
+                    <FileDialog
+                        title=title
+                        folder=folder
+                        file_name=file_name
+                        save=true
+                        fs=fs.get_value()
+                        finish=finish
+                    />
+                }
+                .into_view(),
+            };
+            view! {
+                <div
+                    data-ui-slot="dialog-backdrop"
+                    node_ref=backdrop
+                    tabindex="-1"
+                    on:keydown=move |ev: ev::KeyboardEvent| {
+                        ev.stop_propagation();
+                        if ev.key() == "Escape" {
+                            ev.prevent_default();
+                            finish.call(DialogResponse::Cancelled);
+                        }
+                    }
+                    on:mousedown=move |ev: ev::MouseEvent| ev.stop_propagation()
+                >
+                    {body}
+                </div>
+            }
+        })
+    }
+}
+
+/// Focuses `node` once it is in the document.
+fn focus_on_mount(node: NodeRef<html::Div>) {
+    request_animation_frame(move || {
+        if let Some(element) = node.get_untracked() {
+            let _ = element.focus();
+        }
+    });
+}
+
+/// Focuses and selects the text of `input` once it is in the document.
+fn select_on_mount(input: NodeRef<html::Input>) {
+    request_animation_frame(move || {
+        if let Some(input) = input.get_untracked() {
+            let _ = input.focus();
+            input.select();
+        }
+    });
+}
+
+#[component]
+fn ConfirmDialog(
+    title: String,
+    message: String,
+    confirm_label: String,
+    destructive: bool,
+    finish: Callback<DialogResponse>,
+) -> impl IntoView {
+    view! {
+        <Modal aria_label=title.clone()>
+            <Heading role=TextRole::Title>{title}</Heading>
+            <Text>{message}</Text>
+            <Cluster justify=LayoutJustify::End>
+                <Button on_click=Callback::new(move |_| finish.call(DialogResponse::Cancelled))>
+                    "Cancel"
+                </Button>
+                <Button
+                    variant=if destructive { ButtonVariant::Danger } else { ButtonVariant::Primary }
+                    on_click=Callback::new(move |_| finish.call(DialogResponse::Confirmed))
+                >
+                    {confirm_label}
+                </Button>
+            </Cluster>
+        </Modal>
+    }
+}
+
+#[component]
+fn PromptDialog(
+    title: String,
+    message: String,
+    value: String,
+    finish: Callback<DialogResponse>,
+) -> impl IntoView {
+    let draft = create_rw_signal(value);
+    let input = create_node_ref::<html::Input>();
+    select_on_mount(input);
+    let submit = move || finish.call(DialogResponse::Text(draft.get_untracked()));
+    view! {
+        <Modal aria_label=title.clone()>
+            <Heading role=TextRole::Title>{title}</Heading>
+            <Text tone=TextTone::Secondary>{message}</Text>
+            <TextField
+                aria_label="Value"
+                node_ref=input
+                value=Signal::derive(move || draft.get())
+                on_input=Callback::new(move |ev| draft.set(event_target_value(&ev)))
+                on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+                    if ev.key() == "Enter" {
+                        ev.prevent_default();
+                        submit();
+                    }
+                })
+            />
+            <Cluster justify=LayoutJustify::End>
+                <Button on_click=Callback::new(move |_| finish.call(DialogResponse::Cancelled))>
+                    "Cancel"
+                </Button>
+                <Button variant=ButtonVariant::Primary on_click=Callback::new(move |_| submit())>
+                    "OK"
+                </Button>
+            </Cluster>
+        </Modal>
+    }
+}
+
+#[component]
+/// Folder browser that picks an existing file to open or a path to save to.
+///
+/// Clicking a folder enters it and clicking a file fills in its name. A typed name that names a
+/// folder enters that folder instead of being picked.
+fn FileDialog(
+    title: String,
+    folder: String,
+    file_name: String,
+    save: bool,
+    fs: Rc<dyn ExplorerFsService>,
+    finish: Callback<DialogResponse>,
+) -> impl IntoView {
+    let fs = store_value(fs);
+    let folder = create_rw_signal(resolve_path("/", &folder));
+    let name = create_rw_signal(file_name);
+    let entries = create_rw_signal(Ok::<Vec<ExplorerEntry>, String>(Vec::new()));
+    let error = create_rw_signal::<Option<String>>(None);
+    let input = create_node_ref::<html::Input>();
+    select_on_mount(input);
+
+    create_effect(move |_| {
+        let path = folder.get();
+        let fs = fs.get_value();
+        spawn_local(async move {
+            let listing = fs.list_dir(&path).await.map(|listing| listing.entries);
+            // Drop listings for a folder the dialog has since left or a dialog that has closed.
+            if folder.try_get_untracked().as_deref() == Some(path.as_str()) {
+                entries.set(listing);
+            }
+        });
+    });
+    let enter = move |path: String| {
+        error.set(None);
+        folder.set(path);
+    };
+    let submit = move || {
+        let typed = name.get_untracked();
+        if typed.trim().is_empty() {
+            return;
+        }
+        let path = resolve_path(&folder.get_untracked(), &typed);
+        let fs = fs.get_value();
+        spawn_local(async move {
+            let kind = fs.stat(&path).await.ok().map(|meta| meta.kind);
+            match kind {
+                Some(ExplorerEntryKind::Directory) => {
+                    name.set(String::new());
+                    enter(path);
+                }
+                Some(ExplorerEntryKind::File) => finish.call(DialogResponse::Path(path)),
+                None if save => finish.call(DialogResponse::Path(path)),
+                None => error.set(Some(format!("No file at {path}"))),
+            }
+        });
+    };
+
+    view! {
+        <Modal aria_label=title.clone()>
+            <Heading role=TextRole::Title>{title}</Heading>
+            <Cluster justify=LayoutJustify::Between>
+                <Text role=TextRole::Label>{move || folder.get()}</Text>
+                <Button
+                    variant=ButtonVariant::Quiet
+                    disabled=Signal::derive(move || folder.get() == "/")
+                    on_click=Callback::new(move |_| enter(resolve_path(&folder.get_untracked(), "..")))
+                >
+                    "Up"
+                </Button>
+            </Cluster>
+            <ListSurface role="list" aria_label="Folder contents">
+                {move || match entries.get() {
+                    Err(err) => view! { <Text tone=TextTone::Secondary>{err}</Text> }.into_view(),
+                    Ok(entries) if entries.is_empty() => {
+                        view! { <Text tone=TextTone::Secondary>"This folder is empty"</Text> }
+                            .into_view()
+                    }
+                    Ok(entries) => entries
+                        .into_iter()
+                        .map(|entry| {
+                            let is_dir = entry.kind == ExplorerEntryKind::Directory;
+                            let pick = {
+                                let entry = entry.clone();
+                                move |_| {
+                                    if is_dir {
+                                        enter(entry.path.clone());
+                                    } else {
+                                        error.set(None);
+                                        name.set(entry.name.clone());
+                                    }
+                                }
+                            };
+                            view! {
+                                <div role="listitem">
+                                    <Button
+                                        variant=ButtonVariant::Quiet
+                                        leading_icon=if is_dir {
+                                            IconName::ExplorerFolder
+                                        } else {
+                                            IconName::DocumentText
+                                        }
+                                        on_click=Callback::new(pick)
+                                        on_dblclick=Callback::new(move |_| {
+                                            if !is_dir {
+                                                submit();
+                                            }
+                                        })
+                                    >
+                                        {entry.name.clone()}
+                                    </Button>
+                                </div>
+                            }
+                        })
+                        .collect_view(),
+                }}
+            </ListSurface>
+            <TextField
+                aria_label="File name"
+                node_ref=input
+                spellcheck=false
+                autocomplete="off"
+                value=Signal::derive(move || name.get())
+                on_input=Callback::new(move |ev| {
+                    name.set(event_target_value(&ev));
+                    error.set(None);
+                })
+                on_keydown=Callback::new(move |ev: ev::KeyboardEvent| {
+                    if ev.key() == "Enter" {
+                        ev.prevent_default();
+                        submit();
+                    }
+                })
+            />
+            {move || error.get().map(|err| view! { <Text tone=TextTone::Secondary>{err}</Text> })}
+            <Cluster justify=LayoutJustify::End>
+                <Button on_click=Callback::new(move |_| finish.call(DialogResponse::Cancelled))>
+                    "Cancel"
+                </Button>
+                <Button
+                    variant=ButtonVariant::Primary
+                    disabled=Signal::derive(move || name.with(|name| name.trim().is_empty()))
+                    on_click=Callback::new(move |_| submit())
+                >
+                    {if save { "Save" } else { "Open" }}
+                </Button>
+            </Cluster>
+        </Modal>
+    }
+}
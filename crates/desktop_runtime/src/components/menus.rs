@@ -1,5 +1,6 @@
 use super::*;
-use crate::wallpaper;
+use crate::{reducer::launch_app_action, wallpaper};
+use desktop_app_contract::AppQuickAction;
 use leptos::ev::MouseEvent;
 use platform_host::{WallpaperConfig, WallpaperMediaKind, WallpaperSelection};
 use system_ui::{ButtonVariant, Icon, IconName, IconSize, MenuItem, MenuSeparator, MenuSurface};
@@ -281,7 +282,7 @@ pub(super) fn OverflowMenu(
                     >
                         <span aria-hidden="true">
                             <Icon
-                                icon=app_icon_name(&win.app_id)
+                                icon=window_icon_name(&win)
                                 size=IconSize::Sm
                             />
                         </span>
@@ -393,6 +394,31 @@ pub(super) fn TaskbarWindowContextMenu(
                     "Restore Size"
                 };
                 let window_id = win.id;
+                let move_items = (0..WORKSPACE_COUNT)
+                    .filter(|workspace| *workspace != win.workspace)
+                    .map(|workspace| {
+                        view! {
+                            <MenuItem
+                                id=format!("taskbar-window-menu-workspace-{}-{}", window_id.0, workspace)
+                                role="menuitem"
+                                on_click=Callback::new(move |_| {
+                                    window_context_menu.set(None);
+                                    runtime.dispatch_action(DesktopAction::MoveWindowToWorkspace {
+                                        window_id,
+                                        workspace,
+                                    });
+                                })
+                            >
+                                {format!("Move to {}", workspace_label(workspace))}
+                            </MenuItem>
+                        }
+                    })
+                    .collect_view();
+                let quick_actions = apps::app_quick_actions_by_id(&win.app_id);
+                let quick_action_items =
+                    taskbar_quick_action_items(runtime, &win.app_id, quick_actions, move || {
+                        window_context_menu.set(None)
+                    });
 
                 view! {
                     <MenuSurface
@@ -416,6 +442,8 @@ pub(super) fn TaskbarWindowContextMenu(
                         })
                         on_mousedown=Callback::new(move |ev: MouseEvent| ev.stop_propagation())
                     >
+                        {quick_action_items}
+                        {(!quick_actions.is_empty()).then(|| view! { <MenuSeparator /> })}
                         <MenuItem
                             id=format!("taskbar-window-menu-focus-{}", window_id.0)
                             role="menuitem"
@@ -464,13 +492,14 @@ pub(super) fn TaskbarWindowContextMenu(
                         >
                             "Maximize"
                         </MenuItem>
+                        {move_items}
                         <MenuItem
                             id=format!("taskbar-window-menu-close-{}", window_id.0)
                             role="menuitem"
                             variant=ButtonVariant::Danger
                             on_click=Callback::new(move |_| {
                                 window_context_menu.set(None);
-                                runtime.dispatch_action(DesktopAction::CloseWindow { window_id });
+                                runtime.request_close_window(window_id);
                             })
                         >
                             "Close"
@@ -482,3 +511,114 @@ pub(super) fn TaskbarWindowContextMenu(
         </Show>
     }
 }
+
+/// Menu items launching the manifest quick actions of `app_id`; `close` dismisses the host menu.
+fn taskbar_quick_action_items(
+    runtime: DesktopRuntimeContext,
+    app_id: &ApplicationId,
+    quick_actions: &'static [AppQuickAction],
+    close: impl Fn() + Copy + 'static,
+) -> View {
+    quick_actions
+        .iter()
+        .map(|quick_action| {
+            let app_id = app_id.clone();
+            let action_id = quick_action.id.clone();
+            let icon = quick_action_icon(quick_action, &app_id);
+            let item_id = format!(
+                "taskbar-quick-action-{}-{}",
+                apps::app_icon_id_by_id(&app_id),
+                quick_action.id
+            );
+            view! {
+                <MenuItem
+                    id=item_id
+                    role="menuitem"
+                    on_click=Callback::new(move |_| {
+                        close();
+                        runtime.dispatch_action(DesktopAction::LaunchQuickAction {
+                            app_id: app_id.clone(),
+                            action_id: action_id.clone(),
+                        });
+                    })
+                >
+                    <span aria-hidden="true">
+                        <Icon icon=icon size=IconSize::Sm />
+                    </span>
+                    <span>{quick_action.label.clone()}</span>
+                </MenuItem>
+            }
+        })
+        .collect_view()
+}
+
+/// Icon a quick action asked for, or the icon of the app that declared it.
+fn quick_action_icon(quick_action: &AppQuickAction, app_id: &ApplicationId) -> IconName {
+    quick_action
+        .icon
+        .as_deref()
+        .and_then(IconName::from_token)
+        .unwrap_or_else(|| app_icon_name(app_id))
+}
+
+#[component]
+pub(super) fn TaskbarAppContextMenu(
+    runtime: DesktopRuntimeContext,
+    app_context_menu: RwSignal<Option<TaskbarAppContextMenuState>>,
+) -> impl IntoView {
+    move || {
+        let menu = app_context_menu.get()?;
+        let app_id = menu.app_id.clone();
+        let title = apps::app_title_by_id(&app_id);
+        let quick_action_items = taskbar_quick_action_items(
+            runtime,
+            &app_id,
+            apps::app_quick_actions_by_id(&app_id),
+            move || app_context_menu.set(None),
+        );
+        let launch_label = if apps::app_descriptor_by_id(&app_id).single_instance {
+            format!("Open {title}")
+        } else {
+            format!("New {title} window")
+        };
+        let app_id_for_launch = app_id.clone();
+        Some(view! {
+            <MenuSurface
+                id="taskbar-app-context-menu"
+                role="menu"
+                aria_label=format!("Quick actions for {title}")
+                style=format!("left:{}px;top:{}px;", menu.x, menu.y)
+                on_keydown=Callback::new(move |ev: web_sys::KeyboardEvent| {
+                    if handle_menu_roving_keydown(&ev, "taskbar-app-context-menu") {
+                        return;
+                    }
+                    if ev.key() == "Escape" {
+                        ev.prevent_default();
+                        ev.stop_propagation();
+                        app_context_menu.set(None);
+                    }
+                })
+                on_mousedown=Callback::new(move |ev: MouseEvent| ev.stop_propagation())
+            >
+                {quick_action_items}
+                <MenuItem
+                    id=format!("taskbar-app-menu-open-{}", apps::app_icon_id_by_id(&app_id))
+                    role="menuitem"
+                    on_click=Callback::new(move |_| {
+                        app_context_menu.set(None);
+                        if let Some(action) = launch_app_action(
+                            &runtime.state.get_untracked(),
+                            app_id_for_launch.clone(),
+                            Value::Null,
+                            false,
+                        ) {
+                            runtime.dispatch_action(action);
+                        }
+                    })
+                >
+                    {launch_label}
+                </MenuItem>
+            </MenuSurface>
+        })
+    }
+}
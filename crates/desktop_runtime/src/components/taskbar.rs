@@ -23,6 +23,7 @@ pub(super) fn Taskbar() -> impl IntoView {
     let clock_now = create_rw_signal(TaskbarClockSnapshot::now());
     let selected_running_window = create_rw_signal(None::<WindowId>);
     let window_context_menu = create_rw_signal(None::<TaskbarWindowContextMenuState>);
+    let app_context_menu = create_rw_signal(None::<TaskbarAppContextMenuState>);
     let overflow_menu_open = create_rw_signal(false);
     let clock_menu_open = create_rw_signal(false);
     let start_menu_was_open = create_rw_signal(false);
@@ -68,6 +69,9 @@ pub(super) fn Taskbar() -> impl IntoView {
         if had_window_menu {
             window_context_menu.set(None);
         }
+        if app_context_menu.get_untracked().is_some() {
+            app_context_menu.set(None);
+        }
         if had_overflow_menu {
             overflow_menu_open.set(false);
         }
@@ -259,7 +263,7 @@ pub(super) fn Taskbar() -> impl IntoView {
         <SystemTaskbar
             role="toolbar"
             aria_label="Desktop taskbar"
-            aria_keyshortcuts="Ctrl+Escape Alt+1 Alt+2 Alt+3 Alt+4 Alt+5 Alt+6 Alt+7 Alt+8 Alt+9"
+            aria_keyshortcuts="Ctrl+Escape Alt+1 Alt+2 Alt+3 Alt+4 Alt+5 Alt+6 Alt+7 Alt+8 Alt+9 Ctrl+Alt+ArrowLeft Ctrl+Alt+ArrowRight"
             on_mousedown=Callback::new(move |ev: MouseEvent| ev.stop_propagation())
             on_keydown=Callback::new(on_taskbar_keydown)
         >
@@ -298,6 +302,7 @@ pub(super) fn Taskbar() -> impl IntoView {
                                 let app_id_for_title = app_id.clone();
                                 let app_id_for_aria = app_id.clone();
                                 let app_id_for_click = app_id.clone();
+                                let app_id_for_menu = app_id.clone();
                                 let app_icon_name_value = app_icon_name(&app_id);
                                 let app_data_id = apps::app_icon_id_by_id(&app_id).to_string();
                                 view! {
@@ -327,11 +332,27 @@ pub(super) fn Taskbar() -> impl IntoView {
                                         })
                                         on_click=Callback::new(move |_| {
                                             window_context_menu.set(None);
+                                            app_context_menu.set(None);
                                             overflow_menu_open.set(false);
                                             clock_menu_open.set(false);
                                             runtime.dispatch_action(DesktopAction::CloseStartMenu);
                                             activate_pinned_taskbar_app(runtime, app_id_for_click.clone());
                                         })
+                                        on_contextmenu=Callback::new(move |ev: MouseEvent| {
+                                            ev.prevent_default();
+                                            ev.stop_propagation();
+                                            window_context_menu.set(None);
+                                            overflow_menu_open.set(false);
+                                            clock_menu_open.set(false);
+                                            runtime.dispatch_action(DesktopAction::CloseStartMenu);
+                                            open_taskbar_app_context_menu(
+                                                runtime.host.get_value(),
+                                                app_context_menu,
+                                                app_id_for_menu.clone(),
+                                                ev.client_x(),
+                                                ev.client_y(),
+                                            );
+                                        })
                                     >
                                         <span aria-hidden="true">
                                             <Icon
@@ -402,12 +423,41 @@ pub(super) fn Taskbar() -> impl IntoView {
                             })
                         >
                             <span aria-hidden="true">
-                                <Icon
-                                    icon=app_icon_name(&win.app_id)
-                                    size=IconSize::Sm
-                                />
+                                {move || {
+                                    let icon = state
+                                        .with(|desktop| {
+                                            desktop
+                                                .windows
+                                                .iter()
+                                                .find(|w| w.id == win.id)
+                                                .map(window_icon_name)
+                                        })
+                                        .unwrap_or_else(|| app_icon_name(&win.app_id));
+                                    view! { <Icon icon=icon size=IconSize::Sm /> }
+                                }}
                             </span>
                             <span>{win.title.clone()}</span>
+                            {move || {
+                                let count = state.with(|desktop| {
+                                    desktop
+                                        .windows
+                                        .iter()
+                                        .find(|w| w.id == win.id)
+                                        .map_or(0, |w| w.badge_count)
+                                });
+                                (count > 0)
+                                    .then(|| {
+                                        view! {
+                                            <span data-ui-slot="taskbar-badge">
+                                                {if count > 99 {
+                                                    "99+".to_string()
+                                                } else {
+                                                    count.to_string()
+                                                }}
+                                            </span>
+                                        }
+                                    })
+                            }}
                         </SystemTaskbarButton>
                     </For>
 
@@ -460,6 +510,35 @@ pub(super) fn Taskbar() -> impl IntoView {
             </SystemTaskbarSection>
 
             <SystemTaskbarSection ui_slot="right">
+                <div role="group" aria-label="Workspaces" data-ui-slot="workspace-switcher">
+                    <For each=move || 0..WORKSPACE_COUNT key=|workspace| *workspace let:workspace>
+                        <SystemTaskbarButton
+                            id=format!("taskbar-workspace-{workspace}")
+                            aria_label=Signal::derive(move || {
+                                state.with(|desktop| workspace_switcher_aria_label(desktop, workspace))
+                            })
+                            title=Signal::derive(move || {
+                                state.with(|desktop| workspace_switcher_aria_label(desktop, workspace))
+                            })
+                            aria_keyshortcuts=format!("Ctrl+Alt+{}", workspace + 1)
+                            aria_pressed=Signal::derive(move || {
+                                state.with(|desktop| desktop.active_workspace == workspace)
+                            })
+                            pressed=Signal::derive(move || {
+                                state.with(|desktop| desktop.active_workspace == workspace)
+                            })
+                            on_click=Callback::new(move |_| {
+                                window_context_menu.set(None);
+                                overflow_menu_open.set(false);
+                                clock_menu_open.set(false);
+                                runtime.dispatch_action(DesktopAction::SwitchWorkspace { workspace });
+                            })
+                        >
+                            <span>{(workspace + 1).to_string()}</span>
+                        </SystemTaskbarButton>
+                    </For>
+                </div>
+
                 <SystemTrayList>
                     <For
                         each=move || {
@@ -545,6 +624,8 @@ pub(super) fn Taskbar() -> impl IntoView {
                 selected_running_window
                 window_context_menu
             />
+
+            <super::menus::TaskbarAppContextMenu runtime app_context_menu />
         </SystemTaskbar>
     }
 }
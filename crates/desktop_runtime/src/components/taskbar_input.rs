@@ -3,8 +3,8 @@
 use leptos::*;
 
 use super::{
-    activate_taskbar_shortcut_target, build_taskbar_shortcut_targets, DesktopAction,
-    DesktopRuntimeContext, TaskbarWindowContextMenuState,
+    activate_taskbar_shortcut_target, adjacent_workspace, build_taskbar_shortcut_targets,
+    DesktopAction, DesktopRuntimeContext, TaskbarWindowContextMenuState, WORKSPACE_COUNT,
 };
 
 fn shortcut_digit_index(ev: &web_sys::KeyboardEvent) -> Option<usize> {
@@ -42,6 +42,39 @@ fn dismiss_taskbar_overlay_menus(
     clock_menu_open.set(false);
 }
 
+/// Actions for a `Ctrl+Alt` workspace shortcut.
+///
+/// Arrows and digits switch workspaces; with `Shift`, arrows take the focused window along.
+fn workspace_shortcut_action(
+    runtime: DesktopRuntimeContext,
+    ev: &web_sys::KeyboardEvent,
+) -> Option<Vec<DesktopAction>> {
+    let desktop = runtime.state.get_untracked();
+    let workspace = match ev.key().as_str() {
+        "ArrowLeft" => adjacent_workspace(&desktop, -1),
+        "ArrowRight" => adjacent_workspace(&desktop, 1),
+        _ if ev.shift_key() => return None,
+        _ => {
+            let index = u32::try_from(shortcut_digit_index(ev)?).ok()?;
+            (index < WORKSPACE_COUNT).then_some(index)?
+        }
+    };
+    let carried = ev
+        .shift_key()
+        .then(|| desktop.focused_window_id())
+        .flatten();
+    Some(match carried {
+        Some(window_id) => vec![
+            DesktopAction::MoveWindowToWorkspace {
+                window_id,
+                workspace,
+            },
+            DesktopAction::FocusWindow { window_id },
+        ],
+        None => vec![DesktopAction::SwitchWorkspace { workspace }],
+    })
+}
+
 /// Handles taskbar-global shortcuts shared by window-level and taskbar-local key handlers.
 pub(super) fn try_handle_taskbar_shortcuts(
     runtime: DesktopRuntimeContext,
@@ -58,6 +91,19 @@ pub(super) fn try_handle_taskbar_shortcuts(
         return true;
     }
 
+    if ev.ctrl_key() && ev.alt_key() && !ev.meta_key() {
+        if let Some(actions) = workspace_shortcut_action(runtime, ev) {
+            ev.prevent_default();
+            ev.stop_propagation();
+            dismiss_taskbar_overlay_menus(window_context_menu, overflow_menu_open, clock_menu_open);
+            runtime.dispatch_action(DesktopAction::CloseStartMenu);
+            for action in actions {
+                runtime.dispatch_action(action);
+            }
+            return true;
+        }
+    }
+
     if ev.alt_key() && !ev.ctrl_key() && !ev.meta_key() {
         if let Some(index) = shortcut_digit_index(ev) {
             let desktop = runtime.state.get_untracked();
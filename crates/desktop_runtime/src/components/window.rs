@@ -1,11 +1,19 @@
 #![allow(clippy::clone_on_copy)]
 
+use super::dialogs::{window_dialog_service, PendingDialog, WindowDialog};
 use super::*;
 use crate::app_runtime::ensure_window_session;
 use crate::apps;
+use crate::reducer::app_granted_capabilities;
 use crate::shell;
-use desktop_app_contract::{AppMountContext, AppServices, ApplicationId, CapabilitySet};
+use desktop_app_contract::{
+    AppCapability, AppCommand, AppLifecycleEvent, AppMountContext, AppServices, ApplicationId,
+    CapabilityGrant, CapabilityRequester, CapabilitySet, DialogRequest, DialogResponse, LogService,
+    NotificationPreference, WindowGeometry, WindowOpener, WorkspaceInfo, IPC_REPLY_TOPIC,
+};
 use leptos::ev::MouseEvent;
+use platform_host::CapabilityStatus;
+use std::{collections::BTreeMap, rc::Rc};
 use system_ui::{
     Icon, IconName, IconSize, WindowBody as SystemWindowBody,
     WindowControlButton as SystemWindowControlButton, WindowControls as SystemWindowControls,
@@ -50,7 +58,7 @@ pub(super) fn DesktopWindow(window_id: WindowId) -> impl IntoView {
         }
     };
     let minimize = move |_| runtime.dispatch_action(DesktopAction::MinimizeWindow { window_id });
-    let close = move |_| runtime.dispatch_action(DesktopAction::CloseWindow { window_id });
+    let close = move |_| runtime.request_close_window(window_id);
     let toggle_maximize = move |_| {
         if let Some(win) = window.get() {
             if win.maximized {
@@ -118,8 +126,17 @@ pub(super) fn DesktopWindow(window_id: WindowId) -> impl IntoView {
                 focused=Signal::derive(move || {
                     window.get().map(|win| win.is_focused).unwrap_or(false)
                 })
+                // Windows on other workspaces stay mounted and are hidden like minimized ones.
                 minimized=Signal::derive(move || {
-                    window.get().map(|win| win.minimized).unwrap_or(false)
+                    runtime.state.with(|desktop| {
+                        desktop
+                            .windows
+                            .iter()
+                            .find(|win| win.id == window_id)
+                            .is_some_and(|win| {
+                                win.minimized || !desktop.is_on_active_workspace(win)
+                            })
+                    })
                 })
                 maximized=Signal::derive(move || {
                     window.get().map(|win| win.maximized).unwrap_or(false)
@@ -137,16 +154,13 @@ pub(super) fn DesktopWindow(window_id: WindowId) -> impl IntoView {
                 >
                     <SystemWindowTitle>
                         <span aria-hidden="true">
-                            <Icon
-                                icon={{
-                                    let app_id = window
-                                        .get_untracked()
-                                        .expect("window exists while shown")
-                                        .app_id;
-                                    app_icon_name(&app_id)
-                                }}
-                                size=IconSize::Sm
-                            />
+                            {move || {
+                                window
+                                    .get()
+                                    .map(|win| {
+                                        view! { <Icon icon=window_icon_name(&win) size=IconSize::Sm /> }
+                                    })
+                            }}
                         </span>
                         <span>
                             {move || {
@@ -319,53 +333,256 @@ fn ManagedWindowBody(window_id: WindowId) -> impl IntoView {
     let wallpaper_current = create_rw_signal(runtime.state.get_untracked().wallpaper);
     let wallpaper_preview = create_rw_signal(runtime.state.get_untracked().wallpaper_preview);
     let wallpaper_library = create_rw_signal(runtime.state.get_untracked().wallpaper_library);
-    let terminal_history = create_rw_signal(runtime.state.get_untracked().terminal_history);
+    let window_geometry = create_rw_signal(WindowGeometry::default());
+    let window_focused = create_rw_signal(false);
+    let workspace = create_rw_signal(WorkspaceInfo::default());
+    let app_id = state
+        .get_untracked()
+        .windows
+        .iter()
+        .find(|w| w.id == window_id)
+        .map(|w| w.app_id.clone())
+        .expect("window app id");
+    let capabilities = create_rw_signal(CapabilitySet::new(
+        app_granted_capabilities(&state.get_untracked(), &app_id),
+        runtime.host.get_value().host_capabilities(),
+    ));
+    create_effect({
+        let app_id = app_id.clone();
+        move |_| {
+            // Grants made from another window of the same app, and revocations from Settings,
+            // reach this one too.
+            let granted = state.with(|desktop| app_granted_capabilities(desktop, &app_id));
+            if capabilities.with_untracked(|current| current.granted() != granted.as_slice()) {
+                capabilities.set(CapabilitySet::new(
+                    granted,
+                    runtime.host.get_value().host_capabilities(),
+                ));
+            }
+        }
+    });
+    let permission_grants = create_rw_signal(Vec::<CapabilityGrant>::new());
+    create_effect(move |_| {
+        let grants = state.with(|desktop| {
+            desktop
+                .capability_grants
+                .iter()
+                .filter_map(|(app_id, capabilities)| {
+                    Some((ApplicationId::new(app_id.clone()).ok()?, capabilities))
+                })
+                .flat_map(|(app_id, capabilities)| {
+                    capabilities.iter().map(move |capability| CapabilityGrant {
+                        app_id: app_id.clone(),
+                        capability: *capability,
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+        if permission_grants.with_untracked(|current| *current != grants) {
+            permission_grants.set(grants);
+        }
+    });
+    let notification_preferences = create_rw_signal(Vec::<NotificationPreference>::new());
+    create_effect(move |_| {
+        let preferences = state.with(|desktop| {
+            desktop
+                .notification_deliveries
+                .iter()
+                .filter_map(|(app_id, delivery)| {
+                    Some(NotificationPreference {
+                        app_id: ApplicationId::new(app_id.clone()).ok()?,
+                        delivery: *delivery,
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+        if notification_preferences.with_untracked(|current| *current != preferences) {
+            notification_preferences.set(preferences);
+        }
+    });
+    let shared_state = create_rw_signal(BTreeMap::<String, Value>::new());
+    create_effect({
+        let prefix = format!("{app_id}:");
+        move |_| {
+            let shared = state.with(|desktop| {
+                desktop
+                    .app_shared_state
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        Some((key.strip_prefix(&prefix)?.to_string(), value.clone()))
+                    })
+                    .collect::<BTreeMap<_, _>>()
+            });
+            if shared_state.with_untracked(|current| *current != shared) {
+                shared_state.set(shared);
+            }
+        }
+    });
     create_effect(move |_| {
         let desktop = runtime.state.get();
+        if let Some(win) = desktop.windows.iter().find(|w| w.id == window_id) {
+            let geometry = WindowGeometry {
+                x: win.rect.x,
+                y: win.rect.y,
+                width: win.rect.w,
+                height: win.rect.h,
+                minimized: win.minimized,
+                maximized: win.maximized,
+            };
+            if window_geometry.get_untracked() != geometry {
+                window_geometry.set(geometry);
+            }
+            if window_focused.get_untracked() != win.is_focused {
+                window_focused.set(win.is_focused);
+            }
+            let info = WorkspaceInfo {
+                active: desktop.active_workspace,
+                count: WORKSPACE_COUNT,
+                window: win.workspace,
+            };
+            if workspace.get_untracked() != info {
+                workspace.set(info);
+            }
+        }
         theme_skin_id.set(desktop.theme.skin.css_id().to_string());
         theme_high_contrast.set(desktop.theme.high_contrast);
         theme_reduced_motion.set(desktop.theme.reduced_motion);
         wallpaper_current.set(desktop.wallpaper);
         wallpaper_preview.set(desktop.wallpaper_preview);
         wallpaper_library.set(desktop.wallpaper_library);
-        terminal_history.set(desktop.terminal_history);
     });
     let command_sender = Callback::new(move |command| {
         spawn_local(async move {
             runtime.dispatch_action(DesktopAction::HandleAppCommand { window_id, command });
         });
     });
-    let app_id = state
-        .get_untracked()
-        .windows
-        .iter()
-        .find(|w| w.id == window_id)
-        .map(|w| w.app_id.clone())
-        .expect("window app id");
-    let capabilities = create_rw_signal(CapabilitySet::new(
-        apps::app_requested_capabilities_by_id(&app_id).to_vec(),
-        runtime.host.get_value().host_capabilities(),
-    ));
+    // Opening runs synchronously so the id of the window the request ended up focusing can be
+    // read back from the reduced state.
+    let window_opener: WindowOpener = Rc::new(move |app_id: ApplicationId, launch_params| {
+        let opened = if capabilities
+            .get_untracked()
+            .is_granted(AppCapability::Window)
+        {
+            let new_window_id = WindowId(state.get_untracked().next_window_id);
+            runtime.dispatch_action(DesktopAction::HandleAppCommand {
+                window_id,
+                command: AppCommand::OpenWindow {
+                    app_id: app_id.clone(),
+                    launch_params,
+                },
+            });
+            state.with_untracked(|desktop| {
+                desktop
+                    .windows
+                    .iter()
+                    .find(|w| w.id == new_window_id)
+                    .or_else(|| {
+                        desktop
+                            .windows
+                            .iter()
+                            .find(|w| w.app_id == app_id && w.is_focused)
+                    })
+                    .map(|w| w.id.0)
+                    .ok_or_else(|| format!("no window could be opened for {app_id}"))
+            })
+        } else {
+            Err("opening windows requires the window capability".to_string())
+        };
+        Box::pin(async move { opened })
+    });
+    let dialog = create_rw_signal(None::<PendingDialog>);
+    let dialogs = window_dialog_service(dialog);
+    let capability_requester: CapabilityRequester = {
+        let app_id = app_id.clone();
+        let dialogs = dialogs.clone();
+        Rc::new(move |capability: AppCapability| {
+            let app_id = app_id.clone();
+            let dialogs = dialogs.clone();
+            Box::pin(async move {
+                // Nothing to consent to when the host cannot provide the capability at all.
+                let host_status = CapabilitySet::new(
+                    vec![capability],
+                    runtime.host.get_value().host_capabilities(),
+                )
+                .status(capability);
+                if host_status == CapabilityStatus::Unavailable {
+                    return host_status;
+                }
+                let app_name = apps::app_title_by_id(&app_id);
+                let answer = dialogs
+                    .show(DialogRequest::Confirm {
+                        title: format!(
+                            "Allow {app_name} to {}?",
+                            capability_prompt_purpose(capability)
+                        ),
+                        message: format!(
+                            "{app_name} is asking for permission. Allowing is remembered for \
+                             {app_name}."
+                        ),
+                        confirm_label: "Allow".to_string(),
+                        destructive: false,
+                    })
+                    .await;
+                if answer == DialogResponse::Confirmed {
+                    runtime.dispatch_action(DesktopAction::GrantCapability { app_id, capability });
+                    capabilities.try_update(|capabilities| capabilities.grant(capability));
+                }
+                capabilities
+                    .try_with_untracked(|capabilities| capabilities.status(capability))
+                    .unwrap_or(CapabilityStatus::Unavailable)
+            })
+        })
+    };
     let services = store_value(AppServices::new(
         command_sender,
-        capabilities.get_untracked(),
+        capabilities.read_only(),
+        capability_requester,
+        permission_grants.read_only(),
+        notification_preferences.read_only(),
+        Rc::new(move || runtime.window_processes()),
+        shared_state.read_only(),
         runtime.host.get_value().app_state_store(),
         runtime.host.get_value().prefs_store(),
         runtime.host.get_value().explorer_fs_service(),
         runtime.host.get_value().content_cache(),
+        runtime.host.get_value().clipboard_service(),
+        runtime.host.get_value().http_service(),
+        apps::app_network_allowlist_by_id(&app_id).to_vec(),
+        window_geometry.read_only(),
+        window_focused.read_only(),
+        workspace.read_only(),
+        window_opener,
+        session.lifecycle_handler,
+        session.link_handler,
         theme_skin_id.read_only(),
         theme_high_contrast.read_only(),
         theme_reduced_motion.read_only(),
         wallpaper_current.read_only(),
         wallpaper_preview.read_only(),
         wallpaper_library.read_only(),
-        shell::build_command_service(
-            runtime.clone(),
-            app_id.clone(),
-            window_id,
-            terminal_history.read_only(),
-        ),
+        shell::build_command_service(runtime.clone(), app_id.clone(), window_id),
+        dialogs,
+        LogService::new(runtime.host.get_value().log_registry(), app_id.as_str()),
+        apps::launchable_apps(),
     ));
+    // Timers and background tasks started through `TaskService` pause while the window is
+    // suspended and stop when it closes.
+    let tasks = services.get_value().tasks;
+    create_effect(move |_| match lifecycle.get() {
+        AppLifecycleEvent::Suspended => tasks.set_suspended(true),
+        AppLifecycleEvent::Resumed => tasks.set_suspended(false),
+        _ => {}
+    });
+    on_cleanup(move || tasks.cancel_all());
+    // Replies to `IpcService::call` resolve the waiting call instead of reaching the app.
+    let ipc = services.get_value().ipc;
+    create_effect(move |_| {
+        if inbox.with(|events| events.iter().any(|event| event.topic == IPC_REPLY_TOPIC)) {
+            inbox.update(|events| {
+                events.retain(|event| !ipc.resolve_reply(event) && event.topic != IPC_REPLY_TOPIC)
+            });
+        }
+    });
     let mounted_window = state
         .get_untracked()
         .windows
@@ -391,10 +608,21 @@ fn ManagedWindowBody(window_id: WindowId) -> impl IntoView {
     view! {
         <div data-ui-slot="window-body-content">
             {contents}
+            <WindowDialog pending=dialog fs=runtime.host.get_value().explorer_fs_service() />
         </div>
     }
 }
 
+/// Phrase completing "Allow <app> to …?" in the consent prompt for `capability`.
+fn capability_prompt_purpose(capability: AppCapability) -> &'static str {
+    match capability {
+        AppCapability::Notifications => "show notifications",
+        AppCapability::ExternalUrl => "open links in your browser",
+        AppCapability::Network => "connect to the internet",
+        _ => "use extra permissions",
+    }
+}
+
 #[component]
 fn MountedManagedApp(app_id: ApplicationId, context: AppMountContext) -> impl IntoView {
     apps::app_module_by_id(&app_id).mount(context)
@@ -0,0 +1,100 @@
+//! Alt+Tab overlay listing the active workspace's windows, most recently used first.
+
+use leptos::*;
+use system_ui::{Button, ButtonVariant, Elevation, Panel};
+
+use super::{focus_or_unminimize_window, window_icon_name, DesktopAction, DesktopRuntimeContext};
+
+fn switcher_open(runtime: DesktopRuntimeContext) -> bool {
+    runtime
+        .interaction
+        .with_untracked(|interaction| interaction.window_switcher.is_some())
+}
+
+#[component]
+/// Window switcher driven by [`DesktopAction::CycleWindowSwitcher`].
+///
+/// `Alt+Tab` and `Alt+Shift+Tab` open it and move the selection, releasing `Alt` brings the
+/// selected window forward, and `Escape` or leaving the page closes it unchanged.
+pub(super) fn WindowSwitcher(runtime: DesktopRuntimeContext) -> impl IntoView {
+    let keydown = window_event_listener(ev::keydown, move |ev| {
+        if ev.key() == "Tab" && ev.alt_key() && !ev.ctrl_key() && !ev.meta_key() {
+            ev.prevent_default();
+            ev.stop_propagation();
+            runtime.dispatch_action(DesktopAction::CycleWindowSwitcher {
+                reverse: ev.shift_key(),
+            });
+        } else if ev.key() == "Escape" && switcher_open(runtime) {
+            ev.prevent_default();
+            ev.stop_propagation();
+            runtime.dispatch_action(DesktopAction::CancelWindowSwitcher);
+        }
+    });
+    let keyup = window_event_listener(ev::keyup, move |ev| {
+        if ev.key() == "Alt" && switcher_open(runtime) {
+            ev.prevent_default();
+            runtime.dispatch_action(DesktopAction::CommitWindowSwitcher);
+        }
+    });
+    let blur = window_event_listener(ev::blur, move |_| {
+        if switcher_open(runtime) {
+            runtime.dispatch_action(DesktopAction::CancelWindowSwitcher);
+        }
+    });
+    on_cleanup(move || {
+        keydown.remove();
+        keyup.remove();
+        blur.remove();
+    });
+
+    move || {
+        let session = runtime
+            .interaction
+            .with(|interaction| interaction.window_switcher.clone())?;
+        let desktop = runtime.state.get();
+        let windows = session
+            .windows
+            .iter()
+            .enumerate()
+            .filter_map(|(index, window_id)| {
+                let win = desktop.windows.iter().find(|win| win.id == *window_id)?;
+                Some((index, win.id, window_icon_name(win), win.title.clone()))
+            })
+            .collect::<Vec<_>>();
+        let entries = windows
+            .into_iter()
+            .map(|(index, window_id, icon, title)| {
+                view! {
+                    <Button
+                        variant=ButtonVariant::Quiet
+                        role="option"
+                        ui_slot="window-switcher-item"
+                        selected=index == session.selected
+                        leading_icon=icon
+                        on_mousedown=Callback::new(|ev: ev::MouseEvent| ev.prevent_default())
+                        on_click=Callback::new(move |_| {
+                            runtime.dispatch_action(DesktopAction::CancelWindowSwitcher);
+                            focus_or_unminimize_window(
+                                runtime,
+                                &runtime.state.get_untracked(),
+                                window_id,
+                            );
+                        })
+                    >
+                        {title}
+                    </Button>
+                }
+            })
+            .collect_view();
+        Some(view! {
+            <Panel
+                elevation=Elevation::Overlay
+                role="listbox"
+                aria_label="Switch windows"
+                ui_slot="window-switcher"
+            >
+                {entries}
+            </Panel>
+        })
+    }
+}
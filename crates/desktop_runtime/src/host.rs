@@ -13,16 +13,21 @@ mod wallpaper_effects;
 
 use std::rc::Rc;
 
-use leptos::{logging, spawn_local, Callback};
+use desktop_app_contract::LogService;
+use leptos::{spawn_local, Callback};
 use platform_host::{
-    AppStateStore, ContentCache, ExplorerFsService, ExternalUrlService, HostCapabilities,
-    HostServices, NotificationService, PrefsStore, TerminalProcessService, WallpaperAssetService,
+    AppStateStore, ClipboardService, ContentCache, ExplorerFsService, ExternalUrlService,
+    HostCapabilities, HostServices, HttpService, LogRegistry, NotificationService,
+    PermissionsRegistry, PrefsStore, TerminalProcessService, WallpaperAssetService,
 };
 
 use crate::{
     model::WindowRect, persistence, reducer::DesktopAction, runtime_context::DesktopRuntimeContext,
 };
 
+/// Log source the desktop runtime records its own problems under.
+pub(crate) const DESKTOP_LOG_SOURCE: &str = "system.desktop";
+
 #[derive(Clone)]
 /// Host service bundle for desktop runtime side effects.
 pub struct DesktopHostContext {
@@ -31,9 +36,13 @@ pub struct DesktopHostContext {
     explorer: Rc<dyn ExplorerFsService>,
     cache: Rc<dyn ContentCache>,
     external_urls: Rc<dyn ExternalUrlService>,
+    clipboard: Rc<dyn ClipboardService>,
+    http: Rc<dyn HttpService>,
     notifications: Rc<dyn NotificationService>,
     wallpaper: Rc<dyn WallpaperAssetService>,
     terminal_process: Option<Rc<dyn TerminalProcessService>>,
+    permissions: PermissionsRegistry,
+    logs: LogRegistry,
     capabilities: HostCapabilities,
     host_strategy_name: &'static str,
 }
@@ -43,10 +52,14 @@ impl DesktopHostContext {
     pub fn new(services: HostServices) -> Self {
         Self {
             app_state: services.app_state,
+            permissions: PermissionsRegistry::new(services.prefs.clone()),
+            logs: LogRegistry::new(services.prefs.clone()),
             prefs: services.prefs,
             explorer: services.explorer,
             cache: services.cache,
             external_urls: services.external_urls,
+            clipboard: services.clipboard,
+            http: services.http,
             notifications: services.notifications,
             wallpaper: services.wallpaper,
             terminal_process: services.terminal_process,
@@ -80,6 +93,16 @@ impl DesktopHostContext {
         self.external_urls.clone()
     }
 
+    /// Returns the configured clipboard service.
+    pub fn clipboard_service(&self) -> Rc<dyn ClipboardService> {
+        self.clipboard.clone()
+    }
+
+    /// Returns the configured outbound HTTP service.
+    pub fn http_service(&self) -> Rc<dyn HttpService> {
+        self.http.clone()
+    }
+
     /// Returns the configured notification delivery service.
     pub fn notification_service(&self) -> Rc<dyn NotificationService> {
         self.notifications.clone()
@@ -95,6 +118,21 @@ impl DesktopHostContext {
         self.terminal_process.clone()
     }
 
+    /// Returns the registry of capabilities users granted apps at runtime.
+    pub fn permissions_registry(&self) -> PermissionsRegistry {
+        self.permissions.clone()
+    }
+
+    /// Returns the log buffers shared by the runtime and every app.
+    pub fn log_registry(&self) -> LogRegistry {
+        self.logs.clone()
+    }
+
+    /// Returns a log service recording as [`DESKTOP_LOG_SOURCE`].
+    pub fn logger(&self) -> LogService {
+        LogService::new(self.logs.clone(), DESKTOP_LOG_SOURCE)
+    }
+
     /// Returns the host capability snapshot for the active strategy.
     pub fn host_capabilities(&self) -> HostCapabilities {
         self.capabilities
@@ -141,7 +179,8 @@ impl DesktopHostContext {
         let host = self.clone();
         spawn_local(async move {
             if let Err(err) = persistence::persist_durable_layout_snapshot(&host, &state).await {
-                logging::warn!("persist durable {cause} snapshot failed: {err}");
+                host.logger()
+                    .warn(format!("persist durable {cause} snapshot failed: {err}"));
             }
         });
     }
@@ -1,7 +1,7 @@
 use crate::{
     app_runtime::{
-        deliver_window_event, publish_topic_event, set_window_lifecycle, subscribe_window_topic,
-        unsubscribe_window_topic,
+        deliver_ipc_reply as deliver_window_ipc_reply, deliver_window_event, publish_topic_event,
+        set_window_lifecycle, subscribe_window_topic, unsubscribe_window_topic,
     },
     components::DesktopRuntimeContext,
 };
@@ -22,6 +22,15 @@ pub(super) fn deliver_app_event(
     deliver_window_event(runtime.app_runtime, window_id, event);
 }
 
+pub(super) fn deliver_ipc_reply(
+    runtime: DesktopRuntimeContext,
+    responder: crate::model::WindowId,
+    window_id: crate::model::WindowId,
+    event: desktop_app_contract::AppEvent,
+) {
+    deliver_window_ipc_reply(runtime.app_runtime, responder, window_id, event);
+}
+
 pub(super) fn subscribe_topic(
     runtime: DesktopRuntimeContext,
     window_id: crate::model::WindowId,
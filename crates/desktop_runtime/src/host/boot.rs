@@ -1,4 +1,4 @@
-use leptos::{create_effect, logging, spawn_local, Callable, Callback};
+use leptos::{create_effect, spawn_local, Callable, Callback};
 
 use crate::{
     current_browser_e2e_config, host::DesktopHostContext, persistence, reducer::DesktopAction,
@@ -12,6 +12,11 @@ pub(super) fn install_boot_hydration(host: DesktopHostContext, dispatch: Callbac
             let browser_e2e_active = current_browser_e2e_config().is_some();
 
             if !browser_e2e_active {
+                if let Err(err) = host.log_registry().load().await {
+                    host.logger()
+                        .warn(format!("log history load failed: {err}"));
+                }
+
                 let legacy_snapshot = persistence::load_boot_snapshot(&host).await;
                 if let Some(snapshot) = legacy_snapshot.clone() {
                     dispatch.call(DesktopAction::HydrateSnapshot { snapshot });
@@ -25,6 +30,18 @@ pub(super) fn install_boot_hydration(host: DesktopHostContext, dispatch: Callbac
                     dispatch.call(DesktopAction::HydrateWallpaper { wallpaper });
                 }
 
+                if let Some(notifications) = persistence::load_notifications(&host).await {
+                    dispatch.call(DesktopAction::HydrateNotifications { notifications });
+                }
+
+                if let Some(deliveries) = persistence::load_notification_deliveries(&host).await {
+                    dispatch.call(DesktopAction::HydrateNotificationDeliveries { deliveries });
+                }
+
+                if let Some(grants) = persistence::load_capability_grants(&host).await {
+                    dispatch.call(DesktopAction::HydrateCapabilityGrants { grants });
+                }
+
                 if let Some(snapshot) = persistence::load_durable_boot_snapshot(&host).await {
                     dispatch.call(DesktopAction::HydrateSnapshot { snapshot });
                 } else if let Some(snapshot) = legacy_snapshot {
@@ -32,7 +49,9 @@ pub(super) fn install_boot_hydration(host: DesktopHostContext, dispatch: Callbac
                     if let Err(err) =
                         persistence::persist_durable_layout_snapshot(&host, &migrated_state).await
                     {
-                        logging::warn!("migrate legacy snapshot to durable store failed: {err}");
+                        host.logger().warn(format!(
+                            "migrate legacy snapshot to durable store failed: {err}"
+                        ));
                     }
                 }
             }
@@ -41,7 +60,9 @@ pub(super) fn install_boot_hydration(host: DesktopHostContext, dispatch: Callbac
                 Ok(snapshot) => {
                     dispatch.call(DesktopAction::WallpaperLibraryLoaded { snapshot });
                 }
-                Err(err) => logging::warn!("wallpaper library load failed: {err}"),
+                Err(err) => host
+                    .logger()
+                    .warn(format!("wallpaper library load failed: {err}")),
             }
 
             dispatch.call(DesktopAction::BootHydrationComplete);
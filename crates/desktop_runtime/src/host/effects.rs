@@ -15,14 +15,24 @@ pub(super) fn run_runtime_effect(
         RuntimeEffect::ParseAndOpenDeepLink(deep_link) => {
             host_ui::open_deep_link(runtime, deep_link)
         }
+        RuntimeEffect::OpenLink(link) => host_ui::open_link(runtime, link),
         RuntimeEffect::PersistLayout => persistence_effects::persist_layout(host, runtime),
         RuntimeEffect::PersistTheme => persistence_effects::persist_theme(host, runtime),
         RuntimeEffect::PersistWallpaper => persistence_effects::persist_wallpaper(host, runtime),
-        RuntimeEffect::PersistTerminalHistory => {
-            persistence_effects::persist_terminal_history(host, runtime)
+        RuntimeEffect::PersistNotifications => {
+            persistence_effects::persist_notifications(host, runtime)
+        }
+        RuntimeEffect::PersistNotificationDeliveries => {
+            persistence_effects::persist_notification_deliveries(host, runtime)
+        }
+        RuntimeEffect::PersistCapabilityGrants => {
+            persistence_effects::persist_capability_grants(host, runtime)
         }
         RuntimeEffect::OpenExternalUrl(url) => host_ui::open_external_url(host, &url),
         RuntimeEffect::FocusWindowInput(window_id) => host.focus_window_input(window_id),
+        RuntimeEffect::MaximizeWindowToViewport(window_id) => {
+            host_ui::maximize_window(host, runtime, window_id)
+        }
         RuntimeEffect::PlaySound(_) => {}
         RuntimeEffect::DispatchLifecycle { window_id, event } => {
             app_bus::dispatch_lifecycle(runtime, window_id, event);
@@ -30,6 +40,11 @@ pub(super) fn run_runtime_effect(
         RuntimeEffect::DeliverAppEvent { window_id, event } => {
             app_bus::deliver_app_event(runtime, window_id, event);
         }
+        RuntimeEffect::DeliverIpcReply {
+            responder,
+            window_id,
+            event,
+        } => app_bus::deliver_ipc_reply(runtime, responder, window_id, event),
         RuntimeEffect::SubscribeWindowTopic { window_id, topic } => {
             app_bus::subscribe_topic(runtime, window_id, topic);
         }
@@ -1,14 +1,19 @@
 #[cfg(target_arch = "wasm32")]
 use desktop_app_contract::window_primary_input_dom_id;
-use leptos::{logging, spawn_local};
+use desktop_app_contract::AppLink;
+use leptos::{spawn_local, SignalWithUntracked};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::{closure::Closure, JsCast};
 
 use crate::{
+    app_runtime::window_session,
     components::DesktopRuntimeContext,
     host::DesktopHostContext,
     model::WindowRect,
-    reducer::{build_open_request_from_deeplink, DesktopAction},
+    reducer::{
+        build_open_request_from_deeplink, focus_app_action, launch_app_action,
+        preferred_window_for_app, DesktopAction,
+    },
 };
 
 pub(super) fn open_deep_link(
@@ -23,6 +28,7 @@ pub(super) fn open_deep_link(
                     viewport: Some(runtime.host.get_value().desktop_viewport_rect(38)),
                 });
             }
+            crate::model::DeepLinkOpenTarget::Link(link) => open_link(runtime, link),
             target => {
                 runtime.dispatch_action(DesktopAction::OpenWindow(
                     build_open_request_from_deeplink(target),
@@ -32,6 +38,39 @@ pub(super) fn open_deep_link(
     }
 }
 
+/// Hands `link` to the link handler of its app's most relevant window, focusing it, or launches
+/// the app with the link's launch params when no open window handles links.
+pub(super) fn open_link(runtime: DesktopRuntimeContext, link: AppLink) {
+    let handler = runtime
+        .state
+        .with_untracked(|desktop| preferred_window_for_app(desktop, &link.app_id))
+        .and_then(|window_id| window_session(runtime.app_runtime, window_id))
+        .and_then(|session| session.link_handler.try_get_value().flatten());
+    let action = runtime.state.with_untracked(|desktop| match handler {
+        Some(_) => focus_app_action(desktop, link.app_id.clone()),
+        None => launch_app_action(desktop, link.app_id.clone(), link.launch_params(), false),
+    });
+    let Some(action) = action else {
+        runtime.logger().warn(format!("no app handles link {link}"));
+        return;
+    };
+    runtime.dispatch_action(action);
+    if let Some(handler) = handler {
+        handler(link);
+    }
+}
+
+pub(super) fn maximize_window(
+    host: DesktopHostContext,
+    runtime: DesktopRuntimeContext,
+    window_id: crate::model::WindowId,
+) {
+    runtime.dispatch_action(DesktopAction::MaximizeWindow {
+        window_id,
+        viewport: host.desktop_viewport_rect(38),
+    });
+}
+
 pub(super) fn focus_window_input(window_id: crate::model::WindowId) {
     #[cfg(target_arch = "wasm32")]
     {
@@ -62,7 +101,8 @@ pub(super) fn open_external_url(host: DesktopHostContext, url: &str) {
     let url = url.to_string();
     spawn_local(async move {
         if let Err(err) = host.external_url_service().open_url(&url).await {
-            logging::warn!("open external url failed for `{url}`: {err}");
+            host.logger()
+                .warn(format!("open external url failed for `{url}`: {err}"));
         }
     });
 }
@@ -70,7 +110,8 @@ pub(super) fn open_external_url(host: DesktopHostContext, url: &str) {
 pub(super) fn notify(host: DesktopHostContext, title: String, body: String) {
     spawn_local(async move {
         if let Err(err) = host.notification_service().notify(&title, &body).await {
-            logging::warn!("notification dispatch failed: {err}");
+            host.logger()
+                .warn(format!("notification dispatch failed: {err}"));
         }
     });
 }
@@ -1,4 +1,4 @@
-use leptos::{logging, spawn_local, SignalGetUntracked};
+use leptos::{spawn_local, SignalGetUntracked};
 use platform_host::save_pref_with;
 
 use crate::{components::DesktopRuntimeContext, host::DesktopHostContext, persistence};
@@ -6,7 +6,7 @@ use crate::{components::DesktopRuntimeContext, host::DesktopHostContext, persist
 pub(super) fn persist_layout(host: DesktopHostContext, runtime: DesktopRuntimeContext) {
     let snapshot_state = runtime.state.get_untracked();
     if let Err(err) = persistence::persist_layout_snapshot(&snapshot_state) {
-        logging::warn!("persist layout failed: {err}");
+        host.logger().warn(format!("persist layout failed: {err}"));
     }
     host.persist_durable_snapshot(snapshot_state, "layout");
 }
@@ -16,7 +16,9 @@ pub(super) fn persist_theme(host: DesktopHostContext, runtime: DesktopRuntimeCon
     let async_host = host.clone();
     spawn_local(async move {
         if let Err(err) = persistence::persist_theme(&async_host, &theme).await {
-            logging::warn!("persist theme failed: {err}");
+            async_host
+                .logger()
+                .warn(format!("persist theme failed: {err}"));
         }
     });
     host.persist_durable_snapshot(runtime.state.get_untracked(), "theme");
@@ -26,20 +28,43 @@ pub(super) fn persist_wallpaper(host: DesktopHostContext, runtime: DesktopRuntim
     let wallpaper = runtime.state.get_untracked().wallpaper;
     spawn_local(async move {
         if let Err(err) = persistence::persist_wallpaper(&host, &wallpaper).await {
-            logging::warn!("persist wallpaper failed: {err}");
+            host.logger()
+                .warn(format!("persist wallpaper failed: {err}"));
         }
     });
 }
 
-pub(super) fn persist_terminal_history(host: DesktopHostContext, runtime: DesktopRuntimeContext) {
-    let history = runtime.state.get_untracked().terminal_history;
-    let async_host = host.clone();
+pub(super) fn persist_notifications(host: DesktopHostContext, runtime: DesktopRuntimeContext) {
+    let notifications = runtime.state.get_untracked().notifications;
+    spawn_local(async move {
+        if let Err(err) = persistence::persist_notifications(&host, &notifications).await {
+            host.logger()
+                .warn(format!("persist notifications failed: {err}"));
+        }
+    });
+}
+
+pub(super) fn persist_notification_deliveries(
+    host: DesktopHostContext,
+    runtime: DesktopRuntimeContext,
+) {
+    let deliveries = runtime.state.get_untracked().notification_deliveries;
+    spawn_local(async move {
+        if let Err(err) = persistence::persist_notification_deliveries(&host, &deliveries).await {
+            host.logger()
+                .warn(format!("persist notification deliveries failed: {err}"));
+        }
+    });
+}
+
+pub(super) fn persist_capability_grants(host: DesktopHostContext, runtime: DesktopRuntimeContext) {
+    let grants = runtime.state.get_untracked().capability_grants;
     spawn_local(async move {
-        if let Err(err) = persistence::persist_terminal_history(&async_host, &history).await {
-            logging::warn!("persist terminal history failed: {err}");
+        if let Err(err) = persistence::persist_capability_grants(&host, &grants).await {
+            host.logger()
+                .warn(format!("persist capability grants failed: {err}"));
         }
     });
-    host.persist_durable_snapshot(runtime.state.get_untracked(), "terminal");
 }
 
 pub(super) fn save_config(
@@ -51,7 +76,8 @@ pub(super) fn save_config(
     let pref_key = format!("{}.{}", namespace, key);
     spawn_local(async move {
         if let Err(err) = save_pref_with(host.prefs_store().as_ref(), &pref_key, &value).await {
-            logging::warn!("persist config preference failed: {err}");
+            host.logger()
+                .warn(format!("persist config preference failed: {err}"));
         }
     });
 }
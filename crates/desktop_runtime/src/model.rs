@@ -2,7 +2,9 @@
 
 use std::collections::BTreeMap;
 
-use desktop_app_contract::ApplicationId;
+use desktop_app_contract::{
+    AppCapability, AppLink, ApplicationId, Notification, NotificationDelivery,
+};
 use platform_host::{WallpaperConfig, WallpaperLibrarySnapshot};
 use serde::de::Error as _;
 use serde::{Deserialize, Serialize};
@@ -16,6 +18,10 @@ pub const DESKTOP_LAYOUT_SCHEMA_VERSION: u32 = 2;
 pub const DEFAULT_WINDOW_WIDTH: i32 = 720;
 /// Default window height used when no explicit geometry is provided.
 pub const DEFAULT_WINDOW_HEIGHT: i32 = 500;
+/// Most notifications the notification center keeps; the oldest are dropped first.
+pub const NOTIFICATION_HISTORY_LIMIT: usize = 50;
+/// Number of workspaces windows can be assigned to.
+pub const WORKSPACE_COUNT: u32 = 4;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 /// Stable runtime identifier for an open desktop window.
@@ -143,6 +149,30 @@ pub struct WindowRecord {
     /// Last lifecycle token observed for this window.
     #[serde(default)]
     pub last_lifecycle_event: Option<String>,
+    /// App-set taskbar badge count; `0` hides the badge.
+    ///
+    /// Not persisted: apps set it again once they are running.
+    #[serde(skip)]
+    pub badge_count: u32,
+    /// Workspace the window belongs to, below [`WORKSPACE_COUNT`].
+    #[serde(default)]
+    pub workspace: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Notification kept by the notification center.
+pub struct NotificationRecord {
+    /// Id unique within the notification history.
+    pub id: u64,
+    /// App that posted the notification.
+    pub app_id: ApplicationId,
+    /// Posted content, icon, actions, and click target.
+    pub notification: Notification,
+    /// Time the notification was posted.
+    pub posted_unix_ms: u64,
+    /// Whether the notification center has shown the notification.
+    #[serde(default)]
+    pub read: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -259,11 +289,21 @@ pub struct DesktopState {
     pub last_explorer_path: Option<String>,
     /// Last notepad slug used by shell shortcuts/workflows.
     pub last_notepad_slug: Option<String>,
-    /// Recent terminal commands captured for history.
-    pub terminal_history: Vec<String>,
     /// App-shared state payloads keyed by `<app_id>:<key>`.
     #[serde(default)]
     pub app_shared_state: BTreeMap<String, Value>,
+    /// Notification center history, oldest first.
+    #[serde(default)]
+    pub notifications: Vec<NotificationRecord>,
+    /// Capabilities the user granted apps at runtime, keyed by app id, on top of their manifests.
+    #[serde(default)]
+    pub capability_grants: BTreeMap<String, Vec<AppCapability>>,
+    /// Notification delivery modes users changed from the default, keyed by app id.
+    #[serde(default)]
+    pub notification_deliveries: BTreeMap<String, NotificationDelivery>,
+    /// Workspace shown on screen; only its windows are visible, focusable, and on the taskbar.
+    #[serde(default)]
+    pub active_workspace: u32,
     /// Whether asynchronous boot hydration has completed for the current runtime session.
     #[serde(skip)]
     pub boot_hydrated: bool,
@@ -285,8 +325,11 @@ impl Default for DesktopState {
             preferences: DesktopPreferences::default(),
             last_explorer_path: None,
             last_notepad_slug: None,
-            terminal_history: Vec::new(),
             app_shared_state: BTreeMap::new(),
+            notifications: Vec::new(),
+            capability_grants: BTreeMap::new(),
+            notification_deliveries: BTreeMap::new(),
+            active_workspace: 0,
             boot_hydrated: false,
         }
     }
@@ -298,6 +341,29 @@ impl DesktopState {
         self.windows.iter().find(|w| w.is_focused).map(|w| w.id)
     }
 
+    /// Returns whether `window` belongs to the workspace shown on screen.
+    pub fn is_on_active_workspace(&self, window: &WindowRecord) -> bool {
+        window.workspace == self.active_workspace
+    }
+
+    /// Returns the windows on the active workspace, most recently used first.
+    ///
+    /// Focusing raises a window, so recency follows the stack; minimized windows come after the
+    /// visible ones.
+    pub fn recently_used_window_ids(&self) -> Vec<WindowId> {
+        let (visible, minimized): (Vec<&WindowRecord>, Vec<&WindowRecord>) = self
+            .windows
+            .iter()
+            .rev()
+            .filter(|win| self.is_on_active_workspace(win))
+            .partition(|win| !win.minimized);
+        visible
+            .into_iter()
+            .chain(minimized)
+            .map(|win| win.id)
+            .collect()
+    }
+
     /// Creates a serializable snapshot of the current desktop state.
     pub fn snapshot(&self) -> DesktopSnapshot {
         DesktopSnapshot {
@@ -306,31 +372,43 @@ impl DesktopState {
             windows: self.windows.clone(),
             last_explorer_path: self.last_explorer_path.clone(),
             last_notepad_slug: self.last_notepad_slug.clone(),
-            terminal_history: self.terminal_history.clone(),
             app_shared_state: self.app_shared_state.clone(),
+            active_workspace: self.active_workspace,
         }
     }
 
     /// Rebuilds runtime state from a persisted snapshot.
     ///
-    /// The next window id is recomputed from the restored window list.
+    /// The next window id is recomputed from the restored window list, and workspaces past
+    /// [`WORKSPACE_COUNT`] fall back to the first one.
     pub fn from_snapshot(snapshot: DesktopSnapshot) -> Self {
-        let mut state = Self::default();
-        state.preferences = snapshot.preferences;
-        state.windows = snapshot.windows;
-        state.last_explorer_path = snapshot.last_explorer_path;
-        state.last_notepad_slug = snapshot.last_notepad_slug;
-        state.terminal_history = snapshot.terminal_history;
-        state.app_shared_state = snapshot.app_shared_state;
-        state.boot_hydrated = false;
-        state.next_window_id = state
-            .windows
+        let mut windows = snapshot.windows;
+        for window in &mut windows {
+            if window.workspace >= WORKSPACE_COUNT {
+                window.workspace = 0;
+            }
+        }
+        let next_window_id = windows
             .iter()
             .map(|w| w.id.0)
             .max()
             .unwrap_or(0)
             .saturating_add(1);
-        state
+        Self {
+            preferences: snapshot.preferences,
+            windows,
+            next_window_id,
+            active_workspace: if snapshot.active_workspace < WORKSPACE_COUNT {
+                snapshot.active_workspace
+            } else {
+                0
+            },
+            last_explorer_path: snapshot.last_explorer_path,
+            last_notepad_slug: snapshot.last_notepad_slug,
+            app_shared_state: snapshot.app_shared_state,
+            boot_hydrated: false,
+            ..Default::default()
+        }
     }
 }
 
@@ -347,11 +425,12 @@ pub struct DesktopSnapshot {
     pub last_explorer_path: Option<String>,
     /// Persisted notepad slug hint.
     pub last_notepad_slug: Option<String>,
-    /// Persisted terminal history lines.
-    pub terminal_history: Vec<String>,
     /// Persisted app-shared state payloads.
     #[serde(default)]
     pub app_shared_state: BTreeMap<String, Value>,
+    /// Persisted active workspace.
+    #[serde(default)]
+    pub active_workspace: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -472,6 +551,24 @@ pub struct InteractionState {
     pub resizing: Option<ResizeSession>,
     /// Origin of a desktop selection gesture, if any.
     pub desktop_selection_origin: Option<PointerPosition>,
+    /// Open window switcher, if any.
+    pub window_switcher: Option<WindowSwitcherSession>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Alt+Tab window switcher session.
+pub struct WindowSwitcherSession {
+    /// Windows listed by the switcher, most recently used first, fixed when it opened.
+    pub windows: Vec<WindowId>,
+    /// Index of the selected entry in `windows`.
+    pub selected: usize,
+}
+
+impl WindowSwitcherSession {
+    /// Returns the selected window.
+    pub fn selected_window_id(&self) -> Option<WindowId> {
+        self.windows.get(self.selected).copied()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -483,6 +580,8 @@ pub enum DeepLinkOpenTarget {
     NotesSlug(String),
     /// Open an explorer window scoped to a project slug.
     ProjectSlug(String),
+    /// Route a `web+os://` link to the app it names.
+    Link(AppLink),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -536,6 +635,8 @@ mod tests {
         let window = snapshot.windows.first().expect("window");
         assert!(!window.suspended);
         assert!(window.last_lifecycle_event.is_none());
+        assert_eq!(window.workspace, 0);
+        assert_eq!(snapshot.active_workspace, 0);
     }
 
     #[test]
@@ -592,6 +693,8 @@ mod tests {
                     app_state: Value::Null,
                     launch_params: Value::Null,
                     last_lifecycle_event: None,
+                    badge_count: 0,
+                    workspace: WORKSPACE_COUNT,
                 },
                 WindowRecord {
                     id: WindowId(11),
@@ -610,15 +713,19 @@ mod tests {
                     app_state: Value::Null,
                     launch_params: Value::Null,
                     last_lifecycle_event: Some("focused".to_string()),
+                    badge_count: 0,
+                    workspace: 2,
                 },
             ],
             last_explorer_path: None,
             last_notepad_slug: None,
-            terminal_history: Vec::new(),
             app_shared_state: BTreeMap::new(),
+            active_workspace: 2,
         });
 
         assert_eq!(state.next_window_id, 12);
         assert_eq!(state.focused_window_id(), Some(WindowId(11)));
+        assert_eq!(state.active_workspace, 2);
+        assert_eq!(state.windows[0].workspace, 0);
     }
 }
@@ -1,12 +1,16 @@
 //! Desktop runtime persistence adapters for boot hydration and lightweight local preferences.
 
+use std::collections::BTreeMap;
+
 use crate::host::DesktopHostContext;
-use crate::model::{DesktopSnapshot, DesktopState, DesktopTheme};
+use crate::model::{DesktopSnapshot, DesktopState, DesktopTheme, NotificationRecord};
+use desktop_app_contract::{AppCapability, NotificationDelivery};
 #[cfg(test)]
 use platform_host::build_app_state_envelope;
 use platform_host::{
     load_app_state_with_migration, load_pref_with, migrate_envelope_payload, save_app_state_with,
-    save_pref_with, AppStateEnvelope, WallpaperConfig, WallpaperSelection, DESKTOP_STATE_NAMESPACE,
+    save_pref_with, AppStateEnvelope, PermissionGrants, WallpaperConfig, WallpaperSelection,
+    DESKTOP_STATE_NAMESPACE,
 };
 use serde::{Deserialize, Serialize};
 
@@ -15,7 +19,14 @@ const SNAPSHOT_KEY: &str = "retrodesk.layout.v1";
 const LEGACY_THEME_KEY: &str = "retrodesk.theme.v1";
 const THEME_KEY: &str = "system.desktop_theme.v2";
 const WALLPAPER_KEY: &str = "system.desktop_wallpaper.v1";
-const TERMINAL_HISTORY_KEY: &str = "retrodesk.terminal_history.v1";
+const NOTIFICATIONS_KEY: &str = "system.notifications.v1";
+const NOTIFICATION_DELIVERIES_KEY: &str = "system.notification_deliveries.v1";
+/// Prefs key for the shell history list persisted by [`system_shell::HistoryStore`].
+pub(crate) const TERMINAL_HISTORY_KEY: &str = "retrodesk.terminal_history.v1";
+/// Prefs key for scheduled shell jobs persisted by [`system_shell::ShellScheduler`].
+pub(crate) const SHELL_SCHEDULE_KEY: &str = "system.shell_schedule.v1";
+/// Prefs key for the command audit log persisted by [`system_shell::ShellAuditLog`].
+pub(crate) const SHELL_AUDIT_KEY: &str = "system.shell_audit.v1";
 /// Persisted runtime policy overlay key for app capability grants.
 pub const APP_POLICY_KEY: &str = "system.app_policy.v1";
 
@@ -44,7 +55,6 @@ struct LegacyDesktopSnapshotV1 {
     windows: Vec<crate::model::WindowRecord>,
     last_explorer_path: Option<String>,
     last_notepad_slug: Option<String>,
-    terminal_history: Vec<String>,
     #[serde(default)]
     app_shared_state: std::collections::BTreeMap<String, serde_json::Value>,
 }
@@ -63,54 +73,27 @@ fn migrate_desktop_snapshot(
                 windows: legacy.windows,
                 last_explorer_path: legacy.last_explorer_path,
                 last_notepad_slug: legacy.last_notepad_slug,
-                terminal_history: legacy.terminal_history,
                 app_shared_state: legacy.app_shared_state,
+                active_workspace: 0,
             }))
         }
         _ => Ok(None),
     }
 }
 
-/// Loads the compatibility boot snapshot and terminal history if present.
+/// Loads the compatibility boot snapshot if present.
 ///
-/// On non-WASM targets this returns `None`.
+/// Terminal history is hydrated separately by the shell engine's history store. On non-WASM
+/// targets this returns `None`.
 pub async fn load_boot_snapshot(_host: &DesktopHostContext) -> Option<DesktopSnapshot> {
     #[cfg(target_arch = "wasm32")]
     {
-        let host = _host;
         let storage = local_storage()?;
-        let snapshot = storage
+        storage
             .get_item(SNAPSHOT_KEY)
             .ok()
             .flatten()
-            .and_then(|raw| serde_json::from_str::<DesktopSnapshot>(&raw).ok());
-        let terminal_history =
-            match load_pref_with(host.prefs_store().as_ref(), TERMINAL_HISTORY_KEY).await {
-                Ok(history) => history,
-                Err(err) => {
-                    leptos::logging::warn!("terminal history compatibility load failed: {err}");
-                    None
-                }
-            };
-
-        match (snapshot, terminal_history) {
-            (None, None) => None,
-            (Some(mut snapshot), history) => {
-                if let Some(history) = history {
-                    snapshot.terminal_history = history;
-                }
-                Some(snapshot)
-            }
-            (None, Some(history)) => Some(DesktopSnapshot {
-                schema_version: crate::model::DESKTOP_LAYOUT_SCHEMA_VERSION,
-                preferences: Default::default(),
-                windows: Vec::new(),
-                last_explorer_path: None,
-                last_notepad_slug: None,
-                terminal_history: history,
-                app_shared_state: Default::default(),
-            }),
-        }
+            .and_then(|raw| serde_json::from_str::<DesktopSnapshot>(&raw).ok())
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -133,7 +116,8 @@ pub async fn load_durable_boot_snapshot(host: &DesktopHostContext) -> Option<Des
     {
         Ok(snapshot) => snapshot,
         Err(err) => {
-            leptos::logging::warn!("durable boot snapshot load failed: {err}");
+            host.logger()
+                .warn(format!("durable boot snapshot load failed: {err}"));
             None
         }
     }
@@ -193,7 +177,8 @@ pub async fn load_theme(host: &DesktopHostContext) -> Option<DesktopTheme> {
             audio_enabled: legacy.audio_enabled,
         }),
         Err(err) => {
-            leptos::logging::warn!("desktop theme load failed: {err}");
+            host.logger()
+                .warn(format!("desktop theme load failed: {err}"));
             None
         }
     }
@@ -218,7 +203,100 @@ pub async fn load_wallpaper(host: &DesktopHostContext) -> Option<WallpaperConfig
             ..WallpaperConfig::default()
         }),
         Err(err) => {
-            leptos::logging::warn!("desktop wallpaper load failed: {err}");
+            host.logger()
+                .warn(format!("desktop wallpaper load failed: {err}"));
+            None
+        }
+    }
+}
+
+/// Persists the notification center history through typed host prefs storage.
+pub async fn persist_notifications(
+    host: &DesktopHostContext,
+    notifications: &[NotificationRecord],
+) -> Result<(), String> {
+    save_pref_with(
+        host.prefs_store().as_ref(),
+        NOTIFICATIONS_KEY,
+        &notifications,
+    )
+    .await
+}
+
+/// Persists per-app notification delivery modes through typed host prefs storage.
+pub async fn persist_notification_deliveries(
+    host: &DesktopHostContext,
+    deliveries: &BTreeMap<String, NotificationDelivery>,
+) -> Result<(), String> {
+    save_pref_with(
+        host.prefs_store().as_ref(),
+        NOTIFICATION_DELIVERIES_KEY,
+        deliveries,
+    )
+    .await
+}
+
+/// Persists runtime capability grants through the host permissions registry.
+pub async fn persist_capability_grants(
+    host: &DesktopHostContext,
+    grants: &BTreeMap<String, Vec<AppCapability>>,
+) -> Result<(), String> {
+    let mut registry_grants = PermissionGrants::default();
+    for (app_id, capabilities) in grants {
+        for capability in capabilities {
+            registry_grants.grant(app_id, capability.as_str());
+        }
+    }
+    host.permissions_registry().replace(registry_grants).await
+}
+
+/// Loads runtime capability grants from the host permissions registry, skipping unknown ids.
+pub async fn load_capability_grants(
+    host: &DesktopHostContext,
+) -> Option<BTreeMap<String, Vec<AppCapability>>> {
+    match host.permissions_registry().load().await {
+        Ok(grants) => Some(
+            grants
+                .apps
+                .into_iter()
+                .map(|(app_id, capabilities)| {
+                    let capabilities = capabilities
+                        .iter()
+                        .filter_map(|id| AppCapability::from_id(id))
+                        .collect();
+                    (app_id, capabilities)
+                })
+                .collect(),
+        ),
+        Err(err) => {
+            host.logger()
+                .warn(format!("capability grants load failed: {err}"));
+            None
+        }
+    }
+}
+
+/// Loads the notification center history from typed prefs.
+pub async fn load_notifications(host: &DesktopHostContext) -> Option<Vec<NotificationRecord>> {
+    match load_pref_with(host.prefs_store().as_ref(), NOTIFICATIONS_KEY).await {
+        Ok(value) => value,
+        Err(err) => {
+            host.logger()
+                .warn(format!("notification history load failed: {err}"));
+            None
+        }
+    }
+}
+
+/// Loads per-app notification delivery modes from typed prefs.
+pub async fn load_notification_deliveries(
+    host: &DesktopHostContext,
+) -> Option<BTreeMap<String, NotificationDelivery>> {
+    match load_pref_with(host.prefs_store().as_ref(), NOTIFICATION_DELIVERIES_KEY).await {
+        Ok(value) => value,
+        Err(err) => {
+            host.logger()
+                .warn(format!("notification delivery load failed: {err}"));
             None
         }
     }
@@ -243,26 +321,20 @@ async fn load_legacy_theme(host: &DesktopHostContext) -> Option<LegacyThemePaylo
     match load_pref_with(host.prefs_store().as_ref(), LEGACY_THEME_KEY).await {
         Ok(value) => value,
         Err(err) => {
-            leptos::logging::warn!("legacy theme compatibility load failed: {err}");
+            host.logger()
+                .warn(format!("legacy theme compatibility load failed: {err}"));
             None
         }
     }
 }
 
-/// Persists the terminal history list through typed host prefs storage.
-pub async fn persist_terminal_history(
-    host: &DesktopHostContext,
-    history: &[String],
-) -> Result<(), String> {
-    save_pref_with(host.prefs_store().as_ref(), TERMINAL_HISTORY_KEY, &history).await
-}
-
 /// Loads app capability policy overlay from typed host prefs storage.
 pub async fn load_app_policy_overlay(host: &DesktopHostContext) -> Option<AppPolicyOverlay> {
     match load_pref_with(host.prefs_store().as_ref(), APP_POLICY_KEY).await {
         Ok(value) => value,
         Err(err) => {
-            leptos::logging::warn!("app policy overlay load failed: {err}");
+            host.logger()
+                .warn(format!("app policy overlay load failed: {err}"));
             None
         }
     }
@@ -2,19 +2,24 @@
 
 mod appearance;
 
-use desktop_app_contract::{AppCapability, AppCommand, AppEvent, AppLifecycleEvent, ApplicationId};
+use desktop_app_contract::{
+    AppCapability, AppCommand, AppEvent, AppLifecycleEvent, AppLink, ApplicationId, Notification,
+    NotificationDelivery, NotificationTarget, IPC_REPLY_TOPIC, NOTIFICATION_MAX_ACTIONS,
+};
 use platform_host::{
     WallpaperAssetMetadataPatch, WallpaperAssetRecord, WallpaperCollection, WallpaperConfig,
     WallpaperImportRequest, WallpaperLibrarySnapshot,
 };
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 use crate::apps;
 use crate::model::{
     DeepLinkOpenTarget, DeepLinkState, DesktopSkin, DesktopSnapshot, DesktopState, DesktopTheme,
-    InteractionState, OpenWindowRequest, PointerPosition, ResizeEdge, ResizeSession, WindowId,
-    WindowRecord, WindowRect, DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH,
+    InteractionState, NotificationRecord, OpenWindowRequest, PointerPosition, ResizeEdge,
+    ResizeSession, WindowId, WindowRecord, WindowRect, WindowSwitcherSession,
+    DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH, NOTIFICATION_HISTORY_LIMIT, WORKSPACE_COUNT,
 };
 use crate::window_manager::{
     focus_window_internal, normalize_window_stack, resize_rect, snap_window_to_viewport_edge,
@@ -69,6 +74,31 @@ pub enum DesktopAction {
         /// Window associated with the taskbar button.
         window_id: WindowId,
     },
+    /// Show another workspace, focusing its topmost window.
+    ///
+    /// Workspaces past [`WORKSPACE_COUNT`] are ignored.
+    SwitchWorkspace {
+        /// Workspace to show.
+        workspace: u32,
+    },
+    /// Assign a window to another workspace without switching to it.
+    MoveWindowToWorkspace {
+        /// Window to move.
+        window_id: WindowId,
+        /// Destination workspace, below [`WORKSPACE_COUNT`].
+        workspace: u32,
+    },
+    /// Open the window switcher on the active workspace's windows, or move its selection.
+    ///
+    /// Opening selects the window used before the focused one (or the last one when `reverse`).
+    CycleWindowSwitcher {
+        /// Whether to move the selection backwards.
+        reverse: bool,
+    },
+    /// Close the window switcher and bring its selected window to the front.
+    CommitWindowSwitcher,
+    /// Close the window switcher without changing focus.
+    CancelWindowSwitcher,
     /// Toggle the start menu open/closed.
     ToggleStartMenu,
     /// Close the start menu if open.
@@ -193,11 +223,6 @@ pub enum DesktopAction {
         /// Whether reduced motion is enabled.
         enabled: bool,
     },
-    /// Append a command to terminal history (subject to preferences and limits).
-    PushTerminalHistory {
-        /// Terminal command text.
-        command: String,
-    },
     /// Replace the app-specific state payload for a window.
     SetAppState {
         /// Window whose app state should be replaced.
@@ -214,6 +239,11 @@ pub enum DesktopAction {
         /// Shared state payload.
         state: Value,
     },
+    /// Drop every shared state key of an app.
+    ClearSharedAppState {
+        /// App whose shared state is dropped.
+        app_id: ApplicationId,
+    },
     /// Hydrate runtime state from a persisted snapshot.
     HydrateSnapshot {
         /// Snapshot payload to restore.
@@ -224,6 +254,70 @@ pub enum DesktopAction {
         /// Parsed deep-link payload.
         deep_link: DeepLinkState,
     },
+    /// Route a `web+os://` link to the app it names.
+    OpenLink {
+        /// Link to open.
+        link: AppLink,
+    },
+    /// Launch `app_id` with the launch params of one of its manifest quick actions.
+    LaunchQuickAction {
+        /// App that declared the quick action.
+        app_id: ApplicationId,
+        /// Quick action id from the app manifest.
+        action_id: String,
+    },
+    /// Replace the notification center history with the persisted one.
+    HydrateNotifications {
+        /// Persisted notifications, oldest first.
+        notifications: Vec<NotificationRecord>,
+    },
+    /// Replace the per-app notification delivery modes with the persisted ones.
+    HydrateNotificationDeliveries {
+        /// Persisted delivery modes keyed by app id.
+        deliveries: BTreeMap<String, NotificationDelivery>,
+    },
+    /// Change how an app's notifications are delivered.
+    SetNotificationDelivery {
+        /// App whose notifications are affected.
+        app_id: ApplicationId,
+        /// New delivery mode; [`NotificationDelivery::Banner`] restores the default.
+        delivery: NotificationDelivery,
+    },
+    /// Open what a notification, or one of its action buttons, points at and remove it.
+    ActivateNotification {
+        /// Notification to activate.
+        notification_id: u64,
+        /// Index of the clicked action button, or `None` for the notification itself.
+        action: Option<usize>,
+    },
+    /// Remove one notification from the notification center.
+    DismissNotification {
+        /// Notification to remove.
+        notification_id: u64,
+    },
+    /// Remove every notification from the notification center.
+    ClearNotifications,
+    /// Mark every notification as read.
+    MarkNotificationsRead,
+    /// Replace the runtime capability grants with the persisted ones.
+    HydrateCapabilityGrants {
+        /// Persisted grants keyed by app id.
+        grants: BTreeMap<String, Vec<AppCapability>>,
+    },
+    /// Record that the user granted an app a requestable capability.
+    GrantCapability {
+        /// App receiving the grant.
+        app_id: ApplicationId,
+        /// Granted capability; capabilities that are not requestable are ignored.
+        capability: AppCapability,
+    },
+    /// Remove a capability the user granted an app at runtime.
+    RevokeCapability {
+        /// App losing the grant.
+        app_id: ApplicationId,
+        /// Revoked capability.
+        capability: AppCapability,
+    },
     /// Marks asynchronous boot hydration as complete for the current runtime session.
     BootHydrationComplete,
 }
@@ -237,12 +331,20 @@ pub enum RuntimeEffect {
     PersistTheme,
     /// Persist wallpaper changes.
     PersistWallpaper,
-    /// Persist terminal history changes.
-    PersistTerminalHistory,
+    /// Persist the notification center history.
+    PersistNotifications,
+    /// Persist the per-app notification delivery modes.
+    PersistNotificationDeliveries,
+    /// Persist the runtime capability grants.
+    PersistCapabilityGrants,
     /// Move focus into the newly focused window's primary input.
     FocusWindowInput(WindowId),
+    /// Maximize a window into the current desktop viewport, which only the host can measure.
+    MaximizeWindowToViewport(WindowId),
     /// Parse and open deep-link targets in the UI layer.
     ParseAndOpenDeepLink(DeepLinkState),
+    /// Route a link to a window's link handler, or launch its app, in the UI layer.
+    OpenLink(AppLink),
     /// Open an external URL (for app actions that leave the shell).
     OpenExternalUrl(String),
     /// Play a named UI sound effect.
@@ -261,6 +363,15 @@ pub enum RuntimeEffect {
         /// Event payload.
         event: AppEvent,
     },
+    /// Delivers an IPC reply if it answers a pending request of the target routed to the responder.
+    DeliverIpcReply {
+        /// Window that sent the reply.
+        responder: WindowId,
+        /// Requesting window.
+        window_id: WindowId,
+        /// Reply event on [`IPC_REPLY_TOPIC`].
+        event: AppEvent,
+    },
     /// Subscribes a window to an app-bus topic.
     SubscribeWindowTopic {
         /// Target window id.
@@ -468,6 +579,8 @@ pub fn reduce_desktop(
                 app_state: req.app_state,
                 launch_params: req.launch_params,
                 last_lifecycle_event: None,
+                badge_count: 0,
+                workspace: state.active_workspace,
             };
             state.windows.push(record);
             if !focus_window_internal(state, window_id) {
@@ -646,6 +759,85 @@ pub fn reduce_desktop(
                 effects.extend(nested);
             }
         }
+        DesktopAction::SwitchWorkspace { workspace } => {
+            if workspace >= WORKSPACE_COUNT || workspace == state.active_workspace {
+                return Ok(effects);
+            }
+            let previous_focus = state.focused_window_id();
+            state.active_workspace = workspace;
+            state.start_menu_open = false;
+            normalize_window_stack(state);
+            let next_focus = state.focused_window_id();
+            emit_focus_transition(previous_focus, next_focus, state, &mut effects);
+            if let Some(next) = next_focus {
+                effects.push(RuntimeEffect::FocusWindowInput(next));
+            }
+            effects.push(RuntimeEffect::PersistLayout);
+        }
+        DesktopAction::MoveWindowToWorkspace {
+            window_id,
+            workspace,
+        } => {
+            if workspace >= WORKSPACE_COUNT {
+                return Ok(effects);
+            }
+            let previous_focus = state.focused_window_id();
+            find_window_mut(state, window_id)?.workspace = workspace;
+            normalize_window_stack(state);
+            let next_focus = state.focused_window_id();
+            emit_focus_transition(previous_focus, next_focus, state, &mut effects);
+            effects.push(RuntimeEffect::PersistLayout);
+        }
+        DesktopAction::CycleWindowSwitcher { reverse } => {
+            let step = |index: usize, len: usize| {
+                if reverse {
+                    (index + len - 1) % len
+                } else {
+                    (index + 1) % len
+                }
+            };
+            if let Some(session) = interaction.window_switcher.as_mut() {
+                session.selected = step(session.selected, session.windows.len());
+                return Ok(effects);
+            }
+            let windows = state.recently_used_window_ids();
+            if windows.is_empty() {
+                return Ok(effects);
+            }
+            state.start_menu_open = false;
+            interaction.window_switcher = Some(WindowSwitcherSession {
+                selected: step(0, windows.len()),
+                windows,
+            });
+        }
+        DesktopAction::CommitWindowSwitcher => {
+            let Some(window_id) = interaction
+                .window_switcher
+                .take()
+                .and_then(|session| session.selected_window_id())
+            else {
+                return Ok(effects);
+            };
+            // The selected window may have closed while the switcher was open.
+            let Some(minimized) = state
+                .windows
+                .iter()
+                .find(|w| w.id == window_id)
+                .map(|w| w.minimized)
+            else {
+                return Ok(effects);
+            };
+            let action = if minimized {
+                DesktopAction::RestoreWindow { window_id }
+            } else {
+                DesktopAction::FocusWindow { window_id }
+            };
+            let nested = reduce_desktop(state, interaction, action)?;
+            effects.extend(nested);
+        }
+        DesktopAction::CancelWindowSwitcher => {
+            interaction.window_switcher = None;
+        }
         DesktopAction::ToggleStartMenu => {
             state.start_menu_open = !state.start_menu_open;
         }
@@ -774,7 +966,7 @@ pub fn reduce_desktop(
                 .map(|w| w.app_id.clone())
                 .ok_or(ReducerError::WindowNotFound)?;
             if let Some(required) = command_required_capability(&command) {
-                if !command_allowed_for_app(&source_app_id, required) {
+                if !command_allowed_for_app(state, &source_app_id, required) {
                     return Ok(effects);
                 }
             }
@@ -787,6 +979,70 @@ pub fn reduce_desktop(
                         effects.push(RuntimeEffect::PersistLayout);
                     }
                 }
+                AppCommand::SetWindowSize { width, height } => {
+                    let window = find_window_mut(state, window_id)?;
+                    if window.flags.resizable && !window.maximized {
+                        let rect = WindowRect {
+                            w: width,
+                            h: height,
+                            ..window.rect
+                        }
+                        .clamped_min(MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT);
+                        if window.rect != rect {
+                            window.rect = rect;
+                            effects.push(RuntimeEffect::PersistLayout);
+                        }
+                    }
+                }
+                AppCommand::SetWindowPosition { x, y } => {
+                    let window = find_window_mut(state, window_id)?;
+                    if !window.maximized && (window.rect.x, window.rect.y) != (x, y) {
+                        window.rect = WindowRect {
+                            x,
+                            y,
+                            ..window.rect
+                        };
+                        effects.push(RuntimeEffect::PersistLayout);
+                    }
+                }
+                AppCommand::MinimizeWindow => {
+                    let window = find_window_mut(state, window_id)?;
+                    if window.flags.minimizable && !window.minimized {
+                        let nested = reduce_desktop(
+                            state,
+                            interaction,
+                            DesktopAction::MinimizeWindow { window_id },
+                        )?;
+                        effects.extend(nested);
+                    }
+                }
+                AppCommand::MaximizeWindow => {
+                    let window = find_window_mut(state, window_id)?;
+                    if window.flags.maximizable && !window.maximized {
+                        effects.push(RuntimeEffect::MaximizeWindowToViewport(window_id));
+                    }
+                }
+                AppCommand::RestoreWindow => {
+                    let window = find_window_mut(state, window_id)?;
+                    if window.minimized || window.maximized {
+                        let nested = reduce_desktop(
+                            state,
+                            interaction,
+                            DesktopAction::RestoreWindow { window_id },
+                        )?;
+                        effects.extend(nested);
+                    }
+                }
+                AppCommand::SetWindowIcon { icon_id } => {
+                    let window = find_window_mut(state, window_id)?;
+                    if !icon_id.trim().is_empty() && window.icon_id != icon_id {
+                        window.icon_id = icon_id;
+                        effects.push(RuntimeEffect::PersistLayout);
+                    }
+                }
+                AppCommand::SetWindowBadge { count } => {
+                    find_window_mut(state, window_id)?.badge_count = count;
+                }
                 AppCommand::PersistState { state: app_state } => {
                     let nested = reduce_desktop(
                         state,
@@ -970,19 +1226,115 @@ pub fn reduce_desktop(
                     )?;
                     effects.extend(nested);
                 }
-                AppCommand::Notify { title, body } => {
-                    effects.push(RuntimeEffect::Notify { title, body });
+                AppCommand::Notify { mut notification } => {
+                    let delivery = state
+                        .notification_deliveries
+                        .get(source_app_id.as_str())
+                        .copied()
+                        .unwrap_or_default();
+                    match delivery {
+                        NotificationDelivery::Muted => return Ok(effects),
+                        NotificationDelivery::Silent => {}
+                        NotificationDelivery::Banner => effects.push(RuntimeEffect::Notify {
+                            title: notification.title.clone(),
+                            body: notification.body.clone(),
+                        }),
+                    }
+                    notification.actions.truncate(NOTIFICATION_MAX_ACTIONS);
+                    post_notification(state, source_app_id, notification);
+                    effects.push(RuntimeEffect::PersistNotifications);
                 }
-            }
-        }
-        DesktopAction::PushTerminalHistory { command } => {
-            if state.preferences.terminal_history_enabled && !command.trim().is_empty() {
-                state.terminal_history.push(command);
-                if state.terminal_history.len() > 100 {
-                    let overflow = state.terminal_history.len() - 100;
-                    state.terminal_history.drain(0..overflow);
+                AppCommand::LaunchApp {
+                    app_id,
+                    launch_params,
+                } => {
+                    let Some(action) = launch_app_action(state, app_id, launch_params, false)
+                    else {
+                        return Ok(effects);
+                    };
+                    let nested = reduce_desktop(state, interaction, action)?;
+                    effects.extend(nested);
+                }
+                AppCommand::OpenLink { link } => {
+                    effects.push(RuntimeEffect::OpenLink(link));
+                }
+                AppCommand::OpenWindow {
+                    app_id,
+                    launch_params,
+                } => {
+                    // Apps may open more windows of themselves regardless of single-instance.
+                    let own_app = app_id == source_app_id;
+                    let Some(action) = launch_app_action(state, app_id, launch_params, own_app)
+                    else {
+                        return Ok(effects);
+                    };
+                    let nested = reduce_desktop(state, interaction, action)?;
+                    effects.extend(nested);
+                }
+                AppCommand::RevokeCapability { app_id, capability } => {
+                    if apps::app_is_privileged_by_id(&source_app_id) {
+                        let nested = reduce_desktop(
+                            state,
+                            interaction,
+                            DesktopAction::RevokeCapability { app_id, capability },
+                        )?;
+                        effects.extend(nested);
+                    }
+                }
+                AppCommand::SetNotificationDelivery { app_id, delivery } => {
+                    if apps::app_is_privileged_by_id(&source_app_id) {
+                        let nested = reduce_desktop(
+                            state,
+                            interaction,
+                            DesktopAction::SetNotificationDelivery { app_id, delivery },
+                        )?;
+                        effects.extend(nested);
+                    }
+                }
+                AppCommand::ClearSharedState { app_id } => {
+                    if apps::app_is_privileged_by_id(&source_app_id) {
+                        let nested = reduce_desktop(
+                            state,
+                            interaction,
+                            DesktopAction::ClearSharedAppState { app_id },
+                        )?;
+                        effects.extend(nested);
+                    }
+                }
+                AppCommand::ForceCloseWindow {
+                    window_id: target_window_id,
+                } => {
+                    let target = WindowId(target_window_id);
+                    // Forced closes bypass the target's lifecycle handler, which only the UI
+                    // layer consults before dispatching `CloseWindow`.
+                    if state.windows.iter().any(|w| w.id == target) {
+                        let nested = reduce_desktop(
+                            state,
+                            interaction,
+                            DesktopAction::CloseWindow { window_id: target },
+                        )?;
+                        effects.extend(nested);
+                    }
+                }
+                AppCommand::ReplyToWindow {
+                    target_window_id,
+                    topic,
+                    correlation_id,
+                    payload,
+                } => {
+                    let target = WindowId(target_window_id);
+                    // Replies to a window that has since closed are dropped; the app runtime
+                    // drops replies to requests that were not routed to this window.
+                    if topic == IPC_REPLY_TOPIC && state.windows.iter().any(|w| w.id == target) {
+                        let event = AppEvent::new(topic, payload, Some(window_id.0))
+                            .with_correlation(Some(correlation_id), None);
+                        effects.push(RuntimeEffect::DeliverIpcReply {
+                            responder: window_id,
+                            window_id: target,
+                            event,
+                        });
+                    }
                 }
-                effects.push(RuntimeEffect::PersistTerminalHistory);
             }
         }
         DesktopAction::SetAppState {
@@ -1002,17 +1354,33 @@ pub fn reduce_desktop(
             state.app_shared_state.insert(storage_key, shared);
             effects.push(RuntimeEffect::PersistLayout);
         }
+        DesktopAction::ClearSharedAppState { app_id } => {
+            let prefix = format!("{}:", app_id.as_str());
+            let before = state.app_shared_state.len();
+            state
+                .app_shared_state
+                .retain(|storage_key, _| !storage_key.starts_with(&prefix));
+            if state.app_shared_state.len() != before {
+                effects.push(RuntimeEffect::PersistLayout);
+            }
+        }
         DesktopAction::HydrateSnapshot { snapshot } => {
             let max_restore = state.preferences.max_restore_windows;
             let theme = state.theme.clone();
             let wallpaper_config = state.wallpaper.clone();
             let wallpaper_preview = state.wallpaper_preview.clone();
             let wallpaper_library = state.wallpaper_library.clone();
+            let notifications = std::mem::take(&mut state.notifications);
+            let capability_grants = std::mem::take(&mut state.capability_grants);
+            let notification_deliveries = std::mem::take(&mut state.notification_deliveries);
             *state = DesktopState::from_snapshot(snapshot);
             state.theme = theme;
             state.wallpaper = wallpaper_config;
             state.wallpaper_preview = wallpaper_preview;
             state.wallpaper_library = wallpaper_library;
+            state.notifications = notifications;
+            state.capability_grants = capability_grants;
+            state.notification_deliveries = notification_deliveries;
             if state.windows.len() > max_restore {
                 state.windows.truncate(max_restore);
             }
@@ -1038,6 +1406,145 @@ pub fn reduce_desktop(
         DesktopAction::ApplyDeepLink { deep_link } => {
             effects.push(RuntimeEffect::ParseAndOpenDeepLink(deep_link));
         }
+        DesktopAction::OpenLink { link } => {
+            // Link handlers live in mounted app views, which only the UI layer can reach.
+            effects.push(RuntimeEffect::OpenLink(link));
+        }
+        DesktopAction::LaunchQuickAction { app_id, action_id } => {
+            let Some(quick_action) = apps::app_quick_actions_by_id(&app_id)
+                .iter()
+                .find(|quick_action| quick_action.id == action_id)
+            else {
+                return Ok(effects);
+            };
+            let Some(action) =
+                launch_app_action(state, app_id, quick_action.launch_params.clone(), false)
+            else {
+                return Ok(effects);
+            };
+            let nested = reduce_desktop(state, interaction, action)?;
+            effects.extend(nested);
+        }
+        DesktopAction::HydrateNotifications { notifications } => {
+            state.notifications = notifications;
+            let overflow = state
+                .notifications
+                .len()
+                .saturating_sub(NOTIFICATION_HISTORY_LIMIT);
+            state.notifications.drain(..overflow);
+        }
+        DesktopAction::HydrateNotificationDeliveries { mut deliveries } => {
+            deliveries.retain(|_, delivery| *delivery != NotificationDelivery::Banner);
+            state.notification_deliveries = deliveries;
+        }
+        DesktopAction::SetNotificationDelivery { app_id, delivery } => {
+            let previous = if delivery == NotificationDelivery::Banner {
+                state.notification_deliveries.remove(app_id.as_str())
+            } else {
+                state
+                    .notification_deliveries
+                    .insert(app_id.as_str().to_string(), delivery)
+            };
+            if previous.unwrap_or_default() != delivery {
+                effects.push(RuntimeEffect::PersistNotificationDeliveries);
+            }
+        }
+        DesktopAction::ActivateNotification {
+            notification_id,
+            action,
+        } => {
+            let Some(index) = state
+                .notifications
+                .iter()
+                .position(|record| record.id == notification_id)
+            else {
+                return Ok(effects);
+            };
+            let record = &state.notifications[index];
+            let target = match action {
+                Some(action) => match record.notification.actions.get(action) {
+                    Some(action) => Some(action.target.clone()),
+                    None => return Ok(effects),
+                },
+                None => record.notification.on_click.clone(),
+            };
+            let record = state.notifications.remove(index);
+            effects.push(RuntimeEffect::PersistNotifications);
+            let next = match target {
+                Some(NotificationTarget {
+                    link: Some(link), ..
+                }) => {
+                    effects.push(RuntimeEffect::OpenLink(link));
+                    None
+                }
+                Some(target) => {
+                    launch_app_action(state, target.app_id, target.launch_params, false)
+                }
+                None => focus_app_action(state, record.app_id),
+            };
+            if let Some(next) = next {
+                let nested = reduce_desktop(state, interaction, next)?;
+                effects.extend(nested);
+            }
+        }
+        DesktopAction::DismissNotification { notification_id } => {
+            let before = state.notifications.len();
+            state
+                .notifications
+                .retain(|record| record.id != notification_id);
+            if state.notifications.len() != before {
+                effects.push(RuntimeEffect::PersistNotifications);
+            }
+        }
+        DesktopAction::ClearNotifications => {
+            if !state.notifications.is_empty() {
+                state.notifications.clear();
+                effects.push(RuntimeEffect::PersistNotifications);
+            }
+        }
+        DesktopAction::MarkNotificationsRead => {
+            let mut changed = false;
+            for record in state.notifications.iter_mut().filter(|record| !record.read) {
+                record.read = true;
+                changed = true;
+            }
+            if changed {
+                effects.push(RuntimeEffect::PersistNotifications);
+            }
+        }
+        DesktopAction::HydrateCapabilityGrants { mut grants } => {
+            for capabilities in grants.values_mut() {
+                capabilities.retain(|capability| capability.is_requestable());
+            }
+            grants.retain(|_, capabilities| !capabilities.is_empty());
+            state.capability_grants = grants;
+        }
+        DesktopAction::GrantCapability { app_id, capability } => {
+            if !capability.is_requestable() {
+                return Ok(effects);
+            }
+            let granted = state
+                .capability_grants
+                .entry(app_id.as_str().to_string())
+                .or_default();
+            if !granted.contains(&capability) {
+                granted.push(capability);
+                effects.push(RuntimeEffect::PersistCapabilityGrants);
+            }
+        }
+        DesktopAction::RevokeCapability { app_id, capability } => {
+            let Some(granted) = state.capability_grants.get_mut(app_id.as_str()) else {
+                return Ok(effects);
+            };
+            let before = granted.len();
+            granted.retain(|existing| *existing != capability);
+            if granted.len() != before {
+                if granted.is_empty() {
+                    state.capability_grants.remove(app_id.as_str());
+                }
+                effects.push(RuntimeEffect::PersistCapabilityGrants);
+            }
+        }
         DesktopAction::BootHydrationComplete => {
             state.boot_hydrated = true;
         }
@@ -1067,6 +1574,11 @@ pub fn reduce_desktop(
 pub fn build_open_request_from_deeplink(target: DeepLinkOpenTarget) -> OpenWindowRequest {
     match target {
         DeepLinkOpenTarget::App(app_id) => OpenWindowRequest::new(app_id),
+        DeepLinkOpenTarget::Link(link) => {
+            let mut req = OpenWindowRequest::new(link.app_id.clone());
+            req.launch_params = link.launch_params();
+            req
+        }
         DeepLinkOpenTarget::NotesSlug(slug) => {
             let mut req = OpenWindowRequest::new(ApplicationId::trusted("system.notepad"));
             req.title = Some(format!("Note - {slug}"));
@@ -1084,13 +1596,74 @@ pub fn build_open_request_from_deeplink(target: DeepLinkOpenTarget) -> OpenWindo
     }
 }
 
+/// Action that opens `app_id` for an app command, or focuses its open window when the app is
+/// single-instance and `new_window` is not forced. Unknown apps yield `None`.
+pub(crate) fn launch_app_action(
+    state: &DesktopState,
+    app_id: ApplicationId,
+    launch_params: Value,
+    new_window: bool,
+) -> Option<DesktopAction> {
+    let mut request = apps::default_open_request_by_id(&app_id, None)?;
+    if !new_window
+        && apps::app_descriptor_by_id(&app_id).single_instance
+        && preferred_window_for_app(state, &app_id).is_some()
+    {
+        return Some(DesktopAction::ActivateApp {
+            app_id,
+            viewport: None,
+        });
+    }
+    request.launch_params = launch_params;
+    Some(DesktopAction::OpenWindow(request))
+}
+
+/// Focuses the most relevant window of `app_id`, restoring it if minimized, or opens one.
+pub(crate) fn focus_app_action(
+    state: &DesktopState,
+    app_id: ApplicationId,
+) -> Option<DesktopAction> {
+    let Some(window_id) = preferred_window_for_app(state, &app_id) else {
+        return launch_app_action(state, app_id, Value::Null, false);
+    };
+    let minimized = state
+        .windows
+        .iter()
+        .any(|w| w.id == window_id && w.minimized);
+    Some(if minimized {
+        DesktopAction::RestoreWindow { window_id }
+    } else {
+        DesktopAction::FocusWindow { window_id }
+    })
+}
+
+/// Adds `notification` to the notification center history, dropping the oldest past the limit.
+fn post_notification(state: &mut DesktopState, app_id: ApplicationId, notification: Notification) {
+    let id = state.notifications.last().map_or(1, |record| record.id + 1);
+    state.notifications.push(NotificationRecord {
+        id,
+        app_id,
+        notification,
+        posted_unix_ms: platform_host::unix_time_ms_now(),
+        read: false,
+    });
+    let overflow = state
+        .notifications
+        .len()
+        .saturating_sub(NOTIFICATION_HISTORY_LIMIT);
+    state.notifications.drain(..overflow);
+}
+
 fn next_window_id(state: &mut DesktopState) -> WindowId {
     let id = WindowId(state.next_window_id);
     state.next_window_id = state.next_window_id.saturating_add(1);
     id
 }
 
-fn preferred_window_for_app(state: &DesktopState, app_id: &ApplicationId) -> Option<WindowId> {
+pub(crate) fn preferred_window_for_app(
+    state: &DesktopState,
+    app_id: &ApplicationId,
+) -> Option<WindowId> {
     state
         .windows
         .iter()
@@ -1161,7 +1734,14 @@ fn emit_focus_transition(
 
 fn command_required_capability(command: &AppCommand) -> Option<AppCapability> {
     match command {
-        AppCommand::SetWindowTitle { .. } => Some(AppCapability::Window),
+        AppCommand::SetWindowTitle { .. }
+        | AppCommand::SetWindowSize { .. }
+        | AppCommand::SetWindowPosition { .. }
+        | AppCommand::MinimizeWindow
+        | AppCommand::MaximizeWindow
+        | AppCommand::RestoreWindow
+        | AppCommand::SetWindowIcon { .. }
+        | AppCommand::SetWindowBadge { .. } => Some(AppCapability::Window),
         AppCommand::PersistState { .. } | AppCommand::PersistSharedState { .. } => {
             Some(AppCapability::State)
         }
@@ -1169,7 +1749,8 @@ fn command_required_capability(command: &AppCommand) -> Option<AppCapability> {
         AppCommand::OpenExternalUrl { .. } => Some(AppCapability::ExternalUrl),
         AppCommand::Subscribe { .. }
         | AppCommand::Unsubscribe { .. }
-        | AppCommand::PublishEvent { .. } => Some(AppCapability::Ipc),
+        | AppCommand::PublishEvent { .. }
+        | AppCommand::ReplyToWindow { .. } => Some(AppCapability::Ipc),
         AppCommand::SetDesktopSkin { .. }
         | AppCommand::SetDesktopHighContrast { .. }
         | AppCommand::SetDesktopReducedMotion { .. } => Some(AppCapability::Theme),
@@ -1187,14 +1768,42 @@ fn command_required_capability(command: &AppCommand) -> Option<AppCapability> {
         | AppCommand::DeleteWallpaperCollection { .. }
         | AppCommand::DeleteWallpaperAsset { .. } => Some(AppCapability::Wallpaper),
         AppCommand::Notify { .. } => Some(AppCapability::Notifications),
+        AppCommand::LaunchApp { .. }
+        | AppCommand::OpenWindow { .. }
+        | AppCommand::OpenLink { .. } => Some(AppCapability::Window),
+        AppCommand::ForceCloseWindow { .. } => Some(AppCapability::Processes),
+        // Checked against the privileged app list instead of a capability.
+        AppCommand::RevokeCapability { .. }
+        | AppCommand::ClearSharedState { .. }
+        | AppCommand::SetNotificationDelivery { .. } => None,
     }
 }
 
-fn command_allowed_for_app(app_id: &ApplicationId, required: AppCapability) -> bool {
+fn command_allowed_for_app(
+    state: &DesktopState,
+    app_id: &ApplicationId,
+    required: AppCapability,
+) -> bool {
     if apps::app_is_privileged_by_id(app_id) {
         return true;
     }
-    apps::app_requested_capabilities_by_id(app_id).contains(&required)
+    app_granted_capabilities(state, app_id).contains(&required)
+}
+
+/// Capabilities `app_id` holds: the ones its manifest requests plus the ones granted at runtime.
+pub(crate) fn app_granted_capabilities(
+    state: &DesktopState,
+    app_id: &ApplicationId,
+) -> Vec<AppCapability> {
+    let mut granted = apps::app_requested_capabilities_by_id(app_id).to_vec();
+    if let Some(runtime) = state.capability_grants.get(app_id.as_str()) {
+        for capability in runtime {
+            if !granted.contains(capability) {
+                granted.push(*capability);
+            }
+        }
+    }
+    granted
 }
 
 #[cfg(test)]
@@ -1706,7 +2315,7 @@ mod tests {
     }
 
     #[test]
-    fn minimize_applies_suspend_policy() {
+    fn handle_app_command_launch_app_opens_window_with_launch_params() {
         let mut state = DesktopState::default();
         let mut interaction = InteractionState::default();
         let explorer = open(
@@ -1714,63 +2323,376 @@ mod tests {
             &mut interaction,
             ApplicationId::trusted("system.explorer"),
         );
-        let terminal = open(
+        let calculator = open(
             &mut state,
             &mut interaction,
-            ApplicationId::trusted("system.terminal"),
+            ApplicationId::trusted("system.calculator"),
         );
+        let launch = |app_id: &str| DesktopAction::HandleAppCommand {
+            window_id: explorer,
+            command: AppCommand::LaunchApp {
+                app_id: ApplicationId::trusted(app_id),
+                launch_params: json!({ "path": "/notes.md" }),
+            },
+        };
 
-        let explorer_effects = reduce_desktop(
+        reduce_desktop(&mut state, &mut interaction, launch("system.notepad"))
+            .expect("launch notepad");
+        let notepad = state.windows.last().expect("notepad window");
+        assert_eq!(notepad.app_id, ApplicationId::trusted("system.notepad"));
+        assert_eq!(notepad.launch_params, json!({ "path": "/notes.md" }));
+        assert_eq!(state.focused_window_id(), Some(notepad.id));
+
+        reduce_desktop(&mut state, &mut interaction, launch("system.calculator"))
+            .expect("launch calculator");
+        assert_eq!(state.windows.len(), 3);
+        assert_eq!(state.focused_window_id(), Some(calculator));
+
+        reduce_desktop(&mut state, &mut interaction, launch("system.unknown"))
+            .expect("unknown app is ignored");
+        assert_eq!(state.windows.len(), 3);
+    }
+
+    #[test]
+    fn quick_actions_launch_apps_with_manifest_launch_params() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let explorer = ApplicationId::trusted("system.explorer");
+        open(&mut state, &mut interaction, explorer.clone());
+        let quick_action = |action_id: &str| DesktopAction::LaunchQuickAction {
+            app_id: explorer.clone(),
+            action_id: action_id.to_string(),
+        };
+
+        reduce_desktop(&mut state, &mut interaction, quick_action("open-documents"))
+            .expect("launch quick action");
+        assert_eq!(state.windows.len(), 2);
+        let documents = state.windows.last().expect("quick action window");
+        assert_eq!(documents.app_id, explorer);
+        assert_eq!(documents.launch_params, json!({ "path": "/Documents" }));
+        assert_eq!(state.focused_window_id(), Some(documents.id));
+
+        reduce_desktop(&mut state, &mut interaction, quick_action("missing"))
+            .expect("unknown quick action is ignored");
+        assert_eq!(state.windows.len(), 2);
+    }
+
+    #[test]
+    fn app_links_route_through_the_ui_layer_and_deep_links_launch_them() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let explorer = open(
             &mut state,
             &mut interaction,
-            DesktopAction::MinimizeWindow {
-                window_id: explorer,
-            },
-        )
-        .expect("minimize explorer");
-        let explorer_window = state.windows.iter().find(|w| w.id == explorer).unwrap();
-        assert!(explorer_window.suspended);
-        assert!(
-            explorer_effects.contains(&RuntimeEffect::DispatchLifecycle {
-                window_id: explorer,
-                event: AppLifecycleEvent::Suspended,
-            })
+            ApplicationId::trusted("system.explorer"),
         );
+        let link = AppLink::new(ApplicationId::trusted("system.settings"))
+            .with_param("section", "display");
 
-        let terminal_effects = reduce_desktop(
+        let effects = reduce_desktop(
             &mut state,
             &mut interaction,
-            DesktopAction::MinimizeWindow {
-                window_id: terminal,
+            DesktopAction::HandleAppCommand {
+                window_id: explorer,
+                command: AppCommand::OpenLink { link: link.clone() },
             },
         )
-        .expect("minimize terminal");
-        let terminal_window = state.windows.iter().find(|w| w.id == terminal).unwrap();
-        assert!(!terminal_window.suspended);
-        assert!(
-            !terminal_effects.contains(&RuntimeEffect::DispatchLifecycle {
-                window_id: terminal,
-                event: AppLifecycleEvent::Suspended,
-            })
-        );
+        .expect("open link");
+        assert!(effects.contains(&RuntimeEffect::OpenLink(link.clone())));
+        assert_eq!(state.windows.len(), 1);
+
+        let request = build_open_request_from_deeplink(DeepLinkOpenTarget::Link(link));
+        assert_eq!(request.app_id, ApplicationId::trusted("system.settings"));
+        assert_eq!(request.launch_params, json!({ "section": "display" }));
     }
 
     #[test]
-    fn close_flow_emits_closing_then_closed() {
+    fn handle_app_command_open_window_lets_apps_open_more_of_their_own_windows() {
         let mut state = DesktopState::default();
         let mut interaction = InteractionState::default();
-        let _first = open(
+        let terminal = open(
             &mut state,
             &mut interaction,
-            ApplicationId::trusted("system.explorer"),
+            ApplicationId::trusted("system.terminal"),
         );
-        let second = open(
+        let explorer = open(
             &mut state,
             &mut interaction,
-            ApplicationId::trusted("system.notepad"),
+            ApplicationId::trusted("system.explorer"),
         );
+        let open_window = |window_id: WindowId, app_id: &str| DesktopAction::HandleAppCommand {
+            window_id,
+            command: AppCommand::OpenWindow {
+                app_id: ApplicationId::trusted(app_id),
+                launch_params: json!({ "cwd": "/Projects" }),
+            },
+        };
 
-        let effects = reduce_desktop(
+        reduce_desktop(
+            &mut state,
+            &mut interaction,
+            open_window(terminal, "system.terminal"),
+        )
+        .expect("open second terminal");
+        let second = state.windows.last().expect("second terminal window");
+        assert_eq!(second.app_id, ApplicationId::trusted("system.terminal"));
+        assert_ne!(second.id, terminal);
+        assert_eq!(second.launch_params, json!({ "cwd": "/Projects" }));
+        assert_eq!(state.focused_window_id(), Some(second.id));
+        let second = second.id;
+        reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::FocusWindow {
+                window_id: explorer,
+            },
+        )
+        .expect("focus explorer");
+
+        reduce_desktop(
+            &mut state,
+            &mut interaction,
+            open_window(explorer, "system.terminal"),
+        )
+        .expect("explorer focuses the open terminal");
+        assert_eq!(state.windows.len(), 3);
+        assert_eq!(state.focused_window_id(), Some(second));
+    }
+
+    #[test]
+    fn workspaces_scope_focus_and_follow_focused_windows() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let explorer = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.explorer"),
+        );
+        let terminal = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.terminal"),
+        );
+
+        reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::MoveWindowToWorkspace {
+                window_id: terminal,
+                workspace: 1,
+            },
+        )
+        .expect("move terminal");
+        assert_eq!(state.active_workspace, 0);
+        assert_eq!(state.focused_window_id(), Some(explorer));
+
+        let effects = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::SwitchWorkspace { workspace: 1 },
+        )
+        .expect("switch workspace");
+        assert_eq!(state.focused_window_id(), Some(terminal));
+        assert!(effects.contains(&RuntimeEffect::FocusWindowInput(terminal)));
+        assert!(effects.contains(&RuntimeEffect::PersistLayout));
+
+        let calculator = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.calculator"),
+        );
+        assert_eq!(state.windows.last().map(|win| win.workspace), Some(1));
+
+        reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::FocusWindow {
+                window_id: explorer,
+            },
+        )
+        .expect("focus explorer");
+        assert_eq!(state.active_workspace, 0);
+
+        reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::SwitchWorkspace { workspace: 2 },
+        )
+        .expect("switch to empty workspace");
+        assert_eq!(state.focused_window_id(), None);
+
+        reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::SwitchWorkspace {
+                workspace: WORKSPACE_COUNT,
+            },
+        )
+        .expect("out-of-range workspace is ignored");
+        assert_eq!(state.active_workspace, 2);
+
+        let restored = DesktopState::from_snapshot(state.snapshot());
+        assert_eq!(restored.active_workspace, 2);
+        assert_eq!(
+            restored
+                .windows
+                .iter()
+                .find(|win| win.id == calculator)
+                .map(|win| win.workspace),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn window_switcher_cycles_in_recently_used_order() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let dispatch = |state: &mut DesktopState, interaction: &mut InteractionState, action| {
+            reduce_desktop(state, interaction, action).expect("switcher action")
+        };
+
+        dispatch(
+            &mut state,
+            &mut interaction,
+            DesktopAction::CycleWindowSwitcher { reverse: false },
+        );
+        assert!(interaction.window_switcher.is_none());
+
+        let explorer = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.explorer"),
+        );
+        let notepad = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.notepad"),
+        );
+        let terminal = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.terminal"),
+        );
+        let cycle = |reverse| DesktopAction::CycleWindowSwitcher { reverse };
+
+        dispatch(&mut state, &mut interaction, cycle(false));
+        let session = interaction.window_switcher.clone().expect("switcher open");
+        assert_eq!(session.windows, vec![terminal, notepad, explorer]);
+        assert_eq!(session.selected_window_id(), Some(notepad));
+        dispatch(&mut state, &mut interaction, cycle(false));
+        dispatch(&mut state, &mut interaction, cycle(false));
+        dispatch(&mut state, &mut interaction, cycle(true));
+        assert_eq!(
+            interaction
+                .window_switcher
+                .as_ref()
+                .and_then(WindowSwitcherSession::selected_window_id),
+            Some(explorer)
+        );
+        dispatch(
+            &mut state,
+            &mut interaction,
+            DesktopAction::CancelWindowSwitcher,
+        );
+        assert!(interaction.window_switcher.is_none());
+        assert_eq!(state.focused_window_id(), Some(terminal));
+
+        dispatch(&mut state, &mut interaction, cycle(false));
+        dispatch(
+            &mut state,
+            &mut interaction,
+            DesktopAction::CommitWindowSwitcher,
+        );
+        assert!(interaction.window_switcher.is_none());
+        assert_eq!(state.focused_window_id(), Some(notepad));
+
+        dispatch(
+            &mut state,
+            &mut interaction,
+            DesktopAction::MinimizeWindow {
+                window_id: terminal,
+            },
+        );
+        assert_eq!(
+            state.recently_used_window_ids(),
+            vec![notepad, explorer, terminal]
+        );
+        dispatch(&mut state, &mut interaction, cycle(true));
+        dispatch(
+            &mut state,
+            &mut interaction,
+            DesktopAction::CommitWindowSwitcher,
+        );
+        assert_eq!(state.focused_window_id(), Some(terminal));
+        assert!(!state.windows.iter().any(|w| w.minimized));
+    }
+
+    #[test]
+    fn minimize_applies_suspend_policy() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let explorer = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.explorer"),
+        );
+        let terminal = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.terminal"),
+        );
+
+        let explorer_effects = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::MinimizeWindow {
+                window_id: explorer,
+            },
+        )
+        .expect("minimize explorer");
+        let explorer_window = state.windows.iter().find(|w| w.id == explorer).unwrap();
+        assert!(explorer_window.suspended);
+        assert!(
+            explorer_effects.contains(&RuntimeEffect::DispatchLifecycle {
+                window_id: explorer,
+                event: AppLifecycleEvent::Suspended,
+            })
+        );
+
+        let terminal_effects = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::MinimizeWindow {
+                window_id: terminal,
+            },
+        )
+        .expect("minimize terminal");
+        let terminal_window = state.windows.iter().find(|w| w.id == terminal).unwrap();
+        assert!(!terminal_window.suspended);
+        assert!(
+            !terminal_effects.contains(&RuntimeEffect::DispatchLifecycle {
+                window_id: terminal,
+                event: AppLifecycleEvent::Suspended,
+            })
+        );
+    }
+
+    #[test]
+    fn close_flow_emits_closing_then_closed() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let _first = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.explorer"),
+        );
+        let second = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.notepad"),
+        );
+
+        let effects = reduce_desktop(
             &mut state,
             &mut interaction,
             DesktopAction::CloseWindow { window_id: second },
@@ -1807,6 +2729,47 @@ mod tests {
         assert!(state.focused_window_id().is_some());
     }
 
+    #[test]
+    fn force_close_requires_the_processes_capability() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let calculator = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.calculator"),
+        );
+        let explorer = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.explorer"),
+        );
+        let terminal = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.terminal"),
+        );
+        let mut force_close = |window_id: WindowId, target: u64| {
+            reduce_desktop(
+                &mut state,
+                &mut interaction,
+                DesktopAction::HandleAppCommand {
+                    window_id,
+                    command: AppCommand::ForceCloseWindow { window_id: target },
+                },
+            )
+            .expect("force close command")
+        };
+
+        assert!(force_close(calculator, explorer.0).is_empty());
+        assert!(force_close(terminal, 9_999).is_empty());
+        let effects = force_close(terminal, explorer.0);
+        assert!(effects.contains(&RuntimeEffect::DispatchLifecycle {
+            window_id: explorer,
+            event: AppLifecycleEvent::Closed,
+        }));
+        assert!(!state.windows.iter().any(|w| w.id == explorer));
+    }
+
     #[test]
     fn app_bus_commands_emit_window_manager_effects() {
         let mut state = DesktopState::default();
@@ -1862,4 +2825,319 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn capability_grants_unlock_app_commands_until_revoked() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let calculator = ApplicationId::trusted("system.calculator");
+        let window_id = open(&mut state, &mut interaction, calculator.clone());
+        let open_url = |state: &mut DesktopState, interaction: &mut InteractionState| {
+            reduce_desktop(
+                state,
+                interaction,
+                DesktopAction::HandleAppCommand {
+                    window_id,
+                    command: AppCommand::OpenExternalUrl {
+                        url: "https://example.com".to_string(),
+                    },
+                },
+            )
+            .expect("open url command")
+        };
+
+        assert!(open_url(&mut state, &mut interaction).is_empty());
+
+        let ignored = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::GrantCapability {
+                app_id: calculator.clone(),
+                capability: AppCapability::Theme,
+            },
+        )
+        .expect("grant theme");
+        assert!(ignored.is_empty());
+        assert!(state.capability_grants.is_empty());
+
+        let granted = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::GrantCapability {
+                app_id: calculator.clone(),
+                capability: AppCapability::ExternalUrl,
+            },
+        )
+        .expect("grant external url");
+        assert_eq!(granted, vec![RuntimeEffect::PersistCapabilityGrants]);
+        assert!(app_granted_capabilities(&state, &calculator).contains(&AppCapability::ExternalUrl));
+        assert_eq!(
+            open_url(&mut state, &mut interaction),
+            vec![RuntimeEffect::OpenExternalUrl(
+                "https://example.com".to_string()
+            )]
+        );
+
+        let revoke = AppCommand::RevokeCapability {
+            app_id: calculator.clone(),
+            capability: AppCapability::ExternalUrl,
+        };
+        let ignored = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::HandleAppCommand {
+                window_id,
+                command: revoke.clone(),
+            },
+        )
+        .expect("unprivileged revoke");
+        assert!(ignored.is_empty());
+        let settings_window = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.settings"),
+        );
+        let revoked = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::HandleAppCommand {
+                window_id: settings_window,
+                command: revoke,
+            },
+        )
+        .expect("settings revoke");
+        assert_eq!(revoked, vec![RuntimeEffect::PersistCapabilityGrants]);
+        assert!(state.capability_grants.is_empty());
+        assert!(open_url(&mut state, &mut interaction).is_empty());
+    }
+
+    #[test]
+    fn notification_delivery_mutes_or_silences_an_apps_notifications() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let settings = ApplicationId::trusted("system.settings");
+        let settings_window = open(&mut state, &mut interaction, settings.clone());
+        let calculator_window = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.calculator"),
+        );
+        let mut send = |window_id: WindowId, command: AppCommand| {
+            reduce_desktop(
+                &mut state,
+                &mut interaction,
+                DesktopAction::HandleAppCommand { window_id, command },
+            )
+            .expect("app command")
+        };
+        let notify = || AppCommand::Notify {
+            notification: Notification::new("Saved", "Changes saved"),
+        };
+        let set_delivery = |delivery| AppCommand::SetNotificationDelivery {
+            app_id: settings.clone(),
+            delivery,
+        };
+
+        assert_eq!(
+            send(settings_window, notify()),
+            vec![
+                RuntimeEffect::Notify {
+                    title: "Saved".to_string(),
+                    body: "Changes saved".to_string(),
+                },
+                RuntimeEffect::PersistNotifications,
+            ]
+        );
+        assert!(send(calculator_window, set_delivery(NotificationDelivery::Muted)).is_empty());
+        assert_eq!(
+            send(settings_window, set_delivery(NotificationDelivery::Silent)),
+            vec![RuntimeEffect::PersistNotificationDeliveries]
+        );
+        assert_eq!(
+            send(settings_window, notify()),
+            vec![RuntimeEffect::PersistNotifications]
+        );
+        assert_eq!(
+            send(settings_window, set_delivery(NotificationDelivery::Muted)),
+            vec![RuntimeEffect::PersistNotificationDeliveries]
+        );
+        assert!(send(settings_window, notify()).is_empty());
+        assert_eq!(
+            send(settings_window, set_delivery(NotificationDelivery::Banner)),
+            vec![RuntimeEffect::PersistNotificationDeliveries]
+        );
+        assert!(send(settings_window, set_delivery(NotificationDelivery::Banner)).is_empty());
+        assert!(state.notification_deliveries.is_empty());
+        assert_eq!(state.notifications.len(), 2);
+    }
+
+    #[test]
+    fn only_privileged_apps_clear_another_apps_shared_state() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let notes = ApplicationId::trusted("system.notepad");
+        for (app_id, key) in [
+            (notes.clone(), "recent"),
+            (notes.clone(), "draft"),
+            (ApplicationId::trusted("system.explorer"), "recent"),
+        ] {
+            reduce_desktop(
+                &mut state,
+                &mut interaction,
+                DesktopAction::SetSharedAppState {
+                    app_id,
+                    key: key.to_string(),
+                    state: json!(true),
+                },
+            )
+            .expect("set shared state");
+        }
+        let clear = AppCommand::ClearSharedState {
+            app_id: notes.clone(),
+        };
+
+        let notes_window = open(&mut state, &mut interaction, notes);
+        let ignored = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            DesktopAction::HandleAppCommand {
+                window_id: notes_window,
+                command: clear.clone(),
+            },
+        )
+        .expect("unprivileged clear");
+        assert!(ignored.is_empty());
+        assert_eq!(state.app_shared_state.len(), 3);
+
+        let settings_window = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.settings"),
+        );
+        let command = |state: &mut DesktopState, interaction: &mut InteractionState| {
+            reduce_desktop(
+                state,
+                interaction,
+                DesktopAction::HandleAppCommand {
+                    window_id: settings_window,
+                    command: clear.clone(),
+                },
+            )
+            .expect("settings clear")
+        };
+        assert_eq!(
+            command(&mut state, &mut interaction),
+            vec![RuntimeEffect::PersistLayout]
+        );
+        assert_eq!(
+            state.app_shared_state.keys().collect::<Vec<_>>(),
+            vec!["system.explorer:recent"]
+        );
+        assert!(command(&mut state, &mut interaction).is_empty());
+    }
+
+    #[test]
+    fn window_commands_update_geometry_icon_and_badge() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let window_id = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.explorer"),
+        );
+        let mut command = |command: AppCommand| {
+            reduce_desktop(
+                &mut state,
+                &mut interaction,
+                DesktopAction::HandleAppCommand { window_id, command },
+            )
+            .expect("window command")
+        };
+
+        command(AppCommand::SetWindowSize {
+            width: 1,
+            height: 500,
+        });
+        command(AppCommand::SetWindowPosition { x: 40, y: 60 });
+        command(AppCommand::SetWindowIcon {
+            icon_id: "play".to_string(),
+        });
+        command(AppCommand::SetWindowBadge { count: 3 });
+        assert_eq!(
+            command(AppCommand::MaximizeWindow),
+            vec![RuntimeEffect::MaximizeWindowToViewport(window_id)]
+        );
+        command(AppCommand::MinimizeWindow);
+
+        let window = state
+            .windows
+            .iter()
+            .find(|w| w.id == window_id)
+            .expect("window");
+        assert_eq!(
+            window.rect,
+            WindowRect {
+                x: 40,
+                y: 60,
+                w: MIN_WINDOW_WIDTH,
+                h: 500,
+            }
+        );
+        assert_eq!(window.icon_id, "play");
+        assert_eq!(window.badge_count, 3);
+        assert!(window.minimized);
+    }
+
+    #[test]
+    fn ipc_replies_are_delivered_to_open_requesting_windows() {
+        let mut state = DesktopState::default();
+        let mut interaction = InteractionState::default();
+        let caller = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.notepad"),
+        );
+        let responder = open(
+            &mut state,
+            &mut interaction,
+            ApplicationId::trusted("system.explorer"),
+        );
+        let reply = |target: WindowId, topic: &str| DesktopAction::HandleAppCommand {
+            window_id: responder,
+            command: AppCommand::ReplyToWindow {
+                target_window_id: target.0,
+                topic: topic.to_string(),
+                correlation_id: "call-1".to_string(),
+                payload: serde_json::json!({ "Ok": 3 }),
+            },
+        };
+
+        let effects = reduce_desktop(&mut state, &mut interaction, reply(caller, "ipc.reply"))
+            .expect("reply command");
+        assert_eq!(
+            effects,
+            vec![RuntimeEffect::DeliverIpcReply {
+                responder,
+                window_id: caller,
+                event: AppEvent::new(
+                    "ipc.reply",
+                    serde_json::json!({ "Ok": 3 }),
+                    Some(responder.0)
+                )
+                .with_correlation(Some("call-1".to_string()), None),
+            }]
+        );
+
+        let effects = reduce_desktop(
+            &mut state,
+            &mut interaction,
+            reply(WindowId(999), "ipc.reply"),
+        )
+        .expect("reply to closed window");
+        assert!(effects.is_empty());
+
+        let effects = reduce_desktop(&mut state, &mut interaction, reply(caller, "calc.add"))
+            .expect("reply on another topic");
+        assert!(effects.is_empty());
+    }
 }
@@ -5,14 +5,22 @@
 //! [`crate::components`].
 #![allow(clippy::clone_on_copy)]
 
+use std::{rc::Rc, time::Duration};
+
+use desktop_app_contract::{confirm_window_close, CloseResponse, LogService, WindowProcessInfo};
 use leptos::*;
-use platform_host::HostServices;
+use platform_host::{unix_time_ms_now, HostServices};
+use system_shell_contract::{ShellStreamEvent, SCHEDULE_TOPIC};
 
 use crate::{
-    app_runtime::{sync_runtime_sessions, AppRuntimeState},
-    apps, effect_executor,
+    app_runtime::{
+        publish_runtime_topic_event, sync_runtime_sessions, window_bus_metrics, window_session,
+        AppRuntimeState,
+    },
+    apps, current_browser_e2e_config, effect_executor,
     host::DesktopHostContext,
-    model::{DesktopState, InteractionState},
+    model::{DesktopState, InteractionState, WindowId},
+    persistence,
     reducer::{reduce_desktop, DesktopAction, RuntimeEffect},
     shell,
 };
@@ -36,6 +44,8 @@ pub struct DesktopRuntimeContext {
     pub dispatch: Callback<DesktopAction>,
     /// Shared shell engine and command registry.
     pub shell_engine: StoredValue<system_shell::ShellEngine>,
+    /// Bumped for every record in the host log registry so log views can refresh.
+    pub log_revision: RwSignal<u64>,
 }
 
 impl DesktopRuntimeContext {
@@ -43,6 +53,58 @@ impl DesktopRuntimeContext {
     pub fn dispatch_action(&self, action: DesktopAction) {
         self.dispatch.call(action);
     }
+
+    /// Returns a log service recording as the desktop runtime itself.
+    pub fn logger(&self) -> LogService {
+        self.host.get_value().logger()
+    }
+
+    /// Closes a window once its app agrees, for closes the user starts.
+    ///
+    /// Apps that registered an [`desktop_app_contract::AppLifecycleHandler`] can keep the window
+    /// open or finish work first; other windows close right away.
+    pub fn request_close_window(&self, window_id: WindowId) {
+        let Some(session) = window_session(self.app_runtime, window_id) else {
+            self.dispatch_action(DesktopAction::CloseWindow { window_id });
+            return;
+        };
+        let runtime = *self;
+        spawn_local(async move {
+            if confirm_window_close(session.lifecycle_handler).await == CloseResponse::Close {
+                runtime.dispatch_action(DesktopAction::CloseWindow { window_id });
+            }
+        });
+    }
+
+    /// Lists open windows, oldest first, with their app-bus activity and persisted state size.
+    pub fn window_processes(&self) -> Vec<WindowProcessInfo> {
+        let mut processes: Vec<WindowProcessInfo> = self.state.with_untracked(|desktop| {
+            desktop
+                .windows
+                .iter()
+                .map(|win| {
+                    let metrics = window_bus_metrics(self.app_runtime, win.id);
+                    WindowProcessInfo {
+                        window_id: win.id.0,
+                        app_id: win.app_id.clone(),
+                        title: win.title.clone(),
+                        focused: win.is_focused,
+                        minimized: win.minimized,
+                        suspended: win.suspended,
+                        workspace: win.workspace,
+                        events_delivered: metrics.delivered,
+                        events_dropped: metrics.dropped,
+                        inbox_len: metrics.inbox_len,
+                        subscriptions: metrics.subscriptions,
+                        app_state_bytes: serde_json::to_string(&win.app_state)
+                            .map_or(0, |json| json.len()),
+                    }
+                })
+                .collect()
+        });
+        processes.sort_by_key(|process| process.window_id);
+        processes
+    }
 }
 
 fn install_runtime_orchestration(runtime: DesktopRuntimeContext) {
@@ -51,9 +113,121 @@ fn install_runtime_orchestration(runtime: DesktopRuntimeContext) {
         .get_value()
         .install_boot_hydration(runtime.dispatch);
     std::mem::forget(shell::register_builtin_commands(runtime));
+    install_shell_history(runtime);
+    install_shell_schedule(runtime);
+    install_shell_audit(runtime);
     effect_executor::install(runtime);
 }
 
+fn install_shell_audit(runtime: DesktopRuntimeContext) {
+    let audit = system_shell::ShellAuditLog::new(
+        &runtime.shell_engine.get_value(),
+        system_shell::DEFAULT_AUDIT_CAPACITY,
+    )
+    .with_prefs(
+        runtime.host.get_value().prefs_store(),
+        persistence::SHELL_AUDIT_KEY,
+    );
+    if current_browser_e2e_config().is_none() {
+        spawn_local({
+            let audit = audit.clone();
+            async move {
+                if let Err(err) = audit.hydrate().await {
+                    runtime
+                        .logger()
+                        .warn(format!("shell audit log hydration failed: {err}"));
+                }
+            }
+        });
+    }
+    std::mem::forget(audit);
+}
+
+fn install_shell_history(runtime: DesktopRuntimeContext) {
+    let history = runtime.shell_engine.get_value().history();
+    create_effect({
+        let history = history.clone();
+        move |_| {
+            history.set_enabled(
+                runtime
+                    .state
+                    .with(|desktop| desktop.preferences.terminal_history_enabled),
+            );
+        }
+    });
+    if current_browser_e2e_config().is_none() {
+        spawn_local(async move {
+            if let Err(err) = history.hydrate().await {
+                runtime
+                    .logger()
+                    .warn(format!("shell history hydration failed: {err}"));
+            }
+        });
+    }
+}
+
+/// How often due scheduled shell jobs and overdue app command executions are checked.
+const SHELL_SCHEDULE_TICK: Duration = Duration::from_secs(1);
+
+fn install_shell_schedule(runtime: DesktopRuntimeContext) {
+    let scheduler = system_shell::ShellScheduler::new(&runtime.shell_engine.get_value())
+        .with_prefs(
+            runtime.host.get_value().prefs_store(),
+            persistence::SHELL_SCHEDULE_KEY,
+        );
+    create_effect({
+        let scheduler = scheduler.clone();
+        move |_| {
+            let events = scheduler.events().get();
+            if events.is_empty() {
+                return;
+            }
+            for event in &events {
+                let ShellStreamEvent::Completed { summary } = event else {
+                    continue;
+                };
+                let Some(run) = scheduler.completed_run(summary) else {
+                    continue;
+                };
+                match serde_json::to_value(&run) {
+                    Ok(payload) => {
+                        publish_runtime_topic_event(runtime.app_runtime, SCHEDULE_TOPIC, payload)
+                    }
+                    Err(err) => runtime
+                        .logger()
+                        .warn(format!("serialize scheduled run failed: {err}")),
+                }
+            }
+            scheduler.acknowledge_events(events.len());
+        }
+    });
+    if current_browser_e2e_config().is_none() {
+        spawn_local({
+            let scheduler = scheduler.clone();
+            async move {
+                if let Err(err) = scheduler.hydrate().await {
+                    runtime
+                        .logger()
+                        .warn(format!("shell schedule hydration failed: {err}"));
+                }
+            }
+        });
+    }
+    let engine = runtime.shell_engine.get_value();
+    if let Err(err) = set_interval_with_handle(
+        move || {
+            let now = unix_time_ms_now();
+            scheduler.tick(now);
+            engine.enforce_quotas(now);
+        },
+        SHELL_SCHEDULE_TICK,
+    ) {
+        runtime
+            .logger()
+            .warn(format!("shell schedule timer failed: {err:?}"));
+    }
+}
+
 #[component]
 /// Provides [`DesktopRuntimeContext`] to descendant components and boots persisted state.
 pub fn DesktopProvider(
@@ -67,7 +241,23 @@ pub fn DesktopProvider(
     let interaction = create_rw_signal(InteractionState::default());
     let effects = create_rw_signal(Vec::<RuntimeEffect>::new());
     let app_runtime = create_rw_signal(AppRuntimeState::default());
-    let shell_engine = store_value(system_shell::ShellEngine::new());
+    let log_revision = create_rw_signal(0_u64);
+    host.get_value()
+        .log_registry()
+        .set_listener(Rc::new(move |_| {
+            log_revision.try_update(|revision| *revision += 1);
+        }));
+    let shell_engine = store_value(
+        system_shell::ShellEngine::new()
+            .with_history(
+                system_shell::HistoryStore::new(system_shell::DEFAULT_HISTORY_CAPACITY).with_prefs(
+                    host.get_value().prefs_store(),
+                    persistence::TERMINAL_HISTORY_KEY,
+                ),
+            )
+            .with_capability_check(Rc::new(shell::caller_has_capability))
+            .with_explorer_fs(host.get_value().explorer_fs_service()),
+    );
 
     let dispatch = Callback::new(move |action: DesktopAction| {
         let mut desktop = state.get_untracked();
@@ -93,7 +283,10 @@ pub fn DesktopProvider(
                     effects.set(queue);
                 }
             }
-            Err(err) => logging::warn!("desktop reducer error: {err}"),
+            Err(err) => host
+                .get_value()
+                .logger()
+                .warn(format!("desktop reducer error: {err}")),
         }
     });
 
@@ -106,6 +299,7 @@ pub fn DesktopProvider(
         app_runtime,
         dispatch,
         shell_engine,
+        log_revision,
     };
 
     provide_context(runtime.clone());
@@ -7,30 +7,30 @@ mod commands;
 mod policy;
 
 use desktop_app_contract::{
-    AppCommandContext, AppCommandProvider, AppCommandRegistration, ApplicationId,
-    CommandRegistrationHandle as AppCommandRegistrationHandle, CommandService, ShellSessionHandle,
+    AppCapability, AppCommandContext, AppCommandProvider, AppCommandRegistration, AppLink,
+    ApplicationId, CommandRegistrationHandle as AppCommandRegistrationHandle, CommandService,
+    ShellSessionHandle,
 };
-use futures::future::LocalBoxFuture;
-use leptos::SignalGetUntracked;
 use nu_ansi_term::{Color, Style};
 use nu_protocol::{Config as NuConfig, Record as NuRecord, Span as NuSpan, Value as NuValue};
 use nu_table::{NuTable, TableTheme, TextStyle};
-use platform_host::{normalize_virtual_path, ExplorerEntry, ExplorerEntryKind};
 use serde_json::Value;
 use system_shell::{CommandExecutionContext, CommandRegistryHandle};
 use system_shell_contract::{
-    CommandArgSpec, CommandDataShape, CommandDescriptor, CommandExample, CommandId,
-    CommandInputShape, CommandInteractionKind, CommandNotice, CommandNoticeLevel,
-    CommandOutputShape, CommandPath, CommandResult, CommandScope, CommandVisibility,
-    CompletionItem, CompletionRequest, DisplayPreference, HelpDoc, ParsedLiteral, ParsedValue,
-    ShellError, ShellErrorCode, ShellRequest, ShellStreamEvent, StructuredData, StructuredField,
-    StructuredRecord, StructuredScalar, StructuredSchema, StructuredSchemaField, StructuredTable,
-    StructuredValue,
+    CommandArgCompletion, CommandArgSpec, CommandCaller, CommandDataShape, CommandDescriptor,
+    CommandExample, CommandId, CommandInputShape, CommandInteractionKind, CommandNotice,
+    CommandNoticeLevel, CommandOptionSpec, CommandOutputShape, CommandPath, CommandResult,
+    CommandScope, CommandValueKind, CommandVisibility, CompletionItem, CompletionRequest,
+    DisplayPreference, HelpDoc, ParsedLiteral, ParsedValue, ShellError, ShellErrorCode,
+    ShellRequest, ShellStreamEvent, StructuredData, StructuredField, StructuredRecord,
+    StructuredScalar, StructuredSchema, StructuredSchemaField, StructuredTable, StructuredValue,
 };
 use tabled::grid::records::vec_records::Text;
 
 use crate::{apps, components::DesktopRuntimeContext, model::WindowId, reducer::DesktopAction};
 
+pub(crate) use policy::caller_has_capability;
+
 const TASKBAR_HEIGHT_PX: i32 = 38;
 const TABLE_RENDER_WIDTH: usize = 120;
 
@@ -39,38 +39,66 @@ pub fn build_command_service(
     runtime: DesktopRuntimeContext,
     app_id: ApplicationId,
     window_id: WindowId,
-    history: leptos::ReadSignal<Vec<String>>,
 ) -> CommandService {
+    let history = runtime.shell_engine.get_value().history();
     CommandService::new(
-        history,
+        history.entries(),
+        runtime.shell_engine.get_value().registry().generation(),
+        Rc::new(move |query: &str, before| history.reverse_search(query, before)),
+        Rc::new({
+            let runtime = runtime.clone();
+            let caller = CommandCaller {
+                app_id: app_id.as_str().to_string(),
+                window_id: Some(window_id.0),
+            };
+            move |query: &str| {
+                runtime
+                    .shell_engine
+                    .get_value()
+                    .search_commands(query, &caller)
+            }
+        }),
         Rc::new({
             let runtime = runtime.clone();
+            let app_id = app_id.clone();
             move |cwd| {
                 let session = leptos::with_owner(runtime.owner, || {
-                    runtime.shell_engine.get_value().new_session(cwd)
+                    runtime.shell_engine.get_value().new_session_for(
+                        cwd,
+                        CommandCaller {
+                            app_id: app_id.as_str().to_string(),
+                            window_id: Some(window_id.0),
+                        },
+                    )
                 });
                 let submit_session = session.clone();
                 let cancel_session = session.clone();
+                let respond_session = session.clone();
                 let complete_session = session.clone();
+                let acknowledge_session = session.clone();
+                let variable_session = session.clone();
+                let prompt_session = session.clone();
                 Ok(ShellSessionHandle::new(
                     session.events(),
                     session.active_execution(),
                     session.cwd(),
-                    Rc::new({
-                        let runtime = runtime;
-                        move |request: ShellRequest| {
-                            runtime.dispatch_action(DesktopAction::PushTerminalHistory {
-                                command: request.line.clone(),
-                            });
-                            submit_session.submit(request);
-                        }
-                    }),
+                    Rc::new(move |request: ShellRequest| submit_session.submit(request)),
                     Rc::new(move || cancel_session.cancel()),
+                    Rc::new(move |execution_id, answer| {
+                        respond_session.respond(execution_id, answer)
+                    }),
                     Rc::new(move |request: CompletionRequest| {
                         let complete_session = complete_session.clone();
                         Box::pin(async move { complete_session.complete(request).await })
                     }),
-                ))
+                )
+                .with_event_acknowledger(Rc::new(move |count| {
+                    acknowledge_session.acknowledge_events(count)
+                }))
+                .with_variable_setter(Rc::new(move |name, data| {
+                    variable_session.set_variable(name, data)
+                }))
+                .with_prompt_attacher(Rc::new(move || prompt_session.attach_prompt_responder())))
             }
         }),
         Rc::new({
@@ -103,21 +131,36 @@ pub fn build_command_service(
     )
 }
 
-/// Registers runtime-owned built-in commands and returns the owning handles.
+/// Registers runtime-owned built-in commands and the core file commands, returning the owning
+/// handles.
 pub fn register_builtin_commands(runtime: DesktopRuntimeContext) -> Vec<CommandRegistryHandle> {
     let mut handles = Vec::new();
     let engine = runtime.shell_engine.get_value();
+    match system_shell_commands::register_fs_commands(
+        &engine,
+        runtime.host.get_value().explorer_fs_service(),
+    ) {
+        Ok(fs_handles) => handles.extend(fs_handles),
+        Err(err) => runtime
+            .logger()
+            .warn(format!("file commands not registered: {}", err.message)),
+    }
     for registration in commands::builtin_registrations(runtime) {
         let descriptor = registration.descriptor.clone();
         let handler = registration.handler.clone();
-        handles.push(engine.register_command(
+        match engine.register_command(
             registration.descriptor,
             registration.completion,
             Rc::new(move |context: CommandExecutionContext| {
                 let app_context = adapt_context(context, descriptor.clone());
                 handler(app_context)
             }),
-        ));
+        ) {
+            Ok(handle) => handles.push(handle),
+            Err(err) => runtime
+                .logger()
+                .warn(format!("builtin command not registered: {}", err.message)),
+        }
     }
     handles
 }
@@ -138,6 +181,8 @@ fn adapt_context(
     let emit_context = context.clone();
     let set_cwd_context = context.clone();
     let cancel_context = context.clone();
+    let execute_context = context.clone();
+    let prompt_context = context.clone();
     AppCommandContext::new(
         context.execution_id,
         context.invocation.clone(),
@@ -150,6 +195,12 @@ fn adapt_context(
         Rc::new(move |cwd| set_cwd_context.set_cwd(cwd)),
         Rc::new(move || cancel_context.is_cancelled()),
     )
+    .with_dry_run(context.dry_run)
+    .with_line_executor(Rc::new(move |line| execute_context.execute_line(line)))
+    .with_prompter(Rc::new(move |prompt, kind| {
+        let prompt_context = prompt_context.clone();
+        Box::pin(async move { prompt_context.prompt(prompt, kind).await })
+    }))
 }
 
 fn emit_shell_event(context: &CommandExecutionContext, event: ShellStreamEvent) {
@@ -160,6 +211,8 @@ fn emit_shell_event(context: &CommandExecutionContext, event: ShellStreamEvent)
             CommandNoticeLevel::Error => context.error(notice.message),
         },
         ShellStreamEvent::Progress { value, label, .. } => context.progress(value, label),
+        ShellStreamEvent::StdoutChunk { text, .. } => context.stdout(text),
+        ShellStreamEvent::StderrChunk { text, .. } => context.stderr(text),
         _ => {}
     }
 }
@@ -172,6 +225,16 @@ fn unavailable(message: impl Into<String>) -> ShellError {
     ShellError::new(ShellErrorCode::Unavailable, message)
 }
 
+/// Builds a [`ShellErrorCode::Filesystem`] error carrying the failed operation and path.
+fn fs_error(operation: &str, path: &str, message: impl Into<String>) -> ShellError {
+    ShellError::new(ShellErrorCode::Filesystem, message).with_details(StructuredRecord {
+        fields: vec![
+            string_field("operation", operation),
+            string_field("path", path),
+        ],
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn descriptor(
     path: &str,
@@ -198,6 +261,9 @@ fn descriptor(
         output_shape,
         args,
         options: Vec::new(),
+        required_capability: None,
+        destructive: false,
+        owner_app_id: None,
         help: HelpDoc {
             summary: summary.to_string(),
             description: None,
@@ -255,6 +321,43 @@ fn namespaced_descriptor(
     )
 }
 
+fn requires_capability(
+    mut descriptor: CommandDescriptor,
+    capability: AppCapability,
+) -> CommandDescriptor {
+    descriptor.required_capability = Some(capability.as_str().to_string());
+    descriptor
+}
+
+fn flag_option(name: &str, short: Option<char>, summary: &str) -> CommandOptionSpec {
+    CommandOptionSpec {
+        name: name.to_string(),
+        short,
+        summary: summary.to_string(),
+        takes_value: false,
+        value_kind: CommandValueKind::Any,
+        choices: Vec::new(),
+    }
+}
+
+fn has_flag(context: &AppCommandContext, name: &str) -> bool {
+    context
+        .invocation
+        .options
+        .iter()
+        .any(|option| option.name == name)
+}
+
+fn option_value(context: &AppCommandContext, name: &str) -> Option<String> {
+    context
+        .invocation
+        .options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_ref())
+        .map(|value| value.raw.clone())
+}
+
 fn empty_result() -> CommandResult {
     CommandResult::success(StructuredData::Empty)
 }
@@ -300,16 +403,6 @@ fn int_field(name: &str, value: i64) -> StructuredField {
     value_field(name, StructuredValue::Scalar(StructuredScalar::Int(value)))
 }
 
-fn optional_u64_field(name: &str, value: Option<u64>) -> StructuredField {
-    match value {
-        Some(value) => value_field(
-            name,
-            StructuredValue::Scalar(StructuredScalar::Int(value as i64)),
-        ),
-        None => value_field(name, StructuredValue::Scalar(StructuredScalar::Null)),
-    }
-}
-
 fn record_data(fields: Vec<StructuredField>) -> StructuredData {
     StructuredData::Record(StructuredRecord { fields })
 }
@@ -486,13 +579,6 @@ fn parsed_value_to_structured(value: &ParsedValue) -> StructuredValue {
     }
 }
 
-fn normalize_session_path(cwd: &str, input: &str) -> String {
-    if input.trim().starts_with('/') {
-        return normalize_virtual_path(input);
-    }
-    normalize_virtual_path(&format!("{}/{}", cwd.trim_end_matches('/'), input))
-}
-
 fn parse_bool_flag(raw: &str) -> Result<bool, ShellError> {
     match raw {
         "on" | "true" | "1" => Ok(true),
@@ -508,6 +594,9 @@ fn parse_window_id(raw: &str) -> Result<WindowId, ShellError> {
 }
 
 fn resolve_open_target(target: &str) -> Option<DesktopAction> {
+    if let Ok(link) = AppLink::parse(target) {
+        return Some(DesktopAction::OpenLink { link });
+    }
     if let Ok(app_id) = ApplicationId::new(target.trim()) {
         return Some(DesktopAction::ActivateApp {
             app_id,
@@ -554,24 +643,6 @@ fn window_row(window: &crate::model::WindowRecord) -> StructuredRecord {
     }
 }
 
-fn explorer_row(entry: &ExplorerEntry) -> StructuredRecord {
-    StructuredRecord {
-        fields: vec![
-            string_field("name", entry.name.clone()),
-            string_field(
-                "kind",
-                match entry.kind {
-                    ExplorerEntryKind::File => "file",
-                    ExplorerEntryKind::Directory => "dir",
-                },
-            ),
-            string_field("path", entry.path.clone()),
-            optional_u64_field("size", entry.size),
-            optional_u64_field("modified_at_unix_ms", entry.modified_at_unix_ms),
-        ],
-    }
-}
-
 fn table_rows_from_descriptors(
     descriptors: &[CommandDescriptor],
     prefix: &[String],
@@ -686,6 +757,7 @@ fn help_show_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistrat
                 summary: "Command path to inspect.".to_string(),
                 required: true,
                 repeatable: true,
+                completion: CommandArgCompletion::None,
             }],
             vec![CommandExample {
                 command: "help show ls".to_string(),
@@ -704,11 +776,7 @@ fn help_show_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistrat
                 let descriptors = runtime.shell_engine.get_value().descriptors();
                 match help_target(&descriptors, &context.args) {
                     HelpTarget::Leaf(descriptor) => Ok(CommandResult {
-                        output: record_data(vec![
-                            string_field("path", descriptor.path.display()),
-                            string_field("summary", descriptor.help.summary.clone()),
-                            string_field("usage", descriptor.help.usage.clone()),
-                        ]),
+                        output: system_shell::manual_page(descriptor, &descriptors),
                         display: DisplayPreference::Help,
                         notices: Vec::new(),
                         cwd: None,
@@ -761,48 +829,190 @@ fn clear_registration() -> AppCommandRegistration {
     }
 }
 
-fn history_list_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
+fn scrollback_registration() -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: namespaced_descriptor(
+            "terminal scrollback",
+            &[],
+            "Show or set how many transcript entries each terminal pane keeps.",
+            "terminal scrollback [<entries>]",
+            vec![CommandArgSpec {
+                name: "entries".to_string(),
+                summary: "New scrollback size, saved as a preference.".to_string(),
+                required: false,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+            vec![CommandExample {
+                command: "terminal scrollback 1000".to_string(),
+                summary: "Keep the newest 1000 transcript entries.".to_string(),
+            }],
+            CommandInputShape::none(),
+            CommandOutputShape::new(CommandDataShape::Empty),
+        ),
+        completion: None,
+        handler: Rc::new(|_| Box::pin(async move { Ok(empty_result()) })),
+    }
+}
+
+fn history_mode_registration() -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: namespaced_descriptor(
+            "terminal history",
+            &[],
+            "Show or set whether terminal recall uses pane or merged history.",
+            "terminal history [pane|merged]",
+            vec![CommandArgSpec {
+                name: "mode".to_string(),
+                summary: "`pane` recalls the pane's own commands; `merged` recalls every window's."
+                    .to_string(),
+                required: false,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+            vec![CommandExample {
+                command: "terminal history merged".to_string(),
+                summary: "Recall commands from every terminal window.".to_string(),
+            }],
+            CommandInputShape::none(),
+            CommandOutputShape::new(CommandDataShape::Empty),
+        ),
+        completion: None,
+        handler: Rc::new(|_| Box::pin(async move { Ok(empty_result()) })),
+    }
+}
+
+fn sync_registration() -> AppCommandRegistration {
     AppCommandRegistration {
         descriptor: namespaced_descriptor(
-            "history list",
+            "terminal sync",
             &[],
-            "Show recent terminal command history.",
-            "history list",
+            "Toggle or set whether commands typed in one pane run in every pane of the window.",
+            "terminal sync [on|off]",
+            vec![CommandArgSpec {
+                name: "state".to_string(),
+                summary: "`on` broadcasts typed commands to every pane; `off` keeps them local."
+                    .to_string(),
+                required: false,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+            vec![CommandExample {
+                command: "terminal sync on".to_string(),
+                summary: "Run each typed command in every pane of the window.".to_string(),
+            }],
+            CommandInputShape::none(),
+            CommandOutputShape::new(CommandDataShape::Empty),
+        ),
+        completion: None,
+        handler: Rc::new(|_| Box::pin(async move { Ok(empty_result()) })),
+    }
+}
+
+fn export_registration() -> AppCommandRegistration {
+    let mut descriptor = namespaced_descriptor(
+        "terminal export",
+        &[],
+        "Write the terminal transcript to a file as text or JSON.",
+        "terminal export [--json] [<path>]",
+        vec![CommandArgSpec {
+            name: "path".to_string(),
+            summary: "Destination file, relative to the cwd.".to_string(),
+            required: false,
+            repeatable: false,
+            completion: CommandArgCompletion::Path,
+        }],
+        vec![CommandExample {
+            command: "terminal export --json logs/session.json".to_string(),
+            summary: "Save the transcript entries as JSON.".to_string(),
+        }],
+        CommandInputShape::none(),
+        CommandOutputShape::new(CommandDataShape::Empty),
+    );
+    descriptor.options = vec![flag_option(
+        "json",
+        None,
+        "Write transcript entries as JSON instead of text.",
+    )];
+    AppCommandRegistration {
+        descriptor,
+        completion: None,
+        handler: Rc::new(|_| Box::pin(async move { Ok(empty_result()) })),
+    }
+}
+
+fn copy_registration() -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: namespaced_descriptor(
+            "terminal copy",
+            &[],
+            "Copy the last output block or command to the clipboard, or enter copy mode.",
+            "terminal copy <output|command|mode>",
+            vec![CommandArgSpec {
+                name: "target".to_string(),
+                summary: "`output`, `command`, or `mode` for keyboard selection.".to_string(),
+                required: true,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+            vec![CommandExample {
+                command: "terminal copy output".to_string(),
+                summary: "Copy the output of the most recent command.".to_string(),
+            }],
+            CommandInputShape::none(),
+            CommandOutputShape::new(CommandDataShape::Empty),
+        ),
+        completion: None,
+        handler: Rc::new(|_| Box::pin(async move { Ok(empty_result()) })),
+    }
+}
+
+fn inspect_registration() -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: namespaced_descriptor(
+            "terminal inspect",
+            &[],
+            "Open the newest record, list, or table output in the inspector drawer.",
+            "terminal inspect",
             Vec::new(),
             Vec::new(),
             CommandInputShape::none(),
-            CommandOutputShape::new(CommandDataShape::Table),
+            CommandOutputShape::new(CommandDataShape::Empty),
         ),
         completion: None,
-        handler: Rc::new(move |_| {
-            let runtime = runtime.clone();
-            Box::pin(async move {
-                let rows = runtime
-                    .state
-                    .get_untracked()
-                    .terminal_history
-                    .iter()
-                    .enumerate()
-                    .map(|(index, command)| StructuredRecord {
-                        fields: vec![
-                            int_field("index", index as i64),
-                            string_field("command", command.clone()),
-                        ],
-                    })
-                    .collect::<Vec<_>>();
-                Ok(CommandResult {
-                    output: table_data(
-                        vec!["index".to_string(), "command".to_string()],
-                        rows,
-                        Some(CommandPath::new("history list")),
-                    ),
-                    display: DisplayPreference::Table,
-                    notices: Vec::new(),
-                    cwd: None,
-                    exit: system_shell_contract::ShellExit::success(),
-                })
-            })
-        }),
+        handler: Rc::new(|_| Box::pin(async move { Ok(empty_result()) })),
+    }
+}
+
+fn profile_registration() -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: namespaced_descriptor(
+            "terminal profile",
+            &[],
+            "List, select, or edit terminal profiles.",
+            "terminal profile [use <name> | set <name> <field> [<value>] | delete <name>]",
+            vec![CommandArgSpec {
+                name: "action".to_string(),
+                summary: "`use`, `set`, or `delete`; lists profiles when omitted.".to_string(),
+                required: false,
+                repeatable: true,
+                completion: CommandArgCompletion::None,
+            }],
+            vec![
+                CommandExample {
+                    command: "terminal profile set work prompt [{time}] {cwd} $".to_string(),
+                    summary: "Give the `work` profile a prompt template.".to_string(),
+                },
+                CommandExample {
+                    command: "terminal profile use work".to_string(),
+                    summary: "Switch this window to the `work` profile.".to_string(),
+                },
+            ],
+            CommandInputShape::none(),
+            CommandOutputShape::new(CommandDataShape::Empty),
+        ),
+        completion: None,
+        handler: Rc::new(|_| Box::pin(async move { Ok(empty_result()) })),
     }
 }
 
@@ -815,6 +1025,8 @@ fn open_completion(request: CompletionRequest) -> Vec<CompletionItem> {
             value: entry.app_id.to_string(),
             label: entry.app_id.to_string(),
             detail: Some(entry.launcher_label.to_string()),
+            score: 0,
+            matched: Vec::new(),
         })
         .collect()
 }
@@ -828,9 +1040,12 @@ fn open_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
             "open <target>",
             vec![CommandArgSpec {
                 name: "target".to_string(),
-                summary: "Canonical app id or deep-link target such as notes:slug.".to_string(),
+                summary:
+                    "Canonical app id, deep-link target such as notes:slug, or web+os:// link."
+                        .to_string(),
                 required: true,
                 repeatable: false,
+                completion: CommandArgCompletion::None,
             }],
             vec![CommandExample {
                 command: "open system.terminal".to_string(),
@@ -873,51 +1088,6 @@ fn open_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
     }
 }
 
-fn path_completion_items(
-    runtime: DesktopRuntimeContext,
-    cwd: &str,
-    raw_prefix: &str,
-    directories_only: bool,
-) -> LocalBoxFuture<'static, Result<Vec<CompletionItem>, ShellError>> {
-    let cwd = cwd.to_string();
-    let raw_prefix = raw_prefix.to_string();
-    Box::pin(async move {
-        let (dir_input, leaf_prefix) = if let Some((dir, leaf)) = raw_prefix.rsplit_once('/') {
-            (dir.to_string(), leaf.to_string())
-        } else {
-            ("".to_string(), raw_prefix.clone())
-        };
-        let dir = if dir_input.is_empty() {
-            cwd.clone()
-        } else {
-            normalize_session_path(&cwd, &dir_input)
-        };
-        let listing = runtime
-            .host
-            .get_value()
-            .explorer_fs_service()
-            .list_dir(&dir)
-            .await
-            .map_err(unavailable)?;
-        Ok(listing
-            .entries
-            .into_iter()
-            .filter(|entry| {
-                (!directories_only || entry.kind == ExplorerEntryKind::Directory)
-                    && entry.name.starts_with(&leaf_prefix)
-            })
-            .map(|entry| CompletionItem {
-                value: entry.path,
-                label: entry.name,
-                detail: Some(match entry.kind {
-                    ExplorerEntryKind::File => "file".to_string(),
-                    ExplorerEntryKind::Directory => "dir".to_string(),
-                }),
-            })
-            .collect())
-    })
-}
-
 fn data_table_input(context: &AppCommandContext) -> Result<StructuredTable, ShellError> {
     match &context.input {
         StructuredData::Table(table) => Ok(table.clone()),
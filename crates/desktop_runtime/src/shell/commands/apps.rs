@@ -4,7 +4,7 @@ use std::rc::Rc;
 
 use desktop_app_contract::AppCommandRegistration;
 use system_shell_contract::{
-    CommandArgSpec, CommandDataShape, CommandOutputShape, CompletionRequest,
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandOutputShape, CompletionRequest,
 };
 
 use crate::{apps, components::DesktopRuntimeContext, reducer::DesktopAction};
@@ -64,6 +64,7 @@ fn apps_open_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistrat
                 summary: "Canonical app id.".to_string(),
                 required: true,
                 repeatable: false,
+                completion: CommandArgCompletion::None,
             }],
             Vec::new(),
             system_shell_contract::CommandInputShape::none(),
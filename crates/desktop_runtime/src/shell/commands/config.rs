@@ -2,10 +2,11 @@
 
 use std::rc::Rc;
 
-use desktop_app_contract::AppCommandRegistration;
+use desktop_app_contract::{AppCapability, AppCommandRegistration};
 use platform_host::{load_pref_with, save_pref_with};
 use system_shell_contract::{
-    CommandArgSpec, CommandDataShape, CommandOutputShape, StructuredScalar, StructuredValue,
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandOutputShape, StructuredScalar,
+    StructuredValue,
 };
 
 use crate::components::DesktopRuntimeContext;
@@ -30,12 +31,14 @@ fn config_get_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistra
                     summary: "Config namespace.".to_string(),
                     required: true,
                     repeatable: false,
+                    completion: CommandArgCompletion::None,
                 },
                 CommandArgSpec {
                     name: "key".to_string(),
                     summary: "Config key.".to_string(),
                     required: true,
                     repeatable: false,
+                    completion: CommandArgCompletion::None,
                 },
             ],
             Vec::new(),
@@ -76,34 +79,40 @@ fn config_get_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistra
 
 fn config_set_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
     AppCommandRegistration {
-        descriptor: super::super::namespaced_descriptor(
-            "config set",
-            &[],
-            "Store one config value in prefs storage.",
-            "config set <namespace> <key> <value>",
-            vec![
-                CommandArgSpec {
-                    name: "namespace".to_string(),
-                    summary: "Config namespace.".to_string(),
-                    required: true,
-                    repeatable: false,
-                },
-                CommandArgSpec {
-                    name: "key".to_string(),
-                    summary: "Config key.".to_string(),
-                    required: true,
-                    repeatable: false,
-                },
-                CommandArgSpec {
-                    name: "value".to_string(),
-                    summary: "Typed literal or string payload.".to_string(),
-                    required: true,
-                    repeatable: false,
-                },
-            ],
-            Vec::new(),
-            system_shell_contract::CommandInputShape::none(),
-            CommandOutputShape::new(CommandDataShape::Empty),
+        descriptor: super::super::requires_capability(
+            super::super::namespaced_descriptor(
+                "config set",
+                &[],
+                "Store one config value in prefs storage.",
+                "config set <namespace> <key> <value>",
+                vec![
+                    CommandArgSpec {
+                        name: "namespace".to_string(),
+                        summary: "Config namespace.".to_string(),
+                        required: true,
+                        repeatable: false,
+                        completion: CommandArgCompletion::None,
+                    },
+                    CommandArgSpec {
+                        name: "key".to_string(),
+                        summary: "Config key.".to_string(),
+                        required: true,
+                        repeatable: false,
+                        completion: CommandArgCompletion::None,
+                    },
+                    CommandArgSpec {
+                        name: "value".to_string(),
+                        summary: "Typed literal or string payload.".to_string(),
+                        required: true,
+                        repeatable: false,
+                        completion: CommandArgCompletion::None,
+                    },
+                ],
+                Vec::new(),
+                system_shell_contract::CommandInputShape::none(),
+                CommandOutputShape::new(CommandDataShape::Empty),
+            ),
+            AppCapability::Config,
         ),
         completion: None,
         handler: Rc::new(move |context| {
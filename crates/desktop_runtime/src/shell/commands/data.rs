@@ -2,8 +2,8 @@ use std::{cmp::Ordering, rc::Rc};
 
 use desktop_app_contract::AppCommandRegistration;
 use system_shell_contract::{
-    CommandArgSpec, CommandDataShape, CommandOutputShape, DisplayPreference, StructuredData,
-    StructuredRecord, StructuredScalar, StructuredValue,
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandOutputShape, DisplayPreference,
+    StructuredData, StructuredRecord, StructuredScalar, StructuredValue,
 };
 
 pub(super) fn registrations() -> Vec<AppCommandRegistration> {
@@ -28,6 +28,7 @@ fn data_select_registration() -> AppCommandRegistration {
                 summary: "Field names to keep.".to_string(),
                 required: true,
                 repeatable: true,
+                completion: CommandArgCompletion::None,
             }],
             Vec::new(),
             system_shell_contract::CommandInputShape::accepts(CommandDataShape::Any),
@@ -116,22 +117,29 @@ fn data_select_registration() -> AppCommandRegistration {
 }
 
 fn data_sort_registration() -> AppCommandRegistration {
+    let mut descriptor = super::super::namespaced_descriptor(
+        "data sort",
+        &[],
+        "Sort table rows by a field.",
+        "data sort <field> [--desc]",
+        vec![CommandArgSpec {
+            name: "field".to_string(),
+            summary: "Field to sort by.".to_string(),
+            required: true,
+            repeatable: false,
+            completion: CommandArgCompletion::None,
+        }],
+        Vec::new(),
+        system_shell_contract::CommandInputShape::accepts(CommandDataShape::Table),
+        CommandOutputShape::new(CommandDataShape::Table),
+    );
+    descriptor.options = vec![super::super::flag_option(
+        "desc",
+        None,
+        "Sort in descending order.",
+    )];
     AppCommandRegistration {
-        descriptor: super::super::namespaced_descriptor(
-            "data sort",
-            &[],
-            "Sort table rows by a field.",
-            "data sort <field> [--desc]",
-            vec![CommandArgSpec {
-                name: "field".to_string(),
-                summary: "Field to sort by.".to_string(),
-                required: true,
-                repeatable: false,
-            }],
-            Vec::new(),
-            system_shell_contract::CommandInputShape::accepts(CommandDataShape::Table),
-            CommandOutputShape::new(CommandDataShape::Table),
-        ),
+        descriptor,
         completion: None,
         handler: Rc::new(|context| {
             Box::pin(async move {
@@ -141,11 +149,7 @@ fn data_sort_registration() -> AppCommandRegistration {
                     .ok_or_else(|| super::super::usage_error("usage: data sort <field> [--desc]"))?
                     .clone();
                 let mut table = super::super::data_table_input(&context)?;
-                let descending = context
-                    .invocation
-                    .options
-                    .iter()
-                    .any(|option| option.name == "desc");
+                let descending = super::super::has_flag(&context, "desc");
                 table.rows.sort_by(|left, right| {
                     let left_value = super::super::field_value(left, &field);
                     let right_value = super::super::field_value(right, &field);
@@ -186,18 +190,21 @@ fn data_where_registration() -> AppCommandRegistration {
                     summary: "Field to inspect.".to_string(),
                     required: true,
                     repeatable: false,
+                    completion: CommandArgCompletion::None,
                 },
                 CommandArgSpec {
                     name: "op".to_string(),
                     summary: "Predicate operator.".to_string(),
                     required: true,
                     repeatable: false,
+                    completion: CommandArgCompletion::None,
                 },
                 CommandArgSpec {
                     name: "value".to_string(),
                     summary: "Expected value.".to_string(),
                     required: true,
                     repeatable: false,
+                    completion: CommandArgCompletion::None,
                 },
             ],
             Vec::new(),
@@ -260,6 +267,7 @@ fn data_first_registration() -> AppCommandRegistration {
                 summary: "Number of items to keep.".to_string(),
                 required: false,
                 repeatable: false,
+                completion: CommandArgCompletion::None,
             }],
             Vec::new(),
             system_shell_contract::CommandInputShape::accepts(CommandDataShape::Any),
@@ -313,6 +321,7 @@ fn data_get_registration() -> AppCommandRegistration {
                 summary: "Field name.".to_string(),
                 required: true,
                 repeatable: false,
+                completion: CommandArgCompletion::None,
             }],
             Vec::new(),
             system_shell_contract::CommandInputShape::accepts(CommandDataShape::Any),
@@ -5,17 +5,14 @@ use std::rc::Rc;
 use desktop_app_contract::AppCommandRegistration;
 use platform_host::ExplorerEntryKind;
 use system_shell_contract::{
-    CommandArgSpec, CommandDataShape, CommandNotice, CommandNoticeLevel, CommandOutputShape,
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandExample, CommandNotice,
+    CommandNoticeLevel, CommandOutputShape, ShellError, ShellErrorCode,
 };
 
 use crate::components::DesktopRuntimeContext;
 
 pub(super) fn registrations(runtime: DesktopRuntimeContext) -> Vec<AppCommandRegistration> {
-    vec![
-        pwd_registration(),
-        cd_registration(runtime.clone()),
-        ls_registration(runtime),
-    ]
+    vec![pwd_registration(), fs_delete_registration(runtime)]
 }
 
 fn pwd_registration() -> AppCommandRegistration {
@@ -45,111 +42,109 @@ fn pwd_registration() -> AppCommandRegistration {
     }
 }
 
-fn cd_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
-    AppCommandRegistration {
-        descriptor: super::super::root_descriptor(
-            "cd",
-            &[],
-            "Change the logical filesystem cwd.",
-            "cd <path>",
-            vec![CommandArgSpec {
-                name: "path".to_string(),
-                summary: "Target directory path.".to_string(),
-                required: true,
-                repeatable: false,
-            }],
-            Vec::new(),
-            system_shell_contract::CommandInputShape::none(),
-            CommandOutputShape::new(CommandDataShape::Empty),
+fn fs_delete_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
+    let mut descriptor = super::super::namespaced_descriptor(
+        "fs delete",
+        &[],
+        "Delete a file or directory using the active explorer backend.",
+        "fs delete <path> [--recursive] [--yes] [--dry-run]",
+        vec![CommandArgSpec {
+            name: "path".to_string(),
+            summary: "File or directory to delete.".to_string(),
+            required: true,
+            repeatable: false,
+            completion: CommandArgCompletion::Path,
+        }],
+        vec![
+            CommandExample {
+                command: "fs delete notes.txt".to_string(),
+                summary: "Delete a file in the cwd.".to_string(),
+            },
+            CommandExample {
+                command: "fs delete /projects/old --recursive --dry-run".to_string(),
+                summary: "Show what deleting a directory tree would remove.".to_string(),
+            },
+            CommandExample {
+                command: "fs delete /projects/old --recursive --yes".to_string(),
+                summary: "Delete a directory tree without asking for confirmation.".to_string(),
+            },
+        ],
+        system_shell_contract::CommandInputShape::none(),
+        CommandOutputShape::new(CommandDataShape::Record),
+    );
+    descriptor.options = vec![
+        super::super::flag_option(
+            "recursive",
+            Some('r'),
+            "Delete directories and everything inside them.",
         ),
-        completion: Some(Rc::new(move |request| {
-            let raw = request.argv.get(1).cloned().unwrap_or_default();
-            super::super::path_completion_items(runtime.clone(), &request.cwd, &raw, true)
-        })),
-        handler: Rc::new(move |context| {
-            let runtime = runtime.clone();
-            Box::pin(async move {
-                let target = context
-                    .args
-                    .first()
-                    .ok_or_else(|| super::super::usage_error("usage: cd <path>"))?;
-                let resolved = super::super::normalize_session_path(&context.cwd, target);
-                let metadata = runtime
-                    .host
-                    .get_value()
-                    .explorer_fs_service()
-                    .stat(&resolved)
-                    .await
-                    .map_err(super::super::unavailable)?;
-                if metadata.kind != ExplorerEntryKind::Directory {
-                    return Err(super::super::usage_error(format!(
-                        "not a directory: `{resolved}`"
-                    )));
-                }
-                let mut result = super::super::info_result(format!("cwd = {resolved}"));
-                result.cwd = Some(resolved);
-                Ok(result)
-            })
-        }),
-    }
-}
+        super::super::flag_option(
+            "yes",
+            Some('y'),
+            "Skip the confirmation prompt for directory deletes.",
+        ),
+    ];
+    descriptor.destructive = true;
 
-fn ls_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
     AppCommandRegistration {
-        descriptor: super::super::root_descriptor(
-            "ls",
-            &[],
-            "List a directory using the active explorer backend.",
-            "ls [path]",
-            vec![CommandArgSpec {
-                name: "path".to_string(),
-                summary: "Optional target directory.".to_string(),
-                required: false,
-                repeatable: false,
-            }],
-            Vec::new(),
-            system_shell_contract::CommandInputShape::none(),
-            CommandOutputShape::new(CommandDataShape::Table),
-        ),
-        completion: Some(Rc::new(move |request| {
-            let raw = request.argv.get(1).cloned().unwrap_or_default();
-            super::super::path_completion_items(runtime.clone(), &request.cwd, &raw, false)
-        })),
+        descriptor,
+        completion: None,
         handler: Rc::new(move |context| {
             let runtime = runtime.clone();
             Box::pin(async move {
-                let target = context
-                    .args
-                    .first()
-                    .map(|path| super::super::normalize_session_path(&context.cwd, path))
-                    .unwrap_or_else(|| context.cwd.clone());
-                let listing = runtime
-                    .host
-                    .get_value()
-                    .explorer_fs_service()
-                    .list_dir(&target)
+                let target = context.args.first().ok_or_else(|| {
+                    super::super::usage_error(
+                        "usage: fs delete <path> [--recursive] [--yes] [--dry-run]",
+                    )
+                })?;
+                let resolved = system_shell::resolve_path(&context.cwd, target);
+                let recursive = super::super::has_flag(&context, "recursive");
+                let fs = runtime.host.get_value().explorer_fs_service();
+                let metadata = fs
+                    .stat(&resolved)
                     .await
-                    .map_err(super::super::unavailable)?;
+                    .map_err(|err| super::super::fs_error("stat", &resolved, err))?;
+                let is_directory = metadata.kind == ExplorerEntryKind::Directory;
+                if is_directory && !recursive {
+                    return Err(super::super::fs_error(
+                        "delete",
+                        &resolved,
+                        format!("`{resolved}` is a directory; pass --recursive to delete it"),
+                    ));
+                }
+                let message = if context.dry_run {
+                    format!("would delete {resolved}")
+                } else {
+                    if is_directory
+                        && !super::super::has_flag(&context, "yes")
+                        && !context
+                            .confirm(format!("Delete {resolved} and everything inside it? [y/N]"))
+                            .await?
+                    {
+                        return Err(ShellError::new(
+                            ShellErrorCode::Cancelled,
+                            format!("kept {resolved}"),
+                        ));
+                    }
+                    fs.delete(&resolved, recursive)
+                        .await
+                        .map_err(|err| super::super::fs_error("delete", &resolved, err))?;
+                    format!("deleted {resolved}")
+                };
                 Ok(system_shell_contract::CommandResult {
-                    output: super::super::table_data(
-                        vec![
-                            "name".to_string(),
-                            "kind".to_string(),
-                            "path".to_string(),
-                            "size".to_string(),
-                            "modified_at_unix_ms".to_string(),
-                        ],
-                        listing
-                            .entries
-                            .iter()
-                            .map(super::super::explorer_row)
-                            .collect(),
-                        Some(system_shell_contract::CommandPath::new("ls")),
-                    ),
-                    display: system_shell_contract::DisplayPreference::Table,
+                    output: super::super::record_data(vec![
+                        super::super::string_field("path", resolved.clone()),
+                        super::super::string_field(
+                            "kind",
+                            if is_directory { "dir" } else { "file" },
+                        ),
+                        super::super::bool_field("recursive", recursive),
+                        super::super::bool_field("deleted", !context.dry_run),
+                    ]),
+                    display: system_shell_contract::DisplayPreference::Record,
                     notices: vec![CommandNotice {
                         level: CommandNoticeLevel::Info,
-                        message: format!("listed {}", listing.cwd),
+                        message,
                     }],
                     cwd: None,
                     exit: system_shell_contract::ShellExit::success(),
@@ -45,7 +45,13 @@ fn inspect_runtime_registration(runtime: DesktopRuntimeContext) -> AppCommandReg
                         super::super::bool_field("reduced_motion", desktop.theme.reduced_motion),
                         super::super::int_field(
                             "terminal_history_len",
-                            desktop.terminal_history.len() as i64,
+                            runtime
+                                .shell_engine
+                                .get_value()
+                                .history()
+                                .entries()
+                                .get_untracked()
+                                .len() as i64,
                         ),
                     ]),
                     display: system_shell_contract::DisplayPreference::Record,
@@ -132,6 +138,7 @@ fn inspect_storage_registration(runtime: DesktopRuntimeContext) -> AppCommandReg
                         capability_field("external_urls", capabilities.external_urls),
                         capability_field("notifications", capabilities.notifications),
                         capability_field("wallpaper_library", capabilities.wallpaper_library),
+                        capability_field("network", capabilities.network),
                         super::super::value_field(
                             "namespaces",
                             StructuredValue::List(
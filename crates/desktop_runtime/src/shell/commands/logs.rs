@@ -0,0 +1,134 @@
+#![allow(clippy::clone_on_copy)]
+
+use std::rc::Rc;
+
+use desktop_app_contract::AppCommandRegistration;
+use platform_host::{LogLevel, LogQuery, LogRecord};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandExample, CommandOptionSpec,
+    CommandOutputShape, CommandValueKind, StructuredRecord,
+};
+
+use crate::components::DesktopRuntimeContext;
+
+/// Rows `logs tail` prints when `--limit` is not given.
+const DEFAULT_LOG_TAIL_ROWS: usize = 50;
+
+pub(super) fn registrations(runtime: DesktopRuntimeContext) -> Vec<AppCommandRegistration> {
+    vec![logs_tail_registration(runtime)]
+}
+
+fn log_row(record: &LogRecord) -> StructuredRecord {
+    let fields = if record.fields.is_empty() {
+        String::new()
+    } else {
+        serde_json::to_string(&record.fields).unwrap_or_default()
+    };
+    StructuredRecord {
+        fields: vec![
+            super::super::int_field("seq", record.seq as i64),
+            super::super::int_field("unix_ms", record.unix_ms as i64),
+            super::super::string_field("level", record.level.as_str()),
+            super::super::string_field("source", record.source.clone()),
+            super::super::string_field("message", record.message.clone()),
+            super::super::string_field("fields", fields),
+        ],
+    }
+}
+
+fn logs_tail_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
+    let mut descriptor = super::super::namespaced_descriptor(
+        "logs tail",
+        &[],
+        "Show the newest log records from apps and the desktop runtime.",
+        "logs tail [<source>] [--level <level>] [--limit <n>]",
+        vec![CommandArgSpec {
+            name: "source".to_string(),
+            summary: "App id, or `system.desktop` for the runtime; defaults to every source."
+                .to_string(),
+            required: false,
+            repeatable: false,
+            completion: CommandArgCompletion::None,
+        }],
+        vec![
+            CommandExample {
+                command: "logs tail --level warn".to_string(),
+                summary: "Show recent warnings and errors from every source.".to_string(),
+            },
+            CommandExample {
+                command: "logs tail system.explorer --limit 10".to_string(),
+                summary: "Show Explorer's last ten records.".to_string(),
+            },
+        ],
+        system_shell_contract::CommandInputShape::none(),
+        CommandOutputShape::new(CommandDataShape::Table),
+    );
+    descriptor.options = vec![
+        CommandOptionSpec {
+            name: "level".to_string(),
+            short: Some('l'),
+            summary: "Only records at or above this level.".to_string(),
+            takes_value: true,
+            value_kind: CommandValueKind::String,
+            choices: LogLevel::ALL
+                .iter()
+                .map(|level| level.as_str().to_string())
+                .collect(),
+        },
+        CommandOptionSpec {
+            name: "limit".to_string(),
+            short: Some('n'),
+            summary: format!("Maximum number of records (default {DEFAULT_LOG_TAIL_ROWS})."),
+            takes_value: true,
+            value_kind: CommandValueKind::Int,
+            choices: Vec::new(),
+        },
+    ];
+
+    AppCommandRegistration {
+        descriptor,
+        completion: None,
+        handler: Rc::new(move |context| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                let min_level = super::super::option_value(&context, "level")
+                    .map(|raw| {
+                        LogLevel::from_id(&raw).ok_or_else(|| {
+                            super::super::usage_error(format!(
+                                "unknown log level `{raw}`; use debug, info, warn, or error"
+                            ))
+                        })
+                    })
+                    .transpose()?;
+                let limit = super::super::option_value(&context, "limit")
+                    .map(|raw| {
+                        raw.parse::<usize>().map_err(|_| {
+                            super::super::usage_error(format!(
+                                "--limit must be a non-negative integer, got `{raw}`"
+                            ))
+                        })
+                    })
+                    .transpose()?
+                    .unwrap_or(DEFAULT_LOG_TAIL_ROWS);
+                let records = runtime.host.get_value().log_registry().tail(&LogQuery {
+                    source: context.args.first().cloned(),
+                    min_level,
+                    limit: Some(limit),
+                });
+                Ok(system_shell_contract::CommandResult {
+                    output: super::super::table_data(
+                        ["seq", "unix_ms", "level", "source", "message", "fields"]
+                            .map(str::to_string)
+                            .to_vec(),
+                        records.iter().map(log_row).collect(),
+                        Some(system_shell_contract::CommandPath::new("logs tail")),
+                    ),
+                    display: system_shell_contract::DisplayPreference::Table,
+                    notices: Vec::new(),
+                    cwd: None,
+                    exit: system_shell_contract::ShellExit::success(),
+                })
+            })
+        }),
+    }
+}
@@ -9,8 +9,11 @@ mod config;
 mod data;
 mod filesystem;
 mod inspect;
+mod logs;
+mod script;
 mod theme;
 mod windows;
+mod wm;
 
 pub(super) fn builtin_registrations(runtime: DesktopRuntimeContext) -> Vec<AppCommandRegistration> {
     let mut registrations = Vec::new();
@@ -18,15 +21,24 @@ pub(super) fn builtin_registrations(runtime: DesktopRuntimeContext) -> Vec<AppCo
         super::help_list_registration(runtime.clone()),
         super::help_show_registration(runtime.clone()),
         super::clear_registration(),
-        super::history_list_registration(runtime.clone()),
+        super::scrollback_registration(),
+        super::history_mode_registration(),
+        super::sync_registration(),
+        super::export_registration(),
+        super::copy_registration(),
+        super::inspect_registration(),
+        super::profile_registration(),
         super::open_registration(runtime.clone()),
     ]);
     registrations.extend(apps::registrations(runtime.clone()));
     registrations.extend(windows::registrations(runtime.clone()));
+    registrations.extend(wm::registrations(runtime.clone()));
+    registrations.extend(logs::registrations(runtime.clone()));
     registrations.extend(theme::registrations(runtime.clone()));
     registrations.extend(inspect::registrations(runtime.clone()));
     registrations.extend(filesystem::registrations(runtime.clone()));
     registrations.extend(data::registrations());
     registrations.extend(config::registrations(runtime.clone()));
+    registrations.extend(script::registrations(runtime.clone()));
     registrations
 }
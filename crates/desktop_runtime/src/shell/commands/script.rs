@@ -0,0 +1,143 @@
+#![allow(clippy::clone_on_copy)]
+
+use std::rc::Rc;
+
+use desktop_app_contract::AppCommandRegistration;
+use system_shell::expand_positional_parameters;
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandExample, CommandOutputShape,
+    ShellExit,
+};
+
+use crate::components::DesktopRuntimeContext;
+
+pub(super) fn registrations(runtime: DesktopRuntimeContext) -> Vec<AppCommandRegistration> {
+    vec![run_registration(runtime)]
+}
+
+/// Returns the executable lines of a script, skipping blanks and `#` comments.
+fn script_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn run_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
+    let mut descriptor = super::super::root_descriptor(
+        "run",
+        &[],
+        "Execute a shell script line by line.",
+        "run <path> [args...] [--continue]",
+        vec![
+            CommandArgSpec {
+                name: "path".to_string(),
+                summary: "Script file to execute.".to_string(),
+                required: true,
+                repeatable: false,
+                completion: CommandArgCompletion::Path,
+            },
+            CommandArgSpec {
+                name: "args".to_string(),
+                summary: "Values bound to `$1..$n` inside the script.".to_string(),
+                required: false,
+                repeatable: true,
+                completion: CommandArgCompletion::None,
+            },
+        ],
+        vec![
+            CommandExample {
+                command: "run /scripts/setup.sh demo".to_string(),
+                summary: "Run a setup script with `$1` bound to `demo`.".to_string(),
+            },
+            CommandExample {
+                command: "run /scripts/cleanup.sh --continue".to_string(),
+                summary: "Keep executing after a failing line.".to_string(),
+            },
+        ],
+        system_shell_contract::CommandInputShape::none(),
+        CommandOutputShape::new(CommandDataShape::Empty),
+    );
+    descriptor.options = vec![super::super::flag_option(
+        "continue",
+        None,
+        "Keep executing after a line fails.",
+    )];
+
+    AppCommandRegistration {
+        descriptor,
+        completion: None,
+        handler: Rc::new(move |context| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                let (target, script_args) = context.args.split_first().ok_or_else(|| {
+                    super::super::usage_error("usage: run <path> [args...] [--continue]")
+                })?;
+                let continue_on_error = super::super::has_flag(&context, "continue");
+                let resolved = system_shell::resolve_path(&context.cwd, target);
+                let script = runtime
+                    .host
+                    .get_value()
+                    .explorer_fs_service()
+                    .read_text_file(&resolved)
+                    .await
+                    .map_err(|err| super::super::fs_error("read", &resolved, err))?;
+
+                let mut executed = 0usize;
+                let mut failures = 0usize;
+                for (line_number, line) in script_lines(&script.text) {
+                    if context.is_cancelled() {
+                        return Ok(system_shell_contract::CommandResult {
+                            exit: ShellExit::cancelled(),
+                            ..super::super::empty_result()
+                        });
+                    }
+                    let expanded = expand_positional_parameters(line, &resolved, script_args);
+                    let exit = context.execute_line(expanded).await;
+                    executed += 1;
+                    if exit.code == 0 {
+                        continue;
+                    }
+                    failures += 1;
+                    if !continue_on_error {
+                        let message = format!(
+                            "{resolved}:{line_number}: stopped after exit code {}",
+                            exit.code
+                        );
+                        let mut result = super::super::info_result(message.clone());
+                        result.exit = ShellExit {
+                            code: exit.code,
+                            message: Some(message),
+                            ..exit
+                        };
+                        return Ok(result);
+                    }
+                }
+
+                let mut result = super::super::info_result(format!(
+                    "ran {executed} line(s) from {resolved}, {failures} failed"
+                ));
+                if failures > 0 {
+                    result.exit = ShellExit {
+                        code: 1,
+                        message: Some(format!("{failures} script line(s) failed")),
+                        error: None,
+                        details: None,
+                    };
+                }
+                Ok(result)
+            })
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_lines_skip_comments_and_blank_lines() {
+        let lines = script_lines("# setup\n\ncd /docs\n  ls   \n#done").collect::<Vec<_>>();
+        assert_eq!(lines, vec![(3, "cd /docs"), (4, "ls")]);
+    }
+}
@@ -2,9 +2,11 @@
 
 use std::rc::Rc;
 
-use desktop_app_contract::AppCommandRegistration;
+use desktop_app_contract::{AppCapability, AppCommandRegistration};
 use leptos::SignalGetUntracked;
-use system_shell_contract::{CommandArgSpec, CommandDataShape, CommandOutputShape};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandOutputShape,
+};
 
 use crate::{components::DesktopRuntimeContext, model::DesktopSkin, reducer::DesktopAction};
 
@@ -53,20 +55,24 @@ fn theme_show_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistra
 
 fn theme_set_skin_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
     AppCommandRegistration {
-        descriptor: super::super::namespaced_descriptor(
-            "theme set skin",
-            &[],
-            "Set the desktop skin.",
-            "theme set skin <soft-neumorphic|modern-adaptive|classic-xp|classic-95>",
-            vec![CommandArgSpec {
-                name: "skin".to_string(),
-                summary: "Desktop skin id.".to_string(),
-                required: true,
-                repeatable: false,
-            }],
-            Vec::new(),
-            system_shell_contract::CommandInputShape::none(),
-            CommandOutputShape::new(CommandDataShape::Empty),
+        descriptor: super::super::requires_capability(
+            super::super::namespaced_descriptor(
+                "theme set skin",
+                &[],
+                "Set the desktop skin.",
+                "theme set skin <soft-neumorphic|modern-adaptive|classic-xp|classic-95>",
+                vec![CommandArgSpec {
+                    name: "skin".to_string(),
+                    summary: "Desktop skin id.".to_string(),
+                    required: true,
+                    repeatable: false,
+                    completion: CommandArgCompletion::None,
+                }],
+                Vec::new(),
+                system_shell_contract::CommandInputShape::none(),
+                CommandOutputShape::new(CommandDataShape::Empty),
+            ),
+            AppCapability::Theme,
         ),
         completion: None,
         handler: Rc::new(move |context| {
@@ -99,20 +105,24 @@ fn theme_flag_registration(
     builder: fn(bool) -> DesktopAction,
 ) -> AppCommandRegistration {
     AppCommandRegistration {
-        descriptor: super::super::namespaced_descriptor(
-            path,
-            &[],
-            summary,
-            &format!("{path} <on|off>"),
-            vec![CommandArgSpec {
-                name: "value".to_string(),
-                summary: "Use on or off.".to_string(),
-                required: true,
-                repeatable: false,
-            }],
-            Vec::new(),
-            system_shell_contract::CommandInputShape::none(),
-            CommandOutputShape::new(CommandDataShape::Empty),
+        descriptor: super::super::requires_capability(
+            super::super::namespaced_descriptor(
+                path,
+                &[],
+                summary,
+                &format!("{path} <on|off>"),
+                vec![CommandArgSpec {
+                    name: "value".to_string(),
+                    summary: "Use on or off.".to_string(),
+                    required: true,
+                    repeatable: false,
+                    completion: CommandArgCompletion::None,
+                }],
+                Vec::new(),
+                system_shell_contract::CommandInputShape::none(),
+                CommandOutputShape::new(CommandDataShape::Empty),
+            ),
+            AppCapability::Theme,
         ),
         completion: None,
         handler: Rc::new(move |context| {
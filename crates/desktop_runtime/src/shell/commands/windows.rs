@@ -2,9 +2,11 @@
 
 use std::rc::Rc;
 
-use desktop_app_contract::AppCommandRegistration;
+use desktop_app_contract::{AppCapability, AppCommandRegistration};
 use leptos::SignalGetUntracked;
-use system_shell_contract::{CommandArgSpec, CommandDataShape, CommandOutputShape};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandOutputShape,
+};
 
 use crate::{components::DesktopRuntimeContext, model::WindowId, reducer::DesktopAction};
 
@@ -69,20 +71,24 @@ fn simple_window_registration(
     builder: fn(WindowId) -> DesktopAction,
 ) -> AppCommandRegistration {
     AppCommandRegistration {
-        descriptor: super::super::namespaced_descriptor(
-            path,
-            &[],
-            summary,
-            &format!("{path} <window-id>"),
-            vec![CommandArgSpec {
-                name: "window-id".to_string(),
-                summary: "Runtime window identifier.".to_string(),
-                required: true,
-                repeatable: false,
-            }],
-            Vec::new(),
-            system_shell_contract::CommandInputShape::none(),
-            CommandOutputShape::new(CommandDataShape::Empty),
+        descriptor: super::super::requires_capability(
+            super::super::namespaced_descriptor(
+                path,
+                &[],
+                summary,
+                &format!("{path} <window-id>"),
+                vec![CommandArgSpec {
+                    name: "window-id".to_string(),
+                    summary: "Runtime window identifier.".to_string(),
+                    required: true,
+                    repeatable: false,
+                    completion: CommandArgCompletion::None,
+                }],
+                Vec::new(),
+                system_shell_contract::CommandInputShape::none(),
+                CommandOutputShape::new(CommandDataShape::Empty),
+            ),
+            AppCapability::Window,
         ),
         completion: None,
         handler: Rc::new(move |context| {
@@ -0,0 +1,197 @@
+#![allow(clippy::clone_on_copy)]
+
+use std::rc::Rc;
+
+use desktop_app_contract::{AppCapability, AppCommandRegistration, WindowProcessInfo};
+use leptos::{SignalGetUntracked, SignalWithUntracked};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandOutputShape, ShellError,
+    ShellErrorCode, StructuredRecord,
+};
+
+use crate::{
+    components::{focus_or_unminimize_window, DesktopRuntimeContext},
+    reducer::DesktopAction,
+};
+
+pub(super) fn registrations(runtime: DesktopRuntimeContext) -> Vec<AppCommandRegistration> {
+    vec![
+        wm_list_registration(runtime.clone()),
+        wm_switch_registration(runtime.clone()),
+        wm_kill_registration(runtime),
+    ]
+}
+
+fn process_row(process: &WindowProcessInfo) -> StructuredRecord {
+    StructuredRecord {
+        fields: vec![
+            super::super::int_field("id", process.window_id as i64),
+            super::super::string_field("app_id", process.app_id.as_str()),
+            super::super::string_field("title", process.title.clone()),
+            super::super::int_field("workspace", i64::from(process.workspace) + 1),
+            super::super::bool_field("focused", process.focused),
+            super::super::bool_field("minimized", process.minimized),
+            super::super::bool_field("suspended", process.suspended),
+            super::super::int_field("events", process.events_delivered as i64),
+            super::super::int_field("dropped", process.events_dropped as i64),
+            super::super::int_field("inbox", process.inbox_len as i64),
+            super::super::int_field("subscriptions", process.subscriptions as i64),
+            super::super::int_field("state_bytes", process.app_state_bytes as i64),
+        ],
+    }
+}
+
+fn wm_list_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: super::super::namespaced_descriptor(
+            "wm list",
+            &[],
+            "List open windows with app-bus activity and persisted state size.",
+            "wm list",
+            Vec::new(),
+            Vec::new(),
+            system_shell_contract::CommandInputShape::none(),
+            CommandOutputShape::new(CommandDataShape::Table),
+        ),
+        completion: None,
+        handler: Rc::new(move |_| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                let processes = runtime.window_processes();
+                Ok(system_shell_contract::CommandResult {
+                    output: super::super::table_data(
+                        [
+                            "id",
+                            "app_id",
+                            "title",
+                            "workspace",
+                            "focused",
+                            "minimized",
+                            "suspended",
+                            "events",
+                            "dropped",
+                            "inbox",
+                            "subscriptions",
+                            "state_bytes",
+                        ]
+                        .map(str::to_string)
+                        .to_vec(),
+                        processes.iter().map(process_row).collect(),
+                        Some(system_shell_contract::CommandPath::new("wm list")),
+                    ),
+                    display: system_shell_contract::DisplayPreference::Table,
+                    notices: Vec::new(),
+                    cwd: None,
+                    exit: system_shell_contract::ShellExit::success(),
+                })
+            })
+        }),
+    }
+}
+
+fn wm_switch_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: super::super::requires_capability(
+            super::super::namespaced_descriptor(
+                "wm switch",
+                &[],
+                "Switch to a window, or to the previously used one as Alt+Tab does.",
+                "wm switch [<window-id>]",
+                vec![CommandArgSpec {
+                    name: "window-id".to_string(),
+                    summary: "Runtime window identifier; defaults to the previous window."
+                        .to_string(),
+                    required: false,
+                    repeatable: false,
+                    completion: CommandArgCompletion::None,
+                }],
+                Vec::new(),
+                system_shell_contract::CommandInputShape::none(),
+                CommandOutputShape::new(CommandDataShape::Empty),
+            ),
+            AppCapability::Window,
+        ),
+        completion: None,
+        handler: Rc::new(move |context| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                match context.args.first() {
+                    Some(raw) => {
+                        let window_id = super::super::parse_window_id(raw)?;
+                        let desktop = runtime.state.get_untracked();
+                        if !desktop.windows.iter().any(|w| w.id == window_id) {
+                            return Err(ShellError::new(
+                                ShellErrorCode::Validation,
+                                format!("no open window with id {}", window_id.0),
+                            ));
+                        }
+                        focus_or_unminimize_window(runtime, &desktop, window_id);
+                    }
+                    None => {
+                        runtime
+                            .dispatch_action(DesktopAction::CycleWindowSwitcher { reverse: false });
+                        runtime.dispatch_action(DesktopAction::CommitWindowSwitcher);
+                    }
+                }
+                let focused = runtime.state.with_untracked(|desktop| {
+                    desktop
+                        .windows
+                        .iter()
+                        .find(|w| w.is_focused)
+                        .map(|w| format!("wm switch {} ({})", w.id.0, w.title))
+                });
+                Ok(super::super::info_result(focused.unwrap_or_else(|| {
+                    "wm switch: no window to switch to".to_string()
+                })))
+            })
+        }),
+    }
+}
+
+fn wm_kill_registration(runtime: DesktopRuntimeContext) -> AppCommandRegistration {
+    AppCommandRegistration {
+        descriptor: super::super::requires_capability(
+            super::super::namespaced_descriptor(
+                "wm kill",
+                &[],
+                "Close a window immediately, without asking its app.",
+                "wm kill <window-id>",
+                vec![CommandArgSpec {
+                    name: "window-id".to_string(),
+                    summary: "Runtime window identifier.".to_string(),
+                    required: true,
+                    repeatable: false,
+                    completion: CommandArgCompletion::None,
+                }],
+                Vec::new(),
+                system_shell_contract::CommandInputShape::none(),
+                CommandOutputShape::new(CommandDataShape::Empty),
+            ),
+            AppCapability::Processes,
+        ),
+        completion: None,
+        handler: Rc::new(move |context| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                let raw = context.args.first().ok_or_else(|| {
+                    super::super::usage_error("usage: wm kill <window-id>".to_string())
+                })?;
+                let window_id = super::super::parse_window_id(raw)?;
+                let open = runtime
+                    .state
+                    .with_untracked(|desktop| desktop.windows.iter().any(|w| w.id == window_id));
+                if !open {
+                    return Err(ShellError::new(
+                        ShellErrorCode::Validation,
+                        format!("no open window with id {}", window_id.0),
+                    ));
+                }
+                runtime.dispatch_action(DesktopAction::CloseWindow { window_id });
+                Ok(super::super::info_result(format!(
+                    "wm kill {}",
+                    window_id.0
+                )))
+            })
+        }),
+    }
+}
@@ -4,13 +4,21 @@ use desktop_app_contract::{
     AppCapability, AppCommandContext, AppCommandRegistration, ApplicationId,
     CommandRegistrationHandle as AppCommandRegistrationHandle,
 };
-use system_shell::CommandExecutionContext;
+use system_shell::{AppQuota, CommandExecutionContext};
 use system_shell_contract::{
-    CommandDescriptor, CommandNoticeLevel, CommandScope, ShellStreamEvent,
+    CommandCaller, CommandDescriptor, CommandNoticeLevel, CommandScope, ShellStreamEvent,
 };
 
 use crate::{apps, components::DesktopRuntimeContext, model::WindowId};
 
+/// Limits applied to app-scoped commands of non-privileged apps, keeping a runaway handler from
+/// starving the single-threaded UI.
+const APP_COMMAND_QUOTA: AppQuota = AppQuota {
+    max_concurrent: Some(4),
+    max_output_bytes: Some(1024 * 1024),
+    max_duration_ms: Some(60_000),
+};
+
 pub(super) fn register_app_command(
     runtime: DesktopRuntimeContext,
     app_id: ApplicationId,
@@ -26,17 +34,24 @@ pub(super) fn register_app_command(
     validate_scope(&registration.descriptor.scope, &app_id, window_id)?;
     let completion = registration.completion.clone();
     let handler = registration.handler.clone();
-    let descriptor = registration.descriptor.clone();
-    let system_handle = runtime.shell_engine.get_value().register_command(
-        registration.descriptor,
-        completion.map(|completion| {
-            Rc::new(move |request| completion(request)) as system_shell::CompletionHandler
-        }),
-        Rc::new(move |context: CommandExecutionContext| {
-            let app_context = adapt_context(context, descriptor.clone());
-            handler(app_context)
-        }),
-    );
+    let mut descriptor = registration.descriptor;
+    descriptor.owner_app_id = Some(app_id.as_str().to_string());
+    let engine = runtime.shell_engine.get_value();
+    if !apps::app_is_privileged_by_id(&app_id) {
+        engine.set_app_quota(app_id.as_str(), APP_COMMAND_QUOTA);
+    }
+    let system_handle = engine
+        .register_command(
+            descriptor.clone(),
+            completion.map(|completion| {
+                Rc::new(move |request| completion(request)) as system_shell::CompletionHandler
+            }),
+            Rc::new(move |context: CommandExecutionContext| {
+                let app_context = adapt_context(context, descriptor.clone());
+                handler(app_context)
+            }),
+        )
+        .map_err(|err| err.message)?;
     Ok(AppCommandRegistrationHandle::new(Rc::new(move || {
         system_handle.unregister();
     })))
@@ -49,6 +64,8 @@ fn adapt_context(
     let emit_context = context.clone();
     let set_cwd_context = context.clone();
     let cancel_context = context.clone();
+    let execute_context = context.clone();
+    let prompt_context = context.clone();
     AppCommandContext::new(
         context.execution_id,
         context.invocation.clone(),
@@ -61,6 +78,12 @@ fn adapt_context(
         Rc::new(move |cwd| set_cwd_context.set_cwd(cwd)),
         Rc::new(move || cancel_context.is_cancelled()),
     )
+    .with_dry_run(context.dry_run)
+    .with_line_executor(Rc::new(move |line| execute_context.execute_line(line)))
+    .with_prompter(Rc::new(move |prompt, kind| {
+        let prompt_context = prompt_context.clone();
+        Box::pin(async move { prompt_context.prompt(prompt, kind).await })
+    }))
 }
 
 fn emit_shell_event(context: &CommandExecutionContext, event: ShellStreamEvent) {
@@ -71,10 +94,27 @@ fn emit_shell_event(context: &CommandExecutionContext, event: ShellStreamEvent)
             CommandNoticeLevel::Error => context.error(notice.message),
         },
         ShellStreamEvent::Progress { value, label, .. } => context.progress(value, label),
+        ShellStreamEvent::StdoutChunk { text, .. } => context.stdout(text),
+        ShellStreamEvent::StderrChunk { text, .. } => context.stderr(text),
         _ => {}
     }
 }
 
+/// Grants `capability` to privileged apps and to apps whose manifest requests it.
+pub(crate) fn caller_has_capability(caller: &CommandCaller, capability: &str) -> bool {
+    let Some(descriptor) = apps::app_registry()
+        .iter()
+        .find(|descriptor| descriptor.app_id.as_str() == caller.app_id)
+    else {
+        return false;
+    };
+    apps::app_is_privileged_by_id(&descriptor.app_id)
+        || descriptor
+            .requested_capabilities
+            .iter()
+            .any(|granted| granted.as_str() == capability)
+}
+
 fn app_can_register_commands(app_id: &ApplicationId) -> bool {
     apps::app_is_privileged_by_id(app_id)
         || apps::app_requested_capabilities_by_id(app_id).contains(&AppCapability::Commands)
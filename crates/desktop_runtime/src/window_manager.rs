@@ -11,7 +11,8 @@ pub const SNAP_EDGE_THRESHOLD: i32 = 24;
 
 /// Focuses and raises `window_id`, ensuring it is the top/focused non-minimized window.
 ///
-/// Returns `true` when stack/focus state changed.
+/// A window on another workspace brings its workspace on screen. Returns `true` when stack/focus
+/// state changed.
 pub fn focus_window_internal(state: &mut DesktopState, window_id: WindowId) -> bool {
     let Some(index) = state.windows.iter().position(|w| w.id == window_id) else {
         return false;
@@ -27,6 +28,7 @@ pub fn focus_window_internal(state: &mut DesktopState, window_id: WindowId) -> b
         return true;
     }
 
+    state.active_workspace = state.windows[index].workspace;
     for window in &mut state.windows {
         window.is_focused = false;
     }
@@ -40,11 +42,15 @@ pub fn focus_window_internal(state: &mut DesktopState, window_id: WindowId) -> b
 }
 
 /// Normalizes z-index ordering and focus invariants for all managed windows.
+///
+/// Only a non-minimized window on the active workspace keeps focus; without one, the topmost such
+/// window takes it.
 pub fn normalize_window_stack(state: &mut DesktopState) {
     let mut has_focused = false;
+    let active_workspace = state.active_workspace;
     for (idx, window) in state.windows.iter_mut().enumerate() {
         window.z_index = (idx + 1) as u32;
-        if window.minimized {
+        if window.minimized || window.workspace != active_workspace {
             window.is_focused = false;
         }
         if window.is_focused {
@@ -57,7 +63,12 @@ pub fn normalize_window_stack(state: &mut DesktopState) {
     }
 
     if !has_focused {
-        if let Some(last_non_minimized) = state.windows.iter_mut().rev().find(|w| !w.minimized) {
+        if let Some(last_non_minimized) = state
+            .windows
+            .iter_mut()
+            .rev()
+            .find(|w| !w.minimized && w.workspace == active_workspace)
+        {
             last_non_minimized.is_focused = true;
         }
     }
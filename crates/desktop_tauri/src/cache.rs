@@ -4,6 +4,7 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use platform_host::StorageUsageEntry;
 use tauri::Manager;
 
 type CacheDomain = BTreeMap<String, String>;
@@ -102,6 +103,31 @@ impl ScopedCacheStore {
         }
         save_cache_map(&self.file, &map)
     }
+
+    /// Lists entry counts and stored text size per cache name, ordered by name.
+    pub fn usage(&self) -> Result<Vec<StorageUsageEntry>, String> {
+        let map = load_cache_map(&self.file)?;
+        Ok(map
+            .into_iter()
+            .map(|(cache_name, domain)| StorageUsageEntry {
+                name: cache_name,
+                entries: domain.len() as u64,
+                bytes: domain.values().map(|value| value.len() as u64).sum(),
+            })
+            .collect())
+    }
+
+    /// Deletes every entry stored under `cache_name`.
+    pub fn clear(&self, cache_name: &str) -> Result<(), String> {
+        if cache_name.is_empty() {
+            return Err("cache_name must not be empty".to_string());
+        }
+        let mut map = load_cache_map(&self.file)?;
+        if map.remove(cache_name).is_some() {
+            save_cache_map(&self.file, &map)?;
+        }
+        Ok(())
+    }
 }
 
 /// Stores cache text content under `cache_name` and `key`.
@@ -131,9 +157,21 @@ pub fn cache_delete(app: tauri::AppHandle, cache_name: String, key: String) -> R
     ScopedCacheStore::from_app(&app)?.delete(&cache_name, &key)
 }
 
+/// Lists entry counts and stored text size per cache name.
+#[tauri::command]
+pub fn cache_usage(app: tauri::AppHandle) -> Result<Vec<StorageUsageEntry>, String> {
+    ScopedCacheStore::from_app(&app)?.usage()
+}
+
+/// Deletes every entry stored under `cache_name`.
+#[tauri::command]
+pub fn cache_clear(app: tauri::AppHandle, cache_name: String) -> Result<(), String> {
+    ScopedCacheStore::from_app(&app)?.clear(&cache_name)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{load_cache_map, save_cache_map, CacheMap, ScopedCacheStore};
+    use super::{load_cache_map, save_cache_map, CacheMap, ScopedCacheStore, StorageUsageEntry};
     use std::fs;
     use std::path::PathBuf;
     use std::process;
@@ -184,6 +222,33 @@ mod tests {
         let _ = fs::remove_file(path);
     }
 
+    #[test]
+    fn scoped_cache_store_reports_usage_and_clears_one_cache() {
+        let root = temp_dir_path();
+        let store = ScopedCacheStore::from_root(&root).expect("init scoped cache store");
+        store.put_text("preview", "/a.txt", "hello").expect("put a");
+        store.put_text("preview", "/b.txt", "hi").expect("put b");
+        store.put_text("thumbs", "/a.png", "x").expect("put thumb");
+
+        assert_eq!(
+            store.usage().expect("usage"),
+            vec![
+                StorageUsageEntry {
+                    name: "preview".to_string(),
+                    entries: 2,
+                    bytes: 7,
+                },
+                StorageUsageEntry::single("thumbs", 1),
+            ]
+        );
+        store.clear("preview").expect("clear");
+        assert_eq!(store.get_text("preview", "/a.txt").expect("get"), None);
+        assert_eq!(store.usage().expect("usage after clear").len(), 1);
+        assert!(store.clear("").is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[test]
     fn scoped_cache_store_rejects_empty_cache_name_or_key() {
         let root = temp_dir_path();
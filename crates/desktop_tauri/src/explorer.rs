@@ -1,13 +1,16 @@
 //! Typed Tauri command handlers for explorer filesystem operations.
 
 use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::time::UNIX_EPOCH;
 
 use platform_host::{
     explorer_preview_cache_key, normalize_virtual_path, ExplorerBackend, ExplorerBackendStatus,
-    ExplorerEntry, ExplorerEntryKind, ExplorerFileReadResult, ExplorerListResult, ExplorerMetadata,
-    ExplorerPermissionMode, ExplorerPermissionState,
+    ExplorerBatchOp, ExplorerBatchResult, ExplorerEntry, ExplorerEntryKind,
+    ExplorerFileBytesResult, ExplorerFileChunk, ExplorerFileReadResult, ExplorerListPage,
+    ExplorerListResult, ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState,
+    ExplorerStorageUsage,
 };
 use tauri::Manager;
 
@@ -122,6 +125,7 @@ fn current_status() -> ExplorerBackendStatus {
         has_native_root: true,
         permission: ExplorerPermissionState::Granted,
         root_path_hint: Some("/".to_string()),
+        mounts: Vec::new(),
     }
 }
 
@@ -237,18 +241,97 @@ impl ScopedExplorerFs {
         })
     }
 
+    /// Reads raw bytes for a file path under the scoped explorer root.
+    pub fn read_file_bytes(&self, path: &str) -> Result<ExplorerFileBytesResult, String> {
+        let (normalized, native) = resolve_virtual_path(&self.root, path);
+        let metadata = metadata_for_path(&self.root, &normalized, &native)?;
+        if metadata.kind != ExplorerEntryKind::File {
+            return Err(format!("path `{normalized}` is not a file"));
+        }
+
+        let bytes = fs::read(&native)
+            .map_err(|err| format!("failed to read {}: {err}", native.display()))?;
+        Ok(ExplorerFileBytesResult {
+            backend: ExplorerBackend::NativeFsAccess,
+            path: normalized,
+            bytes,
+            metadata,
+        })
+    }
+
+    /// Reads up to `len` bytes starting at `offset` from a file under the scoped explorer root.
+    pub fn read_file_chunk(
+        &self,
+        path: &str,
+        offset: u64,
+        len: usize,
+    ) -> Result<ExplorerFileChunk, String> {
+        let (normalized, native) = resolve_virtual_path(&self.root, path);
+        let metadata = metadata_for_path(&self.root, &normalized, &native)?;
+        if metadata.kind != ExplorerEntryKind::File {
+            return Err(format!("path `{normalized}` is not a file"));
+        }
+
+        let read_err = |err: std::io::Error| format!("failed to read {}: {err}", native.display());
+        let mut file = fs::File::open(&native).map_err(read_err)?;
+        let size = file.metadata().map_err(read_err)?.len();
+        file.seek(SeekFrom::Start(offset)).map_err(read_err)?;
+        let mut bytes = Vec::with_capacity(len.min(size.saturating_sub(offset) as usize));
+        file.take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(read_err)?;
+        Ok(ExplorerFileChunk {
+            path: normalized,
+            offset,
+            bytes,
+            size,
+        })
+    }
+
     /// Writes UTF-8 text content to a file path under the scoped explorer root.
     pub fn write_text_file(&self, path: &str, text: &str) -> Result<ExplorerMetadata, String> {
+        self.write_file_bytes(path, text.as_bytes())
+    }
+
+    /// Writes raw bytes to a file path under the scoped explorer root, replacing any existing file.
+    pub fn write_file_bytes(&self, path: &str, bytes: &[u8]) -> Result<ExplorerMetadata, String> {
         let (normalized, native) = resolve_virtual_path(&self.root, path);
         if normalized == "/" {
             return Err("cannot write to explorer root".to_string());
         }
         ensure_parent_within_root(&self.root, &native)?;
-        fs::write(&native, text)
+        fs::write(&native, bytes)
             .map_err(|err| format!("failed to write {}: {err}", native.display()))?;
         metadata_for_path(&self.root, &normalized, &native)
     }
 
+    /// Writes a chunk of bytes at `offset`, replacing the file at offset 0 and appending otherwise.
+    pub fn write_file_chunk(
+        &self,
+        path: &str,
+        offset: u64,
+        bytes: &[u8],
+    ) -> Result<ExplorerMetadata, String> {
+        if offset == 0 {
+            return self.write_file_bytes(path, bytes);
+        }
+        let (normalized, native) = resolve_virtual_path(&self.root, path);
+        let write_err =
+            |err: std::io::Error| format!("failed to write {}: {err}", native.display());
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(&native)
+            .map_err(write_err)?;
+        let size = file.metadata().map_err(write_err)?.len();
+        if size != offset {
+            return Err(format!(
+                "chunk offset {offset} does not match the {size} byte size of `{normalized}`"
+            ));
+        }
+        file.write_all(bytes).map_err(write_err)?;
+        metadata_for_path(&self.root, &normalized, &native)
+    }
+
     /// Creates a directory path under the scoped explorer root.
     pub fn create_dir(&self, path: &str) -> Result<ExplorerMetadata, String> {
         let (normalized, native) = resolve_virtual_path(&self.root, path);
@@ -293,11 +376,88 @@ impl ScopedExplorerFs {
         Ok(())
     }
 
+    /// Renames or moves a path under the scoped explorer root without replacing existing entries.
+    pub fn rename(&self, from: &str, to: &str) -> Result<ExplorerMetadata, String> {
+        let (source, source_native) = resolve_virtual_path(&self.root, from);
+        let (target, target_native) = resolve_virtual_path(&self.root, to);
+        if source == "/" || target == "/" {
+            return Err("cannot rename explorer root".to_string());
+        }
+        ensure_existing_within_root(&self.root, &source_native)?;
+        ensure_parent_within_root(&self.root, &target_native)?;
+        if source == target {
+            return metadata_for_path(&self.root, &target, &target_native);
+        }
+        if target.starts_with(&format!("{source}/")) {
+            return Err(format!("cannot move `{source}` into itself"));
+        }
+        if fs::symlink_metadata(&target_native).is_ok() {
+            return Err(format!("path `{target}` already exists"));
+        }
+        fs::rename(&source_native, &target_native).map_err(|err| {
+            format!(
+                "failed to rename {} to {}: {err}",
+                source_native.display(),
+                target_native.display()
+            )
+        })?;
+        metadata_for_path(&self.root, &target, &target_native)
+    }
+
+    /// Applies batch operations in order. Each failure is reported in its slot and the rest still
+    /// run, since the disk offers no transaction to roll earlier operations back.
+    pub fn batch(&self, ops: &[ExplorerBatchOp]) -> Vec<ExplorerBatchResult> {
+        ops.iter()
+            .map(|op| {
+                ExplorerBatchResult::from(match op {
+                    ExplorerBatchOp::CreateDir { path } => self.create_dir(path).map(Some),
+                    ExplorerBatchOp::WriteText { path, text } => {
+                        self.write_text_file(path, text).map(Some)
+                    }
+                    ExplorerBatchOp::WriteBytes { path, bytes } => {
+                        self.write_file_bytes(path, bytes).map(Some)
+                    }
+                    ExplorerBatchOp::Delete { path, recursive } => {
+                        self.delete(path, *recursive).map(|()| None)
+                    }
+                    ExplorerBatchOp::Rename { from, to } => self.rename(from, to).map(Some),
+                })
+            })
+            .collect()
+    }
+
     /// Returns metadata for a path under the scoped explorer root.
     pub fn stat(&self, path: &str) -> Result<ExplorerMetadata, String> {
         let (normalized, native) = resolve_virtual_path(&self.root, path);
         metadata_for_path(&self.root, &normalized, &native)
     }
+
+    /// Adds up the files under the scoped root. Files on disk have no quota and are never
+    /// evicted, so only usage is reported.
+    pub fn usage(&self) -> Result<ExplorerStorageUsage, String> {
+        let mut bytes = 0u64;
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let entries = fs::read_dir(&dir)
+                .map_err(|err| format!("failed to list {}: {err}", dir.display()))?;
+            for entry in entries.flatten() {
+                // Symlinks are not followed, so links out of the root are never counted.
+                let Ok(metadata) = entry.path().symlink_metadata() else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    pending.push(entry.path());
+                } else if metadata.is_file() {
+                    bytes += metadata.len();
+                }
+            }
+        }
+        Ok(ExplorerStorageUsage {
+            usage_bytes: Some(bytes),
+            quota_bytes: None,
+            persisted: Some(true),
+        })
+    }
 }
 
 /// Returns current explorer backend status for desktop native filesystem mode.
@@ -314,6 +474,13 @@ pub fn explorer_pick_root(_app: tauri::AppHandle) -> Result<ExplorerBackendStatu
     Ok(fs.pick_root())
 }
 
+/// Desktop mode serves one scoped root and never mounts other folders.
+#[tauri::command]
+pub fn explorer_unmount_native_directory(path: String) -> Result<ExplorerBackendStatus, String> {
+    let path = normalize_virtual_path(&path);
+    Err(format!("no folder is mounted at {path}"))
+}
+
 /// Returns granted permission for desktop scoped-root explorer operations.
 #[tauri::command]
 pub fn explorer_request_permission(
@@ -334,6 +501,19 @@ pub fn explorer_list_dir(
     fs.list_dir(&path)
 }
 
+/// Lists one page of a directory under the scoped explorer root.
+#[tauri::command]
+pub fn explorer_list_dir_page(
+    app: tauri::AppHandle,
+    path: String,
+    offset: usize,
+    limit: usize,
+) -> Result<ExplorerListPage, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    fs.list_dir(&path)
+        .map(|listing| listing.page(offset, limit))
+}
+
 /// Reads UTF-8 text content for a file path under the scoped explorer root.
 #[tauri::command]
 pub fn explorer_read_text_file(
@@ -344,6 +524,28 @@ pub fn explorer_read_text_file(
     fs.read_text_file(&path)
 }
 
+/// Reads raw bytes for a file path under the scoped explorer root.
+#[tauri::command]
+pub fn explorer_read_file_bytes(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<ExplorerFileBytesResult, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    fs.read_file_bytes(&path)
+}
+
+/// Reads a byte range of a file under the scoped explorer root.
+#[tauri::command]
+pub fn explorer_read_file_chunk(
+    app: tauri::AppHandle,
+    path: String,
+    offset: u64,
+    len: usize,
+) -> Result<ExplorerFileChunk, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    fs.read_file_chunk(&path, offset, len)
+}
+
 /// Writes UTF-8 text content to a file path under the scoped explorer root.
 #[tauri::command]
 pub fn explorer_write_text_file(
@@ -355,6 +557,29 @@ pub fn explorer_write_text_file(
     fs.write_text_file(&path, &text)
 }
 
+/// Writes raw bytes to a file path under the scoped explorer root.
+#[tauri::command]
+pub fn explorer_write_file_bytes(
+    app: tauri::AppHandle,
+    path: String,
+    bytes: Vec<u8>,
+) -> Result<ExplorerMetadata, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    fs.write_file_bytes(&path, &bytes)
+}
+
+/// Writes a chunk of bytes into a file under the scoped explorer root.
+#[tauri::command]
+pub fn explorer_write_file_chunk(
+    app: tauri::AppHandle,
+    path: String,
+    offset: u64,
+    bytes: Vec<u8>,
+) -> Result<ExplorerMetadata, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    fs.write_file_chunk(&path, offset, &bytes)
+}
+
 /// Creates a directory path under the scoped explorer root.
 #[tauri::command]
 pub fn explorer_create_dir(
@@ -383,6 +608,27 @@ pub fn explorer_delete(app: tauri::AppHandle, path: String, recursive: bool) ->
     fs.delete(&path, recursive)
 }
 
+/// Renames or moves a path under the scoped explorer root without replacing existing entries.
+#[tauri::command]
+pub fn explorer_rename(
+    app: tauri::AppHandle,
+    from: String,
+    to: String,
+) -> Result<ExplorerMetadata, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    fs.rename(&from, &to)
+}
+
+/// Applies a batch of operations under the scoped explorer root.
+#[tauri::command]
+pub fn explorer_batch(
+    app: tauri::AppHandle,
+    ops: Vec<ExplorerBatchOp>,
+) -> Result<Vec<ExplorerBatchResult>, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    Ok(fs.batch(&ops))
+}
+
 /// Returns metadata for a path under the scoped explorer root.
 #[tauri::command]
 pub fn explorer_stat(app: tauri::AppHandle, path: String) -> Result<ExplorerMetadata, String> {
@@ -390,6 +636,19 @@ pub fn explorer_stat(app: tauri::AppHandle, path: String) -> Result<ExplorerMeta
     fs.stat(&path)
 }
 
+/// Reports disk usage under the scoped explorer root.
+#[tauri::command]
+pub fn explorer_usage(app: tauri::AppHandle) -> Result<ExplorerStorageUsage, String> {
+    let fs = ScopedExplorerFs::from_app(&app)?;
+    fs.usage()
+}
+
+/// Files under the scoped explorer root are always persistent.
+#[tauri::command]
+pub fn explorer_request_persistent_storage() -> Result<bool, String> {
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::resolve_virtual_path;
@@ -34,21 +34,34 @@ pub fn run() {
             cache::cache_put_text,
             cache::cache_get_text,
             cache::cache_delete,
+            cache::cache_usage,
+            cache::cache_clear,
             explorer::explorer_status,
             explorer::explorer_pick_root,
+            explorer::explorer_unmount_native_directory,
             explorer::explorer_request_permission,
             explorer::explorer_list_dir,
+            explorer::explorer_list_dir_page,
             explorer::explorer_read_text_file,
+            explorer::explorer_read_file_bytes,
+            explorer::explorer_read_file_chunk,
             explorer::explorer_write_text_file,
+            explorer::explorer_write_file_bytes,
+            explorer::explorer_write_file_chunk,
             explorer::explorer_create_dir,
             explorer::explorer_create_file,
             explorer::explorer_delete,
+            explorer::explorer_rename,
+            explorer::explorer_batch,
             explorer::explorer_stat,
+            explorer::explorer_usage,
+            explorer::explorer_request_persistent_storage,
             external_url::external_open_url,
             notifications::notify_send,
             prefs::prefs_load,
             prefs::prefs_save,
-            prefs::prefs_delete
+            prefs::prefs_delete,
+            prefs::prefs_usage
         ])
         .run(tauri::generate_context!())
         .expect("desktop_tauri failed to run Tauri application");
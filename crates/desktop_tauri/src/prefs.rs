@@ -4,6 +4,7 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use platform_host::StorageUsageEntry;
 use tauri::Manager;
 
 type PrefMap = BTreeMap<String, String>;
@@ -92,6 +93,15 @@ impl ScopedPrefsStore {
         map.remove(key);
         save_pref_map(&self.file, &map)
     }
+
+    /// Lists every preference key with the size of its payload, ordered by key.
+    pub fn usage(&self) -> Result<Vec<StorageUsageEntry>, String> {
+        let map = load_pref_map(&self.file)?;
+        Ok(map
+            .into_iter()
+            .map(|(key, raw)| StorageUsageEntry::single(key, raw.len()))
+            .collect())
+    }
 }
 
 /// Loads a preference raw JSON payload by key.
@@ -112,9 +122,15 @@ pub fn prefs_delete(app: tauri::AppHandle, key: String) -> Result<(), String> {
     ScopedPrefsStore::from_app(&app)?.delete(&key)
 }
 
+/// Lists every preference key with the size of its payload.
+#[tauri::command]
+pub fn prefs_usage(app: tauri::AppHandle) -> Result<Vec<StorageUsageEntry>, String> {
+    ScopedPrefsStore::from_app(&app)?.usage()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{load_pref_map, save_pref_map, PrefMap, ScopedPrefsStore};
+    use super::{load_pref_map, save_pref_map, PrefMap, ScopedPrefsStore, StorageUsageEntry};
     use std::fs;
     use std::path::PathBuf;
     use std::process;
@@ -163,6 +179,26 @@ mod tests {
         let _ = fs::remove_file(path);
     }
 
+    #[test]
+    fn scoped_prefs_store_reports_usage_per_key() {
+        let root = temp_dir_path();
+        let store = ScopedPrefsStore::from_root(&root).expect("init scoped prefs store");
+        store.save("system.logs.v1", "[1,2]").expect("save logs");
+        store
+            .save("system.files.associations", "{}")
+            .expect("save files");
+
+        assert_eq!(
+            store.usage().expect("usage"),
+            vec![
+                StorageUsageEntry::single("system.files.associations", 2),
+                StorageUsageEntry::single("system.logs.v1", 5),
+            ]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+
     #[test]
     fn scoped_prefs_store_rejects_empty_key_for_all_operations() {
         let root = temp_dir_path();
@@ -29,6 +29,29 @@ fn scoped_fs_normalizes_and_stays_within_root() {
     assert_eq!(read.path, "/notes/readme.txt");
     assert_eq!(read.text, "hello");
 
+    let written = fscope
+        .write_file_bytes("/notes/pixel.png", &[0x89, b'P', b'N', b'G', 0xff])
+        .expect("write bytes in root");
+    assert_eq!(written.size, Some(5));
+    assert_eq!(
+        fs::read(root.join("notes/pixel.png")).expect("read native file"),
+        [0x89, b'P', b'N', b'G', 0xff]
+    );
+    let bytes = fscope
+        .read_file_bytes("/notes/pixel.png")
+        .expect("read bytes in root");
+    assert_eq!(bytes.bytes, [0x89, b'P', b'N', b'G', 0xff]);
+    assert_eq!(bytes.metadata.size, Some(5));
+    let chunk = fscope
+        .read_file_chunk("/notes/pixel.png", 3, 8)
+        .expect("read chunk in root");
+    assert_eq!((chunk.offset, chunk.size), (3, 5));
+    assert_eq!(chunk.bytes, [b'G', 0xff]);
+    let past_end = fscope
+        .read_file_chunk("/notes/pixel.png", 9, 8)
+        .expect("read past end");
+    assert!(past_end.bytes.is_empty());
+
     let _ = fs::remove_dir_all(root);
 }
 
@@ -46,6 +69,51 @@ fn scoped_fs_rejects_root_delete_and_root_write() {
         .write_text_file("/", "x")
         .expect_err("root write should be rejected");
     assert_eq!(write_err, "cannot write to explorer root");
+    let write_bytes_err = fscope
+        .write_file_bytes("/", &[1])
+        .expect_err("root byte write should be rejected");
+    assert_eq!(write_bytes_err, "cannot write to explorer root");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn scoped_fs_renames_without_replacing_existing_entries() {
+    let root = temp_dir("explorer_scoped_rename");
+    let fscope = ScopedExplorerFs::from_root(&root).expect("init scoped fs");
+
+    fscope.create_dir("/notes").expect("create notes dir");
+    fscope
+        .write_text_file("/notes/a.txt", "a")
+        .expect("write a");
+    fscope
+        .write_text_file("/notes/b.txt", "b")
+        .expect("write b");
+
+    let err = fscope
+        .rename("/notes/a.txt", "/notes/b.txt")
+        .expect_err("rename onto existing file should fail");
+    assert_eq!(err, "path `/notes/b.txt` already exists");
+    let err = fscope
+        .rename("/notes", "/notes/inner")
+        .expect_err("rename into itself should fail");
+    assert_eq!(err, "cannot move `/notes` into itself");
+
+    let meta = fscope
+        .rename("/notes/a.txt", "/notes/c.txt")
+        .expect("rename file");
+    assert_eq!(meta.name, "c.txt");
+    assert_eq!(meta.path, "/notes/c.txt");
+    let meta = fscope.rename("/notes", "/archive").expect("rename dir");
+    assert_eq!(meta.path, "/archive");
+    assert_eq!(
+        fscope
+            .read_text_file("/archive/c.txt")
+            .expect("read moved")
+            .text,
+        "a"
+    );
+    assert!(fscope.stat("/notes").is_err());
 
     let _ = fs::remove_dir_all(root);
 }
@@ -71,6 +139,11 @@ fn scoped_fs_error_contract_for_invalid_or_unsupported_operations() {
             fscope.write_text_file("/", "x").err(),
             "cannot write to explorer root",
         ),
+        (
+            "rename_root",
+            fscope.rename("/", "/moved").err(),
+            "cannot rename explorer root",
+        ),
         (
             "list_non_dir",
             fscope.list_dir("/file.txt").err(),
@@ -81,6 +154,16 @@ fn scoped_fs_error_contract_for_invalid_or_unsupported_operations() {
             fscope.read_text_file("/dir").err(),
             "path `/dir` is not a file",
         ),
+        (
+            "read_bytes_non_file",
+            fscope.read_file_bytes("/dir").err(),
+            "path `/dir` is not a file",
+        ),
+        (
+            "read_chunk_non_file",
+            fscope.read_file_chunk("/dir", 0, 16).err(),
+            "path `/dir` is not a file",
+        ),
     ];
 
     for (label, got, expected) in cases {
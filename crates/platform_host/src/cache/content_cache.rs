@@ -1,9 +1,17 @@
 //! Content cache service contracts and adapters.
 
-use std::{cell::RefCell, collections::HashMap, future::Future, pin::Pin, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::storage::usage::StorageUsageEntry;
+
 /// Object-safe boxed future used by [`ContentCache`] async methods.
 pub type ContentCacheFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
 
@@ -30,6 +38,13 @@ pub trait ContentCache {
         cache_name: &'a str,
         key: &'a str,
     ) -> ContentCacheFuture<'a, Result<(), String>>;
+
+    /// Reports entry counts and stored text size per cache name, ordered by name.
+    fn cache_usage<'a>(&'a self) -> ContentCacheFuture<'a, Result<Vec<StorageUsageEntry>, String>>;
+
+    /// Deletes every entry stored under `cache_name`.
+    fn clear_cache<'a>(&'a self, cache_name: &'a str)
+        -> ContentCacheFuture<'a, Result<(), String>>;
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -61,6 +76,17 @@ impl ContentCache for NoopContentCache {
     ) -> ContentCacheFuture<'a, Result<(), String>> {
         Box::pin(async { Ok(()) })
     }
+
+    fn cache_usage<'a>(&'a self) -> ContentCacheFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn clear_cache<'a>(
+        &'a self,
+        _cache_name: &'a str,
+    ) -> ContentCacheFuture<'a, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -110,6 +136,36 @@ impl ContentCache for MemoryContentCache {
             Ok(())
         })
     }
+
+    fn cache_usage<'a>(&'a self) -> ContentCacheFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        Box::pin(async move {
+            let mut usage = BTreeMap::<String, StorageUsageEntry>::new();
+            for ((cache_name, _), value) in self.inner.borrow().iter() {
+                let entry = usage
+                    .entry(cache_name.clone())
+                    .or_insert_with(|| StorageUsageEntry {
+                        name: cache_name.clone(),
+                        entries: 0,
+                        bytes: 0,
+                    });
+                entry.entries += 1;
+                entry.bytes += value.len() as u64;
+            }
+            Ok(usage.into_values().collect())
+        })
+    }
+
+    fn clear_cache<'a>(
+        &'a self,
+        cache_name: &'a str,
+    ) -> ContentCacheFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            self.inner
+                .borrow_mut()
+                .retain(|(name, _), _| name != cache_name);
+            Ok(())
+        })
+    }
 }
 
 /// Serializes and stores a JSON value through a [`ContentCache`] implementation.
@@ -197,5 +253,38 @@ mod tests {
         block_on(cache_obj.put_text("x", "y", "z")).expect("put");
         assert_eq!(block_on(cache_obj.get_text("x", "y")).expect("get"), None);
         block_on(cache_obj.delete("x", "y")).expect("delete");
+        assert!(block_on(cache_obj.cache_usage()).expect("usage").is_empty());
+        block_on(cache_obj.clear_cache("x")).expect("clear");
+    }
+
+    #[test]
+    fn memory_content_cache_reports_usage_and_clears_one_cache() {
+        let cache = MemoryContentCache::default();
+        let cache_obj: &dyn ContentCache = &cache;
+        block_on(cache_obj.put_text("preview", "/a.txt", "hello")).expect("put a");
+        block_on(cache_obj.put_text("preview", "/b.txt", "hi")).expect("put b");
+        block_on(cache_obj.put_text("thumbs", "/a.png", "x")).expect("put thumb");
+
+        assert_eq!(
+            block_on(cache_obj.cache_usage()).expect("usage"),
+            vec![
+                StorageUsageEntry {
+                    name: "preview".to_string(),
+                    entries: 2,
+                    bytes: 7,
+                },
+                StorageUsageEntry::single("thumbs", 1),
+            ]
+        );
+
+        block_on(cache_obj.clear_cache("preview")).expect("clear");
+        assert_eq!(
+            block_on(cache_obj.get_text("preview", "/a.txt")).expect("get"),
+            None
+        );
+        assert_eq!(
+            block_on(cache_obj.cache_usage()).expect("usage after clear"),
+            vec![StorageUsageEntry::single("thumbs", 1)]
+        );
     }
 }
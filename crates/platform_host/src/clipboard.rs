@@ -0,0 +1,22 @@
+//! Clipboard host-service contracts.
+
+use std::{future::Future, pin::Pin};
+
+/// Object-safe boxed future used by [`ClipboardService`].
+pub type ClipboardFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Host service for writing text to the system clipboard.
+pub trait ClipboardService {
+    /// Replaces the clipboard contents with plain text.
+    fn write_text<'a>(&'a self, text: &'a str) -> ClipboardFuture<'a, Result<(), String>>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// No-op clipboard service for unsupported targets.
+pub struct NoopClipboardService;
+
+impl ClipboardService for NoopClipboardService {
+    fn write_text<'a>(&'a self, _text: &'a str) -> ClipboardFuture<'a, Result<(), String>> {
+        Box::pin(async { Err("clipboard is unavailable on this host".to_string()) })
+    }
+}
@@ -0,0 +1,911 @@
+//! ZIP archive export and import built on top of [`ExplorerFsService`].
+//!
+//! Archives are assembled and unpacked in memory by a small codec that covers the stored and
+//! deflate methods, which is what common zip tools write. ZIP64 and encrypted archives are
+//! rejected. Files are streamed in and out of the archive in chunks, so no file is held whole
+//! next to the archive. Like the trash, the helpers only use the service contract, so every
+//! backend supports them.
+
+use std::collections::BTreeSet;
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use super::path::{fresh_entry_name, normalize_virtual_path};
+use super::service::ExplorerFsService;
+use super::stream::EXPLORER_STREAM_CHUNK_BYTES;
+use super::trash::is_trash_path;
+use super::types::{ExplorerBatchOp, ExplorerEntryKind};
+
+/// Largest total of uncompressed file bytes an archive may hold, on export and on import.
+pub const EXPLORER_ARCHIVE_MAX_BYTES: u64 = 512 * 1024 * 1024;
+
+const LOCAL_HEADER_SIG: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIG: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIG: u32 = 0x0605_4b50;
+const LOCAL_HEADER_LEN: usize = 30;
+const CENTRAL_HEADER_LEN: usize = 46;
+const END_OF_CENTRAL_DIR_LEN: usize = 22;
+const ZIP_VERSION: u16 = 20;
+const FLAG_ENCRYPTED: u16 = 0x0001;
+/// General purpose flag marking UTF-8 entry names.
+const FLAG_UTF8: u16 = 0x0800;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+/// MS-DOS attribute set on folder entries.
+const DOS_DIRECTORY_ATTR: u32 = 0x10;
+/// Metadata folder macOS adds to archives it creates; never extracted.
+const MACOS_METADATA_DIR: &str = "__MACOSX";
+/// Bytes of small files an import gathers before writing them in one batch.
+const IMPORT_BATCH_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// What happens to archive entries whose target already exists.
+pub enum ExplorerArchiveConflict {
+    /// Extracts a clashing top-level file or folder under a numbered name such as `Docs (2)`.
+    #[default]
+    KeepBoth,
+    /// Overwrites existing files and merges into existing folders.
+    Replace,
+    /// Leaves existing files alone and merges into existing folders.
+    Skip,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Outcome of extracting an archive.
+pub struct ExplorerArchiveImport {
+    /// Top-level paths the archive was extracted to, in archive order.
+    pub roots: Vec<String>,
+    /// Number of files written.
+    pub files: usize,
+    /// Existing files left untouched under [`ExplorerArchiveConflict::Skip`].
+    pub skipped: Vec<String>,
+}
+
+fn child_path(parent: &str, name: &str) -> String {
+    match parent {
+        "/" => format!("/{name}"),
+        parent => format!("{parent}/{name}"),
+    }
+}
+
+fn parent_path(path: &str) -> &str {
+    match path.rsplit_once('/') {
+        Some(("", _)) | None => "/",
+        Some((parent, _)) => parent,
+    }
+}
+
+fn corrupt() -> String {
+    "archive is truncated or corrupt".to_string()
+}
+
+fn too_large() -> String {
+    "archive is too large for the ZIP format without ZIP64".to_string()
+}
+
+/// Converts a length to a 32-bit ZIP field, keeping clear of the all-ones ZIP64 marker.
+fn zip_u32(value: usize) -> Result<u32, String> {
+    u32::try_from(value)
+        .ok()
+        .filter(|value| *value != u32::MAX)
+        .ok_or_else(too_large)
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Adds a length to an archive offset; a sum past `usize::MAX` (32 bits on wasm) is corrupt.
+fn offset(at: usize, len: usize) -> Result<usize, String> {
+    at.checked_add(len).ok_or_else(corrupt)
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Result<u16, String> {
+    bytes
+        .get(at..offset(at, 2)?)
+        .map(|field| u16::from_le_bytes([field[0], field[1]]))
+        .ok_or_else(corrupt)
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Result<u32, String> {
+    bytes
+        .get(at..offset(at, 4)?)
+        .map(|field| u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+        .ok_or_else(corrupt)
+}
+
+/// Converts unix milliseconds to an MS-DOS `(time, date)` pair in UTC.
+///
+/// Times before 1980, which the format cannot hold, become 1980-01-01.
+fn dos_timestamp(unix_ms: Option<u64>) -> (u16, u16) {
+    let secs = unix_ms.unwrap_or(0) / 1000;
+    let seconds_of_day = secs % 86_400;
+    // Civil date from days since the epoch, after Howard Hinnant's `civil_from_days`.
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    if year < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+    let time = ((seconds_of_day / 3600) << 11)
+        | ((seconds_of_day % 3600 / 60) << 5)
+        | (seconds_of_day % 60 / 2);
+    let date = ((year.min(2107) - 1980) << 9) | (month << 5) | day;
+    (time as u16, date as u16)
+}
+
+/// Builds an archive in memory, one entry at a time.
+#[derive(Default)]
+struct ZipWriter {
+    out: Vec<u8>,
+    central: Vec<u8>,
+    entries: usize,
+}
+
+impl ZipWriter {
+    fn add_dir(&mut self, name: &str, modified_at_unix_ms: Option<u64>) -> Result<(), String> {
+        self.add(
+            name,
+            METHOD_STORED,
+            &[],
+            0,
+            0,
+            modified_at_unix_ms,
+            DOS_DIRECTORY_ATTR,
+        )
+    }
+
+    fn add_file(
+        &mut self,
+        name: &str,
+        file: PackedFile,
+        modified_at_unix_ms: Option<u64>,
+    ) -> Result<(), String> {
+        self.add(
+            name,
+            file.method,
+            &file.data,
+            file.crc,
+            file.size,
+            modified_at_unix_ms,
+            0,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add(
+        &mut self,
+        name: &str,
+        method: u16,
+        stored: &[u8],
+        crc: u32,
+        size: usize,
+        modified_at_unix_ms: Option<u64>,
+        external_attrs: u32,
+    ) -> Result<(), String> {
+        let name_len = u16::try_from(name.len()).map_err(|_| format!("name too long: {name}"))?;
+        let offset = zip_u32(self.out.len())?;
+        let compressed = zip_u32(stored.len())?;
+        let size = zip_u32(size)?;
+        let (time, date) = dos_timestamp(modified_at_unix_ms);
+
+        let out = &mut self.out;
+        put_u32(out, LOCAL_HEADER_SIG);
+        for field in [ZIP_VERSION, FLAG_UTF8, method, time, date] {
+            put_u16(out, field);
+        }
+        for field in [crc, compressed, size] {
+            put_u32(out, field);
+        }
+        put_u16(out, name_len);
+        put_u16(out, 0);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(stored);
+
+        let central = &mut self.central;
+        put_u32(central, CENTRAL_HEADER_SIG);
+        for field in [ZIP_VERSION, ZIP_VERSION, FLAG_UTF8, method, time, date] {
+            put_u16(central, field);
+        }
+        for field in [crc, compressed, size] {
+            put_u32(central, field);
+        }
+        // Name, extra field, and comment lengths, then the disk number and internal attributes.
+        for field in [name_len, 0, 0, 0, 0] {
+            put_u16(central, field);
+        }
+        put_u32(central, external_attrs);
+        put_u32(central, offset);
+        central.extend_from_slice(name.as_bytes());
+        self.entries += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<u8>, String> {
+        let count = u16::try_from(self.entries)
+            .ok()
+            .filter(|count| *count != u16::MAX)
+            .ok_or_else(too_large)?;
+        let dir_offset = zip_u32(self.out.len())?;
+        let dir_size = zip_u32(self.central.len())?;
+        self.out.append(&mut self.central);
+        let out = &mut self.out;
+        put_u32(out, END_OF_CENTRAL_DIR_SIG);
+        for field in [0, 0, count, count] {
+            put_u16(out, field);
+        }
+        put_u32(out, dir_size);
+        put_u32(out, dir_offset);
+        put_u16(out, 0);
+        Ok(self.out)
+    }
+}
+
+/// A file's data as it goes into an archive.
+struct PackedFile {
+    method: u16,
+    data: Vec<u8>,
+    crc: u32,
+    /// Uncompressed size.
+    size: usize,
+}
+
+impl PackedFile {
+    fn stored(data: Vec<u8>) -> Self {
+        Self {
+            method: METHOD_STORED,
+            crc: crc32fast::hash(&data),
+            size: data.len(),
+            data,
+        }
+    }
+}
+
+/// Deflates a file while streaming it, so only the compressed form is held.
+///
+/// Returns `None` when deflating does not make the file smaller and it should be stored instead.
+async fn deflate_file<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    path: &str,
+) -> Result<Option<PackedFile>, String> {
+    let compress_err = |err: std::io::Error| format!("failed to compress {path}: {err}");
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    let mut crc = crc32fast::Hasher::new();
+    let mut size = 0;
+    let mut stream = fs.read_stream(path);
+    while let Some(chunk) = stream.next_chunk().await {
+        let chunk = chunk?;
+        crc.update(&chunk);
+        size += chunk.len();
+        encoder.write_all(&chunk).map_err(compress_err)?;
+    }
+    let data = encoder.finish().map_err(compress_err)?;
+    Ok((data.len() < size).then(|| PackedFile {
+        method: METHOD_DEFLATE,
+        data,
+        crc: crc.finalize(),
+        size,
+    }))
+}
+
+/// One archive entry located through the central directory.
+#[derive(Debug)]
+struct ZipEntry {
+    name: String,
+    /// Path segments below the extraction folder.
+    segments: Vec<String>,
+    directory: bool,
+    method: u16,
+    crc: u32,
+    data_start: usize,
+    compressed: usize,
+    size: usize,
+}
+
+/// Splits an entry name into path segments, rejecting names that climb out of the target folder.
+fn entry_segments(name: &str) -> Result<Vec<String>, String> {
+    let segments = name
+        .split(['/', '\\'])
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if segments.iter().any(|segment| segment == "..") {
+        return Err(format!("archive entry {name} points outside the folder"));
+    }
+    Ok(segments)
+}
+
+/// Reads the central directory, skipping macOS metadata and entries without a usable name.
+fn read_entries(bytes: &[u8]) -> Result<Vec<ZipEntry>, String> {
+    let not_zip = || "not a ZIP archive".to_string();
+    let last = bytes
+        .len()
+        .checked_sub(END_OF_CENTRAL_DIR_LEN)
+        .ok_or_else(not_zip)?;
+    // The end record sits at the very end, followed by a comment of at most 64 KiB.
+    let end = (last.saturating_sub(usize::from(u16::MAX))..=last)
+        .rev()
+        .find(|at| read_u32(bytes, *at) == Ok(END_OF_CENTRAL_DIR_SIG))
+        .ok_or_else(not_zip)?;
+    let count = read_u16(bytes, offset(end, 10)?)?;
+    let dir_offset = read_u32(bytes, offset(end, 16)?)?;
+    if count == u16::MAX || dir_offset == u32::MAX {
+        return Err("ZIP64 archives are not supported".to_string());
+    }
+
+    let mut entries = Vec::new();
+    let mut at = dir_offset as usize;
+    for _ in 0..count {
+        if read_u32(bytes, at)? != CENTRAL_HEADER_SIG {
+            return Err(corrupt());
+        }
+        let flags = read_u16(bytes, offset(at, 8)?)?;
+        let method = read_u16(bytes, offset(at, 10)?)?;
+        let crc = read_u32(bytes, offset(at, 16)?)?;
+        let compressed = read_u32(bytes, offset(at, 20)?)?;
+        let size = read_u32(bytes, offset(at, 24)?)?;
+        let name_len = usize::from(read_u16(bytes, offset(at, 28)?)?);
+        let extra_len = usize::from(read_u16(bytes, offset(at, 30)?)?);
+        let comment_len = usize::from(read_u16(bytes, offset(at, 32)?)?);
+        let local = read_u32(bytes, offset(at, 42)?)? as usize;
+        let name_start = offset(at, CENTRAL_HEADER_LEN)?;
+        let name_end = offset(name_start, name_len)?;
+        let name = bytes
+            .get(name_start..name_end)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .ok_or_else(corrupt)?;
+        at = offset(offset(name_end, extra_len)?, comment_len)?;
+
+        if flags & FLAG_ENCRYPTED != 0 {
+            return Err(format!("{name} is encrypted"));
+        }
+        if compressed == u32::MAX || size == u32::MAX || local == u32::MAX as usize {
+            return Err("ZIP64 archives are not supported".to_string());
+        }
+        let segments = entry_segments(&name)?;
+        if segments.is_empty() || segments[0] == MACOS_METADATA_DIR {
+            continue;
+        }
+        if read_u32(bytes, local)? != LOCAL_HEADER_SIG {
+            return Err(corrupt());
+        }
+        // The local header repeats the name and may carry a different extra field.
+        let data_start = offset(
+            offset(
+                offset(local, LOCAL_HEADER_LEN)?,
+                usize::from(read_u16(bytes, offset(local, 26)?)?),
+            )?,
+            usize::from(read_u16(bytes, offset(local, 28)?)?),
+        )?;
+        if offset(data_start, compressed as usize)? > bytes.len() {
+            return Err(corrupt());
+        }
+        entries.push(ZipEntry {
+            directory: name.ends_with(['/', '\\']),
+            name,
+            segments,
+            method,
+            crc,
+            data_start,
+            compressed: compressed as usize,
+            size: size as usize,
+        });
+    }
+    Ok(entries)
+}
+
+/// Reads one entry's data, decompressing it on the way; reading stops just past the recorded size.
+fn entry_reader<'b>(bytes: &'b [u8], entry: &ZipEntry) -> Result<Box<dyn Read + 'b>, String> {
+    let raw = bytes
+        .get(entry.data_start..offset(entry.data_start, entry.compressed)?)
+        .ok_or_else(corrupt)?;
+    let reader: Box<dyn Read> = match entry.method {
+        METHOD_STORED => Box::new(raw),
+        METHOD_DEFLATE => Box::new(DeflateDecoder::new(raw)),
+        method => {
+            return Err(format!(
+                "{} uses unsupported compression method {method}",
+                entry.name
+            ))
+        }
+    };
+    Ok(Box::new(reader.take(entry.size as u64 + 1)))
+}
+
+/// Reads the next chunk of entry data; an empty chunk means the data has ended.
+fn next_entry_chunk(reader: &mut dyn Read, entry: &ZipEntry) -> Result<Vec<u8>, String> {
+    let mut chunk = Vec::new();
+    reader
+        .take(EXPLORER_STREAM_CHUNK_BYTES as u64)
+        .read_to_end(&mut chunk)
+        .map_err(|err| format!("{} is corrupt: {err}", entry.name))?;
+    Ok(chunk)
+}
+
+/// Checks one entry against its recorded size and CRC, decompressing it a chunk at a time.
+fn check_entry_data(bytes: &[u8], entry: &ZipEntry) -> Result<(), String> {
+    let mut reader = entry_reader(bytes, entry)?;
+    let mut crc = crc32fast::Hasher::new();
+    let mut size = 0;
+    loop {
+        let chunk = next_entry_chunk(&mut reader, entry)?;
+        if chunk.is_empty() {
+            break;
+        }
+        crc.update(&chunk);
+        size += chunk.len();
+    }
+    if size != entry.size || crc.finalize() != entry.crc {
+        return Err(format!("{} is corrupt", entry.name));
+    }
+    Ok(())
+}
+
+/// Reads one small entry whole, checking it against its recorded size and CRC.
+fn read_small_entry(bytes: &[u8], entry: &ZipEntry) -> Result<Vec<u8>, String> {
+    let mut data = Vec::with_capacity(entry.size);
+    entry_reader(bytes, entry)?
+        .read_to_end(&mut data)
+        .map_err(|err| format!("{} is corrupt: {err}", entry.name))?;
+    if data.len() != entry.size || crc32fast::hash(&data) != entry.crc {
+        return Err(format!("{} is corrupt", entry.name));
+    }
+    Ok(data)
+}
+
+/// Small files waiting to be written together in one [`ExplorerFsService::batch`] call.
+#[derive(Default)]
+struct PendingWrites {
+    ops: Vec<ExplorerBatchOp>,
+    bytes: usize,
+}
+
+impl PendingWrites {
+    fn push(&mut self, path: String, bytes: Vec<u8>) {
+        self.bytes += bytes.len();
+        self.ops.push(ExplorerBatchOp::WriteBytes { path, bytes });
+    }
+
+    /// Writes the pending files and returns how many were written.
+    async fn flush<F: ExplorerFsService + ?Sized>(&mut self, fs: &F) -> Result<usize, String> {
+        let ops = std::mem::take(&mut self.ops);
+        self.bytes = 0;
+        if ops.is_empty() {
+            return Ok(0);
+        }
+        for (op, result) in ops.iter().zip(fs.batch(&ops).await?) {
+            if let Some(err) = result.error {
+                return Err(format!("{}: {err}", op.changed_paths()[0]));
+            }
+        }
+        Ok(ops.len())
+    }
+}
+
+/// Checks one entry and then streams its data into the file at `path`.
+///
+/// The entry is decompressed twice, once to check it and once to write it, so a damaged entry
+/// never leaves a partial file behind and no file is held whole.
+async fn extract_entry<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    bytes: &[u8],
+    entry: &ZipEntry,
+    path: &str,
+) -> Result<(), String> {
+    check_entry_data(bytes, entry)?;
+    let mut reader = entry_reader(bytes, entry)?;
+    let mut stream = fs.write_stream(path);
+    loop {
+        let chunk = next_entry_chunk(&mut reader, entry)?;
+        if chunk.is_empty() {
+            break;
+        }
+        stream
+            .write_chunk(&chunk)
+            .await
+            .map_err(|err| format!("{path}: {err}"))?;
+    }
+    stream
+        .finish()
+        .await
+        .map_err(|err| format!("{path}: {err}"))?;
+    Ok(())
+}
+
+/// Names of the top-level files and folders in a ZIP archive, in archive order.
+pub fn archive_top_level_names(bytes: &[u8]) -> Result<Vec<String>, String> {
+    let mut names = Vec::<String>::new();
+    for entry in read_entries(bytes)? {
+        if !names.contains(&entry.segments[0]) {
+            names.push(entry.segments[0].clone());
+        }
+    }
+    Ok(names)
+}
+
+/// Packs `path` into a ZIP archive: a folder with everything below it, or a single file.
+///
+/// Entry names start with the folder's own name, so extracting the archive recreates the folder.
+/// The trash is never included. `on_progress` receives the number of files packed so far and the
+/// total.
+pub async fn export_zip_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    path: &str,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<Vec<u8>, String> {
+    let root = normalize_virtual_path(path);
+    let meta = fs.stat(&root).await?;
+    let mut writer = ZipWriter::default();
+    let mut files = Vec::new();
+    if meta.kind == ExplorerEntryKind::File {
+        files.push((meta.name, root, meta.modified_at_unix_ms, meta.size));
+    } else {
+        let prefix = if root == "/" {
+            String::new()
+        } else {
+            let prefix = format!("{}/", meta.name);
+            writer.add_dir(&prefix, meta.modified_at_unix_ms)?;
+            prefix
+        };
+        let mut pending = vec![(root, prefix)];
+        while let Some((folder, prefix)) = pending.pop() {
+            for entry in fs.list_dir(&folder).await?.entries {
+                if is_trash_path(&entry.path) {
+                    continue;
+                }
+                let name = format!("{prefix}{}", entry.name);
+                match entry.kind {
+                    ExplorerEntryKind::File => {
+                        files.push((name, entry.path, entry.modified_at_unix_ms, entry.size));
+                    }
+                    ExplorerEntryKind::Directory => {
+                        let name = format!("{name}/");
+                        writer.add_dir(&name, entry.modified_at_unix_ms)?;
+                        pending.push((entry.path, name));
+                    }
+                }
+            }
+        }
+    }
+
+    let total_bytes = files.iter().map(|file| file.3.unwrap_or(0)).sum::<u64>();
+    if total_bytes > EXPLORER_ARCHIVE_MAX_BYTES {
+        return Err(format!(
+            "{total_bytes} bytes is over the {EXPLORER_ARCHIVE_MAX_BYTES} byte archive limit"
+        ));
+    }
+    let total = files.len();
+    on_progress(0, total);
+    for (done, (name, path, modified_at_unix_ms, _)) in files.into_iter().enumerate() {
+        let file = match deflate_file(fs, &path).await? {
+            Some(file) => file,
+            None => PackedFile::stored(fs.read_stream(&path).read_to_end().await?),
+        };
+        writer.add_file(&name, file, modified_at_unix_ms)?;
+        on_progress(done + 1, total);
+    }
+    writer.finish()
+}
+
+/// Makes sure `folder` and its ancestors up to an already ensured folder exist.
+async fn ensure_folder<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    folder: &str,
+    ensured: &mut BTreeSet<String>,
+) -> Result<(), String> {
+    let mut missing = Vec::new();
+    let mut current = folder;
+    while !ensured.contains(current) {
+        missing.push(current.to_string());
+        current = parent_path(current);
+    }
+    for folder in missing.into_iter().rev() {
+        match fs.stat(&folder).await {
+            Ok(meta) if meta.kind == ExplorerEntryKind::Directory => {}
+            Ok(_) => return Err(format!("{folder} is a file, not a folder")),
+            Err(_) => {
+                fs.create_dir(&folder).await?;
+            }
+        }
+        ensured.insert(folder);
+    }
+    Ok(())
+}
+
+/// Extracts a ZIP archive into the folder `dest`.
+///
+/// Every entry is checked before anything is written, so an archive with unsafe names is
+/// rejected as a whole. `conflict` decides what happens to entries whose target exists.
+/// Small files are written in batches to spare a host round trip per file; larger ones are
+/// streamed. `on_progress` receives the number of files extracted so far and the total.
+pub async fn import_zip_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    dest: &str,
+    bytes: &[u8],
+    conflict: ExplorerArchiveConflict,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<ExplorerArchiveImport, String> {
+    let dest = normalize_virtual_path(dest);
+    let entries = read_entries(bytes)?;
+    let total_bytes = entries.iter().map(|entry| entry.size as u64).sum::<u64>();
+    if total_bytes > EXPLORER_ARCHIVE_MAX_BYTES {
+        return Err(format!(
+            "{total_bytes} bytes is over the {EXPLORER_ARCHIVE_MAX_BYTES} byte archive limit"
+        ));
+    }
+
+    // Top-level names in the archive and the names they are extracted under.
+    let mut taken = fs
+        .list_dir(&dest)
+        .await?
+        .entries
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect::<Vec<_>>();
+    let mut roots = Vec::<(String, String)>::new();
+    for entry in &entries {
+        let top = &entry.segments[0];
+        if roots.iter().any(|(name, _)| name == top) {
+            continue;
+        }
+        let target = match conflict {
+            ExplorerArchiveConflict::KeepBoth => {
+                fresh_entry_name(taken.iter().map(String::as_str), top)
+            }
+            ExplorerArchiveConflict::Replace | ExplorerArchiveConflict::Skip => top.clone(),
+        };
+        taken.push(target.clone());
+        roots.push((top.clone(), target));
+    }
+
+    let mut outcome = ExplorerArchiveImport {
+        roots: roots
+            .iter()
+            .map(|(_, target)| child_path(&dest, target))
+            .collect(),
+        ..ExplorerArchiveImport::default()
+    };
+    let mut ensured = BTreeSet::from([dest.clone()]);
+    let total = entries.iter().filter(|entry| !entry.directory).count();
+    let mut done = 0;
+    let mut pending = PendingWrites::default();
+    on_progress(done, total);
+    for entry in &entries {
+        let (_, top) = roots
+            .iter()
+            .find(|(name, _)| *name == entry.segments[0])
+            .expect("every top-level name has a target");
+        let path = entry.segments[1..]
+            .iter()
+            .fold(child_path(&dest, top), |path, segment| {
+                child_path(&path, segment)
+            });
+        if entry.directory {
+            ensure_folder(fs, &path, &mut ensured).await?;
+            continue;
+        }
+        ensure_folder(fs, parent_path(&path), &mut ensured).await?;
+        if conflict != ExplorerArchiveConflict::KeepBoth {
+            match fs.stat(&path).await {
+                Ok(meta) if meta.kind == ExplorerEntryKind::Directory => {
+                    return Err(format!("{path} is a folder, not a file"));
+                }
+                Ok(_) if conflict == ExplorerArchiveConflict::Skip => {
+                    outcome.skipped.push(path);
+                    done += 1;
+                    on_progress(done, total);
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        if entry.size <= EXPLORER_STREAM_CHUNK_BYTES {
+            pending.push(path, read_small_entry(bytes, entry)?);
+            if pending.bytes >= IMPORT_BATCH_BYTES {
+                let written = pending.flush(fs).await?;
+                outcome.files += written;
+                done += written;
+                on_progress(done, total);
+            }
+            continue;
+        }
+        extract_entry(fs, bytes, entry, &path).await?;
+        outcome.files += 1;
+        done += 1;
+        on_progress(done, total);
+    }
+    let written = pending.flush(fs).await?;
+    if written > 0 {
+        outcome.files += written;
+        done += written;
+        on_progress(done, total);
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::fs::test_support::MemoryFs;
+
+    fn text(fs: &MemoryFs, path: &str) -> String {
+        block_on(fs.read_text_file(path)).expect(path).text
+    }
+
+    #[test]
+    fn dos_timestamps_use_utc_calendar_fields() {
+        assert_eq!(dos_timestamp(None), (0, (1 << 5) | 1));
+        // 2024-02-29 12:34:56 UTC.
+        assert_eq!(
+            dos_timestamp(Some(1_709_210_096_000)),
+            ((12 << 11) | (34 << 5) | 28, (44 << 9) | (2 << 5) | 29)
+        );
+    }
+
+    #[test]
+    fn exported_folders_round_trip_through_import() {
+        let large = "compressible ".repeat(200);
+        let fs = MemoryFs::with(&[
+            ("/docs", None),
+            ("/docs/a.txt", Some("alpha")),
+            ("/docs/sub", None),
+            ("/docs/sub/large.txt", Some(large.as_str())),
+            ("/docs/empty", None),
+            ("/out", None),
+        ]);
+        let mut progress = Vec::new();
+        let archive = block_on(export_zip_with(&fs, "/docs", |done, total| {
+            progress.push((done, total))
+        }))
+        .expect("export");
+        assert_eq!(progress, [(0, 2), (1, 2), (2, 2)]);
+        assert!(archive.len() < large.len());
+        assert_eq!(archive_top_level_names(&archive).expect("names"), ["docs"]);
+
+        // Both files are small, so they are written together in one batch.
+        let mut progress = Vec::new();
+        let outcome = block_on(import_zip_with(
+            &fs,
+            "/out",
+            &archive,
+            ExplorerArchiveConflict::KeepBoth,
+            |done, total| progress.push((done, total)),
+        ))
+        .expect("import");
+        assert_eq!(progress, [(0, 2), (2, 2)]);
+        assert_eq!(outcome.roots, ["/out/docs"]);
+        assert_eq!(outcome.files, 2);
+        assert_eq!(text(&fs, "/out/docs/a.txt"), "alpha");
+        assert_eq!(text(&fs, "/out/docs/sub/large.txt"), large);
+        assert!(block_on(fs.stat("/out/docs/empty")).is_ok());
+
+        let again = block_on(import_zip_with(
+            &fs,
+            "/out",
+            &archive,
+            ExplorerArchiveConflict::KeepBoth,
+            |_, _| {},
+        ))
+        .expect("import again");
+        assert_eq!(again.roots, ["/out/docs (2)"]);
+        assert_eq!(text(&fs, "/out/docs (2)/a.txt"), "alpha");
+    }
+
+    #[test]
+    fn conflicts_replace_or_skip_existing_files() {
+        let fs = MemoryFs::with(&[("/docs", None), ("/docs/a.txt", Some("new"))]);
+        let archive = block_on(export_zip_with(&fs, "/docs/a.txt", |_, _| {})).expect("export");
+        assert_eq!(archive_top_level_names(&archive).expect("names"), ["a.txt"]);
+
+        let fs = MemoryFs::with(&[("/dest", None), ("/dest/a.txt", Some("old"))]);
+        let skipped = block_on(import_zip_with(
+            &fs,
+            "/dest",
+            &archive,
+            ExplorerArchiveConflict::Skip,
+            |_, _| {},
+        ))
+        .expect("skip");
+        assert_eq!(
+            (skipped.files, skipped.skipped),
+            (0, vec!["/dest/a.txt".into()])
+        );
+        assert_eq!(text(&fs, "/dest/a.txt"), "old");
+
+        let replaced = block_on(import_zip_with(
+            &fs,
+            "/dest",
+            &archive,
+            ExplorerArchiveConflict::Replace,
+            |_, _| {},
+        ))
+        .expect("replace");
+        assert_eq!(replaced.files, 1);
+        assert_eq!(text(&fs, "/dest/a.txt"), "new");
+    }
+
+    #[test]
+    fn unsafe_or_damaged_archives_are_rejected() {
+        let mut writer = ZipWriter::default();
+        writer
+            .add_file("../escape.txt", PackedFile::stored(b"x".to_vec()), None)
+            .expect("add");
+        let escaping = writer.finish().expect("finish");
+        let fs = MemoryFs::with(&[("/dest", None)]);
+        let err = block_on(import_zip_with(
+            &fs,
+            "/dest",
+            &escaping,
+            ExplorerArchiveConflict::KeepBoth,
+            |_, _| {},
+        ))
+        .expect_err("escape");
+        assert!(err.contains("outside"));
+        assert_eq!(fs.paths(), ["/", "/dest"]);
+
+        assert!(archive_top_level_names(b"plain text").is_err());
+        let mut writer = ZipWriter::default();
+        writer
+            .add_file("a.txt", PackedFile::stored(b"stored".to_vec()), None)
+            .expect("add");
+        let mut damaged = writer.finish().expect("finish");
+        damaged[LOCAL_HEADER_LEN + "a.txt".len()] ^= 0xff;
+        let err = block_on(import_zip_with(
+            &fs,
+            "/dest",
+            &damaged,
+            ExplorerArchiveConflict::KeepBoth,
+            |_, _| {},
+        ))
+        .expect_err("crc");
+        assert!(err.contains("corrupt"));
+    }
+
+    #[test]
+    fn forged_sizes_and_offsets_are_corrupt_instead_of_panicking() {
+        assert_eq!(read_u16(b"zip", usize::MAX - 1), Err(corrupt()));
+        assert_eq!(read_u32(b"zip", usize::MAX - 2), Err(corrupt()));
+
+        let mut writer = ZipWriter::default();
+        writer
+            .add_file("a.txt", PackedFile::stored(b"stored".to_vec()), None)
+            .expect("add");
+        let mut forged = writer.finish().expect("finish");
+        let end = forged.len() - END_OF_CENTRAL_DIR_LEN;
+        let central = read_u32(&forged, end + 16).expect("directory offset") as usize;
+        // A compressed size just below the ZIP64 marker wraps a 32-bit `usize` sum.
+        forged[central + 20..central + 24].copy_from_slice(&(u32::MAX - 1).to_le_bytes());
+        assert_eq!(archive_top_level_names(&forged), Err(corrupt()));
+        let fs = MemoryFs::with(&[("/dest", None)]);
+        let err = block_on(import_zip_with(
+            &fs,
+            "/dest",
+            &forged,
+            ExplorerArchiveConflict::KeepBoth,
+            |_, _| {},
+        ))
+        .expect_err("forged size");
+        assert_eq!(err, corrupt());
+        assert_eq!(fs.paths(), ["/", "/dest"]);
+    }
+}
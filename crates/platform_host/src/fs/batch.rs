@@ -0,0 +1,73 @@
+//! Batched explorer changes applied one operation at a time.
+//!
+//! Backends that can group changes, like the IndexedDB store, apply a batch in one transaction.
+//! The rest run [`apply_batch_with`], which still spares callers a host round trip per operation
+//! when it runs on the host side of the bridge.
+
+use super::service::ExplorerFsService;
+use super::types::{ExplorerBatchOp, ExplorerBatchResult};
+
+/// Applies `ops` in order through the individual service calls. A failed operation is reported in
+/// its result and the remaining operations still run.
+pub async fn apply_batch_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    ops: &[ExplorerBatchOp],
+) -> Vec<ExplorerBatchResult> {
+    let mut results = Vec::with_capacity(ops.len());
+    for op in ops {
+        let result = match op {
+            ExplorerBatchOp::CreateDir { path } => fs.create_dir(path).await.map(Some),
+            ExplorerBatchOp::WriteText { path, text } => {
+                fs.write_text_file(path, text).await.map(Some)
+            }
+            ExplorerBatchOp::WriteBytes { path, bytes } => {
+                fs.write_file_bytes(path, bytes).await.map(Some)
+            }
+            ExplorerBatchOp::Delete { path, recursive } => {
+                fs.delete(path, *recursive).await.map(|()| None)
+            }
+            ExplorerBatchOp::Rename { from, to } => fs.rename(from, to).await.map(Some),
+        };
+        results.push(result.into());
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::fs::test_support::MemoryFs;
+
+    #[test]
+    fn batches_report_each_operation_and_keep_going_after_failures() {
+        let fs = MemoryFs::with(&[("/old.txt", Some("old"))]);
+        let ops = [
+            ExplorerBatchOp::CreateDir {
+                path: "/docs".to_string(),
+            },
+            ExplorerBatchOp::WriteBytes {
+                path: "/docs/a.txt".to_string(),
+                bytes: b"alpha".to_vec(),
+            },
+            ExplorerBatchOp::Rename {
+                from: "/missing.txt".to_string(),
+                to: "/b.txt".to_string(),
+            },
+            ExplorerBatchOp::Delete {
+                path: "/old.txt".to_string(),
+                recursive: false,
+            },
+        ];
+        let results = block_on(apply_batch_with(&fs, &ops));
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[1].metadata.as_ref().map(|meta| meta.size),
+            Some(Some(5))
+        );
+        assert!(results[2].error.is_some());
+        assert_eq!(results[3], ExplorerBatchResult::default());
+        assert_eq!(fs.paths(), ["/", "/docs", "/docs/a.txt"]);
+    }
+}
@@ -0,0 +1,129 @@
+//! MIME types for explorer files, from file names and from leading content bytes.
+//!
+//! Names are judged by extension alone. Content is recognized by the signatures common image,
+//! audio, video, document, and archive formats start with, so a file with a missing or wrong
+//! extension can still be previewed or downloaded with the right type.
+
+/// Type reported for files that match neither a known extension nor a known signature.
+pub const EXPLORER_DEFAULT_MIME: &str = "application/octet-stream";
+
+/// MIME type for a file name or path, judged by its extension.
+pub fn mime_from_name(name: &str) -> Option<&'static str> {
+    let name = name.rsplit('/').next().unwrap_or(name);
+    let (_, extension) = name.rsplit_once('.').filter(|(stem, _)| !stem.is_empty())?;
+    let mime = match extension.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "svg" => "image/svg+xml",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" | "oga" | "opus" => "audio/ogg",
+        "m4a" => "audio/mp4",
+        "flac" => "audio/flac",
+        "mp4" | "m4v" => "video/mp4",
+        "webm" => "video/webm",
+        "ogv" => "video/ogg",
+        "mov" => "video/quicktime",
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "toml" => "application/toml",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "wasm" => "application/wasm",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// MIME type recognized from the signature at the start of a file's bytes.
+pub fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+        (b"ID3", "audio/mpeg"),
+        (b"fLaC", "audio/flac"),
+        (b"OggS", "audio/ogg"),
+        (b"\x1a\x45\xdf\xa3", "video/webm"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"PK\x05\x06", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"\x00asm", "application/wasm"),
+    ];
+    if let Some((_, mime)) = SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+    {
+        return Some(mime);
+    }
+    // RIFF containers name their format at byte 8, and ISO media name a brand at byte 8.
+    match (bytes.get(..4), bytes.get(4..8), bytes.get(8..12)) {
+        (Some(b"RIFF"), _, Some(b"WEBP")) => Some("image/webp"),
+        (Some(b"RIFF"), _, Some(b"WAVE")) => Some("audio/wav"),
+        (_, Some(b"ftyp"), Some(brand)) => Some(match brand {
+            b"avif" | b"avis" => "image/avif",
+            b"M4A " => "audio/mp4",
+            b"qt  " => "video/quicktime",
+            _ => "video/mp4",
+        }),
+        // An MPEG audio frame header: eleven set sync bits, then a layer other than reserved.
+        _ if bytes.len() >= 2 && bytes[0] == 0xff && bytes[1] & 0xe0 == 0xe0 => {
+            (bytes[1] & 0x06 != 0).then_some("audio/mpeg")
+        }
+        _ => None,
+    }
+}
+
+/// MIME type of a file, preferring its content signature over its name.
+pub fn explorer_mime_type(name: &str, leading_bytes: &[u8]) -> &'static str {
+    sniff_mime(leading_bytes)
+        .or_else(|| mime_from_name(name))
+        .unwrap_or(EXPLORER_DEFAULT_MIME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_judged_by_extension() {
+        assert_eq!(mime_from_name("/photos/Cat.JPG"), Some("image/jpeg"));
+        assert_eq!(mime_from_name("notes.md"), Some("text/markdown"));
+        assert_eq!(mime_from_name("/photos/.png"), None);
+        assert_eq!(mime_from_name("/photos.png/notes"), None);
+    }
+
+    #[test]
+    fn content_signatures_win_over_names() {
+        assert_eq!(sniff_mime(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+        assert_eq!(
+            sniff_mime(b"RIFF\x10\x00\x00\x00WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(sniff_mime(b"\x00\x00\x00\x18ftypisom"), Some("video/mp4"));
+        assert_eq!(sniff_mime(b"\xff\xfb\x90\x00"), Some("audio/mpeg"));
+        assert_eq!(sniff_mime(b"plain text"), None);
+
+        assert_eq!(explorer_mime_type("photo.txt", b"GIF89a.."), "image/gif");
+        assert_eq!(explorer_mime_type("photo.txt", b"hello"), "text/plain");
+        assert_eq!(
+            explorer_mime_type("blob", b"\x01\x02"),
+            EXPLORER_DEFAULT_MIME
+        );
+    }
+}
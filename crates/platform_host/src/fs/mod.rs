@@ -1,5 +1,14 @@
 //! Filesystem and explorer domain types and path utilities.
 
+pub mod archive;
+pub mod batch;
+pub mod mime;
 pub mod path;
+pub mod properties;
 pub mod service;
+pub mod stream;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod trash;
 pub mod types;
+pub mod watch;
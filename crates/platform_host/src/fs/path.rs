@@ -1,40 +1,44 @@
-//! Virtual-path normalization helpers shared across host abstractions.
+//! Virtual-path normalization and naming helpers shared across host abstractions.
 
 /// Normalizes a virtual filesystem path using Explorer UI semantics.
 ///
 /// This helper trims whitespace, converts backslashes to `/`, resolves `.`/`..`, ensures a
-/// leading slash, and returns `/` for empty or fully-collapsed paths.
+/// leading slash, and returns `/` for empty or fully-collapsed paths. It is
+/// [`platform_paths::normalize_path`], kept here for host code that only depends on this crate.
 pub fn normalize_virtual_path(path: &str) -> String {
-    let trimmed = path.trim();
-    if trimmed.is_empty() {
-        return "/".to_string();
-    }
-
-    let mut out = String::new();
-    for segment in trimmed.replace('\\', "/").split('/') {
-        if segment.is_empty() || segment == "." {
-            continue;
-        }
-        if segment == ".." {
-            if let Some(idx) = out.rfind('/') {
-                out.truncate(idx);
-            }
-            continue;
-        }
-        out.push('/');
-        out.push_str(segment);
-    }
+    platform_paths::normalize_path(path)
+}
 
-    if out.is_empty() {
-        "/".to_string()
-    } else {
-        out
+/// Returns `base` or, when a sibling already uses it, the first free `stem (n).ext` variant.
+///
+/// Names are compared case-insensitively so the result is also free on case-insensitive native
+/// folders.
+pub fn fresh_entry_name<'a>(
+    siblings: impl IntoIterator<Item = &'a str> + Clone,
+    base: &str,
+) -> String {
+    let taken = |candidate: &str| {
+        siblings
+            .clone()
+            .into_iter()
+            .any(|name| name.eq_ignore_ascii_case(candidate))
+    };
+    if !taken(base) {
+        return base.to_string();
     }
+    let (stem, extension) = match base.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+        _ => (base, String::new()),
+    };
+    (2..)
+        .map(|index| format!("{stem} ({index}){extension}"))
+        .find(|candidate| !taken(candidate))
+        .expect("some numbered name is free")
 }
 
 #[cfg(test)]
 mod tests {
-    use super::normalize_virtual_path;
+    use super::{fresh_entry_name, normalize_virtual_path};
 
     #[test]
     fn normalize_virtual_path_matches_expected_cases() {
@@ -52,4 +56,18 @@ mod tests {
             assert_eq!(normalize_virtual_path(input), expected, "input={input:?}");
         }
     }
+
+    #[test]
+    fn fresh_names_number_past_existing_siblings() {
+        assert_eq!(fresh_entry_name(["notes.txt"], "New Folder"), "New Folder");
+        assert_eq!(
+            fresh_entry_name(["new folder", "New Folder (2)"], "New Folder"),
+            "New Folder (3)"
+        );
+        assert_eq!(
+            fresh_entry_name(["New File.txt"], "New File.txt"),
+            "New File (2).txt"
+        );
+        assert_eq!(fresh_entry_name([".env"], ".env"), ".env (2)");
+    }
 }
@@ -0,0 +1,177 @@
+//! File and folder properties computed on top of [`ExplorerFsService`].
+//!
+//! Folder sizes come from walking directory listings and checksums from chunked reads, so every
+//! backend supports them without loading whole trees or files into memory at once.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::path::normalize_virtual_path;
+use super::service::ExplorerFsService;
+use super::types::ExplorerEntryKind;
+
+/// Bytes requested per read while computing a checksum.
+pub const EXPLORER_CHECKSUM_CHUNK_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Totals for everything below a folder.
+pub struct ExplorerFolderSize {
+    /// Sum of the file sizes in bytes.
+    pub bytes: u64,
+    /// Number of files.
+    pub files: u64,
+    /// Number of subfolders.
+    pub folders: u64,
+}
+
+/// Adds up the files and folders below `path`, listing one folder at a time.
+pub async fn folder_size_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    path: &str,
+) -> Result<ExplorerFolderSize, String> {
+    let mut totals = ExplorerFolderSize::default();
+    let mut pending = vec![normalize_virtual_path(path)];
+    while let Some(folder) = pending.pop() {
+        for entry in fs.list_dir(&folder).await?.entries {
+            match entry.kind {
+                ExplorerEntryKind::File => {
+                    totals.files += 1;
+                    totals.bytes += entry.size.unwrap_or(0);
+                }
+                ExplorerEntryKind::Directory => {
+                    totals.folders += 1;
+                    pending.push(entry.path);
+                }
+            }
+        }
+    }
+    Ok(totals)
+}
+
+/// Totals for each folder directly inside the root, largest first, for a storage breakdown.
+pub async fn top_level_folder_sizes_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+) -> Result<Vec<(String, ExplorerFolderSize)>, String> {
+    let mut sizes = Vec::new();
+    for entry in fs.list_dir("/").await?.entries {
+        if entry.kind == ExplorerEntryKind::Directory {
+            let size = folder_size_with(fs, &entry.path).await?;
+            sizes.push((entry.path, size));
+        }
+    }
+    sizes.sort_by(|(left_path, left), (right_path, right)| {
+        right
+            .bytes
+            .cmp(&left.bytes)
+            .then_with(|| left_path.cmp(right_path))
+    });
+    Ok(sizes)
+}
+
+/// Computes the SHA-256 digest of a file as lowercase hex, reading it in bounded chunks.
+///
+/// `on_progress` receives the number of bytes hashed so far and the file size after each chunk.
+pub async fn sha256_file_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    path: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    let mut stream = fs
+        .read_stream(path)
+        .with_chunk_len(EXPLORER_CHECKSUM_CHUNK_BYTES);
+    while let Some(chunk) = stream.next_chunk().await {
+        hasher.update(chunk?);
+        on_progress(stream.position(), stream.size().unwrap_or_default());
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::fs::test_support::MemoryFs;
+
+    #[test]
+    fn folder_size_counts_nested_files_and_folders() {
+        let fs = MemoryFs::with(&[
+            ("/docs", None),
+            ("/docs/a.txt", Some("alpha")),
+            ("/docs/sub", None),
+            ("/docs/sub/b.txt", Some("beta")),
+            ("/docs/sub/empty", None),
+            ("/other.txt", Some("ignored")),
+        ]);
+        assert_eq!(
+            block_on(folder_size_with(&fs, "/docs/")).expect("size"),
+            ExplorerFolderSize {
+                bytes: 9,
+                files: 2,
+                folders: 2,
+            }
+        );
+        assert!(block_on(folder_size_with(&fs, "/missing")).is_err());
+    }
+
+    #[test]
+    fn top_level_folders_are_sized_largest_first() {
+        let fs = MemoryFs::with(&[
+            ("/docs", None),
+            ("/docs/a.txt", Some("alpha")),
+            ("/empty", None),
+            ("/media", None),
+            ("/media/sub", None),
+            ("/media/sub/b.txt", Some("a longer file")),
+            ("/loose.txt", Some("not in a folder")),
+        ]);
+        let sizes = block_on(top_level_folder_sizes_with(&fs)).expect("sizes");
+        assert_eq!(
+            sizes
+                .iter()
+                .map(|(path, size)| (path.as_str(), size.bytes))
+                .collect::<Vec<_>>(),
+            [("/media", 13), ("/docs", 5), ("/empty", 0)]
+        );
+        assert_eq!(sizes[0].1.folders, 1);
+    }
+
+    #[test]
+    fn sha256_streams_the_file_in_chunks() {
+        let large = "x".repeat(EXPLORER_CHECKSUM_CHUNK_BYTES + 10);
+        let fs = MemoryFs::with(&[
+            ("/abc.txt", Some("abc")),
+            ("/empty.txt", Some("")),
+            ("/large.txt", Some(large.as_str())),
+        ]);
+
+        assert_eq!(
+            block_on(sha256_file_with(&fs, "/abc.txt", |_, _| {})).expect("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            block_on(sha256_file_with(&fs, "/empty.txt", |_, _| {})).expect("empty"),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let mut progress = Vec::new();
+        let digest = block_on(sha256_file_with(&fs, "/large.txt", |done, total| {
+            progress.push((done, total))
+        }))
+        .expect("large");
+        assert_eq!(digest, format!("{:x}", Sha256::digest(large.as_bytes())));
+        let total = large.len() as u64;
+        assert_eq!(
+            progress,
+            [
+                (EXPLORER_CHECKSUM_CHUNK_BYTES as u64, total),
+                (total, total)
+            ]
+        );
+    }
+}
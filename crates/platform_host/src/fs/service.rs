@@ -2,10 +2,14 @@
 
 use std::{future::Future, pin::Pin};
 
+use super::stream::{ExplorerReadStream, ExplorerWriteStream};
 use super::types::{
-    ExplorerBackend, ExplorerBackendStatus, ExplorerFileReadResult, ExplorerListResult,
-    ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState,
+    ExplorerBackend, ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult,
+    ExplorerFileBytesResult, ExplorerFileChunk, ExplorerFileReadResult, ExplorerImportFile,
+    ExplorerListPage, ExplorerListResult, ExplorerMetadata, ExplorerPermissionMode,
+    ExplorerPermissionState, ExplorerStorageUsage,
 };
+use super::watch::{ExplorerChangeCallback, ExplorerWatch};
 
 /// Object-safe boxed future used by [`ExplorerFsService`] async methods.
 pub type ExplorerFsFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
@@ -15,11 +19,19 @@ pub trait ExplorerFsService {
     /// Returns the current explorer backend status and capability information.
     fn status<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>>;
 
-    /// Opens the native-directory picker and returns updated backend status.
+    /// Opens the native-directory picker and mounts the chosen folder read-only under
+    /// [`EXPLORER_MOUNT_DIR`](super::types::EXPLORER_MOUNT_DIR), alongside any folders already
+    /// mounted, returning updated backend status.
     fn pick_native_directory<'a>(
         &'a self,
     ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>>;
 
+    /// Removes the native folder mounted at `path` and returns updated backend status.
+    fn unmount_native_directory<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>>;
+
     /// Requests explorer permissions for the active backend.
     fn request_permission<'a>(
         &'a self,
@@ -32,12 +44,36 @@ pub trait ExplorerFsService {
         path: &'a str,
     ) -> ExplorerFsFuture<'a, Result<ExplorerListResult, String>>;
 
+    /// Lists up to `limit` entries of a directory starting at `offset`, so very large folders can
+    /// be loaded a page at a time.
+    fn list_dir_page<'a>(
+        &'a self,
+        path: &'a str,
+        offset: usize,
+        limit: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>>;
+
     /// Reads a text file using the active explorer backend.
     fn read_text_file<'a>(
         &'a self,
         path: &'a str,
     ) -> ExplorerFsFuture<'a, Result<ExplorerFileReadResult, String>>;
 
+    /// Reads a file's raw bytes using the active explorer backend, for previewing binary media.
+    fn read_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>>;
+
+    /// Reads up to `len` bytes of a file starting at `offset`, so large files can be streamed in
+    /// bounded chunks.
+    fn read_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        len: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>>;
+
     /// Writes a text file using the active explorer backend.
     fn write_text_file<'a>(
         &'a self,
@@ -45,6 +81,50 @@ pub trait ExplorerFsService {
         text: &'a str,
     ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>>;
 
+    /// Writes a file's raw bytes using the active explorer backend, replacing any existing file.
+    fn write_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>>;
+
+    /// Writes `bytes` into a file at `offset`. Offset 0 replaces the file; any other offset must
+    /// equal the current size, so chunks can only be appended in order.
+    fn write_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>>;
+
+    /// Streams a file's bytes in chunks read through [`ExplorerFsService::read_file_chunk`].
+    fn read_stream<'a>(&'a self, path: &'a str) -> ExplorerReadStream<'a> {
+        ExplorerReadStream::new(Box::new(move |offset, len| {
+            self.read_file_chunk(path, offset, len)
+        }))
+    }
+
+    /// Streams bytes into a file in chunks written through
+    /// [`ExplorerFsService::write_file_chunk`].
+    fn write_stream<'a>(&'a self, path: &'a str) -> ExplorerWriteStream<'a> {
+        ExplorerWriteStream::new(Box::new(move |offset, bytes| {
+            Box::pin(async move { self.write_file_chunk(path, offset, &bytes).await })
+        }))
+    }
+
+    /// Opens the host's file picker and returns the chosen files; an empty list means the picker
+    /// was cancelled.
+    fn pick_import_files<'a>(
+        &'a self,
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>>;
+
+    /// Saves `bytes` to the host machine as a file called `name`, as a browser download on the web.
+    fn save_download<'a>(
+        &'a self,
+        name: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<(), String>>;
+
     /// Creates a directory using the active explorer backend.
     fn create_dir<'a>(
         &'a self,
@@ -65,8 +145,36 @@ pub trait ExplorerFsService {
         recursive: bool,
     ) -> ExplorerFsFuture<'a, Result<(), String>>;
 
+    /// Renames or moves a file or directory, failing when `to` already exists.
+    fn rename<'a>(
+        &'a self,
+        from: &'a str,
+        to: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>>;
+
+    /// Applies `ops` in order and returns one result per operation; a failed operation does not
+    /// stop the rest. Backends apply the whole batch in one host call, and in one storage
+    /// transaction where they can.
+    fn batch<'a>(
+        &'a self,
+        ops: &'a [ExplorerBatchOp],
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>>;
+
     /// Retrieves metadata for a path using the active explorer backend.
     fn stat<'a>(&'a self, path: &'a str) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>>;
+
+    /// Reports the storage the active backend uses and the quota the host grants it.
+    fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>>;
+
+    /// Asks the host to keep stored files through storage pressure and returns whether storage is
+    /// now persistent. Browsers may decide without prompting the user.
+    fn request_persistent_storage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<bool, String>>;
+
+    /// Calls `on_change` whenever `path`, an entry directly inside it, or a folder containing it
+    /// changes, until the returned watch is dropped.
+    ///
+    /// Backends that cannot observe changes return [`ExplorerWatch::inactive`].
+    fn watch(&self, path: &str, on_change: ExplorerChangeCallback) -> ExplorerWatch;
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -81,6 +189,7 @@ impl NoopExplorerFsService {
             has_native_root: false,
             permission: ExplorerPermissionState::Unsupported,
             root_path_hint: None,
+            mounts: Vec::new(),
         }
     }
 
@@ -100,6 +209,13 @@ impl ExplorerFsService for NoopExplorerFsService {
         Box::pin(async { Err(Self::unsupported_error("pick_native_directory")) })
     }
 
+    fn unmount_native_directory<'a>(
+        &'a self,
+        _path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+        Box::pin(async { Err(Self::unsupported_error("unmount_native_directory")) })
+    }
+
     fn request_permission<'a>(
         &'a self,
         _mode: ExplorerPermissionMode,
@@ -114,6 +230,15 @@ impl ExplorerFsService for NoopExplorerFsService {
         Box::pin(async { Err(Self::unsupported_error("list_dir")) })
     }
 
+    fn list_dir_page<'a>(
+        &'a self,
+        _path: &'a str,
+        _offset: usize,
+        _limit: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>> {
+        Box::pin(async { Err(Self::unsupported_error("list_dir_page")) })
+    }
+
     fn read_text_file<'a>(
         &'a self,
         _path: &'a str,
@@ -121,6 +246,22 @@ impl ExplorerFsService for NoopExplorerFsService {
         Box::pin(async { Err(Self::unsupported_error("read_text_file")) })
     }
 
+    fn read_file_bytes<'a>(
+        &'a self,
+        _path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>> {
+        Box::pin(async { Err(Self::unsupported_error("read_file_bytes")) })
+    }
+
+    fn read_file_chunk<'a>(
+        &'a self,
+        _path: &'a str,
+        _offset: u64,
+        _len: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> {
+        Box::pin(async { Err(Self::unsupported_error("read_file_chunk")) })
+    }
+
     fn write_text_file<'a>(
         &'a self,
         _path: &'a str,
@@ -129,6 +270,37 @@ impl ExplorerFsService for NoopExplorerFsService {
         Box::pin(async { Err(Self::unsupported_error("write_text_file")) })
     }
 
+    fn write_file_bytes<'a>(
+        &'a self,
+        _path: &'a str,
+        _bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async { Err(Self::unsupported_error("write_file_bytes")) })
+    }
+
+    fn write_file_chunk<'a>(
+        &'a self,
+        _path: &'a str,
+        _offset: u64,
+        _bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async { Err(Self::unsupported_error("write_file_chunk")) })
+    }
+
+    fn pick_import_files<'a>(
+        &'a self,
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>> {
+        Box::pin(async { Err(Self::unsupported_error("pick_import_files")) })
+    }
+
+    fn save_download<'a>(
+        &'a self,
+        _name: &'a str,
+        _bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<(), String>> {
+        Box::pin(async { Err(Self::unsupported_error("save_download")) })
+    }
+
     fn create_dir<'a>(
         &'a self,
         _path: &'a str,
@@ -152,12 +324,39 @@ impl ExplorerFsService for NoopExplorerFsService {
         Box::pin(async { Err(Self::unsupported_error("delete")) })
     }
 
+    fn rename<'a>(
+        &'a self,
+        _from: &'a str,
+        _to: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async { Err(Self::unsupported_error("rename")) })
+    }
+
+    fn batch<'a>(
+        &'a self,
+        _ops: &'a [ExplorerBatchOp],
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>> {
+        Box::pin(async { Err(Self::unsupported_error("batch")) })
+    }
+
     fn stat<'a>(
         &'a self,
         _path: &'a str,
     ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
         Box::pin(async { Err(Self::unsupported_error("stat")) })
     }
+
+    fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>> {
+        Box::pin(async { Err(Self::unsupported_error("usage")) })
+    }
+
+    fn request_persistent_storage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<bool, String>> {
+        Box::pin(async { Err(Self::unsupported_error("request_persistent_storage")) })
+    }
+
+    fn watch(&self, _path: &str, _on_change: ExplorerChangeCallback) -> ExplorerWatch {
+        ExplorerWatch::inactive()
+    }
 }
 
 #[cfg(test)]
@@ -174,6 +373,9 @@ mod tests {
         let status = block_on(fs_obj.status()).expect("status");
         assert_eq!(status.permission, ExplorerPermissionState::Unsupported);
         assert!(!status.native_supported);
+        assert!(status.mounts.is_empty());
+        let err = block_on(fs_obj.unmount_native_directory("/mnt/a")).expect_err("unmount");
+        assert!(err.contains("unmount_native_directory"));
 
         assert_eq!(
             block_on(fs_obj.request_permission(ExplorerPermissionMode::Read)).expect("perm"),
@@ -181,5 +383,35 @@ mod tests {
         );
         let err = block_on(fs_obj.list_dir("/")).expect_err("list should fail");
         assert!(err.contains("list_dir"));
+        let err = block_on(fs_obj.list_dir_page("/", 0, 10)).expect_err("page should fail");
+        assert!(err.contains("list_dir_page"));
+        let err = block_on(fs_obj.read_file_bytes("/a.png")).expect_err("read should fail");
+        assert!(err.contains("read_file_bytes"));
+        let err = block_on(fs_obj.read_file_chunk("/a.png", 0, 16)).expect_err("chunk should fail");
+        assert!(err.contains("read_file_chunk"));
+        let err = block_on(fs_obj.rename("/a", "/b")).expect_err("rename should fail");
+        assert!(err.contains("rename"));
+        let err = block_on(fs_obj.batch(&[])).expect_err("batch should fail");
+        assert!(err.contains("batch"));
+        let err = block_on(fs_obj.write_file_bytes("/a.png", &[0, 1])).expect_err("write bytes");
+        assert!(err.contains("write_file_bytes"));
+        let err =
+            block_on(fs_obj.write_stream("/a.png").write_chunk(&[0, 1])).expect_err("write stream");
+        assert!(err.contains("write_file_chunk"));
+        let err = block_on(fs_obj.read_stream("/a.png").read_to_end()).expect_err("read stream");
+        assert!(err.contains("read_file_chunk"));
+        let err = block_on(fs_obj.pick_import_files()).expect_err("import should fail");
+        assert!(err.contains("pick_import_files"));
+        let err = block_on(fs_obj.save_download("a.png", &[0, 1]))
+            .expect_err("save download should fail");
+        assert!(err.contains("save_download"));
+        let err = block_on(fs_obj.usage()).expect_err("usage should fail");
+        assert!(err.contains("usage"));
+        let err = block_on(fs_obj.request_persistent_storage()).expect_err("persist should fail");
+        assert!(err.contains("request_persistent_storage"));
+        assert_eq!(
+            format!("{:?}", fs_obj.watch("/", std::rc::Rc::new(|_: &str| {}))),
+            "ExplorerWatch { active: false }"
+        );
     }
 }
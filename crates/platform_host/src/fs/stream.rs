@@ -0,0 +1,172 @@
+//! Chunked file streams built on top of [`ExplorerFsService`].
+//!
+//! A stream moves a file through bounded chunks, so callers can hash, pack, or copy files of many
+//! megabytes without holding them whole. Streams only use `read_file_chunk` and
+//! `write_file_chunk`, so every backend supports them.
+
+use super::service::ExplorerFsFuture;
+use super::types::{ExplorerFileChunk, ExplorerMetadata};
+
+/// Bytes requested per chunk unless a stream is given another size.
+pub const EXPLORER_STREAM_CHUNK_BYTES: usize = 256 * 1024;
+
+type ReadChunk<'a> =
+    Box<dyn Fn(u64, usize) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> + 'a>;
+type WriteChunk<'a> =
+    Box<dyn Fn(u64, Vec<u8>) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> + 'a>;
+
+/// Reads a file front to back in chunks; created by
+/// [`ExplorerFsService::read_stream`](super::service::ExplorerFsService::read_stream).
+pub struct ExplorerReadStream<'a> {
+    read_chunk: ReadChunk<'a>,
+    chunk_len: usize,
+    position: u64,
+    size: Option<u64>,
+    done: bool,
+}
+
+impl<'a> ExplorerReadStream<'a> {
+    pub(crate) fn new(read_chunk: ReadChunk<'a>) -> Self {
+        Self {
+            read_chunk,
+            chunk_len: EXPLORER_STREAM_CHUNK_BYTES,
+            position: 0,
+            size: None,
+            done: false,
+        }
+    }
+
+    /// Requests chunks of `chunk_len` bytes instead of [`EXPLORER_STREAM_CHUNK_BYTES`].
+    pub fn with_chunk_len(mut self, chunk_len: usize) -> Self {
+        self.chunk_len = chunk_len.max(1);
+        self
+    }
+
+    /// Reads the next chunk, or returns `None` once the file is read or a read has failed.
+    pub async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, String>> {
+        if self.done {
+            return None;
+        }
+        match (self.read_chunk)(self.position, self.chunk_len).await {
+            Ok(chunk) => {
+                self.size = Some(chunk.size);
+                self.position += chunk.bytes.len() as u64;
+                self.done = chunk.bytes.is_empty() || self.position >= chunk.size;
+                (!chunk.bytes.is_empty()).then_some(Ok(chunk.bytes))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+
+    /// Reads the rest of the file into one buffer.
+    pub async fn read_to_end(mut self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = self.next_chunk().await {
+            bytes.extend_from_slice(&chunk?);
+        }
+        Ok(bytes)
+    }
+
+    /// Bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Size of the file, known once the first chunk is read.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+/// Writes a file front to back in chunks; created by
+/// [`ExplorerFsService::write_stream`](super::service::ExplorerFsService::write_stream).
+///
+/// The first chunk replaces any existing file and later chunks are appended, so the file is only
+/// complete once the last chunk is written.
+pub struct ExplorerWriteStream<'a> {
+    write_chunk: WriteChunk<'a>,
+    position: u64,
+    metadata: Option<ExplorerMetadata>,
+}
+
+impl<'a> ExplorerWriteStream<'a> {
+    pub(crate) fn new(write_chunk: WriteChunk<'a>) -> Self {
+        Self {
+            write_chunk,
+            position: 0,
+            metadata: None,
+        }
+    }
+
+    /// Appends `bytes` to the file.
+    pub async fn write_chunk(&mut self, bytes: &[u8]) -> Result<(), String> {
+        if bytes.is_empty() && self.metadata.is_some() {
+            return Ok(());
+        }
+        let metadata = (self.write_chunk)(self.position, bytes.to_vec()).await?;
+        self.position += bytes.len() as u64;
+        self.metadata = Some(metadata);
+        Ok(())
+    }
+
+    /// Bytes written so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Ends the file, creating it empty when nothing was written, and returns its metadata.
+    pub async fn finish(mut self) -> Result<ExplorerMetadata, String> {
+        match self.metadata.take() {
+            Some(metadata) => Ok(metadata),
+            None => (self.write_chunk)(0, Vec::new()).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use crate::fs::service::ExplorerFsService;
+    use crate::fs::test_support::MemoryFs;
+
+    #[test]
+    fn read_stream_yields_bounded_chunks_until_the_end() {
+        let fs = MemoryFs::with(&[("/a.txt", Some("abcdefgh")), ("/empty.txt", Some(""))]);
+        let mut stream = fs.read_stream("/a.txt").with_chunk_len(3);
+        let mut chunks = Vec::new();
+        while let Some(chunk) = block_on(stream.next_chunk()) {
+            chunks.push(chunk.expect("chunk"));
+        }
+        assert_eq!(chunks, [&b"abc"[..], b"def", b"gh"]);
+        assert_eq!((stream.position(), stream.size()), (8, Some(8)));
+
+        let mut empty = fs.read_stream("/empty.txt");
+        assert!(block_on(empty.next_chunk()).is_none());
+        assert_eq!(empty.size(), Some(0));
+
+        let mut missing = fs.read_stream("/missing.txt");
+        assert!(block_on(missing.next_chunk()).expect("error").is_err());
+        assert!(block_on(missing.next_chunk()).is_none());
+    }
+
+    #[test]
+    fn write_stream_replaces_then_appends() {
+        let fs = MemoryFs::with(&[("/a.txt", Some("old contents"))]);
+        let mut stream = fs.write_stream("/a.txt");
+        block_on(stream.write_chunk(b"new ")).expect("first chunk");
+        block_on(stream.write_chunk(b"text")).expect("second chunk");
+        let meta = block_on(stream.finish()).expect("finish");
+        assert_eq!(meta.size, Some(8));
+        let bytes = block_on(fs.read_stream("/a.txt").read_to_end()).expect("read back");
+        assert_eq!(bytes, b"new text");
+
+        let meta = block_on(fs.write_stream("/b.txt").finish()).expect("empty file");
+        assert_eq!(meta.size, Some(0));
+        let err = block_on(fs.write_file_chunk("/a.txt", 3, b"x")).expect_err("gap");
+        assert!(err.contains("offset 3"));
+    }
+}
@@ -0,0 +1,339 @@
+//! In-memory [`ExplorerFsService`] used by the explorer helper tests.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use super::batch::apply_batch_with;
+use super::service::{ExplorerFsFuture, ExplorerFsService};
+use super::types::{
+    ExplorerBackend, ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult, ExplorerEntry,
+    ExplorerEntryKind, ExplorerFileBytesResult, ExplorerFileChunk, ExplorerFileReadResult,
+    ExplorerImportFile, ExplorerListPage, ExplorerListResult, ExplorerMetadata,
+    ExplorerPermissionMode, ExplorerPermissionState, ExplorerStorageUsage,
+};
+use super::watch::{ExplorerChangeCallback, ExplorerWatch};
+
+/// Minimal in-memory tree: `None` marks a folder, `Some(text)` a file.
+#[derive(Default)]
+pub(crate) struct MemoryFs {
+    nodes: RefCell<BTreeMap<String, Option<String>>>,
+}
+
+impl MemoryFs {
+    pub(crate) fn with(paths: &[(&str, Option<&str>)]) -> Self {
+        let fs = Self::default();
+        fs.nodes.borrow_mut().insert("/".to_string(), None);
+        for (path, text) in paths {
+            fs.nodes
+                .borrow_mut()
+                .insert(path.to_string(), text.map(str::to_string));
+        }
+        fs
+    }
+
+    fn meta(&self, path: &str) -> Result<ExplorerMetadata, String> {
+        let nodes = self.nodes.borrow();
+        let node = nodes.get(path).ok_or_else(|| format!("{path} not found"))?;
+        Ok(ExplorerMetadata {
+            name: path.rsplit('/').next().unwrap_or_default().to_string(),
+            path: path.to_string(),
+            kind: if node.is_some() {
+                ExplorerEntryKind::File
+            } else {
+                ExplorerEntryKind::Directory
+            },
+            backend: ExplorerBackend::IndexedDbVirtual,
+            size: node.as_ref().map(|text| text.len() as u64),
+            modified_at_unix_ms: None,
+            permission: ExplorerPermissionState::Virtual,
+        })
+    }
+
+    fn insert(&self, path: &str, node: Option<String>) -> Result<ExplorerMetadata, String> {
+        if self.nodes.borrow().contains_key(path) {
+            return Err(format!("{path} already exists"));
+        }
+        self.nodes.borrow_mut().insert(path.to_string(), node);
+        self.meta(path)
+    }
+
+    pub(crate) fn paths(&self) -> Vec<String> {
+        self.nodes.borrow().keys().cloned().collect()
+    }
+}
+
+fn within(path: &str, root: &str) -> bool {
+    path == root || path.starts_with(&format!("{root}/"))
+}
+
+impl ExplorerFsService for MemoryFs {
+    fn status<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+        Box::pin(async { Err("unused".to_string()) })
+    }
+
+    fn pick_native_directory<'a>(
+        &'a self,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+        Box::pin(async { Err("unused".to_string()) })
+    }
+
+    fn unmount_native_directory<'a>(
+        &'a self,
+        _path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+        Box::pin(async { Err("unused".to_string()) })
+    }
+
+    fn request_permission<'a>(
+        &'a self,
+        _mode: ExplorerPermissionMode,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerPermissionState, String>> {
+        Box::pin(async { Ok(ExplorerPermissionState::Virtual) })
+    }
+
+    fn list_dir<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerListResult, String>> {
+        Box::pin(async move {
+            self.meta(path)?;
+            let prefix = format!("{}/", path.trim_end_matches('/'));
+            let entries = self
+                .paths()
+                .into_iter()
+                .filter(|child| {
+                    child
+                        .strip_prefix(&prefix)
+                        .is_some_and(|rest| !rest.is_empty() && !rest.contains('/'))
+                })
+                .map(|child| {
+                    let meta = self.meta(&child).expect("child");
+                    ExplorerEntry {
+                        name: meta.name,
+                        path: meta.path,
+                        kind: meta.kind,
+                        size: meta.size,
+                        modified_at_unix_ms: None,
+                    }
+                })
+                .collect();
+            Ok(ExplorerListResult {
+                cwd: path.to_string(),
+                backend: ExplorerBackend::IndexedDbVirtual,
+                permission: ExplorerPermissionState::Virtual,
+                entries,
+            })
+        })
+    }
+
+    fn list_dir_page<'a>(
+        &'a self,
+        path: &'a str,
+        offset: usize,
+        limit: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>> {
+        Box::pin(async move { Ok(self.list_dir(path).await?.page(offset, limit)) })
+    }
+
+    fn read_text_file<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileReadResult, String>> {
+        Box::pin(async move {
+            let metadata = self.meta(path)?;
+            let text = self.nodes.borrow()[path]
+                .clone()
+                .ok_or_else(|| format!("{path} is a folder"))?;
+            Ok(ExplorerFileReadResult {
+                backend: ExplorerBackend::IndexedDbVirtual,
+                path: path.to_string(),
+                text,
+                metadata,
+                cached_preview_key: String::new(),
+            })
+        })
+    }
+
+    fn read_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>> {
+        Box::pin(async move {
+            let file = self.read_text_file(path).await?;
+            Ok(ExplorerFileBytesResult {
+                backend: file.backend,
+                path: file.path,
+                bytes: file.text.into_bytes(),
+                metadata: file.metadata,
+            })
+        })
+    }
+
+    fn read_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        len: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> {
+        Box::pin(async move {
+            let bytes = self.read_file_bytes(path).await?.bytes;
+            let start = (offset as usize).min(bytes.len());
+            let end = start.saturating_add(len).min(bytes.len());
+            Ok(ExplorerFileChunk {
+                path: path.to_string(),
+                offset,
+                bytes: bytes[start..end].to_vec(),
+                size: bytes.len() as u64,
+            })
+        })
+    }
+
+    fn write_text_file<'a>(
+        &'a self,
+        _path: &'a str,
+        _text: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async { Err("unused".to_string()) })
+    }
+
+    fn write_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move {
+            if self.nodes.borrow().get(path) == Some(&None) {
+                return Err(format!("{path} is a folder"));
+            }
+            let text = String::from_utf8_lossy(bytes).into_owned();
+            self.nodes.borrow_mut().insert(path.to_string(), Some(text));
+            self.meta(path)
+        })
+    }
+
+    fn write_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move {
+            if offset == 0 {
+                return self.write_file_bytes(path, bytes).await;
+            }
+            let mut existing = self.read_file_bytes(path).await?.bytes;
+            if existing.len() as u64 != offset {
+                return Err(format!(
+                    "chunk offset {offset} does not match the {} byte size of {path}",
+                    existing.len()
+                ));
+            }
+            existing.extend_from_slice(bytes);
+            self.write_file_bytes(path, &existing).await
+        })
+    }
+
+    fn pick_import_files<'a>(
+        &'a self,
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>> {
+        Box::pin(async { Err("unused".to_string()) })
+    }
+
+    fn save_download<'a>(
+        &'a self,
+        _name: &'a str,
+        _bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<(), String>> {
+        Box::pin(async { Err("unused".to_string()) })
+    }
+
+    fn create_dir<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { self.insert(path, None) })
+    }
+
+    fn create_file<'a>(
+        &'a self,
+        path: &'a str,
+        text: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { self.insert(path, Some(text.to_string())) })
+    }
+
+    fn delete<'a>(
+        &'a self,
+        path: &'a str,
+        _recursive: bool,
+    ) -> ExplorerFsFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            self.meta(path)?;
+            self.nodes
+                .borrow_mut()
+                .retain(|node, _| !within(node, path));
+            Ok(())
+        })
+    }
+
+    fn rename<'a>(
+        &'a self,
+        from: &'a str,
+        to: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move {
+            self.meta(from)?;
+            if self.meta(to).is_ok() {
+                return Err(format!("{to} already exists"));
+            }
+            let moved = self
+                .paths()
+                .into_iter()
+                .filter(|node| within(node, from))
+                .collect::<Vec<_>>();
+            let mut nodes = self.nodes.borrow_mut();
+            for node in moved {
+                let value = nodes.remove(&node).expect("node");
+                nodes.insert(format!("{to}{}", &node[from.len()..]), value);
+            }
+            drop(nodes);
+            self.meta(to)
+        })
+    }
+
+    fn batch<'a>(
+        &'a self,
+        ops: &'a [ExplorerBatchOp],
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>> {
+        Box::pin(async move { Ok(apply_batch_with(self, ops).await) })
+    }
+
+    fn stat<'a>(&'a self, path: &'a str) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { self.meta(path) })
+    }
+
+    fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>> {
+        let bytes = self
+            .nodes
+            .borrow()
+            .values()
+            .flatten()
+            .map(String::len)
+            .sum::<usize>();
+        Box::pin(async move {
+            Ok(ExplorerStorageUsage {
+                usage_bytes: Some(bytes as u64),
+                quota_bytes: None,
+                persisted: None,
+            })
+        })
+    }
+
+    fn request_persistent_storage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<bool, String>> {
+        Box::pin(async { Ok(false) })
+    }
+
+    fn watch(&self, _path: &str, _on_change: ExplorerChangeCallback) -> ExplorerWatch {
+        ExplorerWatch::inactive()
+    }
+}
@@ -0,0 +1,251 @@
+//! Recoverable deletion built on top of [`ExplorerFsService`].
+//!
+//! Trashed entries are moved under [`EXPLORER_TRASH_DIR`]: the entry itself lands in `files/<id>`
+//! and a JSON record of where it came from is written to `info/<id>.json`. Because the helpers only
+//! use the service contract, every backend gets the same trash behavior without a dedicated host
+//! operation.
+
+use serde::{Deserialize, Serialize};
+
+use super::path::normalize_virtual_path;
+use super::service::ExplorerFsService;
+use super::types::{ExplorerEntryKind, ExplorerMetadata};
+use crate::time::next_monotonic_timestamp_ms;
+
+/// Virtual path holding trashed entries and their records.
+pub const EXPLORER_TRASH_DIR: &str = "/.trash";
+
+const TRASH_INFO_SUFFIX: &str = ".json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Record of one trashed entry.
+pub struct ExplorerTrashEntry {
+    /// Identifier of the entry within the trash.
+    pub id: String,
+    /// Original entry name.
+    pub name: String,
+    /// Path the entry is restored to.
+    pub original_path: String,
+    /// Entry kind at deletion time.
+    pub kind: ExplorerEntryKind,
+    /// Deletion timestamp in unix milliseconds.
+    pub deleted_at_unix_ms: u64,
+}
+
+/// Returns whether `path` is the trash folder or lies inside it.
+pub fn is_trash_path(path: &str) -> bool {
+    let path = normalize_virtual_path(path);
+    path == EXPLORER_TRASH_DIR
+        || path
+            .strip_prefix(EXPLORER_TRASH_DIR)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn trash_files_dir() -> String {
+    format!("{EXPLORER_TRASH_DIR}/files")
+}
+
+fn trash_info_dir() -> String {
+    format!("{EXPLORER_TRASH_DIR}/info")
+}
+
+fn trashed_path(id: &str) -> String {
+    format!("{}/{id}", trash_files_dir())
+}
+
+fn trash_info_path(id: &str) -> String {
+    format!("{}/{id}{TRASH_INFO_SUFFIX}", trash_info_dir())
+}
+
+async fn ensure_dir<F: ExplorerFsService + ?Sized>(fs: &F, path: &str) -> Result<(), String> {
+    match fs.stat(path).await {
+        Ok(meta) if meta.kind == ExplorerEntryKind::Directory => Ok(()),
+        Ok(_) => Err(format!("{path} is not a folder")),
+        Err(_) => fs.create_dir(path).await.map(|_| ()),
+    }
+}
+
+async fn read_trash_entry<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    id: &str,
+) -> Result<ExplorerTrashEntry, String> {
+    let file = fs.read_text_file(&trash_info_path(id)).await?;
+    serde_json::from_str(&file.text).map_err(|e| format!("corrupt trash record for {id}: {e}"))
+}
+
+/// Moves `path` into the trash and returns its record.
+///
+/// # Errors
+///
+/// Returns an error for the root and for paths already in the trash, and when the backend cannot
+/// stat, record, or move the entry.
+pub async fn move_to_trash_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    path: &str,
+) -> Result<ExplorerTrashEntry, String> {
+    let path = normalize_virtual_path(path);
+    if path == "/" {
+        return Err("cannot move the root directory to the trash".to_string());
+    }
+    if is_trash_path(&path) {
+        return Err(format!("{path} is already in the trash"));
+    }
+    let meta = fs.stat(&path).await?;
+    ensure_dir(fs, EXPLORER_TRASH_DIR).await?;
+    ensure_dir(fs, &trash_files_dir()).await?;
+    ensure_dir(fs, &trash_info_dir()).await?;
+
+    let deleted_at_unix_ms = next_monotonic_timestamp_ms();
+    let entry = ExplorerTrashEntry {
+        id: format!("{deleted_at_unix_ms}-{}", meta.name),
+        name: meta.name,
+        original_path: path,
+        kind: meta.kind,
+        deleted_at_unix_ms,
+    };
+    let record = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    let info_path = trash_info_path(&entry.id);
+    fs.create_file(&info_path, &record).await?;
+    if let Err(err) = fs
+        .rename(&entry.original_path, &trashed_path(&entry.id))
+        .await
+    {
+        let _ = fs.delete(&info_path, false).await;
+        return Err(err);
+    }
+    Ok(entry)
+}
+
+/// Lists trashed entries, most recently deleted first.
+///
+/// Records that cannot be read are skipped, and a missing trash folder lists as empty.
+///
+/// # Errors
+///
+/// Returns an error when the trash folder exists but cannot be listed.
+pub async fn list_trash_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+) -> Result<Vec<ExplorerTrashEntry>, String> {
+    if fs.stat(&trash_info_dir()).await.is_err() {
+        return Ok(Vec::new());
+    }
+    let mut entries = Vec::new();
+    for record in fs.list_dir(&trash_info_dir()).await?.entries {
+        let Some(id) = record.name.strip_suffix(TRASH_INFO_SUFFIX) else {
+            continue;
+        };
+        if let Ok(entry) = read_trash_entry(fs, id).await {
+            entries.push(entry);
+        }
+    }
+    entries.sort_by(|a, b| {
+        b.deleted_at_unix_ms
+            .cmp(&a.deleted_at_unix_ms)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(entries)
+}
+
+/// Moves a trashed entry back to its original path and drops its record.
+///
+/// # Errors
+///
+/// Returns an error when the record is missing or when the move fails, for example because
+/// something now occupies the original path or its parent folder is gone.
+pub async fn restore_from_trash_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    id: &str,
+) -> Result<ExplorerMetadata, String> {
+    let entry = read_trash_entry(fs, id).await?;
+    let meta = fs.rename(&trashed_path(id), &entry.original_path).await?;
+    fs.delete(&trash_info_path(id), false).await?;
+    Ok(meta)
+}
+
+/// Permanently deletes one trashed entry and its record.
+///
+/// # Errors
+///
+/// Returns an error when the backend delete fails.
+pub async fn delete_from_trash_with<F: ExplorerFsService + ?Sized>(
+    fs: &F,
+    id: &str,
+) -> Result<(), String> {
+    if fs.stat(&trashed_path(id)).await.is_ok() {
+        fs.delete(&trashed_path(id), true).await?;
+    }
+    fs.delete(&trash_info_path(id), false).await
+}
+
+/// Permanently deletes everything in the trash and returns how many entries were removed.
+///
+/// # Errors
+///
+/// Returns an error when the trash cannot be listed or deleted.
+pub async fn empty_trash_with<F: ExplorerFsService + ?Sized>(fs: &F) -> Result<usize, String> {
+    let count = list_trash_with(fs).await?.len();
+    if fs.stat(EXPLORER_TRASH_DIR).await.is_ok() {
+        fs.delete(EXPLORER_TRASH_DIR, true).await?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::fs::test_support::MemoryFs;
+
+    #[test]
+    fn trash_paths_cover_the_folder_and_its_contents_only() {
+        assert!(is_trash_path("/.trash"));
+        assert!(is_trash_path("/.trash/files/1-a.txt"));
+        assert!(!is_trash_path("/.trashy"));
+        assert!(!is_trash_path("/docs/.trash"));
+    }
+
+    #[test]
+    fn trashed_entries_restore_to_their_original_path() {
+        let fs = MemoryFs::with(&[
+            ("/docs", None),
+            ("/docs/a.txt", Some("alpha")),
+            ("/docs/sub", None),
+            ("/docs/sub/b.txt", Some("beta")),
+        ]);
+
+        let file = block_on(move_to_trash_with(&fs, "/docs/a.txt")).expect("trash file");
+        let folder = block_on(move_to_trash_with(&fs, "/docs/sub")).expect("trash folder");
+        assert_eq!(file.original_path, "/docs/a.txt");
+        assert_eq!(folder.kind, ExplorerEntryKind::Directory);
+        assert!(block_on(fs.stat("/docs/a.txt")).is_err());
+        assert!(block_on(fs.stat("/docs/sub/b.txt")).is_err());
+
+        let listed = block_on(list_trash_with(&fs)).expect("list");
+        assert_eq!(listed, vec![folder.clone(), file.clone()]);
+
+        let restored = block_on(restore_from_trash_with(&fs, &folder.id)).expect("restore");
+        assert_eq!(restored.path, "/docs/sub");
+        let text = block_on(fs.read_text_file("/docs/sub/b.txt")).expect("read");
+        assert_eq!(text.text, "beta");
+        assert_eq!(block_on(list_trash_with(&fs)).expect("list"), vec![file]);
+
+        assert!(block_on(move_to_trash_with(&fs, "/.trash/info")).is_err());
+        assert!(block_on(move_to_trash_with(&fs, "/")).is_err());
+    }
+
+    #[test]
+    fn restore_refuses_to_replace_and_empty_removes_everything() {
+        let fs = MemoryFs::with(&[("/a.txt", Some("old"))]);
+        let entry = block_on(move_to_trash_with(&fs, "/a.txt")).expect("trash");
+        block_on(fs.create_file("/a.txt", "new")).expect("recreate");
+
+        assert!(block_on(restore_from_trash_with(&fs, &entry.id)).is_err());
+        assert_eq!(block_on(list_trash_with(&fs)).expect("list").len(), 1);
+
+        assert_eq!(block_on(empty_trash_with(&fs)).expect("empty"), 1);
+        assert!(block_on(list_trash_with(&fs)).expect("list").is_empty());
+        assert_eq!(fs.paths(), ["/", "/a.txt"]);
+        assert_eq!(block_on(empty_trash_with(&fs)).expect("empty again"), 0);
+    }
+}
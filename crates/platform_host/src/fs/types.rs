@@ -2,6 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::mime::{explorer_mime_type, mime_from_name};
+use super::path::normalize_virtual_path;
+
+/// Folder under which native directories are mounted, one subfolder per mount.
+pub const EXPLORER_MOUNT_DIR: &str = "/mnt";
 /// Cache API cache name used for explorer text previews.
 pub const EXPLORER_CACHE_NAME: &str = "retrodesk-explorer-cache-v1";
 /// localStorage key used for explorer UI preferences.
@@ -79,6 +84,52 @@ pub struct ExplorerBackendStatus {
     pub permission: ExplorerPermissionState,
     /// Optional user-facing root path hint.
     pub root_path_hint: Option<String>,
+    /// Native folders mounted read-only under [`EXPLORER_MOUNT_DIR`], in the order they were added.
+    #[serde(default)]
+    pub mounts: Vec<ExplorerMount>,
+}
+
+impl ExplorerBackendStatus {
+    /// Mount that serves `path`, when it is a mount point or lies inside one.
+    pub fn mount_for(&self, path: &str) -> Option<&ExplorerMount> {
+        let path = normalize_virtual_path(path);
+        self.mounts.iter().find(|mount| {
+            path.strip_prefix(mount.path.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Native folder mounted read-only into the explorer namespace.
+pub struct ExplorerMount {
+    /// Mount point, such as `/mnt/photos`.
+    pub path: String,
+    /// Name of the native folder.
+    pub name: String,
+    /// Read permission the host currently grants for the folder.
+    pub permission: ExplorerPermissionState,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Storage in use by the explorer backend and the quota the host grants it.
+pub struct ExplorerStorageUsage {
+    /// Bytes in use, or `None` when the host cannot report it.
+    pub usage_bytes: Option<u64>,
+    /// Bytes the host allows before refusing writes, or `None` when it sets no reported limit.
+    pub quota_bytes: Option<u64>,
+    /// Whether stored files survive storage pressure, or `None` when the host cannot tell.
+    pub persisted: Option<bool>,
+}
+
+impl ExplorerStorageUsage {
+    /// Share of the quota in use, from `0.0` to `1.0`, when both figures are known.
+    pub fn used_fraction(&self) -> Option<f64> {
+        match (self.usage_bytes, self.quota_bytes) {
+            (Some(usage), Some(quota)) if quota > 0 => Some((usage as f64 / quota as f64).min(1.0)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -115,6 +166,16 @@ pub struct ExplorerMetadata {
     pub permission: ExplorerPermissionState,
 }
 
+impl ExplorerMetadata {
+    /// MIME type of a file judged by its name; `None` for folders and unknown extensions.
+    pub fn mime_type(&self) -> Option<&'static str> {
+        match self.kind {
+            ExplorerEntryKind::File => mime_from_name(&self.name),
+            ExplorerEntryKind::Directory => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Result payload for directory listing operations.
 pub struct ExplorerListResult {
@@ -128,6 +189,45 @@ pub struct ExplorerListResult {
     pub entries: Vec<ExplorerEntry>,
 }
 
+impl ExplorerListResult {
+    /// Keeps the `limit` entries starting at `offset`, for backends that list a folder in full.
+    pub fn page(self, offset: usize, limit: usize) -> ExplorerListPage {
+        let total = self.entries.len();
+        ExplorerListPage {
+            cwd: self.cwd,
+            backend: self.backend,
+            permission: self.permission,
+            entries: self.entries.into_iter().skip(offset).take(limit).collect(),
+            offset,
+            total,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// One slice of a directory listing, in the same order as [`ExplorerListResult::entries`].
+pub struct ExplorerListPage {
+    /// Normalized directory path that was listed.
+    pub cwd: String,
+    /// Backend that served the list request.
+    pub backend: ExplorerBackend,
+    /// Effective permission state for the listing.
+    pub permission: ExplorerPermissionState,
+    /// Child entries in this page.
+    pub entries: Vec<ExplorerEntry>,
+    /// Position of the first entry of this page in the whole listing.
+    pub offset: usize,
+    /// Number of entries in the whole listing.
+    pub total: usize,
+}
+
+impl ExplorerListPage {
+    /// Whether entries remain after this page.
+    pub fn has_more(&self) -> bool {
+        !self.entries.is_empty() && self.offset + self.entries.len() < self.total
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// Result payload for reading a text file in the explorer.
 pub struct ExplorerFileReadResult {
@@ -143,6 +243,183 @@ pub struct ExplorerFileReadResult {
     pub cached_preview_key: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Result payload for reading a file's raw bytes in the explorer.
+pub struct ExplorerFileBytesResult {
+    /// Backend that served the read request.
+    pub backend: ExplorerBackend,
+    /// Normalized file path.
+    pub path: String,
+    /// Raw file content.
+    pub bytes: Vec<u8>,
+    /// File metadata snapshot captured at read time.
+    pub metadata: ExplorerMetadata,
+}
+
+impl ExplorerFileBytesResult {
+    /// MIME type sniffed from the file's content, falling back to its name.
+    pub fn mime_type(&self) -> &'static str {
+        explorer_mime_type(&self.path, &self.bytes)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// One slice of a file returned by a chunked explorer read.
+pub struct ExplorerFileChunk {
+    /// Normalized file path.
+    pub path: String,
+    /// Byte offset the chunk starts at.
+    pub offset: u64,
+    /// Bytes read; fewer than requested, or none, at the end of the file.
+    pub bytes: Vec<u8>,
+    /// Total file size in bytes.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A file chosen in the host's file picker for import into the explorer filesystem.
+pub struct ExplorerImportFile {
+    /// File name reported by the host, without any folder.
+    pub name: String,
+    /// File contents.
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+/// One change applied by an explorer batch.
+pub enum ExplorerBatchOp {
+    /// Creates a folder.
+    CreateDir {
+        /// Folder to create.
+        path: String,
+    },
+    /// Writes a text file, replacing any existing file.
+    WriteText {
+        /// File to write.
+        path: String,
+        /// New file contents.
+        text: String,
+    },
+    /// Writes a file's raw bytes, replacing any existing file.
+    WriteBytes {
+        /// File to write.
+        path: String,
+        /// New file contents.
+        bytes: Vec<u8>,
+    },
+    /// Deletes a file or folder.
+    Delete {
+        /// Entry to delete.
+        path: String,
+        /// Whether a folder is deleted with everything inside it.
+        recursive: bool,
+    },
+    /// Renames or moves a file or folder, failing when `to` already exists.
+    Rename {
+        /// Entry to move.
+        from: String,
+        /// New path.
+        to: String,
+    },
+}
+
+impl ExplorerBatchOp {
+    /// Paths the operation changes, for change notifications.
+    pub fn changed_paths(&self) -> Vec<&str> {
+        match self {
+            Self::CreateDir { path }
+            | Self::WriteText { path, .. }
+            | Self::WriteBytes { path, .. }
+            | Self::Delete { path, .. } => vec![path],
+            Self::Rename { from, to } => vec![from, to],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+/// Outcome of one operation in an explorer batch.
+pub struct ExplorerBatchResult {
+    /// Metadata of the created, written, or renamed entry; `None` for deletes and failures.
+    #[serde(default)]
+    pub metadata: Option<ExplorerMetadata>,
+    /// Why the operation failed, when it did.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl ExplorerBatchResult {
+    /// The outcome as a `Result`.
+    pub fn into_result(self) -> Result<Option<ExplorerMetadata>, String> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.metadata),
+        }
+    }
+}
+
+impl From<Result<Option<ExplorerMetadata>, String>> for ExplorerBatchResult {
+    fn from(result: Result<Option<ExplorerMetadata>, String>) -> Self {
+        match result {
+            Ok(metadata) => Self {
+                metadata,
+                error: None,
+            },
+            Err(error) => Self {
+                metadata: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+/// Explorer listing column entries are sorted by.
+pub enum ExplorerSortKey {
+    /// Entry name.
+    #[default]
+    Name,
+    /// Entry kind, then file extension.
+    Type,
+    /// Last-modified time.
+    Modified,
+    /// File size.
+    Size,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+/// Explorer listing sort direction.
+pub enum ExplorerSortDirection {
+    /// Smallest, oldest, or alphabetically first entries first.
+    #[default]
+    Ascending,
+    /// Largest, newest, or alphabetically last entries first.
+    Descending,
+}
+
+impl ExplorerSortDirection {
+    /// Returns the opposite direction.
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+/// Layout used for the explorer listing.
+pub enum ExplorerViewMode {
+    /// Table with name, type, modified, and size columns.
+    #[default]
+    Details,
+    /// Wrapping grid of large icons.
+    Icons,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// User preferences for the explorer app UI.
 pub struct ExplorerPrefs {
@@ -152,6 +429,28 @@ pub struct ExplorerPrefs {
     pub details_visible: bool,
     /// Whether hidden files should be shown.
     pub show_hidden: bool,
+    /// Listing column entries are sorted by.
+    #[serde(default)]
+    pub sort_key: ExplorerSortKey,
+    /// Listing sort direction.
+    #[serde(default)]
+    pub sort_direction: ExplorerSortDirection,
+    /// Whether folders are listed before files regardless of the sort direction.
+    #[serde(default = "default_directories_first")]
+    pub directories_first: bool,
+    /// Listing layout.
+    #[serde(default)]
+    pub view_mode: ExplorerViewMode,
+    /// Folders pinned to the Favorites sidebar section, in display order.
+    #[serde(default)]
+    pub favorites: Vec<String>,
+    /// Recently visited folders, most recent first.
+    #[serde(default)]
+    pub recent_locations: Vec<String>,
+}
+
+fn default_directories_first() -> bool {
+    true
 }
 
 impl Default for ExplorerPrefs {
@@ -160,6 +459,12 @@ impl Default for ExplorerPrefs {
             preferred_backend: ExplorerBackend::IndexedDbVirtual,
             details_visible: true,
             show_hidden: true,
+            sort_key: ExplorerSortKey::Name,
+            sort_direction: ExplorerSortDirection::Ascending,
+            directories_first: default_directories_first(),
+            view_mode: ExplorerViewMode::Details,
+            favorites: Vec::new(),
+            recent_locations: Vec::new(),
         }
     }
 }
@@ -198,6 +503,44 @@ mod tests {
         let mode: ExplorerPermissionMode =
             serde_json::from_str("\"readwrite\"").expect("deserialize");
         assert_eq!(mode, ExplorerPermissionMode::Readwrite);
+
+        let op = ExplorerBatchOp::Delete {
+            path: "/a".to_string(),
+            recursive: true,
+        };
+        assert_eq!(
+            serde_json::to_value(&op).expect("serialize"),
+            json!({ "op": "delete", "path": "/a", "recursive": true })
+        );
+        let result: ExplorerBatchResult =
+            serde_json::from_value(json!({ "error": "denied" })).expect("deserialize");
+        assert_eq!(result.into_result(), Err("denied".to_string()));
+    }
+
+    #[test]
+    fn explorer_prefs_default_listing_layout_when_fields_are_missing() {
+        let prefs: ExplorerPrefs = serde_json::from_value(json!({
+            "preferred_backend": "indexed-db-virtual",
+            "details_visible": false,
+            "show_hidden": true
+        }))
+        .expect("deserialize");
+        assert_eq!(prefs.sort_key, ExplorerSortKey::Name);
+        assert_eq!(prefs.sort_direction, ExplorerSortDirection::Ascending);
+        assert!(prefs.directories_first);
+        assert_eq!(prefs.view_mode, ExplorerViewMode::Details);
+        assert!(prefs.favorites.is_empty() && prefs.recent_locations.is_empty());
+
+        let sorted = ExplorerPrefs {
+            sort_key: ExplorerSortKey::Modified,
+            sort_direction: ExplorerSortDirection::Ascending.reversed(),
+            view_mode: ExplorerViewMode::Icons,
+            ..prefs
+        };
+        let value = serde_json::to_value(&sorted).expect("serialize");
+        assert_eq!(value["sort_key"], json!("modified"));
+        assert_eq!(value["sort_direction"], json!("descending"));
+        assert_eq!(value["view_mode"], json!("icons"));
     }
 
     #[test]
@@ -233,5 +576,113 @@ mod tests {
         let round_trip: ExplorerFileReadResult =
             serde_json::from_value(value).expect("deserialize");
         assert_eq!(round_trip.text, "hello");
+
+        let bytes = ExplorerFileBytesResult {
+            backend: ExplorerBackend::NativeFsAccess,
+            path: "/file.txt".to_string(),
+            bytes: vec![0x89, b'P', b'N', b'G'],
+            metadata: round_trip.metadata,
+        };
+        let round_trip: ExplorerFileBytesResult =
+            serde_json::from_value(serde_json::to_value(&bytes).expect("serialize"))
+                .expect("deserialize");
+        assert_eq!(round_trip, bytes);
+        assert_eq!(bytes.metadata.mime_type(), Some("text/plain"));
+        assert_eq!(bytes.mime_type(), "text/plain");
+        let png = ExplorerFileBytesResult {
+            bytes: b"\x89PNG\r\n\x1a\n".to_vec(),
+            ..bytes
+        };
+        assert_eq!(png.mime_type(), "image/png");
+    }
+
+    #[test]
+    fn list_results_split_into_pages() {
+        let entries = ["a", "b", "c", "d", "e"]
+            .map(|name| ExplorerEntry {
+                name: name.to_string(),
+                path: format!("/{name}"),
+                kind: ExplorerEntryKind::File,
+                size: Some(1),
+                modified_at_unix_ms: None,
+            })
+            .to_vec();
+        let result = ExplorerListResult {
+            cwd: "/".to_string(),
+            backend: ExplorerBackend::IndexedDbVirtual,
+            permission: ExplorerPermissionState::Virtual,
+            entries,
+        };
+
+        let page = result.clone().page(2, 2);
+        assert_eq!((page.offset, page.total), (2, 5));
+        assert_eq!(page.entries[0].name, "c");
+        assert_eq!(page.entries.len(), 2);
+        assert!(page.has_more());
+
+        let last = result.clone().page(4, 2);
+        assert_eq!(last.entries.len(), 1);
+        assert!(!last.has_more());
+        assert!(!result.page(9, 2).has_more());
+    }
+
+    #[test]
+    fn mounts_serve_their_mount_point_and_everything_inside() {
+        let mount = |name: &str| ExplorerMount {
+            path: format!("{EXPLORER_MOUNT_DIR}/{name}"),
+            name: name.to_string(),
+            permission: ExplorerPermissionState::Granted,
+        };
+        let status = ExplorerBackendStatus {
+            backend: ExplorerBackend::IndexedDbVirtual,
+            native_supported: true,
+            has_native_root: false,
+            permission: ExplorerPermissionState::Virtual,
+            root_path_hint: None,
+            mounts: vec![mount("photos"), mount("projects")],
+        };
+        assert_eq!(
+            status
+                .mount_for("/mnt/photos")
+                .map(|mount| mount.name.as_str()),
+            Some("photos")
+        );
+        assert_eq!(
+            status
+                .mount_for("/mnt/projects/os/../readme.md")
+                .map(|mount| mount.name.as_str()),
+            Some("projects")
+        );
+        assert_eq!(status.mount_for("/mnt/photos-old"), None);
+        assert_eq!(status.mount_for("/mnt"), None);
+
+        let legacy: ExplorerBackendStatus = serde_json::from_value(json!({
+            "backend": "indexed-db-virtual",
+            "native_supported": false,
+            "has_native_root": false,
+            "permission": "virtual",
+            "root_path_hint": null,
+        }))
+        .expect("status without mounts");
+        assert!(legacy.mounts.is_empty());
+    }
+
+    #[test]
+    fn storage_usage_fraction_needs_both_figures() {
+        let usage = ExplorerStorageUsage {
+            usage_bytes: Some(25),
+            quota_bytes: Some(100),
+            persisted: None,
+        };
+        assert_eq!(usage.used_fraction(), Some(0.25));
+        assert_eq!(
+            ExplorerStorageUsage {
+                quota_bytes: Some(0),
+                ..usage
+            }
+            .used_fraction(),
+            None
+        );
+        assert_eq!(ExplorerStorageUsage::default().used_fraction(), None);
     }
 }
@@ -0,0 +1,174 @@
+//! Change notifications for watched explorer paths.
+//!
+//! A watch on a folder hears about the folder itself, entries directly inside it, and any folder
+//! that contains it, so a listing can refresh when a child is created, written, renamed, or
+//! deleted, or when the folder is moved away from under it.
+
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use super::path::normalize_virtual_path;
+
+/// Callback receiving the normalized path that changed.
+pub type ExplorerChangeCallback = Rc<dyn Fn(&str)>;
+
+/// Subscription returned by [`ExplorerFsService::watch`](super::service::ExplorerFsService::watch).
+///
+/// Notifications stop when the watch is dropped.
+#[must_use = "dropping a watch stops its notifications"]
+pub struct ExplorerWatch {
+    cancel: Option<Box<dyn FnOnce()>>,
+}
+
+impl ExplorerWatch {
+    /// Creates a watch that runs `cancel` when dropped.
+    pub fn new(cancel: impl FnOnce() + 'static) -> Self {
+        Self {
+            cancel: Some(Box::new(cancel)),
+        }
+    }
+
+    /// Watch for backends that never report changes.
+    pub fn inactive() -> Self {
+        Self { cancel: None }
+    }
+}
+
+impl Drop for ExplorerWatch {
+    fn drop(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            cancel();
+        }
+    }
+}
+
+impl fmt::Debug for ExplorerWatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExplorerWatch")
+            .field("active", &self.cancel.is_some())
+            .finish()
+    }
+}
+
+/// Whether a change at `changed` concerns a watch on `watched`: the same path, an entry directly
+/// inside it, or a folder containing it.
+pub fn change_affects(watched: &str, changed: &str) -> bool {
+    let watched = normalize_virtual_path(watched);
+    let changed = normalize_virtual_path(changed);
+    let parent = match changed.rfind('/') {
+        Some(idx) if idx > 0 => &changed[..idx],
+        _ => "/",
+    };
+    let contains_watched = changed == "/" || watched.starts_with(&format!("{changed}/"));
+    watched == changed || parent == watched || contains_watched
+}
+
+#[derive(Default)]
+struct WatchSlots {
+    next_id: u64,
+    watchers: Vec<(u64, String, ExplorerChangeCallback)>,
+}
+
+/// Watches registered in one page, for services that announce their own mutations.
+#[derive(Clone, Default)]
+pub struct ExplorerWatchRegistry {
+    slots: Rc<RefCell<WatchSlots>>,
+}
+
+impl ExplorerWatchRegistry {
+    /// Registers `on_change` for `path` until the returned watch is dropped.
+    pub fn watch(&self, path: &str, on_change: ExplorerChangeCallback) -> ExplorerWatch {
+        let id = {
+            let mut slots = self.slots.borrow_mut();
+            slots.next_id += 1;
+            let id = slots.next_id;
+            slots
+                .watchers
+                .push((id, normalize_virtual_path(path), on_change));
+            id
+        };
+        let slots = Rc::downgrade(&self.slots);
+        ExplorerWatch::new(move || {
+            if let Some(slots) = slots.upgrade() {
+                slots
+                    .borrow_mut()
+                    .watchers
+                    .retain(|(watcher, _, _)| *watcher != id);
+            }
+        })
+    }
+
+    /// Calls every watch that `changed` concerns.
+    ///
+    /// Callbacks run after the registry is released, so they may add or drop watches.
+    pub fn notify(&self, changed: &str) {
+        let changed = normalize_virtual_path(changed);
+        let callbacks = self
+            .slots
+            .borrow()
+            .watchers
+            .iter()
+            .filter(|(_, watched, _)| change_affects(watched, &changed))
+            .map(|(_, _, callback)| Rc::clone(callback))
+            .collect::<Vec<_>>();
+        for callback in callbacks {
+            callback(&changed);
+        }
+    }
+
+    /// Number of live watches.
+    pub fn len(&self) -> usize {
+        self.slots.borrow().watchers.len()
+    }
+
+    /// Whether no watch is live.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for ExplorerWatchRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExplorerWatchRegistry")
+            .field("watches", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_reach_the_path_its_children_and_moved_ancestors() {
+        assert!(change_affects("/docs", "/docs"));
+        assert!(change_affects("/docs", "/docs/a.txt"));
+        assert!(change_affects("/docs/", "/docs/sub"));
+        assert!(change_affects("/docs/sub", "/docs"));
+        assert!(change_affects("/", "/a.txt"));
+        assert!(change_affects("/docs", "/"));
+        assert!(!change_affects("/docs", "/docs/sub/deep.txt"));
+        assert!(!change_affects("/docs", "/documents"));
+        assert!(!change_affects("/docs", "/other/a.txt"));
+    }
+
+    #[test]
+    fn registry_notifies_until_the_watch_is_dropped() {
+        let registry = ExplorerWatchRegistry::default();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&seen);
+        let watch = registry.watch(
+            "/docs",
+            Rc::new(move |path: &str| log.borrow_mut().push(path.to_string())),
+        );
+        assert_eq!(registry.len(), 1);
+
+        registry.notify("/docs/a.txt/");
+        registry.notify("/other/b.txt");
+        assert_eq!(*seen.borrow(), ["/docs/a.txt"]);
+
+        drop(watch);
+        assert!(registry.is_empty());
+        registry.notify("/docs/c.txt");
+        assert_eq!(seen.borrow().len(), 1);
+    }
+}
@@ -3,8 +3,8 @@
 use std::rc::Rc;
 
 use crate::{
-    AppStateStore, ContentCache, ExplorerFsService, ExternalUrlService, NotificationService,
-    PrefsStore, TerminalProcessService, WallpaperAssetService,
+    AppStateStore, ClipboardService, ContentCache, ExplorerFsService, ExternalUrlService,
+    HttpService, NotificationService, PrefsStore, TerminalProcessService, WallpaperAssetService,
 };
 
 /// Stable host strategy selected for the current build/runtime composition path.
@@ -111,6 +111,8 @@ pub struct HostCapabilities {
     pub notifications: CapabilityStatus,
     /// Wallpaper import/library mutation availability.
     pub wallpaper_library: CapabilityStatus,
+    /// Outbound HTTP request availability.
+    pub network: CapabilityStatus,
 }
 
 impl HostCapabilities {
@@ -123,6 +125,7 @@ impl HostCapabilities {
             external_urls: CapabilityStatus::Available,
             notifications: CapabilityStatus::RequiresUserActivation,
             wallpaper_library: CapabilityStatus::Available,
+            network: CapabilityStatus::Available,
         }
     }
 
@@ -135,6 +138,7 @@ impl HostCapabilities {
             external_urls: CapabilityStatus::Available,
             notifications: CapabilityStatus::Available,
             wallpaper_library: CapabilityStatus::Available,
+            network: CapabilityStatus::Available,
         }
     }
 
@@ -147,6 +151,7 @@ impl HostCapabilities {
             external_urls: CapabilityStatus::Unavailable,
             notifications: CapabilityStatus::Unavailable,
             wallpaper_library: CapabilityStatus::Available,
+            network: CapabilityStatus::Unavailable,
         }
     }
 }
@@ -168,6 +173,10 @@ pub struct HostServices {
     pub cache: Rc<dyn ContentCache>,
     /// External URL opening service.
     pub external_urls: Rc<dyn ExternalUrlService>,
+    /// Clipboard write service.
+    pub clipboard: Rc<dyn ClipboardService>,
+    /// Outbound HTTP service.
+    pub http: Rc<dyn HttpService>,
     /// Notification delivery service.
     pub notifications: Rc<dyn NotificationService>,
     /// Wallpaper asset/library service.
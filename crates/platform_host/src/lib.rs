@@ -12,10 +12,14 @@
 #![warn(missing_docs, rustdoc::broken_intra_doc_links)]
 
 pub mod cache;
+pub mod clipboard;
 pub mod external_url;
 pub mod fs;
 pub mod host;
+pub mod logs;
+pub mod network;
 pub mod notifications;
+pub mod permissions;
 pub mod session;
 pub mod storage;
 pub mod terminal_process;
@@ -26,17 +30,45 @@ pub use cache::{
     cache_get_json_with, cache_put_json_with, ContentCache, ContentCacheFuture, MemoryContentCache,
     NoopContentCache,
 };
+pub use clipboard::{ClipboardFuture, ClipboardService, NoopClipboardService};
 pub use external_url::{ExternalUrlFuture, ExternalUrlService, NoopExternalUrlService};
-pub use fs::path::normalize_virtual_path;
+pub use fs::archive::{
+    archive_top_level_names, export_zip_with, import_zip_with, ExplorerArchiveConflict,
+    ExplorerArchiveImport, EXPLORER_ARCHIVE_MAX_BYTES,
+};
+pub use fs::batch::apply_batch_with;
+pub use fs::mime::{explorer_mime_type, mime_from_name, sniff_mime, EXPLORER_DEFAULT_MIME};
+pub use fs::path::{fresh_entry_name, normalize_virtual_path};
+pub use fs::properties::{
+    folder_size_with, sha256_file_with, top_level_folder_sizes_with, ExplorerFolderSize,
+    EXPLORER_CHECKSUM_CHUNK_BYTES,
+};
 pub use fs::service::{ExplorerFsFuture, ExplorerFsService, NoopExplorerFsService};
+pub use fs::stream::{ExplorerReadStream, ExplorerWriteStream, EXPLORER_STREAM_CHUNK_BYTES};
+pub use fs::trash::{
+    delete_from_trash_with, empty_trash_with, is_trash_path, list_trash_with, move_to_trash_with,
+    restore_from_trash_with, ExplorerTrashEntry, EXPLORER_TRASH_DIR,
+};
 pub use fs::types::{
-    explorer_preview_cache_key, ExplorerBackend, ExplorerBackendStatus, ExplorerEntry,
-    ExplorerEntryKind, ExplorerFileReadResult, ExplorerListResult, ExplorerMetadata,
-    ExplorerPermissionMode, ExplorerPermissionState, ExplorerPrefs, EXPLORER_CACHE_NAME,
+    explorer_preview_cache_key, ExplorerBackend, ExplorerBackendStatus, ExplorerBatchOp,
+    ExplorerBatchResult, ExplorerEntry, ExplorerEntryKind, ExplorerFileBytesResult,
+    ExplorerFileChunk, ExplorerFileReadResult, ExplorerImportFile, ExplorerListPage,
+    ExplorerListResult, ExplorerMetadata, ExplorerMount, ExplorerPermissionMode,
+    ExplorerPermissionState, ExplorerPrefs, ExplorerSortDirection, ExplorerSortKey,
+    ExplorerStorageUsage, ExplorerViewMode, EXPLORER_CACHE_NAME, EXPLORER_MOUNT_DIR,
     EXPLORER_PREFS_KEY,
 };
+pub use fs::watch::{change_affects, ExplorerChangeCallback, ExplorerWatch, ExplorerWatchRegistry};
 pub use host::{CapabilityError, CapabilityStatus, HostCapabilities, HostServices, HostStrategy};
+pub use logs::{
+    LogBuffers, LogLevel, LogListener, LogQuery, LogRecord, LogRegistry, LOGS_PREFS_KEY,
+    LOG_BUFFER_CAPACITY,
+};
+pub use network::{
+    HttpFuture, HttpMethod, HttpRequest, HttpResponse, HttpService, NoopHttpService,
+};
 pub use notifications::{NoopNotificationService, NotificationFuture, NotificationService};
+pub use permissions::{PermissionGrants, PermissionsRegistry, PERMISSIONS_PREFS_KEY};
 pub use session::{session_store, MemorySessionStore};
 pub use storage::app_state::{
     build_app_state_envelope, load_app_state_typed_with, load_app_state_with_migration,
@@ -49,6 +81,7 @@ pub use storage::app_state::{
 pub use storage::prefs::{
     load_pref_with, save_pref_with, MemoryPrefsStore, NoopPrefsStore, PrefsStore, PrefsStoreFuture,
 };
+pub use storage::usage::{total_usage_bytes, StorageUsageEntry};
 pub use terminal_process::{
     NoopTerminalProcessService, TerminalEvent, TerminalProcessFuture, TerminalProcessService,
     TerminalResizeRequest, TerminalSessionId, TerminalWriteRequest,
@@ -0,0 +1,404 @@
+//! Leveled, structured log records kept in per-source ring buffers.
+//!
+//! Each source (an app id, or `system.desktop` for the runtime itself) keeps its newest
+//! [`LOG_BUFFER_CAPACITY`] records. Buffers live in memory unless persistence is switched on, in
+//! which case they are written to typed prefs so they survive a reload.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{load_pref_with, save_pref_with, unix_time_ms_now, PrefsStore};
+
+/// Prefs key holding the persisted [`LogBuffers`].
+pub const LOGS_PREFS_KEY: &str = "system.logs.v1";
+
+/// Number of records each source keeps before the oldest are dropped.
+pub const LOG_BUFFER_CAPACITY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Severity of a [`LogRecord`], ordered from least to most severe.
+pub enum LogLevel {
+    /// Diagnostic detail.
+    Debug,
+    /// Normal operation.
+    Info,
+    /// Recoverable problem.
+    Warn,
+    /// Failed operation.
+    Error,
+}
+
+impl LogLevel {
+    /// Every level, least severe first.
+    pub const ALL: [Self; 4] = [Self::Debug, Self::Info, Self::Warn, Self::Error];
+
+    /// Returns the stable lowercase id of the level.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    /// Parses a level id such as `warn`.
+    pub fn from_id(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.as_str() == raw)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// One log line with its source and structured fields.
+pub struct LogRecord {
+    /// Registry-wide sequence number; later records have larger numbers.
+    pub seq: u64,
+    /// Unix timestamp in milliseconds when the record was made.
+    pub unix_ms: u64,
+    /// Severity.
+    pub level: LogLevel,
+    /// App id or runtime component that logged the record.
+    pub source: String,
+    /// Human-readable message.
+    pub message: String,
+    /// Structured key/value context.
+    #[serde(default)]
+    pub fields: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Persisted form of a [`LogRegistry`].
+pub struct LogBuffers {
+    /// Whether records are written to prefs as they are made.
+    #[serde(default)]
+    pub persist: bool,
+    /// Records per source, oldest first; empty unless `persist` is set.
+    #[serde(default)]
+    pub sources: BTreeMap<String, VecDeque<LogRecord>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Filter for [`LogRegistry::tail`].
+pub struct LogQuery {
+    /// Only records from this source.
+    pub source: Option<String>,
+    /// Only records at or above this level.
+    pub min_level: Option<LogLevel>,
+    /// Only the newest `limit` matching records.
+    pub limit: Option<usize>,
+}
+
+/// Callback run for every record made through a [`LogRegistry`].
+pub type LogListener = Rc<dyn Fn(&LogRecord)>;
+
+/// Shared log buffers for one desktop session.
+///
+/// Clones share the same buffers. Recording is synchronous; callers that want persisted records
+/// to reach prefs await [`Self::persist`] afterwards, claiming the write with
+/// [`Self::claim_persist`] so a burst of records costs one write.
+#[derive(Clone)]
+pub struct LogRegistry {
+    prefs: Rc<dyn PrefsStore>,
+    buffers: Rc<RefCell<LogBuffers>>,
+    next_seq: Rc<Cell<u64>>,
+    listener: Rc<RefCell<Option<LogListener>>>,
+    persist_pending: Rc<Cell<bool>>,
+}
+
+impl LogRegistry {
+    /// Creates an empty, non-persistent registry backed by `prefs`; call [`Self::load`] to read
+    /// persisted records.
+    pub fn new(prefs: Rc<dyn PrefsStore>) -> Self {
+        Self {
+            prefs,
+            buffers: Rc::new(RefCell::new(LogBuffers::default())),
+            next_seq: Rc::new(Cell::new(1)),
+            listener: Rc::new(RefCell::new(None)),
+            persist_pending: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// Reads persisted records into the registry, ahead of anything recorded since it was created.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the prefs store or deserialization fails; the registry is left as it
+    /// was.
+    pub async fn load(&self) -> Result<(), String> {
+        let loaded: LogBuffers = load_pref_with(self.prefs.as_ref(), LOGS_PREFS_KEY)
+            .await?
+            .unwrap_or_default();
+        let mut buffers = self.buffers.borrow_mut();
+        buffers.persist = loaded.persist;
+        if !loaded.persist {
+            return Ok(());
+        }
+        let offset = loaded
+            .sources
+            .values()
+            .flatten()
+            .map(|record| record.seq)
+            .max()
+            .unwrap_or(0);
+        for record in buffers.sources.values_mut().flatten() {
+            record.seq += offset;
+        }
+        for (source, mut records) in loaded.sources {
+            records.extend(buffers.sources.remove(&source).unwrap_or_default());
+            let overflow = records.len().saturating_sub(LOG_BUFFER_CAPACITY);
+            records.drain(..overflow);
+            buffers.sources.insert(source, records);
+        }
+        self.next_seq.set(self.next_seq.get() + offset);
+        Ok(())
+    }
+
+    /// Replaces the listener told about every new record.
+    pub fn set_listener(&self, listener: LogListener) {
+        *self.listener.borrow_mut() = Some(listener);
+    }
+
+    /// Appends a record for `source`, dropping its oldest record past [`LOG_BUFFER_CAPACITY`].
+    pub fn record(
+        &self,
+        source: &str,
+        level: LogLevel,
+        message: impl Into<String>,
+        fields: BTreeMap<String, Value>,
+    ) -> LogRecord {
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq + 1);
+        let record = LogRecord {
+            seq,
+            unix_ms: unix_time_ms_now(),
+            level,
+            source: source.to_string(),
+            message: message.into(),
+            fields,
+        };
+        {
+            let mut buffers = self.buffers.borrow_mut();
+            let records = buffers.sources.entry(source.to_string()).or_default();
+            if records.len() >= LOG_BUFFER_CAPACITY {
+                records.pop_front();
+            }
+            records.push_back(record.clone());
+        }
+        let listener = self.listener.borrow().clone();
+        if let Some(listener) = listener {
+            listener(&record);
+        }
+        record
+    }
+
+    /// Returns matching records across sources, oldest first.
+    pub fn tail(&self, query: &LogQuery) -> Vec<LogRecord> {
+        let buffers = self.buffers.borrow();
+        let mut records = buffers
+            .sources
+            .iter()
+            .filter(|(source, _)| query.source.as_deref().is_none_or(|want| want == *source))
+            .flat_map(|(_, records)| records.iter())
+            .filter(|record| query.min_level.is_none_or(|min| record.level >= min))
+            .cloned()
+            .collect::<Vec<_>>();
+        records.sort_by_key(|record| record.seq);
+        if let Some(limit) = query.limit {
+            records.drain(..records.len().saturating_sub(limit));
+        }
+        records
+    }
+
+    /// Returns the sources that have records, sorted.
+    pub fn sources(&self) -> Vec<String> {
+        self.buffers.borrow().sources.keys().cloned().collect()
+    }
+
+    /// Returns whether records are persisted to prefs.
+    pub fn is_persistent(&self) -> bool {
+        self.buffers.borrow().persist
+    }
+
+    /// Switches persistence on or off and writes the change; switching off also drops the
+    /// persisted records, while the in-memory ones stay.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting fails.
+    pub async fn set_persistent(&self, persist: bool) -> Result<(), String> {
+        self.buffers.borrow_mut().persist = persist;
+        self.save().await
+    }
+
+    /// Drops the records of `source`, or of every source, and persists the change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting fails.
+    pub async fn clear(&self, source: Option<&str>) -> Result<(), String> {
+        {
+            let mut buffers = self.buffers.borrow_mut();
+            match source {
+                Some(source) => {
+                    buffers.sources.remove(source);
+                }
+                None => buffers.sources.clear(),
+            }
+        }
+        self.persist().await
+    }
+
+    /// Claims the write that will persist records made since the last one.
+    ///
+    /// Returns `true` when persistence is on and no write is pending, in which case the caller
+    /// should run [`Self::persist`]. Later calls return `false` until that write snapshots the
+    /// buffers, so records made meanwhile ride along with it.
+    pub fn claim_persist(&self) -> bool {
+        self.is_persistent() && !self.persist_pending.replace(true)
+    }
+
+    /// Writes the buffers to prefs when persistence is on; otherwise does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting fails.
+    pub async fn persist(&self) -> Result<(), String> {
+        self.persist_pending.set(false);
+        if !self.is_persistent() {
+            return Ok(());
+        }
+        self.save().await
+    }
+
+    async fn save(&self) -> Result<(), String> {
+        let buffers = {
+            let buffers = self.buffers.borrow();
+            if buffers.persist {
+                buffers.clone()
+            } else {
+                LogBuffers::default()
+            }
+        };
+        save_pref_with(self.prefs.as_ref(), LOGS_PREFS_KEY, &buffers).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::MemoryPrefsStore;
+
+    fn registry() -> (Rc<MemoryPrefsStore>, LogRegistry) {
+        let prefs = Rc::new(MemoryPrefsStore::default());
+        (prefs.clone(), LogRegistry::new(prefs))
+    }
+
+    #[test]
+    fn tail_merges_sources_and_filters_by_level_and_limit() {
+        let (_, logs) = registry();
+        logs.record("acme.a", LogLevel::Info, "one", BTreeMap::new());
+        logs.record("acme.b", LogLevel::Error, "two", BTreeMap::new());
+        logs.record("acme.a", LogLevel::Warn, "three", BTreeMap::new());
+        logs.record("acme.a", LogLevel::Debug, "four", BTreeMap::new());
+
+        let messages = |query: LogQuery| {
+            logs.tail(&query)
+                .into_iter()
+                .map(|record| record.message)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(LogQuery::default()),
+            ["one", "two", "three", "four"]
+        );
+        assert_eq!(
+            messages(LogQuery {
+                min_level: Some(LogLevel::Warn),
+                ..LogQuery::default()
+            }),
+            ["two", "three"]
+        );
+        assert_eq!(
+            messages(LogQuery {
+                source: Some("acme.a".to_string()),
+                limit: Some(2),
+                ..LogQuery::default()
+            }),
+            ["three", "four"]
+        );
+        assert_eq!(logs.sources(), ["acme.a", "acme.b"]);
+        assert_eq!(LogLevel::from_id("warn"), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::from_id("loud"), None);
+    }
+
+    #[test]
+    fn buffers_drop_their_oldest_records_past_capacity() {
+        let (_, logs) = registry();
+        for index in 0..LOG_BUFFER_CAPACITY + 5 {
+            logs.record("acme.a", LogLevel::Info, index.to_string(), BTreeMap::new());
+        }
+        logs.record("acme.b", LogLevel::Info, "other", BTreeMap::new());
+
+        let records = logs.tail(&LogQuery {
+            source: Some("acme.a".to_string()),
+            ..LogQuery::default()
+        });
+        assert_eq!(records.len(), LOG_BUFFER_CAPACITY);
+        assert_eq!(records[0].message, "5");
+        assert_eq!(
+            logs.tail(&LogQuery::default()).len(),
+            LOG_BUFFER_CAPACITY + 1
+        );
+    }
+
+    #[test]
+    fn records_reach_prefs_only_while_persistence_is_on() {
+        let (prefs, logs) = registry();
+        let mut fields = BTreeMap::new();
+        fields.insert("path".to_string(), Value::from("/notes.md"));
+        logs.record("acme.a", LogLevel::Warn, "kept", fields.clone());
+        block_on(logs.persist()).expect("persist");
+        block_on(LogRegistry::new(prefs.clone()).load()).expect("load");
+        assert!(LogRegistry::new(prefs.clone())
+            .tail(&LogQuery::default())
+            .is_empty());
+
+        block_on(logs.set_persistent(true)).expect("enable");
+        let reloaded = LogRegistry::new(prefs.clone());
+        reloaded.record("acme.b", LogLevel::Info, "before load", BTreeMap::new());
+        block_on(reloaded.load()).expect("load");
+        assert!(reloaded.is_persistent());
+        let records = reloaded.tail(&LogQuery::default());
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.message.as_str())
+                .collect::<Vec<_>>(),
+            ["kept", "before load"]
+        );
+        assert_eq!(records[0].fields, fields);
+        let next = reloaded.record("acme.a", LogLevel::Info, "after load", BTreeMap::new());
+        assert!(next.seq > records[1].seq);
+
+        assert!(reloaded.claim_persist());
+        assert!(!reloaded.claim_persist());
+        block_on(reloaded.persist()).expect("persist");
+        assert!(reloaded.claim_persist());
+
+        block_on(reloaded.set_persistent(false)).expect("disable");
+        let cleared = LogRegistry::new(prefs);
+        block_on(cleared.load()).expect("load");
+        assert!(!cleared.is_persistent());
+        assert!(!cleared.claim_persist());
+        assert!(cleared.tail(&LogQuery::default()).is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+//! HTTP host-service contracts.
+
+use std::{future::Future, pin::Pin};
+
+use serde::{Deserialize, Serialize};
+
+/// Object-safe boxed future used by [`HttpService`].
+pub type HttpFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// HTTP method supported by [`HttpService`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    /// `GET` request without a body.
+    Get,
+    /// `POST` request with an optional body.
+    Post,
+}
+
+/// Outgoing HTTP request with the limits the host must enforce.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpRequest {
+    /// Request method.
+    pub method: HttpMethod,
+    /// Absolute `http` or `https` URL.
+    pub url: String,
+    /// Request headers in send order.
+    pub headers: Vec<(String, String)>,
+    /// Text body sent with `POST` requests.
+    pub body: Option<String>,
+    /// Largest response body, in bytes, the host reads before failing the request.
+    pub max_response_bytes: u64,
+    /// Time, in milliseconds, after which the host aborts the request.
+    pub timeout_ms: u64,
+}
+
+/// HTTP response returned by [`HttpService`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers with lowercase names.
+    pub headers: Vec<(String, String)>,
+    /// Response body decoded as UTF-8 text.
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Returns whether the status is in the `2xx` range.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the first header named `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Host service for sending HTTP requests on behalf of apps.
+pub trait HttpService {
+    /// Sends `request`, failing when it times out or its response exceeds the size limit.
+    fn send<'a>(&'a self, request: &'a HttpRequest)
+        -> HttpFuture<'a, Result<HttpResponse, String>>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// No-op HTTP service for unsupported targets.
+pub struct NoopHttpService;
+
+impl HttpService for NoopHttpService {
+    fn send<'a>(
+        &'a self,
+        _request: &'a HttpRequest,
+    ) -> HttpFuture<'a, Result<HttpResponse, String>> {
+        Box::pin(async { Err("network requests are unavailable on this host".to_string()) })
+    }
+}
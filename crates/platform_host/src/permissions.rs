@@ -0,0 +1,192 @@
+//! Per-app capability grants made at runtime and persisted in typed prefs.
+//!
+//! Apps declare most capabilities in their manifests; the grants kept here are the ones users
+//! allowed afterwards. Capabilities are stored by their kebab-case manifest ids so this crate does
+//! not depend on the app contract.
+
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{load_pref_with, save_pref_with, PrefsStore};
+
+/// Prefs key holding the persisted [`PermissionGrants`].
+pub const PERMISSIONS_PREFS_KEY: &str = "system.permissions.v1";
+
+/// Runtime capability grants keyed by app id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionGrants {
+    /// Granted capability ids per app id, sorted; apps without grants are absent.
+    #[serde(default)]
+    pub apps: BTreeMap<String, Vec<String>>,
+}
+
+impl PermissionGrants {
+    /// Returns the capability ids granted to `app_id`.
+    pub fn granted(&self, app_id: &str) -> &[String] {
+        self.apps.get(app_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns whether `capability` is granted to `app_id`.
+    pub fn is_granted(&self, app_id: &str, capability: &str) -> bool {
+        self.granted(app_id)
+            .iter()
+            .any(|granted| granted == capability)
+    }
+
+    /// Grants `capability` to `app_id`, returning whether the grants changed.
+    pub fn grant(&mut self, app_id: &str, capability: &str) -> bool {
+        let granted = self.apps.entry(app_id.to_string()).or_default();
+        match granted.binary_search_by(|existing| existing.as_str().cmp(capability)) {
+            Ok(_) => false,
+            Err(index) => {
+                granted.insert(index, capability.to_string());
+                true
+            }
+        }
+    }
+
+    /// Revokes `capability` from `app_id`, returning whether it was granted.
+    pub fn revoke(&mut self, app_id: &str, capability: &str) -> bool {
+        let Some(granted) = self.apps.get_mut(app_id) else {
+            return false;
+        };
+        let before = granted.len();
+        granted.retain(|existing| existing != capability);
+        let changed = granted.len() != before;
+        if granted.is_empty() {
+            self.apps.remove(app_id);
+        }
+        changed
+    }
+}
+
+/// Shared, persisted view of the [`PermissionGrants`] for one desktop session.
+///
+/// Clones share the same grants. Changes are written back to the prefs store before the async
+/// methods resolve.
+#[derive(Clone)]
+pub struct PermissionsRegistry {
+    prefs: Rc<dyn PrefsStore>,
+    grants: Rc<RefCell<PermissionGrants>>,
+}
+
+impl PermissionsRegistry {
+    /// Creates an empty registry persisted through `prefs`; call [`Self::load`] to read it.
+    pub fn new(prefs: Rc<dyn PrefsStore>) -> Self {
+        Self {
+            prefs,
+            grants: Rc::new(RefCell::new(PermissionGrants::default())),
+        }
+    }
+
+    /// Reads the persisted grants into the registry and returns them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the prefs store or deserialization fails; the registry is left as it
+    /// was.
+    pub async fn load(&self) -> Result<PermissionGrants, String> {
+        let loaded: PermissionGrants = load_pref_with(self.prefs.as_ref(), PERMISSIONS_PREFS_KEY)
+            .await?
+            .unwrap_or_default();
+        *self.grants.borrow_mut() = loaded.clone();
+        Ok(loaded)
+    }
+
+    /// Returns the current grants.
+    pub fn snapshot(&self) -> PermissionGrants {
+        self.grants.borrow().clone()
+    }
+
+    /// Returns the capability ids granted to `app_id`.
+    pub fn granted(&self, app_id: &str) -> Vec<String> {
+        self.grants.borrow().granted(app_id).to_vec()
+    }
+
+    /// Grants `capability` to `app_id` and persists the change, returning whether anything changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting fails.
+    pub async fn grant(&self, app_id: &str, capability: &str) -> Result<bool, String> {
+        let changed = self.grants.borrow_mut().grant(app_id, capability);
+        if changed {
+            self.persist().await?;
+        }
+        Ok(changed)
+    }
+
+    /// Revokes `capability` from `app_id` and persists the change, returning whether it was
+    /// granted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting fails.
+    pub async fn revoke(&self, app_id: &str, capability: &str) -> Result<bool, String> {
+        let changed = self.grants.borrow_mut().revoke(app_id, capability);
+        if changed {
+            self.persist().await?;
+        }
+        Ok(changed)
+    }
+
+    /// Replaces every grant with `grants` and persists them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when persisting fails.
+    pub async fn replace(&self, grants: PermissionGrants) -> Result<(), String> {
+        *self.grants.borrow_mut() = grants;
+        self.persist().await
+    }
+
+    async fn persist(&self) -> Result<(), String> {
+        let grants = self.snapshot();
+        save_pref_with(self.prefs.as_ref(), PERMISSIONS_PREFS_KEY, &grants).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::MemoryPrefsStore;
+
+    #[test]
+    fn grants_stay_sorted_and_drop_empty_apps() {
+        let mut grants = PermissionGrants::default();
+        assert!(grants.grant("acme.weather", "network"));
+        assert!(grants.grant("acme.weather", "notifications"));
+        assert!(grants.grant("acme.weather", "external-url"));
+        assert!(!grants.grant("acme.weather", "network"));
+        assert_eq!(
+            grants.granted("acme.weather"),
+            ["external-url", "network", "notifications"]
+        );
+
+        assert!(grants.revoke("acme.weather", "network"));
+        assert!(!grants.revoke("acme.weather", "network"));
+        assert!(!grants.is_granted("acme.weather", "network"));
+        assert!(grants.revoke("acme.weather", "notifications"));
+        assert!(grants.revoke("acme.weather", "external-url"));
+        assert!(grants.apps.is_empty());
+        assert!(grants.granted("acme.other").is_empty());
+    }
+
+    #[test]
+    fn registry_persists_changes_through_prefs() {
+        let prefs = Rc::new(MemoryPrefsStore::default());
+        let registry = PermissionsRegistry::new(prefs.clone());
+        assert!(block_on(registry.grant("acme.weather", "network")).expect("grant"));
+        assert!(block_on(registry.grant("acme.chat", "notifications")).expect("grant"));
+        assert!(block_on(registry.revoke("acme.chat", "notifications")).expect("revoke"));
+
+        let reloaded = PermissionsRegistry::new(prefs);
+        let grants = block_on(reloaded.load()).expect("load");
+        assert_eq!(grants, registry.snapshot());
+        assert_eq!(reloaded.granted("acme.weather"), ["network"]);
+        assert!(reloaded.granted("acme.chat").is_empty());
+    }
+}
@@ -5,6 +5,8 @@ use std::{cell::RefCell, collections::HashMap, future::Future, pin::Pin, rc::Rc}
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 
+use super::usage::StorageUsageEntry;
+
 /// Version for [`AppStateEnvelope`] metadata serialization.
 pub const APP_STATE_ENVELOPE_VERSION: u32 = 1;
 /// Namespace used by the desktop runtime durable snapshot.
@@ -75,6 +77,22 @@ pub trait AppStateStore {
     fn list_app_state_namespaces<'a>(
         &'a self,
     ) -> AppStateStoreFuture<'a, Result<Vec<String>, String>>;
+
+    /// Reports the serialized size of each stored namespace's envelope, ordered by namespace.
+    fn app_state_usage<'a>(
+        &'a self,
+    ) -> AppStateStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        Box::pin(async move {
+            let mut usage = Vec::new();
+            for namespace in self.list_app_state_namespaces().await? {
+                if let Some(envelope) = self.load_app_state_envelope(&namespace).await? {
+                    let bytes = serde_json::to_vec(&envelope).map_err(|e| e.to_string())?;
+                    usage.push(StorageUsageEntry::single(namespace, bytes.len()));
+                }
+            }
+            Ok(usage)
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -484,4 +502,28 @@ mod tests {
             Vec::<String>::new()
         );
     }
+
+    #[test]
+    fn app_state_usage_sizes_each_stored_envelope() {
+        let store = MemoryAppStateStore::default();
+        let store_obj: &dyn AppStateStore = &store;
+        let envelope = AppStateEnvelope {
+            envelope_version: APP_STATE_ENVELOPE_VERSION,
+            namespace: "system.desktop".to_string(),
+            schema_version: 1,
+            updated_at_unix_ms: 1,
+            payload: json!({"windows": []}),
+        };
+        block_on(store_obj.save_app_state_envelope(&envelope)).expect("save");
+
+        let usage = block_on(store_obj.app_state_usage()).expect("usage");
+        let expected = serde_json::to_vec(&envelope).expect("serialize").len();
+        assert_eq!(
+            usage,
+            vec![crate::StorageUsageEntry::single("system.desktop", expected)]
+        );
+        assert!(block_on(NoopAppStateStore.app_state_usage())
+            .expect("noop usage")
+            .is_empty());
+    }
 }
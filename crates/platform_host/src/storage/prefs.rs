@@ -4,6 +4,8 @@ use std::{cell::RefCell, collections::HashMap, future::Future, pin::Pin, rc::Rc}
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use super::usage::StorageUsageEntry;
+
 /// Object-safe boxed future used by [`PrefsStore`] async methods.
 pub type PrefsStoreFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
 
@@ -24,6 +26,9 @@ pub trait PrefsStore {
 
     /// Deletes a preference key.
     fn delete_pref<'a>(&'a self, key: &'a str) -> PrefsStoreFuture<'a, Result<(), String>>;
+
+    /// Reports the stored size of every preference key, ordered by key.
+    fn prefs_usage<'a>(&'a self) -> PrefsStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>>;
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -49,6 +54,10 @@ impl PrefsStore for NoopPrefsStore {
     fn delete_pref<'a>(&'a self, _key: &'a str) -> PrefsStoreFuture<'a, Result<(), String>> {
         Box::pin(async { Ok(()) })
     }
+
+    fn prefs_usage<'a>(&'a self) -> PrefsStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -84,6 +93,19 @@ impl PrefsStore for MemoryPrefsStore {
             Ok(())
         })
     }
+
+    fn prefs_usage<'a>(&'a self) -> PrefsStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        Box::pin(async move {
+            let mut usage = self
+                .inner
+                .borrow()
+                .iter()
+                .map(|(key, raw)| StorageUsageEntry::single(key.clone(), raw.len()))
+                .collect::<Vec<_>>();
+            usage.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(usage)
+        })
+    }
 }
 
 /// Loads and deserializes a typed preference value through a [`PrefsStore`] implementation.
@@ -175,5 +197,23 @@ mod tests {
         assert_eq!(block_on(store_obj.load_pref("k")).expect("load"), None);
         block_on(store_obj.save_pref("k", "{}")).expect("save");
         block_on(store_obj.delete_pref("k")).expect("delete");
+        assert!(block_on(store_obj.prefs_usage()).expect("usage").is_empty());
+    }
+
+    #[test]
+    fn memory_prefs_store_reports_usage_per_key() {
+        let store = MemoryPrefsStore::default();
+        block_on(store.save_pref("system.logs.v1", "[1,2]")).expect("save logs");
+        block_on(store.save_pref("system.files.associations", "{}")).expect("save files");
+
+        let usage = block_on(store.prefs_usage()).expect("usage");
+        assert_eq!(
+            usage,
+            vec![
+                StorageUsageEntry::single("system.files.associations", 2),
+                StorageUsageEntry::single("system.logs.v1", 5),
+            ]
+        );
+        assert_eq!(crate::total_usage_bytes(&usage), 7);
     }
 }
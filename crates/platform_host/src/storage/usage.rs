@@ -0,0 +1,30 @@
+//! Storage usage reporting shared by the app-state, prefs, and cache stores.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Space one app-state namespace, preference key, or cache name takes up in a store.
+pub struct StorageUsageEntry {
+    /// Namespace, preference key, or cache name.
+    pub name: String,
+    /// Stored records under `name`: cache entries, or `1` for a namespace or key.
+    pub entries: u64,
+    /// Stored payload size in bytes.
+    pub bytes: u64,
+}
+
+impl StorageUsageEntry {
+    /// Creates an entry for a single stored value of `bytes` bytes.
+    pub fn single(name: impl Into<String>, bytes: usize) -> Self {
+        Self {
+            name: name.into(),
+            entries: 1,
+            bytes: bytes as u64,
+        }
+    }
+}
+
+/// Adds up the bytes of `entries`.
+pub fn total_usage_bytes(entries: &[StorageUsageEntry]) -> u64 {
+    entries.iter().map(|entry| entry.bytes).sum()
+}
@@ -1,24 +1,28 @@
 use std::rc::Rc;
 
 use platform_host::{
-    AppStateEnvelope, AppStateStore, AppStateStoreFuture, ContentCache, ContentCacheFuture,
-    ExplorerBackendStatus, ExplorerFileReadResult, ExplorerFsFuture, ExplorerFsService,
-    ExplorerListResult, ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState,
-    ExternalUrlFuture, ExternalUrlService, HostCapabilities, HostServices, HostStrategy,
-    NoopAppStateStore, NoopContentCache, NoopExplorerFsService, NoopExternalUrlService,
-    NoopNotificationService, NoopPrefsStore, NoopWallpaperAssetService, NotificationFuture,
-    NotificationService, PrefsStore, PrefsStoreFuture, ResolvedWallpaperSource,
-    WallpaperAssetDeleteResult, WallpaperAssetFuture, WallpaperAssetMetadataPatch,
-    WallpaperAssetRecord, WallpaperAssetService, WallpaperCollection,
+    AppStateEnvelope, AppStateStore, AppStateStoreFuture, ClipboardFuture, ClipboardService,
+    ContentCache, ContentCacheFuture, ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult,
+    ExplorerChangeCallback, ExplorerFileBytesResult, ExplorerFileChunk, ExplorerFileReadResult,
+    ExplorerFsFuture, ExplorerFsService, ExplorerImportFile, ExplorerListPage, ExplorerListResult,
+    ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState, ExplorerStorageUsage,
+    ExplorerWatch, ExternalUrlFuture, ExternalUrlService, HostCapabilities, HostServices,
+    HostStrategy, HttpFuture, HttpRequest, HttpResponse, HttpService, NoopAppStateStore,
+    NoopClipboardService, NoopContentCache, NoopExplorerFsService, NoopExternalUrlService,
+    NoopHttpService, NoopNotificationService, NoopPrefsStore, NoopWallpaperAssetService,
+    NotificationFuture, NotificationService, PrefsStore, PrefsStoreFuture, ResolvedWallpaperSource,
+    StorageUsageEntry, WallpaperAssetDeleteResult, WallpaperAssetFuture,
+    WallpaperAssetMetadataPatch, WallpaperAssetRecord, WallpaperAssetService, WallpaperCollection,
     WallpaperCollectionDeleteResult, WallpaperImportRequest, WallpaperImportResult,
     WallpaperLibrarySnapshot, WallpaperSelection,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    TauriAppStateStore, TauriContentCache, TauriExplorerFsService, TauriExternalUrlService,
-    TauriNotificationService, TauriPrefsStore, WebAppStateStore, WebContentCache,
-    WebExplorerFsService, WebExternalUrlService, WebNotificationService, WebPrefsStore,
+    TauriAppStateStore, TauriClipboardService, TauriContentCache, TauriExplorerFsService,
+    TauriExternalUrlService, TauriHttpService, TauriNotificationService, TauriPrefsStore,
+    WebAppStateStore, WebClipboardService, WebContentCache, WebExplorerFsService,
+    WebExternalUrlService, WebHttpService, WebNotificationService, WebPrefsStore,
     WebWallpaperAssetService,
 };
 
@@ -97,6 +101,16 @@ impl AppStateStore for AppStateStoreAdapter {
             Self::DesktopStub(store) => store.list_app_state_namespaces(),
         }
     }
+
+    fn app_state_usage<'a>(
+        &'a self,
+    ) -> AppStateStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        match self {
+            Self::Browser(store) => store.app_state_usage(),
+            Self::DesktopTauri(store) => store.app_state_usage(),
+            Self::DesktopStub(store) => store.app_state_usage(),
+        }
+    }
 }
 
 /// Adapter enum that erases the concrete content-cache backend behind [`ContentCache`].
@@ -147,6 +161,25 @@ impl ContentCache for ContentCacheAdapter {
             Self::DesktopStub(store) => store.delete(cache_name, key),
         }
     }
+
+    fn cache_usage<'a>(&'a self) -> ContentCacheFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        match self {
+            Self::Browser(store) => store.cache_usage(),
+            Self::DesktopTauri(store) => store.cache_usage(),
+            Self::DesktopStub(store) => store.cache_usage(),
+        }
+    }
+
+    fn clear_cache<'a>(
+        &'a self,
+        cache_name: &'a str,
+    ) -> ContentCacheFuture<'a, Result<(), String>> {
+        match self {
+            Self::Browser(store) => store.clear_cache(cache_name),
+            Self::DesktopTauri(store) => store.clear_cache(cache_name),
+            Self::DesktopStub(store) => store.clear_cache(cache_name),
+        }
+    }
 }
 
 /// Adapter enum that erases the concrete explorer/filesystem backend behind
@@ -180,6 +213,17 @@ impl ExplorerFsService for ExplorerFsServiceAdapter {
         }
     }
 
+    fn unmount_native_directory<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+        match self {
+            Self::Browser(store) => store.unmount_native_directory(path),
+            Self::DesktopTauri(store) => store.unmount_native_directory(path),
+            Self::DesktopStub(store) => store.unmount_native_directory(path),
+        }
+    }
+
     fn request_permission<'a>(
         &'a self,
         mode: ExplorerPermissionMode,
@@ -202,6 +246,19 @@ impl ExplorerFsService for ExplorerFsServiceAdapter {
         }
     }
 
+    fn list_dir_page<'a>(
+        &'a self,
+        path: &'a str,
+        offset: usize,
+        limit: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>> {
+        match self {
+            Self::Browser(store) => store.list_dir_page(path, offset, limit),
+            Self::DesktopTauri(store) => store.list_dir_page(path, offset, limit),
+            Self::DesktopStub(store) => store.list_dir_page(path, offset, limit),
+        }
+    }
+
     fn read_text_file<'a>(
         &'a self,
         path: &'a str,
@@ -213,6 +270,30 @@ impl ExplorerFsService for ExplorerFsServiceAdapter {
         }
     }
 
+    fn read_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>> {
+        match self {
+            Self::Browser(store) => store.read_file_bytes(path),
+            Self::DesktopTauri(store) => store.read_file_bytes(path),
+            Self::DesktopStub(store) => store.read_file_bytes(path),
+        }
+    }
+
+    fn read_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        len: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> {
+        match self {
+            Self::Browser(store) => store.read_file_chunk(path, offset, len),
+            Self::DesktopTauri(store) => store.read_file_chunk(path, offset, len),
+            Self::DesktopStub(store) => store.read_file_chunk(path, offset, len),
+        }
+    }
+
     fn write_text_file<'a>(
         &'a self,
         path: &'a str,
@@ -225,6 +306,53 @@ impl ExplorerFsService for ExplorerFsServiceAdapter {
         }
     }
 
+    fn write_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        match self {
+            Self::Browser(store) => store.write_file_bytes(path, bytes),
+            Self::DesktopTauri(store) => store.write_file_bytes(path, bytes),
+            Self::DesktopStub(store) => store.write_file_bytes(path, bytes),
+        }
+    }
+
+    fn write_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        match self {
+            Self::Browser(store) => store.write_file_chunk(path, offset, bytes),
+            Self::DesktopTauri(store) => store.write_file_chunk(path, offset, bytes),
+            Self::DesktopStub(store) => store.write_file_chunk(path, offset, bytes),
+        }
+    }
+
+    fn pick_import_files<'a>(
+        &'a self,
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>> {
+        match self {
+            Self::Browser(store) => store.pick_import_files(),
+            Self::DesktopTauri(store) => store.pick_import_files(),
+            Self::DesktopStub(store) => store.pick_import_files(),
+        }
+    }
+
+    fn save_download<'a>(
+        &'a self,
+        name: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<(), String>> {
+        match self {
+            Self::Browser(store) => store.save_download(name, bytes),
+            Self::DesktopTauri(store) => store.save_download(name, bytes),
+            Self::DesktopStub(store) => store.save_download(name, bytes),
+        }
+    }
+
     fn create_dir<'a>(
         &'a self,
         path: &'a str,
@@ -260,6 +388,29 @@ impl ExplorerFsService for ExplorerFsServiceAdapter {
         }
     }
 
+    fn rename<'a>(
+        &'a self,
+        from: &'a str,
+        to: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        match self {
+            Self::Browser(store) => store.rename(from, to),
+            Self::DesktopTauri(store) => store.rename(from, to),
+            Self::DesktopStub(store) => store.rename(from, to),
+        }
+    }
+
+    fn batch<'a>(
+        &'a self,
+        ops: &'a [ExplorerBatchOp],
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>> {
+        match self {
+            Self::Browser(store) => store.batch(ops),
+            Self::DesktopTauri(store) => store.batch(ops),
+            Self::DesktopStub(store) => store.batch(ops),
+        }
+    }
+
     fn stat<'a>(&'a self, path: &'a str) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
         match self {
             Self::Browser(store) => store.stat(path),
@@ -267,6 +418,30 @@ impl ExplorerFsService for ExplorerFsServiceAdapter {
             Self::DesktopStub(store) => store.stat(path),
         }
     }
+
+    fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>> {
+        match self {
+            Self::Browser(store) => store.usage(),
+            Self::DesktopTauri(store) => store.usage(),
+            Self::DesktopStub(store) => store.usage(),
+        }
+    }
+
+    fn request_persistent_storage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<bool, String>> {
+        match self {
+            Self::Browser(store) => store.request_persistent_storage(),
+            Self::DesktopTauri(store) => store.request_persistent_storage(),
+            Self::DesktopStub(store) => store.request_persistent_storage(),
+        }
+    }
+
+    fn watch(&self, path: &str, on_change: ExplorerChangeCallback) -> ExplorerWatch {
+        match self {
+            Self::Browser(store) => store.watch(path, on_change),
+            Self::DesktopTauri(store) => store.watch(path, on_change),
+            Self::DesktopStub(store) => store.watch(path, on_change),
+        }
+    }
 }
 
 /// Adapter enum that erases the concrete external URL backend behind [`ExternalUrlService`].
@@ -290,6 +465,51 @@ impl ExternalUrlService for ExternalUrlServiceAdapter {
     }
 }
 
+/// Adapter enum that erases the concrete clipboard backend behind [`ClipboardService`].
+#[derive(Debug, Clone, Copy)]
+pub enum ClipboardServiceAdapter {
+    /// Browser-backed clipboard writes.
+    Browser(WebClipboardService),
+    /// Desktop-webview clipboard writes.
+    DesktopTauri(TauriClipboardService),
+    /// No-op fallback used when desktop transport is intentionally stubbed.
+    DesktopStub(NoopClipboardService),
+}
+
+impl ClipboardService for ClipboardServiceAdapter {
+    fn write_text<'a>(&'a self, text: &'a str) -> ClipboardFuture<'a, Result<(), String>> {
+        match self {
+            Self::Browser(service) => service.write_text(text),
+            Self::DesktopTauri(service) => service.write_text(text),
+            Self::DesktopStub(service) => service.write_text(text),
+        }
+    }
+}
+
+/// Adapter enum that erases the concrete HTTP backend behind [`HttpService`].
+#[derive(Debug, Clone, Copy)]
+pub enum HttpServiceAdapter {
+    /// Browser-backed `fetch` requests.
+    Browser(WebHttpService),
+    /// Desktop-webview `fetch` requests.
+    DesktopTauri(TauriHttpService),
+    /// No-op fallback used when desktop transport is intentionally stubbed.
+    DesktopStub(NoopHttpService),
+}
+
+impl HttpService for HttpServiceAdapter {
+    fn send<'a>(
+        &'a self,
+        request: &'a HttpRequest,
+    ) -> HttpFuture<'a, Result<HttpResponse, String>> {
+        match self {
+            Self::Browser(service) => service.send(request),
+            Self::DesktopTauri(service) => service.send(request),
+            Self::DesktopStub(service) => service.send(request),
+        }
+    }
+}
+
 /// Adapter enum that erases the concrete preferences backend behind [`PrefsStore`].
 #[derive(Debug, Clone, Copy)]
 pub enum PrefsStoreAdapter {
@@ -359,6 +579,14 @@ impl PrefsStore for PrefsStoreAdapter {
             Self::DesktopStub(store) => store.delete_pref(key),
         }
     }
+
+    fn prefs_usage<'a>(&'a self) -> PrefsStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        match self {
+            Self::Browser(store) => store.prefs_usage(),
+            Self::DesktopTauri(store) => store.prefs_usage(),
+            Self::DesktopStub(store) => store.prefs_usage(),
+        }
+    }
 }
 
 /// Adapter enum that erases the concrete notification backend behind
@@ -556,6 +784,24 @@ pub fn external_url_service() -> ExternalUrlServiceAdapter {
     }
 }
 
+/// Builds the clipboard adapter for the compile-time selected host strategy.
+pub fn clipboard_service() -> ClipboardServiceAdapter {
+    match selected_host_strategy() {
+        HostStrategy::Browser => ClipboardServiceAdapter::Browser(WebClipboardService),
+        HostStrategy::DesktopTauri => ClipboardServiceAdapter::DesktopTauri(TauriClipboardService),
+        HostStrategy::DesktopStub => ClipboardServiceAdapter::DesktopStub(NoopClipboardService),
+    }
+}
+
+/// Builds the HTTP adapter for the compile-time selected host strategy.
+pub fn http_service() -> HttpServiceAdapter {
+    match selected_host_strategy() {
+        HostStrategy::Browser => HttpServiceAdapter::Browser(WebHttpService),
+        HostStrategy::DesktopTauri => HttpServiceAdapter::DesktopTauri(TauriHttpService),
+        HostStrategy::DesktopStub => HttpServiceAdapter::DesktopStub(NoopHttpService),
+    }
+}
+
 /// Builds the wallpaper-library adapter for the compile-time selected host strategy.
 pub fn wallpaper_asset_service() -> WallpaperAssetServiceAdapter {
     match selected_host_strategy() {
@@ -586,6 +832,8 @@ pub fn build_host_services() -> HostServices {
         explorer: Rc::new(explorer_fs_service()),
         cache: Rc::new(content_cache()),
         external_urls: Rc::new(external_url_service()),
+        clipboard: Rc::new(clipboard_service()),
+        http: Rc::new(http_service()),
         notifications: Rc::new(notification_service()),
         wallpaper: Rc::new(wallpaper_asset_service()),
         terminal_process: None,
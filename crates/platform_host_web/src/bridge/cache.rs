@@ -1,3 +1,5 @@
+use platform_host::StorageUsageEntry;
+
 pub(crate) async fn cache_put_text(cache_name: &str, key: &str, value: &str) -> Result<(), String> {
     super::interop::cache_put_text(cache_name, key, value).await
 }
@@ -9,3 +11,11 @@ pub(crate) async fn cache_get_text(cache_name: &str, key: &str) -> Result<Option
 pub(crate) async fn cache_delete(cache_name: &str, key: &str) -> Result<(), String> {
     super::interop::cache_delete(cache_name, key).await
 }
+
+pub(crate) async fn cache_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    super::interop::cache_usage().await
+}
+
+pub(crate) async fn cache_clear(cache_name: &str) -> Result<(), String> {
+    super::interop::cache_clear(cache_name).await
+}
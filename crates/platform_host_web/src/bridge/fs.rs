@@ -1,6 +1,8 @@
 use platform_host::{
-    ExplorerBackendStatus, ExplorerFileReadResult, ExplorerListResult, ExplorerMetadata,
-    ExplorerPermissionMode, ExplorerPermissionState,
+    ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult, ExplorerFileBytesResult,
+    ExplorerFileChunk, ExplorerFileReadResult, ExplorerImportFile, ExplorerListPage,
+    ExplorerListResult, ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState,
+    ExplorerStorageUsage,
 };
 
 pub(crate) async fn explorer_status() -> Result<ExplorerBackendStatus, String> {
@@ -11,6 +13,12 @@ pub(crate) async fn explorer_pick_native_directory() -> Result<ExplorerBackendSt
     super::interop::explorer_pick_native_directory().await
 }
 
+pub(crate) async fn explorer_unmount_native_directory(
+    path: &str,
+) -> Result<ExplorerBackendStatus, String> {
+    super::interop::explorer_unmount_native_directory(path).await
+}
+
 pub(crate) async fn explorer_request_permission(
     mode: ExplorerPermissionMode,
 ) -> Result<ExplorerPermissionState, String> {
@@ -21,32 +29,117 @@ pub(crate) async fn explorer_list_dir(path: &str) -> Result<ExplorerListResult,
     super::interop::explorer_list_dir(path).await
 }
 
+pub(crate) async fn explorer_list_dir_page(
+    path: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<ExplorerListPage, String> {
+    super::interop::explorer_list_dir_page(path, offset, limit).await
+}
+
 pub(crate) async fn explorer_read_text_file(path: &str) -> Result<ExplorerFileReadResult, String> {
     super::interop::explorer_read_text_file(path).await
 }
 
+pub(crate) async fn explorer_read_file_bytes(
+    path: &str,
+) -> Result<ExplorerFileBytesResult, String> {
+    super::interop::explorer_read_file_bytes(path).await
+}
+
+pub(crate) async fn explorer_read_file_chunk(
+    path: &str,
+    offset: u64,
+    len: usize,
+) -> Result<ExplorerFileChunk, String> {
+    super::interop::explorer_read_file_chunk(path, offset, len).await
+}
+
 pub(crate) async fn explorer_write_text_file(
     path: &str,
     text: &str,
 ) -> Result<ExplorerMetadata, String> {
-    super::interop::explorer_write_text_file(path, text).await
+    let metadata = super::interop::explorer_write_text_file(path, text).await?;
+    super::watch::explorer_notify_changed(&[path]);
+    Ok(metadata)
+}
+
+pub(crate) async fn explorer_write_file_bytes(
+    path: &str,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    let metadata = super::interop::explorer_write_file_bytes(path, bytes).await?;
+    super::watch::explorer_notify_changed(&[path]);
+    Ok(metadata)
+}
+
+pub(crate) async fn explorer_write_file_chunk(
+    path: &str,
+    offset: u64,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    let metadata = super::interop::explorer_write_file_chunk(path, offset, bytes).await?;
+    super::watch::explorer_notify_changed(&[path]);
+    Ok(metadata)
+}
+
+pub(crate) async fn explorer_pick_import_files() -> Result<Vec<ExplorerImportFile>, String> {
+    super::interop::explorer_pick_import_files().await
+}
+
+pub(crate) async fn explorer_save_download(name: &str, bytes: &[u8]) -> Result<(), String> {
+    super::interop::explorer_save_download(name, bytes).await
 }
 
 pub(crate) async fn explorer_create_dir(path: &str) -> Result<ExplorerMetadata, String> {
-    super::interop::explorer_create_dir(path).await
+    let metadata = super::interop::explorer_create_dir(path).await?;
+    super::watch::explorer_notify_changed(&[path]);
+    Ok(metadata)
 }
 
 pub(crate) async fn explorer_create_file(
     path: &str,
     text: &str,
 ) -> Result<ExplorerMetadata, String> {
-    super::interop::explorer_create_file(path, text).await
+    let metadata = super::interop::explorer_create_file(path, text).await?;
+    super::watch::explorer_notify_changed(&[path]);
+    Ok(metadata)
 }
 
 pub(crate) async fn explorer_delete(path: &str, recursive: bool) -> Result<(), String> {
-    super::interop::explorer_delete(path, recursive).await
+    super::interop::explorer_delete(path, recursive).await?;
+    super::watch::explorer_notify_changed(&[path]);
+    Ok(())
+}
+
+pub(crate) async fn explorer_rename(from: &str, to: &str) -> Result<ExplorerMetadata, String> {
+    let metadata = super::interop::explorer_rename(from, to).await?;
+    super::watch::explorer_notify_changed(&[from, to]);
+    Ok(metadata)
+}
+
+pub(crate) async fn explorer_batch(
+    ops: &[ExplorerBatchOp],
+) -> Result<Vec<ExplorerBatchResult>, String> {
+    let results = super::interop::explorer_batch(ops).await?;
+    let changed = ops
+        .iter()
+        .zip(&results)
+        .filter(|(_, result)| result.error.is_none())
+        .flat_map(|(op, _)| op.changed_paths())
+        .collect::<Vec<_>>();
+    super::watch::explorer_notify_changed(&changed);
+    Ok(results)
 }
 
 pub(crate) async fn explorer_stat(path: &str) -> Result<ExplorerMetadata, String> {
     super::interop::explorer_stat(path).await
 }
+
+pub(crate) async fn explorer_usage() -> Result<ExplorerStorageUsage, String> {
+    super::interop::explorer_usage().await
+}
+
+pub(crate) async fn explorer_request_persistent_storage() -> Result<bool, String> {
+    super::interop::explorer_request_persistent_storage().await
+}
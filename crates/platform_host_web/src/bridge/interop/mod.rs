@@ -3,9 +3,14 @@
 //! This module routes calls to target-specific implementations while preserving a uniform API
 //! for higher-level bridge domain modules.
 
+use std::rc::Rc;
+
 use platform_host::{
-    AppStateEnvelope, ExplorerBackendStatus, ExplorerFileReadResult, ExplorerListResult,
-    ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState,
+    AppStateEnvelope, ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult,
+    ExplorerFileBytesResult, ExplorerFileChunk, ExplorerFileReadResult, ExplorerImportFile,
+    ExplorerListPage, ExplorerListResult, ExplorerMetadata, ExplorerPermissionMode,
+    ExplorerPermissionState, ExplorerStorageUsage, ExplorerWatch, HttpRequest, HttpResponse,
+    StorageUsageEntry,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -46,6 +51,10 @@ pub async fn delete_pref(key: &str) -> Result<(), String> {
     imp::delete_pref(key).await
 }
 
+pub async fn prefs_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    imp::prefs_usage().await
+}
+
 pub async fn cache_put_text(cache_name: &str, key: &str, value: &str) -> Result<(), String> {
     imp::cache_put_text(cache_name, key, value).await
 }
@@ -58,6 +67,14 @@ pub async fn cache_delete(cache_name: &str, key: &str) -> Result<(), String> {
     imp::cache_delete(cache_name, key).await
 }
 
+pub async fn cache_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    imp::cache_usage().await
+}
+
+pub async fn cache_clear(cache_name: &str) -> Result<(), String> {
+    imp::cache_clear(cache_name).await
+}
+
 pub async fn explorer_status() -> Result<ExplorerBackendStatus, String> {
     imp::explorer_status().await
 }
@@ -66,6 +83,12 @@ pub async fn explorer_pick_native_directory() -> Result<ExplorerBackendStatus, S
     imp::explorer_pick_native_directory().await
 }
 
+pub async fn explorer_unmount_native_directory(
+    path: &str,
+) -> Result<ExplorerBackendStatus, String> {
+    imp::explorer_unmount_native_directory(path).await
+}
+
 pub async fn explorer_request_permission(
     mode: ExplorerPermissionMode,
 ) -> Result<ExplorerPermissionState, String> {
@@ -76,14 +99,57 @@ pub async fn explorer_list_dir(path: &str) -> Result<ExplorerListResult, String>
     imp::explorer_list_dir(path).await
 }
 
+pub async fn explorer_list_dir_page(
+    path: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<ExplorerListPage, String> {
+    imp::explorer_list_dir_page(path, offset, limit).await
+}
+
 pub async fn explorer_read_text_file(path: &str) -> Result<ExplorerFileReadResult, String> {
     imp::explorer_read_text_file(path).await
 }
 
+pub async fn explorer_read_file_bytes(path: &str) -> Result<ExplorerFileBytesResult, String> {
+    imp::explorer_read_file_bytes(path).await
+}
+
+pub async fn explorer_read_file_chunk(
+    path: &str,
+    offset: u64,
+    len: usize,
+) -> Result<ExplorerFileChunk, String> {
+    imp::explorer_read_file_chunk(path, offset, len).await
+}
+
 pub async fn explorer_write_text_file(path: &str, text: &str) -> Result<ExplorerMetadata, String> {
     imp::explorer_write_text_file(path, text).await
 }
 
+pub async fn explorer_write_file_bytes(
+    path: &str,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    imp::explorer_write_file_bytes(path, bytes).await
+}
+
+pub async fn explorer_write_file_chunk(
+    path: &str,
+    offset: u64,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    imp::explorer_write_file_chunk(path, offset, bytes).await
+}
+
+pub async fn explorer_pick_import_files() -> Result<Vec<ExplorerImportFile>, String> {
+    imp::explorer_pick_import_files().await
+}
+
+pub async fn explorer_save_download(name: &str, bytes: &[u8]) -> Result<(), String> {
+    imp::explorer_save_download(name, bytes).await
+}
+
 pub async fn explorer_create_dir(path: &str) -> Result<ExplorerMetadata, String> {
     imp::explorer_create_dir(path).await
 }
@@ -96,10 +162,49 @@ pub async fn explorer_delete(path: &str, recursive: bool) -> Result<(), String>
     imp::explorer_delete(path, recursive).await
 }
 
+pub async fn explorer_rename(from: &str, to: &str) -> Result<ExplorerMetadata, String> {
+    imp::explorer_rename(from, to).await
+}
+
+pub async fn explorer_batch(ops: &[ExplorerBatchOp]) -> Result<Vec<ExplorerBatchResult>, String> {
+    imp::explorer_batch(ops).await
+}
+
 pub async fn explorer_stat(path: &str) -> Result<ExplorerMetadata, String> {
     imp::explorer_stat(path).await
 }
 
+pub async fn explorer_usage() -> Result<ExplorerStorageUsage, String> {
+    imp::explorer_usage().await
+}
+
+pub async fn explorer_request_persistent_storage() -> Result<bool, String> {
+    imp::explorer_request_persistent_storage().await
+}
+
+/// Tells other pages of this origin that `paths` changed.
+pub fn explorer_broadcast_changes(paths: &[&str]) {
+    imp::explorer_broadcast_changes(paths)
+}
+
+/// Calls `on_change` with each path that another page of this origin reports as changed.
+pub fn explorer_listen_changes(on_change: Rc<dyn Fn(String)>) {
+    imp::explorer_listen_changes(on_change)
+}
+
+/// Observes `path` in the connected native folder where the browser supports it.
+pub fn explorer_watch_native(path: &str, on_change: Rc<dyn Fn(String)>) -> ExplorerWatch {
+    imp::explorer_watch_native(path, on_change)
+}
+
 pub async fn open_external_url(url: &str) -> Result<(), String> {
     imp::open_external_url(url).await
 }
+
+pub async fn clipboard_write_text(text: &str) -> Result<(), String> {
+    imp::clipboard_write_text(text).await
+}
+
+pub async fn http_send(request: &HttpRequest) -> Result<HttpResponse, String> {
+    imp::http_send(request).await
+}
@@ -32,6 +32,10 @@ pub async fn delete_pref(_key: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub async fn prefs_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    Ok(Vec::new())
+}
+
 pub async fn cache_put_text(_cache_name: &str, _key: &str, _value: &str) -> Result<(), String> {
     Ok(())
 }
@@ -44,6 +48,14 @@ pub async fn cache_delete(_cache_name: &str, _key: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub async fn cache_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    Ok(Vec::new())
+}
+
+pub async fn cache_clear(_cache_name: &str) -> Result<(), String> {
+    Ok(())
+}
+
 pub async fn explorer_status() -> Result<ExplorerBackendStatus, String> {
     Err(unsupported())
 }
@@ -52,6 +64,12 @@ pub async fn explorer_pick_native_directory() -> Result<ExplorerBackendStatus, S
     Err(unsupported())
 }
 
+pub async fn explorer_unmount_native_directory(
+    _path: &str,
+) -> Result<ExplorerBackendStatus, String> {
+    Err(unsupported())
+}
+
 pub async fn explorer_request_permission(
     _mode: ExplorerPermissionMode,
 ) -> Result<ExplorerPermissionState, String> {
@@ -62,10 +80,30 @@ pub async fn explorer_list_dir(_path: &str) -> Result<ExplorerListResult, String
     Err(unsupported())
 }
 
+pub async fn explorer_list_dir_page(
+    _path: &str,
+    _offset: usize,
+    _limit: usize,
+) -> Result<ExplorerListPage, String> {
+    Err(unsupported())
+}
+
 pub async fn explorer_read_text_file(_path: &str) -> Result<ExplorerFileReadResult, String> {
     Err(unsupported())
 }
 
+pub async fn explorer_read_file_bytes(_path: &str) -> Result<ExplorerFileBytesResult, String> {
+    Err(unsupported())
+}
+
+pub async fn explorer_read_file_chunk(
+    _path: &str,
+    _offset: u64,
+    _len: usize,
+) -> Result<ExplorerFileChunk, String> {
+    Err(unsupported())
+}
+
 pub async fn explorer_write_text_file(
     _path: &str,
     _text: &str,
@@ -73,6 +111,29 @@ pub async fn explorer_write_text_file(
     Err(unsupported())
 }
 
+pub async fn explorer_write_file_bytes(
+    _path: &str,
+    _bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    Err(unsupported())
+}
+
+pub async fn explorer_write_file_chunk(
+    _path: &str,
+    _offset: u64,
+    _bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    Err(unsupported())
+}
+
+pub async fn explorer_pick_import_files() -> Result<Vec<ExplorerImportFile>, String> {
+    Err(unsupported())
+}
+
+pub async fn explorer_save_download(_name: &str, _bytes: &[u8]) -> Result<(), String> {
+    Err(unsupported())
+}
+
 pub async fn explorer_create_dir(_path: &str) -> Result<ExplorerMetadata, String> {
     Err(unsupported())
 }
@@ -85,10 +146,42 @@ pub async fn explorer_delete(_path: &str, _recursive: bool) -> Result<(), String
     Err(unsupported())
 }
 
+pub async fn explorer_rename(_from: &str, _to: &str) -> Result<ExplorerMetadata, String> {
+    Err(unsupported())
+}
+
+pub async fn explorer_batch(_ops: &[ExplorerBatchOp]) -> Result<Vec<ExplorerBatchResult>, String> {
+    Err(unsupported())
+}
+
 pub async fn explorer_stat(_path: &str) -> Result<ExplorerMetadata, String> {
     Err(unsupported())
 }
 
+pub async fn explorer_usage() -> Result<ExplorerStorageUsage, String> {
+    Err(unsupported())
+}
+
+pub async fn explorer_request_persistent_storage() -> Result<bool, String> {
+    Err(unsupported())
+}
+
+pub fn explorer_broadcast_changes(_paths: &[&str]) {}
+
+pub fn explorer_listen_changes(_on_change: Rc<dyn Fn(String)>) {}
+
+pub fn explorer_watch_native(_path: &str, _on_change: Rc<dyn Fn(String)>) -> ExplorerWatch {
+    ExplorerWatch::inactive()
+}
+
 pub async fn open_external_url(_url: &str) -> Result<(), String> {
     Err(unsupported())
 }
+
+pub async fn clipboard_write_text(_text: &str) -> Result<(), String> {
+    Err(unsupported())
+}
+
+pub async fn http_send(_request: &HttpRequest) -> Result<HttpResponse, String> {
+    Err(unsupported())
+}
@@ -14,6 +14,7 @@ const DB_VERSION = 1;
 const APP_STATE_STORE = 'app_state';
 const VFS_STORE = 'vfs_nodes';
 const FS_CONFIG_STORE = 'fs_config';
+const MOUNT_DIR = '/mnt';
 
 function fail(message) {
   throw new Error(message);
@@ -178,6 +179,11 @@ function bytesLen(text) {
   return new TextEncoder().encode(text).length;
 }
 
+// Imported files keep their raw bytes; files written as text keep a string.
+function vfsNodeBytes(node) {
+  return node.bytes ?? new TextEncoder().encode(node.content ?? '');
+}
+
 function sortEntries(entries) {
   entries.sort((a, b) => {
 if (a.kind !== b.kind) {
@@ -289,7 +295,7 @@ async function vfsReadText(path) {
   return {
 backend: 'indexed-db-virtual',
 path: node.path,
-text: node.content ?? '',
+text: node.bytes ? new TextDecoder().decode(node.bytes) : (node.content ?? ''),
 metadata,
 cached_preview_key: `file-preview:${node.path}`,
   };
@@ -302,7 +308,7 @@ async function vfsWriteText(path, text) {
   const existing = await getByKey(VFS_STORE, normalized);
   const ts = nowMs();
   const node = existing
-? { ...existing, kind: 'file', content: text, size: bytesLen(text), modifiedAt: ts }
+? { ...existing, kind: 'file', content: text, bytes: undefined, size: bytesLen(text), modifiedAt: ts }
 : {
     path: normalized,
     parent: dirname(normalized),
@@ -318,6 +324,30 @@ async function vfsWriteText(path, text) {
   return vfsNodeToMetadata(node, 'virtual');
 }
 
+async function vfsWriteBytes(path, bytes) {
+  const normalized = normalizePath(path);
+  if (normalized === '/') fail('Cannot write to root');
+  await vfsEnsureParentDir(normalized);
+  const existing = await getByKey(VFS_STORE, normalized);
+  if (existing && existing.kind !== 'file') fail(`Not a file: ${normalized}`);
+  const ts = nowMs();
+  const node = existing
+? { ...existing, content: undefined, bytes, size: bytes.length, modifiedAt: ts }
+: {
+    path: normalized,
+    parent: dirname(normalized),
+    name: basename(normalized),
+    kind: 'file',
+    bytes,
+    size: bytes.length,
+    createdAt: ts,
+    modifiedAt: ts,
+  };
+  await putRecord(VFS_STORE, node);
+  await vfsTouchParent(normalized);
+  return vfsNodeToMetadata(node, 'virtual');
+}
+
 async function vfsCreateDir(path) {
   const normalized = normalizePath(path);
   if (normalized === '/') {
@@ -376,29 +406,300 @@ await deleteByKey(VFS_STORE, normalized);
   await vfsTouchParent(normalized);
 }
 
+async function vfsRename(from, to) {
+  const source = normalizePath(from);
+  const target = normalizePath(to);
+  if (source === '/' || target === '/') fail('Cannot rename root directory');
+  const node = await vfsRequireNode(source);
+  if (source === target) return vfsNodeToMetadata(node, 'virtual');
+  if (isDescendantPath(source, target)) fail(`Cannot move ${source} into itself`);
+  await vfsEnsureParentDir(target);
+  if (await getByKey(VFS_STORE, target)) fail(`Already exists: ${target}`);
+  const allNodes = await getAllNodes();
+  const ts = nowMs();
+  const txDb = await openDb();
+  const tx = txDb.transaction(VFS_STORE, 'readwrite');
+  const store = tx.objectStore(VFS_STORE);
+  let renamed = null;
+  for (const candidate of allNodes || []) {
+    if (candidate.path !== source && !isDescendantPath(source, candidate.path)) continue;
+    const path = target + candidate.path.slice(source.length);
+    const moved = { ...candidate, path, parent: dirname(path), name: basename(path) };
+    if (candidate.path === source) {
+      moved.modifiedAt = ts;
+      renamed = moved;
+    }
+    await requestToPromise(store.delete(candidate.path));
+    await requestToPromise(store.put(moved));
+  }
+  await txDone(tx);
+  await vfsTouchParent(source);
+  await vfsTouchParent(target);
+  return vfsNodeToMetadata(renamed, 'virtual');
+}
+
 async function vfsStat(path) {
   const node = await vfsRequireNode(path);
   return vfsNodeToMetadata(node, 'virtual');
 }
 
+function errorText(err) {
+  return err instanceof Error ? err.message : String(err);
+}
+
+// The paths a batch operation writes, removes, or moves.
+function batchOpPaths(op) {
+  return op.op === 'rename' ? [op.from, op.to] : [op.path];
+}
+
+async function vfsBatchRequireParentDir(store, path) {
+  const parentPath = dirname(path);
+  const parent = await requestToPromise(store.get(parentPath));
+  if (!parent) fail(`Path not found: ${parentPath}`);
+  if (parent.kind !== 'dir') fail(`Not a directory: ${parentPath}`);
+}
+
+async function vfsBatchTouchParent(store, path, ts) {
+  const parent = await requestToPromise(store.get(dirname(path)));
+  if (!parent || parent.kind !== 'dir') return;
+  parent.modifiedAt = ts;
+  await requestToPromise(store.put(parent));
+}
+
+// Keys of the nodes below `path`, which is never the root here.
+function vfsDescendantRange(path) {
+  return IDBKeyRange.bound(`${path}/`, `${path}/\uffff`);
+}
+
+// One batch operation against the store of an open transaction. Every check runs before the
+// first write, so a failed operation leaves the store as it found it.
+async function vfsBatchOp(store, op) {
+  const ts = nowMs();
+  switch (op.op) {
+case 'create_dir': {
+  const normalized = normalizePath(op.path);
+  const existing = await requestToPromise(store.get(normalized));
+  if (normalized === '/') return vfsNodeToMetadata(existing, 'virtual');
+  await vfsBatchRequireParentDir(store, normalized);
+  if (existing) {
+    if (existing.kind !== 'dir') fail(`File already exists at ${normalized}`);
+    return vfsNodeToMetadata(existing, 'virtual');
+  }
+  const node = {
+    path: normalized,
+    parent: dirname(normalized),
+    name: basename(normalized),
+    kind: 'dir',
+    createdAt: ts,
+    modifiedAt: ts,
+  };
+  await requestToPromise(store.put(node));
+  await vfsBatchTouchParent(store, normalized, ts);
+  return vfsNodeToMetadata(node, 'virtual');
+}
+case 'write_text':
+case 'write_bytes': {
+  const normalized = normalizePath(op.path);
+  if (normalized === '/') fail('Cannot write to root');
+  await vfsBatchRequireParentDir(store, normalized);
+  const existing = await requestToPromise(store.get(normalized));
+  if (existing && existing.kind !== 'file') fail(`Not a file: ${normalized}`);
+  const contents = op.op === 'write_text'
+    ? { content: op.text, bytes: undefined, size: bytesLen(op.text) }
+    : { content: undefined, bytes: Uint8Array.from(op.bytes), size: op.bytes.length };
+  const node = existing
+    ? { ...existing, ...contents, modifiedAt: ts }
+    : {
+        path: normalized,
+        parent: dirname(normalized),
+        name: basename(normalized),
+        kind: 'file',
+        ...contents,
+        createdAt: ts,
+        modifiedAt: ts,
+      };
+  await requestToPromise(store.put(node));
+  await vfsBatchTouchParent(store, normalized, ts);
+  return vfsNodeToMetadata(node, 'virtual');
+}
+case 'delete': {
+  const normalized = normalizePath(op.path);
+  if (normalized === '/') fail('Cannot delete root directory');
+  const node = await requestToPromise(store.get(normalized));
+  if (!node) fail(`Path not found: ${normalized}`);
+  if (node.kind === 'dir') {
+    const children = await requestToPromise(store.index('by_parent').getAll(normalized));
+    if (children.length > 0 && !op.recursive) fail(`Directory not empty: ${normalized}`);
+    await requestToPromise(store.delete(vfsDescendantRange(normalized)));
+  }
+  await requestToPromise(store.delete(normalized));
+  await vfsBatchTouchParent(store, normalized, ts);
+  return null;
+}
+case 'rename': {
+  const source = normalizePath(op.from);
+  const target = normalizePath(op.to);
+  if (source === '/' || target === '/') fail('Cannot rename root directory');
+  const node = await requestToPromise(store.get(source));
+  if (!node) fail(`Path not found: ${source}`);
+  if (source === target) return vfsNodeToMetadata(node, 'virtual');
+  if (isDescendantPath(source, target)) fail(`Cannot move ${source} into itself`);
+  await vfsBatchRequireParentDir(store, target);
+  if (await requestToPromise(store.get(target))) fail(`Already exists: ${target}`);
+  const descendants = await requestToPromise(store.getAll(vfsDescendantRange(source)));
+  const renamed = { ...node, path: target, parent: dirname(target), name: basename(target), modifiedAt: ts };
+  for (const candidate of [node, ...descendants]) {
+    await requestToPromise(store.delete(candidate.path));
+  }
+  await requestToPromise(store.put(renamed));
+  for (const candidate of descendants) {
+    const path = target + candidate.path.slice(source.length);
+    await requestToPromise(store.put({ ...candidate, path, parent: dirname(path), name: basename(path) }));
+  }
+  await vfsBatchTouchParent(store, source, ts);
+  await vfsBatchTouchParent(store, target, ts);
+  return vfsNodeToMetadata(renamed, 'virtual');
+}
+default:
+  fail(`Unknown batch operation: ${op.op}`);
+  }
+}
+
+// Applies a batch in one IndexedDB transaction, so its writes land together. Operations with an
+// entry in `blocked` are skipped with that error; any other failure is recorded and the batch
+// carries on.
+async function vfsBatch(ops, blocked) {
+  const db = await openDb();
+  const tx = db.transaction(VFS_STORE, 'readwrite');
+  const store = tx.objectStore(VFS_STORE);
+  const results = [];
+  for (const [index, op] of ops.entries()) {
+if (blocked[index]) {
+  results.push({ metadata: null, error: blocked[index] });
+  continue;
+}
+try {
+  results.push({ metadata: await vfsBatchOp(store, op), error: null });
+} catch (err) {
+  results.push({ metadata: null, error: errorText(err) });
+}
+  }
+  await txDone(tx);
+  return results;
+}
+
 async function getNativeRootHandle() {
   const record = await getByKey(FS_CONFIG_STORE, 'native_root_handle');
   return record?.value ?? null;
 }
 
-async function setNativeRootHandle(handle) {
-  await putRecord(FS_CONFIG_STORE, {
-key: 'native_root_handle',
-value: handle,
-updatedAt: nowMs(),
-  });
+async function getNativeMounts() {
+  const record = await getByKey(FS_CONFIG_STORE, 'native_mounts');
+  return Array.isArray(record?.value) ? record.value : [];
+}
+
+async function setNativeMounts(mounts) {
   await putRecord(FS_CONFIG_STORE, {
-key: 'native_root_name',
-value: handle?.name ?? null,
+key: 'native_mounts',
+value: mounts,
 updatedAt: nowMs(),
   });
 }
 
+function mountSlug(name) {
+  const slug = String(name ?? '')
+.toLowerCase()
+.replace(/[^a-z0-9._-]+/g, '-')
+.replace(/^-+|-+$/g, '');
+  return slug || 'folder';
+}
+
+// The mount serving `path`, with `path` relative to the mounted folder, or null.
+async function resolveMount(path) {
+  const normalized = normalizePath(path);
+  for (const mount of await getNativeMounts()) {
+if (normalized === mount.path || normalized.startsWith(`${mount.path}/`)) {
+  return { mount, relative: normalizePath(normalized.slice(mount.path.length)) };
+}
+  }
+  return null;
+}
+
+// Read access to `path` through the mount serving it, or null when no mount does.
+async function mountReadContext(path) {
+  const resolved = await resolveMount(path);
+  if (!resolved) return null;
+  const permission = await queryHandlePermission(resolved.mount.handle, 'read');
+  if (permission === 'denied') fail(`Permission to read ${resolved.mount.path} was denied`);
+  return { root: resolved.mount.handle, relative: resolved.relative, permission };
+}
+
+// Read access to `path` through the connected native root.
+async function nativeRootReadContext(path) {
+  const root = await getNativeRootHandle();
+  const permission = await queryHandlePermission(root, 'read');
+  if (permission === 'denied') fail('Native folder permission denied');
+  return { root, relative: normalizePath(path), permission };
+}
+
+// Mounted folders are read-only, and the mount folder itself stays while anything is mounted.
+async function failIfMounted(...paths) {
+  const mounts = await getNativeMounts();
+  if (!mounts.length) return;
+  for (const path of paths) {
+const normalized = normalizePath(path);
+if (normalized === MOUNT_DIR || isDescendantPath(normalized, MOUNT_DIR)) {
+  fail(`${MOUNT_DIR} holds mounted folders`);
+}
+const mount = mounts.find((candidate) => normalized === candidate.path || normalized.startsWith(`${candidate.path}/`));
+if (mount) fail(`${mount.path} is mounted read-only`);
+  }
+}
+
+// A native root connected before folders were mounted under /mnt stays readable, but nothing grants
+// it write access any more.
+async function failIfReadOnly(...paths) {
+  if ((await nativeStatus()).backend === 'native-fs-access') {
+fail('The connected native folder is read-only');
+  }
+  await failIfMounted(...paths);
+}
+
+async function mountStatuses() {
+  const mounts = [];
+  for (const mount of await getNativeMounts()) {
+mounts.push({
+  path: mount.path,
+  name: mount.name,
+  permission: await queryHandlePermission(mount.handle, 'read'),
+});
+  }
+  return mounts;
+}
+
+// Adds the mount folder to the root listing and the mount points to the mount folder's listing.
+async function withMountEntries(listing) {
+  const mounts = await getNativeMounts();
+  const cwd = normalizePath(listing.cwd);
+  const added = cwd === '/' && mounts.length
+? [MOUNT_DIR]
+: cwd === MOUNT_DIR
+  ? mounts.map((mount) => mount.path)
+  : [];
+  for (const path of added) {
+if (listing.entries.some((entry) => entry.path === path)) continue;
+listing.entries.push({
+  name: basename(path),
+  path,
+  kind: 'directory',
+  size: null,
+  modified_at_unix_ms: null,
+});
+  }
+  sortEntries(listing.entries);
+  return listing;
+}
+
 async function clearNativeRootHandle() {
   await deleteByKey(FS_CONFIG_STORE, 'native_root_handle');
   await deleteByKey(FS_CONFIG_STORE, 'native_root_name');
@@ -438,8 +739,8 @@ return 'denied';
   }
 }
 
-async function resolveNativeDirectoryHandle(path, opts = { create: false }) {
-  const root = await getNativeRootHandle();
+async function resolveNativeDirectoryHandle(path, opts = { create: false }, root = null) {
+  root = root ?? (await getNativeRootHandle());
   if (!root) fail('No native directory is connected');
   let current = root;
   const segments = splitSegments(path);
@@ -449,12 +750,12 @@ current = await current.getDirectoryHandle(segment, { create: !!opts.create });
   return current;
 }
 
-async function resolveNativeParentAndName(path) {
+async function resolveNativeParentAndName(path, root = null) {
   const normalized = normalizePath(path);
   if (normalized === '/') fail('Root path is not writable');
   const parentPath = dirname(normalized);
   const name = basename(normalized);
-  const parent = await resolveNativeDirectoryHandle(parentPath, { create: false });
+  const parent = await resolveNativeDirectoryHandle(parentPath, { create: false }, root);
   return { normalized, parentPath, parent, name };
 }
 
@@ -483,19 +784,19 @@ permission,
   };
 }
 
-async function resolveNativeFileHandle(path) {
-  const { parent, name } = await resolveNativeParentAndName(path);
+async function resolveNativeFileHandle(path, root = null) {
+  const { parent, name } = await resolveNativeParentAndName(path, root);
   return await parent.getFileHandle(name, { create: false });
 }
 
-async function resolveNativeEntry(path) {
+async function resolveNativeEntry(path, root = null) {
   const normalized = normalizePath(path);
   if (normalized === '/') {
-const root = await getNativeRootHandle();
+root = root ?? (await getNativeRootHandle());
 if (!root) fail('No native directory is connected');
 return root;
   }
-  const { parent, name } = await resolveNativeParentAndName(normalized);
+  const { parent, name } = await resolveNativeParentAndName(normalized, root);
   try {
 return await parent.getFileHandle(name, { create: false });
   } catch (_) {
@@ -510,7 +811,7 @@ async function nativeStatus() {
   const permission = !native_supported
 ? 'virtual'
 : root
-  ? await queryHandlePermission(root, 'readwrite')
+  ? await queryHandlePermission(root, 'read')
   : 'prompt';
   const rootName = root?.name ?? (await getNativeRootName());
   return {
@@ -519,6 +820,7 @@ native_supported,
 has_native_root,
 permission: has_native_root ? permission : (native_supported ? 'prompt' : 'virtual'),
 root_path_hint: rootName ? `/${rootName}` : null,
+mounts: await mountStatuses(),
   };
 }
 
@@ -586,6 +888,45 @@ fail('Cache API unavailable');
   await cache.delete(req);
 }
 
+async function cacheUsageInternal() {
+  const tauri = await tauriInvoke('cache_usage', {});
+  if (tauri.available) {
+    return tauri.value ?? [];
+  }
+  if (typeof caches === 'undefined') {
+    return [];
+  }
+  const usage = [];
+  for (const name of (await caches.keys()).sort()) {
+    const cache = await caches.open(name);
+    let entries = 0;
+    let bytes = 0;
+    for (const req of await cache.keys()) {
+      const res = await cache.match(req);
+      if (!res) continue;
+      entries += 1;
+      bytes += (await res.blob()).size;
+    }
+    usage.push({ name, entries, bytes });
+  }
+  return usage;
+}
+
+async function cacheClearInternal(cacheName) {
+  const tauri = await tauriInvoke('cache_clear', {
+    cacheName,
+    cache_name: cacheName,
+  });
+  if (tauri.available) {
+    return null;
+  }
+  if (typeof caches === 'undefined') {
+    fail('Cache API unavailable');
+  }
+  await caches.delete(cacheName);
+  return null;
+}
+
 async function appStateLoad(namespace) {
   const tauri = await tauriInvoke('app_state_load', { namespace });
   if (tauri.available) {
@@ -662,6 +1003,23 @@ async function prefsDelete(key) {
   return null;
 }
 
+async function prefsUsage() {
+  const tauri = await tauriInvoke('prefs_usage', {});
+  if (tauri.available) {
+    return tauri.value ?? [];
+  }
+  const storage = (typeof window !== 'undefined') ? window.localStorage : null;
+  if (!storage) return [];
+  const encoder = new TextEncoder();
+  const usage = [];
+  for (let index = 0; index < storage.length; index += 1) {
+    const name = storage.key(index);
+    if (name === null) continue;
+    usage.push({ name, entries: 1, bytes: encoder.encode(storage.getItem(name) ?? '').length });
+  }
+  return usage.sort((a, b) => (a.name < b.name ? -1 : a.name > b.name ? 1 : 0));
+}
+
 async function explorerStatus() {
   const tauri = await tauriInvoke('explorer_status', {});
   if (tauri.available) {
@@ -679,9 +1037,35 @@ return tauri.value;
   if (typeof window === 'undefined' || typeof window.showDirectoryPicker !== 'function') {
 fail('File System Access API is not supported in this browser');
   }
-  const handle = await window.showDirectoryPicker({ mode: 'readwrite' });
-  await setNativeRootHandle(handle);
-  await requestHandlePermission(handle, 'readwrite');
+  const handle = await window.showDirectoryPicker({ mode: 'read' });
+  await requestHandlePermission(handle, 'read');
+  const mounts = await getNativeMounts();
+  for (const mount of mounts) {
+if (typeof handle.isSameEntry === 'function' && (await handle.isSameEntry(mount.handle))) {
+  return await nativeStatus();
+}
+  }
+  const taken = new Set(mounts.map((mount) => mount.path));
+  const slug = mountSlug(handle.name);
+  let path = `${MOUNT_DIR}/${slug}`;
+  for (let index = 2; taken.has(path); index += 1) {
+path = `${MOUNT_DIR}/${slug}-${index}`;
+  }
+  mounts.push({ path, name: handle.name, handle });
+  await setNativeMounts(mounts);
+  return await nativeStatus();
+}
+
+async function explorerUnmountNativeDirectory(path) {
+  const tauri = await tauriInvoke('explorer_unmount_native_directory', { path });
+  if (tauri.available) {
+return tauri.value;
+  }
+  const normalized = normalizePath(path);
+  const mounts = await getNativeMounts();
+  const remaining = mounts.filter((mount) => mount.path !== normalized);
+  if (remaining.length === mounts.length) fail(`No folder is mounted at ${normalized}`);
+  await setNativeMounts(remaining);
   return await nativeStatus();
 }
 
@@ -689,13 +1073,17 @@ async function explorerRequestPermission(mode) {
   const tauri = await tauriInvoke('explorer_request_permission', { mode });
   if (tauri.available) {
 return tauri.value;
+  }
+  for (const mount of await getNativeMounts()) {
+if ((await queryHandlePermission(mount.handle, 'read')) === 'prompt') {
+  await requestHandlePermission(mount.handle, 'read');
+}
   }
   const status = await nativeStatus();
   if (status.backend !== 'native-fs-access') {
 return 'virtual';
   }
-  const root = await getNativeRootHandle();
-  return await requestHandlePermission(root, mode === 'readwrite' ? 'readwrite' : 'read');
+  return await requestHandlePermission(await getNativeRootHandle(), 'read');
 }
 
 async function explorerListDir(path) {
@@ -704,16 +1092,27 @@ async function explorerListDir(path) {
 return tauri.value;
   }
   await ensureVfsSeed();
+  const mounted = await mountReadContext(path);
+  if (mounted) {
+return await nativeListDir(path, mounted);
+  }
+  const listing = await rootListDir(path).catch(async (err) => {
+if (normalizePath(path) !== MOUNT_DIR || !(await getNativeMounts()).length) throw err;
+return { cwd: MOUNT_DIR, backend: 'indexed-db-virtual', permission: 'virtual', entries: [] };
+  });
+  return await withMountEntries(listing);
+}
+
+async function rootListDir(path) {
   const status = await nativeStatus();
   if (status.backend !== 'native-fs-access') {
 return await vfsListDir(path);
   }
-  const root = await getNativeRootHandle();
-  const permission = await queryHandlePermission(root, 'read');
-  if (permission === 'denied') {
-fail('Native folder permission denied');
-  }
-  const dir = await resolveNativeDirectoryHandle(path, { create: false });
+  return await nativeListDir(path, await nativeRootReadContext(path));
+}
+
+async function nativeListDir(path, { root, relative, permission }) {
+  const dir = await resolveNativeDirectoryHandle(relative, { create: false }, root);
   const entries = [];
   for await (const [name, handle] of dir.entries()) {
 const entryPath = normalizePath(`${normalizePath(path)}/${name}`);
@@ -751,17 +1150,15 @@ async function explorerReadTextFile(path) {
 return tauri.value;
   }
   await ensureVfsSeed();
-  const status = await nativeStatus();
-  if (status.backend !== 'native-fs-access') {
+  const mounted = await mountReadContext(path);
+  if (!mounted && (await nativeStatus()).backend !== 'native-fs-access') {
 const result = await vfsReadText(path);
 await cachePutTextInternal('retrodesk-explorer-cache-v1', result.cached_preview_key, result.text);
 return result;
   }
-  const root = await getNativeRootHandle();
-  const permission = await queryHandlePermission(root, 'read');
-  if (permission === 'denied') fail('Native folder permission denied');
+  const { root, relative, permission } = mounted ?? (await nativeRootReadContext(path));
   const normalized = normalizePath(path);
-  const fileHandle = await resolveNativeFileHandle(normalized);
+  const fileHandle = await resolveNativeFileHandle(relative, root);
   const file = await fileHandle.getFile();
   const text = await file.text();
   const metadata = await nativeEntryMetadata(normalized, fileHandle, permission);
@@ -776,56 +1173,131 @@ cached_preview_key,
   };
 }
 
-async function explorerWriteTextFile(path, text) {
-  const tauri = await tauriInvoke('explorer_write_text_file', { path, text });
+async function explorerReadFileBytes(path) {
+  const tauri = await tauriInvoke('explorer_read_file_bytes', { path });
   if (tauri.available) {
 return tauri.value;
   }
   await ensureVfsSeed();
-  const status = await nativeStatus();
-  if (status.backend !== 'native-fs-access') {
-const meta = await vfsWriteText(path, text ?? '');
-await cachePutTextInternal('retrodesk-explorer-cache-v1', `file-preview:${meta.path}`, text ?? '');
-return meta;
+  const mounted = await mountReadContext(path);
+  if (!mounted && (await nativeStatus()).backend !== 'native-fs-access') {
+const node = await vfsRequireNode(path);
+if (node.kind !== 'file') fail(`Not a file: ${normalizePath(path)}`);
+return {
+  backend: 'indexed-db-virtual',
+  path: node.path,
+  bytes: vfsNodeBytes(node),
+  metadata: vfsNodeToMetadata(node, 'virtual'),
+};
   }
-  const root = await getNativeRootHandle();
-  const permission = await requestHandlePermission(root, 'readwrite');
-  if (permission !== 'granted') fail('Write permission is required to save files');
+  const { root, relative, permission } = mounted ?? (await nativeRootReadContext(path));
   const normalized = normalizePath(path);
-  const { parent, name } = await resolveNativeParentAndName(normalized);
-  const fileHandle = await parent.getFileHandle(name, { create: true });
-  const writable = await fileHandle.createWritable();
-  await writable.write(text ?? '');
-  await writable.close();
+  const fileHandle = await resolveNativeFileHandle(relative, root);
+  const file = await fileHandle.getFile();
+  const bytes = new Uint8Array(await file.arrayBuffer());
   const metadata = await nativeEntryMetadata(normalized, fileHandle, permission);
-  await cachePutTextInternal('retrodesk-explorer-cache-v1', `file-preview:${normalized}`, text ?? '');
-  return metadata;
+  return {
+backend: 'native-fs-access',
+path: normalized,
+bytes,
+metadata,
+  };
 }
 
-async function explorerCreateDir(path) {
-  const tauri = await tauriInvoke('explorer_create_dir', { path });
+async function explorerReadFileChunk(path, offset, len) {
+  const tauri = await tauriInvoke('explorer_read_file_chunk', { path, offset, len });
   if (tauri.available) {
 return tauri.value;
   }
   await ensureVfsSeed();
-  const status = await nativeStatus();
-  if (status.backend !== 'native-fs-access') {
-return await vfsCreateDir(path);
+  const mounted = await mountReadContext(path);
+  if (!mounted && (await nativeStatus()).backend !== 'native-fs-access') {
+const node = await vfsRequireNode(path);
+if (node.kind !== 'file') fail(`Not a file: ${normalizePath(path)}`);
+const bytes = vfsNodeBytes(node);
+return {
+  path: node.path,
+  offset,
+  bytes: bytes.slice(offset, offset + len),
+  size: bytes.length,
+};
   }
-  const root = await getNativeRootHandle();
-  const permission = await requestHandlePermission(root, 'readwrite');
-  if (permission !== 'granted') fail('Write permission is required to create folders');
+  const { root, relative } = mounted ?? (await nativeRootReadContext(path));
   const normalized = normalizePath(path);
-  if (normalized === '/') {
-const rootHandle = await getNativeRootHandle();
-return await nativeEntryMetadata('/', rootHandle, permission);
+  const fileHandle = await resolveNativeFileHandle(relative, root);
+  const file = await fileHandle.getFile();
+  const bytes = new Uint8Array(await file.slice(offset, offset + len).arrayBuffer());
+  return {
+path: normalized,
+offset,
+bytes,
+size: file.size,
+  };
+}
+
+async function explorerWriteTextFile(path, text) {
+  const tauri = await tauriInvoke('explorer_write_text_file', { path, text });
+  if (tauri.available) {
+return tauri.value;
   }
-  const segments = splitSegments(normalized);
-  let current = await getNativeRootHandle();
-  for (const segment of segments) {
-current = await current.getDirectoryHandle(segment, { create: true });
+  await ensureVfsSeed();
+  await failIfReadOnly(path);
+  const meta = await vfsWriteText(path, text ?? '');
+  await cachePutTextInternal('retrodesk-explorer-cache-v1', `file-preview:${meta.path}`, text ?? '');
+  return meta;
+}
+
+async function explorerWriteFileBytes(path, bytes) {
+  // `bytes` views wasm memory, so copy it before the first await.
+  const data = new Uint8Array(bytes);
+  const tauri = await tauriInvoke('explorer_write_file_bytes', { path, bytes: Array.from(data) });
+  if (tauri.available) {
+return tauri.value;
+  }
+  await ensureVfsSeed();
+  await failIfReadOnly(path);
+  const meta = await vfsWriteBytes(path, data);
+  await cacheDeleteInternal('retrodesk-explorer-cache-v1', `file-preview:${meta.path}`);
+  return meta;
+}
+
+function chunkOffsetMismatch(path, offset, size) {
+  fail(`Chunk offset ${offset} does not match the ${size} byte size of ${path}`);
+}
+
+async function explorerWriteFileChunk(path, offset, bytes) {
+  // `bytes` views wasm memory, so copy it before the first await.
+  const data = new Uint8Array(bytes);
+  const tauri = await tauriInvoke('explorer_write_file_chunk', { path, offset, bytes: Array.from(data) });
+  if (tauri.available) {
+return tauri.value;
+  }
+  await ensureVfsSeed();
+  await failIfReadOnly(path);
+  const normalized = normalizePath(path);
+  let joined = data;
+  if (offset > 0) {
+const node = await vfsRequireNode(normalized);
+if (node.kind !== 'file') fail(`Not a file: ${normalized}`);
+const existing = vfsNodeBytes(node);
+if (existing.length !== offset) chunkOffsetMismatch(normalized, offset, existing.length);
+joined = new Uint8Array(existing.length + data.length);
+joined.set(existing);
+joined.set(data, existing.length);
+  }
+  const meta = await vfsWriteBytes(normalized, joined);
+  await cacheDeleteInternal('retrodesk-explorer-cache-v1', `file-preview:${meta.path}`);
+  return meta;
+}
+
+async function explorerCreateDir(path) {
+  const tauri = await tauriInvoke('explorer_create_dir', { path });
+  if (tauri.available) {
+return tauri.value;
   }
-  return await nativeEntryMetadata(normalized, current, permission);
+  await ensureVfsSeed();
+  await failIfReadOnly(path);
+  return await vfsCreateDir(path);
 }
 
 async function explorerCreateFile(path, text) {
@@ -842,38 +1314,211 @@ async function explorerDelete(path, recursive) {
 return null;
   }
   await ensureVfsSeed();
-  const status = await nativeStatus();
-  if (status.backend !== 'native-fs-access') {
-await vfsDelete(path, !!recursive);
-return null;
-  }
-  const root = await getNativeRootHandle();
-  const permission = await requestHandlePermission(root, 'readwrite');
-  if (permission !== 'granted') fail('Write permission is required to delete entries');
-  const { parent, name, normalized } = await resolveNativeParentAndName(path);
-  if (normalized === '/') fail('Cannot delete root directory');
-  await parent.removeEntry(name, { recursive: !!recursive });
-  await cacheDeleteInternal('retrodesk-explorer-cache-v1', `file-preview:${normalized}`).catch(() => {});
+  await failIfReadOnly(path);
+  await vfsDelete(path, !!recursive);
   return null;
 }
 
+async function explorerRename(from, to) {
+  const tauri = await tauriInvoke('explorer_rename', { from, to });
+  if (tauri.available) {
+    return tauri.value;
+  }
+  await ensureVfsSeed();
+  await failIfReadOnly(from, to);
+  const meta = await vfsRename(from, to);
+  await cacheDeleteInternal('retrodesk-explorer-cache-v1', `file-preview:${normalizePath(from)}`).catch(() => {});
+  return meta;
+}
+
+async function explorerBatch(ops) {
+  const tauri = await tauriInvoke('explorer_batch', { ops });
+  if (tauri.available) {
+return tauri.value;
+  }
+  await ensureVfsSeed();
+  const blocked = [];
+  for (const op of ops) {
+blocked.push(await failIfReadOnly(...batchOpPaths(op)).then(() => null, errorText));
+  }
+  const results = await vfsBatch(ops, blocked);
+  for (const [index, op] of ops.entries()) {
+if (results[index].error) continue;
+for (const path of batchOpPaths(op)) {
+  await cacheDeleteInternal('retrodesk-explorer-cache-v1', `file-preview:${normalizePath(path)}`).catch(() => {});
+}
+  }
+  return results;
+}
+
 async function explorerStat(path) {
   const tauri = await tauriInvoke('explorer_stat', { path });
   if (tauri.available) {
 return tauri.value;
   }
   await ensureVfsSeed();
+  const mounted = await mountReadContext(path);
+  if (mounted) {
+const handle = await resolveNativeEntry(mounted.relative, mounted.root);
+return await nativeEntryMetadata(path, handle, mounted.permission);
+  }
   const status = await nativeStatus();
+  if (normalizePath(path) === MOUNT_DIR && (await getNativeMounts()).length) {
+const stat = status.backend === 'native-fs-access' ? rootNativeStat(path) : vfsStat(path);
+return await stat.catch(() => ({
+  name: basename(MOUNT_DIR),
+  path: MOUNT_DIR,
+  kind: 'directory',
+  backend: status.backend,
+  size: null,
+  modified_at_unix_ms: null,
+  permission: 'virtual',
+}));
+  }
   if (status.backend !== 'native-fs-access') {
 return await vfsStat(path);
   }
-  const root = await getNativeRootHandle();
-  const permission = await queryHandlePermission(root, 'read');
-  if (permission === 'denied') fail('Native folder permission denied');
-  const handle = await resolveNativeEntry(path);
+  return await rootNativeStat(path);
+}
+
+async function rootNativeStat(path) {
+  const { root, relative, permission } = await nativeRootReadContext(path);
+  const handle = await resolveNativeEntry(relative, root);
   return await nativeEntryMetadata(path, handle, permission);
 }
 
+async function explorerUsage() {
+  const tauri = await tauriInvoke('explorer_usage', {});
+  if (tauri.available) {
+return tauri.value;
+  }
+  const storage = typeof navigator === 'undefined' ? null : navigator.storage;
+  if (!storage || typeof storage.estimate !== 'function') {
+return { usage_bytes: null, quota_bytes: null, persisted: null };
+  }
+  const estimate = await storage.estimate();
+  const bytes = (value) => (typeof value === 'number' ? Math.round(value) : null);
+  return {
+usage_bytes: bytes(estimate.usage),
+quota_bytes: bytes(estimate.quota),
+persisted: typeof storage.persisted === 'function' ? await storage.persisted() : null,
+  };
+}
+
+async function explorerRequestPersistentStorage() {
+  const tauri = await tauriInvoke('explorer_request_persistent_storage', {});
+  if (tauri.available) {
+return tauri.value;
+  }
+  const storage = typeof navigator === 'undefined' ? null : navigator.storage;
+  if (!storage || typeof storage.persist !== 'function') {
+fail('Persistent storage is not supported in this browser');
+  }
+  return await storage.persist();
+}
+
+async function explorerListDirPage(path, offset, limit) {
+  const tauri = await tauriInvoke('explorer_list_dir_page', { path, offset, limit });
+  if (tauri.available) {
+return tauri.value;
+  }
+  const listing = await explorerListDir(path);
+  return {
+...listing,
+entries: listing.entries.slice(offset, offset + limit),
+offset,
+total: listing.entries.length,
+  };
+}
+
+async function explorerPickImportFiles() {
+  if (typeof document === 'undefined') fail('File import is not supported in this environment');
+  const input = document.createElement('input');
+  input.type = 'file';
+  input.multiple = true;
+  const files = await new Promise((resolve) => {
+input.addEventListener('change', () => resolve(Array.from(input.files ?? [])), { once: true });
+input.addEventListener('cancel', () => resolve([]), { once: true });
+input.click();
+  });
+  const picked = [];
+  for (const file of files) {
+picked.push({ name: file.name, bytes: new Uint8Array(await file.arrayBuffer()) });
+  }
+  return picked;
+}
+
+async function explorerSaveDownload(name, bytes) {
+  if (typeof document === 'undefined') fail('Downloads are not supported in this environment');
+  const url = URL.createObjectURL(new Blob([new Uint8Array(bytes)]));
+  const anchor = document.createElement('a');
+  anchor.href = url;
+  anchor.download = name;
+  document.body.appendChild(anchor);
+  anchor.click();
+  anchor.remove();
+  // Revoking right away can cancel the download before the browser starts it.
+  setTimeout(() => URL.revokeObjectURL(url), 60000);
+  return null;
+}
+
+const VFS_CHANNEL = 'retrodesk_os.vfs';
+let vfsChannel = null;
+let nativeWatchSeq = 0;
+const nativeWatches = new Map();
+
+function changeChannel() {
+  if (typeof BroadcastChannel === 'undefined') return null;
+  vfsChannel ??= new BroadcastChannel(VFS_CHANNEL);
+  return vfsChannel;
+}
+
+function explorerBroadcastChanges(paths) {
+  changeChannel()?.postMessage({ paths: Array.from(paths, String) });
+}
+
+function explorerListenChanges(callback) {
+  // A channel does not receive its own messages, so this page only hears other pages.
+  changeChannel()?.addEventListener('message', (event) => {
+for (const path of event.data?.paths ?? []) callback(normalizePath(String(path)));
+  });
+}
+
+function explorerWatchNative(path, callback) {
+  if (typeof FileSystemObserver === 'undefined' || tauriInvokeFn()) return 0;
+  const id = ++nativeWatchSeq;
+  const base = normalizePath(path);
+  const watch = { observer: null, cancelled: false };
+  nativeWatches.set(id, watch);
+  (async () => {
+const mounted = await resolveMount(base);
+if (!mounted && (await nativeStatus()).backend !== 'native-fs-access') return;
+if (watch.cancelled) return;
+const handle = mounted
+  ? await resolveNativeEntry(mounted.relative, mounted.mount.handle)
+  : await resolveNativeEntry(base);
+const observer = new FileSystemObserver((records) => {
+  if (watch.cancelled) return;
+  for (const record of records) {
+    const parts = record.relativePathComponents ?? [];
+    callback(parts.length ? normalizePath(`${base}/${parts.join('/')}`) : base);
+  }
+});
+await observer.observe(handle);
+watch.observer = observer;
+if (watch.cancelled) observer.disconnect();
+  })().catch(() => nativeWatches.delete(id));
+  return id;
+}
+
+function explorerUnwatchNative(id) {
+  const watch = nativeWatches.get(id);
+  if (!watch) return;
+  nativeWatches.delete(id);
+  watch.cancelled = true;
+  watch.observer?.disconnect();
+}
+
 export async function jsAppStateLoad(namespace) { return await appStateLoad(namespace); }
 export async function jsAppStateSave(envelope) { return await appStateSave(envelope); }
 export async function jsAppStateDelete(namespace) { return await appStateDelete(namespace); }
@@ -881,22 +1526,41 @@ export async function jsAppStateNamespaces() { return await appStateNamespaces()
 export async function jsPrefsLoad(key) { return await prefsLoad(key); }
 export async function jsPrefsSave(key, rawJson) { return await prefsSave(key, rawJson); }
 export async function jsPrefsDelete(key) { return await prefsDelete(key); }
+export async function jsPrefsUsage() { return await prefsUsage(); }
 
 export async function jsCachePutText(cacheName, key, value) { return await cachePutTextInternal(cacheName, key, value); }
 export async function jsCacheGetText(cacheName, key) { return await cacheGetTextInternal(cacheName, key); }
 export async function jsCacheDelete(cacheName, key) { return await cacheDeleteInternal(cacheName, key); }
+export async function jsCacheUsage() { return await cacheUsageInternal(); }
+export async function jsCacheClear(cacheName) { return await cacheClearInternal(cacheName); }
 
 export async function jsExplorerStatus() { return await explorerStatus(); }
 export async function jsExplorerPickNativeDirectory() { return await explorerPickNativeDirectory(); }
+export async function jsExplorerUnmountNativeDirectory(path) { return await explorerUnmountNativeDirectory(path); }
 export async function jsExplorerRequestPermission(mode) { return await explorerRequestPermission(mode); }
 export async function jsExplorerListDir(path) { return await explorerListDir(path); }
+export async function jsExplorerListDirPage(path, offset, limit) { return await explorerListDirPage(path, offset, limit); }
 export async function jsExplorerReadTextFile(path) { return await explorerReadTextFile(path); }
+export async function jsExplorerReadFileBytes(path) { return await explorerReadFileBytes(path); }
+export async function jsExplorerReadFileChunk(path, offset, len) { return await explorerReadFileChunk(path, offset, len); }
 export async function jsExplorerWriteTextFile(path, text) { return await explorerWriteTextFile(path, text); }
+export async function jsExplorerWriteFileBytes(path, bytes) { return await explorerWriteFileBytes(path, bytes); }
+export async function jsExplorerWriteFileChunk(path, offset, bytes) { return await explorerWriteFileChunk(path, offset, bytes); }
+export async function jsExplorerPickImportFiles() { return await explorerPickImportFiles(); }
+export async function jsExplorerSaveDownload(name, bytes) { return await explorerSaveDownload(name, bytes); }
 export async function jsExplorerCreateDir(path) { return await explorerCreateDir(path); }
 export async function jsExplorerCreateFile(path, text) { return await explorerCreateFile(path, text); }
 export async function jsExplorerDelete(path, recursive) { return await explorerDelete(path, recursive); }
+export async function jsExplorerRename(from, to) { return await explorerRename(from, to); }
+export async function jsExplorerBatch(ops) { return await explorerBatch(ops); }
 export async function jsExplorerStat(path) { return await explorerStat(path); }
+export async function jsExplorerUsage() { return await explorerUsage(); }
+export async function jsExplorerRequestPersistentStorage() { return await explorerRequestPersistentStorage(); }
 export async function jsExplorerClearNativeRoot() { await clearNativeRootHandle(); return await nativeStatus(); }
+export function jsExplorerBroadcastChanges(paths) { explorerBroadcastChanges(paths); }
+export function jsExplorerListenChanges(callback) { explorerListenChanges(callback); }
+export function jsExplorerWatchNative(path, callback) { return explorerWatchNative(path, callback); }
+export function jsExplorerUnwatchNative(id) { explorerUnwatchNative(id); }
 export async function jsOpenExternalUrl(url) {
   if (!url || typeof url !== 'string') fail('URL is required');
   const tauri = await tauriInvoke('external_open_url', { url });
@@ -908,6 +1572,66 @@ export async function jsOpenExternalUrl(url) {
   if (!opened) fail(`Failed to open external URL: ${url}`);
   return null;
 }
+export async function jsClipboardWriteText(text) {
+  if (typeof navigator === 'undefined' || !navigator.clipboard || typeof navigator.clipboard.writeText !== 'function') {
+    fail('Clipboard API is unavailable in this browser context');
+  }
+  await navigator.clipboard.writeText(String(text ?? ''));
+  return null;
+}
+export async function jsHttpSend(request) {
+  if (typeof fetch !== 'function') fail('fetch is unavailable in this browser context');
+  const controller = typeof AbortController === 'function' ? new AbortController() : null;
+  const timeoutMs = Number(request?.timeoutMs ?? 0);
+  const limit = Number(request?.maxResponseBytes ?? 0);
+  let timedOut = false;
+  const timer = controller && timeoutMs > 0
+    ? setTimeout(() => { timedOut = true; controller.abort(); }, timeoutMs)
+    : null;
+  try {
+    const response = await fetch(String(request.url), {
+      method: request.method,
+      headers: request.headers ?? [],
+      body: request.method === 'POST' ? (request.body ?? undefined) : undefined,
+      credentials: 'omit',
+      signal: controller?.signal,
+    });
+    const chunks = [];
+    let total = 0;
+    const keep = (chunk) => {
+      total += chunk.byteLength;
+      if (limit > 0 && total > limit) {
+        controller?.abort();
+        fail(`Response exceeded ${limit} bytes`);
+      }
+      chunks.push(chunk);
+    };
+    if (response.body && typeof response.body.getReader === 'function') {
+      const reader = response.body.getReader();
+      for (;;) {
+        const { done, value } = await reader.read();
+        if (done) break;
+        keep(value);
+      }
+    } else {
+      keep(new Uint8Array(await response.arrayBuffer()));
+    }
+    const bytes = new Uint8Array(total);
+    let offset = 0;
+    for (const chunk of chunks) {
+      bytes.set(chunk, offset);
+      offset += chunk.byteLength;
+    }
+    const headers = [];
+    response.headers.forEach((value, key) => headers.push([key.toLowerCase(), value]));
+    return { status: response.status, headers, body: new TextDecoder().decode(bytes) };
+  } catch (err) {
+    if (timedOut) fail(`Request timed out after ${timeoutMs} ms`);
+    throw err;
+  } finally {
+    if (timer !== null) clearTimeout(timer);
+  }
+}
 "#)]
 extern "C" {
     #[wasm_bindgen(js_name = jsAppStateLoad)]
@@ -924,6 +1648,8 @@ extern "C" {
     fn js_prefs_save(key: &str, raw_json: &str) -> Promise;
     #[wasm_bindgen(js_name = jsPrefsDelete)]
     fn js_prefs_delete(key: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsPrefsUsage)]
+    fn js_prefs_usage() -> Promise;
 
     #[wasm_bindgen(js_name = jsCachePutText)]
     fn js_cache_put_text(cache_name: &str, key: &str, value: &str) -> Promise;
@@ -931,31 +1657,71 @@ extern "C" {
     fn js_cache_get_text(cache_name: &str, key: &str) -> Promise;
     #[wasm_bindgen(js_name = jsCacheDelete)]
     fn js_cache_delete(cache_name: &str, key: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsCacheUsage)]
+    fn js_cache_usage() -> Promise;
+    #[wasm_bindgen(js_name = jsCacheClear)]
+    fn js_cache_clear(cache_name: &str) -> Promise;
 
     #[wasm_bindgen(js_name = jsExplorerStatus)]
     fn js_explorer_status() -> Promise;
     #[wasm_bindgen(js_name = jsExplorerPickNativeDirectory)]
     fn js_explorer_pick_native_directory() -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerUnmountNativeDirectory)]
+    fn js_explorer_unmount_native_directory(path: &str) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerRequestPermission)]
     fn js_explorer_request_permission(mode: &str) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerListDir)]
     fn js_explorer_list_dir(path: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerListDirPage)]
+    fn js_explorer_list_dir_page(path: &str, offset: u32, limit: u32) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerReadTextFile)]
     fn js_explorer_read_text_file(path: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerReadFileBytes)]
+    fn js_explorer_read_file_bytes(path: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerReadFileChunk)]
+    fn js_explorer_read_file_chunk(path: &str, offset: f64, len: u32) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerWriteTextFile)]
     fn js_explorer_write_text_file(path: &str, text: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerWriteFileBytes)]
+    fn js_explorer_write_file_bytes(path: &str, bytes: &[u8]) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerWriteFileChunk)]
+    fn js_explorer_write_file_chunk(path: &str, offset: f64, bytes: &[u8]) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerPickImportFiles)]
+    fn js_explorer_pick_import_files() -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerSaveDownload)]
+    fn js_explorer_save_download(name: &str, bytes: &[u8]) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerCreateDir)]
     fn js_explorer_create_dir(path: &str) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerCreateFile)]
     fn js_explorer_create_file(path: &str, text: &str) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerDelete)]
     fn js_explorer_delete(path: &str, recursive: bool) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerRename)]
+    fn js_explorer_rename(from: &str, to: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerBatch)]
+    fn js_explorer_batch(ops: JsValue) -> Promise;
     #[wasm_bindgen(js_name = jsExplorerStat)]
     fn js_explorer_stat(path: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerUsage)]
+    fn js_explorer_usage() -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerRequestPersistentStorage)]
+    fn js_explorer_request_persistent_storage() -> Promise;
     #[wasm_bindgen(js_name = jsExplorerClearNativeRoot)]
     fn js_explorer_clear_native_root() -> Promise;
+    #[wasm_bindgen(js_name = jsExplorerBroadcastChanges)]
+    fn js_explorer_broadcast_changes(paths: js_sys::Array);
+    #[wasm_bindgen(js_name = jsExplorerListenChanges)]
+    fn js_explorer_listen_changes(callback: &Closure<dyn Fn(String)>);
+    #[wasm_bindgen(js_name = jsExplorerWatchNative)]
+    fn js_explorer_watch_native(path: &str, callback: &Closure<dyn Fn(String)>) -> u32;
+    #[wasm_bindgen(js_name = jsExplorerUnwatchNative)]
+    fn js_explorer_unwatch_native(id: u32);
     #[wasm_bindgen(js_name = jsOpenExternalUrl)]
     fn js_open_external_url(url: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsClipboardWriteText)]
+    fn js_clipboard_write_text(text: &str) -> Promise;
+    #[wasm_bindgen(js_name = jsHttpSend)]
+    fn js_http_send(request: JsValue) -> Promise;
 }
 
 async fn await_promise(promise: Promise) -> Result<JsValue, String> {
@@ -1033,6 +1799,10 @@ pub async fn delete_pref(key: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub async fn prefs_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    promise_to_json(js_prefs_usage()).await
+}
+
 pub async fn cache_put_text(cache_name: &str, key: &str, value: &str) -> Result<(), String> {
     let _ = await_promise(js_cache_put_text(cache_name, key, value)).await?;
     Ok(())
@@ -1055,6 +1825,15 @@ pub async fn cache_delete(cache_name: &str, key: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub async fn cache_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    promise_to_json(js_cache_usage()).await
+}
+
+pub async fn cache_clear(cache_name: &str) -> Result<(), String> {
+    let _ = await_promise(js_cache_clear(cache_name)).await?;
+    Ok(())
+}
+
 pub async fn explorer_status() -> Result<ExplorerBackendStatus, String> {
     promise_to_json(js_explorer_status()).await
 }
@@ -1063,6 +1842,12 @@ pub async fn explorer_pick_native_directory() -> Result<ExplorerBackendStatus, S
     promise_to_json(js_explorer_pick_native_directory()).await
 }
 
+pub async fn explorer_unmount_native_directory(
+    path: &str,
+) -> Result<ExplorerBackendStatus, String> {
+    promise_to_json(js_explorer_unmount_native_directory(path)).await
+}
+
 pub async fn explorer_request_permission(
     mode: ExplorerPermissionMode,
 ) -> Result<ExplorerPermissionState, String> {
@@ -1077,14 +1862,62 @@ pub async fn explorer_list_dir(path: &str) -> Result<ExplorerListResult, String>
     promise_to_json(js_explorer_list_dir(path)).await
 }
 
+pub async fn explorer_list_dir_page(
+    path: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<ExplorerListPage, String> {
+    let offset = u32::try_from(offset).unwrap_or(u32::MAX);
+    let limit = u32::try_from(limit).unwrap_or(u32::MAX);
+    promise_to_json(js_explorer_list_dir_page(path, offset, limit)).await
+}
+
 pub async fn explorer_read_text_file(path: &str) -> Result<ExplorerFileReadResult, String> {
     promise_to_json(js_explorer_read_text_file(path)).await
 }
 
+pub async fn explorer_read_file_bytes(path: &str) -> Result<ExplorerFileBytesResult, String> {
+    promise_to_json(js_explorer_read_file_bytes(path)).await
+}
+
+pub async fn explorer_read_file_chunk(
+    path: &str,
+    offset: u64,
+    len: usize,
+) -> Result<ExplorerFileChunk, String> {
+    // Offsets cross into JS as numbers, which are exact for any realistic file size.
+    let len = u32::try_from(len).unwrap_or(u32::MAX);
+    promise_to_json(js_explorer_read_file_chunk(path, offset as f64, len)).await
+}
+
 pub async fn explorer_write_text_file(path: &str, text: &str) -> Result<ExplorerMetadata, String> {
     promise_to_json(js_explorer_write_text_file(path, text)).await
 }
 
+pub async fn explorer_write_file_bytes(
+    path: &str,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    promise_to_json(js_explorer_write_file_bytes(path, bytes)).await
+}
+
+pub async fn explorer_write_file_chunk(
+    path: &str,
+    offset: u64,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    promise_to_json(js_explorer_write_file_chunk(path, offset as f64, bytes)).await
+}
+
+pub async fn explorer_pick_import_files() -> Result<Vec<ExplorerImportFile>, String> {
+    promise_to_json(js_explorer_pick_import_files()).await
+}
+
+pub async fn explorer_save_download(name: &str, bytes: &[u8]) -> Result<(), String> {
+    let _ = await_promise(js_explorer_save_download(name, bytes)).await?;
+    Ok(())
+}
+
 pub async fn explorer_create_dir(path: &str) -> Result<ExplorerMetadata, String> {
     promise_to_json(js_explorer_create_dir(path)).await
 }
@@ -1098,16 +1931,70 @@ pub async fn explorer_delete(path: &str, recursive: bool) -> Result<(), String>
     Ok(())
 }
 
+pub async fn explorer_rename(from: &str, to: &str) -> Result<ExplorerMetadata, String> {
+    promise_to_json(js_explorer_rename(from, to)).await
+}
+
+pub async fn explorer_batch(ops: &[ExplorerBatchOp]) -> Result<Vec<ExplorerBatchResult>, String> {
+    let value = ops
+        .serialize(&Serializer::json_compatible())
+        .map_err(|e| e.to_string())?;
+    promise_to_json(js_explorer_batch(value)).await
+}
+
 pub async fn explorer_stat(path: &str) -> Result<ExplorerMetadata, String> {
     promise_to_json(js_explorer_stat(path)).await
 }
 
+pub async fn explorer_usage() -> Result<ExplorerStorageUsage, String> {
+    promise_to_json(js_explorer_usage()).await
+}
+
+pub async fn explorer_request_persistent_storage() -> Result<bool, String> {
+    promise_to_json(js_explorer_request_persistent_storage()).await
+}
+
 #[allow(dead_code)]
 pub async fn explorer_clear_native_root() -> Result<ExplorerBackendStatus, String> {
     promise_to_json(js_explorer_clear_native_root()).await
 }
 
+pub fn explorer_broadcast_changes(paths: &[&str]) {
+    js_explorer_broadcast_changes(paths.iter().map(|path| JsValue::from_str(path)).collect());
+}
+
+pub fn explorer_listen_changes(on_change: Rc<dyn Fn(String)>) {
+    // The listener lives as long as the page, so its closure is leaked on purpose.
+    let callback = Closure::<dyn Fn(String)>::new(move |changed: String| on_change(changed));
+    js_explorer_listen_changes(&callback);
+    callback.forget();
+}
+
+pub fn explorer_watch_native(path: &str, on_change: Rc<dyn Fn(String)>) -> ExplorerWatch {
+    let callback = Closure::<dyn Fn(String)>::new(move |changed: String| on_change(changed));
+    match js_explorer_watch_native(path, &callback) {
+        0 => ExplorerWatch::inactive(),
+        id => ExplorerWatch::new(move || {
+            // Unwatch first so JS never calls the closure after it is dropped.
+            js_explorer_unwatch_native(id);
+            drop(callback);
+        }),
+    }
+}
+
 pub async fn open_external_url(url: &str) -> Result<(), String> {
     let _ = await_promise(js_open_external_url(url)).await?;
     Ok(())
 }
+
+pub async fn clipboard_write_text(text: &str) -> Result<(), String> {
+    let _ = await_promise(js_clipboard_write_text(text)).await?;
+    Ok(())
+}
+
+pub async fn http_send(request: &HttpRequest) -> Result<HttpResponse, String> {
+    let value = request
+        .serialize(&Serializer::json_compatible())
+        .map_err(|e| e.to_string())?;
+    promise_to_json(js_http_send(value)).await
+}
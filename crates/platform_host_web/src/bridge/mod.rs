@@ -8,10 +8,14 @@ mod cache;
 mod fs;
 mod interop;
 mod prefs;
+mod watch;
 
 use platform_host::{
-    AppStateEnvelope, ExplorerBackendStatus, ExplorerFileReadResult, ExplorerListResult,
-    ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState,
+    AppStateEnvelope, ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult,
+    ExplorerChangeCallback, ExplorerFileBytesResult, ExplorerFileChunk, ExplorerFileReadResult,
+    ExplorerImportFile, ExplorerListPage, ExplorerListResult, ExplorerMetadata,
+    ExplorerPermissionMode, ExplorerPermissionState, ExplorerStorageUsage, ExplorerWatch,
+    HttpRequest, HttpResponse, StorageUsageEntry,
 };
 
 pub async fn load_app_state_envelope(namespace: &str) -> Result<Option<AppStateEnvelope>, String> {
@@ -42,6 +46,10 @@ pub async fn delete_pref(key: &str) -> Result<(), String> {
     prefs::delete_pref(key).await
 }
 
+pub async fn prefs_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    prefs::prefs_usage().await
+}
+
 pub async fn cache_put_text(cache_name: &str, key: &str, value: &str) -> Result<(), String> {
     cache::cache_put_text(cache_name, key, value).await
 }
@@ -54,6 +62,14 @@ pub async fn cache_delete(cache_name: &str, key: &str) -> Result<(), String> {
     cache::cache_delete(cache_name, key).await
 }
 
+pub async fn cache_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    cache::cache_usage().await
+}
+
+pub async fn cache_clear(cache_name: &str) -> Result<(), String> {
+    cache::cache_clear(cache_name).await
+}
+
 pub async fn explorer_status() -> Result<ExplorerBackendStatus, String> {
     fs::explorer_status().await
 }
@@ -62,6 +78,12 @@ pub async fn explorer_pick_native_directory() -> Result<ExplorerBackendStatus, S
     fs::explorer_pick_native_directory().await
 }
 
+pub async fn explorer_unmount_native_directory(
+    path: &str,
+) -> Result<ExplorerBackendStatus, String> {
+    fs::explorer_unmount_native_directory(path).await
+}
+
 pub async fn explorer_request_permission(
     mode: ExplorerPermissionMode,
 ) -> Result<ExplorerPermissionState, String> {
@@ -72,14 +94,57 @@ pub async fn explorer_list_dir(path: &str) -> Result<ExplorerListResult, String>
     fs::explorer_list_dir(path).await
 }
 
+pub async fn explorer_list_dir_page(
+    path: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<ExplorerListPage, String> {
+    fs::explorer_list_dir_page(path, offset, limit).await
+}
+
 pub async fn explorer_read_text_file(path: &str) -> Result<ExplorerFileReadResult, String> {
     fs::explorer_read_text_file(path).await
 }
 
+pub async fn explorer_read_file_bytes(path: &str) -> Result<ExplorerFileBytesResult, String> {
+    fs::explorer_read_file_bytes(path).await
+}
+
+pub async fn explorer_read_file_chunk(
+    path: &str,
+    offset: u64,
+    len: usize,
+) -> Result<ExplorerFileChunk, String> {
+    fs::explorer_read_file_chunk(path, offset, len).await
+}
+
 pub async fn explorer_write_text_file(path: &str, text: &str) -> Result<ExplorerMetadata, String> {
     fs::explorer_write_text_file(path, text).await
 }
 
+pub async fn explorer_write_file_bytes(
+    path: &str,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    fs::explorer_write_file_bytes(path, bytes).await
+}
+
+pub async fn explorer_write_file_chunk(
+    path: &str,
+    offset: u64,
+    bytes: &[u8],
+) -> Result<ExplorerMetadata, String> {
+    fs::explorer_write_file_chunk(path, offset, bytes).await
+}
+
+pub async fn explorer_pick_import_files() -> Result<Vec<ExplorerImportFile>, String> {
+    fs::explorer_pick_import_files().await
+}
+
+pub async fn explorer_save_download(name: &str, bytes: &[u8]) -> Result<(), String> {
+    fs::explorer_save_download(name, bytes).await
+}
+
 pub async fn explorer_create_dir(path: &str) -> Result<ExplorerMetadata, String> {
     fs::explorer_create_dir(path).await
 }
@@ -92,14 +157,42 @@ pub async fn explorer_delete(path: &str, recursive: bool) -> Result<(), String>
     fs::explorer_delete(path, recursive).await
 }
 
+pub async fn explorer_rename(from: &str, to: &str) -> Result<ExplorerMetadata, String> {
+    fs::explorer_rename(from, to).await
+}
+
+pub async fn explorer_batch(ops: &[ExplorerBatchOp]) -> Result<Vec<ExplorerBatchResult>, String> {
+    fs::explorer_batch(ops).await
+}
+
 pub async fn explorer_stat(path: &str) -> Result<ExplorerMetadata, String> {
     fs::explorer_stat(path).await
 }
 
+pub async fn explorer_usage() -> Result<ExplorerStorageUsage, String> {
+    fs::explorer_usage().await
+}
+
+pub async fn explorer_request_persistent_storage() -> Result<bool, String> {
+    fs::explorer_request_persistent_storage().await
+}
+
+pub fn explorer_watch(path: &str, on_change: ExplorerChangeCallback) -> ExplorerWatch {
+    watch::explorer_watch(path, on_change)
+}
+
 pub async fn open_external_url(url: &str) -> Result<(), String> {
     interop::open_external_url(url).await
 }
 
+pub async fn clipboard_write_text(text: &str) -> Result<(), String> {
+    interop::clipboard_write_text(text).await
+}
+
+pub async fn http_send(request: &HttpRequest) -> Result<HttpResponse, String> {
+    interop::http_send(request).await
+}
+
 #[cfg(test)]
 mod tests {
     use futures::executor::block_on;
@@ -140,6 +233,8 @@ mod tests {
             None
         );
         block_on(cache_delete("cache", "k")).expect("delete text");
+        assert!(block_on(cache_usage()).expect("usage").is_empty());
+        block_on(cache_clear("cache")).expect("clear cache");
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -151,6 +246,7 @@ mod tests {
         );
         block_on(save_pref("retrodesk.explorer.prefs.v1", "{\"k\":1}")).expect("save pref");
         block_on(delete_pref("retrodesk.explorer.prefs.v1")).expect("delete pref");
+        assert!(block_on(prefs_usage()).expect("usage").is_empty());
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -167,6 +263,11 @@ mod tests {
             block_on(explorer_pick_native_directory()).expect_err("pick should fail"),
             expected
         );
+        assert_eq!(
+            block_on(explorer_unmount_native_directory("/mnt/docs"))
+                .expect_err("unmount should fail"),
+            expected
+        );
         assert_eq!(
             block_on(explorer_request_permission(ExplorerPermissionMode::Read))
                 .expect_err("permission should fail"),
@@ -176,6 +277,10 @@ mod tests {
             block_on(explorer_list_dir("/")).expect_err("list should fail"),
             expected
         );
+        assert_eq!(
+            block_on(explorer_list_dir_page("/", 0, 100)).expect_err("list page should fail"),
+            expected
+        );
         assert_eq!(
             block_on(explorer_read_text_file("/readme.txt")).expect_err("read should fail"),
             expected
@@ -185,6 +290,25 @@ mod tests {
                 .expect_err("write should fail"),
             expected
         );
+        assert_eq!(
+            block_on(explorer_write_file_bytes("/photo.png", &[0x89, b'P']))
+                .expect_err("write bytes should fail"),
+            expected
+        );
+        assert_eq!(
+            block_on(explorer_write_file_chunk("/photo.png", 0, &[0x89, b'P']))
+                .expect_err("write chunk should fail"),
+            expected
+        );
+        assert_eq!(
+            block_on(explorer_pick_import_files()).expect_err("import should fail"),
+            expected
+        );
+        assert_eq!(
+            block_on(explorer_save_download("readme.txt", b"text"))
+                .expect_err("save download should fail"),
+            expected
+        );
         assert_eq!(
             block_on(explorer_create_dir("/Docs")).expect_err("create dir should fail"),
             expected
@@ -198,9 +322,26 @@ mod tests {
             block_on(explorer_delete("/Docs/new.txt", false)).expect_err("delete should fail"),
             expected
         );
+        assert_eq!(
+            block_on(explorer_rename("/Docs/new.txt", "/Docs/old.txt"))
+                .expect_err("rename should fail"),
+            expected
+        );
+        assert_eq!(
+            block_on(explorer_batch(&[])).expect_err("batch should fail"),
+            expected
+        );
         assert_eq!(
             block_on(explorer_stat("/Docs")).expect_err("stat should fail"),
             expected
         );
+        assert_eq!(
+            block_on(explorer_usage()).expect_err("usage should fail"),
+            expected
+        );
+        assert_eq!(
+            block_on(explorer_request_persistent_storage()).expect_err("persist should fail"),
+            expected
+        );
     }
 }
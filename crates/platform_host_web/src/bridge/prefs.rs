@@ -1,3 +1,5 @@
+use platform_host::StorageUsageEntry;
+
 pub(crate) async fn load_pref(key: &str) -> Result<Option<String>, String> {
     super::interop::load_pref(key).await
 }
@@ -9,3 +11,7 @@ pub(crate) async fn save_pref(key: &str, raw_json: &str) -> Result<(), String> {
 pub(crate) async fn delete_pref(key: &str) -> Result<(), String> {
     super::interop::delete_pref(key).await
 }
+
+pub(crate) async fn prefs_usage() -> Result<Vec<StorageUsageEntry>, String> {
+    super::interop::prefs_usage().await
+}
@@ -0,0 +1,68 @@
+//! Change notifications for explorer paths.
+//!
+//! Mutations made through this bridge notify watches in the same page directly and reach other
+//! pages of the same origin over a `BroadcastChannel`. Changes made outside the app are only seen
+//! for a connected native folder in browsers that provide `FileSystemObserver`.
+
+use std::{cell::Cell, rc::Rc};
+
+use platform_host::{ExplorerChangeCallback, ExplorerWatch, ExplorerWatchRegistry};
+
+thread_local! {
+    static WATCHES: ExplorerWatchRegistry = ExplorerWatchRegistry::default();
+    static LISTENING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn registry() -> ExplorerWatchRegistry {
+    WATCHES.with(Clone::clone)
+}
+
+pub(crate) fn explorer_watch(path: &str, on_change: ExplorerChangeCallback) -> ExplorerWatch {
+    if !LISTENING.with(|listening| listening.replace(true)) {
+        super::interop::explorer_listen_changes(Rc::new(|changed: String| {
+            registry().notify(&changed)
+        }));
+    }
+    let local = registry().watch(path, Rc::clone(&on_change));
+    let native = super::interop::explorer_watch_native(
+        path,
+        Rc::new(move |changed: String| on_change(&changed)),
+    );
+    ExplorerWatch::new(move || {
+        drop(local);
+        drop(native);
+    })
+}
+
+pub(crate) fn explorer_notify_changed(paths: &[&str]) {
+    let registry = registry();
+    for path in paths {
+        registry.notify(path);
+    }
+    super::interop::explorer_broadcast_changes(paths);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[test]
+    fn bridge_mutations_reach_watches_in_the_same_page() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&seen);
+        let watch = explorer_watch(
+            "/Docs",
+            Rc::new(move |path: &str| log.borrow_mut().push(path.to_string())),
+        );
+
+        explorer_notify_changed(&["/Docs/a.txt", "/Other/b.txt"]);
+        explorer_notify_changed(&["/Docs/sub/deep.txt"]);
+        assert_eq!(*seen.borrow(), ["/Docs/a.txt"]);
+
+        drop(watch);
+        explorer_notify_changed(&["/Docs/c.txt"]);
+        assert_eq!(seen.borrow().len(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 //! Tauri command-backed content cache implementation.
 
-use platform_host::{ContentCache, ContentCacheFuture};
+use platform_host::{ContentCache, ContentCacheFuture, StorageUsageEntry};
 
 #[derive(Debug, Clone, Copy, Default)]
 /// Desktop content cache backed by Tauri command transport.
@@ -31,6 +31,17 @@ impl ContentCache for TauriContentCache {
     ) -> ContentCacheFuture<'a, Result<(), String>> {
         Box::pin(async move { crate::bridge::cache_delete(cache_name, key).await })
     }
+
+    fn cache_usage<'a>(&'a self) -> ContentCacheFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        Box::pin(async move { crate::bridge::cache_usage().await })
+    }
+
+    fn clear_cache<'a>(
+        &'a self,
+        cache_name: &'a str,
+    ) -> ContentCacheFuture<'a, Result<(), String>> {
+        Box::pin(async move { crate::bridge::cache_clear(cache_name).await })
+    }
 }
 
 #[cfg(test)]
@@ -51,5 +62,7 @@ mod tests {
             None
         );
         block_on(cache_obj.delete("cache", "k")).expect("delete");
+        assert!(block_on(cache_obj.cache_usage()).expect("usage").is_empty());
+        block_on(cache_obj.clear_cache("cache")).expect("clear");
     }
 }
@@ -0,0 +1,25 @@
+//! Clipboard host-service adapters for browser and desktop-webview contexts.
+
+use platform_host::{ClipboardFuture, ClipboardService};
+
+use crate::bridge;
+
+#[derive(Debug, Clone, Copy, Default)]
+/// Browser clipboard adapter backed by the async Clipboard API.
+pub struct WebClipboardService;
+
+impl ClipboardService for WebClipboardService {
+    fn write_text<'a>(&'a self, text: &'a str) -> ClipboardFuture<'a, Result<(), String>> {
+        Box::pin(async move { bridge::clipboard_write_text(text).await })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// Desktop-webview clipboard adapter backed by the webview's Clipboard API.
+pub struct TauriClipboardService;
+
+impl ClipboardService for TauriClipboardService {
+    fn write_text<'a>(&'a self, text: &'a str) -> ClipboardFuture<'a, Result<(), String>> {
+        Box::pin(async move { bridge::clipboard_write_text(text).await })
+    }
+}
@@ -1,8 +1,10 @@
 //! Browser explorer/filesystem service backed by the shared JS bridge.
 
 use platform_host::{
-    ExplorerBackendStatus, ExplorerFileReadResult, ExplorerFsFuture, ExplorerFsService,
-    ExplorerListResult, ExplorerMetadata, ExplorerPermissionMode, ExplorerPermissionState,
+    ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult, ExplorerChangeCallback,
+    ExplorerFileBytesResult, ExplorerFileChunk, ExplorerFileReadResult, ExplorerFsFuture,
+    ExplorerFsService, ExplorerImportFile, ExplorerListPage, ExplorerListResult, ExplorerMetadata,
+    ExplorerPermissionMode, ExplorerPermissionState, ExplorerStorageUsage, ExplorerWatch,
 };
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -20,6 +22,13 @@ impl ExplorerFsService for WebExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_pick_native_directory().await })
     }
 
+    fn unmount_native_directory<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+        Box::pin(async move { crate::bridge::explorer_unmount_native_directory(path).await })
+    }
+
     fn request_permission<'a>(
         &'a self,
         mode: ExplorerPermissionMode,
@@ -34,6 +43,15 @@ impl ExplorerFsService for WebExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_list_dir(path).await })
     }
 
+    fn list_dir_page<'a>(
+        &'a self,
+        path: &'a str,
+        offset: usize,
+        limit: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>> {
+        Box::pin(async move { crate::bridge::explorer_list_dir_page(path, offset, limit).await })
+    }
+
     fn read_text_file<'a>(
         &'a self,
         path: &'a str,
@@ -41,6 +59,22 @@ impl ExplorerFsService for WebExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_read_text_file(path).await })
     }
 
+    fn read_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>> {
+        Box::pin(async move { crate::bridge::explorer_read_file_bytes(path).await })
+    }
+
+    fn read_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        len: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> {
+        Box::pin(async move { crate::bridge::explorer_read_file_chunk(path, offset, len).await })
+    }
+
     fn write_text_file<'a>(
         &'a self,
         path: &'a str,
@@ -49,6 +83,37 @@ impl ExplorerFsService for WebExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_write_text_file(path, text).await })
     }
 
+    fn write_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { crate::bridge::explorer_write_file_bytes(path, bytes).await })
+    }
+
+    fn write_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { crate::bridge::explorer_write_file_chunk(path, offset, bytes).await })
+    }
+
+    fn pick_import_files<'a>(
+        &'a self,
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>> {
+        Box::pin(async move { crate::bridge::explorer_pick_import_files().await })
+    }
+
+    fn save_download<'a>(
+        &'a self,
+        name: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<(), String>> {
+        Box::pin(async move { crate::bridge::explorer_save_download(name, bytes).await })
+    }
+
     fn create_dir<'a>(
         &'a self,
         path: &'a str,
@@ -72,9 +137,36 @@ impl ExplorerFsService for WebExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_delete(path, recursive).await })
     }
 
+    fn rename<'a>(
+        &'a self,
+        from: &'a str,
+        to: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { crate::bridge::explorer_rename(from, to).await })
+    }
+
+    fn batch<'a>(
+        &'a self,
+        ops: &'a [ExplorerBatchOp],
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>> {
+        Box::pin(async move { crate::bridge::explorer_batch(ops).await })
+    }
+
     fn stat<'a>(&'a self, path: &'a str) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
         Box::pin(async move { crate::bridge::explorer_stat(path).await })
     }
+
+    fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>> {
+        Box::pin(async move { crate::bridge::explorer_usage().await })
+    }
+
+    fn request_persistent_storage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<bool, String>> {
+        Box::pin(async move { crate::bridge::explorer_request_persistent_storage().await })
+    }
+
+    fn watch(&self, path: &str, on_change: ExplorerChangeCallback) -> ExplorerWatch {
+        crate::bridge::explorer_watch(path, on_change)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -92,6 +184,13 @@ impl ExplorerFsService for TauriExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_pick_native_directory().await })
     }
 
+    fn unmount_native_directory<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+        Box::pin(async move { crate::bridge::explorer_unmount_native_directory(path).await })
+    }
+
     fn request_permission<'a>(
         &'a self,
         mode: ExplorerPermissionMode,
@@ -106,6 +205,15 @@ impl ExplorerFsService for TauriExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_list_dir(path).await })
     }
 
+    fn list_dir_page<'a>(
+        &'a self,
+        path: &'a str,
+        offset: usize,
+        limit: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>> {
+        Box::pin(async move { crate::bridge::explorer_list_dir_page(path, offset, limit).await })
+    }
+
     fn read_text_file<'a>(
         &'a self,
         path: &'a str,
@@ -113,6 +221,22 @@ impl ExplorerFsService for TauriExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_read_text_file(path).await })
     }
 
+    fn read_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>> {
+        Box::pin(async move { crate::bridge::explorer_read_file_bytes(path).await })
+    }
+
+    fn read_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        len: usize,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> {
+        Box::pin(async move { crate::bridge::explorer_read_file_chunk(path, offset, len).await })
+    }
+
     fn write_text_file<'a>(
         &'a self,
         path: &'a str,
@@ -121,6 +245,37 @@ impl ExplorerFsService for TauriExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_write_text_file(path, text).await })
     }
 
+    fn write_file_bytes<'a>(
+        &'a self,
+        path: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { crate::bridge::explorer_write_file_bytes(path, bytes).await })
+    }
+
+    fn write_file_chunk<'a>(
+        &'a self,
+        path: &'a str,
+        offset: u64,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { crate::bridge::explorer_write_file_chunk(path, offset, bytes).await })
+    }
+
+    fn pick_import_files<'a>(
+        &'a self,
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>> {
+        Box::pin(async move { crate::bridge::explorer_pick_import_files().await })
+    }
+
+    fn save_download<'a>(
+        &'a self,
+        name: &'a str,
+        bytes: &'a [u8],
+    ) -> ExplorerFsFuture<'a, Result<(), String>> {
+        Box::pin(async move { crate::bridge::explorer_save_download(name, bytes).await })
+    }
+
     fn create_dir<'a>(
         &'a self,
         path: &'a str,
@@ -144,9 +299,36 @@ impl ExplorerFsService for TauriExplorerFsService {
         Box::pin(async move { crate::bridge::explorer_delete(path, recursive).await })
     }
 
+    fn rename<'a>(
+        &'a self,
+        from: &'a str,
+        to: &'a str,
+    ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+        Box::pin(async move { crate::bridge::explorer_rename(from, to).await })
+    }
+
+    fn batch<'a>(
+        &'a self,
+        ops: &'a [ExplorerBatchOp],
+    ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>> {
+        Box::pin(async move { crate::bridge::explorer_batch(ops).await })
+    }
+
     fn stat<'a>(&'a self, path: &'a str) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
         Box::pin(async move { crate::bridge::explorer_stat(path).await })
     }
+
+    fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>> {
+        Box::pin(async move { crate::bridge::explorer_usage().await })
+    }
+
+    fn request_persistent_storage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<bool, String>> {
+        Box::pin(async move { crate::bridge::explorer_request_persistent_storage().await })
+    }
+
+    fn watch(&self, path: &str, on_change: ExplorerChangeCallback) -> ExplorerWatch {
+        crate::bridge::explorer_watch(path, on_change)
+    }
 }
 
 #[cfg(test)]
@@ -167,6 +349,10 @@ mod tests {
             block_on(fs_obj.pick_native_directory()).expect_err("pick native dir"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.unmount_native_directory("/mnt/docs")).expect_err("unmount"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.request_permission(ExplorerPermissionMode::Read))
                 .expect_err("request permission"),
@@ -176,14 +362,42 @@ mod tests {
             block_on(fs_obj.list_dir("/")).expect_err("list dir"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.list_dir_page("/", 0, 100)).expect_err("list dir page"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.read_text_file("/demo.txt")).expect_err("read file"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.read_file_bytes("/demo.png")).expect_err("read file bytes"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.read_file_chunk("/demo.png", 0, 16)).expect_err("read file chunk"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.write_text_file("/demo.txt", "text")).expect_err("write file"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.write_file_bytes("/demo.png", &[1, 2])).expect_err("write bytes"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.write_file_chunk("/demo.png", 0, &[1, 2])).expect_err("write chunk"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.pick_import_files()).expect_err("pick import files"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.save_download("demo.txt", b"text")).expect_err("download"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.create_dir("/Demo")).expect_err("create dir"),
             expected
@@ -196,10 +410,20 @@ mod tests {
             block_on(fs_obj.delete("/Demo/new.txt", false)).expect_err("delete"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.rename("/Demo/new.txt", "/Demo/old.txt")).expect_err("rename"),
+            expected
+        );
+        assert_eq!(block_on(fs_obj.batch(&[])).expect_err("batch"), expected);
         assert_eq!(
             block_on(fs_obj.stat("/Demo/new.txt")).expect_err("stat"),
             expected
         );
+        assert_eq!(block_on(fs_obj.usage()).expect_err("usage"), expected);
+        assert_eq!(
+            block_on(fs_obj.request_persistent_storage()).expect_err("persist"),
+            expected
+        );
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -214,6 +438,10 @@ mod tests {
             block_on(fs_obj.pick_native_directory()).expect_err("pick native dir"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.unmount_native_directory("/mnt/docs")).expect_err("unmount"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.request_permission(ExplorerPermissionMode::Read))
                 .expect_err("request permission"),
@@ -223,14 +451,42 @@ mod tests {
             block_on(fs_obj.list_dir("/")).expect_err("list dir"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.list_dir_page("/", 0, 100)).expect_err("list dir page"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.read_text_file("/demo.txt")).expect_err("read file"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.read_file_bytes("/demo.png")).expect_err("read file bytes"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.read_file_chunk("/demo.png", 0, 16)).expect_err("read file chunk"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.write_text_file("/demo.txt", "text")).expect_err("write file"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.write_file_bytes("/demo.png", &[1, 2])).expect_err("write bytes"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.write_file_chunk("/demo.png", 0, &[1, 2])).expect_err("write chunk"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.pick_import_files()).expect_err("pick import files"),
+            expected
+        );
+        assert_eq!(
+            block_on(fs_obj.save_download("demo.txt", b"text")).expect_err("download"),
+            expected
+        );
         assert_eq!(
             block_on(fs_obj.create_dir("/Demo")).expect_err("create dir"),
             expected
@@ -243,9 +499,19 @@ mod tests {
             block_on(fs_obj.delete("/Demo/new.txt", false)).expect_err("delete"),
             expected
         );
+        assert_eq!(
+            block_on(fs_obj.rename("/Demo/new.txt", "/Demo/old.txt")).expect_err("rename"),
+            expected
+        );
+        assert_eq!(block_on(fs_obj.batch(&[])).expect_err("batch"), expected);
         assert_eq!(
             block_on(fs_obj.stat("/Demo/new.txt")).expect_err("stat"),
             expected
         );
+        assert_eq!(block_on(fs_obj.usage()).expect_err("usage"), expected);
+        assert_eq!(
+            block_on(fs_obj.request_persistent_storage()).expect_err("persist"),
+            expected
+        );
     }
 }
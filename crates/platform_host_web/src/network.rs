@@ -0,0 +1,31 @@
+//! HTTP host-service adapters for browser and desktop-webview contexts.
+
+use platform_host::{HttpFuture, HttpRequest, HttpResponse, HttpService};
+
+use crate::bridge;
+
+#[derive(Debug, Clone, Copy, Default)]
+/// Browser HTTP adapter backed by `fetch`.
+pub struct WebHttpService;
+
+impl HttpService for WebHttpService {
+    fn send<'a>(
+        &'a self,
+        request: &'a HttpRequest,
+    ) -> HttpFuture<'a, Result<HttpResponse, String>> {
+        Box::pin(async move { bridge::http_send(request).await })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// Desktop-webview HTTP adapter backed by the webview's `fetch`.
+pub struct TauriHttpService;
+
+impl HttpService for TauriHttpService {
+    fn send<'a>(
+        &'a self,
+        request: &'a HttpRequest,
+    ) -> HttpFuture<'a, Result<HttpResponse, String>> {
+        Box::pin(async move { bridge::http_send(request).await })
+    }
+}
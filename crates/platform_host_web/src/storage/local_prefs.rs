@@ -4,7 +4,7 @@
 //! implementing [`platform_host::PrefsStore`] (async trait) for compatibility with higher-level
 //! host abstractions.
 
-use platform_host::{PrefsStore, PrefsStoreFuture};
+use platform_host::{PrefsStore, PrefsStoreFuture, StorageUsageEntry};
 use serde::{de::DeserializeOwned, Serialize};
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -74,6 +74,36 @@ impl WebPrefsStore {
         }
     }
 
+    /// Lists every localStorage key with the UTF-8 size of its value, ordered by key.
+    pub fn usage(self) -> Vec<StorageUsageEntry> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+            else {
+                return Vec::new();
+            };
+            let len = storage.length().unwrap_or(0);
+            let mut usage = (0..len)
+                .filter_map(|index| storage.key(index).ok().flatten())
+                .map(|key| {
+                    let bytes = storage
+                        .get_item(&key)
+                        .ok()
+                        .flatten()
+                        .map_or(0, |raw| raw.len());
+                    StorageUsageEntry::single(key, bytes)
+                })
+                .collect::<Vec<_>>();
+            usage.sort_by(|a, b| a.name.cmp(&b.name));
+            usage
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Vec::new()
+        }
+    }
+
     /// Loads and deserializes a typed preference value.
     pub fn load_typed<T: DeserializeOwned>(self, key: &str) -> Option<T> {
         let raw = self.load_json(key)?;
@@ -113,4 +143,9 @@ impl PrefsStore for WebPrefsStore {
         let store = *self;
         Box::pin(async move { store.delete_json(key) })
     }
+
+    fn prefs_usage<'a>(&'a self) -> PrefsStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        let store = *self;
+        Box::pin(async move { Ok(store.usage()) })
+    }
 }
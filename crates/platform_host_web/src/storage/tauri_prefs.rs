@@ -3,7 +3,7 @@
 //! This store uses the bridge interop layer, which routes preference calls to Tauri commands
 //! when available in desktop webview contexts.
 
-use platform_host::{PrefsStore, PrefsStoreFuture};
+use platform_host::{PrefsStore, PrefsStoreFuture, StorageUsageEntry};
 
 #[derive(Debug, Clone, Copy, Default)]
 /// Desktop preference store backed by Tauri command transport.
@@ -28,6 +28,10 @@ impl PrefsStore for TauriPrefsStore {
     fn delete_pref<'a>(&'a self, key: &'a str) -> PrefsStoreFuture<'a, Result<(), String>> {
         Box::pin(async move { crate::bridge::delete_pref(key).await })
     }
+
+    fn prefs_usage<'a>(&'a self) -> PrefsStoreFuture<'a, Result<Vec<StorageUsageEntry>, String>> {
+        Box::pin(async move { crate::bridge::prefs_usage().await })
+    }
 }
 
 #[cfg(test)]
@@ -48,5 +52,6 @@ mod tests {
         );
         block_on(store_obj.save_pref("retrodesk.key", "\"value\"")).expect("save");
         block_on(store_obj.delete_pref("retrodesk.key")).expect("delete");
+        assert!(block_on(store_obj.prefs_usage()).expect("usage").is_empty());
     }
 }
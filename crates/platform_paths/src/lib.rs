@@ -0,0 +1,133 @@
+//! Virtual filesystem path helpers shared by the explorer, the shell, and apps.
+//!
+//! Every helper works on `/`-separated virtual paths as the explorer services see them. Input is
+//! normalized first, so callers can pass user-typed text with backslashes, repeated slashes, and
+//! `.`/`..` segments. `..` never climbs above `/`.
+
+#![warn(missing_docs, rustdoc::broken_intra_doc_links)]
+
+/// Normalizes a virtual path.
+///
+/// Trims whitespace, converts backslashes to `/`, resolves `.`/`..`, ensures a leading slash, and
+/// returns `/` for empty or fully-collapsed paths.
+pub fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return "/".to_string();
+    }
+    let mut out = String::new();
+    for segment in trimmed.replace('\\', "/").split('/') {
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        if segment == ".." {
+            if let Some(idx) = out.rfind('/') {
+                out.truncate(idx);
+            }
+            continue;
+        }
+        out.push('/');
+        out.push_str(segment);
+    }
+    if out.is_empty() {
+        "/".to_string()
+    } else {
+        out
+    }
+}
+
+/// Whether `path` names a location from the root rather than from a working directory.
+pub fn is_absolute(path: &str) -> bool {
+    path.trim_start().starts_with(['/', '\\'])
+}
+
+/// Appends the entry `name` to the folder `base`; an empty name leaves `base` as it is.
+pub fn join_path(base: &str, name: &str) -> String {
+    let base = normalize_path(base);
+    let name = name.trim().trim_matches('/');
+    if name.is_empty() {
+        return base;
+    }
+    if base == "/" {
+        format!("/{name}")
+    } else {
+        format!("{base}/{name}")
+    }
+}
+
+/// Folder holding `path`; the root is its own parent.
+pub fn parent_path(path: &str) -> String {
+    let path = normalize_path(path);
+    if path == "/" {
+        return path;
+    }
+    match path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(idx) => path[..idx].to_string(),
+    }
+}
+
+/// Last segment of `path`, or `/` for the root.
+pub fn entry_name(path: &str) -> String {
+    let path = normalize_path(path);
+    if path == "/" {
+        "/".to_string()
+    } else {
+        path.rsplit('/').next().unwrap_or_default().to_string()
+    }
+}
+
+/// Resolves `input` against the working directory `cwd` unless it is already absolute.
+///
+/// Empty input resolves to `cwd` itself, so `cd` with a blank argument stays put.
+pub fn resolve_path(cwd: &str, input: &str) -> String {
+    if is_absolute(input) {
+        return normalize_path(input);
+    }
+    normalize_path(&format!("{}/{}", normalize_path(cwd), input.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_path_matches_expected_cases() {
+        let cases = [
+            ("", "/"),
+            ("   ", "/"),
+            ("foo/bar", "/foo/bar"),
+            ("/foo//bar/", "/foo/bar"),
+            ("./foo/../bar", "/bar"),
+            ("\\\\foo\\\\bar", "/foo/bar"),
+            ("/../../", "/"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize_path(input), expected, "input={input:?}");
+        }
+    }
+
+    #[test]
+    fn paths_split_into_parent_and_name() {
+        assert_eq!(join_path("/", "docs"), "/docs");
+        assert_eq!(join_path("/docs/", " notes.txt "), "/docs/notes.txt");
+        assert_eq!(join_path("/docs", ""), "/docs");
+        assert_eq!(parent_path("/docs/notes.txt"), "/docs");
+        assert_eq!(parent_path("/docs"), "/");
+        assert_eq!(parent_path("/"), "/");
+        assert_eq!(entry_name("/docs/notes.txt/"), "notes.txt");
+        assert_eq!(entry_name("/"), "/");
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_the_cwd() {
+        assert_eq!(resolve_path("/docs", "notes"), "/docs/notes");
+        assert_eq!(resolve_path("/docs/", "../music/./a.mp3"), "/music/a.mp3");
+        assert_eq!(resolve_path("/docs", " /music "), "/music");
+        assert_eq!(resolve_path("/docs", "\\music"), "/music");
+        assert_eq!(resolve_path("/docs", ".."), "/");
+        assert_eq!(resolve_path("/", "../.."), "/");
+        assert_eq!(resolve_path("/docs", ""), "/docs");
+        assert_eq!(resolve_path("docs", "a"), "/docs/a");
+    }
+}
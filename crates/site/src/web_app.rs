@@ -1,7 +1,7 @@
 //! Root route components and URL deep-link bootstrap for the site shell.
 
 #[cfg(any(test, target_arch = "wasm32"))]
-use desktop_app_contract::ApplicationId;
+use desktop_app_contract::{AppLink, ApplicationId};
 #[cfg(any(test, target_arch = "wasm32"))]
 use desktop_runtime::DeepLinkOpenTarget;
 use desktop_runtime::{
@@ -165,12 +165,18 @@ fn parse_open_values_from_query_like(query: &str) -> Vec<DeepLinkOpenTarget> {
 
     query
         .split('&')
-        .filter_map(|pair| {
+        .flat_map(|pair| {
             let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
-            (key == "open").then_some(value)
+            match key {
+                "open" => value.split(',').filter_map(parse_open_target).collect(),
+                "link" => AppLink::parse_encoded(value)
+                    .ok()
+                    .map(DeepLinkOpenTarget::Link)
+                    .into_iter()
+                    .collect(),
+                _ => Vec::new(),
+            }
         })
-        .flat_map(|value| value.split(','))
-        .filter_map(parse_open_target)
         .collect()
 }
 
@@ -232,6 +238,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_encoded_app_links() {
+        let parsed = parse_deep_link_from_parts(
+            "?link=web%2Bos%3A%2F%2Fsystem.explorer%2FDocuments%3Fviewer%3Deditor&open=system.terminal",
+            "",
+        );
+        assert_eq!(
+            parsed.open,
+            vec![
+                DeepLinkOpenTarget::Link(
+                    AppLink::new(ApplicationId::trusted("system.explorer"))
+                        .with_path("/Documents")
+                        .with_param("viewer", "editor"),
+                ),
+                DeepLinkOpenTarget::App(ApplicationId::trusted("system.terminal")),
+            ]
+        );
+    }
+
     #[test]
     fn parses_hash_path_style_open_target() {
         let parsed = parse_deep_link_from_parts("", "#/open/notes:hello-world");
@@ -0,0 +1,559 @@
+//! Capped, optionally persisted log of every command dispatch.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+use platform_host::{load_pref_with, save_pref_with, unix_time_ms_now, PrefsStore};
+use system_shell_contract::{
+    AuditEntry, CommandCaller, CommandOptionSpec, CommandResult, CommandValueKind,
+    DisplayPreference, ExecutionId, ShellError, ShellErrorCode, StructuredData, StructuredRecord,
+    StructuredScalar, StructuredTable, StructuredValue,
+};
+
+use crate::{
+    field_string, intrinsic_descriptor,
+    reactive::{
+        create_rw_signal, ReadSignal, RwSignal, SignalGetUntracked, SignalUpdate,
+        SignalWithUntracked,
+    },
+    CommandDispatch, CommandExecutionContext, CommandRegistryHandle, MiddlewareHandle, ShellEngine,
+    ShellMiddleware, StructuredFieldBuilder,
+};
+
+/// Default number of retained audit entries.
+pub const DEFAULT_AUDIT_CAPACITY: usize = 500;
+
+/// Rows shown by `audit list` and `audit query` without `--limit`.
+const DEFAULT_AUDIT_ROWS: usize = 50;
+
+/// Dispatches awaiting completion; older ones are dropped, e.g. when a handler never returns.
+const MAX_PENDING_DISPATCHES: usize = 64;
+
+#[derive(Clone)]
+struct AuditPersistence {
+    store: Rc<dyn PrefsStore>,
+    key: String,
+}
+
+struct PendingDispatch {
+    execution_id: ExecutionId,
+    depth: usize,
+    argv: Vec<String>,
+    caller: Option<CommandCaller>,
+    started_at_unix_ms: u64,
+}
+
+struct AuditState {
+    entries: RwSignal<Vec<AuditEntry>>,
+    capacity: usize,
+    pending: RefCell<Vec<PendingDispatch>>,
+    hydrated: Cell<bool>,
+    persistence: RefCell<Option<AuditPersistence>>,
+}
+
+/// Records every resolved command dispatch of one [`ShellEngine`].
+///
+/// Entries capture the argv, cwd, source window, caller app, exit code, error category, and the
+/// time from dispatch until the handler returned, for every session including nested script and
+/// function lines. Dispatches that fail validation or authorization are recorded with their error.
+/// The oldest entries are dropped once `capacity` is exceeded.
+///
+/// Creating a log registers a middleware plus the `audit list` and `audit query` commands until
+/// the last clone is dropped. Only the first log on an engine owns those commands; later ones are
+/// refused as registry conflicts and stay API-only.
+#[derive(Clone)]
+pub struct ShellAuditLog {
+    state: Rc<AuditState>,
+    _middleware: Rc<MiddlewareHandle>,
+    _commands: Rc<Vec<CommandRegistryHandle>>,
+}
+
+impl ShellAuditLog {
+    /// Creates an in-memory log retaining at most `capacity` entries and hooks it into `engine`.
+    pub fn new(engine: &ShellEngine, capacity: usize) -> Self {
+        let state = Rc::new(AuditState {
+            entries: create_rw_signal(Vec::new()),
+            capacity: capacity.max(1),
+            pending: RefCell::new(Vec::new()),
+            hydrated: Cell::new(false),
+            persistence: RefCell::new(None),
+        });
+        let middleware = engine.register_middleware(Rc::new(AuditRecorder {
+            state: Rc::downgrade(&state),
+        }));
+        let commands = register_audit_commands(engine, Rc::downgrade(&state));
+        Self {
+            state,
+            _middleware: Rc::new(middleware),
+            _commands: Rc::new(commands),
+        }
+    }
+
+    /// Persists entries as a JSON list under `key` in `store`.
+    ///
+    /// Writes start once [`ShellAuditLog::hydrate`] has completed so dispatches recorded during
+    /// boot never overwrite the persisted log.
+    pub fn with_prefs(self, store: Rc<dyn PrefsStore>, key: impl Into<String>) -> Self {
+        *self.state.persistence.borrow_mut() = Some(AuditPersistence {
+            store,
+            key: key.into(),
+        });
+        self
+    }
+
+    /// Loads persisted entries, keeping and renumbering any recorded before hydration finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the attached store fails or holds malformed JSON.
+    pub async fn hydrate(&self) -> Result<(), String> {
+        let Some(persistence) = self.state.persistence.borrow().clone() else {
+            return Ok(());
+        };
+        let loaded: Vec<AuditEntry> = load_pref_with(persistence.store.as_ref(), &persistence.key)
+            .await?
+            .unwrap_or_default();
+        let capacity = self.state.capacity;
+        self.state.entries.update(|entries| {
+            let recent = std::mem::replace(entries, loaded);
+            for entry in recent {
+                push_entry(entries, entry, capacity);
+            }
+        });
+        self.state.hydrated.set(true);
+        persist(&self.state);
+        Ok(())
+    }
+
+    /// Reactive audit entries, oldest first.
+    pub fn entries(&self) -> ReadSignal<Vec<AuditEntry>> {
+        self.state.entries.read_only()
+    }
+}
+
+/// Filters applied by `audit query`; `audit list` uses only the limit.
+#[derive(Default)]
+struct AuditFilter {
+    command: Option<String>,
+    app: Option<String>,
+    window: Option<u64>,
+    failed: bool,
+    since_unix_ms: Option<u64>,
+    limit: Option<usize>,
+}
+
+impl AuditFilter {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        let app = entry
+            .caller
+            .as_ref()
+            .map_or("runtime", |caller| caller.app_id.as_str());
+        self.command
+            .as_ref()
+            .is_none_or(|prefix| entry.command.display().starts_with(prefix.as_str()))
+            && self.app.as_ref().is_none_or(|wanted| wanted == app)
+            && self
+                .window
+                .is_none_or(|window| entry.source_window_id == Some(window))
+            && (!self.failed || entry.exit_code != 0)
+            && self
+                .since_unix_ms
+                .is_none_or(|since| entry.started_at_unix_ms >= since)
+    }
+}
+
+struct AuditRecorder {
+    state: Weak<AuditState>,
+}
+
+impl ShellMiddleware for AuditRecorder {
+    fn before_execute(&self, dispatch: &CommandDispatch) -> Result<(), ShellError> {
+        if let Some(state) = self.state.upgrade() {
+            let mut pending = state.pending.borrow_mut();
+            if pending.len() == MAX_PENDING_DISPATCHES {
+                pending.remove(0);
+            }
+            pending.push(PendingDispatch {
+                execution_id: dispatch.execution_id,
+                depth: dispatch.depth,
+                argv: dispatch.argv.clone(),
+                caller: dispatch.caller.clone(),
+                started_at_unix_ms: unix_time_ms_now(),
+            });
+        }
+        Ok(())
+    }
+
+    fn after_execute(&self, dispatch: &CommandDispatch, result: &CommandResult) {
+        if let Some(state) = self.state.upgrade() {
+            record(&state, dispatch, result.exit.code, result.exit.error);
+        }
+    }
+
+    fn on_error(&self, dispatch: &CommandDispatch, error: &ShellError) {
+        if let Some(state) = self.state.upgrade() {
+            record(&state, dispatch, error.exit_code(), Some(error.code));
+        }
+    }
+}
+
+fn record(
+    state: &AuditState,
+    dispatch: &CommandDispatch,
+    exit_code: i32,
+    error: Option<ShellErrorCode>,
+) {
+    let now = unix_time_ms_now();
+    let started_at_unix_ms = {
+        let mut pending = state.pending.borrow_mut();
+        pending
+            .iter()
+            .rposition(|pending| {
+                pending.execution_id == dispatch.execution_id
+                    && pending.depth == dispatch.depth
+                    && pending.argv == dispatch.argv
+                    && pending.caller == dispatch.caller
+            })
+            .map_or(now, |index| pending.remove(index).started_at_unix_ms)
+    };
+    let entry = AuditEntry {
+        seq: 0,
+        execution_id: dispatch.execution_id,
+        command: dispatch.descriptor.path.clone(),
+        argv: dispatch.argv.clone(),
+        cwd: dispatch.cwd.clone(),
+        source_window_id: dispatch.source_window_id,
+        caller: dispatch.caller.clone(),
+        depth: dispatch.depth,
+        dry_run: dispatch.dry_run,
+        started_at_unix_ms,
+        duration_ms: now.saturating_sub(started_at_unix_ms),
+        exit_code,
+        error,
+    };
+    let capacity = state.capacity;
+    state
+        .entries
+        .update(|entries| push_entry(entries, entry, capacity));
+    persist(state);
+}
+
+/// Appends `entry` with the next sequence number and trims the oldest entries past `capacity`.
+fn push_entry(entries: &mut Vec<AuditEntry>, mut entry: AuditEntry, capacity: usize) {
+    entry.seq = entries.last().map_or(1, |last| last.seq.saturating_add(1));
+    entries.push(entry);
+    if entries.len() > capacity {
+        let overflow = entries.len() - capacity;
+        entries.drain(..overflow);
+    }
+}
+
+fn persist(state: &AuditState) {
+    let Some(persistence) = state.persistence.borrow().clone() else {
+        return;
+    };
+    if !state.hydrated.get() {
+        return;
+    }
+    let entries = state.entries.get_untracked();
+    crate::reactive::spawn_local(async move {
+        if let Err(err) =
+            save_pref_with(persistence.store.as_ref(), &persistence.key, &entries).await
+        {
+            crate::reactive::warn(&format!("persist shell audit log failed: {err}"));
+        }
+    });
+}
+
+fn upgrade(state: &Weak<AuditState>) -> Result<Rc<AuditState>, ShellError> {
+    state.upgrade().ok_or_else(|| {
+        ShellError::new(
+            ShellErrorCode::Unavailable,
+            "shell audit log is not running",
+        )
+    })
+}
+
+fn option_value(context: &CommandExecutionContext, name: &str) -> Option<String> {
+    context
+        .invocation
+        .options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_ref())
+        .map(|value| value.raw.clone())
+}
+
+/// Lists matching entries newest first as a table.
+fn audit_table(
+    state: &AuditState,
+    filter: &AuditFilter,
+    context: &CommandExecutionContext,
+) -> CommandResult {
+    let int = |name: &str, value: i64| {
+        StructuredFieldBuilder::new(name, StructuredValue::Scalar(StructuredScalar::Int(value)))
+            .build()
+    };
+    let rows = state.entries.with_untracked(|entries| {
+        entries
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+            .take(filter.limit.unwrap_or(DEFAULT_AUDIT_ROWS))
+            .map(|entry| StructuredRecord {
+                fields: vec![
+                    int("seq", entry.seq as i64),
+                    int("started_at_unix_ms", entry.started_at_unix_ms as i64),
+                    field_string("command", entry.command.display()),
+                    field_string("argv", entry.argv.join(" ")),
+                    field_string(
+                        "app",
+                        entry
+                            .caller
+                            .as_ref()
+                            .map_or_else(|| "runtime".to_string(), |caller| caller.app_id.clone()),
+                    ),
+                    field_string(
+                        "window",
+                        entry
+                            .source_window_id
+                            .map_or_else(|| "-".to_string(), |window| window.to_string()),
+                    ),
+                    int("exit", i64::from(entry.exit_code)),
+                    field_string(
+                        "error",
+                        entry.error.map_or("-", |code| code.as_str()).to_string(),
+                    ),
+                    int("duration_ms", entry.duration_ms as i64),
+                ],
+            })
+            .collect()
+    });
+    CommandResult {
+        display: DisplayPreference::Table,
+        ..CommandResult::success(StructuredData::Table(StructuredTable {
+            columns: [
+                "seq",
+                "started_at_unix_ms",
+                "command",
+                "argv",
+                "app",
+                "window",
+                "exit",
+                "error",
+                "duration_ms",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            rows,
+            schema: None,
+            source_command: Some(context.descriptor.path.clone()),
+            fallback_text: None,
+        }))
+    }
+}
+
+fn register_audit_commands(
+    engine: &ShellEngine,
+    state: Weak<AuditState>,
+) -> Vec<CommandRegistryHandle> {
+    let option = |name: &str, value_kind: CommandValueKind, summary: &str| CommandOptionSpec {
+        name: name.to_string(),
+        short: None,
+        summary: summary.to_string(),
+        takes_value: true,
+        value_kind,
+        choices: Vec::new(),
+    };
+    let limit = option(
+        "limit",
+        CommandValueKind::Int,
+        "Maximum number of rows, newest first (default 50).",
+    );
+    let mut list = intrinsic_descriptor(
+        "audit list",
+        "audit list [--limit <n>]",
+        "List recent command executions, newest first.",
+        Vec::new(),
+    );
+    list.options = vec![limit.clone()];
+    let mut query = intrinsic_descriptor(
+        "audit query",
+        "audit query [--command <prefix>] [--app <id>] [--window <id>] [--failed] \
+         [--since <unix-ms>] [--limit <n>]",
+        "Filter recorded command executions.",
+        Vec::new(),
+    );
+    query.options = vec![
+        option(
+            "command",
+            CommandValueKind::String,
+            "Command path prefix, such as `fs` or `theme set`.",
+        ),
+        option(
+            "app",
+            CommandValueKind::String,
+            "Caller app id, or `runtime` for trusted sessions.",
+        ),
+        option("window", CommandValueKind::Int, "Source window id."),
+        CommandOptionSpec {
+            name: "failed".to_string(),
+            short: None,
+            summary: "Only executions with a non-zero exit code.".to_string(),
+            takes_value: false,
+            value_kind: CommandValueKind::Any,
+            choices: Vec::new(),
+        },
+        option(
+            "since",
+            CommandValueKind::Int,
+            "Only executions started at or after this unix time in milliseconds.",
+        ),
+        limit,
+    ];
+
+    let list_state = state.clone();
+    [
+        engine.register_command(
+            list,
+            None,
+            Rc::new(move |context| {
+                let state = list_state.clone();
+                Box::pin(async move {
+                    let state = upgrade(&state)?;
+                    let filter = AuditFilter {
+                        limit: parse_limit(&context)?,
+                        ..AuditFilter::default()
+                    };
+                    Ok(audit_table(&state, &filter, &context))
+                })
+            }),
+        ),
+        engine.register_command(
+            query,
+            None,
+            Rc::new(move |context| {
+                let state = state.clone();
+                Box::pin(async move {
+                    let state = upgrade(&state)?;
+                    let filter = AuditFilter {
+                        command: option_value(&context, "command"),
+                        app: option_value(&context, "app"),
+                        window: option_value(&context, "window")
+                            .and_then(|window| window.parse().ok()),
+                        failed: context
+                            .invocation
+                            .options
+                            .iter()
+                            .any(|option| option.name == "failed"),
+                        since_unix_ms: option_value(&context, "since")
+                            .and_then(|since| since.parse().ok()),
+                        limit: parse_limit(&context)?,
+                    };
+                    Ok(audit_table(&state, &filter, &context))
+                })
+            }),
+        ),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .collect()
+}
+
+fn parse_limit(context: &CommandExecutionContext) -> Result<Option<usize>, ShellError> {
+    option_value(context, "limit")
+        .map(|raw| {
+            raw.parse::<usize>().map_err(|_| {
+                ShellError::new(
+                    ShellErrorCode::Usage,
+                    format!("`--limit` must be a non-negative integer, got `{raw}`"),
+                )
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use system_shell_contract::{CommandScope, ShellRequest, ShellStreamEvent};
+
+    use super::*;
+
+    #[test]
+    fn dispatches_are_recorded_capped_and_queryable() {
+        crate::reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let audit = ShellAuditLog::new(&engine, 3);
+        let session = engine.new_session_for(
+            "/",
+            CommandCaller {
+                app_id: "system.terminal".to_string(),
+                window_id: Some(4),
+            },
+        );
+        let submit = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: Some(4),
+            });
+        };
+
+        submit("fn list");
+        submit("page next 99");
+        let entries = audit.entries().get_untracked();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command.display(), "fn list");
+        assert_eq!(entries[0].exit_code, 0);
+        assert_eq!(entries[1].argv, vec!["page", "next", "99"]);
+        assert_eq!(entries[1].error, Some(ShellErrorCode::NotFound));
+        assert_eq!(entries[1].source_window_id, Some(4));
+        assert_eq!(
+            entries[1]
+                .caller
+                .as_ref()
+                .map(|caller| caller.app_id.as_str()),
+            Some("system.terminal")
+        );
+
+        submit("audit query --failed --app system.terminal");
+        let rows = match session
+            .events()
+            .get_untracked()
+            .into_iter()
+            .rev()
+            .find_map(|event| match event {
+                ShellStreamEvent::Data { data, .. } => Some(data),
+                _ => None,
+            }) {
+            Some(StructuredData::Table(table)) => table.rows,
+            other => panic!("expected audit table, got {other:?}"),
+        };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            rows[0].fields[2],
+            field_string("command", "page next".to_string())
+        );
+
+        submit("audit list --limit 1");
+        let entries = audit.entries().get_untracked();
+        assert_eq!(
+            entries.iter().map(|entry| entry.seq).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(entries[2].command.display(), "audit list");
+
+        assert!(engine
+            .descriptors()
+            .iter()
+            .any(|descriptor| descriptor.path.display() == "audit query"
+                && descriptor.scope == CommandScope::Global));
+        drop(audit);
+        assert!(!engine
+            .descriptors()
+            .iter()
+            .any(|descriptor| descriptor.path.display() == "audit list"));
+    }
+}
@@ -0,0 +1,261 @@
+//! Micro-benchmarks for engine hot paths, runnable natively and in the browser.
+//!
+//! Timing reads [`unix_time_ms_now`] instead of `std::time::Instant`, which panics on
+//! `wasm32-unknown-unknown`. Each benchmark doubles its iteration count until one batch runs for
+//! at least [`BenchConfig::min_batch_ms`], keeping millisecond clock resolution negligible.
+
+use std::{hint::black_box, rc::Rc};
+
+use platform_host::unix_time_ms_now;
+use system_shell_contract::{
+    CommandNotice, CommandNoticeLevel, CommandOptionSpec, CommandResult, CommandValueKind,
+    DisplayPreference, ExecutionId, ShellError, ShellErrorCode, StructuredData, StructuredRecord,
+    StructuredScalar, StructuredTable, StructuredValue,
+};
+
+use crate::{
+    event_log::EventLog, field_string, intrinsic_descriptor, parse_command_line,
+    CommandExecutionContext, CommandRegistry, EventEmitter, OutputStream, StructuredFieldBuilder,
+};
+
+/// Upper bound on iterations per batch so a stalled clock cannot loop forever.
+const MAX_ITERATIONS: u64 = 1 << 24;
+
+/// Events retained by the emission benchmarks' log, keeping memory flat across batches.
+const MAX_RETAINED_EVENTS: usize = 1024;
+
+/// Representative pipeline tokenized by the `tokenize` benchmark.
+const SAMPLE_LINE: &str = r#"apps list | data where name == "Terminal Window" | data sort --order desc title | data first 5"#;
+
+/// Sizing for one benchmark run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Synthetic commands registered for the snapshot and resolution benchmarks.
+    pub registry_size: usize,
+    /// Minimum wall-clock milliseconds one measured batch must take.
+    pub min_batch_ms: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            registry_size: 1_000,
+            min_batch_ms: 50,
+        }
+    }
+}
+
+/// Timing of one benchmark's final batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchMeasurement {
+    /// Stable benchmark name.
+    pub name: &'static str,
+    /// Iterations in the measured batch.
+    pub iterations: u64,
+    /// Wall-clock milliseconds the batch took.
+    pub elapsed_ms: u64,
+}
+
+impl BenchMeasurement {
+    /// Average nanoseconds per iteration.
+    pub fn nanos_per_iteration(&self) -> f64 {
+        self.elapsed_ms as f64 * 1_000_000.0 / self.iterations.max(1) as f64
+    }
+}
+
+/// Runs every engine benchmark and returns one measurement each, in a stable order.
+///
+/// Benchmarks run synchronously, so in the browser the page is unresponsive for roughly four
+/// times `min_batch_ms` plus the doubling rounds before it.
+pub fn run_benchmarks(config: BenchConfig) -> Vec<BenchMeasurement> {
+    let registry = CommandRegistry::default();
+    for index in 0..config.registry_size {
+        let path = format!("bench ns{} cmd{}", index / 16, index % 16);
+        let _ = registry.register(
+            intrinsic_descriptor(&path, &path, "Synthetic benchmark command.", Vec::new()),
+            None,
+            Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })),
+        );
+    }
+    let last = config.registry_size.saturating_sub(1);
+    let target = format!("bench ns{} cmd{} --flag value", last / 16, last % 16)
+        .split(' ')
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let snapshot = registry.snapshot();
+
+    let emitter = EventEmitter::new(EventLog::new(MAX_RETAINED_EVENTS));
+    let notice = CommandNotice {
+        level: CommandNoticeLevel::Info,
+        message: "benchmark notice".to_string(),
+    };
+
+    vec![
+        measure("tokenize", config.min_batch_ms, || {
+            let _ = black_box(parse_command_line(black_box(SAMPLE_LINE)));
+        }),
+        measure("registry snapshot", config.min_batch_ms, || {
+            registry.state.borrow_mut().snapshot = None;
+            black_box(registry.snapshot());
+        }),
+        measure("resolve stage", config.min_batch_ms, || {
+            let _ = black_box(snapshot.resolve_stage(black_box(&target)));
+        }),
+        measure("emit stdout", config.min_batch_ms, || {
+            emitter.write(
+                ExecutionId(1),
+                OutputStream::Stdout,
+                "benchmark output line\n",
+            );
+        }),
+        measure("emit notice", config.min_batch_ms, || {
+            emitter.notice(ExecutionId(1), notice.clone());
+        }),
+    ]
+}
+
+fn measure(name: &'static str, min_batch_ms: u64, mut body: impl FnMut()) -> BenchMeasurement {
+    let mut iterations = 1;
+    loop {
+        let started = unix_time_ms_now();
+        for _ in 0..iterations {
+            body();
+        }
+        let elapsed_ms = unix_time_ms_now().saturating_sub(started);
+        if elapsed_ms >= min_batch_ms || iterations >= MAX_ITERATIONS {
+            return BenchMeasurement {
+                name,
+                iterations,
+                elapsed_ms,
+            };
+        }
+        iterations *= 2;
+    }
+}
+
+/// Installs the `shell bench` intrinsic that runs [`run_benchmarks`] and returns a table.
+pub(crate) fn install(registry: &CommandRegistry) {
+    let option = |name: &str, summary: &str| CommandOptionSpec {
+        name: name.to_string(),
+        short: None,
+        summary: summary.to_string(),
+        takes_value: true,
+        value_kind: CommandValueKind::Int,
+        choices: Vec::new(),
+    };
+    let mut descriptor = intrinsic_descriptor(
+        "shell bench",
+        "shell bench [--commands <n>] [--min-ms <ms>]",
+        "Measure tokenization, stage resolution, and event emission in this host.",
+        Vec::new(),
+    );
+    descriptor.options = vec![
+        option(
+            "commands",
+            "Synthetic commands to register for the registry benchmarks (default 1000).",
+        ),
+        option(
+            "min-ms",
+            "Minimum milliseconds each measured batch runs (default 50).",
+        ),
+    ];
+    registry.install_intrinsic(
+        descriptor,
+        Rc::new(|context| {
+            Box::pin(async move {
+                let defaults = BenchConfig::default();
+                let config = BenchConfig {
+                    registry_size: count_option(&context, "commands")?
+                        .unwrap_or(defaults.registry_size as u64)
+                        as usize,
+                    min_batch_ms: count_option(&context, "min-ms")?
+                        .unwrap_or(defaults.min_batch_ms),
+                };
+                let int = |value: u64| StructuredValue::Scalar(StructuredScalar::Int(value as i64));
+                let rows = run_benchmarks(config)
+                    .into_iter()
+                    .map(|measurement| StructuredRecord {
+                        fields: vec![
+                            field_string("benchmark", measurement.name.to_string()),
+                            StructuredFieldBuilder::new("iterations", int(measurement.iterations))
+                                .build(),
+                            StructuredFieldBuilder::new("elapsed_ms", int(measurement.elapsed_ms))
+                                .build(),
+                            StructuredFieldBuilder::new(
+                                "ns_per_iteration",
+                                StructuredValue::Scalar(StructuredScalar::Float(
+                                    measurement.nanos_per_iteration().round(),
+                                )),
+                            )
+                            .build(),
+                        ],
+                    })
+                    .collect();
+                Ok(CommandResult {
+                    display: DisplayPreference::Table,
+                    ..CommandResult::success(StructuredData::Table(StructuredTable {
+                        columns: ["benchmark", "iterations", "elapsed_ms", "ns_per_iteration"]
+                            .map(str::to_string)
+                            .to_vec(),
+                        rows,
+                        schema: None,
+                        source_command: Some(context.descriptor.path.clone()),
+                        fallback_text: None,
+                    }))
+                })
+            })
+        }),
+    );
+}
+
+fn count_option(context: &CommandExecutionContext, name: &str) -> Result<Option<u64>, ShellError> {
+    context
+        .invocation
+        .options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_ref())
+        .map(|value| {
+            value.raw.parse::<u64>().map_err(|_| {
+                ShellError::new(
+                    ShellErrorCode::Usage,
+                    format!(
+                        "`--{name}` must be a non-negative integer, got `{}`",
+                        value.raw
+                    ),
+                )
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_benchmark_reports_a_measurement() {
+        crate::reactive::test_runtime();
+        let measurements = run_benchmarks(BenchConfig {
+            registry_size: 40,
+            min_batch_ms: 1,
+        });
+        let names = measurements
+            .iter()
+            .map(|measurement| measurement.name)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "tokenize",
+                "registry snapshot",
+                "resolve stage",
+                "emit stdout",
+                "emit notice"
+            ]
+        );
+        assert!(measurements
+            .iter()
+            .all(|measurement| measurement.iterations > 0));
+    }
+}
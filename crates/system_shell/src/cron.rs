@@ -0,0 +1,768 @@
+//! Scheduled command lines that run on an interval or once at a wall-clock time.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    rc::{Rc, Weak},
+};
+
+use platform_host::{load_pref_with, save_pref_with, unix_time_ms_now, PrefsStore};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandCaller, CommandNotice, CommandNoticeLevel,
+    CommandOptionSpec, CommandResult, CommandValueKind, DisplayPreference, ScheduleTrigger,
+    ScheduledJob, ScheduledRun, ShellError, ShellErrorCode, ShellExecutionSummary, ShellRequest,
+    ShellStreamEvent, StructuredData, StructuredRecord, StructuredScalar, StructuredTable,
+    StructuredValue,
+};
+
+use crate::{
+    event_log::EventLog,
+    field_string, intrinsic_descriptor, parse_command_line,
+    reactive::{
+        create_rw_signal, ReadSignal, RwSignal, SignalGetUntracked, SignalUpdate,
+        SignalWithUntracked,
+    },
+    CommandRegistryHandle, HistoryStore, ShellEngine, ShellSessionHandle, StructuredFieldBuilder,
+};
+
+/// Shortest accepted interval for repeating jobs.
+pub const MIN_SCHEDULE_INTERVAL_MS: u64 = 1_000;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1_000;
+
+#[derive(Clone)]
+struct SchedulePersistence {
+    store: Rc<dyn PrefsStore>,
+    key: String,
+}
+
+struct CronState {
+    engine: ShellEngine,
+    jobs: RwSignal<Vec<ScheduledJob>>,
+    events: EventLog,
+    next_execution_id: Rc<Cell<u64>>,
+    sessions: RefCell<BTreeMap<u64, ShellSessionHandle>>,
+    launched: RefCell<BTreeMap<u64, (u64, String)>>,
+    hydrated: Cell<bool>,
+    persistence: RefCell<Option<SchedulePersistence>>,
+}
+
+/// Runs registered command lines at intervals or at wall-clock times.
+///
+/// The scheduler owns no timer; the host calls [`ShellScheduler::tick`] periodically with the
+/// current time. Each job runs in its own session bound to the caller that registered it, so runs
+/// are authorized like interactive commands and a job never overlaps its own previous run. Every
+/// job session writes into one shared event log exposed by [`ShellScheduler::events`], and
+/// [`ShellScheduler::completed_run`] maps a completion in that log back to its job.
+///
+/// Creating a scheduler registers the `schedule add`, `schedule list`, and `schedule remove`
+/// commands on the engine until the last clone is dropped. Only the first scheduler on an engine
+/// owns those commands; later ones are refused as registry conflicts and stay API-only.
+#[derive(Clone)]
+pub struct ShellScheduler {
+    state: Rc<CronState>,
+    _commands: Rc<Vec<CommandRegistryHandle>>,
+}
+
+impl ShellScheduler {
+    /// Creates an in-memory scheduler and registers the `schedule` commands on `engine`.
+    pub fn new(engine: &ShellEngine) -> Self {
+        let state = Rc::new(CronState {
+            engine: engine.clone(),
+            jobs: create_rw_signal(Vec::new()),
+            events: EventLog::new(engine.event_retention),
+            next_execution_id: Rc::new(Cell::new(0)),
+            sessions: RefCell::new(BTreeMap::new()),
+            launched: RefCell::new(BTreeMap::new()),
+            hydrated: Cell::new(false),
+            persistence: RefCell::new(None),
+        });
+        let commands = register_schedule_commands(engine, Rc::downgrade(&state));
+        Self {
+            state,
+            _commands: Rc::new(commands),
+        }
+    }
+
+    /// Persists jobs as a JSON list under `key` in `store`.
+    ///
+    /// Writes start once [`ShellScheduler::hydrate`] has completed so jobs added during boot never
+    /// overwrite the persisted list.
+    pub fn with_prefs(self, store: Rc<dyn PrefsStore>, key: impl Into<String>) -> Self {
+        *self.state.persistence.borrow_mut() = Some(SchedulePersistence {
+            store,
+            key: key.into(),
+        });
+        self
+    }
+
+    /// Loads persisted jobs, keeping any jobs added before hydration finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the attached store fails or holds malformed JSON.
+    pub async fn hydrate(&self) -> Result<(), String> {
+        let Some(persistence) = self.state.persistence.borrow().clone() else {
+            return Ok(());
+        };
+        let loaded: Vec<ScheduledJob> =
+            load_pref_with(persistence.store.as_ref(), &persistence.key)
+                .await?
+                .unwrap_or_default();
+        self.state.jobs.update(|jobs| {
+            let recent = std::mem::replace(jobs, loaded);
+            for mut job in recent {
+                job.id = next_job_id(jobs);
+                jobs.push(job);
+            }
+        });
+        self.state.hydrated.set(true);
+        self.persist();
+        Ok(())
+    }
+
+    /// Reactive registered jobs in id order.
+    pub fn jobs(&self) -> ReadSignal<Vec<ScheduledJob>> {
+        self.state.jobs.read_only()
+    }
+
+    /// Reactive event log shared by every job run, bounded like a session log.
+    pub fn events(&self) -> ReadSignal<Vec<ShellStreamEvent>> {
+        self.state.events.events()
+    }
+
+    /// Removes the first `count` events from the shared log once they have been processed.
+    pub fn acknowledge_events(&self, count: usize) {
+        self.state.events.acknowledge(count);
+    }
+
+    /// Registers `line` to run from `/` with no caller identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns a usage error when `line` does not parse, is empty, or the interval is shorter
+    /// than [`MIN_SCHEDULE_INTERVAL_MS`].
+    pub fn add(
+        &self,
+        line: impl Into<String>,
+        trigger: ScheduleTrigger,
+        now_unix_ms: u64,
+    ) -> Result<ScheduledJob, ShellError> {
+        add_job(
+            &self.state,
+            line.into(),
+            "/".to_string(),
+            None,
+            trigger,
+            now_unix_ms,
+        )
+    }
+
+    /// Removes a job, returning it when it existed.
+    pub fn remove(&self, id: u64) -> Option<ScheduledJob> {
+        remove_job(&self.state, id)
+    }
+
+    /// Submits every due job whose previous run has finished and returns how many started.
+    ///
+    /// Repeating jobs are rescheduled one interval after `now_unix_ms`, so missed runs are not
+    /// replayed; one-shot jobs stay listed without a next run until removed.
+    pub fn tick(&self, now_unix_ms: u64) -> usize {
+        let state = &self.state;
+        let due = state.jobs.with_untracked(|jobs| {
+            jobs.iter()
+                .filter(|job| job.next_run_unix_ms.is_some_and(|next| next <= now_unix_ms))
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        let mut started = Vec::new();
+        for job in due {
+            let session = job_session(state, &job);
+            if session.active_execution().get_untracked().is_some() {
+                continue;
+            }
+            let before = state.next_execution_id.get();
+            session.submit(ShellRequest {
+                line: job.line.clone(),
+                cwd: job.cwd.clone(),
+                source_window_id: job.caller.as_ref().and_then(|caller| caller.window_id),
+            });
+            let after = state.next_execution_id.get();
+            if after != before {
+                state
+                    .launched
+                    .borrow_mut()
+                    .insert(after, (job.id, job.line.clone()));
+            }
+            started.push(job.id);
+        }
+        if started.is_empty() {
+            return 0;
+        }
+        state.jobs.update(|jobs| {
+            for job in jobs.iter_mut().filter(|job| started.contains(&job.id)) {
+                job.last_run_unix_ms = Some(now_unix_ms);
+                job.next_run_unix_ms = match job.trigger {
+                    ScheduleTrigger::Every { interval_ms } => {
+                        Some(now_unix_ms.saturating_add(interval_ms))
+                    }
+                    ScheduleTrigger::At { .. } => None,
+                };
+            }
+        });
+        let live = state.jobs.with_untracked(|jobs| {
+            jobs.iter()
+                .filter(|job| job.next_run_unix_ms.is_some())
+                .map(|job| job.id)
+                .collect::<Vec<_>>()
+        });
+        state
+            .sessions
+            .borrow_mut()
+            .retain(|id, _| live.contains(id));
+        self.persist();
+        started.len()
+    }
+
+    /// Returns the job run reported by a `Completed` summary from [`ShellScheduler::events`].
+    ///
+    /// Each run is reported once; later calls for the same execution return `None`.
+    pub fn completed_run(&self, summary: &ShellExecutionSummary) -> Option<ScheduledRun> {
+        let (job_id, line) = self
+            .state
+            .launched
+            .borrow_mut()
+            .remove(&summary.execution_id.0)?;
+        Some(ScheduledRun {
+            job_id,
+            line,
+            summary: summary.clone(),
+        })
+    }
+
+    fn persist(&self) {
+        persist(&self.state);
+    }
+}
+
+fn next_job_id(jobs: &[ScheduledJob]) -> u64 {
+    jobs.iter().map(|job| job.id).max().unwrap_or(0) + 1
+}
+
+fn add_job(
+    state: &CronState,
+    line: String,
+    cwd: String,
+    caller: Option<CommandCaller>,
+    trigger: ScheduleTrigger,
+    now_unix_ms: u64,
+) -> Result<ScheduledJob, ShellError> {
+    let line = line.trim().to_string();
+    if parse_command_line(&line)?.pipeline.is_empty() {
+        return Err(ShellError::new(
+            ShellErrorCode::Usage,
+            "scheduled command line is empty",
+        ));
+    }
+    let next_run_unix_ms = match trigger {
+        ScheduleTrigger::Every { interval_ms } if interval_ms < MIN_SCHEDULE_INTERVAL_MS => {
+            return Err(ShellError::new(
+                ShellErrorCode::Usage,
+                format!(
+                    "schedule interval must be at least {}",
+                    format_duration(MIN_SCHEDULE_INTERVAL_MS)
+                ),
+            ));
+        }
+        ScheduleTrigger::Every { interval_ms } => now_unix_ms.saturating_add(interval_ms),
+        ScheduleTrigger::At { unix_ms } => unix_ms,
+    };
+    let mut added = None;
+    state.jobs.update(|jobs| {
+        let job = ScheduledJob {
+            id: next_job_id(jobs),
+            line,
+            cwd,
+            caller,
+            trigger,
+            next_run_unix_ms: Some(next_run_unix_ms),
+            last_run_unix_ms: None,
+        };
+        jobs.push(job.clone());
+        added = Some(job);
+    });
+    persist(state);
+    Ok(added.expect("scheduled job added"))
+}
+
+fn remove_job(state: &CronState, id: u64) -> Option<ScheduledJob> {
+    let mut removed = None;
+    state.jobs.update(|jobs| {
+        if let Some(position) = jobs.iter().position(|job| job.id == id) {
+            removed = Some(jobs.remove(position));
+        }
+    });
+    if removed.is_some() {
+        state.sessions.borrow_mut().remove(&id);
+        persist(state);
+    }
+    removed
+}
+
+/// Returns the session for `job`, creating it in the shared event log on first use.
+fn job_session(state: &CronState, job: &ScheduledJob) -> ShellSessionHandle {
+    state
+        .sessions
+        .borrow_mut()
+        .entry(job.id)
+        .or_insert_with(|| {
+            let history = HistoryStore::new(1);
+            history.set_enabled(false);
+            state.engine.session_with(
+                job.cwd.clone(),
+                job.caller.clone(),
+                state.events.clone(),
+                state.next_execution_id.clone(),
+                history,
+            )
+        })
+        .clone()
+}
+
+fn persist(state: &CronState) {
+    let Some(persistence) = state.persistence.borrow().clone() else {
+        return;
+    };
+    if !state.hydrated.get() {
+        return;
+    }
+    let jobs = state.jobs.get_untracked();
+    crate::reactive::spawn_local(async move {
+        if let Err(err) = save_pref_with(persistence.store.as_ref(), &persistence.key, &jobs).await
+        {
+            crate::reactive::warn(&format!("persist shell schedule failed: {err}"));
+        }
+    });
+}
+
+/// Parses a duration such as `90s`, `5m`, or `1h30m` into milliseconds.
+fn parse_duration_ms(raw: &str) -> Result<u64, ShellError> {
+    let invalid = || {
+        ShellError::new(
+            ShellErrorCode::Usage,
+            format!("invalid duration `{raw}`; use values like `30s`, `5m`, or `1h30m`"),
+        )
+    };
+    let mut total = 0u64;
+    let mut rest = raw.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount = rest[..digits].parse::<u64>().map_err(|_| invalid())?;
+        rest = &rest[digits..];
+        let unit = rest
+            .find(|ch: char| ch.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit] {
+            "ms" => 1,
+            "s" => 1_000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            "d" => DAY_MS,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit..];
+        total = total.saturating_add(amount.saturating_mul(scale));
+    }
+    Ok(total)
+}
+
+/// Parses `HH:MM` (UTC, next occurrence at or after `now_unix_ms`) or a unix millisecond time.
+fn parse_run_time(raw: &str, now_unix_ms: u64) -> Result<u64, ShellError> {
+    if let Ok(unix_ms) = raw.parse::<u64>() {
+        return Ok(unix_ms);
+    }
+    let clock = raw.split_once(':').and_then(|(hours, minutes)| {
+        let hours = hours.parse::<u64>().ok().filter(|hours| *hours < 24)?;
+        let minutes = minutes
+            .parse::<u64>()
+            .ok()
+            .filter(|minutes| *minutes < 60)?;
+        Some((hours * 60 + minutes) * 60_000)
+    });
+    let Some(offset) = clock else {
+        return Err(ShellError::new(
+            ShellErrorCode::Usage,
+            format!("invalid time `{raw}`; use `HH:MM` (UTC) or a unix timestamp in milliseconds"),
+        ));
+    };
+    let today = now_unix_ms - now_unix_ms % DAY_MS + offset;
+    Ok(if today >= now_unix_ms {
+        today
+    } else {
+        today + DAY_MS
+    })
+}
+
+/// Formats milliseconds as a compact duration such as `1h 5m` or `42s`.
+fn format_duration(ms: u64) -> String {
+    let seconds = ms / 1_000;
+    let parts = [
+        (seconds / 86_400, "d"),
+        (seconds / 3_600 % 24, "h"),
+        (seconds / 60 % 60, "m"),
+        (seconds % 60, "s"),
+    ];
+    let text = parts
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .take(2)
+        .map(|(amount, unit)| format!("{amount}{unit}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        "0s".to_string()
+    } else {
+        text
+    }
+}
+
+fn describe_trigger(trigger: ScheduleTrigger) -> String {
+    match trigger {
+        ScheduleTrigger::Every { interval_ms } => format!("every {}", format_duration(interval_ms)),
+        ScheduleTrigger::At { unix_ms } => format!("at {unix_ms}"),
+    }
+}
+
+fn upgrade(state: &Weak<CronState>) -> Result<Rc<CronState>, ShellError> {
+    state.upgrade().ok_or_else(|| {
+        ShellError::new(
+            ShellErrorCode::Unavailable,
+            "shell scheduler is not running",
+        )
+    })
+}
+
+fn register_schedule_commands(
+    engine: &ShellEngine,
+    state: Weak<CronState>,
+) -> Vec<CommandRegistryHandle> {
+    let value_option = |name: &str, summary: &str| CommandOptionSpec {
+        name: name.to_string(),
+        short: None,
+        summary: summary.to_string(),
+        takes_value: true,
+        value_kind: CommandValueKind::Any,
+        choices: Vec::new(),
+    };
+    let mut add = intrinsic_descriptor(
+        "schedule add",
+        "schedule add (--every <duration> | --at <HH:MM|unix-ms>) <command...>",
+        "Run a command line on an interval or once at a time.",
+        vec![CommandArgSpec {
+            name: "command".to_string(),
+            summary: "Command line to run; quote it to keep options with the command.".to_string(),
+            required: true,
+            repeatable: true,
+            completion: CommandArgCompletion::None,
+        }],
+    );
+    add.options = vec![
+        value_option("every", "Repeat interval such as `30s`, `5m`, or `1h30m`."),
+        value_option(
+            "at",
+            "Run once at `HH:MM` UTC or a unix time in milliseconds.",
+        ),
+    ];
+    let remove = intrinsic_descriptor(
+        "schedule remove",
+        "schedule remove <id>",
+        "Remove a scheduled command.",
+        vec![CommandArgSpec {
+            name: "id".to_string(),
+            summary: "Job id from `schedule list`.".to_string(),
+            required: true,
+            repeatable: false,
+            completion: CommandArgCompletion::None,
+        }],
+    );
+    let list = intrinsic_descriptor(
+        "schedule list",
+        "schedule list",
+        "List scheduled commands.",
+        Vec::new(),
+    );
+
+    let add_state = state.clone();
+    let remove_state = state.clone();
+    [
+        engine.register_command(
+            add,
+            None,
+            Rc::new(move |context| {
+                let state = add_state.clone();
+                Box::pin(async move {
+                    let state = upgrade(&state)?;
+                    let option = |name: &str| {
+                        context
+                            .invocation
+                            .options
+                            .iter()
+                            .find(|option| option.name == name)
+                            .and_then(|option| option.value.as_ref())
+                            .map(|value| value.raw.clone())
+                    };
+                    let now = unix_time_ms_now();
+                    let trigger = match (option("every"), option("at")) {
+                        (Some(every), None) => ScheduleTrigger::Every {
+                            interval_ms: parse_duration_ms(&every)?,
+                        },
+                        (None, Some(at)) => ScheduleTrigger::At {
+                            unix_ms: parse_run_time(&at, now)?,
+                        },
+                        _ => {
+                            return Err(ShellError::new(
+                                ShellErrorCode::Usage,
+                                "`schedule add` needs exactly one of `--every` or `--at`",
+                            ))
+                        }
+                    };
+                    let line = context.arg_values::<String>("command")?.join(" ");
+                    let job = add_job(
+                        &state,
+                        line,
+                        context.cwd.clone(),
+                        context.run.state.caller.clone(),
+                        trigger,
+                        now,
+                    )?;
+                    let next = job.next_run_unix_ms.unwrap_or(now);
+                    let mut result = CommandResult::success(StructuredData::Empty);
+                    result.notices.push(CommandNotice {
+                        level: CommandNoticeLevel::Info,
+                        message: format!(
+                            "scheduled job #{} ({}); next run in {}",
+                            job.id,
+                            describe_trigger(job.trigger),
+                            format_duration(next.saturating_sub(now))
+                        ),
+                    });
+                    Ok(result)
+                })
+            }),
+        ),
+        engine.register_command(
+            list,
+            None,
+            Rc::new(move |context| {
+                let state = state.clone();
+                Box::pin(async move {
+                    let state = upgrade(&state)?;
+                    let now = unix_time_ms_now();
+                    let rows = state
+                        .jobs
+                        .get_untracked()
+                        .into_iter()
+                        .map(|job| StructuredRecord {
+                            fields: vec![
+                                StructuredFieldBuilder::new(
+                                    "id",
+                                    StructuredValue::Scalar(StructuredScalar::Int(job.id as i64)),
+                                )
+                                .build(),
+                                field_string("trigger", describe_trigger(job.trigger)),
+                                field_string(
+                                    "next",
+                                    job.next_run_unix_ms.map_or_else(
+                                        || "done".to_string(),
+                                        |next| {
+                                            format!(
+                                                "in {}",
+                                                format_duration(next.saturating_sub(now))
+                                            )
+                                        },
+                                    ),
+                                ),
+                                field_string(
+                                    "last",
+                                    job.last_run_unix_ms.map_or_else(
+                                        || "never".to_string(),
+                                        |last| {
+                                            format!(
+                                                "{} ago",
+                                                format_duration(now.saturating_sub(last))
+                                            )
+                                        },
+                                    ),
+                                ),
+                                field_string(
+                                    "owner",
+                                    job.caller.map_or_else(
+                                        || "runtime".to_string(),
+                                        |caller| caller.app_id,
+                                    ),
+                                ),
+                                field_string("command", job.line),
+                            ],
+                        })
+                        .collect();
+                    Ok(CommandResult {
+                        display: DisplayPreference::Table,
+                        ..CommandResult::success(StructuredData::Table(StructuredTable {
+                            columns: ["id", "trigger", "next", "last", "owner", "command"]
+                                .map(str::to_string)
+                                .to_vec(),
+                            rows,
+                            schema: None,
+                            source_command: Some(context.descriptor.path.clone()),
+                            fallback_text: None,
+                        }))
+                    })
+                })
+            }),
+        ),
+        engine.register_command(
+            remove,
+            None,
+            Rc::new(move |context| {
+                let state = remove_state.clone();
+                Box::pin(async move {
+                    let state = upgrade(&state)?;
+                    let id = context.arg::<i64>("id")?.unwrap_or_default();
+                    let not_found = || {
+                        ShellError::new(
+                            ShellErrorCode::NotFound,
+                            format!("scheduled job not found: {id}"),
+                        )
+                    };
+                    let id = u64::try_from(id).map_err(|_| not_found())?;
+                    let owner = state.jobs.with_untracked(|jobs| {
+                        jobs.iter()
+                            .find(|job| job.id == id)
+                            .map(|job| job.caller.clone())
+                    });
+                    let Some(owner) = owner else {
+                        return Err(not_found());
+                    };
+                    if let (Some(caller), Some(owner)) = (&context.run.state.caller, &owner) {
+                        if caller.app_id != owner.app_id {
+                            return Err(ShellError::new(
+                                ShellErrorCode::PermissionDenied,
+                                format!(
+                                    "permission denied: job #{id} belongs to app `{}`",
+                                    owner.app_id
+                                ),
+                            ));
+                        }
+                    }
+                    remove_job(&state, id);
+                    let mut result = CommandResult::success(StructuredData::Empty);
+                    result.notices.push(CommandNotice {
+                        level: CommandNoticeLevel::Info,
+                        message: format!("removed scheduled job #{id}"),
+                    });
+                    Ok(result)
+                })
+            }),
+        ),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use system_shell_contract::ExecutionId;
+
+    use super::*;
+
+    #[test]
+    fn durations_and_run_times_parse() {
+        assert_eq!(parse_duration_ms("90s").unwrap(), 90_000);
+        assert_eq!(parse_duration_ms("1h30m").unwrap(), 5_400_000);
+        assert!(parse_duration_ms("5 minutes").is_err());
+        assert!(parse_duration_ms("").is_err());
+
+        let now = 3 * DAY_MS + 10 * 3_600_000;
+        assert_eq!(
+            parse_run_time("12:15", now).unwrap(),
+            3 * DAY_MS + (12 * 60 + 15) * 60_000
+        );
+        assert_eq!(
+            parse_run_time("09:00", now).unwrap(),
+            4 * DAY_MS + 9 * 3_600_000
+        );
+        assert_eq!(
+            parse_run_time("1700000000000", now).unwrap(),
+            1_700_000_000_000
+        );
+        assert!(parse_run_time("25:00", now).is_err());
+        assert_eq!(format_duration(3_900_000), "1h 5m");
+    }
+
+    #[test]
+    fn due_jobs_run_in_job_sessions_and_reschedule() {
+        crate::reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let scheduler = ShellScheduler::new(&engine);
+        let every = scheduler
+            .add(
+                "history list",
+                ScheduleTrigger::Every {
+                    interval_ms: 60_000,
+                },
+                0,
+            )
+            .expect("repeating job");
+        let once = scheduler
+            .add("fn list", ScheduleTrigger::At { unix_ms: 5_000 }, 0)
+            .expect("one-shot job");
+        assert!(scheduler
+            .add("", ScheduleTrigger::At { unix_ms: 0 }, 0)
+            .is_err());
+        assert!(scheduler
+            .add("fn list", ScheduleTrigger::Every { interval_ms: 10 }, 0)
+            .is_err());
+
+        assert_eq!(scheduler.tick(1_000), 0);
+        assert_eq!(scheduler.tick(5_000), 1);
+        assert_eq!(scheduler.tick(60_000), 1);
+        let jobs = scheduler.jobs().get_untracked();
+        assert_eq!(jobs[0].next_run_unix_ms, Some(120_000));
+        assert_eq!(jobs[1].next_run_unix_ms, None);
+        assert_eq!(jobs[1].last_run_unix_ms, Some(5_000));
+        assert!(engine.history().entries().get_untracked().is_empty());
+
+        let runs = scheduler
+            .events()
+            .get_untracked()
+            .into_iter()
+            .filter_map(|event| match event {
+                ShellStreamEvent::Completed { summary } => scheduler.completed_run(&summary),
+                _ => None,
+            })
+            .map(|run| (run.job_id, run.summary.execution_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            vec![(once.id, ExecutionId(1)), (every.id, ExecutionId(2))]
+        );
+
+        assert!(scheduler.remove(every.id).is_some());
+        assert_eq!(scheduler.tick(120_000), 0);
+        assert!(engine
+            .descriptors()
+            .iter()
+            .any(|descriptor| descriptor.path.display() == "schedule add"));
+        drop(scheduler);
+        assert!(!engine
+            .descriptors()
+            .iter()
+            .any(|descriptor| descriptor.path.display() == "schedule add"));
+    }
+}
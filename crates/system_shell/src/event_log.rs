@@ -0,0 +1,150 @@
+//! Bounded session event log with a truncation marker and consumer acknowledgement.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use system_shell_contract::ShellStreamEvent;
+
+use crate::reactive::{create_rw_signal, ReadSignal, RwSignal, SignalUpdate};
+
+/// Callback invoked with each event as it is appended.
+pub(crate) type EventListener = Rc<dyn Fn(&ShellStreamEvent)>;
+
+/// Default number of events a session log retains before dropping the oldest.
+pub const DEFAULT_EVENT_RETENTION: usize = 2_000;
+
+/// Reactive event log shared by the sessions writing into it.
+///
+/// Once more than `retention` events are buffered, the oldest are dropped and counted by a
+/// leading [`ShellStreamEvent::Truncated`] marker. Consumers that process events as they arrive
+/// acknowledge them with [`EventLog::acknowledge`] so the log only holds unread events.
+#[derive(Clone)]
+pub(crate) struct EventLog {
+    events: RwSignal<Vec<ShellStreamEvent>>,
+    retention: Rc<Cell<usize>>,
+    listeners: Rc<RefCell<Vec<EventListener>>>,
+}
+
+impl EventLog {
+    pub(crate) fn new(retention: usize) -> Self {
+        Self {
+            events: create_rw_signal(Vec::new()),
+            retention: Rc::new(Cell::new(retention.max(1))),
+            listeners: Rc::default(),
+        }
+    }
+
+    pub(crate) fn events(&self) -> ReadSignal<Vec<ShellStreamEvent>> {
+        self.events.read_only()
+    }
+
+    /// Calls `listener` with every event appended from now on.
+    pub(crate) fn subscribe(&self, listener: EventListener) {
+        self.listeners.borrow_mut().push(listener);
+    }
+
+    /// Appends `events` in order, notifies listeners, then enforces the retention limit.
+    pub(crate) fn extend(&self, events: impl IntoIterator<Item = ShellStreamEvent>) {
+        let events = events.into_iter().collect::<Vec<_>>();
+        let listeners = self.listeners.borrow().clone();
+        for event in &events {
+            for listener in &listeners {
+                listener(event);
+            }
+        }
+        let retention = self.retention.get();
+        self.events.update(|log| {
+            log.extend(events);
+            truncate(log, retention);
+        });
+    }
+
+    pub(crate) fn push(&self, event: ShellStreamEvent) {
+        self.extend([event]);
+    }
+
+    /// Changes how many events the log retains, dropping any excess now.
+    pub(crate) fn set_retention(&self, retention: usize) {
+        let retention = retention.max(1);
+        self.retention.set(retention);
+        self.events.update(|log| truncate(log, retention));
+    }
+
+    /// Removes the first `count` events, including a leading truncation marker, once the
+    /// consumer has processed them.
+    pub(crate) fn acknowledge(&self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.events.update(|log| {
+            log.drain(..count.min(log.len()));
+        });
+    }
+}
+
+fn truncate(log: &mut Vec<ShellStreamEvent>, retention: usize) {
+    let (marker, dropped) = match log.first() {
+        Some(ShellStreamEvent::Truncated { dropped }) => (1, *dropped),
+        _ => (0, 0),
+    };
+    let excess = (log.len() - marker).saturating_sub(retention);
+    if excess == 0 {
+        return;
+    }
+    log.drain(marker..marker + excess);
+    let marker_event = ShellStreamEvent::Truncated {
+        dropped: dropped + excess as u64,
+    };
+    if marker == 1 {
+        log[0] = marker_event;
+    } else {
+        log.insert(0, marker_event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reactive::SignalGetUntracked;
+    use system_shell_contract::ExecutionId;
+
+    use super::*;
+
+    fn started(id: u64) -> ShellStreamEvent {
+        ShellStreamEvent::Started {
+            execution_id: ExecutionId(id),
+        }
+    }
+
+    #[test]
+    fn overflow_drops_oldest_behind_a_marker_until_acknowledged() {
+        crate::reactive::test_runtime();
+        let log = EventLog::new(2);
+        log.extend((1..=3).map(started));
+        assert_eq!(
+            log.events().get_untracked(),
+            vec![
+                ShellStreamEvent::Truncated { dropped: 1 },
+                started(2),
+                started(3)
+            ]
+        );
+
+        log.push(started(4));
+        assert_eq!(
+            log.events().get_untracked(),
+            vec![
+                ShellStreamEvent::Truncated { dropped: 2 },
+                started(3),
+                started(4)
+            ]
+        );
+
+        log.acknowledge(2);
+        assert_eq!(log.events().get_untracked(), vec![started(4)]);
+        log.set_retention(5);
+        log.extend((5..=6).map(started));
+        assert_eq!(log.events().get_untracked().len(), 3);
+    }
+}
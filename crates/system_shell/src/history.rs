@@ -0,0 +1,277 @@
+//! Shared command history store with capped, deduplicated entries and optional persistence.
+
+use std::{cell::Cell, rc::Rc};
+
+use platform_host::{load_pref_with, save_pref_with, PrefsStore};
+use system_shell_contract::HistoryMatch;
+
+use crate::reactive::{
+    create_rw_signal, ReadSignal, RwSignal, SignalGetUntracked, SignalUpdate, SignalWithUntracked,
+};
+
+/// Default number of retained history entries.
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+#[derive(Clone)]
+struct HistoryPersistence {
+    store: Rc<dyn PrefsStore>,
+    key: String,
+}
+
+/// Command history shared by every session of one [`crate::ShellEngine`].
+///
+/// Entries are ordered oldest first. Re-running a command moves it to the end instead of storing
+/// a duplicate, and the oldest entries are dropped once `capacity` is exceeded. When a
+/// [`PrefsStore`] is attached, every change is written back under the configured key.
+#[derive(Clone)]
+pub struct HistoryStore {
+    entries: RwSignal<Vec<String>>,
+    capacity: usize,
+    enabled: Rc<Cell<bool>>,
+    hydrated: Rc<Cell<bool>>,
+    persistence: Option<HistoryPersistence>,
+}
+
+impl Default for HistoryStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl HistoryStore {
+    /// Creates an empty in-memory history retaining at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: create_rw_signal(Vec::new()),
+            capacity: capacity.max(1),
+            enabled: Rc::new(Cell::new(true)),
+            hydrated: Rc::new(Cell::new(false)),
+            persistence: None,
+        }
+    }
+
+    /// Persists history changes as a JSON string list under `key` in `store`.
+    ///
+    /// Writes start once [`HistoryStore::hydrate`] has completed so early commands never
+    /// overwrite the persisted list.
+    pub fn with_prefs(mut self, store: Rc<dyn PrefsStore>, key: impl Into<String>) -> Self {
+        self.persistence = Some(HistoryPersistence {
+            store,
+            key: key.into(),
+        });
+        self
+    }
+
+    /// Loads persisted entries, keeping any commands recorded before hydration finished.
+    ///
+    /// When such commands were kept, the merged list is written back before this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the attached store fails or holds malformed JSON.
+    pub async fn hydrate(&self) -> Result<(), String> {
+        let Some(persistence) = self.persistence.clone() else {
+            return Ok(());
+        };
+        let loaded: Vec<String> = load_pref_with(persistence.store.as_ref(), &persistence.key)
+            .await?
+            .unwrap_or_default();
+        let capacity = self.capacity;
+        let mut recorded_early = false;
+        self.entries.update(|entries| {
+            let recent = std::mem::take(entries);
+            recorded_early = !recent.is_empty();
+            for line in loaded.into_iter().chain(recent) {
+                push_entry(entries, line, capacity);
+            }
+        });
+        self.hydrated.set(true);
+        // Awaited rather than spawned, since callers may already be inside an executor.
+        if let Some((persistence, entries)) = self.pending_write().filter(|_| recorded_early) {
+            save(persistence, entries).await;
+        }
+        Ok(())
+    }
+
+    /// Reactive history entries, oldest first.
+    pub fn entries(&self) -> ReadSignal<Vec<String>> {
+        self.entries.read_only()
+    }
+
+    /// Enables or disables recording; existing entries are kept either way.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    /// Records one submitted line, returning whether the history changed.
+    ///
+    /// Blank lines are ignored, as is everything while recording is disabled.
+    pub fn push(&self, line: &str) -> bool {
+        let line = line.trim();
+        if !self.enabled.get() || line.is_empty() {
+            return false;
+        }
+        if self
+            .entries
+            .with_untracked(|entries| entries.last().map(String::as_str) == Some(line))
+        {
+            return false;
+        }
+        let capacity = self.capacity;
+        self.entries
+            .update(|entries| push_entry(entries, line.to_string(), capacity));
+        self.persist();
+        true
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        self.entries.update(Vec::clear);
+        self.persist();
+    }
+
+    /// Returns entries containing `query`, newest first.
+    pub fn search(&self, query: &str) -> Vec<HistoryMatch> {
+        self.matching(|line| line.contains(query))
+    }
+
+    /// Returns entries starting with `prefix`, newest first.
+    pub fn search_prefix(&self, prefix: &str) -> Vec<HistoryMatch> {
+        self.matching(|line| line.starts_with(prefix))
+    }
+
+    /// Finds the newest entry containing `query` strictly older than index `before`.
+    ///
+    /// Passing `None` searches from the newest entry; passing the previous match index steps to
+    /// the next older match, which is how repeated Ctrl+R presses cycle through results.
+    pub fn reverse_search(&self, query: &str, before: Option<usize>) -> Option<HistoryMatch> {
+        self.entries.with_untracked(|entries| {
+            let end = before.unwrap_or(entries.len()).min(entries.len());
+            entries[..end]
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, line)| line.contains(query))
+                .map(|(index, line)| HistoryMatch {
+                    index,
+                    line: line.clone(),
+                })
+        })
+    }
+
+    fn matching(&self, predicate: impl Fn(&str) -> bool) -> Vec<HistoryMatch> {
+        self.entries.with_untracked(|entries| {
+            entries
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|(_, line)| predicate(line))
+                .map(|(index, line)| HistoryMatch {
+                    index,
+                    line: line.clone(),
+                })
+                .collect()
+        })
+    }
+
+    fn persist(&self) {
+        if let Some((persistence, entries)) = self.pending_write() {
+            crate::reactive::spawn_local(save(persistence, entries));
+        }
+    }
+
+    /// Where and what to write, or `None` without a store or before hydration has finished.
+    fn pending_write(&self) -> Option<(HistoryPersistence, Vec<String>)> {
+        let persistence = self.persistence.clone()?;
+        self.hydrated
+            .get()
+            .then(|| (persistence, self.entries.get_untracked()))
+    }
+}
+
+async fn save(persistence: HistoryPersistence, entries: Vec<String>) {
+    if let Err(err) = save_pref_with(persistence.store.as_ref(), &persistence.key, &entries).await {
+        crate::reactive::warn(&format!("persist shell history failed: {err}"));
+    }
+}
+
+fn push_entry(entries: &mut Vec<String>, line: String, capacity: usize) {
+    entries.retain(|existing| existing != &line);
+    entries.push(line);
+    if entries.len() > capacity {
+        let overflow = entries.len() - capacity;
+        entries.drain(0..overflow);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use platform_host::MemoryPrefsStore;
+
+    use super::*;
+
+    #[test]
+    fn push_dedupes_caps_and_persists() {
+        crate::reactive::test_runtime();
+        let prefs = Rc::new(MemoryPrefsStore::default());
+        let history = HistoryStore::new(3).with_prefs(prefs.clone(), "history");
+        block_on(history.hydrate()).expect("hydrate");
+        for line in ["ls", "pwd", "ls", "  ", "cd /", "ls", "theme show"] {
+            history.push(line);
+        }
+        assert_eq!(
+            history.entries().get_untracked(),
+            vec!["cd /", "ls", "theme show"]
+        );
+
+        let restored = HistoryStore::new(3).with_prefs(prefs.clone(), "history");
+        restored.push("pwd");
+        block_on(restored.hydrate()).expect("hydrate");
+        assert_eq!(
+            restored.entries().get_untracked(),
+            vec!["ls", "theme show", "pwd"]
+        );
+        let reloaded = HistoryStore::new(3).with_prefs(prefs, "history");
+        block_on(reloaded.hydrate()).expect("hydrate");
+        assert_eq!(
+            reloaded.entries().get_untracked(),
+            vec!["ls", "theme show", "pwd"]
+        );
+
+        history.set_enabled(false);
+        assert!(!history.push("help list"));
+        history.clear();
+        assert!(history.entries().get_untracked().is_empty());
+    }
+
+    #[test]
+    fn searches_return_newest_matches_first() {
+        crate::reactive::test_runtime();
+        let history = HistoryStore::default();
+        for line in ["theme show", "ls /docs", "theme set skin xp", "pwd"] {
+            history.push(line);
+        }
+        let lines = |matches: Vec<HistoryMatch>| {
+            matches
+                .into_iter()
+                .map(|entry| entry.line)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lines(history.search("theme")),
+            vec!["theme set skin xp", "theme show"]
+        );
+        assert_eq!(lines(history.search_prefix("ls")), vec!["ls /docs"]);
+
+        let first = history.reverse_search("theme", None).expect("match");
+        assert_eq!(first.index, 2);
+        let second = history
+            .reverse_search("theme", Some(first.index))
+            .expect("older match");
+        assert_eq!(second.line, "theme show");
+        assert!(history
+            .reverse_search("theme", Some(second.index))
+            .is_none());
+    }
+}
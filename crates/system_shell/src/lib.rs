@@ -4,31 +4,82 @@
 //! its own cwd, event log, active execution slot, and cancellation state. The engine emits typed
 //! stream events defined in [`system_shell_contract`] so the desktop runtime and terminal UI can
 //! render notices, progress, and structured output consistently.
+//!
+//! Session state lives in the cells described in [`reactive`]: Leptos signals by default, or plain
+//! shared values when the `reactive` feature is disabled for headless hosts.
 
 #![warn(missing_docs, rustdoc::broken_intra_doc_links)]
 
+mod audit;
+#[cfg(feature = "bench")]
+pub mod bench;
+mod cron;
+mod event_log;
+mod history;
+mod manual;
+mod middleware;
+mod pager;
+mod paths;
+mod prompt;
+mod quota;
+mod ranking;
+pub mod reactive;
+mod scheduler;
+mod trie;
+mod variables;
+
 use std::{
     cell::{Cell, RefCell},
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    ops::Range,
     rc::Rc,
 };
 
 use futures::future::LocalBoxFuture;
-use leptos::{create_rw_signal, ReadSignal, RwSignal, SignalGetUntracked, SignalSet, SignalUpdate};
+use platform_host::ExplorerFsService;
+use reactive::{
+    create_rw_signal, ReadSignal, RwSignal, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWithUntracked,
+};
 use system_shell_contract::{
-    CommandDataShape, CommandDescriptor, CommandInputShape, CommandNotice, CommandNoticeLevel,
-    CommandPath, CommandRegistrationToken, CommandResult, CommandScope, CommandVisibility,
-    CompletionItem, CompletionRequest, DisplayPreference, ExecutionId, ParsedCommandLine,
-    ParsedInvocation, ParsedLiteral, ParsedOption, ParsedValue, ShellError, ShellErrorCode,
-    ShellExecutionSummary, ShellExit, ShellRequest, ShellStreamEvent, StructuredData,
-    StructuredRecord, StructuredScalar, StructuredTable, StructuredValue,
+    CommandArgCompletion, CommandArgSpec, CommandCaller, CommandDataShape, CommandDescriptor,
+    CommandId, CommandInputShape, CommandInteractionKind, CommandNotice, CommandNoticeLevel,
+    CommandOptionSpec, CommandOutputShape, CommandPath, CommandRegistrationToken, CommandResult,
+    CommandScope, CommandValueKind, CommandVisibility, CompletionItem, CompletionRequest,
+    DisplayPreference, ExecutionId, HelpDoc, PageContinuation, ParsedCommandLine, ParsedInvocation,
+    ParsedLiteral, ParsedOption, ParsedValue, PromptKind, ShellError, ShellErrorCode,
+    ShellExecutionSummary, ShellExit, ShellFunction, ShellRequest, ShellStreamEvent,
+    StructuredData, StructuredRecord, StructuredScalar, StructuredTable, StructuredValue,
 };
 
+pub use audit::{ShellAuditLog, DEFAULT_AUDIT_CAPACITY};
+pub use cron::{ShellScheduler, MIN_SCHEDULE_INTERVAL_MS};
+pub use event_log::DEFAULT_EVENT_RETENTION;
+pub use history::{HistoryStore, DEFAULT_HISTORY_CAPACITY};
+pub use manual::manual_page;
+pub use middleware::{CommandDispatch, ShellMiddleware};
+pub use pager::{PageBudget, DEFAULT_PAGE_BYTES, DEFAULT_PAGE_ROWS};
+pub use paths::{path_completion, resolve_path};
+pub use quota::AppQuota;
+pub use scheduler::DEFAULT_EXECUTION_LIMIT;
+
+use event_log::EventLog;
+use pager::Pager;
+use prompt::PromptSlot;
+use quota::{ExecutionQuota, QuotaTable};
+use ranking::{completion_token, fuzzy_match, rank_completions};
+use scheduler::ExecutionScheduler;
+use trie::PathTrie;
+use variables::{expand_variables, render_text, variable_name};
+
 /// Async completion provider.
 pub type CompletionHandler = Rc<
     dyn Fn(CompletionRequest) -> LocalBoxFuture<'static, Result<Vec<CompletionItem>, ShellError>>,
 >;
 
+/// Decides whether a session caller holds a kebab-case capability id.
+pub type CapabilityCheck = Rc<dyn Fn(&CommandCaller, &str) -> bool>;
+
 /// Async command handler.
 pub type CommandHandler = Rc<
     dyn Fn(CommandExecutionContext) -> LocalBoxFuture<'static, Result<CommandResult, ShellError>>,
@@ -53,9 +104,12 @@ pub struct CommandExecutionContext {
     pub input: StructuredData,
     /// Optional source window identifier.
     pub source_window_id: Option<u64>,
+    /// Whether a destructive command was invoked with `--dry-run` and must not change state.
+    pub dry_run: bool,
     emitter: EventEmitter,
-    session_cwd: RwSignal<String>,
     cancelled: Rc<Cell<bool>>,
+    quota: Option<Rc<ExecutionQuota>>,
+    run: PipelineRun,
 }
 
 impl CommandExecutionContext {
@@ -90,25 +144,301 @@ impl CommandExecutionContext {
         self.emitter.progress(self.execution_id, value, label);
     }
 
+    /// Writes plain text to standard output.
+    ///
+    /// Consecutive writes are batched into one [`ShellStreamEvent::StdoutChunk`] until 4 KiB
+    /// accumulate, another event is emitted, output switches to stderr, the handler returns, or
+    /// [`CommandExecutionContext::flush_output`] is called. Include newlines explicitly.
+    ///
+    /// Text beyond the app's output quota is dropped; see [`AppQuota`].
+    pub fn stdout(&self, text: impl AsRef<str>) {
+        self.write(OutputStream::Stdout, text.as_ref());
+    }
+
+    /// Writes plain text to the diagnostic stream, batched like
+    /// [`CommandExecutionContext::stdout`] into [`ShellStreamEvent::StderrChunk`] events.
+    pub fn stderr(&self, text: impl AsRef<str>) {
+        self.write(OutputStream::Stderr, text.as_ref());
+    }
+
+    fn write(&self, stream: OutputStream, text: &str) {
+        if self
+            .quota
+            .as_ref()
+            .is_some_and(|quota| !quota.charge_output(text.len()))
+        {
+            return;
+        }
+        self.emitter.write(self.execution_id, stream, text);
+    }
+
+    /// Emits any batched output now, typically before awaiting slow work.
+    pub fn flush_output(&self) {
+        self.emitter.flush_output();
+    }
+
     /// Updates the logical cwd for the active session.
     pub fn set_cwd(&self, cwd: impl Into<String>) {
-        self.session_cwd.set(cwd.into());
+        self.run.state.cwd.set(cwd.into());
     }
 
-    /// Returns whether the foreground execution has been cancelled.
+    /// Returns whether the foreground execution has been cancelled or exceeded its app quota.
     pub fn is_cancelled(&self) -> bool {
-        self.cancelled.get()
+        self.cancelled.get() || self.quota.as_ref().is_some_and(|quota| quota.breached())
+    }
+
+    /// Asks the user for input and waits for the answer.
+    ///
+    /// Emits [`ShellStreamEvent::PromptRequest`] after flushing batched output; the host answers
+    /// through [`ShellSessionHandle::respond`]. Sessions without an attached responder, such as
+    /// scheduled jobs and bare service sessions, fail at once instead of waiting, so handlers
+    /// that can run unattended should offer a flag that skips the prompt.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShellErrorCode::Cancelled`] error when the session has no prompt responder
+    /// or the prompt is dismissed, replaced, or the execution is cancelled.
+    pub async fn prompt(
+        &self,
+        prompt: impl Into<String>,
+        kind: PromptKind,
+    ) -> Result<String, ShellError> {
+        if !self.run.state.prompts.has_responder() {
+            return Err(ShellError::new(
+                ShellErrorCode::Cancelled,
+                "this session cannot answer prompts; pass the command's skip-prompt flag",
+            ));
+        }
+        self.flush_output();
+        let answer = self.run.state.prompts.open(self.execution_id);
+        self.emitter.push(ShellStreamEvent::PromptRequest {
+            execution_id: self.execution_id,
+            prompt: prompt.into(),
+            kind,
+        });
+        match answer.await {
+            Ok(Some(answer)) => Ok(answer),
+            Ok(None) | Err(_) => Err(ShellError::new(
+                ShellErrorCode::Cancelled,
+                "prompt dismissed",
+            )),
+        }
+    }
+
+    /// Asks a yes/no question; see [`CommandExecutionContext::prompt`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShellErrorCode::Cancelled`] error when the prompt is dismissed.
+    pub async fn confirm(&self, prompt: impl Into<String>) -> Result<bool, ShellError> {
+        let answer = self.prompt(prompt, PromptKind::Confirm).await?;
+        Ok(system_shell_contract::is_affirmative(&answer))
+    }
+
+    /// Parses and executes `line` inside the current execution.
+    ///
+    /// Nested output streams into the same session event log under this context's
+    /// [`ExecutionId`], and cwd updates apply to the owning session. Parse, lookup, and handler
+    /// failures are emitted as error notices and reported through the returned [`ShellExit`].
+    /// Nesting is bounded so self-invoking scripts fail with a usage error instead of recursing
+    /// forever.
+    pub fn execute_line(&self, line: impl Into<String>) -> LocalBoxFuture<'static, ShellExit> {
+        let run = self.run.clone();
+        let line = line.into();
+        Box::pin(async move { run.execute_nested(&line).await })
+    }
+
+    /// Returns the positional argument bound to the declared arg spec `name`, converted to `T`.
+    ///
+    /// Yields `Ok(None)` when an optional argument was omitted. For repeatable specs this reads
+    /// the first bound value; use [`CommandExecutionContext::arg_values`] to read all of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a usage error when `name` is not declared by the descriptor or the bound value
+    /// does not convert to `T`.
+    pub fn arg<T: FromArgValue>(&self, name: &str) -> Result<Option<T>, ShellError> {
+        Ok(self.arg_values(name)?.into_iter().next())
+    }
+
+    /// Returns every positional argument bound to the declared arg spec `name`, converted to `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a usage error when `name` is not declared by the descriptor or any bound value
+    /// does not convert to `T`.
+    pub fn arg_values<T: FromArgValue>(&self, name: &str) -> Result<Vec<T>, ShellError> {
+        let command = self.descriptor.path.display();
+        let span = bind_args(&self.descriptor.args, self.args.len())
+            .into_iter()
+            .find(|(spec, _)| spec.name == name)
+            .map(|(_, span)| span)
+            .ok_or_else(|| {
+                ShellError::new(
+                    ShellErrorCode::Usage,
+                    format!("`{command}` declares no argument `<{name}>`"),
+                )
+            })?;
+        span.map(|index| {
+            let value = self
+                .invocation
+                .values
+                .get(index)
+                .cloned()
+                .unwrap_or_else(|| parse_value(&self.args[index]));
+            T::from_arg_value(&value).ok_or_else(|| {
+                ShellError::new(
+                    ShellErrorCode::Validation,
+                    format!(
+                        "argument `<{name}>` for `{command}` expects {}, got `{}`",
+                        T::KIND.label(),
+                        value.raw
+                    ),
+                )
+                .with_details(validation_details(
+                    &command,
+                    "argument",
+                    name,
+                    T::KIND.label(),
+                    &value.raw,
+                ))
+            })
+        })
+        .collect()
+    }
+}
+
+/// Conversion from a parsed positional value, used by [`CommandExecutionContext::arg`].
+pub trait FromArgValue: Sized {
+    /// Value kind reported when conversion fails.
+    const KIND: CommandValueKind;
+
+    /// Converts `value`, returning `None` when its literal has the wrong type.
+    fn from_arg_value(value: &ParsedValue) -> Option<Self>;
+}
+
+impl FromArgValue for String {
+    const KIND: CommandValueKind = CommandValueKind::String;
+
+    fn from_arg_value(value: &ParsedValue) -> Option<Self> {
+        Some(value.raw.clone())
+    }
+}
+
+impl FromArgValue for i64 {
+    const KIND: CommandValueKind = CommandValueKind::Int;
+
+    fn from_arg_value(value: &ParsedValue) -> Option<Self> {
+        match value.literal {
+            ParsedLiteral::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl FromArgValue for f64 {
+    const KIND: CommandValueKind = CommandValueKind::Float;
+
+    fn from_arg_value(value: &ParsedValue) -> Option<Self> {
+        match value.literal {
+            ParsedLiteral::Int(value) => Some(value as f64),
+            ParsedLiteral::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl FromArgValue for bool {
+    const KIND: CommandValueKind = CommandValueKind::Bool;
+
+    fn from_arg_value(value: &ParsedValue) -> Option<Self> {
+        match value.literal {
+            ParsedLiteral::Bool(value) => Some(value),
+            _ => None,
+        }
     }
 }
 
 #[derive(Clone)]
 struct EventEmitter {
-    events: RwSignal<Vec<ShellStreamEvent>>,
+    events: EventLog,
+    pending_output: Rc<RefCell<Option<PendingOutput>>>,
+}
+
+/// Pending output is flushed as one chunk event once it reaches this many bytes.
+const OUTPUT_BATCH_BYTES: usize = 4096;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Consecutive writes to one stream, not yet emitted as a chunk event.
+struct PendingOutput {
+    execution_id: ExecutionId,
+    stream: OutputStream,
+    text: String,
 }
 
 impl EventEmitter {
+    fn new(events: EventLog) -> Self {
+        Self {
+            events,
+            pending_output: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// Appends `event` after flushing pending output so the log stays in write order.
     fn push(&self, event: ShellStreamEvent) {
-        self.events.update(|events| events.push(event));
+        self.flush_output();
+        self.events.push(event);
+    }
+
+    /// Batches `text` into the pending chunk, flushing on stream switches and when full.
+    fn write(&self, execution_id: ExecutionId, stream: OutputStream, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let continues = self
+            .pending_output
+            .borrow()
+            .as_ref()
+            .is_some_and(|pending| {
+                pending.execution_id == execution_id && pending.stream == stream
+            });
+        if !continues {
+            self.flush_output();
+        }
+        let full = {
+            let mut pending = self.pending_output.borrow_mut();
+            let pending = pending.get_or_insert_with(|| PendingOutput {
+                execution_id,
+                stream,
+                text: String::new(),
+            });
+            pending.text.push_str(text);
+            pending.text.len() >= OUTPUT_BATCH_BYTES
+        };
+        if full {
+            self.flush_output();
+        }
+    }
+
+    fn flush_output(&self) {
+        let Some(pending) = self.pending_output.borrow_mut().take() else {
+            return;
+        };
+        let PendingOutput {
+            execution_id,
+            stream,
+            text,
+        } = pending;
+        let event = match stream {
+            OutputStream::Stdout => ShellStreamEvent::StdoutChunk { execution_id, text },
+            OutputStream::Stderr => ShellStreamEvent::StderrChunk { execution_id, text },
+        };
+        self.events.push(event);
     }
 
     fn notice(&self, execution_id: ExecutionId, notice: CommandNotice) {
@@ -126,11 +456,18 @@ impl EventEmitter {
         });
     }
 
-    fn data(&self, execution_id: ExecutionId, data: StructuredData, display: DisplayPreference) {
+    fn data(
+        &self,
+        execution_id: ExecutionId,
+        data: StructuredData,
+        display: DisplayPreference,
+        more: Option<PageContinuation>,
+    ) {
         self.push(ShellStreamEvent::Data {
             execution_id,
             data,
             display,
+            more,
         });
     }
 }
@@ -142,58 +479,281 @@ struct RegisteredCommand {
     handler: CommandHandler,
 }
 
+/// Number of refused registrations kept for `shell conflicts`.
+const MAX_REJECTED_CONFLICTS: usize = 32;
+
+/// Option name the engine declares on destructive descriptors.
+const DRY_RUN_OPTION: &str = "dry-run";
+
+/// How the registry settled two commands that claim the same invocation path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Both stay registered and the more specific scope wins resolution.
+    Shadowed,
+    /// The later registration was refused because both scopes have the same precedence.
+    Rejected,
+}
+
+impl ConflictResolution {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Shadowed => "shadowed",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// Registry diagnostic for two commands that claim the same path or alias.
+///
+/// Window scope takes precedence over app scope, which takes precedence over global scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandConflict {
+    /// Claimed invocation tokens, space-separated.
+    pub path: String,
+    /// Command that resolves for `path`.
+    pub winner: CommandId,
+    /// Scope of the resolving command.
+    pub winner_scope: CommandScope,
+    /// Command that is shadowed by, or was refused in favor of, `winner`.
+    pub loser: CommandId,
+    /// Scope of the shadowed or refused command.
+    pub loser_scope: CommandScope,
+    /// Whether `loser` is registered but shadowed or was refused.
+    pub resolution: ConflictResolution,
+}
+
 #[derive(Default)]
 struct RegistryState {
     next_token: u64,
-    by_token: BTreeMap<CommandRegistrationToken, RegisteredCommand>,
+    by_token: BTreeMap<CommandRegistrationToken, Rc<RegisteredCommand>>,
+    intrinsics: Vec<Rc<RegisteredCommand>>,
+    /// Every path and alias of every resolvable command; intrinsics use key 0.
+    paths: PathTrie<Rc<RegisteredCommand>>,
+    snapshot: Option<Rc<RegistrySnapshot>>,
+    rejected: VecDeque<CommandConflict>,
+    next_middleware: u64,
+    middleware: BTreeMap<u64, Rc<dyn ShellMiddleware>>,
+}
+
+impl RegistryState {
+    fn index_paths(&mut self, key: u64, registered: &Rc<RegisteredCommand>) {
+        let public = registered.descriptor.visibility == CommandVisibility::Public;
+        for candidate in candidate_paths(&registered.descriptor) {
+            self.paths
+                .insert(&candidate, key, registered.clone(), public);
+        }
+    }
 }
 
 /// Shared command registry.
 ///
 /// The registry stores descriptors, completion handlers, and execution handlers. Session objects
-/// snapshot visible commands from this registry when resolving completions and command execution.
-#[derive(Clone, Default)]
+/// resolve completions and command execution against an immutable snapshot that is shared until
+/// the next registration change. Paths are indexed incrementally as commands come and go. Every registration change bumps a reactive
+/// [`CommandRegistry::generation`] counter.
+#[derive(Clone)]
 pub struct CommandRegistry {
     state: Rc<RefCell<RegistryState>>,
+    generation: RwSignal<u64>,
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self {
+            state: Rc::default(),
+            generation: create_rw_signal(0),
+        }
+    }
 }
 
 impl CommandRegistry {
+    /// Reactive counter bumped whenever a command is registered or unregistered.
+    ///
+    /// Subscribers such as open terminal sessions refresh cached completions when it changes.
+    pub fn generation(&self) -> ReadSignal<u64> {
+        self.generation.read_only()
+    }
+
+    /// Drops the cached snapshot and notifies generation subscribers.
+    fn bump_generation(&self) {
+        self.state.borrow_mut().snapshot = None;
+        self.generation
+            .update(|generation| *generation = generation.wrapping_add(1));
+    }
+
     /// Registers one command and returns its registration token.
+    ///
+    /// A path or alias already claimed by a command of more or less specific scope is allowed and
+    /// shadowed by precedence (window, then app, then global).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShellErrorCode::Conflict`] error, and records the refusal for
+    /// [`CommandRegistry::conflicts`], when a command with the same scope precedence already
+    /// claims one of the descriptor's paths or aliases.
     pub fn register(
         &self,
         descriptor: CommandDescriptor,
         completion: Option<CompletionHandler>,
         handler: CommandHandler,
-    ) -> CommandRegistrationToken {
+    ) -> Result<CommandRegistrationToken, ShellError> {
+        let descriptor = with_dry_run_option(descriptor);
         let mut state = self.state.borrow_mut();
+        let clash = state
+            .intrinsics
+            .iter()
+            .chain(state.by_token.values())
+            .filter(|existing| {
+                scope_rank(&existing.descriptor.scope) == scope_rank(&descriptor.scope)
+            })
+            .find_map(|existing| {
+                shared_path(&existing.descriptor, &descriptor).map(|path| CommandConflict {
+                    path,
+                    winner: existing.descriptor.id.clone(),
+                    winner_scope: existing.descriptor.scope.clone(),
+                    loser: descriptor.id.clone(),
+                    loser_scope: descriptor.scope.clone(),
+                    resolution: ConflictResolution::Rejected,
+                })
+            });
+        if let Some(conflict) = clash {
+            let error = ShellError::new(
+                ShellErrorCode::Conflict,
+                format!(
+                    "command `{}` is already registered by `{}` in {} scope",
+                    conflict.path,
+                    conflict.winner.as_str(),
+                    scope_label(&conflict.winner_scope)
+                ),
+            );
+            if state.rejected.len() == MAX_REJECTED_CONFLICTS {
+                state.rejected.pop_front();
+            }
+            state.rejected.push_back(conflict);
+            return Err(error);
+        }
         state.next_token = state.next_token.saturating_add(1);
         let token = CommandRegistrationToken(state.next_token);
-        state.by_token.insert(
-            token,
-            RegisteredCommand {
-                descriptor,
-                completion,
-                handler,
-            },
-        );
-        token
+        let registered = Rc::new(RegisteredCommand {
+            descriptor,
+            completion,
+            handler,
+        });
+        state.index_paths(token.0, &registered);
+        state.by_token.insert(token, registered);
+        drop(state);
+        self.bump_generation();
+        Ok(token)
+    }
+
+    /// Lists shadowed paths among current registrations, then recently refused registrations.
+    pub fn conflicts(&self) -> Vec<CommandConflict> {
+        let snapshot = self.snapshot();
+        let commands = &snapshot.commands;
+        let mut conflicts = Vec::new();
+        for (index, left) in commands.iter().enumerate() {
+            for right in &commands[index + 1..] {
+                let (left, right) = (&left.descriptor, &right.descriptor);
+                if scope_rank(&left.scope) == scope_rank(&right.scope) {
+                    continue;
+                }
+                let (winner, loser) = if scope_rank(&left.scope) > scope_rank(&right.scope) {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                if let Some(path) = shared_path(winner, loser) {
+                    conflicts.push(CommandConflict {
+                        path,
+                        winner: winner.id.clone(),
+                        winner_scope: winner.scope.clone(),
+                        loser: loser.id.clone(),
+                        loser_scope: loser.scope.clone(),
+                        resolution: ConflictResolution::Shadowed,
+                    });
+                }
+            }
+        }
+        conflicts.sort_by(|left, right| left.path.cmp(&right.path));
+        conflicts.extend(self.state.borrow().rejected.iter().cloned());
+        conflicts
     }
 
     /// Removes a previously registered command token.
     pub fn unregister(&self, token: CommandRegistrationToken) {
-        self.state.borrow_mut().by_token.remove(&token);
+        let mut state = self.state.borrow_mut();
+        let removed = state.by_token.remove(&token);
+        if let Some(registered) = &removed {
+            let public = registered.descriptor.visibility == CommandVisibility::Public;
+            for candidate in candidate_paths(&registered.descriptor) {
+                state.paths.remove(&candidate, token.0, public);
+            }
+        }
+        drop(state);
+        if removed.is_some() {
+            self.bump_generation();
+        }
     }
 
-    fn visible_commands(&self) -> Vec<RegisteredCommand> {
+    fn visible_commands(&self) -> Vec<Rc<RegisteredCommand>> {
         self.state.borrow().by_token.values().cloned().collect()
     }
 
-    /// Returns the currently registered command descriptors.
+    /// Installs an engine-owned command that cannot be unregistered.
+    fn install_intrinsic(&self, descriptor: CommandDescriptor, handler: CommandHandler) {
+        let mut state = self.state.borrow_mut();
+        let registered = Rc::new(RegisteredCommand {
+            descriptor,
+            completion: None,
+            handler,
+        });
+        state.index_paths(0, &registered);
+        state.intrinsics.push(registered);
+        state.snapshot = None;
+    }
+
+    /// Returns engine intrinsics followed by registered commands.
+    fn resolvable_commands(&self) -> Vec<Rc<RegisteredCommand>> {
+        let mut commands = self.state.borrow().intrinsics.clone();
+        commands.extend(self.visible_commands());
+        commands
+    }
+
+    /// Returns the indexed snapshot of resolvable commands, rebuilding it after a change.
+    fn snapshot(&self) -> Rc<RegistrySnapshot> {
+        if let Some(snapshot) = self.state.borrow().snapshot.clone() {
+            return snapshot;
+        }
+        let paths = self.state.borrow().paths.clone();
+        let snapshot = Rc::new(RegistrySnapshot::new(self.resolvable_commands(), paths));
+        self.state.borrow_mut().snapshot = Some(snapshot.clone());
+        snapshot
+    }
+
+    fn add_middleware(&self, middleware: Rc<dyn ShellMiddleware>) -> u64 {
+        let mut state = self.state.borrow_mut();
+        state.next_middleware = state.next_middleware.saturating_add(1);
+        let id = state.next_middleware;
+        state.middleware.insert(id, middleware);
+        id
+    }
+
+    fn remove_middleware(&self, id: u64) {
+        self.state.borrow_mut().middleware.remove(&id);
+    }
+
+    /// Returns registered middleware in registration order.
+    fn middleware(&self) -> Vec<Rc<dyn ShellMiddleware>> {
+        self.state.borrow().middleware.values().cloned().collect()
+    }
+
+    /// Returns the currently registered command descriptors, including engine intrinsics.
     pub fn descriptors(&self) -> Vec<CommandDescriptor> {
         let mut descriptors = self
-            .visible_commands()
-            .into_iter()
-            .map(|registered| registered.descriptor)
+            .snapshot()
+            .commands
+            .iter()
+            .map(|registered| registered.descriptor.clone())
             .collect::<Vec<_>>();
         descriptors.sort_by(|left, right| left.path.display().cmp(&right.path.display()));
         descriptors
@@ -223,13 +783,49 @@ impl Drop for CommandRegistryHandle {
     }
 }
 
+/// Drop-based middleware registration handle.
+#[derive(Clone)]
+pub struct MiddlewareHandle {
+    registry: CommandRegistry,
+    id: u64,
+    active: Rc<Cell<bool>>,
+}
+
+impl MiddlewareHandle {
+    /// Removes the middleware if it is still registered.
+    pub fn unregister(&self) {
+        if self.active.replace(false) {
+            self.registry.remove_middleware(self.id);
+        }
+    }
+}
+
+impl Drop for MiddlewareHandle {
+    fn drop(&mut self) {
+        self.unregister();
+    }
+}
+
 #[derive(Clone)]
 struct SessionState {
     cwd: RwSignal<String>,
-    events: RwSignal<Vec<ShellStreamEvent>>,
+    events: EventLog,
     active_execution: RwSignal<Option<ExecutionId>>,
     next_execution_id: Rc<Cell<u64>>,
     cancel_flag: Rc<Cell<bool>>,
+    functions: RwSignal<BTreeMap<String, ShellFunction>>,
+    variables: RwSignal<BTreeMap<String, StructuredData>>,
+    history: HistoryStore,
+    caller: Option<CommandCaller>,
+    capability_check: Option<CapabilityCheck>,
+    id: u64,
+    scheduler: ExecutionScheduler,
+    pager: Pager,
+    page_budget: PageBudget,
+    explorer_fs: Option<Rc<dyn ExplorerFsService>>,
+    prompts: PromptSlot,
+    quotas: QuotaTable,
+    _alive: Rc<()>,
 }
 
 /// A shell session with one foreground execution slot.
@@ -241,8 +837,34 @@ pub struct ShellSessionHandle {
 
 impl ShellSessionHandle {
     /// Reactive stream event log for this session.
+    ///
+    /// The log keeps at most the session's retention limit of events; see
+    /// [`ShellSessionHandle::acknowledge_events`].
     pub fn events(&self) -> ReadSignal<Vec<ShellStreamEvent>> {
-        self.state.events.read_only()
+        self.state.events.events()
+    }
+
+    /// Removes the first `count` events from the log once the consumer has processed them.
+    ///
+    /// Consumers that acknowledge everything they read never see a
+    /// [`ShellStreamEvent::Truncated`] marker unless they fall more than the retention limit
+    /// behind. Sessions sharing a log, such as scheduled job sessions, share acknowledgements.
+    pub fn acknowledge_events(&self, count: usize) {
+        self.state.events.acknowledge(count);
+    }
+
+    /// Calls `listener` with every event this session's log receives from now on.
+    ///
+    /// Listeners see each event once as it is emitted, before retention drops anything, so hosts
+    /// without a reactive runtime can stream output without reading the log. Sessions sharing a
+    /// log share listeners.
+    pub fn subscribe_events(&self, listener: Rc<dyn Fn(&ShellStreamEvent)>) {
+        self.state.events.subscribe(listener);
+    }
+
+    /// Changes how many unacknowledged events this session's log retains.
+    pub fn set_event_retention(&self, retention: usize) {
+        self.state.events.set_retention(retention);
     }
 
     /// Reactive active execution id for this session.
@@ -255,61 +877,160 @@ impl ShellSessionHandle {
         self.state.cwd.read_only()
     }
 
-    /// Cancels the active foreground execution.
+    /// Reactive user-defined functions for this session, keyed by name.
+    ///
+    /// Hosts that want functions to survive reloads can persist this map and restore entries with
+    /// [`ShellSessionHandle::define_function`].
+    pub fn functions(&self) -> ReadSignal<BTreeMap<String, ShellFunction>> {
+        self.state.functions.read_only()
+    }
+
+    /// Defines or replaces one session function after validating its name and body.
+    pub fn define_function(&self, function: ShellFunction) -> Result<(), ShellError> {
+        validate_function(&function)?;
+        self.state.functions.update(|functions| {
+            functions.insert(function.name.clone(), function);
+        });
+        Ok(())
+    }
+
+    /// Removes one session function, returning it when it existed.
+    pub fn remove_function(&self, name: &str) -> Option<ShellFunction> {
+        remove_session_function(&self.state, name)
+    }
+
+    /// Reactive variables stored by `capture` stages in this session, keyed by name.
+    pub fn variables(&self) -> ReadSignal<BTreeMap<String, StructuredData>> {
+        self.state.variables.read_only()
+    }
+
+    /// Stores `data` in a session variable as a `capture` stage would, so hosts can hand values
+    /// to later `var` stages.
+    ///
+    /// # Errors
+    ///
+    /// Returns a usage error when `name` is not a valid variable name.
+    pub fn set_variable(&self, name: &str, data: StructuredData) -> Result<(), ShellError> {
+        let name = variable_name(name)?;
+        self.state.variables.update(|variables| {
+            variables.insert(name, data);
+        });
+        Ok(())
+    }
+
+    /// Cancels the active foreground execution, dismissing any prompt it is waiting on.
     pub fn cancel(&self) {
         if self.state.active_execution.get_untracked().is_some() {
             self.state.cancel_flag.set(true);
+            self.state.prompts.dismiss();
         }
     }
 
+    /// Declares that the host renders prompts and answers them through
+    /// [`ShellSessionHandle::respond`].
+    ///
+    /// Until a responder attaches, [`CommandExecutionContext::prompt`] fails as cancelled
+    /// without emitting a prompt request.
+    pub fn attach_prompt_responder(&self) {
+        self.state.prompts.attach_responder();
+    }
+
+    /// Answers the prompt that `execution_id` is waiting on; `None` dismisses it.
+    ///
+    /// Returns `false` when that execution has no open prompt, for example because it already
+    /// finished or a newer prompt replaced it.
+    pub fn respond(&self, execution_id: ExecutionId, answer: Option<String>) -> bool {
+        self.state.prompts.respond(execution_id, answer)
+    }
+
     /// Resolves completion candidates for the current input.
     pub async fn complete(
         &self,
         request: CompletionRequest,
     ) -> Result<Vec<CompletionItem>, ShellError> {
-        let snapshot = RegistrySnapshot::new(self.registry.visible_commands());
-        snapshot.complete(request).await
+        let snapshot = self.registry.snapshot();
+        let first_word = request.line.trim_start();
+        let completing_command = !first_word.contains(char::is_whitespace);
+        let mut items = snapshot
+            .complete(request.clone(), self.state.explorer_fs.as_ref())
+            .await?;
+        if completing_command {
+            let functions = self.state.functions.get_untracked();
+            items.extend(
+                functions
+                    .values()
+                    .filter(|function| fuzzy_match(first_word, &function.name).is_some())
+                    .map(|function| CompletionItem {
+                        value: function.name.clone(),
+                        label: function.name.clone(),
+                        detail: Some(format!("function: {}", function.body)),
+                        score: 0,
+                        matched: Vec::new(),
+                    }),
+            );
+            items.sort_by(|left, right| left.label.cmp(&right.label));
+            items.dedup_by(|left, right| left.value == right.value);
+        }
+        let history = self.state.history.entries().get_untracked();
+        Ok(rank_completions(
+            items,
+            completion_token(&request.line),
+            &history,
+        ))
     }
 
     /// Parses and executes one command request.
     pub fn submit(&self, request: ShellRequest) {
         if self.state.active_execution.get_untracked().is_some() {
-            self.state.events.update(|events| {
-                events.push(ShellStreamEvent::Notice {
-                    execution_id: ExecutionId(0),
-                    notice: CommandNotice {
-                        level: CommandNoticeLevel::Warning,
-                        message: "another command is already running".to_string(),
-                    },
-                });
+            self.state.events.push(ShellStreamEvent::Notice {
+                execution_id: ExecutionId(0),
+                notice: CommandNotice {
+                    level: CommandNoticeLevel::Warning,
+                    message: "another command is already running".to_string(),
+                },
+            });
+            return;
+        }
+
+        self.state.history.push(&request.line);
+
+        if let Some(definition) = parse_function_definition(&request.line) {
+            let execution_id = self.next_execution_id();
+            let run = self.pipeline_run(execution_id, request.source_window_id);
+            run.emitter.push(ShellStreamEvent::Started { execution_id });
+            let exit = run.define(definition);
+            run.emitter.push(ShellStreamEvent::Completed {
+                summary: ShellExecutionSummary {
+                    execution_id,
+                    command_path: None,
+                    exit,
+                },
             });
             return;
         }
 
-        let parsed = match parse_command_line(&request.line) {
+        let line = expand_session_variables(&self.state, &request.line);
+        let parsed = match parse_command_line(&line) {
             Ok(parsed) => parsed,
             Err(err) => {
                 let execution_id = self.next_execution_id();
-                self.state.events.update(|events| {
-                    events.push(ShellStreamEvent::Started { execution_id });
-                    events.push(ShellStreamEvent::Notice {
+                self.state.events.extend([
+                    ShellStreamEvent::Started { execution_id },
+                    ShellStreamEvent::Notice {
                         execution_id,
                         notice: CommandNotice {
                             level: CommandNoticeLevel::Error,
                             message: err.message.clone(),
                         },
-                    });
-                    events.push(ShellStreamEvent::Completed {
+                    },
+                    ShellStreamEvent::Completed {
                         summary: ShellExecutionSummary {
                             execution_id,
                             command_path: None,
-                            exit: ShellExit {
-                                code: err.exit_code(),
-                                message: Some(err.message),
-                            },
+                            exit: ShellExit::from(err),
                         },
-                    });
-                });
+                    },
+                ]);
                 return;
             }
         };
@@ -321,178 +1042,384 @@ impl ShellSessionHandle {
         let execution_id = self.next_execution_id();
         self.state.cancel_flag.set(false);
         self.state.active_execution.set(Some(execution_id));
-        let state = self.state.clone();
-        let registry = self.registry.clone();
-        leptos::spawn_local(async move {
-            let emitter = EventEmitter {
-                events: state.events,
-            };
-            emitter.push(ShellStreamEvent::Started { execution_id });
-
-            let snapshot = RegistrySnapshot::new(registry.visible_commands());
-            let mut piped_input = StructuredData::Empty;
-            let mut final_summary = ShellExecutionSummary {
+        let run = self.pipeline_run(execution_id, request.source_window_id);
+        run.emitter.push(ShellStreamEvent::Started { execution_id });
+        let start_run = run.clone();
+        let start = Box::new(move || {
+            reactive::spawn_local(async move {
+                let run = start_run;
+                let outcome = run.execute(parsed.pipeline, StructuredData::Empty).await;
+                run.emitter.push(ShellStreamEvent::Completed {
+                    summary: outcome.summary,
+                });
+                run.state.active_execution.set(None);
+                run.state.scheduler.finish(run.state.id);
+            });
+        });
+        if let Some(ahead) = self.state.scheduler.schedule(self.state.id, start) {
+            run.emitter.notice(
                 execution_id,
-                command_path: None,
-                exit: ShellExit::success(),
-            };
+                CommandNotice {
+                    level: CommandNoticeLevel::Info,
+                    message: format!(
+                        "queued behind {ahead} command(s); at most {} run at once",
+                        self.state.scheduler.limit()
+                    ),
+                },
+            );
+        }
+    }
+
+    fn pipeline_run(
+        &self,
+        execution_id: ExecutionId,
+        source_window_id: Option<u64>,
+    ) -> PipelineRun {
+        PipelineRun {
+            state: self.state.clone(),
+            registry: self.registry.clone(),
+            execution_id,
+            emitter: EventEmitter::new(self.state.events.clone()),
+            source_window_id,
+            depth: 0,
+        }
+    }
+
+    fn next_execution_id(&self) -> ExecutionId {
+        let next = self.state.next_execution_id.get().saturating_add(1);
+        self.state.next_execution_id.set(next);
+        ExecutionId(next)
+    }
+}
+
+/// Maximum nesting depth for lines executed from inside a running handler.
+const MAX_NESTED_EXECUTION_DEPTH: usize = 16;
+
+/// One pipeline execution bound to a session, execution id, and event stream.
+#[derive(Clone)]
+struct PipelineRun {
+    state: SessionState,
+    registry: CommandRegistry,
+    execution_id: ExecutionId,
+    emitter: EventEmitter,
+    source_window_id: Option<u64>,
+    depth: usize,
+}
+
+/// Final summary and output of one executed pipeline.
+struct PipelineOutcome {
+    summary: ShellExecutionSummary,
+    output: StructuredData,
+}
+
+impl PipelineRun {
+    async fn execute(
+        &self,
+        pipeline: Vec<ParsedInvocation>,
+        input: StructuredData,
+    ) -> PipelineOutcome {
+        let execution_id = self.execution_id;
+        let emitter = &self.emitter;
+        let state = &self.state;
+        let snapshot = self.registry.snapshot();
+        let mut piped_input = input;
+        let mut final_summary = ShellExecutionSummary {
+            execution_id,
+            command_path: None,
+            exit: ShellExit::success(),
+        };
+
+        for stage in pipeline {
+            if state.cancel_flag.get() {
+                emitter.push(ShellStreamEvent::Cancelled { execution_id });
+                final_summary.exit = ShellExit::cancelled();
+                break;
+            }
 
-            for stage in parsed.pipeline {
-                if state.cancel_flag.get() {
-                    emitter.push(ShellStreamEvent::Cancelled { execution_id });
-                    final_summary.exit = ShellExit::cancelled();
+            let function = stage.tokens.first().and_then(|name| {
+                state
+                    .functions
+                    .with_untracked(|functions| functions.get(name).cloned())
+            });
+            if let Some(function) = function {
+                let outcome = self
+                    .call_function(&function, &stage.tokens[1..], piped_input)
+                    .await;
+                piped_input = outcome.output;
+                final_summary.command_path = Some(CommandPath::new(&function.name));
+                final_summary.exit = outcome.summary.exit;
+                if final_summary.exit.code != 0 {
                     break;
                 }
+                continue;
+            }
 
-                match snapshot.resolve_stage(&stage.tokens) {
-                    Ok(ResolvedStage::Namespace { path }) => {
-                        let result = snapshot.namespace_result(&path);
-                        for notice in &result.notices {
-                            emitter.notice(execution_id, notice.clone());
-                        }
-                        if !matches!(result.output, StructuredData::Empty) {
-                            emitter.data(execution_id, result.output.clone(), result.display);
-                            piped_input = result.output;
-                        }
-                        final_summary.command_path = Some(path);
-                        final_summary.exit = result.exit;
+            match snapshot.resolve_stage(&stage.tokens) {
+                Ok(ResolvedStage::Namespace { path }) => {
+                    let result = snapshot.namespace_result(&path);
+                    for notice in &result.notices {
+                        emitter.notice(execution_id, notice.clone());
+                    }
+                    if !matches!(result.output, StructuredData::Empty) {
+                        self.emit_output(result.output.clone(), result.display);
+                        piped_input = result.output;
+                    }
+                    final_summary.command_path = Some(path);
+                    final_summary.exit = result.exit;
+                }
+                Ok(ResolvedStage::Leaf {
+                    registered,
+                    matched_len,
+                }) => {
+                    let (options, values, args) = parse_invocation_arguments(
+                        &stage.tokens[matched_len..],
+                        &registered.descriptor.options,
+                    );
+                    let invocation = ParsedInvocation {
+                        tokens: stage.tokens.clone(),
+                        options,
+                        values,
+                    };
+
+                    if wants_help(&invocation) {
+                        let result = snapshot.command_help_result(&registered.descriptor);
+                        emitter.data(execution_id, result.output.clone(), result.display, None);
+                        piped_input = result.output;
+                        final_summary.command_path = Some(registered.descriptor.path.clone());
+                        final_summary.exit = result.exit;
+                        continue;
                     }
-                    Ok(ResolvedStage::Leaf {
-                        registered,
-                        matched_len,
-                    }) => {
-                        let (options, values, args) =
-                            parse_invocation_arguments(&stage.tokens[matched_len..]);
-                        let invocation = ParsedInvocation {
-                            tokens: stage.tokens.clone(),
-                            options,
-                            values,
-                        };
-
-                        if wants_help(&invocation) {
-                            let result = snapshot.command_help_result(&registered.descriptor);
-                            emitter.data(execution_id, result.output.clone(), result.display);
-                            piped_input = result.output;
-                            final_summary.command_path = Some(registered.descriptor.path.clone());
-                            final_summary.exit = result.exit;
-                            continue;
-                        }
 
-                        let input_shape = registered.descriptor.input_shape.clone();
-                        if let Err(err) = validate_input_shape(&piped_input, &input_shape) {
-                            emitter.notice(
-                                execution_id,
-                                CommandNotice {
-                                    level: CommandNoticeLevel::Error,
-                                    message: err.message.clone(),
-                                },
-                            );
+                    let dry_run = wants_dry_run(&registered.descriptor, &invocation);
+                    let dispatch = CommandDispatch {
+                        execution_id,
+                        descriptor: registered.descriptor.clone(),
+                        argv: stage.tokens.clone(),
+                        cwd: state.cwd.get_untracked(),
+                        source_window_id: self.source_window_id,
+                        caller: state.caller.clone(),
+                        depth: self.depth,
+                        dry_run,
+                    };
+                    let middleware = self.registry.middleware();
+                    let checked = authorize(&registered.descriptor, state)
+                        .and_then(|()| {
+                            validate_options(&invocation.options, &registered.descriptor)
+                        })
+                        .and_then(|()| validate_args(&args, &registered.descriptor))
+                        .and_then(|()| {
+                            validate_input_shape(&piped_input, &registered.descriptor.input_shape)
+                        })
+                        .and_then(|()| {
+                            middleware
+                                .iter()
+                                .try_for_each(|hook| hook.before_execute(&dispatch))
+                        });
+                    let admission = match checked
+                        .and_then(|()| state.quotas.admit(&registered.descriptor))
+                    {
+                        Ok(admission) => admission,
+                        Err(err) => {
+                            for hook in middleware.iter().rev() {
+                                hook.on_error(&dispatch, &err);
+                            }
                             final_summary.command_path = Some(registered.descriptor.path.clone());
-                            final_summary.exit = ShellExit {
-                                code: err.exit_code(),
-                                message: Some(err.message),
-                            };
+                            final_summary.exit = self.fail(err);
                             break;
                         }
+                    };
 
-                        let context = CommandExecutionContext {
-                            execution_id,
-                            descriptor: registered.descriptor.clone(),
-                            invocation,
-                            argv: stage.tokens.clone(),
-                            args,
-                            cwd: state.cwd.get_untracked(),
-                            input: piped_input.clone(),
-                            source_window_id: request.source_window_id,
-                            emitter: emitter.clone(),
-                            session_cwd: state.cwd,
-                            cancelled: state.cancel_flag.clone(),
-                        };
-                        match (registered.handler)(context).await {
-                            Ok(result) => {
-                                if let Some(cwd) = result.cwd.clone() {
-                                    state.cwd.set(cwd);
-                                }
-                                for notice in &result.notices {
-                                    emitter.notice(execution_id, notice.clone());
-                                }
-                                if !matches!(result.output, StructuredData::Empty) {
-                                    emitter.data(
-                                        execution_id,
-                                        result.output.clone(),
-                                        result.display,
-                                    );
-                                }
-                                piped_input = result.output;
-                                final_summary.command_path =
-                                    Some(registered.descriptor.path.clone());
-                                final_summary.exit = result.exit.clone();
-                                if final_summary.exit.code != 0 {
-                                    break;
-                                }
+                    let context = CommandExecutionContext {
+                        execution_id,
+                        descriptor: registered.descriptor.clone(),
+                        invocation,
+                        argv: stage.tokens.clone(),
+                        args,
+                        cwd: state.cwd.get_untracked(),
+                        input: piped_input.clone(),
+                        source_window_id: self.source_window_id,
+                        dry_run,
+                        emitter: emitter.clone(),
+                        cancelled: state.cancel_flag.clone(),
+                        quota: admission.as_ref().map(|admission| admission.execution()),
+                        run: self.clone(),
+                    };
+                    let handled = match admission {
+                        Some(admission) => admission.run((registered.handler)(context)).await,
+                        None => (registered.handler)(context).await,
+                    };
+                    emitter.flush_output();
+                    match handled {
+                        Ok(result) => {
+                            for hook in middleware.iter().rev() {
+                                hook.after_execute(&dispatch, &result);
+                            }
+                            if let Some(cwd) = result.cwd.clone() {
+                                state.cwd.set(cwd);
                             }
-                            Err(err) => {
-                                emitter.notice(
-                                    execution_id,
-                                    CommandNotice {
-                                        level: CommandNoticeLevel::Error,
-                                        message: err.message.clone(),
-                                    },
-                                );
-                                final_summary.command_path =
-                                    Some(registered.descriptor.path.clone());
-                                final_summary.exit = ShellExit {
-                                    code: err.exit_code(),
-                                    message: Some(err.message),
-                                };
+                            for notice in &result.notices {
+                                emitter.notice(execution_id, notice.clone());
+                            }
+                            if !matches!(result.output, StructuredData::Empty)
+                                && result.output != piped_input
+                            {
+                                self.emit_output(result.output.clone(), result.display);
+                            }
+                            piped_input = result.output;
+                            final_summary.command_path = Some(registered.descriptor.path.clone());
+                            final_summary.exit = result.exit.clone();
+                            if final_summary.exit.code != 0 {
                                 break;
                             }
                         }
+                        Err(err) => {
+                            for hook in middleware.iter().rev() {
+                                hook.on_error(&dispatch, &err);
+                            }
+                            final_summary.command_path = Some(registered.descriptor.path.clone());
+                            final_summary.exit = self.fail(err);
+                            break;
+                        }
                     }
-                    Err(err) => {
-                        emitter.notice(
-                            execution_id,
-                            CommandNotice {
-                                level: CommandNoticeLevel::Error,
-                                message: err.message.clone(),
-                            },
-                        );
-                        final_summary.exit = ShellExit {
-                            code: err.exit_code(),
-                            message: Some(err.message),
-                        };
-                        break;
-                    }
+                }
+                Err(err) => {
+                    final_summary.exit = self.fail(err);
+                    break;
                 }
             }
+        }
 
-            emitter.push(ShellStreamEvent::Completed {
-                summary: final_summary,
-            });
-            state.active_execution.set(None);
+        PipelineOutcome {
+            summary: final_summary,
+            output: piped_input,
+        }
+    }
+
+    /// Emits `data` as one data frame, withholding rows beyond the session page budget.
+    fn emit_output(&self, data: StructuredData, display: DisplayPreference) {
+        let (page, more) = self
+            .state
+            .pager
+            .paginate(data, display, self.state.page_budget);
+        self.emitter.data(self.execution_id, page, display, more);
+    }
+
+    /// Parses and executes `line` one nesting level deeper within the same execution.
+    async fn execute_nested(&self, line: &str) -> ShellExit {
+        if let Some(definition) = parse_function_definition(line) {
+            return self.define(definition);
+        }
+        self.execute_nested_with_input(line, StructuredData::Empty)
+            .await
+            .summary
+            .exit
+    }
+
+    async fn execute_nested_with_input(
+        &self,
+        line: &str,
+        input: StructuredData,
+    ) -> PipelineOutcome {
+        let failed = |exit| PipelineOutcome {
+            summary: ShellExecutionSummary {
+                execution_id: self.execution_id,
+                command_path: None,
+                exit,
+            },
+            output: StructuredData::Empty,
+        };
+        if self.depth >= MAX_NESTED_EXECUTION_DEPTH {
+            return failed(self.fail(ShellError::new(
+                ShellErrorCode::Usage,
+                format!("nested execution depth limit ({MAX_NESTED_EXECUTION_DEPTH}) exceeded"),
+            )));
+        }
+        let line = expand_session_variables(&self.state, line);
+        let parsed = match parse_command_line(&line) {
+            Ok(parsed) => parsed,
+            Err(err) => return failed(self.fail(err)),
+        };
+        let nested = Self {
+            depth: self.depth + 1,
+            ..self.clone()
+        };
+        Box::pin(nested.execute(parsed.pipeline, input)).await
+    }
+
+    /// Runs a session function body with `args` bound to its positional parameters.
+    async fn call_function(
+        &self,
+        function: &ShellFunction,
+        args: &[String],
+        input: StructuredData,
+    ) -> PipelineOutcome {
+        let line = expand_positional_parameters(&function.body, &function.name, args);
+        self.execute_nested_with_input(&line, input).await
+    }
+
+    /// Stores a parsed function definition, reporting the outcome as a notice.
+    fn define(&self, definition: Result<ShellFunction, ShellError>) -> ShellExit {
+        let function = match definition.and_then(|function| {
+            validate_function(&function)?;
+            Ok(function)
+        }) {
+            Ok(function) => function,
+            Err(err) => return self.fail(err),
+        };
+        let message = format!("defined function `{}`", function.name);
+        self.state.functions.update(|functions| {
+            functions.insert(function.name.clone(), function);
         });
+        self.emitter.notice(
+            self.execution_id,
+            CommandNotice {
+                level: CommandNoticeLevel::Info,
+                message,
+            },
+        );
+        ShellExit::success()
     }
 
-    fn next_execution_id(&self) -> ExecutionId {
-        let next = self.state.next_execution_id.get().saturating_add(1);
-        self.state.next_execution_id.set(next);
-        ExecutionId(next)
+    /// Emits `err` as an error notice and converts it into exit metadata.
+    fn fail(&self, err: ShellError) -> ShellExit {
+        self.emitter.notice(
+            self.execution_id,
+            CommandNotice {
+                level: CommandNoticeLevel::Error,
+                message: err.message.clone(),
+            },
+        );
+        ShellExit::from(err)
     }
 }
 
-#[derive(Clone)]
+/// Immutable view of the resolvable commands, indexed by invocation path.
 struct RegistrySnapshot {
-    commands: Vec<RegisteredCommand>,
+    commands: Vec<Rc<RegisteredCommand>>,
+    paths: PathTrie<Rc<RegisteredCommand>>,
+    /// Public descriptors sorted by path, for help, namespace listings, and completion.
+    public: Vec<CommandDescriptor>,
 }
 
 impl RegistrySnapshot {
-    fn new(commands: Vec<RegisteredCommand>) -> Self {
-        Self { commands }
+    fn new(commands: Vec<Rc<RegisteredCommand>>, paths: PathTrie<Rc<RegisteredCommand>>) -> Self {
+        let mut public = commands
+            .iter()
+            .filter(|registered| registered.descriptor.visibility == CommandVisibility::Public)
+            .map(|registered| registered.descriptor.clone())
+            .collect::<Vec<_>>();
+        public.sort_by(|left, right| left.path.display().cmp(&right.path.display()));
+        Self {
+            commands,
+            paths,
+            public,
+        }
     }
 
     async fn complete(
         &self,
         request: CompletionRequest,
+        explorer_fs: Option<&Rc<dyn ExplorerFsService>>,
     ) -> Result<Vec<CompletionItem>, ShellError> {
         let parsed = tokenize_line(&request.line)?;
         let stages = split_pipeline_tokens(parsed)?;
@@ -520,7 +1447,19 @@ impl RegistrySnapshot {
         }) = self.resolve_stage(&base_tokens)
         {
             if base_tokens.len() >= matched_len {
-                if let Some(completion) = registered.completion {
+                if let Some(items) =
+                    option_completions(&registered.descriptor, &base_tokens[matched_len..], &prefix)
+                {
+                    return Ok(items);
+                }
+                let args = &base_tokens[matched_len..];
+                if let Some(fs) = explorer_fs {
+                    let kind = positional_completion(&registered.descriptor, args);
+                    if kind != CommandArgCompletion::None {
+                        return path_completion(fs.clone(), kind)(request).await;
+                    }
+                }
+                if let Some(completion) = registered.completion.clone() {
                     return completion(request).await;
                 }
             }
@@ -532,6 +1471,8 @@ impl RegistrySnapshot {
                 value: segment.clone(),
                 label: segment,
                 detail: descriptor.map(|descriptor| descriptor.help.summary.clone()),
+                score: 0,
+                matched: Vec::new(),
             });
         }
         items.sort_by(|left, right| left.label.cmp(&right.label));
@@ -539,15 +1480,8 @@ impl RegistrySnapshot {
         Ok(items)
     }
 
-    fn descriptors(&self) -> Vec<CommandDescriptor> {
-        let mut descriptors = self
-            .commands
-            .iter()
-            .filter(|registered| registered.descriptor.visibility == CommandVisibility::Public)
-            .map(|registered| registered.descriptor.clone())
-            .collect::<Vec<_>>();
-        descriptors.sort_by(|left, right| left.path.display().cmp(&right.path.display()));
-        descriptors
+    fn descriptors(&self) -> &[CommandDescriptor] {
+        &self.public
     }
 
     fn child_segments(
@@ -558,12 +1492,12 @@ impl RegistrySnapshot {
         let mut out = Vec::new();
         let mut seen = BTreeSet::new();
         for descriptor in self.descriptors() {
-            let tokens = descriptor_path_tokens(&descriptor);
+            let tokens = descriptor_path_tokens(descriptor);
             if tokens.len() <= base_tokens.len() || !tokens.starts_with(base_tokens) {
                 continue;
             }
             let next = tokens[base_tokens.len()].clone();
-            if next.starts_with(prefix) && seen.insert(next.clone()) {
+            if fuzzy_match(prefix, &next).is_some() && seen.insert(next.clone()) {
                 out.push((next, Some(descriptor.clone())));
             }
         }
@@ -571,43 +1505,8 @@ impl RegistrySnapshot {
     }
 
     fn command_help_result(&self, descriptor: &CommandDescriptor) -> CommandResult {
-        let aliases = if descriptor.aliases.is_empty() {
-            StructuredValue::List(Vec::new())
-        } else {
-            StructuredValue::List(
-                descriptor
-                    .aliases
-                    .iter()
-                    .cloned()
-                    .map(|alias| StructuredValue::Scalar(StructuredScalar::String(alias)))
-                    .collect(),
-            )
-        };
-        let examples = StructuredValue::List(
-            descriptor
-                .help
-                .examples
-                .iter()
-                .map(|example| {
-                    StructuredValue::Record(StructuredRecord {
-                        fields: vec![
-                            field_string("command", example.command.clone()),
-                            field_string("summary", example.summary.clone()),
-                        ],
-                    })
-                })
-                .collect(),
-        );
         CommandResult {
-            output: StructuredData::Record(StructuredRecord {
-                fields: vec![
-                    field_string("path", descriptor.path.display()),
-                    field_string("summary", descriptor.help.summary.clone()),
-                    field_string("usage", descriptor.help.usage.clone()),
-                    StructuredFieldBuilder::new("aliases", aliases).build(),
-                    StructuredFieldBuilder::new("examples", examples).build(),
-                ],
-            }),
+            output: manual_page(descriptor, self.descriptors()),
             display: DisplayPreference::Help,
             notices: Vec::new(),
             cwd: None,
@@ -624,7 +1523,7 @@ impl RegistrySnapshot {
         let mut rows = Vec::new();
         let mut seen = BTreeSet::new();
         for descriptor in self.descriptors() {
-            let tokens = descriptor_path_tokens(&descriptor);
+            let tokens = descriptor_path_tokens(descriptor);
             if tokens.len() <= prefix.len() || !tokens.starts_with(&prefix) {
                 continue;
             }
@@ -655,51 +1554,31 @@ impl RegistrySnapshot {
         }
     }
 
+    /// Resolves the longest registered path prefixing `tokens`, preferring the most specific
+    /// scope among commands claiming it.
     fn resolve_stage(&self, tokens: &[String]) -> Result<ResolvedStage, ShellError> {
-        let mut best_match: Option<(RegisteredCommand, usize, u8)> = None;
-        let mut ambiguous = false;
-
-        for registered in &self.commands {
-            for candidate in candidate_paths(&registered.descriptor) {
-                if tokens.len() < candidate.len() || !tokens.starts_with(&candidate) {
-                    continue;
-                }
-                let score = (candidate.len(), scope_rank(&registered.descriptor.scope));
-                match best_match.as_ref() {
-                    Some((_, best_len, best_scope))
-                        if score.0 < *best_len
-                            || (score.0 == *best_len && score.1 < *best_scope) =>
-                    {
-                        continue;
-                    }
-                    Some((_, best_len, best_scope))
-                        if score.0 == *best_len && score.1 == *best_scope =>
-                    {
-                        ambiguous = true;
-                    }
-                    _ => {
-                        ambiguous = false;
-                        best_match = Some((registered.clone(), candidate.len(), score.1));
-                    }
-                }
+        if let Some((matched_len, claimants)) = self.paths.longest_match(tokens) {
+            let best = claimants
+                .iter()
+                .map(|claim| scope_rank(&claim.value.descriptor.scope))
+                .max()
+                .unwrap_or_default();
+            let mut winners = claimants
+                .iter()
+                .filter(|claim| scope_rank(&claim.value.descriptor.scope) == best);
+            if let (Some(winner), None) = (winners.next(), winners.next()) {
+                return Ok(ResolvedStage::Leaf {
+                    registered: winner.value.clone(),
+                    matched_len,
+                });
             }
-        }
-
-        if ambiguous {
             return Err(ShellError::new(
                 ShellErrorCode::Usage,
                 format!("ambiguous command `{}`", tokens.join(" ")),
             ));
         }
 
-        if let Some((registered, matched_len, _)) = best_match {
-            return Ok(ResolvedStage::Leaf {
-                registered: Box::new(registered),
-                matched_len,
-            });
-        }
-
-        if prefix_exists(&self.descriptors(), tokens) {
+        if self.paths.has_public_below(tokens) {
             return Ok(ResolvedStage::Namespace {
                 path: CommandPath::from_segments(
                     tokens
@@ -723,7 +1602,7 @@ enum ResolvedStage {
         path: CommandPath,
     },
     Leaf {
-        registered: Box<RegisteredCommand>,
+        registered: Rc<RegisteredCommand>,
         matched_len: usize,
     },
 }
@@ -736,6 +1615,23 @@ fn scope_rank(scope: &CommandScope) -> u8 {
     }
 }
 
+fn scope_label(scope: &CommandScope) -> String {
+    match scope {
+        CommandScope::Window { window_id } => format!("window {window_id}"),
+        CommandScope::App { app_id } => format!("app {app_id}"),
+        CommandScope::Global => "global".to_string(),
+    }
+}
+
+/// Returns the first invocation path, joined by spaces, that both descriptors claim.
+fn shared_path(left: &CommandDescriptor, right: &CommandDescriptor) -> Option<String> {
+    let theirs = candidate_paths(right);
+    candidate_paths(left)
+        .into_iter()
+        .find(|path| theirs.contains(path))
+        .map(|path| path.join(" "))
+}
+
 fn descriptor_path_tokens(descriptor: &CommandDescriptor) -> Vec<String> {
     descriptor
         .path
@@ -756,12 +1652,127 @@ fn candidate_paths(descriptor: &CommandDescriptor) -> Vec<Vec<String>> {
     candidates
 }
 
-fn prefix_exists(descriptors: &[CommandDescriptor], prefix: &[String]) -> bool {
-    descriptors.iter().any(|descriptor| {
-        candidate_paths(descriptor)
+/// Completes option flags and option values for a resolved leaf command.
+///
+/// Returns `None` when `prefix` is a positional argument so argument completion can run instead.
+/// A value position yields an empty list rather than `None` when the option has no known values.
+fn option_completions(
+    descriptor: &CommandDescriptor,
+    args: &[String],
+    prefix: &str,
+) -> Option<Vec<CompletionItem>> {
+    if args.iter().any(|arg| arg == "--") {
+        return None;
+    }
+    let mut items = if let Some((name, value)) = prefix
+        .strip_prefix("--")
+        .and_then(|rest| rest.split_once('='))
+    {
+        let spec = descriptor.options.iter().find(|spec| spec.name == name)?;
+        option_value_items(spec, value, |choice| format!("--{name}={choice}"))
+    } else if prefix.starts_with('-') && prefix.parse::<f64>().is_err() {
+        let mut flags = descriptor
+            .options
+            .iter()
+            .map(|spec| (format!("--{}", spec.name), spec.summary.clone()))
+            .collect::<Vec<_>>();
+        if !descriptor.options.iter().any(|spec| spec.name == "help") {
+            flags.push(("--help".to_string(), "Show the manual page.".to_string()));
+        }
+        flags
             .into_iter()
-            .any(|candidate| candidate.len() > prefix.len() && candidate.starts_with(prefix))
-    })
+            .filter(|(flag, _)| fuzzy_match(prefix, flag).is_some())
+            .map(|(flag, summary)| CompletionItem {
+                value: flag.clone(),
+                label: flag,
+                detail: Some(summary),
+                score: 0,
+                matched: Vec::new(),
+            })
+            .collect()
+    } else {
+        let previous = args.last()?;
+        let spec = match previous.strip_prefix("--") {
+            Some(name) => descriptor.options.iter().find(|spec| spec.name == name),
+            None => {
+                let mut shorts = previous.strip_prefix('-')?.chars();
+                let short = shorts.next_back()?;
+                descriptor
+                    .options
+                    .iter()
+                    .find(|spec| spec.short == Some(short))
+            }
+        }
+        .filter(|spec| spec.takes_value)?;
+        option_value_items(spec, prefix, str::to_string)
+    };
+    items.sort_by(|left, right| left.label.cmp(&right.label));
+    Some(items)
+}
+
+/// Returns the built-in completion of the positional argument that follows `args`.
+///
+/// Options and their values are skipped; past the declared specs, a trailing repeatable spec
+/// keeps applying.
+fn positional_completion(descriptor: &CommandDescriptor, args: &[String]) -> CommandArgCompletion {
+    let (_, _, positionals) = parse_invocation_arguments(args, &descriptor.options);
+    descriptor
+        .args
+        .get(positionals.len())
+        .or_else(|| descriptor.args.last().filter(|spec| spec.repeatable))
+        .map_or(CommandArgCompletion::None, |spec| spec.completion)
+}
+
+/// Lists declared choices, or `true`/`false` for boolean options, that fuzzily match `prefix`.
+fn option_value_items(
+    spec: &CommandOptionSpec,
+    prefix: &str,
+    value: impl Fn(&str) -> String,
+) -> Vec<CompletionItem> {
+    let choices = if spec.choices.is_empty() && spec.value_kind == CommandValueKind::Bool {
+        vec!["true".to_string(), "false".to_string()]
+    } else {
+        spec.choices.clone()
+    };
+    choices
+        .into_iter()
+        .filter(|choice| fuzzy_match(prefix, choice).is_some())
+        .map(|choice| CompletionItem {
+            value: value(&choice),
+            label: choice,
+            detail: Some(spec.summary.clone()),
+            score: 0,
+            matched: Vec::new(),
+        })
+        .collect()
+}
+
+/// Declares the engine-provided `--dry-run` flag on destructive descriptors that lack it.
+fn with_dry_run_option(mut descriptor: CommandDescriptor) -> CommandDescriptor {
+    if descriptor.destructive
+        && !descriptor
+            .options
+            .iter()
+            .any(|spec| spec.name == DRY_RUN_OPTION)
+    {
+        descriptor.options.push(CommandOptionSpec {
+            name: DRY_RUN_OPTION.to_string(),
+            short: None,
+            summary: "Report what would change without changing anything.".to_string(),
+            takes_value: false,
+            value_kind: CommandValueKind::Any,
+            choices: Vec::new(),
+        });
+    }
+    descriptor
+}
+
+fn wants_dry_run(descriptor: &CommandDescriptor, invocation: &ParsedInvocation) -> bool {
+    descriptor.destructive
+        && invocation
+            .options
+            .iter()
+            .any(|option| option.name == DRY_RUN_OPTION)
 }
 
 fn wants_help(invocation: &ParsedInvocation) -> bool {
@@ -904,233 +1915,2923 @@ fn parse_command_line(line: &str) -> Result<ParsedCommandLine, ShellError> {
     })
 }
 
-fn parse_invocation_arguments(
-    tokens: &[String],
-) -> (Vec<ParsedOption>, Vec<ParsedValue>, Vec<String>) {
-    let mut options = Vec::new();
-    let mut values = Vec::new();
-    let mut args = Vec::new();
-    let mut index = 0usize;
-
-    while index < tokens.len() {
-        let token = &tokens[index];
-        if let Some(rest) = token.strip_prefix("--") {
-            if !rest.is_empty() {
-                if let Some((name, raw_value)) = rest.split_once('=') {
-                    options.push(ParsedOption {
-                        name: name.to_string(),
-                        short: None,
-                        value: Some(parse_value(raw_value)),
-                    });
-                } else {
-                    let takes_value =
-                        index + 1 < tokens.len() && !tokens[index + 1].starts_with('-');
-                    let value = takes_value.then(|| {
-                        index += 1;
-                        parse_value(&tokens[index])
-                    });
-                    options.push(ParsedOption {
-                        name: rest.to_string(),
-                        short: None,
-                        value,
-                    });
-                }
-                index += 1;
-                continue;
-            }
-        }
-
-        if token.starts_with('-') && token.len() > 1 {
-            for short in token.trim_start_matches('-').chars() {
-                options.push(ParsedOption {
-                    name: short.to_string(),
-                    short: Some(short),
-                    value: None,
-                });
-            }
-            index += 1;
-            continue;
-        }
-
-        args.push(token.clone());
-        values.push(parse_value(token));
-        index += 1;
+/// Recognizes `fn name { body }` definitions.
+///
+/// Returns `None` when `line` is not a definition so `fn list`-style command lines still resolve
+/// through the registry.
+fn parse_function_definition(line: &str) -> Option<Result<ShellFunction, ShellError>> {
+    let rest = line.trim().strip_prefix("fn")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
     }
-
-    (options, values, args)
-}
-
-fn parse_value(raw: &str) -> ParsedValue {
-    let literal = if raw == "null" {
-        ParsedLiteral::Null
-    } else if matches!(raw, "true" | "on") {
-        ParsedLiteral::Bool(true)
-    } else if matches!(raw, "false" | "off") {
-        ParsedLiteral::Bool(false)
-    } else if let Ok(value) = raw.parse::<i64>() {
-        ParsedLiteral::Int(value)
-    } else if let Ok(value) = raw.parse::<f64>() {
-        ParsedLiteral::Float(value)
-    } else {
-        ParsedLiteral::String(raw.to_string())
+    let (name, body) = rest.split_once('{')?;
+    let Some(body) = body.trim_end().strip_suffix('}') else {
+        return Some(Err(ShellError::new(
+            ShellErrorCode::Usage,
+            "function body must end with `}`",
+        )));
     };
-
-    ParsedValue {
-        raw: raw.to_string(),
-        literal,
-    }
+    Some(Ok(ShellFunction {
+        name: name.trim().to_string(),
+        body: body.trim().to_string(),
+    }))
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Token {
-    Pipe,
-    Word(String),
+fn validate_function(function: &ShellFunction) -> Result<(), ShellError> {
+    let mut chars = function.name.chars();
+    let valid_name = chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+        && function.name != "fn";
+    if !valid_name {
+        return Err(ShellError::new(
+            ShellErrorCode::Usage,
+            format!("invalid function name `{}`", function.name),
+        ));
+    }
+    let parsed = parse_command_line(&function.body)?;
+    if parsed.pipeline.is_empty() {
+        return Err(ShellError::new(
+            ShellErrorCode::Usage,
+            format!("function `{}` has an empty body", function.name),
+        ));
+    }
+    Ok(())
 }
 
-fn field_string(name: &str, value: String) -> system_shell_contract::StructuredField {
-    StructuredFieldBuilder::new(
-        name,
-        StructuredValue::Scalar(StructuredScalar::String(value)),
-    )
-    .build()
+fn remove_session_function(state: &SessionState, name: &str) -> Option<ShellFunction> {
+    let mut removed = None;
+    state
+        .functions
+        .update(|functions| removed = functions.remove(name));
+    removed
 }
 
-struct StructuredFieldBuilder {
-    name: String,
-    value: StructuredValue,
+/// Expands `$name` references to the session's captured variables in `line`.
+fn expand_session_variables(state: &SessionState, line: &str) -> String {
+    state
+        .variables
+        .with_untracked(|variables| expand_variables(line, |name| variables.get(name).cloned()))
 }
 
-impl StructuredFieldBuilder {
-    fn new(name: &str, value: StructuredValue) -> Self {
-        Self {
-            name: name.to_string(),
-            value,
-        }
-    }
-
-    fn build(self) -> system_shell_contract::StructuredField {
-        system_shell_contract::StructuredField {
-            name: self.name,
-            value: self.value,
-        }
+fn intrinsic_descriptor(
+    path: &str,
+    usage: &str,
+    summary: &str,
+    args: Vec<CommandArgSpec>,
+) -> CommandDescriptor {
+    let path = CommandPath::new(path);
+    CommandDescriptor {
+        id: CommandId::new(path.display()),
+        parent_path: path.parent(),
+        path,
+        aliases: Vec::new(),
+        scope: CommandScope::Global,
+        visibility: CommandVisibility::Public,
+        interaction_kind: CommandInteractionKind::Hierarchical,
+        discoverable_children: true,
+        input_shape: CommandInputShape::none(),
+        output_shape: CommandOutputShape::new(CommandDataShape::Any),
+        args,
+        options: Vec::new(),
+        required_capability: None,
+        destructive: false,
+        owner_app_id: None,
+        help: HelpDoc {
+            summary: summary.to_string(),
+            description: None,
+            usage: usage.to_string(),
+            examples: Vec::new(),
+        },
     }
 }
 
-/// Root shell engine used by the runtime.
-///
-/// Create one engine for the runtime, register built-in or app-provided commands on its registry,
-/// then spawn per-window sessions with [`ShellEngine::new_session`].
-#[derive(Clone, Default)]
-pub struct ShellEngine {
-    registry: CommandRegistry,
+/// Installs engine-owned commands that operate on session state.
+fn install_intrinsics(registry: &CommandRegistry) {
+    #[cfg(feature = "bench")]
+    bench::install(registry);
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "fn list",
+            "fn list",
+            "List functions defined in this session.",
+            Vec::new(),
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let rows = context.run.state.functions.with_untracked(|functions| {
+                    functions
+                        .values()
+                        .map(|function| StructuredRecord {
+                            fields: vec![
+                                field_string("name", function.name.clone()),
+                                field_string("body", function.body.clone()),
+                            ],
+                        })
+                        .collect::<Vec<_>>()
+                });
+                Ok(CommandResult::success(StructuredData::Table(
+                    StructuredTable {
+                        columns: vec!["name".to_string(), "body".to_string()],
+                        rows,
+                        schema: None,
+                        source_command: Some(context.descriptor.path.clone()),
+                        fallback_text: None,
+                    },
+                )))
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "history list",
+            "history list [query]",
+            "Show command history, optionally filtered by substring.",
+            vec![CommandArgSpec {
+                name: "query".to_string(),
+                summary: "Substring to match.".to_string(),
+                required: false,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let query = context.arg::<String>("query")?.unwrap_or_default();
+                let mut matches = context.run.state.history.search(&query);
+                matches.reverse();
+                let rows = matches
+                    .into_iter()
+                    .map(|entry| StructuredRecord {
+                        fields: vec![
+                            StructuredFieldBuilder::new(
+                                "index",
+                                StructuredValue::Scalar(StructuredScalar::Int(entry.index as i64)),
+                            )
+                            .build(),
+                            field_string("command", entry.line),
+                        ],
+                    })
+                    .collect();
+                Ok(CommandResult {
+                    display: DisplayPreference::Table,
+                    ..CommandResult::success(StructuredData::Table(StructuredTable {
+                        columns: vec!["index".to_string(), "command".to_string()],
+                        rows,
+                        schema: None,
+                        source_command: Some(context.descriptor.path.clone()),
+                        fallback_text: None,
+                    }))
+                })
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "history clear",
+            "history clear",
+            "Clear command history.",
+            Vec::new(),
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                context.run.state.history.clear();
+                let mut result = CommandResult::success(StructuredData::Empty);
+                result.notices.push(CommandNotice {
+                    level: CommandNoticeLevel::Info,
+                    message: "history cleared".to_string(),
+                });
+                Ok(result)
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "shell sessions list",
+            "shell sessions list",
+            "List live shell sessions and their scheduling state.",
+            Vec::new(),
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let optional_int = |value: Option<u64>| {
+                    StructuredValue::Scalar(value.map_or(StructuredScalar::Null, |value| {
+                        StructuredScalar::Int(value as i64)
+                    }))
+                };
+                let rows = context
+                    .run
+                    .state
+                    .scheduler
+                    .sessions()
+                    .into_iter()
+                    .map(|session| StructuredRecord {
+                        fields: vec![
+                            StructuredFieldBuilder::new("id", optional_int(Some(session.id)))
+                                .build(),
+                            field_string(
+                                "app",
+                                session.caller.as_ref().map_or_else(
+                                    || "runtime".to_string(),
+                                    |caller| caller.app_id.clone(),
+                                ),
+                            ),
+                            StructuredFieldBuilder::new(
+                                "window",
+                                optional_int(session.caller.and_then(|caller| caller.window_id)),
+                            )
+                            .build(),
+                            field_string("cwd", session.cwd),
+                            field_string("status", session.activity.label().to_string()),
+                            StructuredFieldBuilder::new(
+                                "execution",
+                                optional_int(session.execution_id.map(|id| id.0)),
+                            )
+                            .build(),
+                        ],
+                    })
+                    .collect();
+                Ok(CommandResult {
+                    display: DisplayPreference::Table,
+                    ..CommandResult::success(StructuredData::Table(StructuredTable {
+                        columns: ["id", "app", "window", "cwd", "status", "execution"]
+                            .map(str::to_string)
+                            .to_vec(),
+                        rows,
+                        schema: None,
+                        source_command: Some(context.descriptor.path.clone()),
+                        fallback_text: None,
+                    }))
+                })
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "shell conflicts",
+            "shell conflicts",
+            "List command paths claimed by more than one registration.",
+            Vec::new(),
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let rows = context
+                    .run
+                    .registry
+                    .conflicts()
+                    .into_iter()
+                    .map(|conflict| StructuredRecord {
+                        fields: vec![
+                            field_string("path", conflict.path),
+                            field_string("resolution", conflict.resolution.label().to_string()),
+                            field_string("winner", conflict.winner.as_str().to_string()),
+                            field_string("winner_scope", scope_label(&conflict.winner_scope)),
+                            field_string("loser", conflict.loser.as_str().to_string()),
+                            field_string("loser_scope", scope_label(&conflict.loser_scope)),
+                        ],
+                    })
+                    .collect();
+                Ok(CommandResult {
+                    display: DisplayPreference::Table,
+                    ..CommandResult::success(StructuredData::Table(StructuredTable {
+                        columns: [
+                            "path",
+                            "resolution",
+                            "winner",
+                            "winner_scope",
+                            "loser",
+                            "loser_scope",
+                        ]
+                        .map(str::to_string)
+                        .to_vec(),
+                        rows,
+                        schema: None,
+                        source_command: Some(context.descriptor.path.clone()),
+                        fallback_text: None,
+                    }))
+                })
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "man",
+            "man <command...>",
+            "Show the manual page for a command or namespace.",
+            vec![CommandArgSpec {
+                name: "command".to_string(),
+                summary: "Command path or alias to document.".to_string(),
+                required: true,
+                repeatable: true,
+                completion: CommandArgCompletion::None,
+            }],
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let target = context.arg_values::<String>("command")?;
+                let snapshot = context.run.registry.snapshot();
+                match snapshot.resolve_stage(&target)? {
+                    ResolvedStage::Leaf {
+                        registered,
+                        matched_len,
+                    } if matched_len == target.len() => {
+                        Ok(snapshot.command_help_result(&registered.descriptor))
+                    }
+                    ResolvedStage::Namespace { path } => Ok(snapshot.namespace_result(&path)),
+                    ResolvedStage::Leaf { .. } => Err(ShellError::new(
+                        ShellErrorCode::NotFound,
+                        format!("no manual entry for `{}`", target.join(" ")),
+                    )),
+                }
+            })
+        }),
+    );
+    let page_token = || {
+        vec![CommandArgSpec {
+            name: "token".to_string(),
+            summary: "Continuation token from the truncated frame.".to_string(),
+            required: false,
+            repeatable: false,
+            completion: CommandArgCompletion::None,
+        }]
+    };
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "page next",
+            "page next [token]",
+            "Show the next page of truncated output.",
+            page_token(),
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let (data, display) = take_page(&context)?;
+                context.run.emit_output(data, display);
+                Ok(CommandResult::success(StructuredData::Empty))
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "page all",
+            "page all [token]",
+            "Show all remaining rows of truncated output.",
+            page_token(),
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let (data, display) = take_page(&context)?;
+                context
+                    .emitter
+                    .data(context.execution_id, data, display, None);
+                Ok(CommandResult::success(StructuredData::Empty))
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "fn remove",
+            "fn remove <name>",
+            "Remove a function defined in this session.",
+            vec![CommandArgSpec {
+                name: "name".to_string(),
+                summary: "Function name.".to_string(),
+                required: true,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let name = context.arg::<String>("name")?.unwrap_or_default();
+                if remove_session_function(&context.run.state, &name).is_none() {
+                    return Err(ShellError::new(
+                        ShellErrorCode::NotFound,
+                        format!("function not found: {name}"),
+                    ));
+                }
+                let mut result = CommandResult::success(StructuredData::Empty);
+                result.notices.push(CommandNotice {
+                    level: CommandNoticeLevel::Info,
+                    message: format!("removed function `{name}`"),
+                });
+                Ok(result)
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        CommandDescriptor {
+            input_shape: CommandInputShape::accepts(CommandDataShape::Any),
+            ..intrinsic_descriptor(
+                "tee",
+                "tee <path>",
+                "Write piped data to a file and pass it on unchanged.",
+                vec![CommandArgSpec {
+                    name: "path".to_string(),
+                    summary: "File to write; relative paths resolve against the cwd.".to_string(),
+                    required: true,
+                    repeatable: false,
+                    completion: CommandArgCompletion::Path,
+                }],
+            )
+        },
+        Rc::new(|context| {
+            Box::pin(async move {
+                let path = context.arg::<String>("path")?.unwrap_or_default();
+                let path = resolve_path(&context.cwd, &path);
+                let Some(fs) = context.run.state.explorer_fs.clone() else {
+                    return Err(ShellError::new(
+                        ShellErrorCode::Unavailable,
+                        "tee needs a filesystem attached to the shell",
+                    ));
+                };
+                fs.write_text_file(&path, &render_text(&context.input))
+                    .await
+                    .map_err(|err| {
+                        ShellError::new(ShellErrorCode::Filesystem, err).with_details(
+                            StructuredRecord {
+                                fields: vec![
+                                    field_string("operation", "write".to_string()),
+                                    field_string("path", path.clone()),
+                                ],
+                            },
+                        )
+                    })?;
+                Ok(CommandResult::success(context.input))
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        CommandDescriptor {
+            input_shape: CommandInputShape::accepts(CommandDataShape::Any),
+            ..intrinsic_descriptor(
+                "capture",
+                "capture <$name>",
+                "Store piped data in a session variable and pass it on unchanged.",
+                vec![CommandArgSpec {
+                    name: "name".to_string(),
+                    summary: "Variable to assign, with or without a leading `$`.".to_string(),
+                    required: true,
+                    repeatable: false,
+                    completion: CommandArgCompletion::None,
+                }],
+            )
+        },
+        Rc::new(|context| {
+            Box::pin(async move {
+                let name = variable_name(&context.arg::<String>("name")?.unwrap_or_default())?;
+                context.run.state.variables.update(|variables| {
+                    variables.insert(name, context.input.clone());
+                });
+                Ok(CommandResult::success(context.input))
+            })
+        }),
+    );
+    registry.install_intrinsic(
+        intrinsic_descriptor(
+            "var",
+            "var <$name>",
+            "Emit a session variable's stored value as structured data.",
+            vec![CommandArgSpec {
+                name: "name".to_string(),
+                summary: "Variable to read, with or without a leading `$`.".to_string(),
+                required: true,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+        ),
+        Rc::new(|context| {
+            Box::pin(async move {
+                let name = variable_name(&context.arg::<String>("name")?.unwrap_or_default())?;
+                let value = context
+                    .run
+                    .state
+                    .variables
+                    .with_untracked(|variables| variables.get(&name).cloned());
+                value.map(CommandResult::success).ok_or_else(|| {
+                    ShellError::new(
+                        ShellErrorCode::NotFound,
+                        format!("variable not set: ${name}"),
+                    )
+                })
+            })
+        }),
+    );
+}
+
+/// Takes the session's pending page, honoring an optional `token` argument.
+fn take_page(
+    context: &CommandExecutionContext,
+) -> Result<(StructuredData, DisplayPreference), ShellError> {
+    let token = context
+        .arg::<i64>("token")?
+        .map(|token| u64::try_from(token).unwrap_or(u64::MAX));
+    context.run.state.pager.take(token)
+}
+
+fn parse_invocation_arguments(
+    tokens: &[String],
+    specs: &[CommandOptionSpec],
+) -> (Vec<ParsedOption>, Vec<ParsedValue>, Vec<String>) {
+    let mut options = Vec::new();
+    let mut values = Vec::new();
+    let mut args = Vec::new();
+    let mut index = 0usize;
+
+    while index < tokens.len() {
+        let token = &tokens[index];
+        if token == "--" {
+            for token in &tokens[index + 1..] {
+                args.push(token.clone());
+                values.push(parse_value(token));
+            }
+            break;
+        }
+
+        if let Some(rest) = token.strip_prefix("--") {
+            if let Some((name, raw_value)) = rest.split_once('=') {
+                options.push(ParsedOption {
+                    name: name.to_string(),
+                    short: None,
+                    value: Some(parse_value(raw_value)),
+                });
+            } else {
+                let has_next = index + 1 < tokens.len();
+                let takes_value = match specs.iter().find(|spec| spec.name == rest) {
+                    Some(spec) => spec.takes_value && has_next,
+                    None => has_next && !tokens[index + 1].starts_with('-'),
+                };
+                let value = takes_value.then(|| {
+                    index += 1;
+                    parse_value(&tokens[index])
+                });
+                options.push(ParsedOption {
+                    name: rest.to_string(),
+                    short: None,
+                    value,
+                });
+            }
+            index += 1;
+            continue;
+        }
+
+        if token.starts_with('-') && token.len() > 1 && token.parse::<f64>().is_err() {
+            let shorts = token.trim_start_matches('-').chars().collect::<Vec<_>>();
+            for (position, short) in shorts.iter().copied().enumerate() {
+                let spec = specs.iter().find(|spec| spec.short == Some(short));
+                let value = match spec {
+                    Some(spec)
+                        if spec.takes_value
+                            && position + 1 == shorts.len()
+                            && index + 1 < tokens.len() =>
+                    {
+                        index += 1;
+                        Some(parse_value(&tokens[index]))
+                    }
+                    _ => None,
+                };
+                options.push(ParsedOption {
+                    name: spec.map_or_else(|| short.to_string(), |spec| spec.name.clone()),
+                    short: Some(short),
+                    value,
+                });
+            }
+            index += 1;
+            continue;
+        }
+
+        args.push(token.clone());
+        values.push(parse_value(token));
+        index += 1;
+    }
+
+    (options, values, args)
+}
+
+/// Checks parsed options against the descriptor's declared option specs.
+///
+/// `--help`/`-h` are always accepted. Unknown options report the closest declared option name.
+fn validate_options(
+    options: &[ParsedOption],
+    descriptor: &CommandDescriptor,
+) -> Result<(), ShellError> {
+    let command = descriptor.path.display();
+    for option in options {
+        let display = match option.short {
+            Some(short) => format!("-{short}"),
+            None => format!("--{}", option.name),
+        };
+        let spec = descriptor.options.iter().find(|spec| match option.short {
+            Some(short) => spec.short == Some(short),
+            None => spec.name == option.name,
+        });
+        let Some(spec) = spec else {
+            if option.name == "help" || option.short == Some('h') {
+                continue;
+            }
+            let mut message = format!("unknown option `{display}` for `{command}`");
+            if let Some(suggestion) = nearest_option(&option.name, &descriptor.options) {
+                message.push_str(&format!("; did you mean `--{suggestion}`?"));
+            }
+            return Err(ShellError::new(ShellErrorCode::Usage, message));
+        };
+        match (&option.value, spec.takes_value) {
+            (None, true) => {
+                return Err(ShellError::new(
+                    ShellErrorCode::Usage,
+                    format!(
+                        "option `{display}` for `{command}` requires a value ({})",
+                        spec.value_kind.label()
+                    ),
+                ));
+            }
+            (Some(_), false) => {
+                return Err(ShellError::new(
+                    ShellErrorCode::Usage,
+                    format!("option `{display}` for `{command}` does not take a value"),
+                ));
+            }
+            (Some(value), true) if !spec.value_kind.accepts(&value.literal) => {
+                return Err(ShellError::new(
+                    ShellErrorCode::Validation,
+                    format!(
+                        "option `{display}` for `{command}` expects {}, got `{}`",
+                        spec.value_kind.label(),
+                        value.raw
+                    ),
+                )
+                .with_details(validation_details(
+                    &command,
+                    "option",
+                    &spec.name,
+                    spec.value_kind.label(),
+                    &value.raw,
+                )));
+            }
+            (Some(value), true)
+                if !spec.choices.is_empty() && !spec.choices.contains(&value.raw) =>
+            {
+                let expected = spec.choices.join(", ");
+                return Err(ShellError::new(
+                    ShellErrorCode::Validation,
+                    format!(
+                        "option `{display}` for `{command}` expects one of {expected}, got `{}`",
+                        value.raw
+                    ),
+                )
+                .with_details(validation_details(
+                    &command, "option", &spec.name, &expected, &value.raw,
+                )));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Builds the details record attached to [`ShellErrorCode::Validation`] errors.
+///
+/// `target` is `argument` or `option` and names the field holding the declared name.
+fn validation_details(
+    command: &str,
+    target: &str,
+    name: &str,
+    expected: &str,
+    value: &str,
+) -> StructuredRecord {
+    StructuredRecord {
+        fields: vec![
+            field_string("command", command.to_string()),
+            field_string(target, name.to_string()),
+            field_string("expected", expected.to_string()),
+            field_string("value", value.to_string()),
+        ],
+    }
+}
+
+/// Assigns positional indices to each declared arg spec, in declaration order.
+///
+/// Optional arguments only take a value when enough remain for the required specs after them,
+/// and a repeatable spec takes everything not reserved for later required specs.
+fn bind_args(specs: &[CommandArgSpec], count: usize) -> Vec<(&CommandArgSpec, Range<usize>)> {
+    let mut cursor = 0usize;
+    specs
+        .iter()
+        .enumerate()
+        .map(|(position, spec)| {
+            let required_after = specs[position + 1..]
+                .iter()
+                .filter(|spec| spec.required)
+                .count();
+            let available = count.saturating_sub(cursor);
+            let len = if spec.repeatable {
+                available.saturating_sub(required_after)
+            } else if available > 0 && (spec.required || available > required_after) {
+                1
+            } else {
+                0
+            };
+            let span = cursor..cursor + len;
+            cursor += len;
+            (spec, span)
+        })
+        .collect()
+}
+
+/// Checks the session caller against the descriptor's scope and required capability.
+///
+/// Sessions without a caller are trusted runtime sessions and skip these checks.
+fn authorize(descriptor: &CommandDescriptor, state: &SessionState) -> Result<(), ShellError> {
+    let Some(caller) = &state.caller else {
+        return Ok(());
+    };
+    let command = descriptor.path.display();
+    let denied = |reason: String, requirement: (&str, String)| {
+        Err(ShellError::new(
+            ShellErrorCode::PermissionDenied,
+            format!("permission denied: {reason}"),
+        )
+        .with_details(StructuredRecord {
+            fields: vec![
+                field_string("command", command.clone()),
+                field_string("caller", caller.app_id.clone()),
+                field_string(requirement.0, requirement.1),
+            ],
+        }))
+    };
+    match &descriptor.scope {
+        CommandScope::App { app_id } if *app_id != caller.app_id => {
+            return denied(
+                format!("`{command}` belongs to app `{app_id}`"),
+                ("scope", scope_label(&descriptor.scope)),
+            );
+        }
+        CommandScope::Window { window_id } if caller.window_id != Some(*window_id) => {
+            return denied(
+                format!("`{command}` belongs to window {window_id}"),
+                ("scope", scope_label(&descriptor.scope)),
+            );
+        }
+        _ => {}
+    }
+    if let Some(capability) = &descriptor.required_capability {
+        let granted = state
+            .capability_check
+            .as_ref()
+            .is_some_and(|check| check(caller, capability));
+        if !granted {
+            return denied(
+                format!(
+                    "`{}` lacks the `{capability}` capability required by `{command}`",
+                    caller.app_id
+                ),
+                ("capability", capability.clone()),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Whether `caller` is in the descriptor's scope and holds its required capability; the quiet
+/// counterpart of [`authorize`] used to filter listings.
+fn caller_may_run(
+    descriptor: &CommandDescriptor,
+    caller: &CommandCaller,
+    capability_check: Option<&CapabilityCheck>,
+) -> bool {
+    let in_scope = match &descriptor.scope {
+        CommandScope::App { app_id } => *app_id == caller.app_id,
+        CommandScope::Window { window_id } => caller.window_id == Some(*window_id),
+        _ => true,
+    };
+    in_scope
+        && descriptor
+            .required_capability
+            .as_ref()
+            .is_none_or(|capability| {
+                capability_check.is_some_and(|check| check(caller, capability))
+            })
+}
+
+/// Checks positional arguments against the descriptor's declared arg specs.
+///
+/// Every required spec must receive a value, and extra positionals are rejected unless a spec is
+/// repeatable.
+fn validate_args(args: &[String], descriptor: &CommandDescriptor) -> Result<(), ShellError> {
+    let command = descriptor.path.display();
+    let usage = &descriptor.help.usage;
+    let bound = bind_args(&descriptor.args, args.len());
+    if let Some((spec, _)) = bound
+        .iter()
+        .find(|(spec, span)| spec.required && span.is_empty())
+    {
+        return Err(ShellError::new(
+            ShellErrorCode::Usage,
+            format!(
+                "missing argument `<{}>` for `{command}`; usage: {usage}",
+                spec.name
+            ),
+        ));
+    }
+    let consumed = bound.last().map_or(0, |(_, span)| span.end);
+    if let Some(extra) = args.get(consumed) {
+        return Err(ShellError::new(
+            ShellErrorCode::Usage,
+            format!("unexpected argument `{extra}` for `{command}`; usage: {usage}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the declared option name closest to `name`, if any is plausibly a typo.
+fn nearest_option<'a>(name: &str, specs: &'a [CommandOptionSpec]) -> Option<&'a str> {
+    let threshold = (name.chars().count() / 3).max(2);
+    specs
+        .iter()
+        .map(|spec| (edit_distance(name, &spec.name), spec.name.as_str()))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    for (row, left_ch) in left.chars().enumerate() {
+        let mut current = vec![row + 1; right.len() + 1];
+        for (column, right_ch) in right.iter().enumerate() {
+            let substitution = previous[column] + usize::from(left_ch != *right_ch);
+            current[column + 1] = substitution
+                .min(previous[column + 1] + 1)
+                .min(current[column] + 1);
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+fn parse_value(raw: &str) -> ParsedValue {
+    let literal = if raw == "null" {
+        ParsedLiteral::Null
+    } else if matches!(raw, "true" | "on") {
+        ParsedLiteral::Bool(true)
+    } else if matches!(raw, "false" | "off") {
+        ParsedLiteral::Bool(false)
+    } else if let Ok(value) = raw.parse::<i64>() {
+        ParsedLiteral::Int(value)
+    } else if let Ok(value) = raw.parse::<f64>() {
+        ParsedLiteral::Float(value)
+    } else {
+        ParsedLiteral::String(raw.to_string())
+    };
+
+    ParsedValue {
+        raw: raw.to_string(),
+        literal,
+    }
+}
+
+/// Expands script positional parameters in one shell line.
+///
+/// Supported forms are `$0` (script path), `$1`..`$9`, `${N}` for any index, `$#` (argument
+/// count), and `$@` (all arguments). Parameters inside single quotes and `\$` escapes are left
+/// untouched. Substituted values are quoted when needed so each argument stays one token; unset
+/// indices expand to nothing.
+pub fn expand_positional_parameters(line: &str, script: &str, args: &[String]) -> String {
+    let lookup = |index: usize| -> Option<&str> {
+        if index == 0 {
+            Some(script)
+        } else {
+            args.get(index - 1).map(String::as_str)
+        }
+    };
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut quote = None::<char>;
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if quote != Some('\'') => {
+                out.push(ch);
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            }
+            '"' | '\'' if quote.is_none() => {
+                quote = Some(ch);
+                out.push(ch);
+            }
+            _ if quote == Some(ch) => {
+                quote = None;
+                out.push(ch);
+            }
+            '$' if quote != Some('\'') => {
+                let in_quotes = quote.is_some();
+                match chars.peek().copied() {
+                    Some(digit @ '0'..='9') => {
+                        chars.next();
+                        let index = digit.to_digit(10).unwrap_or_default() as usize;
+                        out.push_str(&quote_parameter(
+                            lookup(index).unwrap_or_default(),
+                            in_quotes,
+                        ));
+                    }
+                    Some('{') => {
+                        let rest = chars.clone().skip(1).take_while(|ch| *ch != '}');
+                        let digits = rest.collect::<String>();
+                        match digits.parse::<usize>() {
+                            Ok(index) if chars.clone().nth(digits.len() + 1) == Some('}') => {
+                                for _ in 0..digits.len() + 2 {
+                                    chars.next();
+                                }
+                                out.push_str(&quote_parameter(
+                                    lookup(index).unwrap_or_default(),
+                                    in_quotes,
+                                ));
+                            }
+                            _ => out.push(ch),
+                        }
+                    }
+                    Some('#') => {
+                        chars.next();
+                        out.push_str(&args.len().to_string());
+                    }
+                    Some('@') => {
+                        chars.next();
+                        let expanded = args
+                            .iter()
+                            .map(|arg| quote_parameter(arg, in_quotes))
+                            .collect::<Vec<_>>();
+                        out.push_str(&expanded.join(" "));
+                    }
+                    _ => out.push(ch),
+                }
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+fn quote_parameter(value: &str, in_quotes: bool) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    if in_quotes {
+        return escaped;
+    }
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|ch| ch.is_whitespace() || matches!(ch, '"' | '\'' | '\\' | '|'));
+    if needs_quotes && !value.is_empty() {
+        format!("\"{escaped}\"")
+    } else {
+        value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Pipe,
+    Word(String),
+}
+
+fn field_string(name: &str, value: String) -> system_shell_contract::StructuredField {
+    StructuredFieldBuilder::new(
+        name,
+        StructuredValue::Scalar(StructuredScalar::String(value)),
+    )
+    .build()
+}
+
+struct StructuredFieldBuilder {
+    name: String,
+    value: StructuredValue,
+}
+
+impl StructuredFieldBuilder {
+    fn new(name: &str, value: StructuredValue) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    fn build(self) -> system_shell_contract::StructuredField {
+        system_shell_contract::StructuredField {
+            name: self.name,
+            value: self.value,
+        }
+    }
+}
+
+/// Root shell engine used by the runtime.
+///
+/// Create one engine for the runtime, register built-in or app-provided commands on its registry,
+/// then spawn per-window sessions with [`ShellEngine::new_session`]. The engine also owns the
+/// shared [`HistoryStore`], the scheduler that bounds concurrently running pipelines, and the
+/// `history`, `fn`, and `shell sessions` intrinsics that operate on session state.
+#[derive(Clone)]
+pub struct ShellEngine {
+    registry: CommandRegistry,
+    history: HistoryStore,
+    capability_check: Option<CapabilityCheck>,
+    scheduler: ExecutionScheduler,
+    page_budget: PageBudget,
+    explorer_fs: Option<Rc<dyn ExplorerFsService>>,
+    quotas: QuotaTable,
+    event_retention: usize,
+}
+
+impl Default for ShellEngine {
+    fn default() -> Self {
+        let registry = CommandRegistry::default();
+        install_intrinsics(&registry);
+        Self {
+            registry,
+            history: HistoryStore::default(),
+            capability_check: None,
+            scheduler: ExecutionScheduler::new(DEFAULT_EXECUTION_LIMIT),
+            page_budget: PageBudget::default(),
+            explorer_fs: None,
+            quotas: QuotaTable::default(),
+            event_retention: DEFAULT_EVENT_RETENTION,
+        }
+    }
+}
+
+impl ShellEngine {
+    /// Creates a new shared shell engine.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the shared history store, typically with one backed by persistent prefs.
+    pub fn with_history(mut self, history: HistoryStore) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Sets the capability check consulted for commands that declare a required capability.
+    ///
+    /// Without a check, such commands are denied to every session that has a caller.
+    pub fn with_capability_check(mut self, check: CapabilityCheck) -> Self {
+        self.capability_check = Some(check);
+        self
+    }
+
+    /// Bounds how many pipelines may run at once across all sessions; later submissions queue.
+    pub fn with_execution_limit(mut self, limit: usize) -> Self {
+        self.scheduler = ExecutionScheduler::new(limit);
+        self
+    }
+
+    /// Sets how many unacknowledged events new session logs retain before dropping the oldest.
+    pub fn with_event_retention(mut self, retention: usize) -> Self {
+        self.event_retention = retention;
+        self
+    }
+
+    /// Sets the size limits beyond which table and list output is paged.
+    pub fn with_page_budget(mut self, budget: PageBudget) -> Self {
+        self.page_budget = budget;
+        self
+    }
+
+    /// Sets the filesystem listed when completing arguments that opt into path completion.
+    ///
+    /// Without one, such arguments fall back to the command's own completion handler.
+    pub fn with_explorer_fs(mut self, fs: Rc<dyn ExplorerFsService>) -> Self {
+        self.explorer_fs = Some(fs);
+        self
+    }
+
+    /// Limits the executions of commands owned by `app_id`; see [`ShellEngine::set_app_quota`].
+    pub fn with_app_quota(self, app_id: impl Into<String>, quota: AppQuota) -> Self {
+        self.set_app_quota(app_id, quota);
+        self
+    }
+
+    /// Replaces the resource limits applied to commands owned by `app_id` in every session.
+    ///
+    /// A command's owner is its descriptor's `owner_app_id`, or the app of an app-scoped command
+    /// without one.
+    ///
+    /// Executions over a limit fail with [`ShellErrorCode::ResourceExhausted`]. Passing
+    /// `AppQuota::default()` removes the app's limits. The duration limit is checked when the
+    /// handler returns and by [`ShellEngine::enforce_quotas`], which aborts handlers stuck in an
+    /// await.
+    pub fn set_app_quota(&self, app_id: impl Into<String>, quota: AppQuota) {
+        self.quotas.set(app_id.into(), quota);
+    }
+
+    /// Aborts app command executions whose duration limit passed at `now_unix_ms`.
+    ///
+    /// Returns how many executions were aborted. Call this periodically from a host timer.
+    pub fn enforce_quotas(&self, now_unix_ms: u64) -> usize {
+        self.quotas.enforce(now_unix_ms)
+    }
+
+    /// Returns the history store shared by every session.
+    pub fn history(&self) -> HistoryStore {
+        self.history.clone()
+    }
+
+    /// Returns the shared registry.
+    pub fn registry(&self) -> CommandRegistry {
+        self.registry.clone()
+    }
+
+    /// Returns all currently visible command descriptors.
+    pub fn descriptors(&self) -> Vec<CommandDescriptor> {
+        self.registry.descriptors()
+    }
+
+    /// Fuzzy-searches the public commands `caller` may run, best match first.
+    ///
+    /// Each item carries the command path as its value and label and the help summary as its
+    /// detail. The query matches the path the same way completion matches a token, so recently
+    /// used commands rank higher.
+    pub fn search_commands(&self, query: &str, caller: &CommandCaller) -> Vec<CompletionItem> {
+        let items = self
+            .descriptors()
+            .into_iter()
+            .filter(|descriptor| {
+                descriptor.visibility == CommandVisibility::Public
+                    && caller_may_run(descriptor, caller, self.capability_check.as_ref())
+            })
+            .map(|descriptor| {
+                let path = descriptor.path.display();
+                CompletionItem {
+                    value: path.clone(),
+                    label: path,
+                    detail: Some(descriptor.help.summary),
+                    score: 0,
+                    matched: Vec::new(),
+                }
+            })
+            .collect();
+        let history = self.history.entries().get_untracked();
+        rank_completions(items, query.trim(), &history)
+    }
+
+    /// Registers a command and returns a drop-based handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShellErrorCode::Conflict`] error when a command of the same scope precedence
+    /// already claims one of the descriptor's paths or aliases; see [`CommandRegistry::register`].
+    pub fn register_command(
+        &self,
+        descriptor: CommandDescriptor,
+        completion: Option<CompletionHandler>,
+        handler: CommandHandler,
+    ) -> Result<CommandRegistryHandle, ShellError> {
+        let token = self.registry.register(descriptor, completion, handler)?;
+        Ok(CommandRegistryHandle {
+            registry: self.registry.clone(),
+            token,
+            active: Rc::new(Cell::new(true)),
+        })
+    }
+
+    /// Registers execution hooks shared by every session and returns a drop-based handle.
+    pub fn register_middleware(&self, middleware: Rc<dyn ShellMiddleware>) -> MiddlewareHandle {
+        MiddlewareHandle {
+            registry: self.registry.clone(),
+            id: self.registry.add_middleware(middleware),
+            active: Rc::new(Cell::new(true)),
+        }
+    }
+
+    /// Creates one shell session with its own cwd and event stream.
+    ///
+    /// The session has no caller identity, so scope and capability checks do not apply; use
+    /// [`ShellEngine::new_session_for`] for sessions owned by an app window.
+    pub fn new_session(&self, cwd: impl Into<String>) -> ShellSessionHandle {
+        self.session(cwd.into(), None)
+    }
+
+    /// Creates one shell session whose commands are authorized against `caller`.
+    pub fn new_session_for(
+        &self,
+        cwd: impl Into<String>,
+        caller: CommandCaller,
+    ) -> ShellSessionHandle {
+        self.session(cwd.into(), Some(caller))
+    }
+
+    fn session(&self, cwd: String, caller: Option<CommandCaller>) -> ShellSessionHandle {
+        self.session_with(
+            cwd,
+            caller,
+            EventLog::new(self.event_retention),
+            Rc::new(Cell::new(0)),
+            self.history.clone(),
+        )
+    }
+
+    /// Creates a session that writes into `events` and draws ids from `next_execution_id`.
+    ///
+    /// Sessions sharing both form one event log with unique execution ids.
+    fn session_with(
+        &self,
+        cwd: String,
+        caller: Option<CommandCaller>,
+        events: EventLog,
+        next_execution_id: Rc<Cell<u64>>,
+        history: HistoryStore,
+    ) -> ShellSessionHandle {
+        let cwd = create_rw_signal(cwd);
+        let active_execution = create_rw_signal(None);
+        let alive = Rc::new(());
+        let id = self.scheduler.register_session(
+            caller.clone(),
+            cwd.read_only(),
+            active_execution.read_only(),
+            &alive,
+        );
+        let state = SessionState {
+            cwd,
+            events,
+            active_execution,
+            next_execution_id,
+            cancel_flag: Rc::new(Cell::new(false)),
+            functions: create_rw_signal(BTreeMap::new()),
+            variables: create_rw_signal(BTreeMap::new()),
+            history,
+            caller,
+            capability_check: self.capability_check.clone(),
+            id,
+            scheduler: self.scheduler.clone(),
+            pager: Pager::default(),
+            page_budget: self.page_budget,
+            explorer_fs: self.explorer_fs.clone(),
+            prompts: PromptSlot::default(),
+            quotas: self.quotas.clone(),
+            _alive: alive,
+        };
+        ShellSessionHandle {
+            state,
+            registry: self.registry.clone(),
+        }
+    }
 }
 
-impl ShellEngine {
-    /// Creates a new shared shell engine.
-    pub fn new() -> Self {
-        Self::default()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use system_shell_contract::CommandExample;
+
+    fn descriptor(path: &str, aliases: &[&str], scope: CommandScope) -> CommandDescriptor {
+        let path = CommandPath::new(path);
+        let display = path.display();
+        CommandDescriptor {
+            id: CommandId::new(display.clone()),
+            parent_path: path.parent(),
+            path,
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            scope,
+            visibility: CommandVisibility::Public,
+            interaction_kind: CommandInteractionKind::Hierarchical,
+            discoverable_children: true,
+            input_shape: CommandInputShape::none(),
+            output_shape: CommandOutputShape::new(CommandDataShape::Table),
+            args: vec![CommandArgSpec {
+                name: "value".to_string(),
+                summary: "value".to_string(),
+                required: false,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            }],
+            options: vec![CommandOptionSpec {
+                name: "help".to_string(),
+                short: Some('h'),
+                summary: "show help".to_string(),
+                takes_value: false,
+                value_kind: CommandValueKind::Any,
+                choices: Vec::new(),
+            }],
+            required_capability: None,
+            destructive: false,
+            owner_app_id: None,
+            help: HelpDoc {
+                summary: "summary".to_string(),
+                description: None,
+                usage: display.clone(),
+                examples: vec![CommandExample {
+                    command: display,
+                    summary: "example".to_string(),
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn registration_handle_unregisters() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let handle = engine
+            .register_command(
+                descriptor("apps list", &[], CommandScope::Global),
+                None,
+                Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })),
+            )
+            .expect("register command");
+        let generation = engine.registry().generation();
+        assert_eq!(engine.registry.visible_commands().len(), 1);
+        assert_eq!(generation.get_untracked(), 1);
+        let snapshot = engine.registry.snapshot();
+        assert!(Rc::ptr_eq(&snapshot, &engine.registry.snapshot()));
+        handle.unregister();
+        assert_eq!(engine.registry.visible_commands().len(), 0);
+        assert!(!Rc::ptr_eq(&snapshot, &engine.registry.snapshot()));
+        assert_eq!(generation.get_untracked(), 2);
+        drop(handle);
+        assert_eq!(generation.get_untracked(), 2);
+    }
+
+    #[test]
+    fn same_scope_claims_are_rejected_and_scoped_claims_shadow() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let handler = |marker: &'static str| -> CommandHandler {
+            Rc::new(move |_| {
+                Box::pin(async move {
+                    Ok(CommandResult::success(StructuredData::Value(
+                        StructuredValue::Scalar(StructuredScalar::String(marker.to_string())),
+                    )))
+                })
+            })
+        };
+        let _global = engine
+            .register_command(
+                descriptor("apps list", &[], CommandScope::Global),
+                None,
+                handler("global"),
+            )
+            .expect("first claim");
+        let duplicate = engine
+            .register_command(
+                descriptor("apps ls", &["apps list"], CommandScope::Global),
+                None,
+                handler("duplicate"),
+            )
+            .err()
+            .expect("same-scope alias claim");
+        assert_eq!(duplicate.code, ShellErrorCode::Conflict);
+        assert!(duplicate.message.contains("`apps list`"));
+        assert!(engine
+            .register_command(
+                descriptor("history list", &[], CommandScope::Global),
+                None,
+                handler("history"),
+            )
+            .is_err());
+        let _app = engine
+            .register_command(
+                descriptor(
+                    "apps list",
+                    &[],
+                    CommandScope::App {
+                        app_id: "system.explorer".to_string(),
+                    },
+                ),
+                None,
+                handler("app"),
+            )
+            .expect("more specific scope shadows");
+
+        let conflicts = engine.registry().conflicts();
+        assert_eq!(
+            conflicts
+                .iter()
+                .map(|conflict| (
+                    conflict.path.as_str(),
+                    conflict.loser.as_str(),
+                    conflict.resolution
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("apps list", "apps list", ConflictResolution::Shadowed),
+                ("apps list", "apps ls", ConflictResolution::Rejected),
+                ("history list", "history list", ConflictResolution::Rejected),
+            ]
+        );
+        assert_eq!(
+            conflicts[0].winner_scope,
+            CommandScope::App {
+                app_id: "system.explorer".to_string()
+            }
+        );
+
+        let session = engine.new_session("/");
+        let last_value = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            session
+                .events()
+                .get_untracked()
+                .into_iter()
+                .rev()
+                .find_map(|event| match event {
+                    ShellStreamEvent::Data { data, .. } => Some(data),
+                    _ => None,
+                })
+                .expect("data frame")
+        };
+        assert_eq!(
+            last_value("apps list"),
+            StructuredData::Value(StructuredValue::Scalar(StructuredScalar::String(
+                "app".to_string()
+            )))
+        );
+        match last_value("shell conflicts") {
+            StructuredData::Table(table) => assert_eq!(table.rows.len(), 3),
+            other => panic!("expected conflicts table, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn middleware_hooks_wrap_dispatch_and_can_veto() {
+        struct Recorder {
+            label: &'static str,
+            log: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl ShellMiddleware for Recorder {
+            fn before_execute(&self, dispatch: &CommandDispatch) -> Result<(), ShellError> {
+                let path = dispatch.descriptor.path.display();
+                self.log
+                    .borrow_mut()
+                    .push(format!("{} before {path}", self.label));
+                if self.label == "policy" && path == "fail" && dispatch.argv.len() > 1 {
+                    return Err(ShellError::new(
+                        ShellErrorCode::PermissionDenied,
+                        "blocked by policy",
+                    ));
+                }
+                Ok(())
+            }
+
+            fn after_execute(&self, dispatch: &CommandDispatch, result: &CommandResult) {
+                self.log.borrow_mut().push(format!(
+                    "{} after {} {}",
+                    self.label,
+                    dispatch.descriptor.path.display(),
+                    result.exit.code
+                ));
+            }
+
+            fn on_error(&self, dispatch: &CommandDispatch, error: &ShellError) {
+                self.log.borrow_mut().push(format!(
+                    "{} error {} {}",
+                    self.label,
+                    dispatch.descriptor.path.display(),
+                    error.message
+                ));
+            }
+        }
+
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let _ok = engine
+            .register_command(
+                descriptor("ok", &[], CommandScope::Global),
+                None,
+                Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })),
+            )
+            .expect("register command");
+        let _fail = engine
+            .register_command(
+                descriptor("fail", &[], CommandScope::Global),
+                None,
+                Rc::new(|_| {
+                    Box::pin(async { Err(ShellError::new(ShellErrorCode::Internal, "boom")) })
+                }),
+            )
+            .expect("register command");
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let audit = engine.register_middleware(Rc::new(Recorder {
+            label: "audit",
+            log: log.clone(),
+        }));
+        let _policy = engine.register_middleware(Rc::new(Recorder {
+            label: "policy",
+            log: log.clone(),
+        }));
+        let session = engine.new_session("/");
+        let run = |line: &str| {
+            log.borrow_mut().clear();
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            log.borrow().clone()
+        };
+
+        assert_eq!(
+            run("ok"),
+            vec![
+                "audit before ok",
+                "policy before ok",
+                "policy after ok 0",
+                "audit after ok 0"
+            ]
+        );
+        assert_eq!(
+            run("fail"),
+            vec![
+                "audit before fail",
+                "policy before fail",
+                "policy error fail boom",
+                "audit error fail boom"
+            ]
+        );
+        assert_eq!(
+            run("fail now"),
+            vec![
+                "audit before fail",
+                "policy before fail",
+                "policy error fail blocked by policy",
+                "audit error fail blocked by policy"
+            ]
+        );
+        assert_eq!(
+            run("ok extra arg"),
+            vec![
+                "policy error ok unexpected argument `arg` for `ok`; usage: ok",
+                "audit error ok unexpected argument `arg` for `ok`; usage: ok"
+            ]
+        );
+
+        audit.unregister();
+        assert_eq!(run("ok"), vec!["policy before ok", "policy after ok 0"]);
+    }
+
+    #[test]
+    fn caller_sessions_enforce_scope_and_capabilities() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new().with_capability_check(Rc::new(|caller, capability| {
+            caller.app_id == "system.settings" && capability == "config"
+        }));
+        let ok = || -> CommandHandler {
+            Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) }))
+        };
+        let mut config_set = descriptor("config set", &[], CommandScope::Global);
+        config_set.required_capability = Some("config".to_string());
+        let _config = engine
+            .register_command(config_set, None, ok())
+            .expect("register command");
+        let notes_scope = CommandScope::App {
+            app_id: "system.notes".to_string(),
+        };
+        let _notes = engine
+            .register_command(descriptor("notes new", &[], notes_scope), None, ok())
+            .expect("register command");
+        let window_scope = CommandScope::Window { window_id: 7 };
+        let _window = engine
+            .register_command(descriptor("panel", &[], window_scope), None, ok())
+            .expect("register command");
+
+        let exit = |session: &ShellSessionHandle, line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            match session.events().get_untracked().last() {
+                Some(ShellStreamEvent::Completed { summary }) => summary.exit.clone(),
+                _ => panic!("expected completion"),
+            }
+        };
+        let caller = |app_id: &str, window_id: u64| CommandCaller {
+            app_id: app_id.to_string(),
+            window_id: Some(window_id),
+        };
+
+        let settings = engine.new_session_for("/", caller("system.settings", 7));
+        assert_eq!(exit(&settings, "config set").code, 0);
+        assert_eq!(exit(&settings, "panel").code, 0);
+        let denied = exit(&settings, "notes new");
+        assert_eq!(denied.code, 4);
+        assert_eq!(
+            denied.message.as_deref(),
+            Some("permission denied: `notes new` belongs to app `system.notes`")
+        );
+
+        let notes = engine.new_session_for("/", caller("system.notes", 8));
+        assert_eq!(exit(&notes, "notes new").code, 0);
+        assert_eq!(exit(&notes, "panel").code, 4);
+        let denied = exit(&notes, "config set");
+        assert_eq!(
+            denied.message.as_deref(),
+            Some(
+                "permission denied: `system.notes` lacks the `config` capability required by \
+                 `config set`"
+            )
+        );
+        assert_eq!(denied.error, Some(ShellErrorCode::PermissionDenied));
+        assert_eq!(
+            denied.details.expect("permission details").fields,
+            vec![
+                field_string("command", "config set".to_string()),
+                field_string("caller", "system.notes".to_string()),
+                field_string("capability", "config".to_string()),
+            ]
+        );
+        assert_eq!(exit(&notes, "config set --help").code, 0);
+
+        let runtime_session = engine.new_session("/");
+        assert_eq!(exit(&runtime_session, "notes new").code, 0);
+
+        let listed = |caller: CommandCaller| {
+            engine
+                .search_commands("", &caller)
+                .into_iter()
+                .map(|item| item.value)
+                .filter(|path| ["config set", "notes new", "panel"].contains(&path.as_str()))
+                .collect::<Vec<_>>()
+        };
+        // `panel` ran above, so history ranks it first.
+        assert_eq!(
+            listed(caller("system.settings", 7)),
+            vec!["panel", "config set"]
+        );
+        assert_eq!(listed(caller("system.notes", 8)), vec!["notes new"]);
+        let found = engine.search_commands(" nn ", &caller("system.notes", 8));
+        assert_eq!(found[0].label, "notes new");
+        assert_eq!(found[0].matched, vec![0, 6]);
+    }
+
+    #[test]
+    fn sessions_list_reports_live_sessions() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new().with_execution_limit(2);
+        let runtime_session = engine.new_session("/");
+        let terminal = engine.new_session_for(
+            "/docs",
+            CommandCaller {
+                app_id: "system.terminal".to_string(),
+                window_id: Some(3),
+            },
+        );
+        let list = || {
+            runtime_session.submit(ShellRequest {
+                line: "shell sessions list".to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            runtime_session
+                .events()
+                .get_untracked()
+                .into_iter()
+                .rev()
+                .find_map(|event| match event {
+                    ShellStreamEvent::Data {
+                        data: StructuredData::Table(table),
+                        ..
+                    } => Some(table),
+                    _ => None,
+                })
+                .expect("sessions table")
+        };
+        let cell = |record: &StructuredRecord, name: &str| {
+            record
+                .fields
+                .iter()
+                .find(|field| field.name == name)
+                .map(|field| field.value.clone())
+        };
+        let string = |value: &str| {
+            Some(StructuredValue::Scalar(StructuredScalar::String(
+                value.to_string(),
+            )))
+        };
+
+        let table = list();
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(cell(&table.rows[0], "app"), string("runtime"));
+        assert_eq!(cell(&table.rows[0], "status"), string("running"));
+        assert_eq!(cell(&table.rows[1], "app"), string("system.terminal"));
+        assert_eq!(cell(&table.rows[1], "cwd"), string("/docs"));
+        assert_eq!(cell(&table.rows[1], "status"), string("idle"));
+        assert_eq!(
+            cell(&table.rows[1], "window"),
+            Some(StructuredValue::Scalar(StructuredScalar::Int(3)))
+        );
+
+        drop(terminal);
+        assert_eq!(list().rows.len(), 1);
+    }
+
+    #[test]
+    fn handler_output_is_batched_per_stream() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let _scan = engine
+            .register_command(
+                descriptor("scan", &[], CommandScope::Global),
+                None,
+                Rc::new(|context| {
+                    Box::pin(async move {
+                        context.stdout("a\n");
+                        context.stdout("b\n");
+                        context.stderr("skipped /tmp\n");
+                        context.stdout("c\n");
+                        context.flush_output();
+                        context.stdout("d");
+                        context.info("done");
+                        context.stdout("e");
+                        Ok(CommandResult::success(StructuredData::Empty))
+                    })
+                }),
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        session.submit(ShellRequest {
+            line: "scan".to_string(),
+            cwd: "/".to_string(),
+            source_window_id: None,
+        });
+        let events = session.events().get_untracked();
+        let kinds = events
+            .iter()
+            .map(|event| match event {
+                ShellStreamEvent::StdoutChunk { text, .. } => format!("out:{text}"),
+                ShellStreamEvent::StderrChunk { text, .. } => format!("err:{text}"),
+                ShellStreamEvent::Notice { notice, .. } => format!("notice:{}", notice.message),
+                ShellStreamEvent::Started { .. } => "started".to_string(),
+                ShellStreamEvent::Completed { .. } => "completed".to_string(),
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                "started",
+                "out:a\nb\n",
+                "err:skipped /tmp\n",
+                "out:c\n",
+                "out:d",
+                "notice:done",
+                "out:e",
+                "completed"
+            ]
+        );
+
+        let emitter = EventEmitter::new(EventLog::new(DEFAULT_EVENT_RETENTION));
+        emitter.write(ExecutionId(1), OutputStream::Stdout, &"x".repeat(3000));
+        assert!(emitter.events.events().get_untracked().is_empty());
+        emitter.write(ExecutionId(1), OutputStream::Stdout, &"y".repeat(2000));
+        assert_eq!(emitter.events.events().get_untracked().len(), 1);
+        assert!(emitter.pending_output.borrow().is_none());
+    }
+
+    #[test]
+    fn event_listeners_see_events_the_retained_log_drops() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new().with_event_retention(2);
+        let session = engine.new_session("/");
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        session.subscribe_events(Rc::new({
+            let seen = seen.clone();
+            move |event: &ShellStreamEvent| seen.borrow_mut().push(event.clone())
+        }));
+        session.submit(ShellRequest {
+            line: "pwd".to_string(),
+            cwd: "/".to_string(),
+            source_window_id: None,
+        });
+
+        let seen = seen.borrow();
+        assert!(matches!(seen[0], ShellStreamEvent::Started { .. }));
+        assert!(matches!(
+            seen.last(),
+            Some(ShellStreamEvent::Completed { .. })
+        ));
+        let events = session.events().get_untracked();
+        assert_eq!(
+            events[0],
+            ShellStreamEvent::Truncated {
+                dropped: seen.len() as u64 - 2
+            }
+        );
+        assert_eq!(events[1..], seen[seen.len() - 2..]);
+    }
+
+    #[test]
+    fn handlers_execute_nested_lines_within_the_same_execution() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let _echo = engine
+            .register_command(
+                descriptor("echo", &[], CommandScope::Global),
+                None,
+                Rc::new(|context| {
+                    Box::pin(async move {
+                        Ok(CommandResult::success(StructuredData::Value(
+                            StructuredValue::Scalar(StructuredScalar::String(
+                                context.args.join(" "),
+                            )),
+                        )))
+                    })
+                }),
+            )
+            .expect("register command");
+        let _twice = engine
+            .register_command(
+                descriptor("twice", &[], CommandScope::Global),
+                None,
+                Rc::new(|context| {
+                    Box::pin(async move {
+                        let first = context.execute_line("echo one").await;
+                        let second = context.execute_line("echo two").await;
+                        assert_eq!(first.code, 0);
+                        assert_eq!(second.code, 0);
+                        Ok(CommandResult::success(StructuredData::Empty))
+                    })
+                }),
+            )
+            .expect("register command");
+        let _recurse = engine
+            .register_command(
+                descriptor("recurse", &[], CommandScope::Global),
+                None,
+                Rc::new(|context| {
+                    Box::pin(async move {
+                        let exit = context.execute_line("recurse").await;
+                        Ok(CommandResult {
+                            exit,
+                            ..CommandResult::success(StructuredData::Empty)
+                        })
+                    })
+                }),
+            )
+            .expect("register command");
+
+        let session = engine.new_session("/");
+        session.submit(ShellRequest {
+            line: "twice".to_string(),
+            cwd: "/".to_string(),
+            source_window_id: None,
+        });
+        let events = session.events().get_untracked();
+        let data = events
+            .iter()
+            .filter(|event| {
+                matches!(event, ShellStreamEvent::Data { execution_id, .. } if *execution_id == ExecutionId(1))
+            })
+            .count();
+        assert_eq!(data, 2);
+        assert!(matches!(
+            events.last(),
+            Some(ShellStreamEvent::Completed { summary }) if summary.exit.code == 0
+        ));
+
+        session.submit(ShellRequest {
+            line: "recurse".to_string(),
+            cwd: "/".to_string(),
+            source_window_id: None,
+        });
+        let events = session.events().get_untracked();
+        assert!(matches!(
+            events.last(),
+            Some(ShellStreamEvent::Completed { summary }) if summary.exit.code == 2
+        ));
+        assert!(session.active_execution().get_untracked().is_none());
+    }
+
+    #[test]
+    fn positional_parameters_expand_and_stay_single_tokens() {
+        let args = vec!["alpha".to_string(), "two words".to_string()];
+        assert_eq!(
+            expand_positional_parameters("echo $1 $2 $3", "/setup.sh", &args),
+            "echo alpha \"two words\" "
+        );
+        assert_eq!(
+            expand_positional_parameters("run $0 ${2} $# $@", "/setup.sh", &args),
+            "run /setup.sh \"two words\" 2 alpha \"two words\""
+        );
+        assert_eq!(
+            expand_positional_parameters("echo '$1' \\$1 \"x $2\"", "/s", &args),
+            "echo '$1' \\$1 \"x two words\""
+        );
+
+        let parsed =
+            parse_command_line(&expand_positional_parameters("ls $2", "/s", &args)).expect("parse");
+        assert_eq!(parsed.pipeline[0].tokens, vec!["ls", "two words"]);
     }
 
-    /// Returns the shared registry.
-    pub fn registry(&self) -> CommandRegistry {
-        self.registry.clone()
+    #[test]
+    fn declared_flags_do_not_consume_following_values() {
+        let tokens = ["/setup.sh", "--continue", "alpha", "--label", "beta"]
+            .map(str::to_string)
+            .to_vec();
+        let specs = vec![CommandOptionSpec {
+            name: "continue".to_string(),
+            short: None,
+            summary: "keep going".to_string(),
+            takes_value: false,
+            value_kind: CommandValueKind::Any,
+            choices: Vec::new(),
+        }];
+        let (options, _, args) = parse_invocation_arguments(&tokens, &specs);
+        assert_eq!(args, vec!["/setup.sh", "alpha"]);
+        assert_eq!(options[0].name, "continue");
+        assert!(options[0].value.is_none());
+        assert_eq!(options[1].name, "label");
+        assert_eq!(
+            options[1].value.as_ref().map(|value| value.raw.as_str()),
+            Some("beta")
+        );
     }
 
-    /// Returns all currently visible command descriptors.
-    pub fn descriptors(&self) -> Vec<CommandDescriptor> {
-        self.registry.descriptors()
+    #[test]
+    fn destructive_commands_receive_the_dry_run_flag() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let seen = Rc::new(Cell::new(None));
+        let mut purge = descriptor("purge", &[], CommandScope::Global);
+        purge.destructive = true;
+        let _purge = engine
+            .register_command(purge, None, {
+                let seen = seen.clone();
+                Rc::new(move |context| {
+                    seen.set(Some(context.dry_run));
+                    Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })
+                })
+            })
+            .expect("register command");
+        let _keep = engine
+            .register_command(
+                descriptor("keep", &[], CommandScope::Global),
+                None,
+                Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })),
+            )
+            .expect("register command");
+        assert!(engine
+            .descriptors()
+            .iter()
+            .any(|descriptor| descriptor.path.display() == "purge"
+                && descriptor.options.iter().any(|spec| spec.name == "dry-run")));
+
+        let session = engine.new_session("/");
+        let exit = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            match session.events().get_untracked().last() {
+                Some(ShellStreamEvent::Completed { summary }) => summary.exit.clone(),
+                _ => panic!("expected completion"),
+            }
+        };
+        assert_eq!(exit("purge --dry-run").code, 0);
+        assert_eq!(seen.take(), Some(true));
+        assert_eq!(exit("purge").code, 0);
+        assert_eq!(seen.take(), Some(false));
+        let rejected = exit("keep --dry-run");
+        assert_eq!(rejected.error, Some(ShellErrorCode::Usage));
+        assert!(rejected
+            .message
+            .is_some_and(|message| message.contains("unknown option `--dry-run`")));
     }
 
-    /// Registers a command and returns a drop-based handle.
-    pub fn register_command(
-        &self,
-        descriptor: CommandDescriptor,
-        completion: Option<CompletionHandler>,
-        handler: CommandHandler,
-    ) -> CommandRegistryHandle {
-        let token = self.registry.register(descriptor, completion, handler);
-        CommandRegistryHandle {
-            registry: self.registry.clone(),
-            token,
-            active: Rc::new(Cell::new(true)),
-        }
+    #[test]
+    fn app_quotas_cut_off_runaway_output() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new().with_app_quota(
+            "demo",
+            AppQuota {
+                max_output_bytes: Some(16),
+                ..AppQuota::default()
+            },
+        );
+        let stopped = Rc::new(Cell::new(false));
+        let _spam = engine
+            .register_command(
+                descriptor(
+                    "spam",
+                    &[],
+                    CommandScope::App {
+                        app_id: "demo".to_string(),
+                    },
+                ),
+                None,
+                {
+                    let stopped = stopped.clone();
+                    Rc::new(move |context| {
+                        for _ in 0..3 {
+                            context.stdout("0123456789");
+                        }
+                        stopped.set(context.is_cancelled());
+                        Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })
+                    })
+                },
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let submit = || {
+            session.submit(ShellRequest {
+                line: "spam".to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            match session.events().get_untracked().last() {
+                Some(ShellStreamEvent::Completed { summary }) => summary.exit.clone(),
+                _ => panic!("expected completion"),
+            }
+        };
+
+        let exit = submit();
+        assert!(stopped.get());
+        assert_eq!(exit.error, Some(ShellErrorCode::ResourceExhausted));
+        assert_eq!(exit.code, 7);
+        assert!(exit
+            .details
+            .is_some_and(
+                |details| details.fields.iter().any(|field| field.name == "limit"
+                    && field.value
+                        == StructuredValue::Scalar(StructuredScalar::String(
+                            "max-output-bytes".to_string()
+                        )))
+            ));
+        let written = session
+            .events()
+            .get_untracked()
+            .into_iter()
+            .filter_map(|event| match event {
+                ShellStreamEvent::StdoutChunk { text, .. } => Some(text),
+                _ => None,
+            })
+            .collect::<String>();
+        assert_eq!(written, "0123456789");
+
+        engine.set_app_quota("demo", AppQuota::default());
+        assert_eq!(submit().code, 0);
+        assert!(!stopped.get());
     }
 
-    /// Creates one shell session with its own cwd and event stream.
-    pub fn new_session(&self, cwd: impl Into<String>) -> ShellSessionHandle {
-        let cwd = cwd.into();
-        let state = SessionState {
-            cwd: create_rw_signal(cwd),
-            events: create_rw_signal(Vec::new()),
-            active_execution: create_rw_signal(None),
-            next_execution_id: Rc::new(Cell::new(0)),
-            cancel_flag: Rc::new(Cell::new(false)),
+    #[test]
+    fn session_functions_define_resolve_and_remove() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let mut echo = descriptor("echo", &[], CommandScope::Global);
+        echo.args[0].repeatable = true;
+        let _echo = engine
+            .register_command(
+                echo,
+                None,
+                Rc::new(|context| {
+                    Box::pin(async move {
+                        Ok(CommandResult::success(StructuredData::Value(
+                            StructuredValue::Scalar(StructuredScalar::String(
+                                context.args.join(" "),
+                            )),
+                        )))
+                    })
+                }),
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let submit = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            session.events().get_untracked()
         };
-        ShellSessionHandle {
-            state,
-            registry: self.registry.clone(),
-        }
+        let last_exit = |events: &[ShellStreamEvent]| match events.last() {
+            Some(ShellStreamEvent::Completed { summary }) => summary.exit.code,
+            _ => panic!("expected completion"),
+        };
+
+        let events = submit("fn greet { echo hello $1 }");
+        assert_eq!(last_exit(&events), 0);
+        assert!(session.functions().get_untracked().contains_key("greet"));
+
+        let events = submit("greet world");
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ShellStreamEvent::Data {
+                data: StructuredData::Value(StructuredValue::Scalar(StructuredScalar::String(text))),
+                ..
+            } if text == "hello world"
+        )));
+        assert!(matches!(
+            events.last(),
+            Some(ShellStreamEvent::Completed { summary })
+                if summary.command_path == Some(CommandPath::new("greet"))
+        ));
+
+        assert_eq!(last_exit(&submit("fn 9bad { echo }")), 2);
+        assert_eq!(last_exit(&submit("fn loop { loop }")), 0);
+        assert_eq!(last_exit(&submit("loop")), 2);
+
+        assert_eq!(last_exit(&submit("fn remove greet")), 0);
+        assert_eq!(
+            engine.history().search_prefix("greet")[0].line,
+            "greet world"
+        );
+        assert_eq!(last_exit(&submit("greet world")), 3);
+        assert_eq!(engine.registry.visible_commands().len(), 1);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use system_shell_contract::{
-        CommandArgSpec, CommandExample, CommandId, CommandInteractionKind, CommandOptionSpec,
-        CommandOutputShape, HelpDoc,
-    };
+    #[test]
+    fn captured_variables_pass_data_through_and_expand_later() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let mut echo = descriptor("echo", &[], CommandScope::Global);
+        echo.args[0].repeatable = true;
+        let _echo = engine
+            .register_command(
+                echo,
+                None,
+                Rc::new(|context| {
+                    Box::pin(async move {
+                        Ok(CommandResult::success(StructuredData::Value(
+                            StructuredValue::Scalar(StructuredScalar::String(
+                                context.args.join("+"),
+                            )),
+                        )))
+                    })
+                }),
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let submit = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            let events = session.events().get_untracked();
+            let start = events
+                .iter()
+                .rposition(|event| matches!(event, ShellStreamEvent::Started { .. }))
+                .expect("started");
+            events[start..]
+                .iter()
+                .filter_map(|event| match event {
+                    ShellStreamEvent::Data {
+                        data: StructuredData::Value(StructuredValue::Scalar(scalar)),
+                        ..
+                    } => Some(scalar.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let text = |value: &str| StructuredScalar::String(value.to_string());
 
-    fn descriptor(path: &str, aliases: &[&str], scope: CommandScope) -> CommandDescriptor {
-        let path = CommandPath::new(path);
-        let display = path.display();
-        CommandDescriptor {
-            id: CommandId::new(display.clone()),
-            parent_path: path.parent(),
-            path,
-            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
-            scope,
-            visibility: CommandVisibility::Public,
-            interaction_kind: CommandInteractionKind::Hierarchical,
-            discoverable_children: true,
-            input_shape: CommandInputShape::none(),
-            output_shape: CommandOutputShape::new(CommandDataShape::Table),
-            args: vec![CommandArgSpec {
-                name: "value".to_string(),
-                summary: "value".to_string(),
-                required: false,
-                repeatable: false,
-            }],
-            options: vec![CommandOptionSpec {
-                name: "help".to_string(),
-                short: Some('h'),
-                summary: "show help".to_string(),
-                takes_value: false,
-            }],
-            help: HelpDoc {
-                summary: "summary".to_string(),
-                description: None,
-                usage: display.clone(),
-                examples: vec![CommandExample {
-                    command: display,
-                    summary: "example".to_string(),
-                }],
+        assert_eq!(
+            submit("echo two words | capture $greeting"),
+            vec![text("two+words")]
+        );
+        assert_eq!(
+            session.variables().get_untracked().get("greeting"),
+            Some(&StructuredData::Value(StructuredValue::Scalar(text(
+                "two+words"
+            ))))
+        );
+        assert_eq!(
+            submit("echo $greeting '$greeting' ${greeting}!"),
+            vec![text("two+words+$greeting+two+words!")]
+        );
+        assert_eq!(submit("echo $unset x"), vec![text("x")]);
+        session
+            .set_variable(
+                "$picked",
+                StructuredData::Value(StructuredValue::Scalar(text("kept"))),
+            )
+            .expect("set variable");
+        assert_eq!(submit("var $picked"), vec![text("kept")]);
+        assert!(session.set_variable("9bad", StructuredData::Empty).is_err());
+        assert_eq!(submit("var unset"), Vec::new());
+        assert!(matches!(
+            session.events().get_untracked().last(),
+            Some(ShellStreamEvent::Completed { summary }) if summary.exit.code != 0
+        ));
+        assert_eq!(submit("echo | capture 9bad"), vec![text("")]);
+        assert!(matches!(
+            session.events().get_untracked().last(),
+            Some(ShellStreamEvent::Completed { summary }) if summary.exit.code == 2
+        ));
+    }
+
+    #[test]
+    fn option_flags_and_values_complete() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let mut sort = descriptor("data sort", &[], CommandScope::Global);
+        sort.options.extend([
+            CommandOptionSpec {
+                name: "order".to_string(),
+                short: Some('o'),
+                summary: "Sort order.".to_string(),
+                takes_value: true,
+                value_kind: CommandValueKind::String,
+                choices: vec!["asc".to_string(), "desc".to_string()],
+            },
+            CommandOptionSpec {
+                name: "stable".to_string(),
+                short: None,
+                summary: "Keep equal rows in order.".to_string(),
+                takes_value: true,
+                value_kind: CommandValueKind::Bool,
+                choices: Vec::new(),
             },
+        ]);
+        let _sort = engine
+            .register_command(
+                sort,
+                Some(Rc::new(|_| {
+                    Box::pin(async {
+                        Ok(vec![CompletionItem {
+                            value: "column".to_string(),
+                            label: "column".to_string(),
+                            detail: None,
+                            score: 0,
+                            matched: Vec::new(),
+                        }])
+                    })
+                })),
+                Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })),
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let complete = |line: &str| {
+            futures::executor::block_on(session.complete(CompletionRequest {
+                cwd: "/".to_string(),
+                line: line.to_string(),
+                argv: line.split_whitespace().map(str::to_string).collect(),
+                cursor: line.len(),
+                source_window_id: None,
+            }))
+            .expect("completion")
+            .into_iter()
+            .map(|item| item.value)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            complete("data sort --"),
+            vec!["--help", "--order", "--stable"]
+        );
+        assert_eq!(complete("data sort --st"), vec!["--stable"]);
+        assert_eq!(complete("data sort --order "), vec!["asc", "desc"]);
+        assert_eq!(complete("data sort -o d"), vec!["desc"]);
+        assert_eq!(complete("data sort --stable=t"), vec!["--stable=true"]);
+        assert_eq!(complete("data sort --stable true "), vec!["column"]);
+        assert_eq!(complete("data sort -- --"), vec!["column"]);
+        assert_eq!(complete("data sort -- cl"), vec!["column"]);
+
+        let check = |line: &str| {
+            let tokens = line
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            let descriptor = &engine
+                .registry
+                .visible_commands()
+                .into_iter()
+                .next()
+                .expect("sort command")
+                .descriptor;
+            let (options, _, _) = parse_invocation_arguments(&tokens, &descriptor.options);
+            validate_options(&options, descriptor)
+        };
+        assert!(check("--order asc").is_ok());
+        assert!(check("--order sideways")
+            .unwrap_err()
+            .message
+            .contains("expects one of asc, desc"));
+    }
+
+    #[test]
+    fn path_arguments_complete_from_the_explorer_fs() {
+        use platform_host::{
+            ExplorerBackend, ExplorerBackendStatus, ExplorerBatchOp, ExplorerBatchResult,
+            ExplorerChangeCallback, ExplorerEntry, ExplorerEntryKind, ExplorerFileBytesResult,
+            ExplorerFileChunk, ExplorerFileReadResult, ExplorerFsFuture, ExplorerImportFile,
+            ExplorerListPage, ExplorerListResult, ExplorerMetadata, ExplorerPermissionMode,
+            ExplorerPermissionState, ExplorerStorageUsage, ExplorerWatch,
+        };
+
+        struct FakeFs;
+
+        fn unsupported<'a, T: 'a>() -> ExplorerFsFuture<'a, Result<T, String>> {
+            Box::pin(async { Err("unsupported".to_string()) })
+        }
+
+        impl ExplorerFsService for FakeFs {
+            fn status<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+                unsupported()
+            }
+            fn pick_native_directory<'a>(
+                &'a self,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+                unsupported()
+            }
+            fn unmount_native_directory<'a>(
+                &'a self,
+                _path: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+                unsupported()
+            }
+            fn request_permission<'a>(
+                &'a self,
+                _mode: ExplorerPermissionMode,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerPermissionState, String>> {
+                unsupported()
+            }
+            fn list_dir<'a>(
+                &'a self,
+                path: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerListResult, String>> {
+                let entries: &[(&str, ExplorerEntryKind)] = match path {
+                    "/" => &[
+                        ("Projects", ExplorerEntryKind::Directory),
+                        ("Profile.txt", ExplorerEntryKind::File),
+                        ("music", ExplorerEntryKind::Directory),
+                    ],
+                    "/Projects" => &[("demo", ExplorerEntryKind::Directory)],
+                    _ => &[],
+                };
+                let entries = entries
+                    .iter()
+                    .map(|(name, kind)| ExplorerEntry {
+                        name: name.to_string(),
+                        path: format!("{}/{name}", path.trim_end_matches('/')),
+                        kind: *kind,
+                        size: None,
+                        modified_at_unix_ms: None,
+                    })
+                    .collect();
+                Box::pin(async move {
+                    Ok(ExplorerListResult {
+                        cwd: path.to_string(),
+                        backend: ExplorerBackend::IndexedDbVirtual,
+                        permission: ExplorerPermissionState::Virtual,
+                        entries,
+                    })
+                })
+            }
+            fn list_dir_page<'a>(
+                &'a self,
+                _path: &'a str,
+                _offset: usize,
+                _limit: usize,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>> {
+                unsupported()
+            }
+            fn read_text_file<'a>(
+                &'a self,
+                _path: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerFileReadResult, String>> {
+                unsupported()
+            }
+            fn read_file_bytes<'a>(
+                &'a self,
+                _path: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>> {
+                unsupported()
+            }
+            fn read_file_chunk<'a>(
+                &'a self,
+                _path: &'a str,
+                _offset: u64,
+                _len: usize,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> {
+                unsupported()
+            }
+            fn write_text_file<'a>(
+                &'a self,
+                _path: &'a str,
+                _text: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+                unsupported()
+            }
+            fn write_file_bytes<'a>(
+                &'a self,
+                _path: &'a str,
+                _bytes: &'a [u8],
+            ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+                unsupported()
+            }
+            fn write_file_chunk<'a>(
+                &'a self,
+                _path: &'a str,
+                _offset: u64,
+                _bytes: &'a [u8],
+            ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+                unsupported()
+            }
+            fn pick_import_files<'a>(
+                &'a self,
+            ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>> {
+                unsupported()
+            }
+            fn save_download<'a>(
+                &'a self,
+                _name: &'a str,
+                _bytes: &'a [u8],
+            ) -> ExplorerFsFuture<'a, Result<(), String>> {
+                unsupported()
+            }
+            fn create_dir<'a>(
+                &'a self,
+                _path: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+                unsupported()
+            }
+            fn create_file<'a>(
+                &'a self,
+                _path: &'a str,
+                _text: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+                unsupported()
+            }
+            fn delete<'a>(
+                &'a self,
+                _path: &'a str,
+                _recursive: bool,
+            ) -> ExplorerFsFuture<'a, Result<(), String>> {
+                unsupported()
+            }
+            fn rename<'a>(
+                &'a self,
+                _from: &'a str,
+                _to: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+                unsupported()
+            }
+            fn batch<'a>(
+                &'a self,
+                _ops: &'a [ExplorerBatchOp],
+            ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>> {
+                unsupported()
+            }
+            fn stat<'a>(
+                &'a self,
+                _path: &'a str,
+            ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+                unsupported()
+            }
+            fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>> {
+                unsupported()
+            }
+            fn request_persistent_storage<'a>(
+                &'a self,
+            ) -> ExplorerFsFuture<'a, Result<bool, String>> {
+                unsupported()
+            }
+            fn watch(&self, _path: &str, _on_change: ExplorerChangeCallback) -> ExplorerWatch {
+                ExplorerWatch::inactive()
+            }
         }
+
+        reactive::test_runtime();
+        let engine = ShellEngine::new().with_explorer_fs(Rc::new(FakeFs));
+        let handler: CommandHandler =
+            Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) }));
+        let mut go = descriptor("go", &[], CommandScope::Global);
+        go.args[0].completion = CommandArgCompletion::Directory;
+        let mut view = descriptor("view", &[], CommandScope::Global);
+        view.args[0].completion = CommandArgCompletion::Path;
+        view.args[0].repeatable = true;
+        let _go = engine
+            .register_command(go, None, handler.clone())
+            .expect("register command");
+        let _view = engine
+            .register_command(view, None, handler)
+            .expect("register command");
+        let session = engine.new_session("/");
+        let complete = |line: &str| {
+            futures::executor::block_on(session.complete(CompletionRequest {
+                cwd: "/".to_string(),
+                line: line.to_string(),
+                argv: line.split_whitespace().map(str::to_string).collect(),
+                cursor: line.len(),
+                source_window_id: None,
+            }))
+            .expect("completion")
+            .into_iter()
+            .map(|item| item.value)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(complete("go /Pro"), vec!["/Projects"]);
+        assert_eq!(complete("go "), vec!["Projects", "music"]);
+        assert_eq!(complete("go Projects/"), vec!["Projects/demo"]);
+        assert_eq!(complete("view Pro"), vec!["Profile.txt", "Projects"]);
+        assert_eq!(complete("view Profile.txt mu"), vec!["music"]);
+        assert_eq!(complete("view --help"), vec!["--help"]);
     }
 
     #[test]
-    fn registration_handle_unregisters() {
-        let _ = leptos::create_runtime();
+    fn man_renders_manual_sections_for_commands() {
+        reactive::test_runtime();
         let engine = ShellEngine::new();
-        let handle = engine.register_command(
-            descriptor("apps list", &[], CommandScope::Global),
-            None,
-            Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) })),
+        let handler: CommandHandler =
+            Rc::new(|_| Box::pin(async { Ok(CommandResult::success(StructuredData::Empty)) }));
+        let mut set = descriptor("theme set", &["skin"], CommandScope::Global);
+        set.required_capability = Some("theme".to_string());
+        let _set = engine
+            .register_command(set, None, handler.clone())
+            .expect("register command");
+        let _show = engine
+            .register_command(
+                descriptor("theme show", &[], CommandScope::Global),
+                None,
+                handler,
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let submit = |line: &str| {
+            let seen = session.events().get_untracked().len();
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            session.events().get_untracked().split_off(seen)
+        };
+        let page = |events: &[ShellStreamEvent]| {
+            events.iter().find_map(|event| match event {
+                ShellStreamEvent::Data {
+                    data: StructuredData::Record(record),
+                    display: DisplayPreference::Help,
+                    ..
+                } => Some(record.clone()),
+                _ => None,
+            })
+        };
+
+        let manual = page(&submit("man theme set")).expect("manual page");
+        let sections = manual
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sections,
+            vec![
+                "name",
+                "synopsis",
+                "description",
+                "arguments",
+                "options",
+                "aliases",
+                "capability",
+                "examples",
+                "see also"
+            ]
         );
-        assert_eq!(engine.registry.visible_commands().len(), 1);
-        handle.unregister();
-        assert_eq!(engine.registry.visible_commands().len(), 0);
+        assert_eq!(
+            manual.fields[0].value,
+            StructuredValue::Scalar(StructuredScalar::String("theme set - summary".to_string()))
+        );
+        assert_eq!(
+            manual.fields[8].value,
+            StructuredValue::List(vec![StructuredValue::Scalar(StructuredScalar::String(
+                "theme show".to_string()
+            ))])
+        );
+        assert_eq!(page(&submit("theme set --help")), Some(manual.clone()));
+        assert_eq!(page(&submit("man skin")), Some(manual));
+        assert!(submit("man theme").iter().any(|event| matches!(
+            event,
+            ShellStreamEvent::Data {
+                data: StructuredData::Table(_),
+                ..
+            }
+        )));
+        assert!(submit("man theme set extra").iter().any(|event| matches!(
+            event,
+            ShellStreamEvent::Completed { summary } if summary.exit.code == 3
+        )));
+    }
+
+    #[test]
+    fn oversized_output_is_paged_and_continued() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new().with_page_budget(PageBudget {
+            max_rows: 2,
+            max_bytes: DEFAULT_PAGE_BYTES,
+        });
+        let _count = engine
+            .register_command(
+                descriptor("count", &[], CommandScope::Global),
+                None,
+                Rc::new(|_| {
+                    Box::pin(async {
+                        Ok(CommandResult::success(StructuredData::List(
+                            (1..=5)
+                                .map(|value| StructuredValue::Scalar(StructuredScalar::Int(value)))
+                                .collect(),
+                        )))
+                    })
+                }),
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let submit = |line: &str| {
+            let seen = session.events().get_untracked().len();
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            session.events().get_untracked().split_off(seen)
+        };
+        let frame = |events: &[ShellStreamEvent]| {
+            events.iter().find_map(|event| match event {
+                ShellStreamEvent::Data {
+                    data: StructuredData::List(items),
+                    more,
+                    ..
+                } => Some((items.len(), *more)),
+                _ => None,
+            })
+        };
+
+        assert_eq!(
+            frame(&submit("count")),
+            Some((
+                2,
+                Some(PageContinuation {
+                    token: 1,
+                    remaining: 3
+                })
+            ))
+        );
+        assert_eq!(
+            frame(&submit("page next")),
+            Some((
+                2,
+                Some(PageContinuation {
+                    token: 2,
+                    remaining: 1
+                })
+            ))
+        );
+        assert!(submit("page all 1").iter().any(|event| matches!(
+            event,
+            ShellStreamEvent::Completed { summary } if summary.exit.code == 3
+        )));
+        assert_eq!(frame(&submit("page all 2")), Some((1, None)));
+        assert!(frame(&submit("page next")).is_none());
+    }
+
+    #[test]
+    fn function_definitions_parse_only_with_braces() {
+        assert!(parse_function_definition("fn list").is_none());
+        assert!(parse_function_definition("fnord { x }").is_none());
+        assert!(matches!(
+            parse_function_definition("fn a { b"),
+            Some(Err(err)) if err.code == ShellErrorCode::Usage
+        ));
+        let function = parse_function_definition("fn ll { ls -l | data select name }")
+            .expect("definition")
+            .expect("valid");
+        assert_eq!(function.name, "ll");
+        assert_eq!(function.body, "ls -l | data select name");
+    }
+
+    #[test]
+    fn options_are_validated_against_declared_specs() {
+        let mut sort = descriptor("data sort", &[], CommandScope::Global);
+        sort.options = vec![
+            CommandOptionSpec {
+                name: "desc".to_string(),
+                short: Some('d'),
+                summary: "descending".to_string(),
+                takes_value: false,
+                value_kind: CommandValueKind::Any,
+                choices: Vec::new(),
+            },
+            CommandOptionSpec {
+                name: "limit".to_string(),
+                short: Some('n'),
+                summary: "row limit".to_string(),
+                takes_value: true,
+                value_kind: CommandValueKind::Int,
+                choices: Vec::new(),
+            },
+        ];
+        let check = |line: &str| {
+            let tokens = line
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            let (options, _, args) = parse_invocation_arguments(&tokens, &sort.options);
+            validate_options(&options, &sort).map(|()| (options, args))
+        };
+
+        let (options, args) = check("name -d -n 5 -- -x").expect("valid");
+        assert_eq!(args, vec!["name", "-x"]);
+        assert_eq!(options[0].name, "desc");
+        assert_eq!(options[1].name, "limit");
+        assert!(check("size --limit=-3 --help").is_ok());
+        assert_eq!(check("size -5").expect("numeric arg").1, vec!["size", "-5"]);
+
+        let err = check("name --dsc").expect_err("unknown");
+        assert_eq!(err.code, ShellErrorCode::Usage);
+        assert!(
+            err.message.contains("did you mean `--desc`"),
+            "{}",
+            err.message
+        );
+        assert!(!check("name --verbose")
+            .expect_err("unknown")
+            .message
+            .contains("did you mean"));
+        assert!(check("name --limit")
+            .expect_err("missing")
+            .message
+            .contains("requires a value (int)"));
+        let err = check("name --limit many").expect_err("mismatch");
+        assert_eq!(err.code, ShellErrorCode::Validation);
+        assert!(err.message.contains("expects int, got `many`"));
+        assert_eq!(
+            err.details.expect("validation details").fields,
+            vec![
+                field_string("command", "data sort".to_string()),
+                field_string("option", "limit".to_string()),
+                field_string("expected", "int".to_string()),
+                field_string("value", "many".to_string()),
+            ]
+        );
+        assert!(check("name --desc=yes")
+            .expect_err("flag value")
+            .message
+            .contains("does not take a value"));
+    }
+
+    #[test]
+    fn positional_args_are_checked_and_bound_by_spec() {
+        let spec = |name: &str, required: bool, repeatable: bool| CommandArgSpec {
+            name: name.to_string(),
+            summary: name.to_string(),
+            required,
+            repeatable,
+            completion: CommandArgCompletion::None,
+        };
+        let copy = vec![
+            spec("source", true, false),
+            spec("extra", false, true),
+            spec("dest", true, false),
+        ];
+        let spans = |count: usize| {
+            bind_args(&copy, count)
+                .into_iter()
+                .map(|(_, span)| span)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(spans(4), vec![0..1, 1..3, 3..4]);
+        assert_eq!(spans(2), vec![0..1, 1..1, 1..2]);
+        assert_eq!(spans(1), vec![0..1, 1..1, 1..1]);
+
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let calls = Rc::new(Cell::new(0));
+        let mut take = descriptor("take", &[], CommandScope::Global);
+        take.args = vec![spec("count", true, false), spec("label", false, false)];
+        take.help.usage = "take <count> [label]".to_string();
+        let handler_calls = calls.clone();
+        let _take = engine
+            .register_command(
+                take,
+                None,
+                Rc::new(move |context| {
+                    handler_calls.set(handler_calls.get() + 1);
+                    Box::pin(async move {
+                        let count = context.arg::<i64>("count")?.unwrap_or_default();
+                        let label = context.arg::<String>("label")?;
+                        assert!(context.arg::<i64>("missing").is_err());
+                        Ok(CommandResult::success(StructuredData::Value(
+                            StructuredValue::Scalar(StructuredScalar::String(format!(
+                                "{count} {}",
+                                label.unwrap_or_default()
+                            ))),
+                        )))
+                    })
+                }),
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let run = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            session.events().get_untracked()
+        };
+        let error_message = |events: &[ShellStreamEvent]| {
+            events
+                .iter()
+                .rev()
+                .find_map(|event| match event {
+                    ShellStreamEvent::Notice { notice, .. }
+                        if notice.level == CommandNoticeLevel::Error =>
+                    {
+                        Some(notice.message.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default()
+        };
+
+        let events = run("take 3 rows");
+        assert!(events.iter().any(|event| matches!(
+            event,
+            ShellStreamEvent::Data {
+                data: StructuredData::Value(StructuredValue::Scalar(StructuredScalar::String(text))),
+                ..
+            } if text == "3 rows"
+        )));
+        assert!(error_message(&run("take")).contains("missing argument `<count>` for `take`"));
+        assert!(error_message(&run("take 1 rows extra"))
+            .contains("unexpected argument `extra` for `take`; usage: take <count> [label]"));
+        assert_eq!(calls.get(), 1);
+        assert!(error_message(&run("take many")).contains("expects int, got `many`"));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn recursive_delete_confirmations_cancel_in_headless_sessions() {
+        reactive::test_runtime();
+        let engine = ShellEngine::new();
+        let mut delete = descriptor("fs delete", &[], CommandScope::Global);
+        delete.options.push(CommandOptionSpec {
+            name: "recursive".to_string(),
+            short: Some('r'),
+            summary: "delete directories".to_string(),
+            takes_value: false,
+            value_kind: CommandValueKind::Any,
+            choices: Vec::new(),
+        });
+        let deleted = Rc::new(Cell::new(false));
+        let _delete = engine
+            .register_command(
+                delete,
+                None,
+                Rc::new({
+                    let deleted = deleted.clone();
+                    move |context| {
+                        let deleted = deleted.clone();
+                        Box::pin(async move {
+                            let recursive = context
+                                .invocation
+                                .options
+                                .iter()
+                                .any(|option| option.name == "recursive");
+                            if recursive
+                                && !context
+                                    .confirm("Delete /docs and everything inside it? [y/N]")
+                                    .await?
+                            {
+                                return Err(ShellError::new(ShellErrorCode::Cancelled, "kept"));
+                            }
+                            deleted.set(true);
+                            Ok(CommandResult::success(StructuredData::Empty))
+                        })
+                    }
+                }),
+            )
+            .expect("register command");
+        let session = engine.new_session("/");
+        let submit = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: "/".to_string(),
+                source_window_id: None,
+            });
+            session.events().get_untracked()
+        };
+
+        let events = submit("fs delete /docs --recursive");
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, ShellStreamEvent::PromptRequest { .. })));
+        assert!(matches!(
+            events.last(),
+            Some(ShellStreamEvent::Completed { summary }) if summary.exit.code == 130
+        ));
+        assert_eq!(session.active_execution().get_untracked(), None);
+        assert!(!deleted.get());
+
+        assert!(matches!(
+            submit("fs delete /docs").last(),
+            Some(ShellStreamEvent::Completed { summary }) if summary.exit.code == 0
+        ));
+        assert!(deleted.get());
     }
 
     #[test]
@@ -0,0 +1,138 @@
+//! Man-page style help documents built from command descriptors.
+
+use system_shell_contract::{
+    CommandArgSpec, CommandDescriptor, CommandOptionSpec, CommandVisibility, StructuredData,
+    StructuredRecord, StructuredScalar, StructuredValue,
+};
+
+use crate::{field_string, StructuredFieldBuilder};
+
+/// Builds the manual page for `descriptor` as a record with one field per section.
+///
+/// Sections appear in man-page order: `name`, `synopsis`, `description`, then `arguments`,
+/// `options`, `aliases`, `capability`, `examples`, and `see also` when they have content. See-also
+/// entries list public sibling commands from `descriptors` that share the command's parent
+/// namespace.
+pub fn manual_page(
+    descriptor: &CommandDescriptor,
+    descriptors: &[CommandDescriptor],
+) -> StructuredData {
+    let path = descriptor.path.display();
+    let help = &descriptor.help;
+    let mut fields = vec![
+        field_string("name", format!("{path} - {}", help.summary)),
+        field_string(
+            "synopsis",
+            if help.usage.is_empty() {
+                synopsis(&path, &descriptor.args)
+            } else {
+                help.usage.clone()
+            },
+        ),
+        field_string(
+            "description",
+            help.description
+                .clone()
+                .unwrap_or_else(|| help.summary.clone()),
+        ),
+    ];
+    let mut section = |name: &str, entries: Vec<StructuredValue>| {
+        if !entries.is_empty() {
+            fields.push(StructuredFieldBuilder::new(name, StructuredValue::List(entries)).build());
+        }
+    };
+    section(
+        "arguments",
+        descriptor
+            .args
+            .iter()
+            .map(|spec| entry(arg_synopsis(spec), spec.summary.clone()))
+            .collect(),
+    );
+    section(
+        "options",
+        descriptor
+            .options
+            .iter()
+            .map(|spec| entry(option_synopsis(spec), spec.summary.clone()))
+            .collect(),
+    );
+    section("aliases", descriptor.aliases.iter().map(text).collect());
+    section(
+        "capability",
+        descriptor
+            .required_capability
+            .iter()
+            .map(|capability| text(format!("requires the `{capability}` app capability")))
+            .collect(),
+    );
+    section(
+        "examples",
+        help.examples
+            .iter()
+            .map(|example| entry(example.command.clone(), example.summary.clone()))
+            .collect(),
+    );
+    let mut siblings = descriptor
+        .parent_path
+        .as_ref()
+        .map(|parent| {
+            descriptors
+                .iter()
+                .filter(|other| {
+                    other.parent_path.as_ref() == Some(parent)
+                        && other.path != descriptor.path
+                        && other.visibility == CommandVisibility::Public
+                })
+                .map(|other| other.path.display())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    siblings.sort();
+    siblings.dedup();
+    section("see also", siblings.into_iter().map(text).collect());
+    StructuredData::Record(StructuredRecord { fields })
+}
+
+/// Generates a synopsis from declared arguments for descriptors without a usage string.
+fn synopsis(path: &str, args: &[CommandArgSpec]) -> String {
+    std::iter::once(path.to_string())
+        .chain(args.iter().map(arg_synopsis))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn arg_synopsis(spec: &CommandArgSpec) -> String {
+    let ellipsis = if spec.repeatable { "..." } else { "" };
+    if spec.required {
+        format!("<{}{ellipsis}>", spec.name)
+    } else {
+        format!("[{}{ellipsis}]", spec.name)
+    }
+}
+
+fn option_synopsis(spec: &CommandOptionSpec) -> String {
+    let mut flag = match spec.short {
+        Some(short) => format!("-{short}, --{}", spec.name),
+        None => format!("--{}", spec.name),
+    };
+    if !spec.takes_value {
+        return flag;
+    }
+    if spec.choices.is_empty() {
+        flag.push_str(&format!(" <{}>", spec.value_kind.label()));
+    } else {
+        flag.push_str(&format!(" <{}>", spec.choices.join("|")));
+    }
+    flag
+}
+
+fn text(value: impl Into<String>) -> StructuredValue {
+    StructuredValue::Scalar(StructuredScalar::String(value.into()))
+}
+
+fn entry(term: String, summary: String) -> StructuredValue {
+    StructuredValue::Record(StructuredRecord {
+        fields: vec![field_string("term", term), field_string("summary", summary)],
+    })
+}
@@ -0,0 +1,55 @@
+//! Execution interception hooks registered on [`crate::ShellEngine`].
+
+use system_shell_contract::{
+    CommandCaller, CommandDescriptor, CommandResult, ExecutionId, ShellError,
+};
+
+/// Resolved command dispatch passed to every [`ShellMiddleware`] hook.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandDispatch {
+    /// Execution identifier shared by every stage and nested line of one submission.
+    pub execution_id: ExecutionId,
+    /// Descriptor of the resolved command.
+    pub descriptor: CommandDescriptor,
+    /// Full token vector for the pipeline stage.
+    pub argv: Vec<String>,
+    /// Logical cwd at dispatch time.
+    pub cwd: String,
+    /// Optional source window identifier.
+    pub source_window_id: Option<u64>,
+    /// App window identity bound to the session, if any.
+    pub caller: Option<CommandCaller>,
+    /// Nesting level; `0` for stages typed at the prompt, higher for script and function lines.
+    pub depth: usize,
+    /// Whether a destructive command was invoked with `--dry-run`.
+    pub dry_run: bool,
+}
+
+/// Interception point around command handler dispatch.
+///
+/// Hooks run for every resolved leaf command, including stages nested inside scripts and
+/// functions. `before_execute` hooks run in registration order once the caller has been
+/// authorized and arguments, options, and pipeline input have been validated; returning an error
+/// stops dispatch and is reported like a handler failure. `after_execute` and `on_error` hooks run
+/// in reverse registration order.
+pub trait ShellMiddleware {
+    /// Runs before the handler; an error vetoes the dispatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error reported to the session instead of running the handler.
+    fn before_execute(&self, dispatch: &CommandDispatch) -> Result<(), ShellError> {
+        let _ = dispatch;
+        Ok(())
+    }
+
+    /// Runs after the handler returned a result, including results with a non-zero exit.
+    fn after_execute(&self, dispatch: &CommandDispatch, result: &CommandResult) {
+        let _ = (dispatch, result);
+    }
+
+    /// Runs when authorization, validation, a `before_execute` hook, or the handler failed.
+    fn on_error(&self, dispatch: &CommandDispatch, error: &ShellError) {
+        let _ = (dispatch, error);
+    }
+}
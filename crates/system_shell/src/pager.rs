@@ -0,0 +1,232 @@
+//! Session pager that splits oversized table and list results into continuable pages.
+
+use std::{cell::RefCell, rc::Rc};
+
+use system_shell_contract::{
+    DisplayPreference, PageContinuation, ShellError, ShellErrorCode, StructuredData,
+    StructuredTable,
+};
+
+/// Default maximum number of rows or list items emitted in one data frame.
+pub const DEFAULT_PAGE_ROWS: usize = 200;
+
+/// Default maximum serialized size, in bytes, of the rows emitted in one data frame.
+pub const DEFAULT_PAGE_BYTES: usize = 64 * 1024;
+
+/// Size limits applied to table and list output before it reaches the session event log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageBudget {
+    /// Maximum rows or list items per frame.
+    pub max_rows: usize,
+    /// Maximum serialized bytes of the rows or items per frame; a frame always holds one row.
+    pub max_bytes: usize,
+}
+
+impl Default for PageBudget {
+    fn default() -> Self {
+        Self {
+            max_rows: DEFAULT_PAGE_ROWS,
+            max_bytes: DEFAULT_PAGE_BYTES,
+        }
+    }
+}
+
+struct PendingPage {
+    token: u64,
+    data: StructuredData,
+    display: DisplayPreference,
+}
+
+#[derive(Default)]
+struct PagerState {
+    next_token: u64,
+    pending: Option<PendingPage>,
+}
+
+/// Withheld output of one session, continued by `page next` and `page all`.
+#[derive(Clone, Default)]
+pub(crate) struct Pager {
+    state: Rc<RefCell<PagerState>>,
+}
+
+impl Pager {
+    /// Returns the first page of `data` and stores the rest under a fresh continuation token.
+    ///
+    /// Output within `budget` is returned unchanged and leaves any earlier pending pages intact.
+    pub(crate) fn paginate(
+        &self,
+        data: StructuredData,
+        display: DisplayPreference,
+        budget: PageBudget,
+    ) -> (StructuredData, Option<PageContinuation>) {
+        let (page, rest) = split_page(data, budget);
+        let Some((rest, remaining)) = rest else {
+            return (page, None);
+        };
+        let mut state = self.state.borrow_mut();
+        state.next_token = state.next_token.saturating_add(1);
+        let token = state.next_token;
+        state.pending = Some(PendingPage {
+            token,
+            data: rest,
+            display,
+        });
+        (page, Some(PageContinuation { token, remaining }))
+    }
+
+    /// Removes the pending output, checking `token` against it when provided.
+    pub(crate) fn take(
+        &self,
+        token: Option<u64>,
+    ) -> Result<(StructuredData, DisplayPreference), ShellError> {
+        let mut state = self.state.borrow_mut();
+        let Some(pending) = state.pending.take() else {
+            return Err(ShellError::new(
+                ShellErrorCode::NotFound,
+                "no paged output to continue",
+            ));
+        };
+        if let Some(token) = token.filter(|token| *token != pending.token) {
+            let current = pending.token;
+            state.pending = Some(pending);
+            return Err(ShellError::new(
+                ShellErrorCode::NotFound,
+                format!("page token {token} has expired; the current token is {current}"),
+            ));
+        }
+        Ok((pending.data, pending.display))
+    }
+}
+
+/// Splits tables and lists that exceed `budget`, returning the remainder and its row count.
+///
+/// Other shapes cannot be split by row and are returned whole.
+fn split_page(
+    data: StructuredData,
+    budget: PageBudget,
+) -> (StructuredData, Option<(StructuredData, usize)>) {
+    match data {
+        StructuredData::Table(mut table) => {
+            let len = page_len(
+                table
+                    .rows
+                    .iter()
+                    .map(|row| serde_json::to_string(row).map_or(0, |raw| raw.len())),
+                budget,
+            );
+            if len == table.rows.len() {
+                return (StructuredData::Table(table), None);
+            }
+            let rest = table.rows.split_off(len);
+            let remaining = rest.len();
+            table.fallback_text = None;
+            let rest = StructuredTable {
+                columns: table.columns.clone(),
+                rows: rest,
+                schema: table.schema.clone(),
+                source_command: table.source_command.clone(),
+                fallback_text: None,
+            };
+            (
+                StructuredData::Table(table),
+                Some((StructuredData::Table(rest), remaining)),
+            )
+        }
+        StructuredData::List(mut items) => {
+            let len = page_len(
+                items
+                    .iter()
+                    .map(|item| serde_json::to_string(item).map_or(0, |raw| raw.len())),
+                budget,
+            );
+            if len == items.len() {
+                return (StructuredData::List(items), None);
+            }
+            let rest = items.split_off(len);
+            let remaining = rest.len();
+            (
+                StructuredData::List(items),
+                Some((StructuredData::List(rest), remaining)),
+            )
+        }
+        other => (other, None),
+    }
+}
+
+/// Counts the leading items, given their serialized sizes, that fit in `budget`.
+///
+/// The first item is always admitted so a single oversized row still makes progress.
+fn page_len(sizes: impl Iterator<Item = usize>, budget: PageBudget) -> usize {
+    let max_rows = budget.max_rows.max(1);
+    let mut bytes = 0usize;
+    let mut len = 0usize;
+    for size in sizes {
+        bytes = bytes.saturating_add(size);
+        if len == max_rows || (len > 0 && bytes > budget.max_bytes) {
+            break;
+        }
+        len += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use system_shell_contract::{StructuredScalar, StructuredValue};
+
+    use super::*;
+
+    fn list(len: usize) -> StructuredData {
+        StructuredData::List(
+            (0..len)
+                .map(|index| StructuredValue::Scalar(StructuredScalar::Int(index as i64)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn oversized_lists_page_by_rows_and_bytes() {
+        let pager = Pager::default();
+        let budget = PageBudget {
+            max_rows: 3,
+            max_bytes: usize::MAX,
+        };
+
+        let (page, more) = pager.paginate(list(2), DisplayPreference::Auto, budget);
+        assert_eq!(page, list(2));
+        assert_eq!(more, None);
+        assert!(pager.take(None).is_err());
+
+        let (page, more) = pager.paginate(list(5), DisplayPreference::Auto, budget);
+        assert_eq!(page, list(3));
+        assert_eq!(
+            more,
+            Some(PageContinuation {
+                token: 1,
+                remaining: 2
+            })
+        );
+        assert_eq!(
+            pager.take(Some(7)).unwrap_err().code,
+            ShellErrorCode::NotFound
+        );
+        let (rest, _) = pager.take(Some(1)).expect("pending page");
+        assert_eq!(
+            rest,
+            StructuredData::List(
+                [3, 4]
+                    .map(|index| StructuredValue::Scalar(StructuredScalar::Int(index)))
+                    .to_vec()
+            )
+        );
+        assert!(pager.take(None).is_err());
+
+        let tight = PageBudget {
+            max_rows: 100,
+            max_bytes: 1,
+        };
+        let (page, more) = pager.paginate(list(3), DisplayPreference::Auto, tight);
+        assert_eq!(page, list(1));
+        assert_eq!(more.map(|more| more.remaining), Some(2));
+    }
+}
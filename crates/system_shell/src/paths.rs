@@ -0,0 +1,76 @@
+//! Filesystem path completion backed by the explorer filesystem service.
+
+use std::rc::Rc;
+
+use platform_host::{ExplorerEntryKind, ExplorerFsService};
+pub use platform_paths::resolve_path;
+use system_shell_contract::{
+    CommandArgCompletion, CompletionItem, CompletionRequest, ShellError, ShellErrorCode,
+    StructuredRecord,
+};
+
+use crate::{
+    field_string,
+    ranking::{completion_token, fuzzy_match},
+    CompletionHandler,
+};
+
+/// Builds a completion handler that lists paths through `fs`, relative to the request cwd.
+///
+/// The token under the cursor is split at its last `/`: the directory part is resolved against
+/// the session cwd and listed, and entries whose names fuzzily match the remainder are offered.
+/// Values keep the typed directory prefix, so `docs/no` completes to `docs/notes`.
+/// [`CommandArgCompletion::Directory`] offers directories only, and
+/// [`CommandArgCompletion::None`] offers nothing.
+pub fn path_completion(
+    fs: Rc<dyn ExplorerFsService>,
+    kind: CommandArgCompletion,
+) -> CompletionHandler {
+    Rc::new(move |request: CompletionRequest| {
+        let fs = fs.clone();
+        Box::pin(async move {
+            if kind == CommandArgCompletion::None {
+                return Ok(Vec::new());
+            }
+            let token = completion_token(&request.line);
+            let (typed_dir, leaf) = match token.rfind('/') {
+                Some(index) => token.split_at(index + 1),
+                None => ("", token),
+            };
+            let dir = if typed_dir.is_empty() {
+                request.cwd.clone()
+            } else {
+                resolve_path(&request.cwd, typed_dir)
+            };
+            let listing = fs.list_dir(&dir).await.map_err(|err| {
+                ShellError::new(ShellErrorCode::Filesystem, err).with_details(StructuredRecord {
+                    fields: vec![
+                        field_string("operation", "list".to_string()),
+                        field_string("path", dir.clone()),
+                    ],
+                })
+            })?;
+            let mut items = listing
+                .entries
+                .into_iter()
+                .filter(|entry| {
+                    (kind == CommandArgCompletion::Path
+                        || entry.kind == ExplorerEntryKind::Directory)
+                        && fuzzy_match(leaf, &entry.name).is_some()
+                })
+                .map(|entry| CompletionItem {
+                    value: format!("{typed_dir}{}", entry.name),
+                    detail: Some(match entry.kind {
+                        ExplorerEntryKind::File => "file".to_string(),
+                        ExplorerEntryKind::Directory => "dir".to_string(),
+                    }),
+                    label: entry.name,
+                    score: 0,
+                    matched: Vec::new(),
+                })
+                .collect::<Vec<_>>();
+            items.sort_by(|left, right| left.label.cmp(&right.label));
+            Ok(items)
+        })
+    })
+}
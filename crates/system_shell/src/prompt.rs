@@ -0,0 +1,101 @@
+//! Outstanding interactive prompts raised by handlers mid-execution.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use futures::channel::oneshot;
+use system_shell_contract::ExecutionId;
+
+struct PendingPrompt {
+    execution_id: ExecutionId,
+    answer: oneshot::Sender<Option<String>>,
+}
+
+/// The single prompt a session's foreground execution may be waiting on.
+///
+/// Opening a prompt dismisses any earlier one, and dismissing drops the sender so the waiting
+/// handler observes the prompt as cancelled. Prompts only open once a responder has attached;
+/// headless sessions such as scheduled jobs never answer.
+#[derive(Clone, Default)]
+pub(crate) struct PromptSlot {
+    pending: Rc<RefCell<Option<PendingPrompt>>>,
+    responder: Rc<Cell<bool>>,
+}
+
+impl PromptSlot {
+    /// Records that a host will answer this session's prompts.
+    pub(crate) fn attach_responder(&self) {
+        self.responder.set(true);
+    }
+
+    /// Returns whether a host has promised to answer this session's prompts.
+    pub(crate) fn has_responder(&self) -> bool {
+        self.responder.get()
+    }
+
+    /// Opens a prompt for `execution_id` and returns the receiver its answer arrives on.
+    pub(crate) fn open(&self, execution_id: ExecutionId) -> oneshot::Receiver<Option<String>> {
+        let (answer, receiver) = oneshot::channel();
+        *self.pending.borrow_mut() = Some(PendingPrompt {
+            execution_id,
+            answer,
+        });
+        receiver
+    }
+
+    /// Delivers `answer` to the prompt of `execution_id`; `None` dismisses it.
+    ///
+    /// Returns `false` when that execution has no open prompt.
+    pub(crate) fn respond(&self, execution_id: ExecutionId, answer: Option<String>) -> bool {
+        let mut pending = self.pending.borrow_mut();
+        if pending
+            .as_ref()
+            .is_none_or(|prompt| prompt.execution_id != execution_id)
+        {
+            return false;
+        }
+        pending
+            .take()
+            .is_some_and(|prompt| prompt.answer.send(answer).is_ok())
+    }
+
+    /// Dismisses the open prompt, if any.
+    pub(crate) fn dismiss(&self) {
+        self.pending.borrow_mut().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_reach_only_the_prompting_execution() {
+        let slot = PromptSlot::default();
+        let first = slot.open(ExecutionId(1));
+        let second = slot.open(ExecutionId(2));
+        assert_eq!(futures::executor::block_on(first), Err(oneshot::Canceled));
+        assert!(!slot.respond(ExecutionId(1), Some("y".to_string())));
+        assert!(slot.respond(ExecutionId(2), Some("y".to_string())));
+        assert_eq!(
+            futures::executor::block_on(second),
+            Ok(Some("y".to_string()))
+        );
+        assert!(!slot.respond(ExecutionId(2), None));
+
+        let dismissed = slot.open(ExecutionId(3));
+        slot.dismiss();
+        assert!(futures::executor::block_on(dismissed).is_err());
+    }
+
+    #[test]
+    fn clones_share_the_attached_responder() {
+        let slot = PromptSlot::default();
+        let shared = slot.clone();
+        assert!(!slot.has_responder());
+        shared.attach_responder();
+        assert!(slot.has_responder());
+    }
+}
@@ -0,0 +1,391 @@
+//! Per-app resource limits enforced on executions of the commands each app registers.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use futures::future::{AbortHandle, AbortRegistration, Abortable, LocalBoxFuture};
+use platform_host::unix_time_ms_now;
+use system_shell_contract::{
+    CommandDescriptor, CommandResult, CommandScope, ShellError, ShellErrorCode, StructuredData,
+    StructuredRecord, StructuredScalar, StructuredValue,
+};
+
+use crate::{field_string, StructuredFieldBuilder};
+
+/// Resource limits for the commands one app registers; `None` leaves a limit unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AppQuota {
+    /// Executions of the app's commands that may run at once across all sessions.
+    pub max_concurrent: Option<usize>,
+    /// Bytes one execution may stream through `stdout`/`stderr` plus return as data.
+    pub max_output_bytes: Option<usize>,
+    /// Wall-clock milliseconds one execution may run.
+    pub max_duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum QuotaLimit {
+    Concurrent,
+    OutputBytes,
+    DurationMs,
+}
+
+impl QuotaLimit {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Concurrent => "max-concurrent",
+            Self::OutputBytes => "max-output-bytes",
+            Self::DurationMs => "max-duration-ms",
+        }
+    }
+}
+
+/// An admitted execution still holding one of its app's concurrency slots.
+struct RunningExecution {
+    slot: u64,
+    deadline_unix_ms: Option<u64>,
+    quota: Rc<ExecutionQuota>,
+}
+
+#[derive(Default)]
+struct QuotaState {
+    quotas: HashMap<String, AppQuota>,
+    running: Vec<RunningExecution>,
+    next_slot: u64,
+}
+
+/// Configured app quotas and the executions currently admitted under them.
+#[derive(Clone, Default)]
+pub(crate) struct QuotaTable {
+    state: Rc<RefCell<QuotaState>>,
+}
+
+impl QuotaTable {
+    /// Replaces the quota of `app_id`; an unbounded quota removes it.
+    pub(crate) fn set(&self, app_id: String, quota: AppQuota) {
+        let mut state = self.state.borrow_mut();
+        if quota == AppQuota::default() {
+            state.quotas.remove(&app_id);
+        } else {
+            state.quotas.insert(app_id, quota);
+        }
+    }
+
+    /// Admits one execution of `descriptor`, or returns `None` when its app has no quota.
+    ///
+    /// Executions are charged to the descriptor's owner app, falling back to the app of an
+    /// app-scoped command, so window-scoped registrations count against their app too.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShellErrorCode::ResourceExhausted`] error when the app already runs its
+    /// maximum number of concurrent executions.
+    pub(crate) fn admit(
+        &self,
+        descriptor: &CommandDescriptor,
+    ) -> Result<Option<Admission>, ShellError> {
+        let Some(app_id) = command_owner(descriptor) else {
+            return Ok(None);
+        };
+        let mut state = self.state.borrow_mut();
+        let Some(quota) = state.quotas.get(app_id).copied() else {
+            return Ok(None);
+        };
+        if let Some(max) = quota.max_concurrent {
+            let running = state
+                .running
+                .iter()
+                .filter(|running| running.quota.app_id == app_id)
+                .count();
+            if running >= max {
+                return Err(exhausted(
+                    app_id,
+                    QuotaLimit::Concurrent,
+                    max as u64,
+                    format!(
+                        "app `{app_id}` is already running {running} of {max} allowed executions"
+                    ),
+                ));
+            }
+        }
+        let started_at_unix_ms = unix_time_ms_now();
+        let (abort, registration) = AbortHandle::new_pair();
+        let execution = Rc::new(ExecutionQuota {
+            app_id: app_id.to_string(),
+            quota,
+            started_at_unix_ms,
+            output_bytes: Cell::new(0),
+            breach: RefCell::new(None),
+            abort,
+        });
+        state.next_slot = state.next_slot.saturating_add(1);
+        let slot = state.next_slot;
+        state.running.push(RunningExecution {
+            slot,
+            deadline_unix_ms: quota
+                .max_duration_ms
+                .map(|max| started_at_unix_ms.saturating_add(max)),
+            quota: execution.clone(),
+        });
+        Ok(Some(Admission {
+            slot: AdmittedSlot {
+                table: self.clone(),
+                slot,
+            },
+            execution,
+            registration,
+        }))
+    }
+
+    /// Aborts admitted executions whose duration limit passed at `now_unix_ms`.
+    ///
+    /// Returns how many executions were aborted.
+    pub(crate) fn enforce(&self, now_unix_ms: u64) -> usize {
+        let overdue = self
+            .state
+            .borrow()
+            .running
+            .iter()
+            .filter(|running| {
+                running
+                    .deadline_unix_ms
+                    .is_some_and(|deadline| deadline <= now_unix_ms)
+                    && !running.quota.breached()
+            })
+            .map(|running| running.quota.clone())
+            .collect::<Vec<_>>();
+        for quota in &overdue {
+            quota.exceed_duration();
+        }
+        overdue.len()
+    }
+}
+
+/// Usage of one admitted execution against its app quota.
+pub(crate) struct ExecutionQuota {
+    app_id: String,
+    quota: AppQuota,
+    started_at_unix_ms: u64,
+    output_bytes: Cell<usize>,
+    breach: RefCell<Option<ShellError>>,
+    abort: AbortHandle,
+}
+
+impl ExecutionQuota {
+    /// Charges `bytes` of output and returns whether they may still be emitted.
+    ///
+    /// Exceeding the output limit records a breach and aborts the handler at its next await.
+    pub(crate) fn charge_output(&self, bytes: usize) -> bool {
+        if self.breached() {
+            return false;
+        }
+        let total = self.output_bytes.get().saturating_add(bytes);
+        self.output_bytes.set(total);
+        if let Some(max) = self.quota.max_output_bytes.filter(|max| total > *max) {
+            self.breach(QuotaLimit::OutputBytes, max as u64, "bytes of output");
+            return false;
+        }
+        true
+    }
+
+    /// Returns whether a limit was exceeded, after which the handler should stop working.
+    pub(crate) fn breached(&self) -> bool {
+        self.breach.borrow().is_some()
+    }
+
+    /// Records that the execution outlived its duration limit and aborts it.
+    fn exceed_duration(&self) {
+        let max = self.quota.max_duration_ms.unwrap_or_default();
+        self.breach(QuotaLimit::DurationMs, max, "ms of run time");
+    }
+
+    fn breach(&self, limit: QuotaLimit, max: u64, unit: &str) {
+        if self.breached() {
+            return;
+        }
+        *self.breach.borrow_mut() = Some(exhausted(
+            &self.app_id,
+            limit,
+            max,
+            format!("app `{}` exceeded its limit of {max} {unit}", self.app_id),
+        ));
+        self.abort.abort();
+    }
+
+    /// Applies the duration and data-size limits to a finished handler outcome.
+    fn settle(
+        &self,
+        handled: Result<CommandResult, ShellError>,
+    ) -> Result<CommandResult, ShellError> {
+        let elapsed = unix_time_ms_now().saturating_sub(self.started_at_unix_ms);
+        if self.quota.max_duration_ms.is_some_and(|max| elapsed > max) {
+            self.exceed_duration();
+        }
+        if let Ok(result) = &handled {
+            if !matches!(result.output, StructuredData::Empty) {
+                let bytes = serde_json::to_string(&result.output).map_or(0, |raw| raw.len());
+                self.charge_output(bytes);
+            }
+        }
+        match self.breach.borrow().clone() {
+            Some(err) => Err(err),
+            None => handled,
+        }
+    }
+}
+
+/// Removes an admitted execution from its app's running set when dropped.
+struct AdmittedSlot {
+    table: QuotaTable,
+    slot: u64,
+}
+
+impl Drop for AdmittedSlot {
+    fn drop(&mut self) {
+        self.table
+            .state
+            .borrow_mut()
+            .running
+            .retain(|running| running.slot != self.slot);
+    }
+}
+
+/// One execution admitted under its app quota, ready to run its handler.
+pub(crate) struct Admission {
+    slot: AdmittedSlot,
+    execution: Rc<ExecutionQuota>,
+    registration: AbortRegistration,
+}
+
+impl Admission {
+    /// Returns the usage tracker handed to the handler's execution context.
+    pub(crate) fn execution(&self) -> Rc<ExecutionQuota> {
+        self.execution.clone()
+    }
+
+    /// Runs `handler`, aborting it on a breach and failing the outcome when any limit was exceeded.
+    pub(crate) async fn run(
+        self,
+        handler: LocalBoxFuture<'static, Result<CommandResult, ShellError>>,
+    ) -> Result<CommandResult, ShellError> {
+        let Self {
+            slot,
+            execution,
+            registration,
+        } = self;
+        let handled = Abortable::new(handler, registration)
+            .await
+            .unwrap_or_else(|_| {
+                Err(ShellError::new(
+                    ShellErrorCode::Cancelled,
+                    "execution aborted",
+                ))
+            });
+        drop(slot);
+        execution.settle(handled)
+    }
+}
+
+/// Returns the app whose quota an execution of `descriptor` is charged to.
+fn command_owner(descriptor: &CommandDescriptor) -> Option<&str> {
+    descriptor
+        .owner_app_id
+        .as_deref()
+        .or(match &descriptor.scope {
+            CommandScope::App { app_id } => Some(app_id.as_str()),
+            _ => None,
+        })
+}
+
+fn exhausted(app_id: &str, limit: QuotaLimit, max: u64, message: String) -> ShellError {
+    ShellError::new(ShellErrorCode::ResourceExhausted, message).with_details(StructuredRecord {
+        fields: vec![
+            field_string("app", app_id.to_string()),
+            field_string("limit", limit.as_str().to_string()),
+            StructuredFieldBuilder::new(
+                "max",
+                StructuredValue::Scalar(StructuredScalar::Int(max as i64)),
+            )
+            .build(),
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_command(app_id: &str) -> CommandDescriptor {
+        CommandDescriptor {
+            scope: CommandScope::App {
+                app_id: app_id.to_string(),
+            },
+            ..crate::intrinsic_descriptor("work", "work", "Works.", Vec::new())
+        }
+    }
+
+    #[test]
+    fn concurrency_slots_free_up_and_overdue_runs_abort() {
+        let table = QuotaTable::default();
+        assert!(table.admit(&app_command("demo")).unwrap().is_none());
+        table.set(
+            "demo".to_string(),
+            AppQuota {
+                max_concurrent: Some(1),
+                max_duration_ms: Some(1_000),
+                ..AppQuota::default()
+            },
+        );
+
+        let first = table
+            .admit(&app_command("demo"))
+            .unwrap()
+            .expect("quota applies");
+        let refused = table.admit(&app_command("demo")).err().expect("slot taken");
+        assert_eq!(refused.code, ShellErrorCode::ResourceExhausted);
+        assert!(table.admit(&app_command("other")).unwrap().is_none());
+
+        assert_eq!(table.enforce(0), 0);
+        assert_eq!(table.enforce(u64::MAX), 1);
+        assert!(first.execution().breached());
+        let outcome = futures::executor::block_on(first.run(Box::pin(futures::future::pending())));
+        assert_eq!(
+            outcome.err().map(|err| err.code),
+            Some(ShellErrorCode::ResourceExhausted)
+        );
+        assert!(table.admit(&app_command("demo")).unwrap().is_some());
+    }
+
+    #[test]
+    fn window_scoped_registrations_charge_their_owner_app() {
+        let table = QuotaTable::default();
+        table.set(
+            "demo".to_string(),
+            AppQuota {
+                max_concurrent: Some(1),
+                ..AppQuota::default()
+            },
+        );
+        let window_command = CommandDescriptor {
+            scope: CommandScope::Window { window_id: 7 },
+            owner_app_id: Some("demo".to_string()),
+            ..crate::intrinsic_descriptor("work", "work", "Works.", Vec::new())
+        };
+        let unowned = CommandDescriptor {
+            owner_app_id: None,
+            ..window_command.clone()
+        };
+        assert!(table.admit(&unowned).unwrap().is_none());
+
+        let _running = table
+            .admit(&window_command)
+            .unwrap()
+            .expect("owner quota applies");
+        let refused = table.admit(&app_command("demo")).err().expect("slot taken");
+        assert_eq!(refused.code, ShellErrorCode::ResourceExhausted);
+        assert!(table.admit(&window_command).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Fuzzy subsequence matching and history-aware ranking of completion candidates.
+
+use system_shell_contract::{last_token_start, CompletionItem};
+
+/// Points for each matched character.
+const MATCH_SCORE: i64 = 16;
+/// Extra points when a match directly follows the previous one.
+const CONSECUTIVE_BONUS: i64 = 24;
+/// Extra points when the first candidate character matches.
+const START_BONUS: i64 = 32;
+/// Extra points when a match follows a word separator such as `-`, `/`, or `.`.
+const BOUNDARY_BONUS: i64 = 16;
+/// Number of newest history entries that boost candidates they mention.
+const HISTORY_WINDOW: usize = 32;
+/// Points for a candidate used by the newest history entry; older entries give less.
+const HISTORY_BOOST: i64 = 64;
+
+/// Result of matching a typed token against one candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FuzzyMatch {
+    /// Relevance score; higher is better.
+    pub(crate) score: i64,
+    /// Character indices of the candidate matched by the token.
+    pub(crate) positions: Vec<usize>,
+}
+
+/// Matches `query` as a case-insensitive subsequence of `candidate`.
+///
+/// Characters are matched left to right, so `gst` matches `git status`. Runs of consecutive
+/// matches, matches at the start, and matches after separators score higher, while skipped
+/// characters cost one point each. An empty query matches everything with a zero score.
+pub(crate) fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let mut positions = Vec::new();
+    let mut score = 0i64;
+    let mut query_chars = query.chars().peekable();
+    let mut previous: Option<(usize, char)> = None;
+    let mut before = None::<char>;
+    for (index, ch) in candidate.chars().enumerate() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        let follows_boundary =
+            before.is_some_and(|before| matches!(before, '-' | '_' | '.' | '/' | ' ' | ':' | '='));
+        before = Some(ch);
+        if !ch.to_lowercase().eq(wanted.to_lowercase()) {
+            continue;
+        }
+        query_chars.next();
+        score += MATCH_SCORE;
+        if ch == wanted {
+            score += 1;
+        }
+        match previous {
+            None if index == 0 => score += START_BONUS,
+            Some((last, _)) if last + 1 == index => score += CONSECUTIVE_BONUS,
+            _ => {}
+        }
+        let gap_start = previous.map_or(0, |(last, _)| last + 1);
+        score -= (index - gap_start) as i64;
+        if follows_boundary {
+            score += BOUNDARY_BONUS;
+        }
+        positions.push(index);
+        previous = Some((index, ch));
+    }
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Returns the trailing token of `line` without its opening quote, or an empty token after
+/// whitespace.
+pub(crate) fn completion_token(line: &str) -> &str {
+    line[last_token_start(line)..].trim_start_matches(['"', '\''])
+}
+
+/// Scores `items` against the typed `token` and sorts best first.
+///
+/// Items are matched through their label; when the value extends the label with a typed prefix,
+/// such as `docs/` or `--format=`, that prefix is stripped from the token first. Candidates that
+/// appear as a word in one of the newest `history` entries (oldest first) get a recency boost.
+/// Sources filter their own candidates, so items the token does not match are kept, unscored,
+/// after every match. Ties sort by label.
+pub(crate) fn rank_completions(
+    items: Vec<CompletionItem>,
+    token: &str,
+    history: &[String],
+) -> Vec<CompletionItem> {
+    let recent = history
+        .iter()
+        .rev()
+        .take(HISTORY_WINDOW)
+        .collect::<Vec<_>>();
+    let mut ranked = items
+        .into_iter()
+        .map(|mut item| {
+            let label_token = item
+                .value
+                .strip_suffix(item.label.as_str())
+                .and_then(|head| token.strip_prefix(head))
+                .unwrap_or(token);
+            let found = fuzzy_match(label_token, &item.label).or_else(|| {
+                fuzzy_match(token, &item.value).map(|found| FuzzyMatch {
+                    positions: Vec::new(),
+                    ..found
+                })
+            });
+            let Some(found) = found else {
+                item.score = 0;
+                item.matched = Vec::new();
+                return (false, item);
+            };
+            let boost = recent
+                .iter()
+                .position(|line| line.split_whitespace().any(|word| word == item.value))
+                .map_or(0, |age| {
+                    HISTORY_BOOST * (HISTORY_WINDOW - age) as i64 / HISTORY_WINDOW as i64
+                });
+            item.score = found.score + boost;
+            item.matched = found.positions;
+            (true, item)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|(left_matched, left), (right_matched, right)| {
+        right_matched
+            .cmp(left_matched)
+            .then_with(|| right.score.cmp(&left.score))
+            .then_with(|| left.label.cmp(&right.label))
+    });
+    ranked.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(value: &str, label: &str) -> CompletionItem {
+        CompletionItem {
+            value: value.to_string(),
+            label: label.to_string(),
+            detail: None,
+            score: 0,
+            matched: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn fuzzy_matches_rank_prefixes_and_recent_history_first() {
+        assert_eq!(
+            fuzzy_match("wls", "windows list").unwrap().positions,
+            vec![0, 8, 10]
+        );
+        assert!(fuzzy_match("xyz", "windows").is_none());
+        assert!(
+            fuzzy_match("the", "theme").unwrap().score
+                > fuzzy_match("the", "tail-head-end").unwrap().score
+        );
+
+        let ranked = rank_completions(
+            vec![
+                item("theme", "theme"),
+                item("terminal", "terminal"),
+                item("open", "open"),
+            ],
+            "te",
+            &[],
+        );
+        assert_eq!(
+            ranked
+                .iter()
+                .map(|item| item.label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["terminal", "theme", "open"]
+        );
+        assert_eq!(ranked[0].matched, vec![0, 1]);
+        assert!(ranked[2].matched.is_empty());
+
+        let ranked = rank_completions(
+            vec![item("terminal", "terminal"), item("theme", "theme")],
+            "t",
+            &["theme list".to_string(), "ls".to_string()],
+        );
+        assert_eq!(ranked[0].label, "theme");
+
+        let ranked = rank_completions(vec![item("docs/notes", "notes")], "docs/nt", &[]);
+        assert_eq!(ranked[0].matched, vec![0, 2]);
+    }
+
+    #[test]
+    fn completion_tokens_respect_multibyte_whitespace_and_quotes() {
+        assert_eq!(completion_token("cat\u{3000}no"), "no");
+        assert_eq!(completion_token("cat\u{3000}"), "");
+        assert_eq!(completion_token("cat \"my fi"), "my fi");
+        assert_eq!(completion_token("cat 'a b' \"c d"), "c d");
+        assert_eq!(completion_token("cat my\\ fi"), "my\\ fi");
+        assert_eq!(completion_token("ls|gr"), "gr");
+        assert_eq!(completion_token("ls "), "");
+    }
+}
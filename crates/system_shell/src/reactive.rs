@@ -0,0 +1,179 @@
+//! State cells and task spawning the engine is built on.
+//!
+//! With the default `reactive` feature these are Leptos signals and `leptos::spawn_local`, so
+//! the desktop UI can track session state directly. Without it the engine builds headless: cells
+//! are plain shared values with the same method names, and executions run on a thread-local
+//! executor, letting native tests, fuzzers, and servers drive the same command registry without a
+//! Leptos runtime. Headless hosts observe output through
+//! [`ShellSessionHandle::subscribe_events`](crate::ShellSessionHandle::subscribe_events) and call
+//! [`run_until_stalled`] after waking a suspended execution, for example after answering a prompt.
+
+#[cfg(feature = "reactive")]
+pub use leptos::{
+    create_rw_signal, spawn_local, ReadSignal, RwSignal, SignalGetUntracked, SignalSet,
+    SignalUpdate, SignalWithUntracked,
+};
+
+#[cfg(not(feature = "reactive"))]
+pub use headless::{
+    create_rw_signal, run_until_stalled, spawn_local, ReadSignal, RwSignal, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWithUntracked,
+};
+
+/// Logs a non-fatal engine failure, such as a preference write that did not persist.
+pub(crate) fn warn(message: &str) {
+    #[cfg(feature = "reactive")]
+    leptos::logging::warn!("{message}");
+    #[cfg(not(feature = "reactive"))]
+    eprintln!("warning: {message}");
+}
+
+/// Prepares the current thread to create engine state in tests.
+#[cfg(test)]
+pub(crate) fn test_runtime() {
+    #[cfg(feature = "reactive")]
+    let _ = leptos::create_runtime();
+}
+
+#[cfg(not(feature = "reactive"))]
+mod headless {
+    use std::{cell::RefCell, future::Future, rc::Rc};
+
+    use futures::{
+        executor::{enter, LocalPool, LocalSpawner},
+        task::LocalSpawnExt,
+    };
+
+    /// Shared mutable value with the Leptos signal method names.
+    pub struct RwSignal<T>(Rc<RefCell<T>>);
+
+    /// Read-only view of an [`RwSignal`].
+    pub struct ReadSignal<T>(Rc<RefCell<T>>);
+
+    impl<T> Clone for RwSignal<T> {
+        fn clone(&self) -> Self {
+            Self(Rc::clone(&self.0))
+        }
+    }
+
+    impl<T> Clone for ReadSignal<T> {
+        fn clone(&self) -> Self {
+            Self(Rc::clone(&self.0))
+        }
+    }
+
+    impl<T> RwSignal<T> {
+        /// Returns a read-only view sharing this cell.
+        pub fn read_only(&self) -> ReadSignal<T> {
+            ReadSignal(Rc::clone(&self.0))
+        }
+    }
+
+    /// Creates a cell holding `value`.
+    pub fn create_rw_signal<T>(value: T) -> RwSignal<T> {
+        RwSignal(Rc::new(RefCell::new(value)))
+    }
+
+    /// Clones the current value out of a cell.
+    pub trait SignalGetUntracked {
+        /// Value held by the cell.
+        type Value;
+
+        /// Returns a clone of the current value.
+        fn get_untracked(&self) -> Self::Value;
+    }
+
+    /// Borrows the current value of a cell.
+    pub trait SignalWithUntracked {
+        /// Value held by the cell.
+        type Value;
+
+        /// Runs `f` with a reference to the current value.
+        fn with_untracked<O>(&self, f: impl FnOnce(&Self::Value) -> O) -> O;
+    }
+
+    /// Mutates a cell in place.
+    pub trait SignalUpdate {
+        /// Value held by the cell.
+        type Value;
+
+        /// Runs `f` with a mutable reference to the current value.
+        fn update(&self, f: impl FnOnce(&mut Self::Value));
+    }
+
+    /// Replaces the value of a cell.
+    pub trait SignalSet {
+        /// Value held by the cell.
+        type Value;
+
+        /// Stores `new_value`.
+        fn set(&self, new_value: Self::Value);
+    }
+
+    macro_rules! impl_read {
+        ($signal:ident) => {
+            impl<T: Clone> SignalGetUntracked for $signal<T> {
+                type Value = T;
+
+                fn get_untracked(&self) -> T {
+                    self.0.borrow().clone()
+                }
+            }
+
+            impl<T> SignalWithUntracked for $signal<T> {
+                type Value = T;
+
+                fn with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+                    f(&self.0.borrow())
+                }
+            }
+        };
+    }
+
+    impl_read!(RwSignal);
+    impl_read!(ReadSignal);
+
+    impl<T> SignalUpdate for RwSignal<T> {
+        type Value = T;
+
+        fn update(&self, f: impl FnOnce(&mut T)) {
+            f(&mut self.0.borrow_mut());
+        }
+    }
+
+    impl<T> SignalSet for RwSignal<T> {
+        type Value = T;
+
+        fn set(&self, new_value: T) {
+            *self.0.borrow_mut() = new_value;
+        }
+    }
+
+    thread_local! {
+        static POOL: RefCell<LocalPool> = RefCell::new(LocalPool::new());
+        static SPAWNER: LocalSpawner = POOL.with(|pool| pool.borrow().spawner());
+    }
+
+    /// Queues `future` on this thread's executor and, unless called from inside an executor such
+    /// as a task this one is polling, runs it until every queued task is finished or waiting.
+    ///
+    /// Executions that never wait on the host therefore finish before this returns, matching how
+    /// Leptos runs spawned work natively.
+    pub fn spawn_local(future: impl Future<Output = ()> + 'static) {
+        SPAWNER
+            .with(|spawner| spawner.spawn_local(future))
+            .expect("headless shell executor stays alive for the thread");
+        run_until_stalled();
+    }
+
+    /// Polls queued and woken executions until none can make progress.
+    ///
+    /// Call this after answering a prompt or cancelling an execution that is waiting on one.
+    /// Calls made from inside an executor, including this one, return without polling.
+    pub fn run_until_stalled() {
+        if enter().is_err() {
+            return;
+        }
+        POOL.with(|pool| pool.borrow_mut().run_until_stalled());
+    }
+}
@@ -0,0 +1,254 @@
+//! Engine-wide scheduler bounding how many session pipelines run at once.
+
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::{Rc, Weak},
+};
+
+use system_shell_contract::{CommandCaller, ExecutionId};
+
+use crate::reactive::{ReadSignal, SignalGetUntracked};
+
+/// Default number of pipelines allowed to run concurrently across all sessions.
+pub const DEFAULT_EXECUTION_LIMIT: usize = 4;
+
+/// Scheduling state of one session, as reported by `shell sessions list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SessionActivity {
+    Idle,
+    Running,
+    Queued,
+}
+
+impl SessionActivity {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Running => "running",
+            Self::Queued => "queued",
+        }
+    }
+}
+
+/// Diagnostic snapshot of one live session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SessionStatus {
+    pub(crate) id: u64,
+    pub(crate) caller: Option<CommandCaller>,
+    pub(crate) cwd: String,
+    pub(crate) activity: SessionActivity,
+    pub(crate) execution_id: Option<ExecutionId>,
+}
+
+struct SessionRecord {
+    id: u64,
+    caller: Option<CommandCaller>,
+    cwd: ReadSignal<String>,
+    active_execution: ReadSignal<Option<ExecutionId>>,
+    alive: Weak<()>,
+}
+
+struct QueuedStart {
+    session_id: u64,
+    start: Box<dyn FnOnce()>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    limit: usize,
+    running: Vec<u64>,
+    queue: VecDeque<QueuedStart>,
+    next_session: u64,
+    sessions: Vec<SessionRecord>,
+}
+
+/// Shared FIFO scheduler for foreground pipelines.
+///
+/// Each session runs at most one foreground pipeline, so queueing submissions in arrival order
+/// gives every session a fair turn once a slot frees up.
+#[derive(Clone)]
+pub(crate) struct ExecutionScheduler {
+    state: Rc<RefCell<SchedulerState>>,
+}
+
+impl ExecutionScheduler {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(SchedulerState {
+                limit: limit.max(1),
+                ..SchedulerState::default()
+            })),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> usize {
+        self.state.borrow().limit
+    }
+
+    /// Tracks a session for diagnostics until every clone of `alive` is dropped.
+    pub(crate) fn register_session(
+        &self,
+        caller: Option<CommandCaller>,
+        cwd: ReadSignal<String>,
+        active_execution: ReadSignal<Option<ExecutionId>>,
+        alive: &Rc<()>,
+    ) -> u64 {
+        let mut state = self.state.borrow_mut();
+        state.next_session = state.next_session.saturating_add(1);
+        let id = state.next_session;
+        state.sessions.push(SessionRecord {
+            id,
+            caller,
+            cwd,
+            active_execution,
+            alive: Rc::downgrade(alive),
+        });
+        id
+    }
+
+    /// Runs `start` now when a slot is free, otherwise queues it.
+    ///
+    /// Returns the number of pipelines ahead of a queued start, or `None` when it started
+    /// immediately. Every started pipeline must call [`ExecutionScheduler::finish`] exactly once.
+    pub(crate) fn schedule(&self, session_id: u64, start: Box<dyn FnOnce()>) -> Option<usize> {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.running.len() >= state.limit {
+                state.queue.push_back(QueuedStart { session_id, start });
+                return Some(state.running.len() + state.queue.len() - 1);
+            }
+            state.running.push(session_id);
+        }
+        start();
+        None
+    }
+
+    /// Releases the slot held by `session_id` and starts the next queued pipeline.
+    pub(crate) fn finish(&self, session_id: u64) {
+        let next = {
+            let mut state = self.state.borrow_mut();
+            if let Some(position) = state.running.iter().position(|id| *id == session_id) {
+                state.running.remove(position);
+            }
+            let next = state.queue.pop_front();
+            if let Some(next) = &next {
+                state.running.push(next.session_id);
+            }
+            next
+        };
+        if let Some(next) = next {
+            (next.start)();
+        }
+    }
+
+    /// Returns live sessions in creation order, pruning dropped ones.
+    pub(crate) fn sessions(&self) -> Vec<SessionStatus> {
+        let mut state = self.state.borrow_mut();
+        state
+            .sessions
+            .retain(|record| record.alive.strong_count() > 0);
+        state
+            .sessions
+            .iter()
+            .map(|record| {
+                let activity = if state.running.contains(&record.id) {
+                    SessionActivity::Running
+                } else if state
+                    .queue
+                    .iter()
+                    .any(|queued| queued.session_id == record.id)
+                {
+                    SessionActivity::Queued
+                } else {
+                    SessionActivity::Idle
+                };
+                SessionStatus {
+                    id: record.id,
+                    caller: record.caller.clone(),
+                    cwd: record.cwd.get_untracked(),
+                    activity,
+                    execution_id: record.active_execution.get_untracked(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::reactive::create_rw_signal;
+
+    use super::*;
+
+    #[test]
+    fn overflow_queues_in_arrival_order_until_slots_free() {
+        crate::reactive::test_runtime();
+        let scheduler = ExecutionScheduler::new(1);
+        let alive = Rc::new(());
+        let sessions = (0..3)
+            .map(|_| {
+                scheduler.register_session(
+                    None,
+                    create_rw_signal("/".to_string()).read_only(),
+                    create_rw_signal(None).read_only(),
+                    &alive,
+                )
+            })
+            .collect::<Vec<_>>();
+        let started = Rc::new(RefCell::new(Vec::new()));
+        let start = |session: u64| -> Box<dyn FnOnce()> {
+            let started = started.clone();
+            Box::new(move || started.borrow_mut().push(session))
+        };
+
+        assert_eq!(scheduler.schedule(sessions[0], start(sessions[0])), None);
+        assert_eq!(scheduler.schedule(sessions[1], start(sessions[1])), Some(1));
+        assert_eq!(scheduler.schedule(sessions[2], start(sessions[2])), Some(2));
+        assert_eq!(*started.borrow(), vec![sessions[0]]);
+        let activity = scheduler
+            .sessions()
+            .into_iter()
+            .map(|status| status.activity)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            activity,
+            vec![
+                SessionActivity::Running,
+                SessionActivity::Queued,
+                SessionActivity::Queued
+            ]
+        );
+
+        scheduler.finish(sessions[0]);
+        assert_eq!(*started.borrow(), vec![sessions[0], sessions[1]]);
+        scheduler.finish(sessions[1]);
+        scheduler.finish(sessions[2]);
+        assert_eq!(
+            *started.borrow(),
+            vec![sessions[0], sessions[1], sessions[2]]
+        );
+
+        let finished_inline = Rc::new(Cell::new(false));
+        let inline = scheduler.clone();
+        let flag = finished_inline.clone();
+        let session = sessions[0];
+        scheduler.schedule(
+            session,
+            Box::new(move || {
+                inline.finish(session);
+                flag.set(true);
+            }),
+        );
+        assert!(finished_inline.get());
+        assert!(scheduler
+            .sessions()
+            .iter()
+            .all(|status| status.activity == SessionActivity::Idle));
+
+        drop(alive);
+        assert!(scheduler.sessions().is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+//! Persistent token trie indexing command invocation paths.
+//!
+//! Nodes are shared through [`Rc`], so cloning a trie is constant time and an update copies only
+//! the nodes along the changed path. The registry edits its trie in place at registration time
+//! while snapshots keep the version they were taken from.
+
+use std::{collections::HashMap, rc::Rc};
+
+/// One command claiming a path, tagged with the key it was inserted under.
+#[derive(Clone)]
+pub(crate) struct TrieEntry<T> {
+    pub(crate) key: u64,
+    pub(crate) value: T,
+}
+
+#[derive(Clone)]
+struct TrieNode<T> {
+    children: HashMap<String, Rc<TrieNode<T>>>,
+    /// Commands whose path or alias ends at this node.
+    entries: Vec<TrieEntry<T>>,
+    /// Public paths continuing below this node.
+    public_below: usize,
+}
+
+impl<T> Default for TrieNode<T> {
+    fn default() -> Self {
+        Self {
+            children: HashMap::new(),
+            entries: Vec::new(),
+            public_below: 0,
+        }
+    }
+}
+
+/// Maps path tokens to the commands claiming them.
+#[derive(Clone)]
+pub(crate) struct PathTrie<T> {
+    root: Rc<TrieNode<T>>,
+}
+
+impl<T> Default for PathTrie<T> {
+    fn default() -> Self {
+        Self {
+            root: Rc::default(),
+        }
+    }
+}
+
+impl<T: Clone> PathTrie<T> {
+    /// Indexes `path` as claimed by `value` under `key`.
+    pub(crate) fn insert(&mut self, path: &[String], key: u64, value: T, public: bool) {
+        let mut node = Rc::make_mut(&mut self.root);
+        for token in path {
+            node.public_below += usize::from(public);
+            node = Rc::make_mut(node.children.entry(token.clone()).or_default());
+        }
+        node.entries.push(TrieEntry { key, value });
+    }
+
+    /// Removes one claim `key` holds on `path`, pruning nodes left without claims.
+    pub(crate) fn remove(&mut self, path: &[String], key: u64, public: bool) {
+        fn remove_at<T: Clone>(
+            node: &mut Rc<TrieNode<T>>,
+            path: &[String],
+            key: u64,
+            public: bool,
+        ) -> bool {
+            let node = Rc::make_mut(node);
+            match path.split_first() {
+                None => match node.entries.iter().position(|entry| entry.key == key) {
+                    Some(index) => {
+                        node.entries.remove(index);
+                        true
+                    }
+                    None => false,
+                },
+                Some((token, rest)) => {
+                    let Some(child) = node.children.get_mut(token) else {
+                        return false;
+                    };
+                    if !remove_at(child, rest, key, public) {
+                        return false;
+                    }
+                    if child.entries.is_empty() && child.children.is_empty() {
+                        node.children.remove(token);
+                    }
+                    node.public_below -= usize::from(public);
+                    true
+                }
+            }
+        }
+        remove_at(&mut self.root, path, key, public);
+    }
+
+    /// Returns the claims on the longest path that prefixes `tokens`, with that path's length.
+    pub(crate) fn longest_match(&self, tokens: &[String]) -> Option<(usize, &[TrieEntry<T>])> {
+        let mut node = &self.root;
+        let mut best = None;
+        for (depth, token) in tokens.iter().enumerate() {
+            let Some(child) = node.children.get(token) else {
+                break;
+            };
+            node = child;
+            if !node.entries.is_empty() {
+                best = Some((depth + 1, node.entries.as_slice()));
+            }
+        }
+        best
+    }
+
+    /// Returns whether a public path extends beyond `prefix`.
+    pub(crate) fn has_public_below(&self, prefix: &[String]) -> bool {
+        let mut node = &self.root;
+        for token in prefix {
+            match node.children.get(token) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        node.public_below > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn keys<T>(found: Option<(usize, &[TrieEntry<T>])>) -> Option<(usize, Vec<u64>)> {
+        found.map(|(len, entries)| (len, entries.iter().map(|entry| entry.key).collect()))
+    }
+
+    #[test]
+    fn longest_claimed_prefix_wins_and_snapshots_survive_removal() {
+        let mut trie = PathTrie::default();
+        trie.insert(&path("theme"), 1, "theme", false);
+        trie.insert(&path("theme set skin"), 2, "skin", true);
+        trie.insert(&path("theme set skin"), 3, "skin override", true);
+        let snapshot = trie.clone();
+
+        assert_eq!(
+            keys(trie.longest_match(&path("theme set skin dark"))),
+            Some((3, vec![2, 3]))
+        );
+        assert_eq!(
+            keys(trie.longest_match(&path("theme set"))),
+            Some((1, vec![1]))
+        );
+        assert_eq!(keys(trie.longest_match(&path("windows"))), None);
+        assert!(trie.has_public_below(&path("theme set")));
+        assert!(!trie.has_public_below(&path("theme set skin")));
+
+        trie.remove(&path("theme set skin"), 2, true);
+        trie.remove(&path("theme set skin"), 3, true);
+        assert_eq!(
+            keys(trie.longest_match(&path("theme set skin dark"))),
+            Some((1, vec![1]))
+        );
+        assert!(!trie.has_public_below(&path("theme")));
+        assert_eq!(
+            keys(snapshot.longest_match(&path("theme set skin"))),
+            Some((3, vec![2, 3]))
+        );
+    }
+}
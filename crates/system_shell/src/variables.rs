@@ -0,0 +1,209 @@
+//! Session variables filled by `capture` stages and expanded as `$name` in later lines.
+
+use system_shell_contract::{
+    ShellError, ShellErrorCode, StructuredData, StructuredScalar, StructuredValue,
+};
+
+use crate::quote_parameter;
+
+/// Validates a variable name given to `capture`, accepting an optional leading `$`.
+///
+/// # Errors
+///
+/// Returns a usage error unless the name is an ASCII letter or `_` followed by letters, digits,
+/// or `_`.
+pub(crate) fn variable_name(raw: &str) -> Result<String, ShellError> {
+    let name = raw.strip_prefix('$').unwrap_or(raw);
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    if !valid {
+        return Err(ShellError::new(
+            ShellErrorCode::Usage,
+            format!("invalid variable name `{raw}`"),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Expands `$name` and `${name}` references to session variables in one shell line.
+///
+/// References inside single quotes, `\$` escapes, and the whole of any `capture` or `var` stage
+/// are left untouched so those stages can name their variable. Values expand to words as described by
+/// [`variable_words`], each quoted when needed so it stays one token; unset variables expand to
+/// nothing.
+pub(crate) fn expand_variables(
+    line: &str,
+    lookup: impl Fn(&str) -> Option<StructuredData>,
+) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut quote = None::<char>;
+    let mut stage_start = true;
+    let mut literal_stage = false;
+
+    while let Some(ch) = chars.next() {
+        if stage_start && quote.is_none() && !ch.is_whitespace() {
+            stage_start = false;
+            let word = std::iter::once(ch)
+                .chain(chars.clone())
+                .take_while(|ch| !ch.is_whitespace() && *ch != '|')
+                .collect::<String>();
+            literal_stage = matches!(word.as_str(), "capture" | "var");
+        }
+        match ch {
+            '\\' if quote != Some('\'') => {
+                out.push(ch);
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            }
+            '"' | '\'' if quote.is_none() => {
+                quote = Some(ch);
+                out.push(ch);
+            }
+            _ if quote == Some(ch) => {
+                quote = None;
+                out.push(ch);
+            }
+            '|' if quote.is_none() => {
+                stage_start = true;
+                literal_stage = false;
+                out.push(ch);
+            }
+            '$' if quote != Some('\'') && !literal_stage => {
+                let braced = chars.peek() == Some(&'{');
+                let rest = chars.clone().skip(usize::from(braced));
+                let name = rest
+                    .take_while(|ch| ch.is_ascii_alphanumeric() || *ch == '_')
+                    .collect::<String>();
+                let closed = !braced || chars.clone().nth(name.len() + 1) == Some('}');
+                if variable_name(&name).is_err() || !closed {
+                    out.push(ch);
+                    continue;
+                }
+                for _ in 0..name.len() + 2 * usize::from(braced) {
+                    chars.next();
+                }
+                let in_quotes = quote.is_some();
+                let words = lookup(&name).map(|data| variable_words(&data));
+                let expanded = words
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|word| quote_parameter(word, in_quotes))
+                    .collect::<Vec<_>>();
+                out.push_str(&expanded.join(" "));
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+/// Splits a captured value into the words it expands to.
+///
+/// Scalars become one word, lists of scalars and single-column tables one word per item, and
+/// any other value one word holding its JSON encoding.
+pub(crate) fn variable_words(data: &StructuredData) -> Vec<String> {
+    scalar_words(data).unwrap_or_else(|| vec![serde_json::to_string(data).unwrap_or_default()])
+}
+
+/// Renders piped data as the text `tee` writes: scalars verbatim, word lists one per line, and
+/// other values as indented JSON.
+pub(crate) fn render_text(data: &StructuredData) -> String {
+    match (data, scalar_words(data)) {
+        (StructuredData::Value(StructuredValue::Scalar(scalar)), _) => scalar_text(scalar),
+        (_, Some(words)) if words.is_empty() => String::new(),
+        (_, Some(words)) => format!("{}\n", words.join("\n")),
+        (_, None) => serde_json::to_string_pretty(data).unwrap_or_default(),
+    }
+}
+
+fn scalar_words(data: &StructuredData) -> Option<Vec<String>> {
+    let scalars = |values: &mut dyn Iterator<Item = &StructuredValue>| {
+        values
+            .map(|value| match value {
+                StructuredValue::Scalar(scalar) => Some(scalar_text(scalar)),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+    };
+    match data {
+        StructuredData::Empty => Some(Vec::new()),
+        StructuredData::Value(StructuredValue::Scalar(scalar)) => Some(vec![scalar_text(scalar)]),
+        StructuredData::List(values) | StructuredData::Value(StructuredValue::List(values)) => {
+            scalars(&mut values.iter())
+        }
+        StructuredData::Table(table) if table.columns.len() == 1 => scalars(
+            &mut table
+                .rows
+                .iter()
+                .filter_map(|row| row.fields.first().map(|field| &field.value)),
+        ),
+        _ => None,
+    }
+}
+
+fn scalar_text(scalar: &StructuredScalar) -> String {
+    match scalar {
+        StructuredScalar::Null => String::new(),
+        StructuredScalar::Bool(value) => value.to_string(),
+        StructuredScalar::Int(value) => value.to_string(),
+        StructuredScalar::Float(value) => value.to_string(),
+        StructuredScalar::String(value) => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use system_shell_contract::{StructuredField, StructuredRecord, StructuredTable};
+
+    fn string(value: &str) -> StructuredValue {
+        StructuredValue::Scalar(StructuredScalar::String(value.to_string()))
+    }
+
+    #[test]
+    fn variables_expand_outside_capture_stages_and_single_quotes() {
+        let names = StructuredData::Table(StructuredTable {
+            columns: vec!["name".to_string()],
+            rows: ["a.txt", "my notes.md"]
+                .map(|name| StructuredRecord {
+                    fields: vec![StructuredField {
+                        name: "name".to_string(),
+                        value: string(name),
+                    }],
+                })
+                .to_vec(),
+            ..StructuredTable::default()
+        });
+        let lookup = |name: &str| match name {
+            "files" => Some(names.clone()),
+            "dir" => Some(StructuredData::Value(string("/docs"))),
+            _ => None,
+        };
+
+        assert_eq!(
+            expand_variables("cat $files ${dir}/x $missing", lookup),
+            "cat a.txt \"my notes.md\" /docs/x "
+        );
+        assert_eq!(
+            expand_variables("echo '$dir' \\$dir \"in $dir\" $1 $", lookup),
+            "echo '$dir' \\$dir \"in /docs\" $1 $"
+        );
+        assert_eq!(
+            expand_variables("ls $dir | capture $dir | tee $dir", lookup),
+            "ls /docs | capture $dir | tee /docs"
+        );
+        assert_eq!(
+            expand_variables("var $dir | tee $dir", lookup),
+            "var $dir | tee /docs"
+        );
+        assert!(variable_name("$ok_1").is_ok());
+        assert!(variable_name("1bad").is_err());
+        assert_eq!(render_text(&names), "a.txt\nmy notes.md\n");
+    }
+}
@@ -0,0 +1,934 @@
+//! Classic file commands backed by the explorer filesystem service.
+//!
+//! The explorer service only moves UTF-8 text, so `cp` and `mv` copy files by reading and
+//! rewriting their text, and directories by walking them.
+
+use std::rc::Rc;
+
+use platform_host::{ExplorerEntry, ExplorerEntryKind, ExplorerFsService, ExplorerMetadata};
+use system_shell::{resolve_path, CommandExecutionContext, CommandRegistryHandle, ShellEngine};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandOutputShape, CommandPath,
+    CommandResult, DisplayPreference, ShellError, ShellExit, StructuredData, StructuredRecord,
+    StructuredScalar, StructuredTable, StructuredValue,
+};
+
+use crate::{
+    example, flag_option, fs_error, handler, has_flag, info_result, optional_u64_field, path_arg,
+    root_descriptor, search, string_field, usage_error,
+};
+
+type Fs = Rc<dyn ExplorerFsService>;
+
+/// Registers `ls`, `cd`, `cat`, `mkdir`, `rm`, `mv`, `cp`, `touch`, `fs search`, and `fs grep`
+/// on `engine`.
+///
+/// Every command resolves paths against the session cwd. `rm`, `mv`, and `cp` are destructive
+/// and honor `--dry-run`. Pair this with [`ShellEngine::with_explorer_fs`] on the same service
+/// so path arguments complete.
+///
+/// # Errors
+///
+/// Returns the registry's [`system_shell_contract::ShellErrorCode::Conflict`] error when another
+/// global command already claims one of these names; commands registered before the clash are
+/// unregistered again.
+pub fn register_fs_commands(
+    engine: &ShellEngine,
+    fs: Rc<dyn ExplorerFsService>,
+) -> Result<Vec<CommandRegistryHandle>, ShellError> {
+    let commands = [
+        (ls_descriptor(), handler(fs.clone(), ls)),
+        (cd_descriptor(), handler(fs.clone(), cd)),
+        (cat_descriptor(), handler(fs.clone(), cat)),
+        (mkdir_descriptor(), handler(fs.clone(), mkdir)),
+        (rm_descriptor(), handler(fs.clone(), rm)),
+        (mv_descriptor(), handler(fs.clone(), mv)),
+        (cp_descriptor(), handler(fs.clone(), cp)),
+        (touch_descriptor(), handler(fs.clone(), touch)),
+        (
+            search::search_descriptor(),
+            handler(fs.clone(), search::search),
+        ),
+        (search::grep_descriptor(), handler(fs, search::grep)),
+    ];
+    commands
+        .into_iter()
+        .map(|(descriptor, handler)| engine.register_command(descriptor, None, handler))
+        .collect()
+}
+
+fn ls_descriptor() -> system_shell_contract::CommandDescriptor {
+    root_descriptor(
+        "ls",
+        "List a directory, or describe a file, as a table.",
+        "ls [path]",
+        vec![path_arg(
+            "path",
+            "Directory or file; defaults to the cwd.",
+            false,
+            false,
+        )],
+        Vec::new(),
+        vec![
+            example("ls", "List the cwd."),
+            example("ls /Projects | data sort size", "Sort a listing by size."),
+        ],
+        CommandOutputShape::new(CommandDataShape::Table),
+    )
+}
+
+async fn ls(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    let target = target_path(&context, context.args.first());
+    let metadata = stat(&fs, &target).await?;
+    let entries = if metadata.kind == ExplorerEntryKind::Directory {
+        fs.list_dir(&target)
+            .await
+            .map_err(|err| fs_error("list", &target, err))?
+            .entries
+    } else {
+        vec![ExplorerEntry {
+            name: metadata.name,
+            path: metadata.path,
+            kind: metadata.kind,
+            size: metadata.size,
+            modified_at_unix_ms: metadata.modified_at_unix_ms,
+        }]
+    };
+    Ok(CommandResult {
+        output: StructuredData::Table(StructuredTable {
+            columns: ["name", "kind", "path", "size", "modified_at_unix_ms"]
+                .map(str::to_string)
+                .to_vec(),
+            rows: entries.iter().map(entry_row).collect(),
+            schema: None,
+            source_command: Some(CommandPath::new("ls")),
+            fallback_text: None,
+        }),
+        display: DisplayPreference::Table,
+        notices: Vec::new(),
+        cwd: None,
+        exit: ShellExit::success(),
+    })
+}
+
+fn cd_descriptor() -> system_shell_contract::CommandDescriptor {
+    root_descriptor(
+        "cd",
+        "Change the session cwd.",
+        "cd [path]",
+        vec![CommandArgSpec {
+            completion: CommandArgCompletion::Directory,
+            ..path_arg("path", "Target directory; defaults to `/`.", false, false)
+        }],
+        Vec::new(),
+        vec![
+            example("cd /Projects", "Enter a directory."),
+            example("cd ..", "Go up one level."),
+        ],
+        CommandOutputShape::new(CommandDataShape::Empty),
+    )
+}
+
+async fn cd(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    let target = match context.args.first() {
+        Some(path) => resolve_path(&context.cwd, path),
+        None => "/".to_string(),
+    };
+    if stat(&fs, &target).await?.kind != ExplorerEntryKind::Directory {
+        return Err(fs_error(
+            "cd",
+            &target,
+            format!("not a directory: `{target}`"),
+        ));
+    }
+    let mut result = info_result(format!("cwd = {target}"));
+    result.cwd = Some(target);
+    Ok(result)
+}
+
+fn cat_descriptor() -> system_shell_contract::CommandDescriptor {
+    root_descriptor(
+        "cat",
+        "Print the text of one or more files.",
+        "cat <path>...",
+        vec![path_arg("path", "Files to print, in order.", true, true)],
+        Vec::new(),
+        vec![example("cat notes.txt", "Print a file in the cwd.")],
+        CommandOutputShape::new(CommandDataShape::Scalar),
+    )
+}
+
+async fn cat(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    let mut text = String::new();
+    for path in &context.args {
+        let path = resolve_path(&context.cwd, path);
+        let file = fs
+            .read_text_file(&path)
+            .await
+            .map_err(|err| fs_error("read", &path, err))?;
+        text.push_str(&file.text);
+    }
+    Ok(CommandResult {
+        output: StructuredData::Value(StructuredValue::Scalar(StructuredScalar::String(text))),
+        display: DisplayPreference::Value,
+        notices: Vec::new(),
+        cwd: None,
+        exit: ShellExit::success(),
+    })
+}
+
+fn mkdir_descriptor() -> system_shell_contract::CommandDescriptor {
+    root_descriptor(
+        "mkdir",
+        "Create directories.",
+        "mkdir <path>... [--parents]",
+        vec![CommandArgSpec {
+            completion: CommandArgCompletion::Directory,
+            ..path_arg("path", "Directories to create.", true, true)
+        }],
+        vec![flag_option(
+            "parents",
+            'p',
+            "Create missing parents and accept existing directories.",
+        )],
+        vec![example(
+            "mkdir -p /Projects/demo/src",
+            "Create a nested directory.",
+        )],
+        CommandOutputShape::new(CommandDataShape::Empty),
+    )
+}
+
+async fn mkdir(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    let parents = has_flag(&context, "parents");
+    let mut created = Vec::new();
+    for path in &context.args {
+        let path = resolve_path(&context.cwd, path);
+        let chain = if parents {
+            ancestors(&path)
+        } else {
+            vec![path.clone()]
+        };
+        for dir in chain {
+            if parents {
+                if let Ok(existing) = fs.stat(&dir).await {
+                    if existing.kind != ExplorerEntryKind::Directory {
+                        return Err(fs_error(
+                            "create",
+                            &dir,
+                            format!("`{dir}` exists and is not a directory"),
+                        ));
+                    }
+                    continue;
+                }
+            }
+            fs.create_dir(&dir)
+                .await
+                .map_err(|err| fs_error("create", &dir, err))?;
+            created.push(dir);
+        }
+    }
+    Ok(info_result(if created.is_empty() {
+        "nothing to create".to_string()
+    } else {
+        format!("created {}", created.join(", "))
+    }))
+}
+
+fn rm_descriptor() -> system_shell_contract::CommandDescriptor {
+    let mut descriptor = root_descriptor(
+        "rm",
+        "Remove files or directories.",
+        "rm <path>... [--recursive] [--dry-run]",
+        vec![path_arg(
+            "path",
+            "Files or directories to remove.",
+            true,
+            true,
+        )],
+        vec![flag_option(
+            "recursive",
+            'r',
+            "Remove directories and everything inside them.",
+        )],
+        vec![
+            example("rm notes.txt", "Remove a file."),
+            example(
+                "rm -r /Projects/old --dry-run",
+                "Show what would be removed.",
+            ),
+        ],
+        CommandOutputShape::new(CommandDataShape::Empty),
+    );
+    descriptor.destructive = true;
+    descriptor
+}
+
+async fn rm(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    let recursive = has_flag(&context, "recursive");
+    let mut targets = Vec::new();
+    for path in &context.args {
+        let path = resolve_path(&context.cwd, path);
+        if path == "/" {
+            return Err(fs_error("delete", &path, "refusing to remove `/`"));
+        }
+        if stat(&fs, &path).await?.kind == ExplorerEntryKind::Directory && !recursive {
+            return Err(fs_error(
+                "delete",
+                &path,
+                format!("`{path}` is a directory; pass --recursive to remove it"),
+            ));
+        }
+        targets.push(path);
+    }
+    if context.dry_run {
+        return Ok(info_result(format!("would remove {}", targets.join(", "))));
+    }
+    for path in &targets {
+        fs.delete(path, recursive)
+            .await
+            .map_err(|err| fs_error("delete", path, err))?;
+    }
+    Ok(info_result(format!("removed {}", targets.join(", "))))
+}
+
+fn mv_descriptor() -> system_shell_contract::CommandDescriptor {
+    let mut descriptor = root_descriptor(
+        "mv",
+        "Move or rename a file or directory.",
+        "mv <source> <destination> [--dry-run]",
+        transfer_args(),
+        Vec::new(),
+        vec![
+            example("mv draft.txt notes.txt", "Rename a file."),
+            example("mv notes.txt /Archive", "Move a file into a directory."),
+        ],
+        CommandOutputShape::new(CommandDataShape::Empty),
+    );
+    descriptor.destructive = true;
+    descriptor
+}
+
+async fn mv(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    let transfer = Transfer::resolve(&fs, &context, "mv").await?;
+    if context.dry_run {
+        return Ok(info_result(format!(
+            "would move {} to {}",
+            transfer.source, transfer.destination
+        )));
+    }
+    transfer.copy(&fs).await?;
+    fs.delete(&transfer.source, true)
+        .await
+        .map_err(|err| fs_error("delete", &transfer.source, err))?;
+    Ok(info_result(format!(
+        "moved {} to {}",
+        transfer.source, transfer.destination
+    )))
+}
+
+fn cp_descriptor() -> system_shell_contract::CommandDescriptor {
+    let mut descriptor = root_descriptor(
+        "cp",
+        "Copy a file, or a directory with --recursive.",
+        "cp <source> <destination> [--recursive] [--dry-run]",
+        transfer_args(),
+        vec![flag_option(
+            "recursive",
+            'r',
+            "Copy directories and everything inside them.",
+        )],
+        vec![
+            example("cp notes.txt notes.bak", "Copy a file."),
+            example("cp -r /Projects/demo /Archive", "Copy a directory tree."),
+        ],
+        CommandOutputShape::new(CommandDataShape::Empty),
+    );
+    descriptor.destructive = true;
+    descriptor
+}
+
+async fn cp(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    let transfer = Transfer::resolve(&fs, &context, "cp").await?;
+    if transfer.is_directory && !has_flag(&context, "recursive") {
+        return Err(fs_error(
+            "copy",
+            &transfer.source,
+            format!(
+                "`{}` is a directory; pass --recursive to copy it",
+                transfer.source
+            ),
+        ));
+    }
+    if context.dry_run {
+        return Ok(info_result(format!(
+            "would copy {} to {}",
+            transfer.source, transfer.destination
+        )));
+    }
+    transfer.copy(&fs).await?;
+    Ok(info_result(format!(
+        "copied {} to {}",
+        transfer.source, transfer.destination
+    )))
+}
+
+fn touch_descriptor() -> system_shell_contract::CommandDescriptor {
+    root_descriptor(
+        "touch",
+        "Create empty files or refresh the modified time of existing ones.",
+        "touch <path>...",
+        vec![path_arg("path", "Files to create or touch.", true, true)],
+        Vec::new(),
+        vec![example("touch notes.txt", "Create an empty file.")],
+        CommandOutputShape::new(CommandDataShape::Empty),
+    )
+}
+
+async fn touch(fs: Fs, context: CommandExecutionContext) -> Result<CommandResult, ShellError> {
+    for path in &context.args {
+        let path = resolve_path(&context.cwd, path);
+        match fs.stat(&path).await {
+            Ok(metadata) if metadata.kind == ExplorerEntryKind::Directory => {}
+            Ok(_) => {
+                let file = fs
+                    .read_text_file(&path)
+                    .await
+                    .map_err(|err| fs_error("read", &path, err))?;
+                fs.write_text_file(&path, &file.text)
+                    .await
+                    .map_err(|err| fs_error("write", &path, err))?;
+            }
+            Err(_) => {
+                fs.create_file(&path, "")
+                    .await
+                    .map_err(|err| fs_error("create", &path, err))?;
+            }
+        }
+    }
+    Ok(CommandResult::success(StructuredData::Empty))
+}
+
+fn transfer_args() -> Vec<CommandArgSpec> {
+    vec![
+        path_arg("source", "Existing file or directory.", true, false),
+        path_arg(
+            "destination",
+            "New path, or an existing directory to place the source in.",
+            true,
+            false,
+        ),
+    ]
+}
+
+/// A resolved `cp` or `mv` source and destination.
+struct Transfer {
+    source: String,
+    destination: String,
+    is_directory: bool,
+}
+
+impl Transfer {
+    /// Resolves both paths, placing the source inside the destination when that is a directory.
+    async fn resolve(
+        fs: &Fs,
+        context: &CommandExecutionContext,
+        command: &str,
+    ) -> Result<Self, ShellError> {
+        let [source, destination] = context.args.as_slice() else {
+            return Err(usage_error(format!(
+                "usage: {command} <source> <destination>"
+            )));
+        };
+        let source = resolve_path(&context.cwd, source);
+        let mut destination = resolve_path(&context.cwd, destination);
+        let metadata = stat(fs, &source).await?;
+        if fs
+            .stat(&destination)
+            .await
+            .is_ok_and(|existing| existing.kind == ExplorerEntryKind::Directory)
+        {
+            destination = join(&destination, &metadata.name);
+        }
+        let is_directory = metadata.kind == ExplorerEntryKind::Directory;
+        if destination == source || (is_directory && destination.starts_with(&format!("{source}/")))
+        {
+            return Err(fs_error(
+                command,
+                &source,
+                format!("cannot {command} `{source}` into itself"),
+            ));
+        }
+        Ok(Self {
+            source,
+            destination,
+            is_directory,
+        })
+    }
+
+    /// Copies the source tree to the destination, overwriting files that already exist.
+    async fn copy(&self, fs: &Fs) -> Result<(), ShellError> {
+        if !self.is_directory {
+            return copy_file(fs, &self.source, &self.destination).await;
+        }
+        let mut pending = vec![(self.source.clone(), self.destination.clone())];
+        while let Some((source, destination)) = pending.pop() {
+            if fs.stat(&destination).await.is_err() {
+                fs.create_dir(&destination)
+                    .await
+                    .map_err(|err| fs_error("create", &destination, err))?;
+            }
+            let listing = fs
+                .list_dir(&source)
+                .await
+                .map_err(|err| fs_error("list", &source, err))?;
+            for entry in listing.entries {
+                let target = join(&destination, &entry.name);
+                match entry.kind {
+                    ExplorerEntryKind::Directory => pending.push((entry.path, target)),
+                    ExplorerEntryKind::File => copy_file(fs, &entry.path, &target).await?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn copy_file(fs: &Fs, source: &str, destination: &str) -> Result<(), ShellError> {
+    let file = fs
+        .read_text_file(source)
+        .await
+        .map_err(|err| fs_error("read", source, err))?;
+    fs.write_text_file(destination, &file.text)
+        .await
+        .map_err(|err| fs_error("write", destination, err))?;
+    Ok(())
+}
+
+async fn stat(fs: &Fs, path: &str) -> Result<ExplorerMetadata, ShellError> {
+    fs.stat(path)
+        .await
+        .map_err(|err| fs_error("stat", path, err))
+}
+
+fn target_path(context: &CommandExecutionContext, path: Option<&String>) -> String {
+    path.map(|path| resolve_path(&context.cwd, path))
+        .unwrap_or_else(|| context.cwd.clone())
+}
+
+fn join(dir: &str, name: &str) -> String {
+    format!("{}/{name}", dir.trim_end_matches('/'))
+}
+
+/// Returns every directory from the root down to `path`, excluding `/`.
+fn ancestors(path: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut current = String::new();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        current = format!("{current}/{segment}");
+        chain.push(current.clone());
+    }
+    chain
+}
+
+fn entry_row(entry: &ExplorerEntry) -> StructuredRecord {
+    StructuredRecord {
+        fields: vec![
+            string_field("name", entry.name.clone()),
+            string_field(
+                "kind",
+                match entry.kind {
+                    ExplorerEntryKind::File => "file",
+                    ExplorerEntryKind::Directory => "dir",
+                },
+            ),
+            string_field("path", entry.path.clone()),
+            optional_u64_field("size", entry.size),
+            optional_u64_field("modified_at_unix_ms", entry.modified_at_unix_ms),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::BTreeMap};
+
+    use leptos::SignalGetUntracked;
+    use platform_host::{
+        apply_batch_with, ExplorerBackend, ExplorerBackendStatus, ExplorerBatchOp,
+        ExplorerBatchResult, ExplorerChangeCallback, ExplorerFileBytesResult, ExplorerFileChunk,
+        ExplorerFileReadResult, ExplorerFsFuture, ExplorerImportFile, ExplorerListPage,
+        ExplorerListResult, ExplorerPermissionMode, ExplorerPermissionState, ExplorerStorageUsage,
+        ExplorerWatch,
+    };
+    use system_shell_contract::{ShellRequest, ShellStreamEvent};
+
+    use super::*;
+
+    /// In-memory tree keyed by path; `None` marks a directory.
+    #[derive(Default)]
+    struct MemoryFs {
+        nodes: RefCell<BTreeMap<String, Option<String>>>,
+    }
+
+    impl MemoryFs {
+        fn metadata(&self, path: &str) -> Result<ExplorerMetadata, String> {
+            let nodes = self.nodes.borrow();
+            let node = match path {
+                "/" => &None,
+                _ => nodes.get(path).ok_or(format!("missing {path}"))?,
+            };
+            Ok(ExplorerMetadata {
+                name: path.rsplit('/').next().unwrap_or_default().to_string(),
+                path: path.to_string(),
+                kind: match node {
+                    Some(_) => ExplorerEntryKind::File,
+                    None => ExplorerEntryKind::Directory,
+                },
+                backend: ExplorerBackend::IndexedDbVirtual,
+                size: node.as_ref().map(|text| text.len() as u64),
+                modified_at_unix_ms: None,
+                permission: ExplorerPermissionState::Virtual,
+            })
+        }
+
+        fn parent_exists(&self, path: &str) -> Result<(), String> {
+            match path.rsplit_once('/') {
+                Some(("", _)) => Ok(()),
+                Some((parent, _)) if self.nodes.borrow().get(parent) == Some(&None) => Ok(()),
+                _ => Err(format!("no parent for {path}")),
+            }
+        }
+
+        fn put(&self, path: &str, node: Option<String>) -> Result<ExplorerMetadata, String> {
+            self.parent_exists(path)?;
+            self.nodes.borrow_mut().insert(path.to_string(), node);
+            self.metadata(path)
+        }
+    }
+
+    fn ready<'a, T: 'a>(value: Result<T, String>) -> ExplorerFsFuture<'a, Result<T, String>> {
+        Box::pin(async move { value })
+    }
+
+    impl ExplorerFsService for MemoryFs {
+        fn status<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+            ready(Err("unsupported".to_string()))
+        }
+        fn pick_native_directory<'a>(
+            &'a self,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+            ready(Err("unsupported".to_string()))
+        }
+        fn unmount_native_directory<'a>(
+            &'a self,
+            _path: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerBackendStatus, String>> {
+            ready(Err("unsupported".to_string()))
+        }
+        fn request_permission<'a>(
+            &'a self,
+            _mode: ExplorerPermissionMode,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerPermissionState, String>> {
+            ready(Ok(ExplorerPermissionState::Virtual))
+        }
+        fn list_dir<'a>(
+            &'a self,
+            path: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerListResult, String>> {
+            let prefix = format!("{}/", path.trim_end_matches('/'));
+            let entries = self
+                .nodes
+                .borrow()
+                .keys()
+                .filter(|key| {
+                    key.strip_prefix(&prefix)
+                        .is_some_and(|rest| !rest.is_empty() && !rest.contains('/'))
+                })
+                .filter_map(|key| self.metadata(key).ok())
+                .map(|metadata| ExplorerEntry {
+                    name: metadata.name,
+                    path: metadata.path,
+                    kind: metadata.kind,
+                    size: metadata.size,
+                    modified_at_unix_ms: None,
+                })
+                .collect();
+            ready(Ok(ExplorerListResult {
+                cwd: path.to_string(),
+                backend: ExplorerBackend::IndexedDbVirtual,
+                permission: ExplorerPermissionState::Virtual,
+                entries,
+            }))
+        }
+        fn list_dir_page<'a>(
+            &'a self,
+            path: &'a str,
+            offset: usize,
+            limit: usize,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerListPage, String>> {
+            Box::pin(async move { Ok(self.list_dir(path).await?.page(offset, limit)) })
+        }
+        fn read_text_file<'a>(
+            &'a self,
+            path: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerFileReadResult, String>> {
+            let text = self.nodes.borrow().get(path).cloned().flatten();
+            ready(match text {
+                Some(text) => self.metadata(path).map(|metadata| ExplorerFileReadResult {
+                    backend: ExplorerBackend::IndexedDbVirtual,
+                    path: path.to_string(),
+                    text,
+                    metadata,
+                    cached_preview_key: String::new(),
+                }),
+                None => Err(format!("not a file: {path}")),
+            })
+        }
+        fn read_file_bytes<'a>(
+            &'a self,
+            path: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerFileBytesResult, String>> {
+            let text = self.nodes.borrow().get(path).cloned().flatten();
+            ready(match text {
+                Some(text) => self.metadata(path).map(|metadata| ExplorerFileBytesResult {
+                    backend: ExplorerBackend::IndexedDbVirtual,
+                    path: path.to_string(),
+                    bytes: text.into_bytes(),
+                    metadata,
+                }),
+                None => Err(format!("not a file: {path}")),
+            })
+        }
+        fn read_file_chunk<'a>(
+            &'a self,
+            path: &'a str,
+            offset: u64,
+            len: usize,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerFileChunk, String>> {
+            let text = self.nodes.borrow().get(path).cloned().flatten();
+            ready(match text {
+                Some(text) => {
+                    let bytes = text.as_bytes();
+                    let start = (offset as usize).min(bytes.len());
+                    let end = start.saturating_add(len).min(bytes.len());
+                    Ok(ExplorerFileChunk {
+                        path: path.to_string(),
+                        offset,
+                        bytes: bytes[start..end].to_vec(),
+                        size: bytes.len() as u64,
+                    })
+                }
+                None => Err(format!("not a file: {path}")),
+            })
+        }
+        fn write_text_file<'a>(
+            &'a self,
+            path: &'a str,
+            text: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+            ready(self.put(path, Some(text.to_string())))
+        }
+        fn write_file_bytes<'a>(
+            &'a self,
+            path: &'a str,
+            bytes: &'a [u8],
+        ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+            let text = String::from_utf8_lossy(bytes).into_owned();
+            ready(self.put(path, Some(text)))
+        }
+        fn write_file_chunk<'a>(
+            &'a self,
+            path: &'a str,
+            offset: u64,
+            bytes: &'a [u8],
+        ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+            let mut text = match offset {
+                0 => String::new(),
+                _ => self
+                    .nodes
+                    .borrow()
+                    .get(path)
+                    .cloned()
+                    .flatten()
+                    .unwrap_or_default(),
+            };
+            if text.len() as u64 != offset {
+                return ready(Err(format!("offset {offset} is not the end of {path}")));
+            }
+            text.push_str(&String::from_utf8_lossy(bytes));
+            ready(self.put(path, Some(text)))
+        }
+        fn pick_import_files<'a>(
+            &'a self,
+        ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerImportFile>, String>> {
+            ready(Err("unsupported".to_string()))
+        }
+        fn save_download<'a>(
+            &'a self,
+            _name: &'a str,
+            _bytes: &'a [u8],
+        ) -> ExplorerFsFuture<'a, Result<(), String>> {
+            ready(Err("unsupported".to_string()))
+        }
+        fn create_dir<'a>(
+            &'a self,
+            path: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+            ready(self.put(path, None))
+        }
+        fn create_file<'a>(
+            &'a self,
+            path: &'a str,
+            text: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+            ready(self.put(path, Some(text.to_string())))
+        }
+        fn delete<'a>(
+            &'a self,
+            path: &'a str,
+            _recursive: bool,
+        ) -> ExplorerFsFuture<'a, Result<(), String>> {
+            let prefix = format!("{path}/");
+            self.nodes
+                .borrow_mut()
+                .retain(|key, _| key != path && !key.starts_with(&prefix));
+            ready(Ok(()))
+        }
+        fn rename<'a>(
+            &'a self,
+            from: &'a str,
+            to: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+            if self.nodes.borrow().contains_key(to) {
+                return ready(Err(format!("exists {to}")));
+            }
+            let prefix = format!("{from}/");
+            let moved = self
+                .nodes
+                .borrow()
+                .iter()
+                .filter(|(key, _)| *key == from || key.starts_with(&prefix))
+                .map(|(key, node)| (format!("{to}{}", &key[from.len()..]), node.clone()))
+                .collect::<Vec<_>>();
+            let mut nodes = self.nodes.borrow_mut();
+            nodes.retain(|key, _| key != from && !key.starts_with(&prefix));
+            nodes.extend(moved);
+            drop(nodes);
+            ready(self.metadata(to))
+        }
+        fn batch<'a>(
+            &'a self,
+            ops: &'a [ExplorerBatchOp],
+        ) -> ExplorerFsFuture<'a, Result<Vec<ExplorerBatchResult>, String>> {
+            Box::pin(async move { Ok(apply_batch_with(self, ops).await) })
+        }
+        fn stat<'a>(
+            &'a self,
+            path: &'a str,
+        ) -> ExplorerFsFuture<'a, Result<ExplorerMetadata, String>> {
+            ready(self.metadata(path))
+        }
+        fn usage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<ExplorerStorageUsage, String>> {
+            ready(Ok(ExplorerStorageUsage::default()))
+        }
+        fn request_persistent_storage<'a>(&'a self) -> ExplorerFsFuture<'a, Result<bool, String>> {
+            ready(Ok(false))
+        }
+        fn watch(&self, _path: &str, _on_change: ExplorerChangeCallback) -> ExplorerWatch {
+            ExplorerWatch::inactive()
+        }
+    }
+
+    #[test]
+    fn file_commands_edit_the_explorer_tree() {
+        let _ = leptos::create_runtime();
+        let fs = Rc::new(MemoryFs::default());
+        let engine = ShellEngine::new();
+        let _commands = register_fs_commands(&engine, fs.clone()).expect("register fs commands");
+        let session = engine.new_session("/");
+        let run = |line: &str| {
+            session.submit(ShellRequest {
+                line: line.to_string(),
+                cwd: session.cwd().get_untracked(),
+                source_window_id: None,
+            });
+            let events = session.events().get_untracked();
+            let output = events.iter().rev().find_map(|event| match event {
+                ShellStreamEvent::Data { data, .. } => Some(data.clone()),
+                _ => None,
+            });
+            match events.last() {
+                Some(ShellStreamEvent::Completed { summary }) => (summary.exit.code, output),
+                _ => panic!("expected completion"),
+            }
+        };
+        let paths = || fs.nodes.borrow().keys().cloned().collect::<Vec<_>>();
+
+        assert_eq!(run("mkdir -p /docs/drafts").0, 0);
+        assert_eq!(run("cd /docs").0, 0);
+        assert_eq!(session.cwd().get_untracked(), "/docs");
+        assert_eq!(run("touch drafts/a.txt notes.txt").0, 0);
+        fs.nodes
+            .borrow_mut()
+            .insert("/docs/notes.txt".to_string(), Some("hello".to_string()));
+        assert_eq!(
+            run("cat notes.txt").1,
+            Some(StructuredData::Value(StructuredValue::Scalar(
+                StructuredScalar::String("hello".to_string())
+            )))
+        );
+
+        assert_ne!(run("cp drafts /backup").0, 0);
+        assert_eq!(run("cp -r drafts /backup").0, 0);
+        assert_eq!(run("mv notes.txt drafts").0, 0);
+        assert_eq!(run("mv drafts drafts/inner").0, 4);
+        assert_eq!(
+            paths(),
+            vec![
+                "/backup",
+                "/backup/a.txt",
+                "/docs",
+                "/docs/drafts",
+                "/docs/drafts/a.txt",
+                "/docs/drafts/notes.txt",
+            ]
+        );
+
+        let Some(StructuredData::Table(listing)) = run("ls drafts").1 else {
+            panic!("expected a listing table");
+        };
+        assert_eq!(listing.rows.len(), 2);
+
+        let stdout = || {
+            session
+                .events()
+                .get_untracked()
+                .into_iter()
+                .rev()
+                .skip_while(|event| !matches!(event, ShellStreamEvent::StdoutChunk { .. }))
+                .take_while(|event| matches!(event, ShellStreamEvent::StdoutChunk { .. }))
+                .filter_map(|event| match event {
+                    ShellStreamEvent::StdoutChunk { text, .. } => Some(text),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run("fs search / --name '*.TXT' --type file").0, 0);
+        assert_eq!(
+            stdout().concat(),
+            "/backup/a.txt\n/docs/drafts/a.txt\n/docs/drafts/notes.txt\n"
+        );
+        assert_eq!(run("fs grep -i HELLO").0, 0);
+        assert_eq!(stdout(), vec!["/docs/drafts/notes.txt:1:hello\n"]);
+
+        assert_ne!(run("rm /docs").0, 0);
+        assert_eq!(run("rm -r /docs --dry-run").0, 0);
+        assert!(paths().contains(&"/docs".to_string()));
+        assert_eq!(run("rm -r /docs /backup/a.txt").0, 0);
+        assert_eq!(paths(), vec!["/backup"]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Core command providers registered on a [`system_shell::ShellEngine`].
+//!
+//! Providers implement classic shell commands on top of host services so every terminal is
+//! useful without app registrations. [`register_fs_commands`] installs `ls`, `cd`, `cat`,
+//! `mkdir`, `rm`, `mv`, `cp`, `touch`, `fs search`, and `fs grep` backed by an
+//! [`platform_host::ExplorerFsService`].
+
+#![warn(missing_docs, rustdoc::broken_intra_doc_links)]
+
+mod fs;
+mod search;
+
+use std::future::Future;
+
+use system_shell::{CommandExecutionContext, CommandHandler};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDescriptor, CommandExample, CommandId,
+    CommandInputShape, CommandInteractionKind, CommandNotice, CommandNoticeLevel,
+    CommandOptionSpec, CommandOutputShape, CommandPath, CommandResult, CommandScope,
+    CommandValueKind, CommandVisibility, HelpDoc, ShellError, ShellErrorCode, StructuredData,
+    StructuredField, StructuredRecord, StructuredScalar, StructuredValue,
+};
+
+pub use fs::register_fs_commands;
+
+/// Builds a global root-verb descriptor.
+fn root_descriptor(
+    name: &str,
+    summary: &str,
+    usage: &str,
+    args: Vec<CommandArgSpec>,
+    options: Vec<CommandOptionSpec>,
+    examples: Vec<CommandExample>,
+    output_shape: CommandOutputShape,
+) -> CommandDescriptor {
+    let path = CommandPath::new(name);
+    CommandDescriptor {
+        id: CommandId::new(path.display()),
+        parent_path: None,
+        path,
+        aliases: Vec::new(),
+        scope: CommandScope::Global,
+        visibility: CommandVisibility::Public,
+        interaction_kind: CommandInteractionKind::RootVerb,
+        discoverable_children: false,
+        input_shape: CommandInputShape::none(),
+        output_shape,
+        args,
+        options,
+        required_capability: None,
+        destructive: false,
+        owner_app_id: None,
+        help: HelpDoc {
+            summary: summary.to_string(),
+            description: None,
+            usage: usage.to_string(),
+            examples,
+        },
+    }
+}
+
+fn path_arg(name: &str, summary: &str, required: bool, repeatable: bool) -> CommandArgSpec {
+    CommandArgSpec {
+        name: name.to_string(),
+        summary: summary.to_string(),
+        required,
+        repeatable,
+        completion: CommandArgCompletion::Path,
+    }
+}
+
+fn flag_option(name: &str, short: char, summary: &str) -> CommandOptionSpec {
+    CommandOptionSpec {
+        name: name.to_string(),
+        short: Some(short),
+        summary: summary.to_string(),
+        takes_value: false,
+        value_kind: CommandValueKind::Any,
+        choices: Vec::new(),
+    }
+}
+
+fn example(command: &str, summary: &str) -> CommandExample {
+    CommandExample {
+        command: command.to_string(),
+        summary: summary.to_string(),
+    }
+}
+
+fn has_flag(context: &CommandExecutionContext, name: &str) -> bool {
+    context
+        .invocation
+        .options
+        .iter()
+        .any(|option| option.name == name)
+}
+
+/// Adapts an async command function sharing `service` into a [`CommandHandler`].
+fn handler<S, F, Fut>(service: S, run: F) -> CommandHandler
+where
+    S: Clone + 'static,
+    F: Fn(S, CommandExecutionContext) -> Fut + 'static,
+    Fut: Future<Output = Result<CommandResult, ShellError>> + 'static,
+{
+    std::rc::Rc::new(move |context| Box::pin(run(service.clone(), context)))
+}
+
+fn option_value(context: &CommandExecutionContext, name: &str) -> Option<String> {
+    context
+        .invocation
+        .options
+        .iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.value.as_ref())
+        .map(|value| value.raw.clone())
+}
+
+fn info_result(message: impl Into<String>) -> CommandResult {
+    CommandResult {
+        notices: vec![CommandNotice {
+            level: CommandNoticeLevel::Info,
+            message: message.into(),
+        }],
+        ..CommandResult::success(StructuredData::Empty)
+    }
+}
+
+fn usage_error(message: impl Into<String>) -> ShellError {
+    ShellError::new(ShellErrorCode::Usage, message)
+}
+
+/// Builds a [`ShellErrorCode::Filesystem`] error carrying the failed operation and path.
+fn fs_error(operation: &str, path: &str, message: impl Into<String>) -> ShellError {
+    ShellError::new(ShellErrorCode::Filesystem, message).with_details(StructuredRecord {
+        fields: vec![
+            string_field("operation", operation),
+            string_field("path", path),
+        ],
+    })
+}
+
+fn string_field(name: &str, value: impl Into<String>) -> StructuredField {
+    StructuredField {
+        name: name.to_string(),
+        value: StructuredValue::Scalar(StructuredScalar::String(value.into())),
+    }
+}
+
+fn optional_u64_field(name: &str, value: Option<u64>) -> StructuredField {
+    StructuredField {
+        name: name.to_string(),
+        value: StructuredValue::Scalar(match value {
+            Some(value) => StructuredScalar::Int(value as i64),
+            None => StructuredScalar::Null,
+        }),
+    }
+}
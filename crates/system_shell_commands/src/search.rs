@@ -0,0 +1,305 @@
+//! `fs search` and `fs grep`, which walk directory trees and stream matches as they are found.
+
+use std::rc::Rc;
+
+use platform_host::{ExplorerEntry, ExplorerEntryKind, ExplorerFsService};
+use system_shell::{resolve_path, CommandExecutionContext};
+use system_shell_contract::{
+    CommandArgCompletion, CommandArgSpec, CommandDataShape, CommandDescriptor,
+    CommandInteractionKind, CommandOptionSpec, CommandOutputShape, CommandResult, CommandValueKind,
+    ShellError, ShellExit, StructuredData,
+};
+
+use crate::{
+    example, flag_option, fs_error, has_flag, info_result, option_value, path_arg, root_descriptor,
+    usage_error,
+};
+
+type Fs = Rc<dyn ExplorerFsService>;
+
+pub(crate) fn search_descriptor() -> CommandDescriptor {
+    let mut descriptor = fs_descriptor(
+        "fs search",
+        "Find files and directories whose names match a glob.",
+        "fs search [path] --name <glob> [--type file|dir]",
+        vec![path_arg(
+            "path",
+            "Directory to search; defaults to the cwd.",
+            false,
+            false,
+        )],
+        vec![
+            CommandOptionSpec {
+                name: "name".to_string(),
+                short: Some('n'),
+                summary: "Glob matched against entry names; `*` and `?` are wildcards.".to_string(),
+                takes_value: true,
+                value_kind: CommandValueKind::String,
+                choices: Vec::new(),
+            },
+            CommandOptionSpec {
+                name: "type".to_string(),
+                short: Some('t'),
+                summary: "Only report files or directories.".to_string(),
+                takes_value: true,
+                value_kind: CommandValueKind::String,
+                choices: vec!["file".to_string(), "dir".to_string()],
+            },
+        ],
+    );
+    descriptor.help.examples = vec![
+        example(
+            "fs search --name '*.md'",
+            "Find Markdown files under the cwd.",
+        ),
+        example(
+            "fs search /Projects --name 'src' --type dir",
+            "Find directories named src.",
+        ),
+    ];
+    descriptor
+}
+
+pub(crate) async fn search(
+    fs: Fs,
+    context: CommandExecutionContext,
+) -> Result<CommandResult, ShellError> {
+    let pattern = option_value(&context, "name")
+        .ok_or_else(|| usage_error("usage: fs search [path] --name <glob> [--type file|dir]"))?;
+    let kind = option_value(&context, "type").map(|kind| match kind.as_str() {
+        "dir" => ExplorerEntryKind::Directory,
+        _ => ExplorerEntryKind::File,
+    });
+    let root = match context.args.first() {
+        Some(path) => resolve_path(&context.cwd, path),
+        None => context.cwd.clone(),
+    };
+    let mut matches = 0usize;
+    let walked = walk(&fs, &context, &root, |entry| {
+        if glob_match(&pattern, &entry.name) && kind.is_none_or(|kind| kind == entry.kind) {
+            matches += 1;
+            context.stdout(format!("{}\n", entry.path));
+        }
+    })
+    .await?;
+    Ok(finish(walked, format!("{matches} matches under {root}")))
+}
+
+pub(crate) fn grep_descriptor() -> CommandDescriptor {
+    let mut descriptor = fs_descriptor(
+        "fs grep",
+        "Print lines containing a literal pattern in a file or directory tree.",
+        "fs grep <pattern> [path] [--ignore-case]",
+        vec![
+            CommandArgSpec {
+                name: "pattern".to_string(),
+                summary: "Literal text to look for.".to_string(),
+                required: true,
+                repeatable: false,
+                completion: CommandArgCompletion::None,
+            },
+            path_arg(
+                "path",
+                "File or directory to search; defaults to the cwd.",
+                false,
+                false,
+            ),
+        ],
+        vec![flag_option(
+            "ignore-case",
+            'i',
+            "Match regardless of letter case.",
+        )],
+    );
+    descriptor.help.examples = vec![
+        example("fs grep TODO /Projects", "Find TODO lines in a project."),
+        example(
+            "fs grep -i error logs/app.log",
+            "Search one file ignoring case.",
+        ),
+    ];
+    descriptor
+}
+
+pub(crate) async fn grep(
+    fs: Fs,
+    context: CommandExecutionContext,
+) -> Result<CommandResult, ShellError> {
+    let Some(pattern) = context.args.first().cloned() else {
+        return Err(usage_error(
+            "usage: fs grep <pattern> [path] [--ignore-case]",
+        ));
+    };
+    let ignore_case = has_flag(&context, "ignore-case");
+    let needle = if ignore_case {
+        pattern.to_lowercase()
+    } else {
+        pattern
+    };
+    let root = match context.args.get(1) {
+        Some(path) => resolve_path(&context.cwd, path),
+        None => context.cwd.clone(),
+    };
+    let metadata = fs
+        .stat(&root)
+        .await
+        .map_err(|err| fs_error("stat", &root, err))?;
+    let mut files = Vec::new();
+    let walked = if metadata.kind == ExplorerEntryKind::File {
+        files.push(root.clone());
+        true
+    } else {
+        walk(&fs, &context, &root, |entry| {
+            if entry.kind == ExplorerEntryKind::File {
+                files.push(entry.path.clone());
+            }
+        })
+        .await?
+    };
+    if !walked {
+        return Ok(finish(false, String::new()));
+    }
+
+    let mut matches = 0usize;
+    for path in files {
+        if context.is_cancelled() {
+            return Ok(finish(false, String::new()));
+        }
+        let text = match fs.read_text_file(&path).await {
+            Ok(file) => file.text,
+            Err(err) => {
+                context.stderr(format!("{path}: {err}\n"));
+                continue;
+            }
+        };
+        for (index, line) in text.lines().enumerate() {
+            let found = if ignore_case {
+                line.to_lowercase().contains(&needle)
+            } else {
+                line.contains(&needle)
+            };
+            if found {
+                matches += 1;
+                context.stdout(format!("{path}:{}:{line}\n", index + 1));
+            }
+        }
+        context.flush_output();
+    }
+    Ok(finish(
+        true,
+        format!("{matches} matching lines under {root}"),
+    ))
+}
+
+fn fs_descriptor(
+    path: &str,
+    summary: &str,
+    usage: &str,
+    args: Vec<CommandArgSpec>,
+    options: Vec<CommandOptionSpec>,
+) -> CommandDescriptor {
+    let mut descriptor = root_descriptor(
+        path,
+        summary,
+        usage,
+        args,
+        options,
+        Vec::new(),
+        CommandOutputShape::new(CommandDataShape::Empty),
+    );
+    descriptor.parent_path = descriptor.path.parent();
+    descriptor.interaction_kind = CommandInteractionKind::Hierarchical;
+    descriptor
+}
+
+/// Visits every entry below `root` depth-first in name order, flushing output per directory.
+///
+/// Returns `false` when the execution was cancelled before the walk finished.
+async fn walk(
+    fs: &Fs,
+    context: &CommandExecutionContext,
+    root: &str,
+    mut visit: impl FnMut(&ExplorerEntry),
+) -> Result<bool, ShellError> {
+    let mut pending = vec![root.to_string()];
+    while let Some(dir) = pending.pop() {
+        if context.is_cancelled() {
+            return Ok(false);
+        }
+        let mut entries = fs
+            .list_dir(&dir)
+            .await
+            .map_err(|err| fs_error("list", &dir, err))?
+            .entries;
+        entries.sort_by(|left, right| left.name.cmp(&right.name));
+        for entry in &entries {
+            visit(entry);
+        }
+        pending.extend(
+            entries
+                .into_iter()
+                .rev()
+                .filter(|entry| entry.kind == ExplorerEntryKind::Directory)
+                .map(|entry| entry.path),
+        );
+        context.flush_output();
+    }
+    Ok(true)
+}
+
+fn finish(completed: bool, summary: String) -> CommandResult {
+    if completed {
+        info_result(summary)
+    } else {
+        CommandResult {
+            exit: ShellExit::cancelled(),
+            ..CommandResult::success(StructuredData::Empty)
+        }
+    }
+}
+
+/// Matches `name` against a glob where `*` spans any run of characters and `?` exactly one,
+/// ignoring ASCII case.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase().chars().collect::<Vec<_>>();
+    let name = name.to_ascii_lowercase().chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs_match_whole_names_with_wildcards() {
+        assert!(glob_match("*.md", "README.md"));
+        assert!(glob_match("notes-??.txt", "notes-01.txt"));
+        assert!(glob_match("*a*b*", "xaxxbx"));
+        assert!(glob_match("src", "SRC"));
+        assert!(!glob_match("*.md", "README.md.bak"));
+        assert!(!glob_match("notes-?.txt", "notes-01.txt"));
+        assert!(!glob_match("", "a"));
+    }
+}
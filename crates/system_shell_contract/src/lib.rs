@@ -225,6 +225,22 @@ pub struct CommandArgSpec {
     pub required: bool,
     /// Whether this argument consumes remaining values.
     pub repeatable: bool,
+    /// Built-in completion source used when the cursor is on this argument.
+    #[serde(default)]
+    pub completion: CommandArgCompletion,
+}
+
+/// Built-in completion source a positional argument opts into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommandArgCompletion {
+    /// No built-in completion; the command's own completion handler applies.
+    #[default]
+    None,
+    /// Files and directories from the session's explorer filesystem.
+    Path,
+    /// Directories from the session's explorer filesystem.
+    Directory,
 }
 
 /// Named option or flag specification.
@@ -238,6 +254,53 @@ pub struct CommandOptionSpec {
     pub summary: String,
     /// Whether the option consumes a value.
     pub takes_value: bool,
+    /// Expected literal type for the option value when [`CommandOptionSpec::takes_value`] is set.
+    #[serde(default)]
+    pub value_kind: CommandValueKind,
+    /// Accepted values for enum-like options; empty when any value of `value_kind` is allowed.
+    #[serde(default)]
+    pub choices: Vec<String>,
+}
+
+/// Expected literal type for a typed option value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommandValueKind {
+    /// Any literal, including `null`.
+    #[default]
+    Any,
+    /// Any non-null literal, read through its raw text.
+    String,
+    /// Integer literal.
+    Int,
+    /// Integer or float literal.
+    Float,
+    /// Boolean literal (`true`/`false`/`on`/`off`).
+    Bool,
+}
+
+impl CommandValueKind {
+    /// Returns whether `literal` satisfies this kind.
+    pub fn accepts(self, literal: &ParsedLiteral) -> bool {
+        match self {
+            Self::Any => true,
+            Self::String => !matches!(literal, ParsedLiteral::Null),
+            Self::Int => matches!(literal, ParsedLiteral::Int(_)),
+            Self::Float => matches!(literal, ParsedLiteral::Int(_) | ParsedLiteral::Float(_)),
+            Self::Bool => matches!(literal, ParsedLiteral::Bool(_)),
+        }
+    }
+
+    /// Lowercase label used in usage errors.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::String => "string",
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Bool => "bool",
+        }
+    }
 }
 
 /// Example invocation rendered in help output.
@@ -289,6 +352,21 @@ pub struct CommandDescriptor {
     pub args: Vec<CommandArgSpec>,
     /// Option metadata.
     pub options: Vec<CommandOptionSpec>,
+    /// Kebab-case app capability id, such as `config` or `theme`, that a session caller must
+    /// hold to execute this command.
+    #[serde(default)]
+    pub required_capability: Option<String>,
+    /// Whether the command deletes or overwrites state.
+    ///
+    /// The engine gives destructive commands a `--dry-run` flag; handlers must then report what
+    /// would change without changing it.
+    #[serde(default)]
+    pub destructive: bool,
+    /// App that registered the command, set by the host from the registering app.
+    ///
+    /// App quotas charge executions to this app whatever the command's scope.
+    #[serde(default)]
+    pub owner_app_id: Option<String>,
     /// Help metadata.
     pub help: HelpDoc,
 }
@@ -317,6 +395,12 @@ pub struct CompletionItem {
     pub label: String,
     /// Optional short description.
     pub detail: Option<String>,
+    /// Relevance assigned by the shell's ranking; higher scores sort first.
+    #[serde(default)]
+    pub score: i64,
+    /// Character indices of `label` matched by the typed token, for highlighting.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matched: Vec<usize>,
 }
 
 /// Shell execution request.
@@ -330,6 +414,122 @@ pub struct ShellRequest {
     pub source_window_id: Option<u64>,
 }
 
+/// App window identity bound to a shell session and checked before each dispatch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandCaller {
+    /// Canonical application identifier of the session owner.
+    pub app_id: String,
+    /// Owning window identifier, when the session belongs to a window.
+    pub window_id: Option<u64>,
+}
+
+/// One command history entry matched by a history search.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryMatch {
+    /// Zero-based index into the history list, oldest first.
+    pub index: usize,
+    /// Matched command line.
+    pub line: String,
+}
+
+/// User-defined shell function declared with `fn name { ... }`.
+///
+/// Functions are stored per session and resolve before registered commands. The body is one
+/// pipeline line; `$1`..`$9`, `${N}`, `$#`, and `$@` expand to the invocation arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShellFunction {
+    /// Invocation name.
+    pub name: String,
+    /// Pipeline line executed when the function is invoked.
+    pub body: String,
+}
+
+/// When a scheduled command line runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum ScheduleTrigger {
+    /// Repeats every `interval_ms` milliseconds.
+    Every {
+        /// Interval between runs.
+        interval_ms: u64,
+    },
+    /// Runs once at a unix millisecond timestamp.
+    At {
+        /// Wall-clock run time.
+        unix_ms: u64,
+    },
+}
+
+/// Command line registered with the shell scheduler.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    /// Scheduler-assigned identifier.
+    pub id: u64,
+    /// Pipeline line submitted when the job is due.
+    pub line: String,
+    /// Logical cwd the line runs in.
+    pub cwd: String,
+    /// App window identity that registered the job; runs are authorized as this caller.
+    #[serde(default)]
+    pub caller: Option<CommandCaller>,
+    /// Run trigger.
+    pub trigger: ScheduleTrigger,
+    /// Next due time; `None` once a one-shot job has run.
+    pub next_run_unix_ms: Option<u64>,
+    /// Time of the most recent run.
+    pub last_run_unix_ms: Option<u64>,
+}
+
+/// App-bus topic that receives a [`ScheduledRun`] payload whenever a scheduled job completes.
+pub const SCHEDULE_TOPIC: &str = "system.shell.schedule";
+
+/// Completion report for one run of a [`ScheduledJob`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledRun {
+    /// Identifier of the job that ran.
+    pub job_id: u64,
+    /// Line that was submitted.
+    pub line: String,
+    /// Final execution summary.
+    pub summary: ShellExecutionSummary,
+}
+
+/// One command dispatch recorded by the shell audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Audit-log sequence number, increasing across sessions.
+    pub seq: u64,
+    /// Execution identifier within the originating session.
+    pub execution_id: ExecutionId,
+    /// Canonical path of the dispatched command.
+    pub command: CommandPath,
+    /// Full token vector for the pipeline stage.
+    pub argv: Vec<String>,
+    /// Logical cwd at dispatch time.
+    pub cwd: String,
+    /// Optional source window identifier.
+    #[serde(default)]
+    pub source_window_id: Option<u64>,
+    /// App window identity bound to the session; `None` for trusted runtime sessions.
+    #[serde(default)]
+    pub caller: Option<CommandCaller>,
+    /// Nesting level; `0` for stages typed at the prompt.
+    #[serde(default)]
+    pub depth: usize,
+    /// Whether the command ran as a dry run.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Dispatch time in unix milliseconds.
+    pub started_at_unix_ms: u64,
+    /// Time from dispatch until the handler returned or failed.
+    pub duration_ms: u64,
+    /// Process-style exit code.
+    pub exit_code: i32,
+    /// Error category when the dispatch failed.
+    #[serde(default)]
+    pub error: Option<ShellErrorCode>,
+}
+
 /// Typed literal parsed from shell input.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "kind", content = "value", rename_all = "kebab-case")]
@@ -554,7 +754,7 @@ impl CommandResult {
 }
 
 /// Final execution result metadata.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ShellExecutionSummary {
     /// Execution identifier.
     pub execution_id: ExecutionId,
@@ -569,12 +769,18 @@ pub struct ShellExecutionSummary {
 pub struct ExecutionId(pub u64);
 
 /// Shell exit status.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ShellExit {
     /// Numeric exit code.
     pub code: i32,
     /// Optional explanatory message.
     pub message: Option<String>,
+    /// Error category when the command failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ShellErrorCode>,
+    /// Machine-readable failure details copied from the originating [`ShellError`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<StructuredRecord>,
 }
 
 impl ShellExit {
@@ -583,14 +789,29 @@ impl ShellExit {
         Self {
             code: 0,
             message: None,
+            error: None,
+            details: None,
         }
     }
 
     /// Cancellation completion.
     pub fn cancelled() -> Self {
         Self {
-            code: 130,
+            code: ShellErrorCode::Cancelled.exit_code(),
             message: Some("command cancelled".to_string()),
+            error: Some(ShellErrorCode::Cancelled),
+            details: None,
+        }
+    }
+}
+
+impl From<ShellError> for ShellExit {
+    fn from(err: ShellError) -> Self {
+        Self {
+            code: err.exit_code(),
+            message: Some(err.message),
+            error: Some(err.code),
+            details: err.details,
         }
     }
 }
@@ -609,15 +830,59 @@ pub enum ShellErrorCode {
     PermissionDenied,
     /// Internal command or runtime failure.
     Internal,
+    /// The request collides with existing state, such as an already registered command path.
+    Conflict,
+    /// An argument or option value failed type or choice validation.
+    Validation,
+    /// A filesystem operation failed, such as reading a missing file.
+    Filesystem,
+    /// The command was cancelled before completing.
+    Cancelled,
+    /// The command exceeded a resource limit, such as its app's concurrency or output quota.
+    ResourceExhausted,
+}
+
+impl ShellErrorCode {
+    /// Returns the stable kebab-case identifier scripts and app UIs can branch on.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Usage => "usage",
+            Self::NotFound => "not-found",
+            Self::Unavailable => "unavailable",
+            Self::PermissionDenied => "permission-denied",
+            Self::Internal => "internal",
+            Self::Conflict => "conflict",
+            Self::Validation => "validation",
+            Self::Filesystem => "filesystem",
+            Self::Cancelled => "cancelled",
+            Self::ResourceExhausted => "resource-exhausted",
+        }
+    }
+
+    /// Converts the category into a conventional exit code.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Usage | Self::Validation => 2,
+            Self::NotFound => 3,
+            Self::Unavailable | Self::PermissionDenied | Self::Filesystem => 4,
+            Self::Internal => 5,
+            Self::Conflict => 6,
+            Self::ResourceExhausted => 7,
+            Self::Cancelled => 130,
+        }
+    }
 }
 
 /// Error emitted by shell parsing, lookup, or handlers.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ShellError {
     /// Error category.
     pub code: ShellErrorCode,
     /// Human-readable message.
     pub message: String,
+    /// Optional machine-readable context, such as the offending path or option.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<StructuredRecord>,
 }
 
 impl ShellError {
@@ -626,18 +891,78 @@ impl ShellError {
         Self {
             code,
             message: message.into(),
+            details: None,
         }
     }
 
+    /// Attaches machine-readable details to the error.
+    pub fn with_details(mut self, details: StructuredRecord) -> Self {
+        self.details = Some(details);
+        self
+    }
+
     /// Converts the error into a conventional exit code.
     pub fn exit_code(&self) -> i32 {
-        match self.code {
-            ShellErrorCode::Usage => 2,
-            ShellErrorCode::NotFound => 3,
-            ShellErrorCode::Unavailable | ShellErrorCode::PermissionDenied => 4,
-            ShellErrorCode::Internal => 5,
+        self.code.exit_code()
+    }
+}
+
+/// Continuation marker attached to a paged [`ShellStreamEvent::Data`] frame.
+///
+/// The withheld rows stay with the session until `page next` or `page all` consumes them or a
+/// later oversized result replaces them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageContinuation {
+    /// Token accepted by `page next` and `page all` to continue this output.
+    pub token: u64,
+    /// Number of table rows or list items not yet emitted.
+    pub remaining: usize,
+}
+
+/// Input style requested by [`ShellStreamEvent::PromptRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PromptKind {
+    /// Yes/no question; see [`is_affirmative`].
+    Confirm,
+    /// Free-form text answer.
+    Text,
+    /// Text answer that must not be echoed or persisted, such as a passphrase.
+    Secret,
+}
+
+/// Returns the byte offset where the last token of a command `line` starts.
+///
+/// Quoting follows the shell tokenizer: whitespace and `|` inside quotes or after a backslash do
+/// not end a token, and an open quote belongs to the token it starts. After trailing whitespace
+/// the offset is `line.len()`, an empty token. The offset is always a char boundary.
+pub fn last_token_start(line: &str) -> usize {
+    let mut start = 0;
+    let mut quote = None::<char>;
+    let mut escaped = false;
+    for (index, ch) in line.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match quote {
+            Some(active) if ch == active => quote = None,
+            Some(_) => escaped = ch == '\\',
+            None if ch == '\\' => escaped = true,
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch.is_whitespace() || ch == '|' => start = index + ch.len_utf8(),
+            None => {}
         }
     }
+    start
+}
+
+/// Returns whether a [`PromptKind::Confirm`] answer means yes (`y`, `yes`, or `true`).
+pub fn is_affirmative(answer: &str) -> bool {
+    matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes" | "true"
+    )
 }
 
 /// Incremental stream event emitted by the shell runtime.
@@ -665,6 +990,20 @@ pub enum ShellStreamEvent {
         /// Optional short label.
         label: Option<String>,
     },
+    /// Batched plain-text standard output from a running handler.
+    StdoutChunk {
+        /// Execution identifier.
+        execution_id: ExecutionId,
+        /// Output text, possibly spanning several lines.
+        text: String,
+    },
+    /// Batched plain-text diagnostic output from a running handler.
+    StderrChunk {
+        /// Execution identifier.
+        execution_id: ExecutionId,
+        /// Output text, possibly spanning several lines.
+        text: String,
+    },
     /// Structured result data frame.
     Data {
         /// Execution identifier.
@@ -673,6 +1012,19 @@ pub enum ShellStreamEvent {
         data: StructuredData,
         /// Preferred presentation.
         display: DisplayPreference,
+        /// Continuation marker when the payload was truncated to the session page budget.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        more: Option<PageContinuation>,
+    },
+    /// A running handler is waiting for user input; answer with the session's `respond` API.
+    PromptRequest {
+        /// Execution identifier.
+        execution_id: ExecutionId,
+        /// Question shown next to the input.
+        prompt: String,
+        /// Requested input style.
+        #[serde(rename = "prompt_kind")]
+        kind: PromptKind,
     },
     /// Execution completed successfully or with a command error.
     Completed {
@@ -684,6 +1036,13 @@ pub enum ShellStreamEvent {
         /// Execution identifier.
         execution_id: ExecutionId,
     },
+    /// Older events were dropped to keep the session log within its retention limit.
+    ///
+    /// Always the first event of a truncated log; later drops grow `dropped` in place.
+    Truncated {
+        /// Events dropped since the log was last acknowledged.
+        dropped: u64,
+    },
 }
 
 /// Opaque registration token used to unregister commands.
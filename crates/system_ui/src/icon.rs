@@ -55,9 +55,46 @@ pub enum IconName {
     WindowRestore,
     /// Dismiss/close icon.
     Dismiss,
+    /// Notification bell icon.
+    Alert,
 }
 
 impl IconName {
+    /// Every icon in the catalog.
+    pub const ALL: &'static [Self] = &[
+        Self::Calculator,
+        Self::ExplorerFolder,
+        Self::DocumentText,
+        Self::PaintBrush,
+        Self::Terminal,
+        Self::Settings,
+        Self::Connect,
+        Self::Play,
+        Self::Pause,
+        Self::Stop,
+        Self::Next,
+        Self::Home,
+        Self::Launcher,
+        Self::WindowMultiple,
+        Self::DesktopArrowDown,
+        Self::WifiOn,
+        Self::WifiOff,
+        Self::MotionOn,
+        Self::MotionOff,
+        Self::ChevronDown,
+        Self::Checkmark,
+        Self::WindowMinimize,
+        Self::WindowMaximize,
+        Self::WindowRestore,
+        Self::Dismiss,
+        Self::Alert,
+    ];
+
+    /// Looks up the icon whose [`IconName::token`] is `token`.
+    pub fn from_token(token: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|icon| icon.token() == token)
+    }
+
     /// Stable token used for CSS hooks and debugging.
     pub const fn token(self) -> &'static str {
         match self {
@@ -86,6 +123,7 @@ impl IconName {
             Self::WindowMaximize => "window-maximize",
             Self::WindowRestore => "window-restore",
             Self::Dismiss => "dismiss",
+            Self::Alert => "alert",
         }
     }
 
@@ -164,6 +202,9 @@ impl IconName {
             Self::WindowRestore => {
                 r#"<path d="M7.52 5H6c.13-1.68 1.53-3 3.24-3h8A4.75 4.75 0 0 1 22 6.75v8a3.25 3.25 0 0 1-3 3.24v-1.5c.85-.13 1.5-.86 1.5-1.74v-8c0-1.8-1.46-3.25-3.25-3.25h-8c-.88 0-1.61.65-1.73 1.5ZM5.25 6A3.25 3.25 0 0 0 2 9.25v9.5C2 20.55 3.46 22 5.25 22h9.5c1.8 0 3.25-1.46 3.25-3.25v-9.5C18 7.45 16.55 6 14.75 6h-9.5ZM3.5 9.25c0-.97.78-1.75 1.75-1.75h9.5c.97 0 1.75.78 1.75 1.75v9.5c0 .97-.78 1.75-1.75 1.75h-9.5c-.97 0-1.75-.78-1.75-1.75v-9.5Z"/>"#
             }
+            Self::Alert => {
+                r#"<path d="M12 2a7.5 7.5 0 0 1 7.5 7.25v4.35l1.38 3.15a1.25 1.25 0 0 1-1.15 1.75H15a3 3 0 0 1-6 .18v-.18H4.27a1.25 1.25 0 0 1-1.14-1.75L4.5 13.6V9.5C4.5 5.35 7.85 2 12 2Zm1.5 16.5h-3a1.5 1.5 0 0 0 3 .15v-.15ZM12 3.5c-3.32 0-6 2.67-6 6v4.41L4.66 17h14.7L18 13.91V9.5l-.01-.22A5.99 5.99 0 0 0 12 3.5Z"/>"#
+            }
             Self::Dismiss => {
                 r#"<path d="m4.4 4.55.07-.08a.75.75 0 0 1 .98-.07l.08.07L12 10.94l6.47-6.47a.75.75 0 1 1 1.06 1.06L13.06 12l6.47 6.47c.27.27.3.68.07.98l-.07.08a.75.75 0 0 1-.98.07l-.08-.07L12 13.06l-6.47 6.47a.75.75 0 0 1-1.06-1.06L10.94 12 4.47 5.53a.75.75 0 0 1-.07-.98l.07-.08-.07.08Z"/>"#
             }
@@ -15,20 +15,22 @@ mod primitives;
 
 pub use icon::{Icon, IconName, IconSize};
 pub use primitives::{
-    AppShell, Badge, Button, ButtonShape, ButtonSize, ButtonVariant, Card, CheckboxField,
-    CircularProgress, ClockButton, Cluster, ColorField, CompletionItem, CompletionList, DataTable,
-    DesktopBackdrop, DesktopIconButton, DesktopIconGrid, DesktopRoot, DesktopWindowLayer,
-    DisclosurePanel, Elevation, ElevationLayer, EmptyState, FieldGroup, FieldVariant, Grid,
-    Heading, IconButton, InspectorGrid, KnobDial, LauncherMenu, LayoutAlign, LayoutGap,
-    LayoutJustify, LayoutPadding, ListSurface, MenuBar, MenuItem, MenuSeparator, MenuSurface,
-    Modal, OptionCard, Pane, PaneHeader, Panel, PreviewFrame, ProgressBar, ProgressVariant,
-    RangeField, ResizeHandle, SegmentedControl, SegmentedControlOption, SelectField, SplitLayout,
-    Stack, StatusBar, StatusBarItem, StepFlow, StepFlowActions, StepFlowHeader, StepFlowStep,
-    StepStatus, Surface, SurfaceVariant, Switch, Tab, TabList, Taskbar, TaskbarButton,
-    TaskbarOverflowButton, TaskbarSection, TerminalLine, TerminalPrompt, TerminalSurface,
-    TerminalTranscript, Text, TextArea, TextField, TextRole, TextTone, ToggleRow, ToolBar,
-    TrayButton, TrayList, Tree, TreeItem, WindowBody, WindowControlButton, WindowControls,
-    WindowFrame, WindowTitle, WindowTitleBar,
+    is_context_menu_key, AppShell, Badge, Button, ButtonShape, ButtonSize, ButtonVariant, Card,
+    CheckboxField, CircularProgress, ClockButton, Cluster, CodeEditor, CodeSpan, CodeToken,
+    ColorField, CompletionItem, CompletionList, ContextMenu, DataTable, DesktopBackdrop,
+    DesktopIconButton, DesktopIconGrid, DesktopRoot, DesktopWindowLayer, DisclosurePanel,
+    Elevation, ElevationLayer, EmptyState, FieldGroup, FieldVariant, Grid, Heading, IconButton,
+    InspectorGrid, KnobDial, LauncherMenu, LayoutAlign, LayoutDirection, LayoutGap, LayoutJustify,
+    LayoutPadding, ListSurface, MenuBar, MenuItem, MenuSeparator, MenuSurface, Modal, OptionCard,
+    Pane, PaneHeader, Panel, PreviewFrame, ProgressBar, ProgressVariant, RangeField, ResizeHandle,
+    SegmentedControl, SegmentedControlOption, SelectField, SplitLayout, Stack, StatusBar,
+    StatusBarItem, StepFlow, StepFlowActions, StepFlowHeader, StepFlowStep, StepStatus, Surface,
+    SurfaceVariant, Switch, Tab, TabList, Taskbar, TaskbarButton, TaskbarOverflowButton,
+    TaskbarSection, TerminalColor, TerminalFontSize, TerminalLine, TerminalPaneGrid,
+    TerminalPrompt, TerminalSpan, TerminalSurface, TerminalTheme, TerminalTranscript, Text,
+    TextArea, TextField, TextRole, TextTone, ToggleRow, ToolBar, TrayButton, TrayList, Tree,
+    TreeItem, VirtualSpacer, WindowBody, WindowControlButton, WindowControls, WindowFrame,
+    WindowTitle, WindowTitleBar,
 };
 
 /// Convenience imports for application crates consuming the shared primitive set.
@@ -37,20 +39,22 @@ pub use primitives::{
 /// diffs do not churn on long individual import lists.
 pub mod prelude {
     pub use crate::{
-        AppShell, Badge, Button, ButtonShape, ButtonSize, ButtonVariant, Card, CheckboxField,
-        CircularProgress, ClockButton, Cluster, ColorField, CompletionItem, CompletionList,
-        DataTable, DesktopBackdrop, DesktopIconButton, DesktopIconGrid, DesktopRoot,
-        DesktopWindowLayer, DisclosurePanel, Elevation, ElevationLayer, EmptyState, FieldGroup,
-        FieldVariant, Grid, Heading, Icon, IconButton, IconName, IconSize, InspectorGrid, KnobDial,
-        LauncherMenu, LayoutAlign, LayoutGap, LayoutJustify, LayoutPadding, ListSurface, MenuBar,
-        MenuItem, MenuSeparator, MenuSurface, Modal, OptionCard, Pane, PaneHeader, Panel,
-        PreviewFrame, ProgressBar, ProgressVariant, RangeField, ResizeHandle, SegmentedControl,
+        is_context_menu_key, AppShell, Badge, Button, ButtonShape, ButtonSize, ButtonVariant, Card,
+        CheckboxField, CircularProgress, ClockButton, Cluster, CodeEditor, CodeSpan, CodeToken,
+        ColorField, CompletionItem, CompletionList, ContextMenu, DataTable, DesktopBackdrop,
+        DesktopIconButton, DesktopIconGrid, DesktopRoot, DesktopWindowLayer, DisclosurePanel,
+        Elevation, ElevationLayer, EmptyState, FieldGroup, FieldVariant, Grid, Heading, Icon,
+        IconButton, IconName, IconSize, InspectorGrid, KnobDial, LauncherMenu, LayoutAlign,
+        LayoutDirection, LayoutGap, LayoutJustify, LayoutPadding, ListSurface, MenuBar, MenuItem,
+        MenuSeparator, MenuSurface, Modal, OptionCard, Pane, PaneHeader, Panel, PreviewFrame,
+        ProgressBar, ProgressVariant, RangeField, ResizeHandle, SegmentedControl,
         SegmentedControlOption, SelectField, SplitLayout, Stack, StatusBar, StatusBarItem,
         StepFlow, StepFlowActions, StepFlowHeader, StepFlowStep, StepStatus, Surface,
         SurfaceVariant, Switch, Tab, TabList, Taskbar, TaskbarButton, TaskbarOverflowButton,
-        TaskbarSection, TerminalLine, TerminalPrompt, TerminalSurface, TerminalTranscript, Text,
+        TaskbarSection, TerminalColor, TerminalFontSize, TerminalLine, TerminalPaneGrid,
+        TerminalPrompt, TerminalSpan, TerminalSurface, TerminalTheme, TerminalTranscript, Text,
         TextArea, TextField, TextRole, TextTone, ToggleRow, ToolBar, TrayButton, TrayList, Tree,
-        TreeItem, WindowBody, WindowControlButton, WindowControls, WindowFrame, WindowTitle,
-        WindowTitleBar,
+        TreeItem, VirtualSpacer, WindowBody, WindowControlButton, WindowControls, WindowFrame,
+        WindowTitle, WindowTitleBar,
     };
 }
@@ -256,6 +256,9 @@ pub fn TextField(
     #[prop(optional)] on_keydown: Option<Callback<KeyboardEvent>>,
     #[prop(optional)] on_focus: Option<Callback<FocusEvent>>,
     #[prop(optional)] on_blur: Option<Callback<FocusEvent>>,
+    #[prop(optional)] on_paste: Option<Callback<web_sys::Event>>,
+    #[prop(optional)] on_dragover: Option<Callback<DragEvent>>,
+    #[prop(optional)] on_drop: Option<Callback<DragEvent>>,
 ) -> impl IntoView {
     view! {
         <input
@@ -295,6 +298,21 @@ pub fn TextField(
                     on_blur.call(ev);
                 }
             }
+            on:paste=move |ev| {
+                if let Some(on_paste) = on_paste.as_ref() {
+                    on_paste.call(ev);
+                }
+            }
+            on:dragover=move |ev| {
+                if let Some(on_dragover) = on_dragover.as_ref() {
+                    on_dragover.call(ev);
+                }
+            }
+            on:drop=move |ev| {
+                if let Some(on_drop) = on_drop.as_ref() {
+                    on_drop.call(ev);
+                }
+            }
         />
     }
 }
@@ -309,6 +327,7 @@ pub fn TextArea(
     #[prop(optional)] spellcheck: Option<&'static str>,
     #[prop(optional)] autocomplete: Option<&'static str>,
     #[prop(optional)] ui_slot: Option<&'static str>,
+    #[prop(optional)] node_ref: NodeRef<html::Textarea>,
     #[prop(optional, into)] value: MaybeSignal<String>,
     #[prop(optional)] on_input: Option<Callback<web_sys::Event>>,
     #[prop(optional)] on_keydown: Option<Callback<KeyboardEvent>>,
@@ -318,6 +337,7 @@ pub fn TextArea(
             class=merge_layout_class("ui-textarea", layout_class)
             id=id
             aria-label=aria_label
+            node_ref=node_ref
             spellcheck=spellcheck.unwrap_or("false")
             autocomplete=autocomplete.unwrap_or("off")
             prop:value=move || value.get()
@@ -339,6 +359,82 @@ pub fn TextArea(
     }
 }
 
+#[component]
+/// Multiline source editor with line numbers and syntax colors.
+///
+/// A transparent text area sits over the colored `lines`, which the caller derives from `value`
+/// and must keep in step with it, one entry per line. With `soft_wrap` off, long lines scroll
+/// horizontally instead of wrapping.
+pub fn CodeEditor(
+    #[prop(optional)] layout_class: Option<&'static str>,
+    #[prop(optional, into)] id: Option<String>,
+    #[prop(optional, into)] aria_label: Option<String>,
+    #[prop(optional)] node_ref: NodeRef<html::Textarea>,
+    #[prop(optional, into)] value: MaybeSignal<String>,
+    #[prop(into)] lines: Signal<Vec<Vec<CodeSpan>>>,
+    #[prop(optional, into)] soft_wrap: MaybeSignal<bool>,
+    #[prop(optional)] on_input: Option<Callback<web_sys::Event>>,
+    #[prop(optional)] on_keydown: Option<Callback<KeyboardEvent>>,
+) -> impl IntoView {
+    view! {
+        <div
+            class=merge_layout_class("ui-code-editor", layout_class)
+            data-ui-primitive="true"
+            data-ui-kind="code-editor"
+            data-ui-wrap=move || bool_token(soft_wrap.get())
+        >
+            <div data-ui-slot="code-lines" aria-hidden="true">
+                {move || {
+                    lines
+                        .get()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, spans)| {
+                            view! {
+                                <div data-ui-slot="code-line">
+                                    <span data-ui-slot="code-line-number">{index + 1}</span>
+                                    <span data-ui-slot="code-line-text">
+                                        {spans
+                                            .into_iter()
+                                            .map(|span| {
+                                                view! {
+                                                    <span data-ui-token=span.token.token()>
+                                                        {span.text}
+                                                    </span>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </span>
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </div>
+            <textarea
+                id=id
+                aria-label=aria_label
+                node_ref=node_ref
+                spellcheck="false"
+                autocomplete="off"
+                wrap=move || if soft_wrap.get() { "soft" } else { "off" }
+                prop:value=move || value.get()
+                data-ui-slot="code-input"
+                on:input=move |ev| {
+                    if let Some(on_input) = on_input.as_ref() {
+                        on_input.call(ev);
+                    }
+                }
+                on:keydown=move |ev| {
+                    if let Some(on_keydown) = on_keydown.as_ref() {
+                        on_keydown.call(ev);
+                    }
+                }
+            ></textarea>
+        </div>
+    }
+}
+
 #[component]
 /// Shared select-field primitive.
 pub fn SelectField(
@@ -692,6 +788,7 @@ pub fn KnobDial(
 /// Shared completion list item.
 pub fn CompletionItem(
     #[prop(optional)] layout_class: Option<&'static str>,
+    #[prop(optional, into)] selected: MaybeSignal<bool>,
     #[prop(optional)] on_click: Option<Callback<MouseEvent>>,
     children: Children,
 ) -> impl IntoView {
@@ -700,6 +797,7 @@ pub fn CompletionItem(
             layout_class=layout_class.unwrap_or("")
             ui_slot="completion-item"
             variant=ButtonVariant::Quiet
+            selected=selected
             on_click=Callback::new(move |ev| {
                 if let Some(on_click) = on_click.as_ref() {
                     on_click.call(ev);
@@ -188,6 +188,24 @@ pub fn EmptyState(
     }
 }
 
+#[component]
+/// Blank block standing in for list rows that are not rendered, so a virtualized list keeps its
+/// full scroll height.
+pub fn VirtualSpacer(
+    #[prop(optional)] layout_class: Option<&'static str>,
+    #[prop(into)] height_px: MaybeSignal<f64>,
+) -> impl IntoView {
+    view! {
+        <div
+            class=merge_layout_class("ui-virtual-spacer", layout_class)
+            data-ui-primitive="true"
+            data-ui-kind="virtual-spacer"
+            aria-hidden="true"
+            style=move || format!("height: {}px", height_px.get())
+        ></div>
+    }
+}
+
 #[component]
 /// Shared pane surface.
 pub fn Pane(
@@ -315,6 +333,11 @@ pub fn DataTable(
 /// Shared tree container.
 pub fn Tree(
     #[prop(optional)] layout_class: Option<&'static str>,
+    #[prop(optional, into)] role: Option<String>,
+    #[prop(optional, into)] aria_label: Option<String>,
+    #[prop(optional)] tabindex: Option<i32>,
+    #[prop(optional, into)] aria_activedescendant: MaybeSignal<String>,
+    #[prop(optional)] on_keydown: Option<Callback<KeyboardEvent>>,
     children: Children,
 ) -> impl IntoView {
     view! {
@@ -322,6 +345,15 @@ pub fn Tree(
             class=merge_layout_class("ui-tree", layout_class)
             data-ui-primitive="true"
             data-ui-kind="tree"
+            role=role
+            aria-label=aria_label
+            tabindex=tabindex
+            aria-activedescendant=move || aria_activedescendant.get()
+            on:keydown=move |ev| {
+                if let Some(on_keydown) = on_keydown.as_ref() {
+                    on_keydown.call(ev);
+                }
+            }
         >
             {children()}
         </ul>
@@ -330,17 +362,56 @@ pub fn Tree(
 
 #[component]
 /// Shared tree item surface.
+///
+/// The drag callbacks let an item accept drops; `drop_target` highlights it while a drag is over it.
+/// Items given a `role` (such as `treeitem`) also expose `selected` as `aria-selected`.
 pub fn TreeItem(
     #[prop(optional)] layout_class: Option<&'static str>,
+    #[prop(optional, into)] id: Option<String>,
+    #[prop(optional, into)] role: Option<String>,
+    #[prop(optional)] aria_level: Option<usize>,
+    #[prop(optional, into)] aria_expanded: MaybeSignal<Option<bool>>,
     #[prop(optional, into)] selected: MaybeSignal<bool>,
+    #[prop(optional, into)] drop_target: MaybeSignal<bool>,
+    #[prop(optional)] on_click: Option<Callback<MouseEvent>>,
+    #[prop(optional)] on_dragover: Option<Callback<DragEvent>>,
+    #[prop(optional)] on_dragleave: Option<Callback<DragEvent>>,
+    #[prop(optional)] on_drop: Option<Callback<DragEvent>>,
     children: Children,
 ) -> impl IntoView {
+    let has_role = role.is_some();
     view! {
         <li
             class=merge_layout_class("ui-tree-item", layout_class)
+            id=id
+            role=role
+            aria-level=aria_level
+            aria-expanded=move || aria_expanded.get().map(bool_token)
+            aria-selected=move || has_role.then(|| bool_token(selected.get()))
             data-ui-primitive="true"
             data-ui-kind="tree-item"
             data-ui-selected=move || bool_token(selected.get())
+            data-ui-drop-target=move || bool_token(drop_target.get())
+            on:click=move |ev| {
+                if let Some(on_click) = on_click.as_ref() {
+                    on_click.call(ev);
+                }
+            }
+            on:dragover=move |ev| {
+                if let Some(on_dragover) = on_dragover.as_ref() {
+                    on_dragover.call(ev);
+                }
+            }
+            on:dragleave=move |ev| {
+                if let Some(on_dragleave) = on_dragleave.as_ref() {
+                    on_dragleave.call(ev);
+                }
+            }
+            on:drop=move |ev| {
+                if let Some(on_drop) = on_drop.as_ref() {
+                    on_drop.call(ev);
+                }
+            }
         >
             {children()}
         </li>
@@ -407,6 +478,10 @@ pub fn TerminalSurface(
     #[prop(optional)] node_ref: NodeRef<html::Div>,
     #[prop(optional, into)] role: Option<String>,
     #[prop(optional, into)] aria_live: Option<&'static str>,
+    #[prop(optional, into)] aria_label: MaybeSignal<String>,
+    #[prop(optional, into)] selected: MaybeSignal<bool>,
+    #[prop(optional, into)] font_size: MaybeSignal<TerminalFontSize>,
+    #[prop(optional, into)] theme: MaybeSignal<TerminalTheme>,
     #[prop(optional)] on_scroll: Option<Callback<web_sys::Event>>,
     children: Children,
 ) -> impl IntoView {
@@ -415,6 +490,10 @@ pub fn TerminalSurface(
             class=merge_layout_class("ui-terminal-surface", layout_class)
             data-ui-primitive="true"
             data-ui-kind="terminal-surface"
+            data-ui-selected=move || bool_token(selected.get())
+            data-ui-size=move || font_size.get().token()
+            data-ui-variant=move || theme.get().token()
+            aria-label=move || aria_label.get()
             node_ref=node_ref
             role=role
             aria-live=aria_live
@@ -429,6 +508,28 @@ pub fn TerminalSurface(
     }
 }
 
+#[component]
+/// Shared container laying out split terminal panes in a row or a column.
+pub fn TerminalPaneGrid(
+    #[prop(optional)] layout_class: Option<&'static str>,
+    #[prop(optional, into)] direction: MaybeSignal<LayoutDirection>,
+    #[prop(optional, into)] aria_label: MaybeSignal<String>,
+    children: Children,
+) -> impl IntoView {
+    view! {
+        <div
+            class=merge_layout_class("ui-terminal-pane-grid", layout_class)
+            data-ui-primitive="true"
+            data-ui-kind="terminal-pane-grid"
+            data-ui-direction=move || direction.get().token()
+            role="group"
+            aria-label=move || aria_label.get()
+        >
+            {children()}
+        </div>
+    }
+}
+
 #[component]
 /// Shared terminal transcript container.
 pub fn TerminalTranscript(
@@ -452,9 +553,15 @@ pub fn TerminalTranscript(
 
 #[component]
 /// Shared terminal line surface.
+///
+/// `selected` marks lines inside a copy-mode selection and `current` marks the copy-mode cursor
+/// line.
 pub fn TerminalLine(
     #[prop(optional)] layout_class: Option<&'static str>,
     #[prop(default = TextTone::Primary)] tone: TextTone,
+    #[prop(optional, into)] selected: MaybeSignal<bool>,
+    #[prop(optional, into)] current: MaybeSignal<bool>,
+    #[prop(optional)] node_ref: NodeRef<html::Div>,
     children: Children,
 ) -> impl IntoView {
     view! {
@@ -463,12 +570,43 @@ pub fn TerminalLine(
             data-ui-primitive="true"
             data-ui-kind="terminal-line"
             data-ui-tone=tone.token()
+            data-ui-selected=move || bool_token(selected.get())
+            data-ui-current=move || bool_token(current.get())
+            node_ref=node_ref
         >
             {children()}
         </div>
     }
 }
 
+#[component]
+/// Styled run of text inside a terminal line, such as a span of ANSI-colored output.
+pub fn TerminalSpan(
+    #[prop(default = None)] foreground: Option<TerminalColor>,
+    #[prop(default = None)] background: Option<TerminalColor>,
+    #[prop(optional)] bold: bool,
+    #[prop(optional)] dim: bool,
+    #[prop(optional)] italic: bool,
+    #[prop(optional)] underline: bool,
+    children: Children,
+) -> impl IntoView {
+    view! {
+        <span
+            class="ui-terminal-span"
+            data-ui-primitive="true"
+            data-ui-kind="terminal-span"
+            data-ui-fg=foreground.map(TerminalColor::token)
+            data-ui-bg=background.map(TerminalColor::token)
+            data-ui-bold=bool_token(bold)
+            data-ui-dim=bool_token(dim)
+            data-ui-italic=bool_token(italic)
+            data-ui-underline=bool_token(underline)
+        >
+            {children()}
+        </span>
+    }
+}
+
 #[component]
 /// Shared terminal prompt row.
 pub fn TerminalPrompt(
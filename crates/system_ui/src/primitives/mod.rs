@@ -1,6 +1,6 @@
 //! Shared structural, shell, overlay, data-display, control, and layout primitives.
 
-use leptos::ev::{FocusEvent, KeyboardEvent, MouseEvent};
+use leptos::ev::{DragEvent, FocusEvent, KeyboardEvent, MouseEvent};
 use leptos::*;
 
 use crate::{Icon, IconName, IconSize};
@@ -13,21 +13,22 @@ mod overlays;
 mod shell;
 
 pub use controls::{
-    Button, CheckboxField, CircularProgress, ColorField, CompletionItem, CompletionList,
-    FieldGroup, IconButton, KnobDial, ProgressBar, RangeField, SegmentedControl,
+    Button, CheckboxField, CircularProgress, CodeEditor, ColorField, CompletionItem,
+    CompletionList, FieldGroup, IconButton, KnobDial, ProgressBar, RangeField, SegmentedControl,
     SegmentedControlOption, SelectField, Switch, TextArea, TextField, ToggleRow,
 };
 pub use data_display::{
     Badge, Card, DataTable, ElevationLayer, EmptyState, Heading, InspectorGrid, ListSurface,
     OptionCard, Pane, PaneHeader, Panel, PreviewFrame, StatusBarItem, Surface, TerminalLine,
-    TerminalPrompt, TerminalSurface, TerminalTranscript, Text, Tree, TreeItem,
+    TerminalPaneGrid, TerminalPrompt, TerminalSpan, TerminalSurface, TerminalTranscript, Text,
+    Tree, TreeItem, VirtualSpacer,
 };
 pub use layout::{Cluster, Grid, SplitLayout, Stack};
 pub use navigation::{
     DisclosurePanel, LauncherMenu, MenuBar, StatusBar, StepFlow, StepFlowActions, StepFlowHeader,
     StepFlowStep, Tab, TabList, ToolBar,
 };
-pub use overlays::{MenuItem, MenuSeparator, MenuSurface, Modal};
+pub use overlays::{is_context_menu_key, ContextMenu, MenuItem, MenuSeparator, MenuSurface, Modal};
 pub use shell::{
     AppShell, ClockButton, DesktopBackdrop, DesktopIconButton, DesktopIconGrid, DesktopRoot,
     DesktopWindowLayer, ResizeHandle, Taskbar, TaskbarButton, TaskbarOverflowButton,
@@ -244,6 +245,185 @@ impl TextTone {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Terminal palette colors addressed by ANSI SGR sequences.
+pub enum TerminalColor {
+    /// ANSI black.
+    Black,
+    /// ANSI red.
+    Red,
+    /// ANSI green.
+    Green,
+    /// ANSI yellow.
+    Yellow,
+    /// ANSI blue.
+    Blue,
+    /// ANSI magenta.
+    Magenta,
+    /// ANSI cyan.
+    Cyan,
+    /// ANSI white.
+    White,
+    /// ANSI bright black.
+    BrightBlack,
+    /// ANSI bright red.
+    BrightRed,
+    /// ANSI bright green.
+    BrightGreen,
+    /// ANSI bright yellow.
+    BrightYellow,
+    /// ANSI bright blue.
+    BrightBlue,
+    /// ANSI bright magenta.
+    BrightMagenta,
+    /// ANSI bright cyan.
+    BrightCyan,
+    /// ANSI bright white.
+    BrightWhite,
+}
+
+impl TerminalColor {
+    /// Maps an ANSI palette index (`0..=15`) to its color.
+    pub fn from_ansi_index(index: u8) -> Option<Self> {
+        Some(match index {
+            0 => Self::Black,
+            1 => Self::Red,
+            2 => Self::Green,
+            3 => Self::Yellow,
+            4 => Self::Blue,
+            5 => Self::Magenta,
+            6 => Self::Cyan,
+            7 => Self::White,
+            8 => Self::BrightBlack,
+            9 => Self::BrightRed,
+            10 => Self::BrightGreen,
+            11 => Self::BrightYellow,
+            12 => Self::BrightBlue,
+            13 => Self::BrightMagenta,
+            14 => Self::BrightCyan,
+            15 => Self::BrightWhite,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn token(self) -> &'static str {
+        match self {
+            Self::Black => "black",
+            Self::Red => "red",
+            Self::Green => "green",
+            Self::Yellow => "yellow",
+            Self::Blue => "blue",
+            Self::Magenta => "magenta",
+            Self::Cyan => "cyan",
+            Self::White => "white",
+            Self::BrightBlack => "bright-black",
+            Self::BrightRed => "bright-red",
+            Self::BrightGreen => "bright-green",
+            Self::BrightYellow => "bright-yellow",
+            Self::BrightBlue => "bright-blue",
+            Self::BrightMagenta => "bright-magenta",
+            Self::BrightCyan => "bright-cyan",
+            Self::BrightWhite => "bright-white",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Syntax classes colored by [`CodeEditor`](crate::CodeEditor).
+pub enum CodeToken {
+    /// Uncolored text.
+    #[default]
+    Plain,
+    /// Language keywords, literals such as `true`, and Markdown list markers.
+    Keyword,
+    /// Type names.
+    Type,
+    /// String and character literals, and Markdown code.
+    String,
+    /// Numeric literals.
+    Number,
+    /// Comments and Markdown quotes and fences.
+    Comment,
+    /// Object keys and Markdown links.
+    Property,
+    /// Markdown headings.
+    Heading,
+    /// Markdown emphasis.
+    Emphasis,
+}
+
+impl CodeToken {
+    pub(crate) fn token(self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Keyword => "keyword",
+            Self::Type => "type",
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Comment => "comment",
+            Self::Property => "property",
+            Self::Heading => "heading",
+            Self::Emphasis => "emphasis",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Run of source text with one syntax class, rendered by [`CodeEditor`](crate::CodeEditor).
+pub struct CodeSpan {
+    /// Syntax class of the run.
+    pub token: CodeToken,
+    /// Text of the run, without line breaks.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Terminal text sizes.
+pub enum TerminalFontSize {
+    /// Compact terminal text.
+    Sm,
+    /// Default terminal text.
+    #[default]
+    Md,
+    /// Enlarged terminal text.
+    Lg,
+}
+
+impl TerminalFontSize {
+    pub(crate) fn token(self) -> &'static str {
+        match self {
+            Self::Sm => "sm",
+            Self::Md => "md",
+            Self::Lg => "lg",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Terminal surface color schemes.
+pub enum TerminalTheme {
+    /// Shell terminal colors.
+    #[default]
+    Default,
+    /// Dark text on a light surface.
+    Light,
+    /// Amber phosphor on black.
+    Amber,
+    /// Green phosphor on black.
+    Green,
+}
+
+impl TerminalTheme {
+    pub(crate) fn token(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Light => "light",
+            Self::Amber => "amber",
+            Self::Green => "green",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 /// Shared progress variants.
 pub enum ProgressVariant {
@@ -277,6 +457,25 @@ impl LayoutGap {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Shared layout direction tokens.
+pub enum LayoutDirection {
+    /// Children sit side by side.
+    #[default]
+    Row,
+    /// Children stack top to bottom.
+    Column,
+}
+
+impl LayoutDirection {
+    pub(crate) fn token(self) -> &'static str {
+        match self {
+            Self::Row => "row",
+            Self::Column => "column",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 /// Shared layout padding tokens.
 pub enum LayoutPadding {
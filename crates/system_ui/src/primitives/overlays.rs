@@ -1,3 +1,5 @@
+use leptos::wasm_bindgen::JsCast;
+
 use super::*;
 
 #[component]
@@ -9,6 +11,8 @@ pub fn MenuSurface(
     #[prop(optional)] ui_slot: Option<&'static str>,
     #[prop(optional, into)] aria_label: MaybeSignal<String>,
     #[prop(optional, into)] style: MaybeSignal<String>,
+    #[prop(optional)] viewport_point: Option<(i32, i32)>,
+    #[prop(optional)] node_ref: NodeRef<html::Div>,
     #[prop(optional)] on_keydown: Option<Callback<KeyboardEvent>>,
     #[prop(optional)] on_mousedown: Option<Callback<MouseEvent>>,
     #[prop(optional)] on_click: Option<Callback<MouseEvent>>,
@@ -17,10 +21,14 @@ pub fn MenuSurface(
     view! {
         <div
             class=merge_layout_class("ui-menu-surface", layout_class)
+            node_ref=node_ref
             id=id
             role=role
             aria-label=move || aria_label.get()
-            style=move || style.get()
+            style=move || match viewport_point {
+                Some((x, y)) => format!("position:fixed;left:{x}px;top:{y}px;{}", style.get()),
+                None => style.get(),
+            }
             data-ui-primitive="true"
             data-ui-kind="menu-surface"
             data-ui-slot=ui_slot
@@ -45,6 +53,100 @@ pub fn MenuSurface(
     }
 }
 
+/// Returns whether a key press asks for a context menu: the Menu key or Shift+F10.
+pub fn is_context_menu_key(ev: &KeyboardEvent) -> bool {
+    ev.key() == "ContextMenu" || (ev.shift_key() && ev.key() == "F10")
+}
+
+#[derive(Clone, Copy)]
+enum MenuStep {
+    First,
+    Last,
+    Next,
+    Previous,
+}
+
+/// Moves focus between the enabled items of `menu`, wrapping at either end.
+fn focus_menu_item(menu: &web_sys::Element, step: MenuStep) {
+    let Ok(nodes) = menu
+        .query_selector_all(r#"[role^="menuitem"]:not([disabled]):not([aria-disabled="true"])"#)
+    else {
+        return;
+    };
+    let items = (0..nodes.length())
+        .filter_map(|index| nodes.item(index))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect::<Vec<_>>();
+    let Some(last) = items.len().checked_sub(1) else {
+        return;
+    };
+    let active = document().active_element();
+    let current = items
+        .iter()
+        .position(|item| item.is_same_node(active.as_ref().map(|active| active.as_ref())));
+    let index = match (step, current) {
+        (MenuStep::First, _) | (MenuStep::Next, None) => 0,
+        (MenuStep::Last, _) | (MenuStep::Previous, None) => last,
+        (MenuStep::Next, Some(current)) if current == last => 0,
+        (MenuStep::Next, Some(current)) => current + 1,
+        (MenuStep::Previous, Some(0)) => last,
+        (MenuStep::Previous, Some(current)) => current - 1,
+    };
+    let _ = items[index].focus();
+}
+
+#[component]
+/// Shared context menu shown at a viewport point, opened by a right click or
+/// [`is_context_menu_key`].
+///
+/// The first item takes focus when the menu mounts. Arrow keys, Home, and End move between items,
+/// and Escape or Tab call `on_close`. Presses inside the menu stop at the surface so outside-click
+/// listeners only see presses elsewhere.
+pub fn ContextMenu(
+    #[prop(optional, into)] aria_label: MaybeSignal<String>,
+    position: (i32, i32),
+    on_close: Callback<()>,
+    children: Children,
+) -> impl IntoView {
+    let menu = create_node_ref::<html::Div>();
+    request_animation_frame(move || {
+        if let Some(menu) = menu.get_untracked() {
+            focus_menu_item(&menu, MenuStep::First);
+        }
+    });
+    view! {
+        <MenuSurface
+            role="menu"
+            aria_label=aria_label
+            viewport_point=position
+            node_ref=menu
+            on_keydown=Callback::new(move |ev: KeyboardEvent| {
+                let step = match ev.key().as_str() {
+                    "ArrowDown" => MenuStep::Next,
+                    "ArrowUp" => MenuStep::Previous,
+                    "Home" => MenuStep::First,
+                    "End" => MenuStep::Last,
+                    "Escape" | "Tab" => {
+                        ev.prevent_default();
+                        ev.stop_propagation();
+                        on_close.call(());
+                        return;
+                    }
+                    _ => return,
+                };
+                ev.prevent_default();
+                ev.stop_propagation();
+                if let Some(menu) = menu.get_untracked() {
+                    focus_menu_item(&menu, step);
+                }
+            })
+            on_mousedown=Callback::new(|ev: MouseEvent| ev.stop_propagation())
+        >
+            {children()}
+        </MenuSurface>
+    }
+}
+
 #[component]
 /// Shared overlay menu item primitive.
 pub fn MenuItem(
@@ -4,6 +4,7 @@ use super::*;
 /// Root application shell layout container.
 pub fn AppShell(
     #[prop(optional)] layout_class: Option<&'static str>,
+    #[prop(optional)] on_keydown: Option<Callback<KeyboardEvent>>,
     children: Children,
 ) -> impl IntoView {
     view! {
@@ -13,6 +14,11 @@ pub fn AppShell(
             data-ui-kind="app-shell"
             data-ui-variant="standard"
             data-ui-elevation="flat"
+            on:keydown=move |ev| {
+                if let Some(on_keydown) = on_keydown.as_ref() {
+                    on_keydown.call(ev);
+                }
+            }
         >
             {children()}
         </div>
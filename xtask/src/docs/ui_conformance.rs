@@ -641,6 +641,7 @@ fn is_allowed_inline_style(rel_path: &str, line: &str) -> bool {
         "crates/desktop_runtime/src/components.rs"
             | "crates/desktop_runtime/src/components/window.rs"
             | "crates/desktop_runtime/src/components/menus.rs"
+            | "crates/system_ui/src/primitives/data_display.rs"
             | "crates/system_ui/src/primitives/overlays.rs"
             | "crates/system_ui/src/primitives/shell.rs"
     )
@@ -653,12 +654,16 @@ fn validate_raw_interactive_markup(root: &Path) -> Vec<Problem> {
         "pane-header",
         "split-layout",
         "list-surface",
+        "terminal-pane-grid",
+        "terminal-span",
         "terminal-surface",
         "terminal-transcript",
         "terminal-prompt",
         "completion-list",
         "completion-item",
         "empty-state",
+        "virtual-spacer",
+        "code-editor",
         "panel",
         "tree",
         "tree-item",